@@ -1,7 +1,10 @@
 use crate::thread::Thread;
+use anyhow::{Context as _, Result};
 use assistant_context::AssistantContext;
 use assistant_tool::outline;
 use collections::HashSet;
+use context_server::types::{ResourceContentsType, ResourcesReadParams};
+use context_server::{ContextServer, ContextServerId};
 use futures::future;
 use futures::{FutureExt, future::Shared};
 use gpui::{App, AppContext as _, ElementId, Entity, SharedString, Task};
@@ -17,6 +20,7 @@ use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 use std::{ops::Range, path::Path, sync::Arc};
 use text::{Anchor, OffsetRangeExt as _};
+use url::Url;
 use util::markdown::MarkdownCodeBlock;
 use util::{ResultExt as _, post_inc};
 
@@ -28,6 +32,7 @@ pub enum ContextKind {
     Symbol,
     Selection,
     FetchedUrl,
+    McpResource,
     Thread,
     TextThread,
     Rules,
@@ -42,6 +47,7 @@ impl ContextKind {
             ContextKind::Symbol => IconName::Code,
             ContextKind::Selection => IconName::Context,
             ContextKind::FetchedUrl => IconName::Globe,
+            ContextKind::McpResource => IconName::Server,
             ContextKind::Thread => IconName::MessageBubbles,
             ContextKind::TextThread => IconName::MessageBubbles,
             ContextKind::Rules => RULES_ICON,
@@ -62,6 +68,7 @@ pub enum AgentContextHandle {
     Symbol(SymbolContextHandle),
     Selection(SelectionContextHandle),
     FetchedUrl(FetchedUrlContext),
+    McpResource(McpResourceContext),
     Thread(ThreadContextHandle),
     TextThread(TextThreadContextHandle),
     Rules(RulesContextHandle),
@@ -76,6 +83,7 @@ impl AgentContextHandle {
             Self::Symbol(context) => context.context_id,
             Self::Selection(context) => context.context_id,
             Self::FetchedUrl(context) => context.context_id,
+            Self::McpResource(context) => context.context_id,
             Self::Thread(context) => context.context_id,
             Self::TextThread(context) => context.context_id,
             Self::Rules(context) => context.context_id,
@@ -97,6 +105,7 @@ pub enum AgentContext {
     Symbol(SymbolContext),
     Selection(SelectionContext),
     FetchedUrl(FetchedUrlContext),
+    McpResource(McpResourceContext),
     Thread(ThreadContext),
     TextThread(TextThreadContext),
     Rules(RulesContext),
@@ -115,6 +124,7 @@ impl AgentContext {
                 AgentContextHandle::Selection(context.handle.clone())
             }
             AgentContext::FetchedUrl(context) => AgentContextHandle::FetchedUrl(context.clone()),
+            AgentContext::McpResource(context) => AgentContextHandle::McpResource(context.clone()),
             AgentContext::Thread(context) => AgentContextHandle::Thread(context.handle.clone()),
             AgentContext::TextThread(context) => {
                 AgentContextHandle::TextThread(context.handle.clone())
@@ -558,6 +568,89 @@ impl Display for FetchedUrlContext {
     }
 }
 
+/// A resource exposed by a context server.
+#[derive(Debug, Clone)]
+pub struct McpResourceContext {
+    pub server_id: ContextServerId,
+    pub uri: SharedString,
+    pub name: SharedString,
+    /// Text contents of the resource. Like for fetched URLs, these are populated when added, and
+    /// refreshed when the context server reports that the resource changed. Not used by
+    /// `PartialEq` or `Hash` for `AgentContextKey`.
+    pub text: SharedString,
+    pub context_id: ContextId,
+}
+
+impl McpResourceContext {
+    pub fn eq_for_key(&self, other: &Self) -> bool {
+        self.server_id == other.server_id && self.uri == other.uri
+    }
+
+    pub fn hash_for_key<H: Hasher>(&self, state: &mut H) {
+        self.server_id.hash(state);
+        self.uri.hash(state);
+    }
+
+    pub fn lookup_key(server_id: ContextServerId, uri: SharedString) -> AgentContextKey {
+        AgentContextKey(AgentContextHandle::McpResource(McpResourceContext {
+            server_id,
+            uri,
+            name: "".into(),
+            text: "".into(),
+            context_id: ContextId::for_lookup(),
+        }))
+    }
+
+    pub fn load(self) -> Task<Option<(AgentContext, Vec<Entity<Buffer>>)>> {
+        Task::ready(Some((AgentContext::McpResource(self), vec![])))
+    }
+}
+
+impl Display for McpResourceContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "<resource server=\"{}\" uri=\"{}\" name=\"{}\">",
+            self.server_id, self.uri, self.name
+        )?;
+        writeln!(f, "{}", self.text.trim_end())?;
+        writeln!(f, "</resource>")
+    }
+}
+
+/// Reads the text contents of a context server resource. Binary contents are left out, with a
+/// note so that the model knows about them.
+pub async fn read_mcp_resource(server: Arc<ContextServer>, uri: Url) -> Result<String> {
+    let protocol = server
+        .client()
+        .context("context server is not initialized")?;
+    let response = protocol
+        .request::<context_server::types::requests::ResourcesRead>(ResourcesReadParams {
+            uri,
+            meta: None,
+        })
+        .await?;
+
+    let mut text = String::new();
+    for contents in response.contents {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        match contents {
+            ResourceContentsType::Text(contents) => text.push_str(&contents.text),
+            ResourceContentsType::Blob(contents) => {
+                let _ = write!(
+                    text,
+                    "[binary contents of {} ({}) omitted]",
+                    contents.uri,
+                    contents.mime_type.as_deref().unwrap_or("unknown type")
+                );
+            }
+        }
+    }
+    Ok(text)
+}
+
 #[derive(Debug, Clone)]
 pub struct ThreadContextHandle {
     pub thread: Entity<Thread>,
@@ -836,6 +929,7 @@ pub fn load_context(
             AgentContextHandle::Symbol(context) => context.load(cx),
             AgentContextHandle::Selection(context) => context.load(cx),
             AgentContextHandle::FetchedUrl(context) => context.load(),
+            AgentContextHandle::McpResource(context) => context.load(),
             AgentContextHandle::Thread(context) => context.load(cx),
             AgentContextHandle::TextThread(context) => context.load(cx),
             AgentContextHandle::Rules(context) => context.load(prompt_store, cx),
@@ -862,6 +956,7 @@ pub fn load_context(
         let mut symbol_context = Vec::new();
         let mut selection_context = Vec::new();
         let mut fetched_url_context = Vec::new();
+        let mut mcp_resource_context = Vec::new();
         let mut thread_context = Vec::new();
         let mut text_thread_context = Vec::new();
         let mut rules_context = Vec::new();
@@ -873,6 +968,7 @@ pub fn load_context(
                 AgentContext::Symbol(context) => symbol_context.push(context),
                 AgentContext::Selection(context) => selection_context.push(context),
                 AgentContext::FetchedUrl(context) => fetched_url_context.push(context),
+                AgentContext::McpResource(context) => mcp_resource_context.push(context),
                 AgentContext::Thread(context) => thread_context.push(context),
                 AgentContext::TextThread(context) => text_thread_context.push(context),
                 AgentContext::Rules(context) => rules_context.push(context),
@@ -887,6 +983,7 @@ pub fn load_context(
             && symbol_context.is_empty()
             && selection_context.is_empty()
             && fetched_url_context.is_empty()
+            && mcp_resource_context.is_empty()
             && thread_context.is_empty()
             && text_thread_context.is_empty()
             && rules_context.is_empty()
@@ -952,6 +1049,15 @@ pub fn load_context(
             text.push_str("</fetched_urls>\n");
         }
 
+        if !mcp_resource_context.is_empty() {
+            text.push_str("<resources>");
+            for context in mcp_resource_context {
+                text.push('\n');
+                let _ = write!(text, "{context}");
+            }
+            text.push_str("</resources>\n");
+        }
+
         if !thread_context.is_empty() {
             text.push_str("<conversation_threads>");
             for context in thread_context {
@@ -1071,6 +1177,11 @@ impl PartialEq for AgentContextKey {
                     return context.eq_for_key(other_context);
                 }
             }
+            AgentContextHandle::McpResource(context) => {
+                if let AgentContextHandle::McpResource(other_context) = &other.0 {
+                    return context.eq_for_key(other_context);
+                }
+            }
             AgentContextHandle::Thread(context) => {
                 if let AgentContextHandle::Thread(other_context) = &other.0 {
                     return context.eq_for_key(other_context);
@@ -1104,6 +1215,7 @@ impl Hash for AgentContextKey {
             AgentContextHandle::Symbol(context) => context.hash_for_key(state),
             AgentContextHandle::Selection(context) => context.hash_for_key(state),
             AgentContextHandle::FetchedUrl(context) => context.hash_for_key(state),
+            AgentContextHandle::McpResource(context) => context.hash_for_key(state),
             AgentContextHandle::Thread(context) => context.hash_for_key(state),
             AgentContextHandle::TextThread(context) => context.hash_for_key(state),
             AgentContextHandle::Rules(context) => context.hash_for_key(state),
@@ -1,8 +1,9 @@
 use crate::{
     context::{
         AgentContextHandle, AgentContextKey, ContextId, ContextKind, DirectoryContextHandle,
-        FetchedUrlContext, FileContextHandle, ImageContext, RulesContextHandle,
+        FetchedUrlContext, FileContextHandle, ImageContext, McpResourceContext, RulesContextHandle,
         SelectionContextHandle, SymbolContextHandle, TextThreadContextHandle, ThreadContextHandle,
+        read_mcp_resource,
     },
     thread::{MessageId, Thread, ThreadId},
     thread_store::ThreadStore,
@@ -10,10 +11,14 @@ use crate::{
 use anyhow::{Context as _, Result, anyhow};
 use assistant_context::AssistantContext;
 use collections::{HashSet, IndexSet};
+use context_server::ContextServerId;
 use futures::{self, FutureExt};
-use gpui::{App, Context, Entity, EventEmitter, Image, SharedString, Task, WeakEntity};
+use gpui::{
+    App, Context, Entity, EventEmitter, Image, SharedString, Subscription, Task, WeakEntity,
+};
 use language::{Buffer, File as _};
 use language_model::LanguageModelImage;
+use project::context_server_store::{self, ContextServerStore};
 use project::{Project, ProjectItem, ProjectPath, Symbol, image_store::is_image_file};
 use prompt_store::UserPromptId;
 use ref_cast::RefCast as _;
@@ -23,6 +28,8 @@ use std::{
     sync::Arc,
 };
 use text::{Anchor, OffsetRangeExt};
+use url::Url;
+use util::ResultExt as _;
 
 pub struct ContextStore {
     project: WeakEntity<Project>,
@@ -31,6 +38,7 @@ pub struct ContextStore {
     context_set: IndexSet<AgentContextKey>,
     context_thread_ids: HashSet<ThreadId>,
     context_text_thread_paths: HashSet<Arc<Path>>,
+    context_server_subscriptions: Vec<Subscription>,
 }
 
 pub enum ContextStoreEvent {
@@ -51,6 +59,7 @@ impl ContextStore {
             context_set: IndexSet::default(),
             context_thread_ids: HashSet::default(),
             context_text_thread_paths: HashSet::default(),
+            context_server_subscriptions: Vec::new(),
         }
     }
 
@@ -59,7 +68,11 @@ impl ContextStore {
     }
 
     pub fn clear(&mut self, cx: &mut Context<Self>) {
-        self.context_set.clear();
+        for key in std::mem::take(&mut self.context_set) {
+            if let AgentContextHandle::McpResource(context) = key.0 {
+                self.unsubscribe_from_mcp_resource(&context, cx);
+            }
+        }
         self.context_thread_ids.clear();
         cx.notify();
     }
@@ -292,6 +305,26 @@ impl ContextStore {
         context
     }
 
+    pub fn add_mcp_resource(
+        &mut self,
+        server_id: ContextServerId,
+        uri: impl Into<SharedString>,
+        name: impl Into<SharedString>,
+        text: impl Into<SharedString>,
+        cx: &mut Context<ContextStore>,
+    ) -> AgentContextHandle {
+        let context = AgentContextHandle::McpResource(McpResourceContext {
+            server_id,
+            uri: uri.into(),
+            name: name.into(),
+            text: text.into(),
+            context_id: self.next_context_id.post_inc(),
+        });
+
+        self.insert_context(context.clone(), cx);
+        context
+    }
+
     pub fn add_image_from_path(
         &mut self,
         project_path: ProjectPath,
@@ -426,8 +459,15 @@ impl ContextStore {
             }
             _ => {}
         }
+        let mcp_resource = match &context {
+            AgentContextHandle::McpResource(context) => Some(context.clone()),
+            _ => None,
+        };
         let inserted = self.context_set.insert(AgentContextKey(context));
         if inserted {
+            if let Some(mcp_resource) = mcp_resource {
+                self.subscribe_to_mcp_resource(&mcp_resource, cx);
+            }
             cx.notify();
         }
         inserted
@@ -448,6 +488,9 @@ impl ContextStore {
                         self.context_text_thread_paths.remove(path);
                     }
                 }
+                AgentContextHandle::McpResource(mcp_resource_context) => {
+                    self.unsubscribe_from_mcp_resource(mcp_resource_context, cx);
+                }
                 _ => {}
             }
             cx.emit(ContextStoreEvent::ContextRemoved(key));
@@ -455,6 +498,83 @@ impl ContextStore {
         }
     }
 
+    fn subscribe_to_mcp_resource(&mut self, context: &McpResourceContext, cx: &mut Context<Self>) {
+        let Some(project) = self.project.upgrade() else {
+            return;
+        };
+        let context_server_store = project.read(cx).context_server_store();
+        if self.context_server_subscriptions.is_empty() {
+            self.context_server_subscriptions = vec![
+                cx.subscribe(&context_server_store, Self::handle_context_server_event),
+                cx.on_release(Self::unsubscribe_from_all_mcp_resources),
+            ];
+        }
+        context_server_store.update(cx, |store, cx| {
+            store.subscribe_to_resource(&context.server_id, &context.uri, cx)
+        });
+    }
+
+    fn unsubscribe_from_mcp_resource(&self, context: &McpResourceContext, cx: &mut App) {
+        let Some(project) = self.project.upgrade() else {
+            return;
+        };
+        project
+            .read(cx)
+            .context_server_store()
+            .update(cx, |store, cx| {
+                store.unsubscribe_from_resource(&context.server_id, &context.uri, cx)
+            });
+    }
+
+    fn unsubscribe_from_all_mcp_resources(&mut self, cx: &mut App) {
+        for key in &self.context_set {
+            if let AgentContextHandle::McpResource(context) = &key.0 {
+                self.unsubscribe_from_mcp_resource(context, cx);
+            }
+        }
+    }
+
+    fn handle_context_server_event(
+        &mut self,
+        context_server_store: Entity<ContextServerStore>,
+        event: &context_server_store::Event,
+        cx: &mut Context<Self>,
+    ) {
+        let context_server_store::Event::ResourceUpdated { server_id, uri } = event else {
+            return;
+        };
+        let key = McpResourceContext::lookup_key(server_id.clone(), uri.clone().into());
+        if !self.context_set.contains(&key) {
+            return;
+        }
+        let Some(server) = context_server_store.read(cx).get_running_server(server_id) else {
+            return;
+        };
+        let Some(url) = Url::parse(uri).log_err() else {
+            return;
+        };
+
+        cx.spawn(async move |this, cx| {
+            let text = read_mcp_resource(server, url).await?;
+            this.update(cx, |this, cx| {
+                let Some(AgentContextHandle::McpResource(context)) =
+                    this.context_set.get(&key).map(|key| key.0.clone())
+                else {
+                    return;
+                };
+                this.context_set
+                    .replace(AgentContextKey(AgentContextHandle::McpResource(
+                        McpResourceContext {
+                            text: text.into(),
+                            ..context
+                        },
+                    )));
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn has_context(&mut self, context: &AgentContextHandle) -> bool {
         self.context_set
             .contains(AgentContextKey::ref_cast(context))
@@ -531,6 +651,13 @@ impl ContextStore {
             .contains(&FetchedUrlContext::lookup_key(url.into()))
     }
 
+    pub fn includes_mcp_resource(&self, server_id: &ContextServerId, uri: &str) -> bool {
+        self.context_set.contains(&McpResourceContext::lookup_key(
+            server_id.clone(),
+            SharedString::from(uri.to_string()),
+        ))
+    }
+
     pub fn get_url_context(&self, url: SharedString) -> Option<AgentContextHandle> {
         self.context_set
             .get(&FetchedUrlContext::lookup_key(url))
@@ -548,6 +675,7 @@ impl ContextStore {
                 | AgentContextHandle::Symbol(_)
                 | AgentContextHandle::Selection(_)
                 | AgentContextHandle::FetchedUrl(_)
+                | AgentContextHandle::McpResource(_)
                 | AgentContextHandle::Thread(_)
                 | AgentContextHandle::TextThread(_)
                 | AgentContextHandle::Rules(_)
@@ -655,3 +783,51 @@ impl FileInclusion {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use settings::SettingsStore;
+
+    #[gpui::test]
+    async fn test_releasing_store_unsubscribes_from_mcp_resources(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let context_server_store =
+            project.read_with(cx, |project, _| project.context_server_store());
+        let server_id = ContextServerId("server".into());
+        let uri = "file:///notes.md";
+
+        let first_store = cx.new(|_| ContextStore::new(project.downgrade(), None));
+        let second_store = cx.new(|_| ContextStore::new(project.downgrade(), None));
+        for context_store in [&first_store, &second_store] {
+            context_store.update(cx, |store, cx| {
+                store.add_mcp_resource(server_id.clone(), uri, "notes.md", "notes", cx);
+            });
+        }
+        context_server_store.read_with(cx, |store, _| {
+            assert_eq!(store.resource_subscriber_count(&server_id, uri), 2);
+        });
+
+        drop(first_store);
+        cx.run_until_parked();
+        context_server_store.read_with(cx, |store, _| {
+            assert_eq!(store.resource_subscriber_count(&server_id, uri), 1);
+        });
+
+        second_store.update(cx, |store, cx| store.clear(cx));
+        drop(second_store);
+        cx.run_until_parked();
+        context_server_store.read_with(cx, |store, _| {
+            assert_eq!(store.resource_subscriber_count(&server_id, uri), 0);
+        });
+    }
+}
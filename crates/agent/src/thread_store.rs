@@ -544,6 +544,8 @@ impl ThreadStore {
                     }
                 }
             }
+            project::context_server_store::Event::ResourceUpdated { .. }
+            | project::context_server_store::Event::SamplingRequested(_) => {}
         }
     }
 
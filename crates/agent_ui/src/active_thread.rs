@@ -749,6 +749,12 @@ fn open_markdown_link(
             }),
             cx,
         ),
+        // Resources can only be opened when they live on the web.
+        Some(MentionLink::McpResource(_, uri)) => {
+            if uri.starts_with("http://") || uri.starts_with("https://") {
+                cx.open_url(&uri);
+            }
+        }
         None => cx.open_url(&text),
     }
 }
@@ -3685,6 +3691,14 @@ pub(crate) fn open_context(
             cx.open_url(&fetched_url_context.url);
         }
 
+        AgentContextHandle::McpResource(resource_context) => {
+            if resource_context.uri.starts_with("http://")
+                || resource_context.uri.starts_with("https://")
+            {
+                cx.open_url(&resource_context.uri);
+            }
+        }
+
         AgentContextHandle::Thread(thread_context) => workspace.update(cx, |workspace, cx| {
             if let Some(panel) = workspace.panel::<AgentPanel>(cx) {
                 panel.update(cx, |panel, cx| {
@@ -299,6 +299,12 @@ impl ConfigureContextServerModal {
             return Task::ready(Err(anyhow::anyhow!("Context server not found")));
         };
 
+        if let ContextServerSettings::Remote { .. } = settings {
+            return Task::ready(Err(anyhow::anyhow!(
+                "Remote context servers can only be configured in the settings file"
+            )));
+        }
+
         window.spawn(cx, async move |cx| {
             let target = match settings {
                 ContextServerSettings::Custom {
@@ -323,6 +329,7 @@ impl ConfigureContextServerModal {
                         None => None,
                     }
                 }
+                ContextServerSettings::Remote { .. } => None,
             };
 
             match target {
@@ -737,6 +744,8 @@ fn wait_for_context_server(
                 _ => {}
             }
         }
+        project::context_server_store::Event::ResourceUpdated { .. }
+        | project::context_server_store::Event::SamplingRequested(_) => {}
     });
 
     cx.spawn(async move |_cx| {
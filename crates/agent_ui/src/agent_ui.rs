@@ -8,6 +8,7 @@ mod buffer_codegen;
 mod burn_mode_tooltip;
 mod context_picker;
 mod context_server_configuration;
+mod context_server_sampling;
 mod context_strip;
 mod debug;
 mod inline_assistant;
@@ -195,6 +196,7 @@ pub fn init(
     agent::init(cx);
     agent_panel::init(cx);
     context_server_configuration::init(language_registry.clone(), fs.clone(), cx);
+    context_server_sampling::init(cx);
     TextThreadEditor::init(cx);
 
     register_slash_commands(cx);
@@ -4,7 +4,11 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use agent::context_store::ContextStore;
-use anyhow::Result;
+use anyhow::{Context as _, Result};
+use assistant_slash_commands::{acceptable_prompt, prompt_arguments, run_prompt};
+use context_server::ContextServerId;
+use context_server::protocol::ServerCapability;
+use context_server::types::{Prompt, requests};
 use editor::{CompletionProvider, Editor, ExcerptId, ToOffset as _};
 use file_icons::FileIcons;
 use fuzzy::{StringMatch, StringMatchCandidate};
@@ -13,6 +17,7 @@ use http_client::HttpClientWithUrl;
 use itertools::Itertools;
 use language::{Buffer, CodeLabel, HighlightId};
 use lsp::CompletionContext;
+use project::context_server_store::ContextServerStore;
 use project::{Completion, CompletionIntent, CompletionResponse, ProjectPath, Symbol, WorktreeId};
 use prompt_store::PromptStore;
 use rope::Point;
@@ -29,6 +34,9 @@ use agent::{
 
 use super::fetch_context_picker::fetch_url_content;
 use super::file_context_picker::{FileMatch, search_files};
+use super::mcp_resource_context_picker::{
+    McpResourceEntry, add_mcp_resource, search_mcp_resources,
+};
 use super::rules_context_picker::{RulesContextEntry, search_rules};
 use super::symbol_context_picker::SymbolMatch;
 use super::symbol_context_picker::search_symbols;
@@ -45,6 +53,7 @@ pub(crate) enum Match {
     Thread(ThreadMatch),
     Fetch(SharedString),
     Rules(RulesContextEntry),
+    McpResource(McpResourceEntry),
     Entry(EntryMatch),
 }
 
@@ -62,6 +71,7 @@ impl Match {
             Match::Symbol(_) => 1.,
             Match::Fetch(_) => 1.,
             Match::Rules(_) => 1.,
+            Match::McpResource(_) => 1.,
         }
     }
 }
@@ -151,6 +161,23 @@ fn search(
             }
         }
 
+        Some(ContextPickerMode::McpResource) => {
+            let context_server_store = workspace.read(cx).project().read(cx).context_server_store();
+            let search_resources_task = search_mcp_resources(
+                query.clone(),
+                cancellation_flag.clone(),
+                &context_server_store,
+                cx,
+            );
+            cx.background_spawn(async move {
+                search_resources_task
+                    .await
+                    .into_iter()
+                    .map(Match::McpResource)
+                    .collect()
+            })
+        }
+
         None => {
             if query.is_empty() {
                 let mut matches = recent_entries
@@ -272,6 +299,141 @@ impl ContextPickerCompletionProvider {
         }
     }
 
+    fn prompt_completions(
+        &self,
+        state: PromptCompletion,
+        excerpt_id: ExcerptId,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Editor>,
+    ) -> Task<Result<Vec<CompletionResponse>>> {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return Task::ready(Ok(Vec::new()));
+        };
+
+        let snapshot = buffer.read(cx).snapshot();
+        let source_range = snapshot.anchor_before(state.source_range.start)
+            ..snapshot.anchor_after(state.source_range.end);
+        let context_server_store = workspace.read(cx).project().read(cx).context_server_store();
+        let search_task = search_prompts(
+            state.name,
+            state.argument.is_some(),
+            Arc::<AtomicBool>::default(),
+            &context_server_store,
+            cx,
+        );
+        let editor = self.editor.clone();
+
+        cx.spawn(async move |_, cx| {
+            let prompts = search_task.await;
+            let Some(editor) = editor.upgrade() else {
+                return Ok(Vec::new());
+            };
+
+            let completions = cx.update(|cx| {
+                prompts
+                    .into_iter()
+                    .map(|prompt| {
+                        Self::completion_for_prompt(
+                            prompt,
+                            state.argument.clone(),
+                            excerpt_id,
+                            source_range.clone(),
+                            editor.clone(),
+                            context_server_store.clone(),
+                            cx,
+                        )
+                    })
+                    .collect()
+            })?;
+
+            Ok(vec![CompletionResponse {
+                completions,
+                is_incomplete: true,
+            }])
+        })
+    }
+
+    fn completion_for_prompt(
+        prompt: PromptMatch,
+        argument: Option<String>,
+        excerpt_id: ExcerptId,
+        source_range: Range<Anchor>,
+        editor: Entity<Editor>,
+        context_server_store: Entity<ContextServerStore>,
+        cx: &App,
+    ) -> Completion {
+        let comment_id = cx.theme().syntax().highlight_id("comment").map(HighlightId);
+        let mut label = CodeLabel::plain(format!("/{}", prompt.prompt.name), None);
+        if let Some(description) = prompt.prompt.description.as_ref() {
+            label.push_str(" ", None);
+            label.push_str(description, comment_id);
+        }
+        let icon_path = Some(IconName::Server.path().into());
+
+        let requires_argument = prompt.prompt.arguments.as_ref().is_some_and(|arguments| {
+            arguments
+                .iter()
+                .any(|argument| argument.required != Some(false))
+        });
+        if argument.is_none() && requires_argument {
+            // Keep the menu open after the prompt's name, so that its argument can be typed.
+            return Completion {
+                replace_range: source_range,
+                new_text: format!("/{} ", prompt.prompt.name),
+                label,
+                documentation: None,
+                insert_text_mode: None,
+                source: project::CompletionSource::Custom,
+                icon_path,
+                confirm: Some(Arc::new(|_, _, _| true)),
+            };
+        }
+
+        let arguments = argument
+            .filter(|argument| !argument.is_empty())
+            .into_iter()
+            .collect::<Vec<_>>();
+        let new_text = match arguments.first() {
+            Some(argument) => format!("/{} {}", prompt.prompt.name, argument),
+            None => format!("/{}", prompt.prompt.name),
+        };
+        let new_text_len = new_text.len();
+        let start = source_range.start;
+        Completion {
+            replace_range: source_range,
+            new_text,
+            label,
+            documentation: None,
+            insert_text_mode: None,
+            source: project::CompletionSource::Custom,
+            icon_path,
+            // Once the prompt is chosen, its command is replaced by the prompt's messages.
+            confirm: Some(Arc::new(move |_, window, cx| {
+                let server = context_server_store
+                    .read(cx)
+                    .get_running_server(&prompt.server_id);
+                let prompt_arguments = prompt_arguments(&prompt.prompt, &arguments);
+                let prompt_name = prompt.prompt.name.clone();
+                let editor = editor.clone();
+                window
+                    .spawn(cx, async move |cx| {
+                        let server = server.context("context server is not running")?;
+                        let (text, _) = run_prompt(server, prompt_name, prompt_arguments?).await?;
+                        editor.update(cx, |editor, cx| {
+                            let snapshot = editor.buffer().read(cx).snapshot(cx);
+                            let Some(start) = snapshot.anchor_in_excerpt(excerpt_id, start) else {
+                                return;
+                            };
+                            let start = start.to_offset(&snapshot);
+                            editor.edit([(start..start + new_text_len, text)], cx);
+                        })
+                    })
+                    .detach_and_log_err(cx);
+                false
+            })),
+        }
+    }
+
     fn completion_for_entry(
         entry: ContextPickerEntry,
         excerpt_id: ExcerptId,
@@ -523,6 +685,73 @@ impl ContextPickerCompletionProvider {
         }
     }
 
+    fn completion_for_mcp_resource(
+        resource: McpResourceEntry,
+        excerpt_id: ExcerptId,
+        source_range: Range<Anchor>,
+        editor: Entity<Editor>,
+        context_store: Entity<ContextStore>,
+        workspace: &Entity<Workspace>,
+        cx: &App,
+    ) -> Completion {
+        let icon_path: SharedString = IconName::Server.path().into();
+        let comment_id = cx.theme().syntax().highlight_id("comment").map(HighlightId);
+        let mut label = CodeLabel::plain(resource.name.to_string(), None);
+        label.push_str(" ", None);
+        label.push_str(&resource.uri, comment_id);
+        label.filter_range = 0..resource.name.len();
+
+        if resource.is_template {
+            // Templates are completed to their URI, so that their variables can be filled in,
+            // after which the typed URI is offered as a resource.
+            return Completion {
+                replace_range: source_range.clone(),
+                new_text: format!(
+                    "@{} {}",
+                    ContextPickerMode::McpResource.keyword(),
+                    resource.uri
+                ),
+                label,
+                documentation: None,
+                insert_text_mode: None,
+                source: project::CompletionSource::Custom,
+                icon_path: Some(icon_path),
+                confirm: Some(Arc::new(|_, _, _| true)),
+            };
+        }
+
+        let context_server_store = workspace.read(cx).project().read(cx).context_server_store();
+        let new_text = format!("{} ", MentionLink::for_mcp_resource(&resource));
+        let new_text_len = new_text.len();
+        Completion {
+            replace_range: source_range.clone(),
+            new_text,
+            label,
+            documentation: None,
+            insert_text_mode: None,
+            source: project::CompletionSource::Custom,
+            icon_path: Some(icon_path.clone()),
+            confirm: Some(confirm_completion_callback(
+                icon_path,
+                resource.name.clone(),
+                excerpt_id,
+                source_range.start,
+                new_text_len - 1,
+                editor.clone(),
+                context_store.clone(),
+                move |_, cx| {
+                    let task = add_mcp_resource(
+                        resource.clone(),
+                        &context_server_store,
+                        context_store.downgrade(),
+                        cx,
+                    );
+                    cx.spawn(async move |_| task.await.log_err())
+                },
+            )),
+        }
+    }
+
     fn completion_for_fetch(
         source_range: Range<Anchor>,
         url_to_fetch: SharedString,
@@ -750,14 +979,24 @@ impl CompletionProvider for ContextPickerCompletionProvider {
         _window: &mut Window,
         cx: &mut Context<Editor>,
     ) -> Task<Result<Vec<CompletionResponse>>> {
-        let state = buffer.update(cx, |buffer, _cx| {
+        let (state, prompt_state) = buffer.update(cx, |buffer, _cx| {
             let position = buffer_position.to_point(buffer);
             let line_start = Point::new(position.row, 0);
             let offset_to_line = buffer.point_to_offset(line_start);
             let mut lines = buffer.text_for_range(line_start..position).lines();
-            let line = lines.next()?;
-            MentionCompletion::try_parse(line, offset_to_line)
+            let Some(line) = lines.next() else {
+                return (None, None);
+            };
+            let state = MentionCompletion::try_parse(line, offset_to_line);
+            let prompt_state = state
+                .is_none()
+                .then(|| PromptCompletion::try_parse(line, offset_to_line))
+                .flatten();
+            (state, prompt_state)
         });
+        if let Some(prompt_state) = prompt_state {
+            return self.prompt_completions(prompt_state, excerpt_id, buffer, cx);
+        }
         let Some(state) = state else {
             return Task::ready(Ok(Vec::new()));
         };
@@ -884,6 +1123,16 @@ impl CompletionProvider for ContextPickerCompletionProvider {
                             context_store.clone(),
                         )),
 
+                        Match::McpResource(resource) => Some(Self::completion_for_mcp_resource(
+                            resource,
+                            excerpt_id,
+                            source_range.clone(),
+                            editor.clone(),
+                            context_store.clone(),
+                            &workspace,
+                            cx,
+                        )),
+
                         Match::Fetch(url) => Some(Self::completion_for_fetch(
                             source_range.clone(),
                             url,
@@ -935,7 +1184,7 @@ impl CompletionProvider for ContextPickerCompletionProvider {
                     completion.source_range.start <= offset_to_line + position.column as usize
                         && completion.source_range.end >= offset_to_line + position.column as usize
                 })
-                .unwrap_or(false)
+                .unwrap_or_else(|| PromptCompletion::try_parse(line, offset_to_line).is_some())
         } else {
             false
         }
@@ -1003,6 +1252,106 @@ fn confirm_completion_callback(
     })
 }
 
+/// A prompt of a running context server, which can be invoked like a slash command.
+#[derive(Clone)]
+struct PromptMatch {
+    server_id: ContextServerId,
+    prompt: Arc<Prompt>,
+}
+
+fn search_prompts(
+    name: String,
+    exact: bool,
+    cancellation_flag: Arc<AtomicBool>,
+    context_server_store: &Entity<ContextServerStore>,
+    cx: &mut App,
+) -> Task<Vec<PromptMatch>> {
+    let servers = context_server_store.read(cx).running_servers();
+    let executor = cx.background_executor().clone();
+    cx.spawn(async move |_| {
+        let prompts = futures::future::join_all(servers.into_iter().map(|server| async move {
+            let protocol = server.client()?;
+            if !protocol.capable(ServerCapability::Prompts) {
+                return None;
+            }
+            let response = protocol
+                .request::<requests::PromptsList>(())
+                .await
+                .log_err()?;
+            Some(
+                response
+                    .prompts
+                    .into_iter()
+                    .filter(acceptable_prompt)
+                    .map(|prompt| PromptMatch {
+                        server_id: server.id(),
+                        prompt: Arc::new(prompt),
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        }))
+        .await
+        .into_iter()
+        .flatten()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        if exact {
+            return prompts
+                .into_iter()
+                .filter(|prompt| prompt.prompt.name == name)
+                .collect();
+        }
+        if name.is_empty() {
+            return prompts;
+        }
+
+        let candidates = prompts
+            .iter()
+            .enumerate()
+            .map(|(ix, prompt)| StringMatchCandidate::new(ix, &prompt.prompt.name))
+            .collect::<Vec<_>>();
+        fuzzy::match_strings(
+            &candidates,
+            &name,
+            false,
+            true,
+            100,
+            &cancellation_flag,
+            executor,
+        )
+        .await
+        .into_iter()
+        .map(|mat| prompts[mat.candidate_id].clone())
+        .collect()
+    })
+}
+
+/// A slash command invoking a context server's prompt, written at the start of a line.
+#[derive(Debug, Default, PartialEq)]
+struct PromptCompletion {
+    source_range: Range<usize>,
+    name: String,
+    /// The argument typed after the prompt's name, present once the name is followed by a space.
+    argument: Option<String>,
+}
+
+impl PromptCompletion {
+    fn try_parse(line: &str, offset_to_line: usize) -> Option<Self> {
+        let rest_of_line = line.strip_prefix('/')?;
+        let (name, argument) = match rest_of_line.split_once(char::is_whitespace) {
+            Some((name, argument)) => (name, Some(argument.trim().to_string())),
+            None => (rest_of_line, None),
+        };
+
+        Some(Self {
+            source_range: offset_to_line..offset_to_line + line.len(),
+            name: name.to_string(),
+            argument,
+        })
+    }
+}
+
 #[derive(Debug, Default, PartialEq)]
 struct MentionCompletion {
     source_range: Range<usize>,
@@ -1141,9 +1490,59 @@ mod tests {
             })
         );
 
+        assert_eq!(
+            MentionCompletion::try_parse("Lorem @resource file:///main.rs", 0),
+            Some(MentionCompletion {
+                source_range: 6..31,
+                mode: Some(ContextPickerMode::McpResource),
+                argument: Some("file:///main.rs".to_string()),
+            })
+        );
+
         assert_eq!(MentionCompletion::try_parse("test@", 0), None);
     }
 
+    #[test]
+    fn test_prompt_completion_parse() {
+        assert_eq!(PromptCompletion::try_parse("Lorem /review", 0), None);
+
+        assert_eq!(
+            PromptCompletion::try_parse("/", 4),
+            Some(PromptCompletion {
+                source_range: 4..5,
+                name: String::new(),
+                argument: None,
+            })
+        );
+
+        assert_eq!(
+            PromptCompletion::try_parse("/rev", 0),
+            Some(PromptCompletion {
+                source_range: 0..4,
+                name: "rev".to_string(),
+                argument: None,
+            })
+        );
+
+        assert_eq!(
+            PromptCompletion::try_parse("/review ", 0),
+            Some(PromptCompletion {
+                source_range: 0..8,
+                name: "review".to_string(),
+                argument: Some(String::new()),
+            })
+        );
+
+        assert_eq!(
+            PromptCompletion::try_parse("/review the main branch", 0),
+            Some(PromptCompletion {
+                source_range: 0..23,
+                name: "review".to_string(),
+                argument: Some("the main branch".to_string()),
+            })
+        );
+    }
+
     struct AtMentionEditor(Entity<Editor>);
 
     impl Item for AtMentionEditor {
@@ -0,0 +1,625 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use context_server::protocol::ServerCapability;
+use context_server::types::requests;
+use context_server::{ContextServer, ContextServerId};
+use fuzzy::StringMatchCandidate;
+use gpui::{App, DismissEvent, Entity, FocusHandle, Focusable, Task, WeakEntity};
+use picker::{Picker, PickerDelegate};
+use project::context_server_store::ContextServerStore;
+use ui::{ListItem, prelude::*};
+use url::Url;
+use util::ResultExt as _;
+
+use crate::context_picker::ContextPicker;
+use agent::context::{AgentContextHandle, read_mcp_resource};
+use agent::context_store::{self, ContextStore};
+
+pub struct McpResourceContextPicker {
+    picker: Entity<Picker<McpResourceContextPickerDelegate>>,
+}
+
+impl McpResourceContextPicker {
+    pub fn new(
+        context_server_store: Entity<ContextServerStore>,
+        context_picker: WeakEntity<ContextPicker>,
+        context_store: WeakEntity<context_store::ContextStore>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = McpResourceContextPickerDelegate::new(
+            context_server_store,
+            context_picker,
+            context_store,
+        );
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+
+        McpResourceContextPicker { picker }
+    }
+}
+
+impl Focusable for McpResourceContextPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for McpResourceContextPicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        self.picker.clone()
+    }
+}
+
+/// A resource, or a resource template, offered by a running context server.
+#[derive(Debug, Clone)]
+pub struct McpResourceEntry {
+    pub server_id: ContextServerId,
+    pub name: SharedString,
+    /// The URI of the resource, or the URI template when `is_template` is set.
+    pub uri: SharedString,
+    pub is_template: bool,
+}
+
+/// A resource template whose variables are being filled in, one query at a time.
+struct PendingTemplate {
+    entry: McpResourceEntry,
+    variables: Vec<String>,
+    values: HashMap<String, String>,
+}
+
+impl PendingTemplate {
+    fn next_variable(&self) -> Option<&str> {
+        self.variables
+            .iter()
+            .find(|variable| !self.values.contains_key(*variable))
+            .map(|variable| variable.as_str())
+    }
+}
+
+pub struct McpResourceContextPickerDelegate {
+    context_server_store: Entity<ContextServerStore>,
+    context_picker: WeakEntity<ContextPicker>,
+    context_store: WeakEntity<context_store::ContextStore>,
+    matches: Vec<McpResourceEntry>,
+    selected_index: usize,
+    pending_template: Option<PendingTemplate>,
+    template_argument: String,
+}
+
+impl McpResourceContextPickerDelegate {
+    pub fn new(
+        context_server_store: Entity<ContextServerStore>,
+        context_picker: WeakEntity<ContextPicker>,
+        context_store: WeakEntity<context_store::ContextStore>,
+    ) -> Self {
+        McpResourceContextPickerDelegate {
+            context_server_store,
+            context_picker,
+            context_store,
+            matches: Vec::new(),
+            selected_index: 0,
+            pending_template: None,
+            template_argument: String::new(),
+        }
+    }
+
+    fn reset_query(&self, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.spawn_in(window, async move |this, cx| {
+            this.update_in(cx, |this, window, cx| {
+                this.set_query("", window, cx);
+                this.refresh_placeholder(window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn add_resource(&self, entry: McpResourceEntry, cx: &mut Context<Picker<Self>>) {
+        add_mcp_resource(
+            entry,
+            &self.context_server_store,
+            self.context_store.clone(),
+            cx,
+        )
+        .detach_and_log_err(cx);
+    }
+}
+
+impl PickerDelegate for McpResourceContextPickerDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        if self.pending_template.is_some() {
+            1
+        } else {
+            self.matches.len()
+        }
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        match self
+            .pending_template
+            .as_ref()
+            .and_then(|template| template.next_variable())
+        {
+            Some(variable) => format!("Enter a value for `{variable}`…").into(),
+            None => "Search resources…".into(),
+        }
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        if self.pending_template.is_some() {
+            self.template_argument = query;
+            self.selected_index = 0;
+            return Task::ready(());
+        }
+
+        let search_task = search_mcp_resources(
+            query,
+            Arc::new(AtomicBool::default()),
+            &self.context_server_store,
+            cx,
+        );
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = search_task.await;
+            this.update(cx, |this, cx| {
+                this.delegate.matches = matches;
+                this.delegate.selected_index = 0;
+                cx.notify();
+            })
+            .ok();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(mut template) = self.pending_template.take() {
+            if let Some(variable) = template.next_variable().map(str::to_string) {
+                template
+                    .values
+                    .insert(variable, std::mem::take(&mut self.template_argument));
+            }
+            if template.next_variable().is_some() {
+                self.pending_template = Some(template);
+            } else {
+                let uri = expand_uri_template(&template.entry.uri, &template.values);
+                self.add_resource(
+                    McpResourceEntry {
+                        uri: uri.into(),
+                        is_template: false,
+                        ..template.entry
+                    },
+                    cx,
+                );
+            }
+            self.reset_query(window, cx);
+            return;
+        }
+
+        let Some(entry) = self.matches.get(self.selected_index) else {
+            return;
+        };
+
+        if entry.is_template {
+            let variables = uri_template_variables(&entry.uri);
+            if !variables.is_empty() {
+                self.pending_template = Some(PendingTemplate {
+                    entry: entry.clone(),
+                    variables,
+                    values: HashMap::default(),
+                });
+                self.reset_query(window, cx);
+                return;
+            }
+        }
+
+        self.add_resource(entry.clone(), cx);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.context_picker
+            .update(cx, |_, cx| {
+                cx.emit(DismissEvent);
+            })
+            .ok();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        if let Some(template) = self.pending_template.as_ref() {
+            let mut values = template.values.clone();
+            if let Some(variable) = template.next_variable() {
+                values.insert(variable.to_string(), self.template_argument.clone());
+            }
+            let entry = McpResourceEntry {
+                uri: expand_uri_template(&template.entry.uri, &values).into(),
+                is_template: false,
+                ..template.entry.clone()
+            };
+            return Some(ListItem::new(ix).inset(true).toggle_state(selected).child(
+                render_mcp_resource_context_entry(&entry, self.context_store.clone(), cx),
+            ));
+        }
+
+        let entry = &self.matches[ix];
+
+        Some(ListItem::new(ix).inset(true).toggle_state(selected).child(
+            render_mcp_resource_context_entry(entry, self.context_store.clone(), cx),
+        ))
+    }
+}
+
+pub fn render_mcp_resource_context_entry(
+    entry: &McpResourceEntry,
+    context_store: WeakEntity<ContextStore>,
+    cx: &mut App,
+) -> Div {
+    let added = !entry.is_template
+        && context_store.upgrade().map_or(false, |context_store| {
+            context_store
+                .read(cx)
+                .includes_mcp_resource(&entry.server_id, &entry.uri)
+        });
+
+    h_flex()
+        .gap_1p5()
+        .w_full()
+        .justify_between()
+        .child(
+            h_flex()
+                .gap_1p5()
+                .max_w_72()
+                .child(
+                    Icon::new(IconName::Server)
+                        .size(IconSize::XSmall)
+                        .color(Color::Muted),
+                )
+                .child(Label::new(entry.name.clone()).truncate())
+                .child(
+                    Label::new(entry.uri.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .truncate(),
+                ),
+        )
+        .when(added, |el| {
+            el.child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Icon::new(IconName::Check)
+                            .size(IconSize::Small)
+                            .color(Color::Success),
+                    )
+                    .child(Label::new("Added").size(LabelSize::Small)),
+            )
+        })
+}
+
+/// Reads a resource from its context server and adds it to the context store.
+pub(crate) fn add_mcp_resource(
+    entry: McpResourceEntry,
+    context_server_store: &Entity<ContextServerStore>,
+    context_store: WeakEntity<ContextStore>,
+    cx: &mut App,
+) -> Task<Result<AgentContextHandle>> {
+    let server = context_server_store
+        .read(cx)
+        .get_running_server(&entry.server_id);
+    cx.spawn(async move |cx| {
+        let server =
+            server.with_context(|| format!("context server {} is not running", entry.server_id))?;
+        let uri = Url::parse(&entry.uri)
+            .with_context(|| format!("invalid resource URI {:?}", entry.uri))?;
+        let text = read_mcp_resource(server, uri.clone()).await?;
+        context_store.update(cx, |context_store, cx| {
+            context_store.add_mcp_resource(entry.server_id, uri.to_string(), entry.name, text, cx)
+        })
+    })
+}
+
+pub(crate) fn search_mcp_resources(
+    query: String,
+    cancellation_flag: Arc<AtomicBool>,
+    context_server_store: &Entity<ContextServerStore>,
+    cx: &mut App,
+) -> Task<Vec<McpResourceEntry>> {
+    let servers = context_server_store.read(cx).running_servers();
+    let executor = cx.background_executor().clone();
+    cx.spawn(async move |_| {
+        let entries = futures::future::join_all(servers.into_iter().map(list_resources))
+            .await
+            .into_iter()
+            .flat_map(|entries| entries.log_err().unwrap_or_default())
+            .collect::<Vec<_>>();
+
+        if query.is_empty() {
+            return entries;
+        }
+
+        // A URI typed out in full is offered as a resource of the first template it fits.
+        let typed_resource = entries
+            .iter()
+            .find(|entry| entry.is_template && uri_matches_template(&entry.uri, &query))
+            .map(|template| McpResourceEntry {
+                uri: query.clone().into(),
+                is_template: false,
+                ..template.clone()
+            });
+
+        let candidates = entries
+            .iter()
+            .enumerate()
+            .map(|(ix, entry)| StringMatchCandidate::new(ix, &entry.name))
+            .collect::<Vec<_>>();
+        let matches = fuzzy::match_strings(
+            &candidates,
+            &query,
+            false,
+            true,
+            100,
+            &cancellation_flag,
+            executor,
+        )
+        .await;
+
+        typed_resource
+            .into_iter()
+            .chain(
+                matches
+                    .into_iter()
+                    .map(|mat| entries[mat.candidate_id].clone()),
+            )
+            .collect()
+    })
+}
+
+async fn list_resources(server: Arc<ContextServer>) -> Result<Vec<McpResourceEntry>> {
+    let Some(protocol) = server.client() else {
+        return Ok(Vec::new());
+    };
+    if !protocol.capable(ServerCapability::Resources) {
+        return Ok(Vec::new());
+    }
+
+    let server_id = server.id();
+    let resources = protocol.request::<requests::ResourcesList>(()).await?;
+    // Resource templates are optional, so servers without them are not an error.
+    let templates = protocol
+        .request::<requests::ListResourceTemplates>(())
+        .await
+        .map(|response| response.resource_templates)
+        .unwrap_or_default();
+
+    let resources = resources
+        .resources
+        .into_iter()
+        .map(|resource| McpResourceEntry {
+            server_id: server_id.clone(),
+            name: resource.name.into(),
+            uri: resource.uri.to_string().into(),
+            is_template: false,
+        });
+    let templates = templates.into_iter().map(|template| McpResourceEntry {
+        server_id: server_id.clone(),
+        name: template.name.into(),
+        uri: template.uri_template.into(),
+        is_template: true,
+    });
+    Ok(resources.chain(templates).collect())
+}
+
+/// Splits a URI template into its literal parts and the expressions between them.
+fn uri_template_parts(template: &str) -> (Vec<&str>, Vec<&str>) {
+    let mut literals = Vec::new();
+    let mut expressions = Vec::new();
+    let mut rest = template;
+    while let Some((literal, expression, remainder)) =
+        rest.split_once('{').and_then(|(literal, tail)| {
+            let (expression, remainder) = tail.split_once('}')?;
+            Some((literal, expression, remainder))
+        })
+    {
+        literals.push(literal);
+        expressions.push(expression);
+        rest = remainder;
+    }
+    literals.push(rest);
+    (literals, expressions)
+}
+
+fn uri_template_operator(expression: &str) -> (Option<char>, &str) {
+    match expression.chars().next() {
+        Some(operator @ ('+' | '#' | '.' | '/' | ';' | '?' | '&')) => {
+            (Some(operator), &expression[1..])
+        }
+        _ => (None, expression),
+    }
+}
+
+fn uri_template_expression_variables(expression: &str) -> impl Iterator<Item = &str> {
+    let (_, variables) = uri_template_operator(expression);
+    variables.split(',').map(|variable| {
+        let variable = variable.trim_end_matches('*');
+        variable
+            .split_once(':')
+            .map_or(variable, |(variable, _)| variable)
+    })
+}
+
+/// The variables of an RFC 6570 URI template, in order of their first appearance.
+pub(crate) fn uri_template_variables(template: &str) -> Vec<String> {
+    let mut variables = Vec::new();
+    for expression in uri_template_parts(template).1 {
+        for variable in uri_template_expression_variables(expression) {
+            if !variable.is_empty() && !variables.iter().any(|existing| existing == variable) {
+                variables.push(variable.to_string());
+            }
+        }
+    }
+    variables
+}
+
+/// Expands an RFC 6570 URI template, percent-encoding the values unless the expression allows
+/// reserved characters.
+pub(crate) fn expand_uri_template(template: &str, values: &HashMap<String, String>) -> String {
+    let (literals, expressions) = uri_template_parts(template);
+    let mut uri = String::new();
+    for (literal, expression) in literals.iter().zip(expressions.iter()) {
+        uri.push_str(literal);
+
+        let (operator, _) = uri_template_operator(expression);
+        let (first, separator, named, allow_reserved) = match operator {
+            Some('+') => ("", ",", false, true),
+            Some('#') => ("#", ",", false, true),
+            Some('.') => (".", ".", false, false),
+            Some('/') => ("/", "/", false, false),
+            Some(';') => (";", ";", true, false),
+            Some('?') => ("?", "&", true, false),
+            Some('&') => ("&", "&", true, false),
+            _ => ("", ",", false, false),
+        };
+        let mut is_first = true;
+        for variable in uri_template_expression_variables(expression) {
+            let Some(value) = values.get(variable) else {
+                continue;
+            };
+            uri.push_str(if is_first { first } else { separator });
+            is_first = false;
+            if named {
+                uri.push_str(variable);
+                if value.is_empty() && operator == Some(';') {
+                    continue;
+                }
+                uri.push('=');
+            }
+            if allow_reserved {
+                uri.push_str(value);
+            } else {
+                uri.push_str(&urlencoding::encode(value));
+            }
+        }
+    }
+    uri.push_str(literals.last().copied().unwrap_or_default());
+    uri
+}
+
+/// Whether the URI could be an expansion of the template, with every variable given a value.
+fn uri_matches_template(template: &str, uri: &str) -> bool {
+    let (literals, _) = uri_template_parts(template);
+    let Some((first, literals)) = literals.split_first() else {
+        return false;
+    };
+    let Some(mut rest) = uri.strip_prefix(first) else {
+        return false;
+    };
+    for (ix, literal) in literals.iter().enumerate() {
+        let is_last = ix + 1 == literals.len();
+        let value_len = if is_last {
+            if !rest.ends_with(literal) {
+                return false;
+            }
+            rest.len().saturating_sub(literal.len())
+        } else if literal.is_empty() {
+            1
+        } else {
+            match rest.get(1..).and_then(|tail| tail.find(literal)) {
+                Some(offset) => offset + 1,
+                None => return false,
+            }
+        };
+        if value_len == 0 || rest[..value_len].contains(['{', '}']) {
+            return false;
+        }
+        rest = &rest[value_len + literal.len()..];
+    }
+    rest.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uri_template_variables() {
+        assert_eq!(
+            uri_template_variables("file:///{path}"),
+            vec!["path".to_string()]
+        );
+        assert_eq!(
+            uri_template_variables("db://{schema}/{table}{?limit,offset}"),
+            vec![
+                "schema".to_string(),
+                "table".to_string(),
+                "limit".to_string(),
+                "offset".to_string()
+            ]
+        );
+        assert_eq!(
+            uri_template_variables("repo://{+path*}/{path:3}"),
+            vec!["path".to_string()]
+        );
+        assert!(uri_template_variables("docs://index").is_empty());
+    }
+
+    #[test]
+    fn test_expand_uri_template() {
+        let values = HashMap::from_iter([
+            ("path".to_string(), "src/main rs".to_string()),
+            ("limit".to_string(), "10".to_string()),
+        ]);
+        assert_eq!(
+            expand_uri_template("file:///{path}", &values),
+            "file:///src%2Fmain%20rs"
+        );
+        assert_eq!(
+            expand_uri_template("file:///{+path}", &values),
+            "file:///src/main rs"
+        );
+        assert_eq!(
+            expand_uri_template("db://rows{?limit,offset}", &values),
+            "db://rows?limit=10"
+        );
+        assert_eq!(expand_uri_template("docs://index", &values), "docs://index");
+    }
+
+    #[test]
+    fn test_uri_matches_template() {
+        assert!(uri_matches_template("file:///{path}", "file:///main.rs"));
+        assert!(!uri_matches_template("file:///{path}", "file:///"));
+        assert!(!uri_matches_template("file:///{path}", "file:///{path}"));
+        assert!(uri_matches_template(
+            "db://{schema}/{table}",
+            "db://public/users"
+        ));
+        assert!(!uri_matches_template(
+            "db://{schema}/{table}",
+            "db://public"
+        ));
+        assert!(!uri_matches_template("docs://{page}", "file:///main.rs"));
+    }
+}
@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use context_server::types::{self, CreateMessageRequest, CreateMessageResult, MessageContent};
+use futures::StreamExt as _;
+use gpui::{App, AsyncApp, Context, PromptLevel, Window};
+use language_model::{
+    ConfiguredModel, LanguageModel, LanguageModelRegistry, LanguageModelRequest,
+    LanguageModelRequestMessage, Role,
+};
+use project::context_server_store::{self, SamplingRequest};
+use util::truncate_and_trailoff;
+use workspace::Workspace;
+
+const MAX_PROMPT_DETAIL_LEN: usize = 500;
+
+/// Answers the sampling requests of the context servers of every workspace with the user's
+/// default language model, once the user allowed it.
+pub(crate) fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+
+        let context_server_store = workspace.project().read(cx).context_server_store();
+        cx.subscribe_in(&context_server_store, window, |_, _, event, window, cx| {
+            if let context_server_store::Event::SamplingRequested(request) = event {
+                handle_sampling_request(request.clone(), window, cx);
+            }
+        })
+        .detach();
+    })
+    .detach();
+}
+
+fn handle_sampling_request(
+    request: SamplingRequest,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(ConfiguredModel { model, .. }) =
+        LanguageModelRegistry::read_global(cx).default_model()
+    else {
+        cx.background_spawn(request.respond(Err(anyhow!("no language model is configured"))))
+            .detach();
+        return;
+    };
+
+    let message = format!(
+        "Allow the {} context server to generate a message with {}?",
+        request.server_id,
+        model.name().0
+    );
+    let detail = prompt_detail(&request.request);
+    let answer = window.prompt(
+        PromptLevel::Info,
+        &message,
+        Some(&detail),
+        &["Allow", "Deny"],
+        cx,
+    );
+    cx.spawn(async move |_, cx| {
+        let result = if answer.await? == 0 {
+            create_message(model, &request.request, cx).await
+        } else {
+            Err(anyhow!("the user rejected the sampling request"))
+        };
+        request.respond(result).await;
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+fn prompt_detail(request: &CreateMessageRequest) -> String {
+    let text = request
+        .messages
+        .iter()
+        .filter_map(|message| match &message.content {
+            MessageContent::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    truncate_and_trailoff(&text, MAX_PROMPT_DETAIL_LEN)
+}
+
+async fn create_message(
+    model: Arc<dyn LanguageModel>,
+    request: &CreateMessageRequest,
+    cx: &AsyncApp,
+) -> Result<CreateMessageResult> {
+    let mut messages = Vec::new();
+    if let Some(system_prompt) = request.system_prompt.clone() {
+        messages.push(LanguageModelRequestMessage {
+            role: Role::System,
+            content: vec![system_prompt.into()],
+            cache: false,
+        });
+    }
+    for message in &request.messages {
+        let MessageContent::Text { text, .. } = &message.content else {
+            anyhow::bail!("only text messages can be sampled");
+        };
+        messages.push(LanguageModelRequestMessage {
+            role: match message.role {
+                types::Role::User => Role::User,
+                types::Role::Assistant => Role::Assistant,
+            },
+            content: vec![text.clone().into()],
+            cache: false,
+        });
+    }
+
+    let request = LanguageModelRequest {
+        thread_id: None,
+        prompt_id: None,
+        intent: None,
+        mode: None,
+        messages,
+        tools: Vec::new(),
+        tool_choice: None,
+        stop: request.stop_sequences.clone().unwrap_or_default(),
+        temperature: request.temperature.map(|temperature| temperature as f32),
+        thinking_allowed: false,
+    };
+    let mut stream = model.stream_completion_text(request, cx).await?.stream;
+    let mut text = String::new();
+    while let Some(chunk) = stream.next().await {
+        text.push_str(&chunk?);
+    }
+
+    Ok(CreateMessageResult {
+        role: types::Role::Assistant,
+        content: MessageContent::Text {
+            text,
+            annotations: None,
+        },
+        model: model.id().0.to_string(),
+        stop_reason: Some("endTurn".to_string()),
+    })
+}
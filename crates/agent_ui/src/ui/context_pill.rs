@@ -15,9 +15,9 @@ use ui::{IconButtonShape, Tooltip, prelude::*, tooltip_container};
 use agent::context::{
     AgentContext, AgentContextHandle, ContextId, ContextKind, DirectoryContext,
     DirectoryContextHandle, FetchedUrlContext, FileContext, FileContextHandle, ImageContext,
-    ImageStatus, RulesContext, RulesContextHandle, SelectionContext, SelectionContextHandle,
-    SymbolContext, SymbolContextHandle, TextThreadContext, TextThreadContextHandle, ThreadContext,
-    ThreadContextHandle,
+    ImageStatus, McpResourceContext, RulesContext, RulesContextHandle, SelectionContext,
+    SelectionContextHandle, SymbolContext, SymbolContextHandle, TextThreadContext,
+    TextThreadContextHandle, ThreadContext, ThreadContextHandle,
 };
 
 #[derive(IntoElement)]
@@ -310,6 +310,7 @@ impl AddedContext {
             AgentContextHandle::Symbol(handle) => Self::pending_symbol(handle, cx),
             AgentContextHandle::Selection(handle) => Self::pending_selection(handle, cx),
             AgentContextHandle::FetchedUrl(handle) => Some(Self::fetched_url(handle)),
+            AgentContextHandle::McpResource(handle) => Some(Self::mcp_resource(handle)),
             AgentContextHandle::Thread(handle) => Some(Self::pending_thread(handle, cx)),
             AgentContextHandle::TextThread(handle) => Some(Self::pending_text_thread(handle, cx)),
             AgentContextHandle::Rules(handle) => Self::pending_rules(handle, prompt_store, cx),
@@ -328,6 +329,7 @@ impl AddedContext {
             AgentContext::Symbol(context) => Self::attached_symbol(context, cx),
             AgentContext::Selection(context) => Self::attached_selection(context, cx),
             AgentContext::FetchedUrl(context) => Self::fetched_url(context.clone()),
+            AgentContext::McpResource(context) => Self::mcp_resource(context.clone()),
             AgentContext::Thread(context) => Self::attached_thread(context),
             AgentContext::TextThread(context) => Self::attached_text_thread(context),
             AgentContext::Rules(context) => Self::attached_rules(context),
@@ -481,6 +483,24 @@ impl AddedContext {
         }
     }
 
+    fn mcp_resource(context: McpResourceContext) -> AddedContext {
+        AddedContext {
+            kind: ContextKind::McpResource,
+            name: context.name.clone(),
+            parent: Some(context.server_id.0.clone().into()),
+            tooltip: None,
+            icon_path: None,
+            status: ContextStatus::Ready,
+            render_hover: {
+                let text = context.text.clone();
+                Some(Rc::new(move |_, cx| {
+                    ContextPillHover::new_text(text.clone(), cx).into()
+                }))
+            },
+            handle: AgentContextHandle::McpResource(context),
+        }
+    }
+
     fn pending_thread(handle: ThreadContextHandle, cx: &App) -> AddedContext {
         AddedContext {
             kind: ContextKind::Thread,
@@ -850,6 +850,8 @@ impl ContextStore {
                     _ => {}
                 }
             }
+            project::context_server_store::Event::ResourceUpdated { .. }
+            | project::context_server_store::Event::SamplingRequested(_) => {}
         }
     }
 
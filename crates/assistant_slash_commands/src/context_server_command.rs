@@ -4,7 +4,7 @@ use assistant_slash_command::{
     SlashCommandOutputSection, SlashCommandResult,
 };
 use collections::HashMap;
-use context_server::{ContextServerId, types::Prompt};
+use context_server::{ContextServer, ContextServerId, types::Prompt};
 use gpui::{App, Entity, Task, WeakEntity, Window};
 use language::{BufferSnapshot, CodeLabel, LspAdapterDelegate};
 use project::context_server_store::ContextServerStore;
@@ -143,47 +143,15 @@ impl SlashCommand for ContextServerSlashCommand {
         let store = self.store.read(cx);
         if let Some(server) = store.get_running_server(&server_id) {
             cx.foreground_executor().spawn(async move {
-                let protocol = server.client().context("Context server not initialized")?;
-                let response = protocol
-                    .request::<context_server::types::requests::PromptsGet>(
-                        context_server::types::PromptsGetParams {
-                            name: prompt_name.clone(),
-                            arguments: Some(prompt_args),
-                            meta: None,
-                        },
-                    )
-                    .await?;
-
-                anyhow::ensure!(
-                    response
-                        .messages
-                        .iter()
-                        .all(|msg| matches!(msg.role, context_server::types::Role::User)),
-                    "Prompt contains non-user roles, which is not supported"
-                );
-
-                // Extract text from user messages into a single prompt string
-                let mut prompt = response
-                    .messages
-                    .into_iter()
-                    .filter_map(|msg| match msg.content {
-                        context_server::types::MessageContent::Text { text, .. } => Some(text),
-                        _ => None,
-                    })
-                    .collect::<Vec<String>>()
-                    .join("\n\n");
-
-                // We must normalize the line endings here, since servers might return CR characters.
-                LineEnding::normalize(&mut prompt);
+                let (prompt, description) =
+                    run_prompt(server, prompt_name.clone(), prompt_args).await?;
 
                 Ok(SlashCommandOutput {
                     sections: vec![SlashCommandOutputSection {
                         range: 0..(prompt.len()),
                         icon: IconName::ZedAssistant,
                         label: SharedString::from(
-                            response
-                                .description
-                                .unwrap_or(format!("Result from {}", prompt_name)),
+                            description.unwrap_or(format!("Result from {}", prompt_name)),
                         ),
                         metadata: None,
                     }],
@@ -198,6 +166,49 @@ impl SlashCommand for ContextServerSlashCommand {
     }
 }
 
+/// Gets a prompt from a context server, returning the text of its messages joined into a
+/// single prompt, along with the prompt's description.
+pub async fn run_prompt(
+    server: Arc<ContextServer>,
+    prompt_name: String,
+    prompt_args: HashMap<String, String>,
+) -> Result<(String, Option<String>)> {
+    let protocol = server.client().context("Context server not initialized")?;
+    let response = protocol
+        .request::<context_server::types::requests::PromptsGet>(
+            context_server::types::PromptsGetParams {
+                name: prompt_name,
+                arguments: Some(prompt_args),
+                meta: None,
+            },
+        )
+        .await?;
+
+    anyhow::ensure!(
+        response
+            .messages
+            .iter()
+            .all(|msg| matches!(msg.role, context_server::types::Role::User)),
+        "Prompt contains non-user roles, which is not supported"
+    );
+
+    // Extract text from user messages into a single prompt string
+    let mut prompt = response
+        .messages
+        .into_iter()
+        .filter_map(|msg| match msg.content {
+            context_server::types::MessageContent::Text { text, .. } => Some(text),
+            _ => None,
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n");
+
+    // We must normalize the line endings here, since servers might return CR characters.
+    LineEnding::normalize(&mut prompt);
+
+    Ok((prompt, response.description))
+}
+
 fn completion_argument(prompt: &Prompt, arguments: &[String]) -> Result<(String, String)> {
     anyhow::ensure!(!arguments.is_empty(), "No arguments given");
 
@@ -212,7 +223,7 @@ fn completion_argument(prompt: &Prompt, arguments: &[String]) -> Result<(String,
     }
 }
 
-fn prompt_arguments(prompt: &Prompt, arguments: &[String]) -> Result<HashMap<String, String>> {
+pub fn prompt_arguments(prompt: &Prompt, arguments: &[String]) -> Result<HashMap<String, String>> {
     match &prompt.arguments {
         Some(args) if args.len() > 1 => {
             anyhow::bail!("Prompt has more than one argument, which is not supported");
@@ -5,7 +5,15 @@ mod diagnostics_tool;
 mod edit_agent;
 mod edit_file_tool;
 mod fetch_tool;
+mod find_definition_tool;
 mod find_path_tool;
+mod find_references_tool;
+mod git_blame_tool;
+mod git_commit_tool;
+mod git_diff_tool;
+mod git_log_tool;
+mod git_repository;
+mod git_status_tool;
 mod grep_tool;
 mod list_directory_tool;
 mod move_path_tool;
@@ -13,12 +21,16 @@ mod now_tool;
 mod open_tool;
 mod project_notifications_tool;
 mod read_file_tool;
+mod rename_symbol_tool;
 mod schema;
+mod symbol_info_tool;
+mod symbol_position;
 mod templates;
 mod terminal_tool;
 mod thinking_tool;
 mod ui;
 mod web_search_tool;
+mod workspace_symbols_tool;
 
 use std::sync::Arc;
 
@@ -37,10 +49,20 @@ use crate::delete_path_tool::DeletePathTool;
 use crate::diagnostics_tool::DiagnosticsTool;
 use crate::edit_file_tool::EditFileTool;
 use crate::fetch_tool::FetchTool;
+use crate::find_definition_tool::FindDefinitionTool;
 use crate::find_path_tool::FindPathTool;
+use crate::find_references_tool::FindReferencesTool;
+use crate::git_blame_tool::GitBlameTool;
+use crate::git_commit_tool::GitCommitTool;
+use crate::git_diff_tool::GitDiffTool;
+use crate::git_log_tool::GitLogTool;
+use crate::git_status_tool::GitStatusTool;
 use crate::list_directory_tool::ListDirectoryTool;
 use crate::now_tool::NowTool;
+use crate::rename_symbol_tool::RenameSymbolTool;
+use crate::symbol_info_tool::SymbolInfoTool;
 use crate::thinking_tool::ThinkingTool;
+use crate::workspace_symbols_tool::WorkspaceSymbolsTool;
 
 pub use edit_file_tool::{EditFileMode, EditFileToolInput};
 pub use find_path_tool::FindPathToolInput;
@@ -67,9 +89,19 @@ pub fn init(http_client: Arc<HttpClientWithUrl>, cx: &mut App) {
     registry.register_tool(FindPathTool);
     registry.register_tool(ReadFileTool);
     registry.register_tool(GrepTool);
+    registry.register_tool(FindDefinitionTool);
+    registry.register_tool(FindReferencesTool);
+    registry.register_tool(WorkspaceSymbolsTool);
+    registry.register_tool(SymbolInfoTool);
+    registry.register_tool(GitStatusTool);
+    registry.register_tool(GitDiffTool);
+    registry.register_tool(GitLogTool);
+    registry.register_tool(GitBlameTool);
     registry.register_tool(ThinkingTool);
     registry.register_tool(FetchTool::new(http_client));
     registry.register_tool(EditFileTool);
+    registry.register_tool(RenameSymbolTool);
+    registry.register_tool(GitCommitTool);
 
     register_web_search_tool(&LanguageModelRegistry::global(cx), cx);
     cx.subscribe(
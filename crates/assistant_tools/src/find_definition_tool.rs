@@ -0,0 +1,224 @@
+use crate::schema::json_schema_for;
+use crate::symbol_position::{format_location, resolve_symbol_position};
+use anyhow::{Result, anyhow};
+use assistant_tool::{ActionLog, Tool, ToolResult};
+use gpui::{AnyWindowHandle, App, Entity, Task};
+use itertools::Itertools as _;
+use language_model::{LanguageModel, LanguageModelRequest, LanguageModelToolSchemaFormat};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use ui::IconName;
+use util::markdown::MarkdownInlineCode;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FindDefinitionToolInput {
+    /// The path of a file in which the symbol is used.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    ///
+    /// <example>
+    /// If the project has the following root directories:
+    ///
+    /// - lorem
+    /// - ipsum
+    ///
+    /// If the symbol is used in `dolor.rs` in `ipsum`, you should use the path `ipsum/dolor.rs`.
+    /// </example>
+    pub path: String,
+    /// The 1-based number of the line on which the symbol is used.
+    pub line: u32,
+    /// The name of the symbol, exactly as it's written on that line.
+    pub symbol: String,
+}
+
+pub struct FindDefinitionTool;
+
+impl Tool for FindDefinitionTool {
+    fn name(&self) -> String {
+        "find_definition".into()
+    }
+
+    fn needs_confirmation(&self, _: &serde_json::Value, _: &App) -> bool {
+        false
+    }
+
+    fn may_perform_edits(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> String {
+        include_str!("./find_definition_tool/description.md").into()
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::ToolSearch
+    }
+
+    fn input_schema(&self, format: LanguageModelToolSchemaFormat) -> Result<serde_json::Value> {
+        json_schema_for::<FindDefinitionToolInput>(format)
+    }
+
+    fn ui_text(&self, input: &serde_json::Value) -> String {
+        match serde_json::from_value::<FindDefinitionToolInput>(input.clone()) {
+            Ok(input) => format!("Find definition of {}", MarkdownInlineCode(&input.symbol)),
+            Err(_) => "Find definition".to_string(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: serde_json::Value,
+        _request: Arc<LanguageModelRequest>,
+        project: Entity<Project>,
+        _action_log: Entity<ActionLog>,
+        _model: Arc<dyn LanguageModel>,
+        _window: Option<AnyWindowHandle>,
+        cx: &mut App,
+    ) -> ToolResult {
+        let input = match serde_json::from_value::<FindDefinitionToolInput>(input) {
+            Ok(input) => input,
+            Err(err) => return Task::ready(Err(anyhow!(err))).into(),
+        };
+
+        let position =
+            resolve_symbol_position(&project, &input.path, input.line, &input.symbol, cx);
+        cx.spawn(async move |cx| {
+            let (buffer, position) = position.await?;
+            let definitions = project
+                .update(cx, |project, cx| project.definitions(&buffer, position, cx))?
+                .await?;
+            if definitions.is_empty() {
+                return Ok(format!("No definition found for `{}`", input.symbol).into());
+            }
+
+            let output = cx.update(|cx| {
+                definitions
+                    .iter()
+                    .map(|definition| format_location(&definition.target, cx))
+                    .join("\n")
+            })?;
+            Ok(output.into())
+        })
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use futures::StreamExt as _;
+    use gpui::{AppContext as _, TestAppContext};
+    use language::{FakeLspAdapter, Language, LanguageConfig, LanguageMatcher};
+    use language_model::fake_provider::FakeLanguageModel;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_find_definition(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "src": {
+                    "main.rs": "mod util;\n\nfn main() {\n    util::greet();\n}\n",
+                    "util.rs": "pub fn greet() {\n    println!(\"Hello!\");\n}\n",
+                }
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_language_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                capabilities: lsp::ServerCapabilities {
+                    definition_provider: Some(lsp::OneOf::Left(true)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/src/main.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let _handle = project.update(cx, |project, cx| {
+            project.register_buffer_with_language_servers(&buffer, cx)
+        });
+        let fake_language_server = fake_language_servers.next().await.unwrap();
+        fake_language_server.set_request_handler::<lsp::request::GotoDefinition, _, _>(
+            |params, _| async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(3, 10)
+                );
+                Ok(Some(lsp::GotoDefinitionResponse::Scalar(
+                    lsp::Location::new(
+                        lsp::Url::from_file_path(path!("/root/src/util.rs")).unwrap(),
+                        lsp::Range::new(lsp::Position::new(0, 7), lsp::Position::new(0, 12)),
+                    ),
+                )))
+            },
+        );
+
+        let action_log = cx.new(|_| ActionLog::new(project.clone()));
+        let model = Arc::new(FakeLanguageModel::default());
+        let result = cx
+            .update(|cx| {
+                let input = json!({
+                    "path": "root/src/main.rs",
+                    "line": 4,
+                    "symbol": "greet",
+                });
+                Arc::new(FindDefinitionTool)
+                    .run(
+                        input,
+                        Arc::default(),
+                        project.clone(),
+                        action_log,
+                        model,
+                        None,
+                        cx,
+                    )
+                    .output
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.content.as_str().unwrap(),
+            "root/src/util.rs:1: pub fn greet() {"
+        );
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+    }
+}
@@ -0,0 +1,235 @@
+use crate::git_repository::format_commit_date;
+use crate::schema::json_schema_for;
+use anyhow::{Context as _, Result, anyhow};
+use assistant_tool::{ActionLog, Tool, ToolResult};
+use gpui::{AnyWindowHandle, App, Entity, Task};
+use language::Point;
+use language_model::{LanguageModel, LanguageModelRequest, LanguageModelToolSchemaFormat};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Write, sync::Arc};
+use ui::IconName;
+use util::markdown::MarkdownInlineCode;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitBlameToolInput {
+    /// The path of the file to blame.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    ///
+    /// <example>
+    /// If the project has the following root directories:
+    ///
+    /// - lorem
+    /// - ipsum
+    ///
+    /// If you want to blame `dolor.rs` in `ipsum`, you should use the path `ipsum/dolor.rs`.
+    /// </example>
+    pub path: String,
+    /// Optional line number to start blaming on (1-based index)
+    #[serde(default)]
+    pub start_line: Option<u32>,
+    /// Optional line number to end blaming on (1-based index, inclusive)
+    #[serde(default)]
+    pub end_line: Option<u32>,
+}
+
+const MAX_LINES: u32 = 500;
+
+pub struct GitBlameTool;
+
+impl Tool for GitBlameTool {
+    fn name(&self) -> String {
+        "git_blame".into()
+    }
+
+    fn needs_confirmation(&self, _: &serde_json::Value, _: &App) -> bool {
+        false
+    }
+
+    fn may_perform_edits(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> String {
+        include_str!("./git_blame_tool/description.md").into()
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::FileGit
+    }
+
+    fn input_schema(&self, format: LanguageModelToolSchemaFormat) -> Result<serde_json::Value> {
+        json_schema_for::<GitBlameToolInput>(format)
+    }
+
+    fn ui_text(&self, input: &serde_json::Value) -> String {
+        match serde_json::from_value::<GitBlameToolInput>(input.clone()) {
+            Ok(input) => format!("Blame {}", MarkdownInlineCode(&input.path)),
+            Err(_) => "Blame file".to_string(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: serde_json::Value,
+        _request: Arc<LanguageModelRequest>,
+        project: Entity<Project>,
+        _action_log: Entity<ActionLog>,
+        _model: Arc<dyn LanguageModel>,
+        _window: Option<AnyWindowHandle>,
+        cx: &mut App,
+    ) -> ToolResult {
+        let input = match serde_json::from_value::<GitBlameToolInput>(input) {
+            Ok(input) => input,
+            Err(err) => return Task::ready(Err(anyhow!(err))).into(),
+        };
+        let Some(project_path) = project.read(cx).find_project_path(&input.path, cx) else {
+            return Task::ready(Err(anyhow!(
+                "Could not find path {} in project",
+                input.path
+            )))
+            .into();
+        };
+
+        let open_buffer = project.update(cx, |project, cx| project.open_buffer(project_path, cx));
+        cx.spawn(async move |cx| {
+            let buffer = open_buffer.await?;
+            let blame = project
+                .update(cx, |project, cx| {
+                    project.git_store().update(cx, |git_store, cx| {
+                        git_store.blame_buffer(&buffer, None, cx)
+                    })
+                })?
+                .await?
+                .with_context(|| format!("{} is not tracked by git", input.path))?;
+            let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot())?;
+
+            let max_row = snapshot.max_point().row;
+            let start_row = input.start_line.unwrap_or(1).saturating_sub(1).min(max_row);
+            let end_row = input
+                .end_line
+                .map_or(max_row, |end_line| end_line.saturating_sub(1))
+                .clamp(start_row, max_row);
+            let truncated_end_row = end_row.min(start_row + MAX_LINES - 1);
+
+            let mut output = String::new();
+            for row in start_row..=truncated_end_row {
+                let entry = blame
+                    .entries
+                    .iter()
+                    .find(|entry| entry.range.contains(&row));
+                let attribution = match entry {
+                    Some(entry) if !entry.sha.is_zero() => format!(
+                        "{} {} {}",
+                        entry.sha.display_short(),
+                        entry.author.as_deref().unwrap_or("unknown"),
+                        entry
+                            .author_time
+                            .map(format_commit_date)
+                            .unwrap_or_default()
+                    ),
+                    _ => "uncommitted".to_string(),
+                };
+                let line_text = snapshot
+                    .text_for_range(Point::new(row, 0)..Point::new(row, snapshot.line_len(row)))
+                    .collect::<String>();
+                writeln!(output, "{} {attribution}: {line_text}", row + 1).ok();
+            }
+            if truncated_end_row < end_row {
+                writeln!(
+                    output,
+                    "\nOnly the first {MAX_LINES} lines were blamed. Pass start_line: {} to see the rest.",
+                    truncated_end_row + 2
+                )
+                .ok();
+            }
+            Ok(output.into())
+        })
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use git::blame::{Blame, BlameEntry};
+    use gpui::{AppContext as _, TestAppContext};
+    use indoc::indoc;
+    use language_model::fake_provider::FakeLanguageModel;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_git_blame(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                ".git": {},
+                "src": {
+                    "main.rs": "fn main() {\n    hello();\n}\n",
+                }
+            }),
+        )
+        .await;
+        fs.set_blame_for_repo(
+            Path::new(path!("/root/.git")),
+            vec![(
+                "src/main.rs".into(),
+                Blame {
+                    entries: vec![BlameEntry {
+                        sha: "1b1b1b1b1b1b1b1b1b1b1b1b1b1b1b1b1b1b1b1b".parse().unwrap(),
+                        range: 0..2,
+                        author: Some("Ada".to_string()),
+                        author_time: Some(1_700_000_000),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )],
+        );
+        let project = Project::test(fs, [path!("/root").as_ref()], cx).await;
+        cx.executor().run_until_parked();
+
+        let action_log = cx.new(|_| ActionLog::new(project.clone()));
+        let model = Arc::new(FakeLanguageModel::default());
+        let result = cx
+            .update(|cx| {
+                Arc::new(GitBlameTool)
+                    .run(
+                        json!({ "path": "root/src/main.rs", "start_line": 1, "end_line": 3 }),
+                        Arc::default(),
+                        project.clone(),
+                        action_log,
+                        model,
+                        None,
+                        cx,
+                    )
+                    .output
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.content.as_str().unwrap(),
+            indoc! {"
+                1 1b1b1b1 Ada 2023-11-14: fn main() {
+                2 1b1b1b1 Ada 2023-11-14:     hello();
+                3 uncommitted: }
+            "}
+        );
+    }
+}
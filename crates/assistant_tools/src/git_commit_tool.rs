@@ -0,0 +1,276 @@
+use crate::git_repository::{resolve_repository, short_sha};
+use crate::schema::json_schema_for;
+use anyhow::{Result, anyhow};
+use assistant_tool::{ActionLog, Tool, ToolResult};
+use git::repository::CommitOptions;
+use gpui::{AnyWindowHandle, App, Entity, Task};
+use language_model::{LanguageModel, LanguageModelRequest, LanguageModelToolSchemaFormat};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use ui::IconName;
+use util::markdown::MarkdownInlineCode;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitCommitToolInput {
+    /// The commit message. Its first line is used as the commit's summary.
+    pub message: String,
+    /// A path in the repository to commit to. If not provided, commits to the project's active
+    /// repository.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    ///
+    /// <example>
+    /// If the project has the following root directories:
+    ///
+    /// - lorem
+    /// - ipsum
+    ///
+    /// If you want to commit to the repository in `ipsum`, you should use the path `ipsum`.
+    /// </example>
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+pub struct GitCommitTool;
+
+impl Tool for GitCommitTool {
+    fn name(&self) -> String {
+        "git_commit".into()
+    }
+
+    fn needs_confirmation(&self, _: &serde_json::Value, _: &App) -> bool {
+        true
+    }
+
+    fn may_perform_edits(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> String {
+        include_str!("./git_commit_tool/description.md").into()
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::GitBranch
+    }
+
+    fn input_schema(&self, format: LanguageModelToolSchemaFormat) -> Result<serde_json::Value> {
+        json_schema_for::<GitCommitToolInput>(format)
+    }
+
+    fn ui_text(&self, input: &serde_json::Value) -> String {
+        match serde_json::from_value::<GitCommitToolInput>(input.clone()) {
+            Ok(input) => format!(
+                "Commit {}",
+                MarkdownInlineCode(input.message.lines().next().unwrap_or_default())
+            ),
+            Err(_) => "Commit changes".to_string(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: serde_json::Value,
+        _request: Arc<LanguageModelRequest>,
+        project: Entity<Project>,
+        _action_log: Entity<ActionLog>,
+        _model: Arc<dyn LanguageModel>,
+        _window: Option<AnyWindowHandle>,
+        cx: &mut App,
+    ) -> ToolResult {
+        let input = match serde_json::from_value::<GitCommitToolInput>(input) {
+            Ok(input) => input,
+            Err(err) => return Task::ready(Err(anyhow!(err))).into(),
+        };
+        if input.message.trim().is_empty() {
+            return Task::ready(Err(anyhow!("The commit message must not be empty"))).into();
+        }
+        let repository = match resolve_repository(&project, input.path.as_deref(), cx) {
+            Ok((repository, _)) => repository,
+            Err(err) => return Task::ready(Err(err)).into(),
+        };
+
+        // Like the git panel, commit the staged changes if there are any, and otherwise stage
+        // and commit every change to a tracked file.
+        let snapshot = repository.read(cx).snapshot();
+        if snapshot.status().any(|entry| entry.status.is_conflicted()) {
+            return Task::ready(Err(anyhow!(
+                "There are still conflicts. They must be resolved and staged before committing"
+            )))
+            .into();
+        }
+        let stage_tracked = !snapshot
+            .status()
+            .any(|entry| entry.status.staging().has_staged());
+
+        let commit = repository.update(cx, |repository, cx| {
+            repository.commit_staged_or_tracked(
+                input.message.trim().to_string().into(),
+                None,
+                stage_tracked,
+                CommitOptions::default(),
+                cx,
+            )
+        });
+
+        cx.spawn(async move |cx| {
+            commit.await?;
+
+            let head = repository
+                .update(cx, |repository, _| repository.show("HEAD".to_string()))?
+                .await?;
+            match head {
+                Ok(head) => Ok(format!("Created commit {}", short_sha(&head.sha)).into()),
+                Err(_) => Ok("Created the commit".to_string().into()),
+            }
+        })
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use git::{
+        repository::RepoPath,
+        status::{FileStatus, StatusCode},
+    };
+    use gpui::{AppContext as _, TestAppContext};
+    use language_model::fake_provider::FakeLanguageModel;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_git_commit_staged_changes(cx: &mut TestAppContext) {
+        let fs = init_test(cx).await;
+        let dot_git = Path::new(path!("/root/.git"));
+        fs.set_status_for_repo(
+            dot_git,
+            &[
+                (Path::new("a.rs"), StatusCode::Modified.index()),
+                (Path::new("b.rs"), StatusCode::Modified.worktree()),
+            ],
+        );
+
+        let output = commit(fs.clone(), "Update a", cx).await;
+        assert!(output.starts_with("Created commit"), "{output}");
+
+        // Only the staged file is committed
+        fs.with_git_state(dot_git, false, |state| {
+            assert_eq!(state.commits.len(), 1);
+            assert_eq!(state.commits[0].message.as_ref(), "Update a");
+            assert_eq!(
+                state
+                    .head_contents
+                    .get(&RepoPath::from("a.rs"))
+                    .map(String::as_str),
+                Some("fn a() {}")
+            );
+            assert_eq!(
+                state
+                    .head_contents
+                    .get(&RepoPath::from("b.rs"))
+                    .map(String::as_str),
+                Some("fn b() {} (modified in working copy)")
+            );
+        })
+        .unwrap();
+    }
+
+    #[gpui::test]
+    async fn test_git_commit_all_tracked_changes(cx: &mut TestAppContext) {
+        let fs = init_test(cx).await;
+        let dot_git = Path::new(path!("/root/.git"));
+        fs.set_status_for_repo(
+            dot_git,
+            &[
+                (Path::new("a.rs"), StatusCode::Modified.worktree()),
+                (Path::new("b.rs"), StatusCode::Modified.worktree()),
+                (Path::new("notes.txt"), FileStatus::Untracked),
+            ],
+        );
+
+        let output = commit(fs.clone(), "Update a and b", cx).await;
+        assert!(output.starts_with("Created commit"), "{output}");
+
+        // Without staged changes, every tracked change is committed but new files aren't
+        fs.with_git_state(dot_git, false, |state| {
+            assert_eq!(state.commits.len(), 1);
+            assert_eq!(
+                state
+                    .head_contents
+                    .get(&RepoPath::from("a.rs"))
+                    .map(String::as_str),
+                Some("fn a() {}")
+            );
+            assert_eq!(
+                state
+                    .head_contents
+                    .get(&RepoPath::from("b.rs"))
+                    .map(String::as_str),
+                Some("fn b() {}")
+            );
+            assert_eq!(
+                state
+                    .head_contents
+                    .get(&RepoPath::from("notes.txt"))
+                    .map(String::as_str),
+                None
+            );
+        })
+        .unwrap();
+    }
+
+    async fn init_test(cx: &mut TestAppContext) -> Arc<FakeFs> {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                ".git": {},
+                "a.rs": "fn a() {}",
+                "b.rs": "fn b() {}",
+                "notes.txt": "",
+            }),
+        )
+        .await;
+        fs
+    }
+
+    async fn commit(fs: Arc<FakeFs>, message: &str, cx: &mut TestAppContext) -> String {
+        let project = Project::test(fs, [path!("/root").as_ref()], cx).await;
+        cx.executor().run_until_parked();
+
+        let action_log = cx.new(|_| ActionLog::new(project.clone()));
+        let model = Arc::new(FakeLanguageModel::default());
+        let result = cx
+            .update(|cx| {
+                Arc::new(GitCommitTool)
+                    .run(
+                        json!({ "message": message }),
+                        Arc::default(),
+                        project.clone(),
+                        action_log,
+                        model,
+                        None,
+                        cx,
+                    )
+                    .output
+            })
+            .await
+            .unwrap();
+        result.content.as_str().unwrap().to_string()
+    }
+}
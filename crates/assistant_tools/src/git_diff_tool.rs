@@ -0,0 +1,246 @@
+use crate::git_repository::{format_commit_date, resolve_repository};
+use crate::schema::json_schema_for;
+use anyhow::{Result, anyhow};
+use assistant_tool::{ActionLog, Tool, ToolResult};
+use git::repository::{DiffType, RepoPath};
+use gpui::{AnyWindowHandle, App, Entity, Task};
+use language_model::{LanguageModel, LanguageModelRequest, LanguageModelToolSchemaFormat};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Write, path::Path, sync::Arc};
+use ui::IconName;
+use util::markdown::MarkdownInlineCode;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitDiffToolInput {
+    /// Only show the changes to this file or directory. If not provided, shows the changes to
+    /// the whole of the project's active repository.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    ///
+    /// <example>
+    /// If the project has the following root directories:
+    ///
+    /// - lorem
+    /// - ipsum
+    ///
+    /// If you want the changes to `dolor.rs` in `ipsum`, you should use the path `ipsum/dolor.rs`.
+    /// </example>
+    #[serde(default)]
+    pub path: Option<String>,
+    /// The SHA of a commit to show the changes of. If not provided, shows the uncommitted
+    /// changes instead.
+    #[serde(default)]
+    pub commit: Option<String>,
+    /// Whether to show only the staged changes, rather than all the uncommitted changes.
+    /// Ignored when `commit` is provided.
+    #[serde(default)]
+    pub staged: bool,
+}
+
+const MAX_DIFF_BYTES: usize = 64 * 1024;
+
+pub struct GitDiffTool;
+
+impl Tool for GitDiffTool {
+    fn name(&self) -> String {
+        "git_diff".into()
+    }
+
+    fn needs_confirmation(&self, _: &serde_json::Value, _: &App) -> bool {
+        false
+    }
+
+    fn may_perform_edits(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> String {
+        include_str!("./git_diff_tool/description.md").into()
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::Diff
+    }
+
+    fn input_schema(&self, format: LanguageModelToolSchemaFormat) -> Result<serde_json::Value> {
+        json_schema_for::<GitDiffToolInput>(format)
+    }
+
+    fn ui_text(&self, input: &serde_json::Value) -> String {
+        match serde_json::from_value::<GitDiffToolInput>(input.clone()) {
+            Ok(GitDiffToolInput {
+                commit: Some(commit),
+                ..
+            }) => format!("Show changes of commit {}", MarkdownInlineCode(&commit)),
+            Ok(GitDiffToolInput { staged: true, .. }) => "Show staged changes".to_string(),
+            _ => "Show uncommitted changes".to_string(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: serde_json::Value,
+        _request: Arc<LanguageModelRequest>,
+        project: Entity<Project>,
+        _action_log: Entity<ActionLog>,
+        _model: Arc<dyn LanguageModel>,
+        _window: Option<AnyWindowHandle>,
+        cx: &mut App,
+    ) -> ToolResult {
+        let input = match serde_json::from_value::<GitDiffToolInput>(input) {
+            Ok(input) => input,
+            Err(err) => return Task::ready(Err(anyhow!(err))).into(),
+        };
+        let (repository, repo_path) = match resolve_repository(&project, input.path.as_deref(), cx)
+        {
+            Ok(repository) => repository,
+            Err(err) => return Task::ready(Err(err)).into(),
+        };
+
+        let output = match input.commit {
+            Some(commit) => {
+                let (details, diff) = repository.update(cx, |repository, _| {
+                    (
+                        repository.show(commit.clone()),
+                        repository.load_commit_diff(commit),
+                    )
+                });
+                cx.background_spawn(async move {
+                    let details = details.await??;
+                    let diff = diff.await??;
+
+                    let mut output = String::new();
+                    writeln!(output, "commit {}", details.sha).ok();
+                    writeln!(
+                        output,
+                        "Author: {} <{}>",
+                        details.author_name, details.author_email
+                    )
+                    .ok();
+                    writeln!(
+                        output,
+                        "Date: {}\n",
+                        format_commit_date(details.commit_timestamp)
+                    )
+                    .ok();
+                    for line in details.message.lines() {
+                        writeln!(output, "    {line}").ok();
+                    }
+                    for file in diff
+                        .files
+                        .iter()
+                        .filter(|file| file.path.starts_with(&*repo_path))
+                    {
+                        let old_path = match file.old_text {
+                            Some(_) => format!("a/{}", file.path),
+                            None => "/dev/null".to_string(),
+                        };
+                        let new_path = match file.new_text {
+                            Some(_) => format!("b/{}", file.path),
+                            None => "/dev/null".to_string(),
+                        };
+                        writeln!(output, "\ndiff --git a/{0} b/{0}", file.path).ok();
+                        writeln!(output, "--- {old_path}\n+++ {new_path}").ok();
+                        output.push_str(&language::unified_diff(
+                            file.old_text.as_deref().unwrap_or_default(),
+                            file.new_text.as_deref().unwrap_or_default(),
+                        ));
+                    }
+                    anyhow::Ok(output)
+                })
+            }
+            None => {
+                let diff_type = if input.staged {
+                    DiffType::HeadToIndex
+                } else {
+                    DiffType::HeadToWorktree
+                };
+                let diff = repository.update(cx, |repository, cx| repository.diff(diff_type, cx));
+                cx.background_spawn(async move {
+                    let diff = diff.await??;
+                    let diff = filter_diff(&diff, &repo_path);
+                    if diff.is_empty() {
+                        anyhow::Ok("No changes".to_string())
+                    } else {
+                        Ok(diff)
+                    }
+                })
+            }
+        };
+
+        cx.background_spawn(async move {
+            let output = output.await?;
+            let truncated = util::truncate_lines_to_byte_limit(&output, MAX_DIFF_BYTES);
+            if truncated.len() < output.len() {
+                Ok(format!(
+                    "{truncated}\n\nThe diff was truncated. Pass a path to see the changes to fewer files."
+                )
+                .into())
+            } else {
+                Ok(output.into())
+            }
+        })
+        .into()
+    }
+}
+
+/// Keeps the sections of a `git diff` output that change files in the given directory or file.
+fn filter_diff(diff: &str, repo_path: &RepoPath) -> String {
+    if repo_path.as_os_str().is_empty() {
+        return diff.to_string();
+    }
+
+    let mut output = String::new();
+    let mut keep_section = false;
+    for line in diff.split_inclusive('\n') {
+        if let Some(paths) = line.strip_prefix("diff --git a/") {
+            let path = paths.split(" b/").next().unwrap_or_default();
+            keep_section = Path::new(path).starts_with(&**repo_path);
+        }
+        if keep_section {
+            output.push_str(line);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_filter_diff() {
+        let diff = indoc! {"
+            diff --git a/src/lib.rs b/src/lib.rs
+            --- a/src/lib.rs
+            +++ b/src/lib.rs
+            @@ -1 +1 @@
+            -one
+            +two
+            diff --git a/docs/README.md b/docs/README.md
+            --- a/docs/README.md
+            +++ b/docs/README.md
+            @@ -1 +1 @@
+            -three
+            +four
+        "};
+
+        assert_eq!(filter_diff(diff, &RepoPath::from("")), diff);
+        assert_eq!(
+            filter_diff(diff, &RepoPath::from("docs")),
+            indoc! {"
+                diff --git a/docs/README.md b/docs/README.md
+                --- a/docs/README.md
+                +++ b/docs/README.md
+                @@ -1 +1 @@
+                -three
+                +four
+            "}
+        );
+        assert_eq!(filter_diff(diff, &RepoPath::from("src/main.rs")), "");
+    }
+}
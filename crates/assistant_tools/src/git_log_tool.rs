@@ -0,0 +1,213 @@
+use crate::git_repository::{format_commit_date, resolve_repository, short_sha};
+use crate::schema::json_schema_for;
+use anyhow::{Result, anyhow};
+use assistant_tool::{ActionLog, Tool, ToolResult};
+use git::repository::LogOptions;
+use gpui::{AnyWindowHandle, App, Entity, Task};
+use itertools::Itertools as _;
+use language_model::{LanguageModel, LanguageModelRequest, LanguageModelToolSchemaFormat};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use ui::IconName;
+use util::markdown::MarkdownInlineCode;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitLogToolInput {
+    /// Only list the commits that touched this file or directory. If not provided, lists the
+    /// commits of the project's active repository.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    ///
+    /// <example>
+    /// If the project has the following root directories:
+    ///
+    /// - lorem
+    /// - ipsum
+    ///
+    /// If you want the history of `dolor.rs` in `ipsum`, you should use the path `ipsum/dolor.rs`.
+    /// </example>
+    #[serde(default)]
+    pub path: Option<String>,
+    /// How many of the most recent commits to skip, used to page through the history.
+    #[serde(default)]
+    pub offset: usize,
+}
+
+const COMMITS_PER_PAGE: usize = 30;
+
+pub struct GitLogTool;
+
+impl Tool for GitLogTool {
+    fn name(&self) -> String {
+        "git_log".into()
+    }
+
+    fn needs_confirmation(&self, _: &serde_json::Value, _: &App) -> bool {
+        false
+    }
+
+    fn may_perform_edits(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> String {
+        include_str!("./git_log_tool/description.md").into()
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::HistoryRerun
+    }
+
+    fn input_schema(&self, format: LanguageModelToolSchemaFormat) -> Result<serde_json::Value> {
+        json_schema_for::<GitLogToolInput>(format)
+    }
+
+    fn ui_text(&self, input: &serde_json::Value) -> String {
+        match serde_json::from_value::<GitLogToolInput>(input.clone()) {
+            Ok(GitLogToolInput {
+                path: Some(path), ..
+            }) if !path.is_empty() => {
+                format!("Get git history of {}", MarkdownInlineCode(&path))
+            }
+            _ => "Get git history".to_string(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: serde_json::Value,
+        _request: Arc<LanguageModelRequest>,
+        project: Entity<Project>,
+        _action_log: Entity<ActionLog>,
+        _model: Arc<dyn LanguageModel>,
+        _window: Option<AnyWindowHandle>,
+        cx: &mut App,
+    ) -> ToolResult {
+        let input = match serde_json::from_value::<GitLogToolInput>(input) {
+            Ok(input) => input,
+            Err(err) => return Task::ready(Err(anyhow!(err))).into(),
+        };
+        let (repository, repo_path) = match resolve_repository(&project, input.path.as_deref(), cx)
+        {
+            Ok(repository) => repository,
+            Err(err) => return Task::ready(Err(err)).into(),
+        };
+
+        let options = LogOptions {
+            skip: input.offset,
+            // Fetch one extra commit to find out whether there are more pages.
+            limit: COMMITS_PER_PAGE + 1,
+            path: (!repo_path.as_os_str().is_empty()).then_some(repo_path),
+            ..Default::default()
+        };
+        let commits = repository.update(cx, |repository, _| repository.log(options));
+        cx.background_spawn(async move {
+            let mut commits = commits.await??;
+            if commits.is_empty() {
+                return Ok("No commits found".to_string().into());
+            }
+
+            let has_more = commits.len() > COMMITS_PER_PAGE;
+            commits.truncate(COMMITS_PER_PAGE);
+            let mut output = commits
+                .iter()
+                .map(|commit| {
+                    format!(
+                        "{} {} {}: {}",
+                        short_sha(&commit.sha),
+                        format_commit_date(commit.commit_timestamp),
+                        commit.author_name,
+                        commit.message.lines().next().unwrap_or_default()
+                    )
+                })
+                .join("\n");
+            if has_more {
+                output.push_str(&format!(
+                    "\n\nThere are more commits. Use offset: {} to see the next page.",
+                    input.offset + COMMITS_PER_PAGE
+                ));
+            }
+            Ok(output.into())
+        })
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use git::repository::CommitDetails;
+    use gpui::{AppContext as _, TestAppContext};
+    use indoc::indoc;
+    use language_model::fake_provider::FakeLanguageModel;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_git_log(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/root"), json!({ ".git": {}, "main.rs": "" }))
+            .await;
+        fs.with_git_state(Path::new(path!("/root/.git")), true, |state| {
+            state.commits = vec![
+                CommitDetails {
+                    sha: "2222222222222222222222222222222222222222".into(),
+                    message: "Add a greeting\n\nSays hello on startup.".into(),
+                    commit_timestamp: 1_700_000_000,
+                    author_name: "Ada".into(),
+                    parent_count: 1,
+                    ..Default::default()
+                },
+                CommitDetails {
+                    sha: "1111111111111111111111111111111111111111".into(),
+                    message: "Initial commit".into(),
+                    commit_timestamp: 1_600_000_000,
+                    author_name: "Grace".into(),
+                    ..Default::default()
+                },
+            ];
+        })
+        .unwrap();
+        let project = Project::test(fs, [path!("/root").as_ref()], cx).await;
+        cx.executor().run_until_parked();
+
+        let action_log = cx.new(|_| ActionLog::new(project.clone()));
+        let model = Arc::new(FakeLanguageModel::default());
+        let result = cx
+            .update(|cx| {
+                Arc::new(GitLogTool)
+                    .run(
+                        json!({}),
+                        Arc::default(),
+                        project.clone(),
+                        action_log,
+                        model,
+                        None,
+                        cx,
+                    )
+                    .output
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.content.as_str().unwrap(),
+            indoc! {"
+                2222222 2023-11-14 Ada: Add a greeting
+                1111111 2020-09-13 Grace: Initial commit"}
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use anyhow::{Context as _, Result, anyhow};
+use git::{SHORT_SHA_LENGTH, repository::RepoPath};
+use gpui::{App, Entity};
+use project::{Project, git_store::Repository};
+use std::path::Path;
+
+/// Finds the repository containing `path` and the path's location within it, falling back to
+/// the project's active repository and its root when no path is given.
+pub(crate) fn resolve_repository(
+    project: &Entity<Project>,
+    path: Option<&str>,
+    cx: &App,
+) -> Result<(Entity<Repository>, RepoPath)> {
+    let project = project.read(cx);
+    let git_store = project.git_store().read(cx);
+    // The model passes an empty string sometimes
+    match path.filter(|path| !path.is_empty()) {
+        Some(path) => {
+            let project_path = project
+                .find_project_path(path, cx)
+                .ok_or_else(|| anyhow!("Could not find path {path} in project"))?;
+            git_store
+                .repository_and_path_for_project_path(&project_path, cx)
+                .with_context(|| format!("{path} is not in a git repository"))
+        }
+        None => {
+            let repository = git_store
+                .active_repository()
+                .context("The project doesn't contain a git repository")?;
+            Ok((repository, RepoPath::default()))
+        }
+    }
+}
+
+/// Formats a path within `repository` the way the project refers to it, so that it can be passed
+/// to the other tools.
+pub(crate) fn display_repo_path(
+    repository: &Repository,
+    repo_path: &RepoPath,
+    project: &Project,
+    cx: &App,
+) -> String {
+    repository
+        .repo_path_to_project_path(repo_path, cx)
+        .and_then(|project_path| {
+            let worktree = project.worktree_for_id(project_path.worktree_id, cx)?;
+            Some(
+                Path::new(worktree.read(cx).root_name())
+                    .join(&project_path.path)
+                    .display()
+                    .to_string(),
+            )
+        })
+        .unwrap_or_else(|| repo_path.to_string())
+}
+
+/// Formats a commit's timestamp as a date.
+pub(crate) fn format_commit_date(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Abbreviates a commit SHA the way git does.
+pub(crate) fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(SHORT_SHA_LENGTH)]
+}
@@ -0,0 +1,245 @@
+use crate::git_repository::{display_repo_path, resolve_repository, short_sha};
+use crate::schema::json_schema_for;
+use anyhow::{Result, anyhow};
+use assistant_tool::{ActionLog, Tool, ToolResult};
+use git::status::{FileStatus, StatusCode};
+use gpui::{AnyWindowHandle, App, Entity, Task};
+use language_model::{LanguageModel, LanguageModelRequest, LanguageModelToolSchemaFormat};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Write, sync::Arc};
+use ui::IconName;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitStatusToolInput {
+    /// A path in the repository to get the status of. If not provided, uses the project's
+    /// active repository.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    ///
+    /// <example>
+    /// If the project has the following root directories:
+    ///
+    /// - lorem
+    /// - ipsum
+    ///
+    /// If you want the status of the repository in `ipsum`, you should use the path `ipsum`.
+    /// </example>
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+pub struct GitStatusTool;
+
+impl Tool for GitStatusTool {
+    fn name(&self) -> String {
+        "git_status".into()
+    }
+
+    fn needs_confirmation(&self, _: &serde_json::Value, _: &App) -> bool {
+        false
+    }
+
+    fn may_perform_edits(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> String {
+        include_str!("./git_status_tool/description.md").into()
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::GitBranch
+    }
+
+    fn input_schema(&self, format: LanguageModelToolSchemaFormat) -> Result<serde_json::Value> {
+        json_schema_for::<GitStatusToolInput>(format)
+    }
+
+    fn ui_text(&self, _input: &serde_json::Value) -> String {
+        "Get git status".to_string()
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: serde_json::Value,
+        _request: Arc<LanguageModelRequest>,
+        project: Entity<Project>,
+        _action_log: Entity<ActionLog>,
+        _model: Arc<dyn LanguageModel>,
+        _window: Option<AnyWindowHandle>,
+        cx: &mut App,
+    ) -> ToolResult {
+        let input = match serde_json::from_value::<GitStatusToolInput>(input) {
+            Ok(input) => input,
+            Err(err) => return Task::ready(Err(anyhow!(err))).into(),
+        };
+        let repository = match resolve_repository(&project, input.path.as_deref(), cx) {
+            Ok((repository, _)) => repository,
+            Err(err) => return Task::ready(Err(err)).into(),
+        };
+
+        let repository = repository.read(cx);
+        let project = project.read(cx);
+        let mut output = String::new();
+        match &repository.branch {
+            Some(branch) => {
+                write!(output, "On branch {}", branch.name()).ok();
+                if let Some(upstream) = &branch.upstream {
+                    if let Some(status) = upstream.tracking.status() {
+                        write!(
+                            output,
+                            " ({} ahead, {} behind {})",
+                            status.ahead, status.behind, upstream.ref_name
+                        )
+                        .ok();
+                    }
+                }
+                writeln!(output).ok();
+            }
+            None => {
+                writeln!(output, "Not on any branch").ok();
+            }
+        }
+        if let Some(head_commit) = &repository.head_commit {
+            let subject = head_commit.message.lines().next().unwrap_or_default();
+            writeln!(output, "HEAD is {} {subject}", short_sha(&head_commit.sha)).ok();
+        }
+
+        let mut has_changes = false;
+        for entry in repository.status() {
+            if entry.status.is_ignored() {
+                continue;
+            }
+            if !has_changes {
+                writeln!(output, "\nChanges:").ok();
+                has_changes = true;
+            }
+            writeln!(
+                output,
+                "{}: {}",
+                describe_status(entry.status),
+                display_repo_path(repository, &entry.repo_path, project, cx)
+            )
+            .ok();
+        }
+        if !has_changes {
+            writeln!(output, "\nNo changes").ok();
+        }
+
+        Task::ready(Ok(output.into())).into()
+    }
+}
+
+fn describe_status(status: FileStatus) -> String {
+    match status {
+        FileStatus::Untracked => "untracked".to_string(),
+        FileStatus::Ignored => "ignored".to_string(),
+        FileStatus::Unmerged(_) => "conflict".to_string(),
+        FileStatus::Tracked(status) => {
+            let mut descriptions = Vec::new();
+            if let Some(change) = describe_status_code(status.index_status) {
+                descriptions.push(format!("staged {change}"));
+            }
+            if let Some(change) = describe_status_code(status.worktree_status) {
+                descriptions.push(change.to_string());
+            }
+            descriptions.join(", ")
+        }
+    }
+}
+
+fn describe_status_code(code: StatusCode) -> Option<&'static str> {
+    match code {
+        StatusCode::Modified => Some("modified"),
+        StatusCode::TypeChanged => Some("type changed"),
+        StatusCode::Added => Some("added"),
+        StatusCode::Deleted => Some("deleted"),
+        StatusCode::Renamed => Some("renamed"),
+        StatusCode::Copied => Some("copied"),
+        StatusCode::Unmodified => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use gpui::{AppContext as _, TestAppContext};
+    use indoc::indoc;
+    use language_model::fake_provider::FakeLanguageModel;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_git_status(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                ".git": {},
+                "notes.txt": "",
+                "src": {
+                    "a.rs": "",
+                    "b.rs": "",
+                    "c.rs": "",
+                }
+            }),
+        )
+        .await;
+        fs.set_branch_name(Path::new(path!("/root/.git")), Some("main"));
+        fs.set_status_for_repo(
+            Path::new(path!("/root/.git")),
+            &[
+                (Path::new("notes.txt"), FileStatus::Untracked),
+                (Path::new("src/a.rs"), StatusCode::Modified.worktree()),
+                (Path::new("src/b.rs"), StatusCode::Added.index()),
+            ],
+        );
+        let project = Project::test(fs, [path!("/root").as_ref()], cx).await;
+        cx.executor().run_until_parked();
+
+        let action_log = cx.new(|_| ActionLog::new(project.clone()));
+        let model = Arc::new(FakeLanguageModel::default());
+        let result = cx
+            .update(|cx| {
+                Arc::new(GitStatusTool)
+                    .run(
+                        json!({}),
+                        Arc::default(),
+                        project.clone(),
+                        action_log,
+                        model,
+                        None,
+                        cx,
+                    )
+                    .output
+            })
+            .await
+            .unwrap();
+
+        let output = result.content.as_str().unwrap();
+        assert!(output.starts_with("On branch main\n"), "{output}");
+        assert!(
+            output.ends_with(indoc! {"
+
+                Changes:
+                untracked: root/notes.txt
+                modified: root/src/a.rs
+                staged added: root/src/b.rs
+            "}),
+            "{output}"
+        );
+    }
+}
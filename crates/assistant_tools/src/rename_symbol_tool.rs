@@ -0,0 +1,314 @@
+use crate::schema::json_schema_for;
+use crate::symbol_position::resolve_symbol_position;
+use anyhow::{Result, anyhow, bail};
+use assistant_tool::{ActionLog, Tool, ToolResult};
+use collections::HashSet;
+use gpui::{AnyWindowHandle, App, Entity, Task};
+use itertools::Itertools as _;
+use language_model::{LanguageModel, LanguageModelRequest, LanguageModelToolSchemaFormat};
+use project::Project;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use ui::IconName;
+use util::markdown::MarkdownInlineCode;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RenameSymbolToolInput {
+    /// The path of a file in which the symbol appears.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    ///
+    /// <example>
+    /// If the project has the following root directories:
+    ///
+    /// - lorem
+    /// - ipsum
+    ///
+    /// If the symbol appears in `dolor.rs` in `ipsum`, you should use the path `ipsum/dolor.rs`.
+    /// </example>
+    pub path: String,
+    /// The 1-based number of the line on which the symbol appears.
+    pub line: u32,
+    /// The name of the symbol, exactly as it's written on that line.
+    pub symbol: String,
+    /// The new name of the symbol.
+    pub new_name: String,
+}
+
+pub struct RenameSymbolTool;
+
+impl Tool for RenameSymbolTool {
+    fn name(&self) -> String {
+        "rename_symbol".into()
+    }
+
+    fn needs_confirmation(&self, _: &serde_json::Value, _: &App) -> bool {
+        false
+    }
+
+    fn may_perform_edits(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> String {
+        include_str!("./rename_symbol_tool/description.md").into()
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::ToolPencil
+    }
+
+    fn input_schema(&self, format: LanguageModelToolSchemaFormat) -> Result<serde_json::Value> {
+        json_schema_for::<RenameSymbolToolInput>(format)
+    }
+
+    fn ui_text(&self, input: &serde_json::Value) -> String {
+        match serde_json::from_value::<RenameSymbolToolInput>(input.clone()) {
+            Ok(input) => format!(
+                "Rename {} to {}",
+                MarkdownInlineCode(&input.symbol),
+                MarkdownInlineCode(&input.new_name)
+            ),
+            Err(_) => "Rename symbol".to_string(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: serde_json::Value,
+        _request: Arc<LanguageModelRequest>,
+        project: Entity<Project>,
+        action_log: Entity<ActionLog>,
+        _model: Arc<dyn LanguageModel>,
+        _window: Option<AnyWindowHandle>,
+        cx: &mut App,
+    ) -> ToolResult {
+        let input = match serde_json::from_value::<RenameSymbolToolInput>(input) {
+            Ok(input) => input,
+            Err(err) => return Task::ready(Err(anyhow!(err))).into(),
+        };
+
+        let position =
+            resolve_symbol_position(&project, &input.path, input.line, &input.symbol, cx);
+        cx.spawn(async move |cx| {
+            let (buffer, position) = position.await?;
+            // Track every buffer that references the symbol before renaming it, so that the
+            // action log can show the rename as a diff.
+            let references = project
+                .update(cx, |project, cx| project.references(&buffer, position, cx))?
+                .await
+                .unwrap_or_default();
+            action_log.update(cx, |log, cx| {
+                log.buffer_read(buffer.clone(), cx);
+                for reference in references {
+                    log.buffer_read(reference.buffer, cx);
+                }
+            })?;
+
+            let transaction = project
+                .update(cx, |project, cx| {
+                    project.perform_rename(buffer, position, input.new_name.clone(), cx)
+                })?
+                .await?;
+            if transaction.0.is_empty() {
+                bail!("Renaming `{}` didn't edit any files", input.symbol);
+            }
+
+            let buffers = transaction.0.into_keys().collect::<HashSet<_>>();
+            project
+                .update(cx, |project, cx| project.save_buffers(buffers.clone(), cx))?
+                .await?;
+
+            let mut paths = Vec::new();
+            action_log.update(cx, |log, cx| {
+                for buffer in buffers {
+                    if let Some(file) = buffer.read(cx).file() {
+                        paths.push(file.full_path(cx).display().to_string());
+                    }
+                    log.buffer_edited(buffer, cx);
+                }
+            })?;
+            paths.sort();
+
+            Ok(format!(
+                "Renamed `{}` to `{}` in:\n{}",
+                input.symbol,
+                input.new_name,
+                paths.iter().map(|path| format!("- {path}")).join("\n")
+            )
+            .into())
+        })
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use futures::StreamExt as _;
+    use gpui::{AppContext as _, TestAppContext};
+    use language::{FakeLspAdapter, Language, LanguageConfig, LanguageMatcher};
+    use language_model::fake_provider::FakeLanguageModel;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_rename_symbol(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "src": {
+                    "main.rs": "mod util;\n\nfn main() {\n    util::greet();\n}\n",
+                    "util.rs": "pub fn greet() {\n    println!(\"Hello!\");\n}\n",
+                }
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_language_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                capabilities: lsp::ServerCapabilities {
+                    references_provider: Some(lsp::OneOf::Left(true)),
+                    rename_provider: Some(lsp::OneOf::Left(true)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/src/main.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let _handle = project.update(cx, |project, cx| {
+            project.register_buffer_with_language_servers(&buffer, cx)
+        });
+        let fake_language_server = fake_language_servers.next().await.unwrap();
+        fake_language_server.set_request_handler::<lsp::request::References, _, _>(
+            |_, _| async move {
+                Ok(Some(vec![
+                    lsp::Location::new(
+                        lsp::Url::from_file_path(path!("/root/src/main.rs")).unwrap(),
+                        lsp::Range::new(lsp::Position::new(3, 10), lsp::Position::new(3, 15)),
+                    ),
+                    lsp::Location::new(
+                        lsp::Url::from_file_path(path!("/root/src/util.rs")).unwrap(),
+                        lsp::Range::new(lsp::Position::new(0, 7), lsp::Position::new(0, 12)),
+                    ),
+                ]))
+            },
+        );
+        fake_language_server.set_request_handler::<lsp::request::Rename, _, _>(
+            |params, _| async move {
+                assert_eq!(
+                    params.text_document_position.position,
+                    lsp::Position::new(3, 10)
+                );
+                assert_eq!(params.new_name, "welcome");
+                Ok(Some(lsp::WorkspaceEdit {
+                    changes: Some(
+                        [
+                            (
+                                lsp::Url::from_file_path(path!("/root/src/main.rs")).unwrap(),
+                                vec![lsp::TextEdit::new(
+                                    lsp::Range::new(
+                                        lsp::Position::new(3, 10),
+                                        lsp::Position::new(3, 15),
+                                    ),
+                                    "welcome".to_string(),
+                                )],
+                            ),
+                            (
+                                lsp::Url::from_file_path(path!("/root/src/util.rs")).unwrap(),
+                                vec![lsp::TextEdit::new(
+                                    lsp::Range::new(
+                                        lsp::Position::new(0, 7),
+                                        lsp::Position::new(0, 12),
+                                    ),
+                                    "welcome".to_string(),
+                                )],
+                            ),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    ),
+                    ..Default::default()
+                }))
+            },
+        );
+
+        let action_log = cx.new(|_| ActionLog::new(project.clone()));
+        let model = Arc::new(FakeLanguageModel::default());
+        let result = cx
+            .update(|cx| {
+                let input = json!({
+                    "path": "root/src/main.rs",
+                    "line": 4,
+                    "symbol": "greet",
+                    "new_name": "welcome",
+                });
+                Arc::new(RenameSymbolTool)
+                    .run(
+                        input,
+                        Arc::default(),
+                        project.clone(),
+                        action_log.clone(),
+                        model,
+                        None,
+                        cx,
+                    )
+                    .output
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.content.as_str().unwrap(),
+            "Renamed `greet` to `welcome` in:\n- root/src/main.rs\n- root/src/util.rs"
+        );
+        assert_eq!(
+            fs.load(path!("/root/src/main.rs").as_ref()).await.unwrap(),
+            "mod util;\n\nfn main() {\n    util::welcome();\n}\n"
+        );
+        assert_eq!(
+            fs.load(path!("/root/src/util.rs").as_ref()).await.unwrap(),
+            "pub fn welcome() {\n    println!(\"Hello!\");\n}\n"
+        );
+        assert_eq!(
+            action_log.read_with(cx, |log, cx| log.changed_buffers(cx).len()),
+            2
+        );
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+    }
+}
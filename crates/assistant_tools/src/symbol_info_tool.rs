@@ -0,0 +1,236 @@
+use crate::schema::json_schema_for;
+use crate::symbol_position::resolve_symbol_position;
+use anyhow::{Result, anyhow};
+use assistant_tool::{ActionLog, Tool, ToolResult};
+use gpui::{AnyWindowHandle, App, Entity, Task};
+use itertools::Itertools as _;
+use language_model::{LanguageModel, LanguageModelRequest, LanguageModelToolSchemaFormat};
+use project::{HoverBlockKind, Project};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use ui::IconName;
+use util::markdown::MarkdownInlineCode;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SymbolInfoToolInput {
+    /// The path of a file in which the symbol appears.
+    ///
+    /// This path should never be absolute, and the first component
+    /// of the path should always be a root directory in a project.
+    ///
+    /// <example>
+    /// If the project has the following root directories:
+    ///
+    /// - lorem
+    /// - ipsum
+    ///
+    /// If the symbol appears in `dolor.rs` in `ipsum`, you should use the path `ipsum/dolor.rs`.
+    /// </example>
+    pub path: String,
+    /// The 1-based number of the line on which the symbol appears.
+    pub line: u32,
+    /// The name of the symbol, exactly as it's written on that line.
+    pub symbol: String,
+}
+
+pub struct SymbolInfoTool;
+
+impl Tool for SymbolInfoTool {
+    fn name(&self) -> String {
+        "symbol_info".into()
+    }
+
+    fn needs_confirmation(&self, _: &serde_json::Value, _: &App) -> bool {
+        false
+    }
+
+    fn may_perform_edits(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> String {
+        include_str!("./symbol_info_tool/description.md").into()
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::Info
+    }
+
+    fn input_schema(&self, format: LanguageModelToolSchemaFormat) -> Result<serde_json::Value> {
+        json_schema_for::<SymbolInfoToolInput>(format)
+    }
+
+    fn ui_text(&self, input: &serde_json::Value) -> String {
+        match serde_json::from_value::<SymbolInfoToolInput>(input.clone()) {
+            Ok(input) => format!("Get info about {}", MarkdownInlineCode(&input.symbol)),
+            Err(_) => "Get symbol info".to_string(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: serde_json::Value,
+        _request: Arc<LanguageModelRequest>,
+        project: Entity<Project>,
+        _action_log: Entity<ActionLog>,
+        _model: Arc<dyn LanguageModel>,
+        _window: Option<AnyWindowHandle>,
+        cx: &mut App,
+    ) -> ToolResult {
+        let input = match serde_json::from_value::<SymbolInfoToolInput>(input) {
+            Ok(input) => input,
+            Err(err) => return Task::ready(Err(anyhow!(err))).into(),
+        };
+
+        let position =
+            resolve_symbol_position(&project, &input.path, input.line, &input.symbol, cx);
+        cx.spawn(async move |cx| {
+            let (buffer, position) = position.await?;
+            let hovers = project
+                .update(cx, |project, cx| project.hover(&buffer, position, cx))?
+                .await;
+            let output = hovers
+                .into_iter()
+                .flat_map(|hover| hover.contents)
+                .filter(|block| !block.text.trim().is_empty())
+                .map(|block| match block.kind {
+                    HoverBlockKind::Code { language } => {
+                        format!("```{language}\n{}\n```", block.text.trim())
+                    }
+                    HoverBlockKind::PlainText | HoverBlockKind::Markdown => {
+                        block.text.trim().to_string()
+                    }
+                })
+                .join("\n\n");
+            if output.is_empty() {
+                Ok(format!("No information found for `{}`", input.symbol).into())
+            } else {
+                Ok(output.into())
+            }
+        })
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use futures::StreamExt as _;
+    use gpui::{AppContext as _, TestAppContext};
+    use language::{FakeLspAdapter, Language, LanguageConfig, LanguageMatcher};
+    use language_model::fake_provider::FakeLanguageModel;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_symbol_info(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "src": {
+                    "main.rs": "mod util;\n\nfn main() {\n    util::greet();\n}\n",
+                    "util.rs": "pub fn greet() {\n    println!(\"Hello!\");\n}\n",
+                }
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_language_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                capabilities: lsp::ServerCapabilities {
+                    hover_provider: Some(lsp::HoverProviderCapability::Simple(true)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/src/main.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let _handle = project.update(cx, |project, cx| {
+            project.register_buffer_with_language_servers(&buffer, cx)
+        });
+        let fake_language_server = fake_language_servers.next().await.unwrap();
+        fake_language_server.set_request_handler::<lsp::request::HoverRequest, _, _>(
+            |params, _| async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(3, 10)
+                );
+                Ok(Some(lsp::Hover {
+                    contents: lsp::HoverContents::Array(vec![
+                        lsp::MarkedString::LanguageString(lsp::LanguageString {
+                            language: "rust".into(),
+                            value: "pub fn greet()".into(),
+                        }),
+                        lsp::MarkedString::String("  ".into()),
+                        lsp::MarkedString::String("Prints a greeting.".into()),
+                    ]),
+                    range: None,
+                }))
+            },
+        );
+
+        let action_log = cx.new(|_| ActionLog::new(project.clone()));
+        let model = Arc::new(FakeLanguageModel::default());
+        let result = cx
+            .update(|cx| {
+                let input = json!({
+                    "path": "root/src/main.rs",
+                    "line": 4,
+                    "symbol": "greet",
+                });
+                Arc::new(SymbolInfoTool)
+                    .run(
+                        input,
+                        Arc::default(),
+                        project.clone(),
+                        action_log,
+                        model,
+                        None,
+                        cx,
+                    )
+                    .output
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.content.as_str().unwrap(),
+            "```rust\npub fn greet()\n```\n\nPrints a greeting."
+        );
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+    }
+}
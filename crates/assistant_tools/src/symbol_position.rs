@@ -0,0 +1,73 @@
+use anyhow::{Context as _, Result, anyhow};
+use gpui::{App, Entity, Task};
+use language::{Buffer, BufferSnapshot, Location, Point, ToPoint as _};
+use project::Project;
+
+/// Opens the buffer at `path` and finds where `symbol` is written on the given 1-based `line`.
+pub(crate) fn resolve_symbol_position(
+    project: &Entity<Project>,
+    path: &str,
+    line: u32,
+    symbol: &str,
+    cx: &mut App,
+) -> Task<Result<(Entity<Buffer>, Point)>> {
+    let Some(project_path) = project.read(cx).find_project_path(path, cx) else {
+        return Task::ready(Err(anyhow!("Could not find path {path} in project")));
+    };
+    let open_buffer = project.update(cx, |project, cx| project.open_buffer(project_path, cx));
+    let path = path.to_string();
+    let symbol = symbol.to_string();
+    cx.spawn(async move |cx| {
+        let buffer = open_buffer.await?;
+        let snapshot = buffer.read_with(cx, |buffer, _cx| buffer.snapshot())?;
+        let position = symbol_position(&snapshot, line, &symbol)
+            .with_context(|| format!("`{symbol}` does not appear on line {line} of {path}"))?;
+        Ok((buffer, position))
+    })
+}
+
+fn symbol_position(snapshot: &BufferSnapshot, line: u32, symbol: &str) -> Option<Point> {
+    let row = line.checked_sub(1)?;
+    if symbol.is_empty() || row > snapshot.max_point().row {
+        return None;
+    }
+
+    let line_text = snapshot
+        .text_for_range(Point::new(row, 0)..Point::new(row, snapshot.line_len(row)))
+        .collect::<String>();
+    let is_identifier_char = |c: char| c.is_alphanumeric() || c == '_';
+    line_text
+        .match_indices(symbol)
+        .find(|(ix, _)| {
+            let before = line_text[..*ix].chars().next_back();
+            let after = line_text[ix + symbol.len()..].chars().next();
+            !before.is_some_and(is_identifier_char) && !after.is_some_and(is_identifier_char)
+        })
+        .map(|(ix, _)| Point::new(row, ix as u32))
+}
+
+/// Orders locations by their path, and then by their position within the file, so that the
+/// output doesn't depend on the order in which the language server returned them.
+pub(crate) fn sort_locations(locations: &mut [Location], cx: &App) {
+    locations.sort_by_cached_key(|location| {
+        let buffer = location.buffer.read(cx);
+        (
+            buffer.file().map(|file| file.full_path(cx)),
+            location.range.start.to_point(buffer),
+        )
+    });
+}
+
+/// Formats `location` as `path:line: text`, where `text` is the line on which it starts.
+pub(crate) fn format_location(location: &Location, cx: &App) -> String {
+    let buffer = location.buffer.read(cx);
+    let path = buffer.file().map_or_else(
+        || "untitled".to_string(),
+        |file| file.full_path(cx).display().to_string(),
+    );
+    let row = location.range.start.to_point(buffer).row;
+    let line_text = buffer
+        .text_for_range(Point::new(row, 0)..Point::new(row, buffer.line_len(row)))
+        .collect::<String>();
+    format!("{path}:{}: {}", row + 1, line_text.trim())
+}
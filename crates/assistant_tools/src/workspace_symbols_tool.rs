@@ -0,0 +1,252 @@
+use crate::schema::json_schema_for;
+use anyhow::{Result, anyhow};
+use assistant_tool::{ActionLog, Tool, ToolResult};
+use gpui::{AnyWindowHandle, App, Entity, Task};
+use itertools::Itertools as _;
+use language_model::{LanguageModel, LanguageModelRequest, LanguageModelToolSchemaFormat};
+use project::{Project, Symbol};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use ui::IconName;
+use util::markdown::MarkdownInlineCode;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WorkspaceSymbolsToolInput {
+    /// The name, or part of the name, of the symbols to search for.
+    pub query: String,
+}
+
+const MAX_SYMBOLS: usize = 100;
+
+pub struct WorkspaceSymbolsTool;
+
+impl Tool for WorkspaceSymbolsTool {
+    fn name(&self) -> String {
+        "workspace_symbols".into()
+    }
+
+    fn needs_confirmation(&self, _: &serde_json::Value, _: &App) -> bool {
+        false
+    }
+
+    fn may_perform_edits(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> String {
+        include_str!("./workspace_symbols_tool/description.md").into()
+    }
+
+    fn icon(&self) -> IconName {
+        IconName::ToolSearch
+    }
+
+    fn input_schema(&self, format: LanguageModelToolSchemaFormat) -> Result<serde_json::Value> {
+        json_schema_for::<WorkspaceSymbolsToolInput>(format)
+    }
+
+    fn ui_text(&self, input: &serde_json::Value) -> String {
+        match serde_json::from_value::<WorkspaceSymbolsToolInput>(input.clone()) {
+            Ok(input) => format!(
+                "Search symbols matching {}",
+                MarkdownInlineCode(&input.query)
+            ),
+            Err(_) => "Search symbols".to_string(),
+        }
+    }
+
+    fn run(
+        self: Arc<Self>,
+        input: serde_json::Value,
+        _request: Arc<LanguageModelRequest>,
+        project: Entity<Project>,
+        _action_log: Entity<ActionLog>,
+        _model: Arc<dyn LanguageModel>,
+        _window: Option<AnyWindowHandle>,
+        cx: &mut App,
+    ) -> ToolResult {
+        let input = match serde_json::from_value::<WorkspaceSymbolsToolInput>(input) {
+            Ok(input) => input,
+            Err(err) => return Task::ready(Err(anyhow!(err))).into(),
+        };
+
+        let symbols = project.update(cx, |project, cx| project.symbols(&input.query, cx));
+        cx.spawn(async move |cx| {
+            let mut symbols = symbols.await?;
+            if symbols.is_empty() {
+                return Ok(format!("No symbols found matching `{}`", input.query).into());
+            }
+
+            let mut output = project.read_with(cx, |project, cx| {
+                symbols.sort_by_cached_key(|symbol| {
+                    (
+                        symbol.name.clone(),
+                        symbol_path(symbol, project, cx),
+                        symbol.range.start,
+                    )
+                });
+                symbols
+                    .iter()
+                    .take(MAX_SYMBOLS)
+                    .map(|symbol| format_symbol(symbol, project, cx))
+                    .join("\n")
+            })?;
+            if symbols.len() > MAX_SYMBOLS {
+                output.push_str(&format!(
+                    "\n\nShowing the first {MAX_SYMBOLS} of {} symbols. Use a more specific query to narrow them down.",
+                    symbols.len()
+                ));
+            }
+            Ok(output.into())
+        })
+        .into()
+    }
+}
+
+fn symbol_path(symbol: &Symbol, project: &Project, cx: &App) -> PathBuf {
+    match project.worktree_for_id(symbol.path.worktree_id, cx) {
+        Some(worktree) => Path::new(worktree.read(cx).root_name()).join(&symbol.path.path),
+        None => symbol.path.path.to_path_buf(),
+    }
+}
+
+fn format_symbol(symbol: &Symbol, project: &Project, cx: &App) -> String {
+    let path = symbol_path(symbol, project, cx);
+    format!(
+        "{} ({}) {}:{}",
+        symbol.name,
+        format!("{:?}", symbol.kind).to_lowercase(),
+        path.display(),
+        symbol.range.start.0.row + 1
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use futures::StreamExt as _;
+    use gpui::{AppContext as _, TestAppContext};
+    use language::{FakeLspAdapter, Language, LanguageConfig, LanguageMatcher};
+    use language_model::fake_provider::FakeLanguageModel;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_workspace_symbols_sorts_by_name_and_path(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "src": {
+                    "main.rs": "mod util;\n\nfn main() {\n    util::greet();\n    util::greet();\n}\n",
+                    "util.rs": "pub fn greet() {\n    println!(\"Hello!\");\n}\n",
+                }
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(Arc::new(Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        )));
+        let mut fake_language_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                capabilities: lsp::ServerCapabilities {
+                    workspace_symbol_provider: Some(lsp::OneOf::Left(true)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/src/main.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let _handle = project.update(cx, |project, cx| {
+            project.register_buffer_with_language_servers(&buffer, cx)
+        });
+        let fake_language_server = fake_language_servers.next().await.unwrap();
+        fake_language_server.set_request_handler::<lsp::WorkspaceSymbolRequest, _, _>(
+            |_, _| async move {
+                let symbol = |name: &str, path: &str, line: u32| {
+                    #[allow(deprecated)]
+                    lsp::SymbolInformation {
+                        name: name.into(),
+                        location: lsp::Location::new(
+                            lsp::Url::from_file_path(path).unwrap(),
+                            lsp::Range::new(
+                                lsp::Position::new(line, 0),
+                                lsp::Position::new(line, 1),
+                            ),
+                        ),
+                        kind: lsp::SymbolKind::FUNCTION,
+                        tags: None,
+                        container_name: None,
+                        deprecated: None,
+                    }
+                };
+                Ok(Some(lsp::WorkspaceSymbolResponse::Flat(vec![
+                    symbol("main", path!("/root/src/main.rs"), 2),
+                    symbol("greet", path!("/root/src/util.rs"), 0),
+                    symbol("greet", path!("/root/src/main.rs"), 3),
+                ])))
+            },
+        );
+
+        let action_log = cx.new(|_| ActionLog::new(project.clone()));
+        let model = Arc::new(FakeLanguageModel::default());
+        let result = cx
+            .update(|cx| {
+                Arc::new(WorkspaceSymbolsTool)
+                    .run(
+                        json!({ "query": "e" }),
+                        Arc::default(),
+                        project.clone(),
+                        action_log,
+                        model,
+                        None,
+                        cx,
+                    )
+                    .output
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.content.as_str().unwrap(),
+            "greet (function) root/src/main.rs:4\n\
+             greet (function) root/src/util.rs:1\n\
+             main (function) root/src/main.rs:3"
+        );
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+    }
+}
@@ -350,10 +350,12 @@ impl Database {
                                         .unwrap(),
                                 )),
 
-                                // Old clients do not use abs path, entry ids or head_commit_details.
+                                // Old clients do not use abs path, entry ids, head_commit_details
+                                // or in_progress_operation.
                                 abs_path: ActiveValue::set(String::new()),
                                 entry_ids: ActiveValue::set("[]".into()),
                                 head_commit_details: ActiveValue::set(None),
+                                in_progress_operation: ActiveValue::set(None),
                             }
                         }),
                     )
@@ -502,6 +504,7 @@ impl Database {
                 current_merge_conflicts: ActiveValue::Set(Some(
                     serde_json::to_string(&update.current_merge_conflicts).unwrap(),
                 )),
+                in_progress_operation: ActiveValue::Set(update.in_progress_operation),
             })
             .on_conflict(
                 OnConflict::columns([
@@ -515,6 +518,7 @@ impl Database {
                     project_repository::Column::AbsPath,
                     project_repository::Column::CurrentMergeConflicts,
                     project_repository::Column::HeadCommitDetails,
+                    project_repository::Column::InProgressOperation,
                 ])
                 .to_owned(),
             )
@@ -984,6 +988,7 @@ impl Database {
                         current_merge_conflicts,
                         branch_summary,
                         head_commit_details,
+                        in_progress_operation: db_repository_entry.in_progress_operation,
                         scan_id: db_repository_entry.scan_id as u64,
                         is_last_update: true,
                     });
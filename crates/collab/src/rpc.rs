@@ -317,6 +317,14 @@ impl Server {
             .add_request_handler(forward_find_search_candidates_request)
             .add_request_handler(forward_read_only_project_request::<proto::GetDocumentHighlights>)
             .add_request_handler(forward_read_only_project_request::<proto::GetDocumentSymbols>)
+            .add_request_handler(forward_read_only_project_request::<proto::PrepareCallHierarchy>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetIncomingCalls>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetOutgoingCalls>)
+            .add_request_handler(forward_read_only_project_request::<proto::PrepareTypeHierarchy>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetSupertypes>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetSubtypes>)
+            .add_request_handler(forward_read_only_project_request::<proto::ResolveDocumentLink>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetSelectionRanges>)
             .add_request_handler(forward_read_only_project_request::<proto::GetProjectSymbols>)
             .add_request_handler(forward_read_only_project_request::<proto::OpenBufferForSymbol>)
             .add_request_handler(forward_read_only_project_request::<proto::OpenBufferById>)
@@ -381,6 +389,9 @@ impl Server {
             .add_request_handler(update_buffer)
             .add_message_handler(broadcast_project_message_from_host::<proto::RefreshInlayHints>)
             .add_message_handler(broadcast_project_message_from_host::<proto::RefreshCodeLens>)
+            .add_message_handler(
+                broadcast_project_message_from_host::<proto::RefreshSemanticTokens>,
+            )
             .add_message_handler(broadcast_project_message_from_host::<proto::UpdateBufferFile>)
             .add_message_handler(broadcast_project_message_from_host::<proto::BufferReloaded>)
             .add_message_handler(broadcast_project_message_from_host::<proto::BufferSaved>)
@@ -448,6 +459,24 @@ impl Server {
             .add_request_handler(forward_mutating_project_request::<proto::GitCreateBranch>)
             .add_request_handler(forward_mutating_project_request::<proto::GitChangeBranch>)
             .add_request_handler(forward_mutating_project_request::<proto::CheckForPushedCommits>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitStashList>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitStashPush>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitStashApply>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitStashPop>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitStashDrop>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitLog>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitCherryPick>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRevert>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRebase>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitContinueOperation>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitAbortOperation>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitGetTags>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitCreateTag>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitDeleteTag>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitCheckoutTag>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitWorktreeList>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitWorktreeAdd>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitWorktreeRemove>)
             .add_message_handler(broadcast_project_message_from_host::<proto::AdvertiseContexts>)
             .add_message_handler(update_context);
 
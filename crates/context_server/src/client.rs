@@ -32,6 +32,7 @@ pub const INTERNAL_ERROR: i32 = -32603;
 
 type ResponseHandler = Box<dyn Send + FnOnce(Result<String, Error>)>;
 type NotificationHandler = Box<dyn Send + FnMut(Value, AsyncApp)>;
+type RequestHandler = Box<dyn Send + FnMut(Value, AsyncApp) -> Task<Result<Value>>>;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -46,6 +47,7 @@ pub(crate) struct Client {
     outbound_tx: channel::Sender<String>,
     name: Arc<str>,
     notification_handlers: Arc<Mutex<HashMap<&'static str, NotificationHandler>>>,
+    request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
     response_handlers: Arc<Mutex<Option<HashMap<RequestId, ResponseHandler>>>>,
     #[allow(clippy::type_complexity)]
     #[allow(dead_code)]
@@ -78,6 +80,24 @@ struct Request<'a, T> {
     params: T,
 }
 
+#[derive(Deserialize)]
+struct AnyRequest {
+    id: RequestId,
+    method: String,
+    #[serde(default)]
+    params: Option<Value>,
+}
+
+#[derive(Serialize)]
+struct OutgoingResponse {
+    jsonrpc: &'static str,
+    id: RequestId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<Error>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct AnyResponse<'a> {
     jsonrpc: &'a str,
@@ -124,6 +144,8 @@ struct AnyNotification<'a> {
 
 #[derive(Debug, Serialize, Deserialize)]
 struct Error {
+    #[serde(default)]
+    code: i32,
     message: String,
 }
 
@@ -173,17 +195,27 @@ impl Client {
 
         let notification_handlers =
             Arc::new(Mutex::new(HashMap::<_, NotificationHandler>::default()));
+        let request_handlers = Arc::new(Mutex::new(HashMap::<_, RequestHandler>::default()));
         let response_handlers =
             Arc::new(Mutex::new(Some(HashMap::<_, ResponseHandler>::default())));
 
         let receive_input_task = cx.spawn({
             let notification_handlers = notification_handlers.clone();
+            let request_handlers = request_handlers.clone();
             let response_handlers = response_handlers.clone();
             let transport = transport.clone();
+            let outbound_tx = outbound_tx.clone();
             async move |cx| {
-                Self::handle_input(transport, notification_handlers, response_handlers, cx)
-                    .log_err()
-                    .await
+                Self::handle_input(
+                    transport,
+                    notification_handlers,
+                    request_handlers,
+                    response_handlers,
+                    outbound_tx,
+                    cx,
+                )
+                .log_err()
+                .await
             }
         });
         let receive_err_task = cx.spawn({
@@ -209,6 +241,7 @@ impl Client {
         Ok(Self {
             server_id,
             notification_handlers,
+            request_handlers,
             response_handlers,
             name: server_name,
             next_id: Default::default(),
@@ -223,19 +256,64 @@ impl Client {
     /// Handles input from the server's stdout.
     ///
     /// This function continuously reads lines from the provided stdout stream,
-    /// parses them as JSON-RPC responses or notifications, and dispatches them
-    /// to the appropriate handlers. It processes both responses (which are matched
-    /// to pending requests) and notifications (which trigger registered handlers).
+    /// parses them as JSON-RPC requests, responses or notifications, and dispatches them
+    /// to the appropriate handlers. It processes requests (which are answered by registered
+    /// handlers), responses (which are matched to pending requests) and notifications
+    /// (which trigger registered handlers).
     async fn handle_input(
         transport: Arc<dyn Transport>,
         notification_handlers: Arc<Mutex<HashMap<&'static str, NotificationHandler>>>,
+        request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
         response_handlers: Arc<Mutex<Option<HashMap<RequestId, ResponseHandler>>>>,
+        outbound_tx: channel::Sender<String>,
         cx: &mut AsyncApp,
     ) -> anyhow::Result<()> {
         let mut receiver = transport.receive();
 
         while let Some(message) = receiver.next().await {
-            if let Ok(response) = serde_json::from_str::<AnyResponse>(&message) {
+            if let Ok(AnyRequest {
+                id, method, params, ..
+            }) = serde_json::from_str::<AnyRequest>(&message)
+            {
+                let response = request_handlers
+                    .lock()
+                    .get_mut(method.as_str())
+                    .map(|handler| handler(params.unwrap_or(Value::Null), cx.clone()));
+                let outbound_tx = outbound_tx.clone();
+                cx.background_spawn(
+                    async move {
+                        let (result, error) = match response {
+                            Some(response) => match response.await {
+                                Ok(result) => (Some(result), None),
+                                Err(error) => (
+                                    None,
+                                    Some(Error {
+                                        code: INTERNAL_ERROR,
+                                        message: format!("{error:#}"),
+                                    }),
+                                ),
+                            },
+                            None => (
+                                None,
+                                Some(Error {
+                                    code: METHOD_NOT_FOUND,
+                                    message: format!("method not found: {method}"),
+                                }),
+                            ),
+                        };
+                        let response = serde_json::to_string(&OutgoingResponse {
+                            jsonrpc: JSON_RPC_VERSION,
+                            id,
+                            result,
+                            error,
+                        })?;
+                        outbound_tx.send(response).await?;
+                        anyhow::Ok(())
+                    }
+                    .log_err(),
+                )
+                .detach();
+            } else if let Ok(response) = serde_json::from_str::<AnyResponse>(&message) {
                 if let Some(handlers) = response_handlers.lock().as_mut() {
                     if let Some(handler) = handlers.remove(&response.id) {
                         handler(Ok(message.to_string()));
@@ -367,7 +445,6 @@ impl Client {
         Ok(())
     }
 
-    #[allow(unused)]
     pub fn on_notification<F>(&self, method: &'static str, f: F)
     where
         F: 'static + Send + FnMut(Value, AsyncApp),
@@ -376,6 +453,14 @@ impl Client {
             .lock()
             .insert(method, Box::new(f));
     }
+
+    /// Registers a handler answering the requests the context server sends with the given method.
+    pub fn on_request<F>(&self, method: &'static str, f: F)
+    where
+        F: 'static + Send + FnMut(Value, AsyncApp) -> Task<Result<Value>>,
+    {
+        self.request_handlers.lock().insert(method, Box::new(f));
+    }
 }
 
 impl fmt::Display for ContextServerId {
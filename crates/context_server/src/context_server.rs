@@ -12,10 +12,14 @@ use std::sync::Arc;
 use anyhow::Result;
 use client::Client;
 use collections::HashMap;
-use gpui::AsyncApp;
-use parking_lot::RwLock;
+use gpui::{AsyncApp, Task};
+use http_client::HttpClient;
+use parking_lot::{Mutex, RwLock};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use transport::HttpTransport;
+use types::Request;
 use util::redact::should_redact;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -51,14 +55,54 @@ impl std::fmt::Debug for ContextServerCommand {
     }
 }
 
+/// The HTTP endpoint of a context server that is not run as a local process.
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq, JsonSchema)]
+pub struct ContextServerEndpoint {
+    /// The URL of the server's MCP endpoint.
+    pub url: String,
+    /// Headers to send with every request, e.g. for authentication.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// The transport the server speaks.
+    #[serde(default)]
+    pub transport: HttpTransportKind,
+}
+
+impl std::fmt::Debug for ContextServerEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Headers usually carry credentials, so only their names are shown.
+        let header_names = self.headers.keys().collect::<Vec<_>>();
+
+        f.debug_struct("ContextServerEndpoint")
+            .field("url", &self.url)
+            .field("headers", &header_names)
+            .field("transport", &self.transport)
+            .finish()
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HttpTransportKind {
+    /// The streamable HTTP transport, where every message is POSTed to the endpoint.
+    #[default]
+    StreamableHttp,
+    /// The legacy HTTP+SSE transport, where server messages arrive over a server-sent events stream.
+    Sse,
+}
+
 enum ContextServerTransport {
     Stdio(ContextServerCommand),
+    Http(ContextServerEndpoint, Arc<dyn HttpClient>),
     Custom(Arc<dyn crate::transport::Transport>),
 }
 
+type RequestHandler = Arc<dyn Send + Sync + Fn(Value, AsyncApp) -> Task<Result<Value>>>;
+
 pub struct ContextServer {
     id: ContextServerId,
     client: RwLock<Option<Arc<crate::protocol::InitializedContextServerProtocol>>>,
+    request_handlers: Mutex<HashMap<&'static str, RequestHandler>>,
     configuration: ContextServerTransport,
 }
 
@@ -67,14 +111,29 @@ impl ContextServer {
         Self {
             id,
             client: RwLock::new(None),
+            request_handlers: Mutex::default(),
             configuration: ContextServerTransport::Stdio(command),
         }
     }
 
+    pub fn http(
+        id: ContextServerId,
+        endpoint: ContextServerEndpoint,
+        http_client: Arc<dyn HttpClient>,
+    ) -> Self {
+        Self {
+            id,
+            client: RwLock::new(None),
+            request_handlers: Mutex::default(),
+            configuration: ContextServerTransport::Http(endpoint, http_client),
+        }
+    }
+
     pub fn new(id: ContextServerId, transport: Arc<dyn crate::transport::Transport>) -> Self {
         Self {
             id,
             client: RwLock::new(None),
+            request_handlers: Mutex::default(),
             configuration: ContextServerTransport::Custom(transport),
         }
     }
@@ -87,6 +146,36 @@ impl ContextServer {
         self.client.read().clone()
     }
 
+    /// Registers a handler answering the requests of the given type that the server sends to us.
+    ///
+    /// Handlers must be registered before the server is started, as the client capabilities that
+    /// are advertised to the server are derived from them.
+    pub fn handle_request<T: Request>(
+        &self,
+        handler: impl 'static + Send + Sync + Fn(T::Params, AsyncApp) -> Task<Result<T::Response>>,
+    ) {
+        let handler = Arc::new(handler);
+        self.request_handlers.lock().insert(
+            T::METHOD,
+            Arc::new(move |params, cx| {
+                // Servers may send an empty object for requests without parameters.
+                let params = serde_json::from_value::<T::Params>(params)
+                    .or_else(|error| serde_json::from_value(Value::Null).map_err(|_| error));
+                let params = match params {
+                    Ok(params) => params,
+                    Err(error) => {
+                        return Task::ready(Err(anyhow::anyhow!(
+                            "invalid {} request: {error}",
+                            T::METHOD
+                        )));
+                    }
+                };
+                let response = handler(params, cx.clone());
+                cx.background_spawn(async move { Ok(serde_json::to_value(response.await?)?) })
+            }),
+        );
+    }
+
     pub async fn start(self: Arc<Self>, cx: &AsyncApp) -> Result<()> {
         let client = match &self.configuration {
             ContextServerTransport::Stdio(command) => Client::stdio(
@@ -98,6 +187,16 @@ impl ContextServer {
                 },
                 cx.clone(),
             )?,
+            ContextServerTransport::Http(endpoint, http_client) => Client::new(
+                client::ContextServerId(self.id.0.clone()),
+                endpoint.url.clone().into(),
+                Arc::new(HttpTransport::new(
+                    endpoint.clone(),
+                    http_client.clone(),
+                    cx,
+                )?),
+                cx.clone(),
+            )?,
             ContextServerTransport::Custom(transport) => Client::new(
                 client::ContextServerId(self.id.0.clone()),
                 self.id().0,
@@ -110,12 +209,28 @@ impl ContextServer {
 
     async fn initialize(&self, client: Client) -> Result<()> {
         log::info!("starting context server {}", self.id);
+        let request_handlers = self.request_handlers.lock().clone();
+        let capabilities = types::ClientCapabilities {
+            experimental: None,
+            sampling: request_handlers
+                .contains_key(types::requests::CreateMessage::METHOD)
+                .then(|| serde_json::json!({})),
+            roots: request_handlers
+                .contains_key(types::requests::ListRoots::METHOD)
+                .then_some(types::RootsCapabilities {
+                    list_changed: Some(true),
+                }),
+        };
+        for (method, handler) in request_handlers {
+            client.on_request(method, move |params, cx| handler(params, cx));
+        }
+
         let protocol = crate::protocol::ModelContextProtocol::new(client);
         let client_info = types::Implementation {
             name: "Zed".to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
         };
-        let initialized_protocol = protocol.initialize(client_info).await?;
+        let initialized_protocol = protocol.initialize(client_info, capabilities).await?;
 
         log::debug!(
             "context server {} initialized: {:?}",
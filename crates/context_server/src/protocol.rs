@@ -6,6 +6,7 @@
 //! of messages.
 
 use anyhow::Result;
+use gpui::AsyncApp;
 
 use crate::client::Client;
 use crate::types::{self, Notification, Request};
@@ -29,14 +30,11 @@ impl ModelContextProtocol {
     pub async fn initialize(
         self,
         client_info: types::Implementation,
+        capabilities: types::ClientCapabilities,
     ) -> Result<InitializedContextServerProtocol> {
         let params = types::InitializeParams {
             protocol_version: types::ProtocolVersion(types::LATEST_PROTOCOL_VERSION.to_string()),
-            capabilities: types::ClientCapabilities {
-                experimental: None,
-                sampling: None,
-                roots: None,
-            },
+            capabilities,
             meta: None,
             client_info,
         };
@@ -98,4 +96,17 @@ impl InitializedContextServerProtocol {
     pub fn notify<T: Notification>(&self, params: T::Params) -> Result<()> {
         self.inner.notify(T::METHOD, params)
     }
+
+    /// Registers a handler for notifications of the given type, replacing any previous one.
+    pub fn on_notification<T: Notification>(
+        &self,
+        mut f: impl 'static + Send + FnMut(T::Params, AsyncApp),
+    ) {
+        self.inner.on_notification(T::METHOD, move |params, cx| {
+            match serde_json::from_value::<T::Params>(params) {
+                Ok(params) => f(params, cx),
+                Err(error) => log::error!("invalid {} notification: {error}", T::METHOD),
+            }
+        });
+    }
 }
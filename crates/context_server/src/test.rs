@@ -1,10 +1,20 @@
 use anyhow::Context as _;
 use collections::HashMap;
-use futures::{Stream, StreamExt as _, lock::Mutex};
+use futures::{
+    AsyncReadExt as _, Stream, StreamExt as _, TryStreamExt as _, channel::oneshot, lock::Mutex,
+};
 use gpui::BackgroundExecutor;
-use std::{pin::Pin, sync::Arc};
+use http_client::{AsyncBody, FakeHttpClient, HttpClient, Method, Request, Response, StatusCode};
+use std::{
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering::SeqCst},
+    },
+};
 
 use crate::{
+    HttpTransportKind,
     transport::Transport,
     types::{Implementation, InitializeResponse, ProtocolVersion, ServerCapabilities},
 };
@@ -19,7 +29,7 @@ pub fn create_fake_transport(
     })
 }
 
-fn create_initialize_response(server_name: String) -> InitializeResponse {
+pub fn create_initialize_response(server_name: String) -> InitializeResponse {
     InitializeResponse {
         protocol_version: ProtocolVersion(crate::types::LATEST_PROTOCOL_VERSION.to_string()),
         server_info: Implementation {
@@ -34,6 +44,8 @@ fn create_initialize_response(server_name: String) -> InitializeResponse {
 pub struct FakeTransport {
     request_handlers:
         HashMap<&'static str, Arc<dyn Fn(serde_json::Value) -> serde_json::Value + Send + Sync>>,
+    client_requests: parking_lot::Mutex<HashMap<u64, oneshot::Sender<serde_json::Value>>>,
+    next_client_request_id: AtomicU64,
     tx: futures::channel::mpsc::UnboundedSender<String>,
     rx: Arc<Mutex<futures::channel::mpsc::UnboundedReceiver<String>>>,
     executor: BackgroundExecutor,
@@ -44,6 +56,8 @@ impl FakeTransport {
         let (tx, rx) = futures::channel::mpsc::unbounded();
         Self {
             request_handlers: Default::default(),
+            client_requests: Default::default(),
+            next_client_request_id: AtomicU64::new(0),
             tx,
             rx: Arc::new(Mutex::new(rx)),
             executor,
@@ -66,6 +80,32 @@ impl FakeTransport {
         );
         self
     }
+
+    /// Sends a request to the client, like the server would, and waits for its response.
+    pub async fn request_client<T: crate::types::Request>(
+        &self,
+        params: T::Params,
+    ) -> anyhow::Result<T::Response> {
+        let id = self.next_client_request_id.fetch_add(1, SeqCst);
+        let (tx, rx) = oneshot::channel();
+        self.client_requests.lock().insert(id, tx);
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": T::METHOD,
+            "params": params,
+        });
+        self.tx
+            .unbounded_send(request.to_string())
+            .context("sending a message")?;
+
+        let response = rx.await?;
+        if let Some(error) = response.get("error") {
+            anyhow::bail!("client responded with an error: {error}");
+        }
+        let result = response.get("result").cloned().unwrap_or_default();
+        Ok(serde_json::from_value(result)?)
+    }
 }
 
 #[async_trait::async_trait]
@@ -89,6 +129,8 @@ impl Transport for FakeTransport {
                 } else {
                     log::debug!("No handler registered for MCP request '{method}'");
                 }
+            } else if let Some(tx) = self.client_requests.lock().remove(&id) {
+                tx.send(msg).ok();
             }
         }
         Ok(())
@@ -116,3 +158,135 @@ impl Transport for FakeTransport {
         Box::pin(futures::stream::empty())
     }
 }
+
+pub const FAKE_HTTP_SESSION_ID: &str = "fake-session";
+const FAKE_HTTP_MESSAGES_PATH: &str = "/messages";
+
+/// Creates an HTTP client that answers like a context server reachable over the given HTTP
+/// transport, so that the HTTP transports can be exercised without a network.
+pub fn create_fake_http_server(
+    name: impl Into<String>,
+    kind: HttpTransportKind,
+) -> Arc<dyn HttpClient> {
+    let name = name.into();
+    FakeHttpServer::new(kind)
+        .on_request::<crate::types::requests::Initialize>(move |_params| {
+            create_initialize_response(name.clone())
+        })
+        .into_http_client()
+}
+
+pub struct FakeHttpServer {
+    kind: HttpTransportKind,
+    request_handlers:
+        HashMap<&'static str, Arc<dyn Fn(serde_json::Value) -> serde_json::Value + Send + Sync>>,
+}
+
+impl FakeHttpServer {
+    pub fn new(kind: HttpTransportKind) -> Self {
+        Self {
+            kind,
+            request_handlers: Default::default(),
+        }
+    }
+
+    pub fn on_request<T: crate::types::Request>(
+        mut self,
+        handler: impl Fn(T::Params) -> T::Response + Send + Sync + 'static,
+    ) -> Self {
+        self.request_handlers.insert(
+            T::METHOD,
+            Arc::new(move |value| {
+                let params = value.get("params").expect("Missing parameters").clone();
+                let params: T::Params =
+                    serde_json::from_value(params).expect("Invalid parameters received");
+                let response = handler(params);
+                serde_json::to_value(response).unwrap()
+            }),
+        );
+        self
+    }
+
+    pub fn into_http_client(self) -> Arc<dyn HttpClient> {
+        let server = Arc::new(self);
+        let event_stream = Arc::new(parking_lot::Mutex::new(None));
+        FakeHttpClient::create(move |request| {
+            let server = server.clone();
+            let event_stream = event_stream.clone();
+            async move { server.handle_request(request, &event_stream).await }
+        })
+    }
+
+    async fn handle_request(
+        &self,
+        request: Request<AsyncBody>,
+        event_stream: &parking_lot::Mutex<Option<smol::channel::Sender<String>>>,
+    ) -> anyhow::Result<Response<AsyncBody>> {
+        match *request.method() {
+            Method::GET if self.kind == HttpTransportKind::Sse => {
+                let (tx, rx) = smol::channel::unbounded::<String>();
+                tx.try_send(format!(
+                    "event: endpoint\ndata: {FAKE_HTTP_MESSAGES_PATH}\n\n"
+                ))?;
+                *event_stream.lock() = Some(tx);
+                let body = rx
+                    .map(|event| Ok::<_, std::io::Error>(event.into_bytes()))
+                    .into_async_read();
+                Ok(Response::builder()
+                    .header("Content-Type", "text/event-stream")
+                    .body(AsyncBody::from_reader(body))?)
+            }
+            Method::POST => {
+                let mut body = String::new();
+                request.into_body().read_to_string(&mut body).await?;
+                let message = serde_json::from_str::<serde_json::Value>(&body)?;
+                // Notifications and responses to requests of the server need no reply.
+                let (Some(id), Some(method)) = (
+                    message.get("id").cloned(),
+                    message.get("method").and_then(|method| method.as_str()),
+                ) else {
+                    return empty_response(StatusCode::ACCEPTED);
+                };
+                let response = match self.request_handlers.get(method) {
+                    Some(handler) => serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "result": handler(message.clone()),
+                    }),
+                    None => serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": {
+                            "code": -32601,
+                            "message": format!("Method not found: {method}"),
+                        },
+                    }),
+                };
+
+                match self.kind {
+                    HttpTransportKind::StreamableHttp => Ok(Response::builder()
+                        .header("Content-Type", "application/json")
+                        .header("Mcp-Session-Id", FAKE_HTTP_SESSION_ID)
+                        .body(response.to_string().into())?),
+                    HttpTransportKind::Sse => {
+                        let tx = event_stream
+                            .lock()
+                            .clone()
+                            .context("no event stream is connected")?;
+                        tx.send(format!("event: message\ndata: {response}\n\n"))
+                            .await?;
+                        empty_response(StatusCode::ACCEPTED)
+                    }
+                }
+            }
+            Method::DELETE => empty_response(StatusCode::OK),
+            _ => empty_response(StatusCode::METHOD_NOT_ALLOWED),
+        }
+    }
+}
+
+fn empty_response(status: StatusCode) -> anyhow::Result<Response<AsyncBody>> {
+    Ok(Response::builder()
+        .status(status)
+        .body(AsyncBody::empty())?)
+}
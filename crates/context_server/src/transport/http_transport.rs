@@ -0,0 +1,317 @@
+use std::pin::Pin;
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use collections::HashMap;
+use futures::io::BufReader;
+use futures::{AsyncBufReadExt as _, AsyncRead, AsyncReadExt as _, Stream, StreamExt as _};
+use gpui::{AsyncApp, BackgroundExecutor, Task};
+use http_client::http::request::Builder;
+use http_client::{AsyncBody, HttpClient, Method, Request, StatusCode, Url};
+use parking_lot::Mutex;
+use postage::{prelude::Stream as _, watch};
+use serde_json::Value;
+use smol::channel;
+use util::ResultExt as _;
+
+use crate::transport::Transport;
+use crate::{ContextServerEndpoint, HttpTransportKind};
+
+const SESSION_ID_HEADER: &str = "Mcp-Session-Id";
+
+/// A transport to a context server behind an HTTP endpoint, speaking either the streamable HTTP
+/// transport or the legacy HTTP+SSE transport of the Model Context Protocol.
+pub struct HttpTransport {
+    kind: HttpTransportKind,
+    http_client: Arc<dyn HttpClient>,
+    headers: HashMap<String, String>,
+    /// The URL that messages are POSTed to. With the SSE transport, it is only known once the
+    /// server announced it on the event stream.
+    message_url: watch::Receiver<Option<Url>>,
+    session_id: Mutex<Option<String>>,
+    inbound_tx: channel::Sender<String>,
+    inbound_rx: channel::Receiver<String>,
+    err_rx: channel::Receiver<String>,
+    executor: BackgroundExecutor,
+    _event_stream: Option<Task<()>>,
+}
+
+impl HttpTransport {
+    pub fn new(
+        endpoint: ContextServerEndpoint,
+        http_client: Arc<dyn HttpClient>,
+        cx: &AsyncApp,
+    ) -> Result<Self> {
+        let url = Url::parse(&endpoint.url)
+            .with_context(|| format!("invalid context server URL {:?}", endpoint.url))?;
+        let (inbound_tx, inbound_rx) = channel::unbounded::<String>();
+        let (err_tx, err_rx) = channel::unbounded::<String>();
+        let (mut message_url_tx, message_url_rx) = watch::channel::<Option<Url>>();
+        let executor = cx.background_executor().clone();
+
+        let event_stream = match endpoint.transport {
+            HttpTransportKind::StreamableHttp => {
+                *message_url_tx.borrow_mut() = Some(url);
+                None
+            }
+            HttpTransportKind::Sse => {
+                let request = with_headers(Request::builder(), &endpoint.headers)
+                    .method(Method::GET)
+                    .uri(url.as_str())
+                    .header("Accept", "text/event-stream")
+                    .body(AsyncBody::empty())?;
+                let http_client = http_client.clone();
+                let inbound_tx = inbound_tx.clone();
+                Some(executor.spawn(async move {
+                    let result = Self::handle_event_stream(
+                        request,
+                        url,
+                        http_client,
+                        message_url_tx,
+                        inbound_tx,
+                    )
+                    .await;
+                    let error = match result {
+                        Ok(()) => "context server closed the event stream".to_string(),
+                        Err(error) => format!("{error:#}"),
+                    };
+                    err_tx.send(error).await.ok();
+                }))
+            }
+        };
+
+        Ok(Self {
+            kind: endpoint.transport,
+            http_client,
+            headers: endpoint.headers,
+            message_url: message_url_rx,
+            session_id: Mutex::new(None),
+            inbound_tx,
+            inbound_rx,
+            err_rx,
+            executor,
+            _event_stream: event_stream,
+        })
+    }
+
+    /// Reads the event stream of the legacy SSE transport, which first announces the URL to POST
+    /// messages to, and then carries all messages of the server.
+    async fn handle_event_stream(
+        request: Request<AsyncBody>,
+        url: Url,
+        http_client: Arc<dyn HttpClient>,
+        mut message_url_tx: watch::Sender<Option<Url>>,
+        inbound_tx: channel::Sender<String>,
+    ) -> Result<()> {
+        let response = http_client.send(request).await?;
+        anyhow::ensure!(
+            response.status().is_success(),
+            "failed to connect to the context server's event stream: {}",
+            response.status()
+        );
+        read_server_sent_events(response.into_body(), |event| {
+            match event.event.as_deref() {
+                Some("endpoint") => match resolve_message_url(&url, &event.data) {
+                    Ok(message_url) => *message_url_tx.borrow_mut() = Some(message_url),
+                    Err(error) => log::error!("invalid context server endpoint: {error:#}"),
+                },
+                None | Some("message") => {
+                    inbound_tx.try_send(event.data).ok();
+                }
+                Some(_) => {}
+            };
+        })
+        .await
+    }
+
+    async fn message_url(&self) -> Result<Url> {
+        let mut message_url = self.message_url.clone();
+        loop {
+            if let Some(url) = message_url.borrow().clone() {
+                return Ok(url);
+            }
+            message_url
+                .recv()
+                .await
+                .context("context server closed the event stream")?;
+        }
+    }
+
+    fn request(&self, method: Method, url: &Url) -> Builder {
+        let mut request = with_headers(Request::builder(), &self.headers)
+            .method(method)
+            .uri(url.as_str());
+        if let Some(session_id) = self.session_id.lock().as_ref() {
+            request = request.header(SESSION_ID_HEADER, session_id);
+        }
+        request
+    }
+}
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn send(&self, message: String) -> Result<()> {
+        let url = self.message_url().await?;
+        let request = self
+            .request(Method::POST, &url)
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json, text/event-stream")
+            .body(AsyncBody::from(message))?;
+        let response = self.http_client.send(request).await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let mut body = String::new();
+            response.into_body().read_to_string(&mut body).await.ok();
+            anyhow::bail!("context server responded with {status}: {body}");
+        }
+        if let Some(session_id) = response
+            .headers()
+            .get(SESSION_ID_HEADER)
+            .and_then(|session_id| session_id.to_str().ok())
+        {
+            *self.session_id.lock() = Some(session_id.to_string());
+        }
+        // Notifications and responses are only acknowledged, and with the SSE transport the
+        // replies to requests arrive on the event stream.
+        if status == StatusCode::ACCEPTED || self.kind == HttpTransportKind::Sse {
+            return Ok(());
+        }
+
+        let is_event_stream = response
+            .headers()
+            .get("Content-Type")
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| content_type.starts_with("text/event-stream"));
+        let inbound_tx = self.inbound_tx.clone();
+        let body = response.into_body();
+        // Read the reply in the background, so that a long running request does not hold up
+        // messages sent after it.
+        self.executor
+            .spawn(async move {
+                let result = if is_event_stream {
+                    read_server_sent_events(body, |event| {
+                        inbound_tx.try_send(event.data).ok();
+                    })
+                    .await
+                } else {
+                    read_json_messages(body, &inbound_tx).await
+                };
+                result
+                    .context("failed to read context server response")
+                    .log_err();
+            })
+            .detach();
+        Ok(())
+    }
+
+    fn receive(&self) -> Pin<Box<dyn Stream<Item = String> + Send>> {
+        Box::pin(self.inbound_rx.clone())
+    }
+
+    fn receive_err(&self) -> Pin<Box<dyn Stream<Item = String> + Send>> {
+        Box::pin(self.err_rx.clone())
+    }
+}
+
+impl Drop for HttpTransport {
+    fn drop(&mut self) {
+        // Let the server know that the session ended, so that it can release its resources.
+        let Some(url) = self.message_url.borrow().clone() else {
+            return;
+        };
+        if self.session_id.lock().is_none() {
+            return;
+        }
+        let Some(request) = self
+            .request(Method::DELETE, &url)
+            .body(AsyncBody::empty())
+            .log_err()
+        else {
+            return;
+        };
+        let http_client = self.http_client.clone();
+        self.executor
+            .spawn(async move {
+                http_client.send(request).await.log_err();
+            })
+            .detach();
+    }
+}
+
+fn with_headers(mut request: Builder, headers: &HashMap<String, String>) -> Builder {
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    request
+}
+
+struct ServerSentEvent {
+    event: Option<String>,
+    data: String,
+}
+
+/// Reads server-sent events until the stream ends.
+/// Resolves the endpoint announced on the SSE event stream against the configured URL. Messages
+/// are sent with the configured headers, so endpoints on other origins are rejected.
+fn resolve_message_url(url: &Url, endpoint: &str) -> Result<Url> {
+    let message_url = url.join(endpoint)?;
+    anyhow::ensure!(
+        message_url.origin() == url.origin(),
+        "endpoint {message_url} is not on the same origin as {url}"
+    );
+    Ok(message_url)
+}
+
+async fn read_server_sent_events(
+    body: impl AsyncRead + Unpin,
+    mut on_event: impl FnMut(ServerSentEvent),
+) -> Result<()> {
+    let mut lines = BufReader::new(body).lines();
+    let mut event = None;
+    let mut data = Vec::new();
+    while let Some(line) = lines.next().await {
+        let line = line?;
+        if line.is_empty() {
+            if !data.is_empty() {
+                on_event(ServerSentEvent {
+                    event: event.take(),
+                    data: data.join("\n"),
+                });
+                data.clear();
+            }
+            event = None;
+            continue;
+        }
+
+        let (field, value) = line.split_once(':').unwrap_or((line.as_str(), ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => event = Some(value.to_string()),
+            "data" => data.push(value.to_string()),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Reads a JSON reply, which is either a single message or a batch of them.
+async fn read_json_messages(
+    mut body: impl AsyncRead + Unpin,
+    inbound_tx: &channel::Sender<String>,
+) -> Result<()> {
+    let mut text = String::new();
+    body.read_to_string(&mut text).await?;
+    if text.trim().is_empty() {
+        return Ok(());
+    }
+    if let Ok(Value::Array(messages)) = serde_json::from_str::<Value>(&text) {
+        for message in messages {
+            inbound_tx.send(message.to_string()).await?;
+        }
+    } else {
+        inbound_tx.send(text).await?;
+    }
+    Ok(())
+}
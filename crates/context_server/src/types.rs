@@ -75,6 +75,12 @@ pub mod requests {
         ListResourceTemplatesResponse
     );
     request!("roots/list", ListRoots, (), ListRootsResponse);
+    request!(
+        "sampling/createMessage",
+        CreateMessage,
+        CreateMessageRequest,
+        CreateMessageResult
+    );
 }
 
 pub trait Request {
@@ -310,7 +316,7 @@ pub struct CreateMessageRequest {
     pub metadata: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateMessageResult {
     pub role: Role,
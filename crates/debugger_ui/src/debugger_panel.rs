@@ -4,9 +4,10 @@ use crate::session::running::RunningState;
 use crate::session::running::breakpoint_list::BreakpointList;
 use crate::{
     ClearAllBreakpoints, Continue, CopyDebugAdapterArguments, Detach, FocusBreakpointList,
-    FocusConsole, FocusFrames, FocusLoadedSources, FocusModules, FocusTerminal, FocusVariables,
-    NewProcessModal, NewProcessMode, Pause, RerunSession, StepInto, StepOut, StepOver, Stop,
-    ToggleExpandItem, ToggleSessionPicker, ToggleThreadPicker, persistence, spawn_task_or_modal,
+    FocusConsole, FocusDisassembly, FocusFrames, FocusLoadedSources, FocusMemory, FocusModules,
+    FocusTerminal, FocusVariables, NewProcessModal, NewProcessMode, Pause, RerunSession, StepInto,
+    StepOut, StepOver, Stop, ToggleExpandItem, ToggleSessionPicker, ToggleThreadPicker,
+    persistence, spawn_task_or_modal,
 };
 use anyhow::{Context as _, Result, anyhow};
 use collections::IndexMap;
@@ -28,7 +29,7 @@ use text::ToPoint as _;
 use itertools::Itertools as _;
 use language::Buffer;
 use project::debugger::session::{Session, SessionQuirks, SessionState, SessionStateEvent};
-use project::{DebugScenarioContext, Fs, ProjectPath, TaskSourceKind, WorktreeId};
+use project::{DebugScenarioContext, Fs, ProjectPath, TaskSourceKind, Worktree, WorktreeId};
 use project::{Project, debugger::session::ThreadStatus};
 use rpc::proto::{self};
 use settings::Settings;
@@ -40,7 +41,7 @@ use util::{ResultExt, debug_panic, maybe};
 use workspace::SplitDirection;
 use workspace::item::SaveOptions;
 use workspace::{
-    Item, Pane, Workspace,
+    Item, Pane, Workspace, WorkspaceId,
     dock::{DockPosition, Panel, PanelEvent},
 };
 use zed_actions::ToggleFocus;
@@ -67,6 +68,7 @@ pub struct DebugPanel {
     active_session: Option<Entity<DebugSession>>,
     project: Entity<Project>,
     workspace: WeakEntity<Workspace>,
+    database_id: Option<WorkspaceId>,
     focus_handle: FocusHandle,
     context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
     debug_scenario_scheduled_last: bool,
@@ -114,6 +116,7 @@ impl DebugPanel {
                 ),
                 project,
                 workspace: workspace.weak_handle(),
+                database_id: workspace.database_id(),
                 context_menu: None,
                 fs: workspace.app_state().fs.clone(),
                 thread_picker_menu_handle,
@@ -159,8 +162,23 @@ impl DebugPanel {
         cx: &mut AsyncWindowContext,
     ) -> Task<Result<Entity<Self>>> {
         cx.spawn(async move |cx| {
+            let database_id = workspace.read_with(cx, |workspace, _| workspace.database_id())?;
+            let recent_scenarios = if let Some(database_id) = database_id {
+                cx.background_spawn(
+                    async move { persistence::DEBUGGER_DB.debug_scenarios(database_id) },
+                )
+                .await
+                .log_err()
+                .unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+
             workspace.update_in(cx, |workspace, window, cx| {
                 let debug_panel = DebugPanel::new(workspace, window, cx);
+                debug_panel.update(cx, |debug_panel, cx| {
+                    debug_panel.restore_recent_scenarios(recent_scenarios, cx);
+                });
 
                 workspace.register_action(|workspace, _: &ClearAllBreakpoints, _, cx| {
                     workspace.project().read(cx).breakpoint_store().update(
@@ -221,6 +239,7 @@ impl DebugPanel {
         };
 
         self.debug_scenario_scheduled_last = true;
+        self.save_recent_scenario(&scenario, &task_context, &worktree, cx);
         if let Some(inventory) = self
             .project
             .read(cx)
@@ -308,6 +327,101 @@ impl DebugPanel {
         });
     }
 
+    fn restore_recent_scenarios(
+        &self,
+        scenarios: Vec<persistence::SerializedDebugScenario>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(inventory) = self
+            .project
+            .read(cx)
+            .task_store()
+            .read(cx)
+            .task_inventory()
+            .cloned()
+        else {
+            return;
+        };
+
+        let scenarios = scenarios
+            .into_iter()
+            .map(|scenario| {
+                let worktree_id = scenario.worktree_path.as_ref().and_then(|path| {
+                    self.project
+                        .read(cx)
+                        .worktrees(cx)
+                        .find(|worktree| worktree.read(cx).abs_path().as_ref() == path)
+                        .map(|worktree| worktree.read(cx).id())
+                });
+                let task_context = scenario.task_context();
+                (scenario.scenario, task_context, worktree_id)
+            })
+            .collect::<Vec<_>>();
+        inventory.update(cx, |inventory, _| {
+            for (scenario, task_context, worktree_id) in scenarios {
+                inventory.scenario_scheduled(scenario, task_context, worktree_id, None);
+            }
+        });
+    }
+
+    fn save_recent_scenario(
+        &self,
+        scenario: &DebugScenario,
+        task_context: &TaskContext,
+        worktree: &Entity<Worktree>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(database_id) = self.database_id else {
+            return;
+        };
+
+        let scenario = persistence::SerializedDebugScenario::new(
+            scenario.clone(),
+            Some(worktree.read(cx).abs_path().to_path_buf()),
+            task_context,
+        );
+        cx.background_spawn(async move {
+            persistence::DEBUGGER_DB
+                .save_debug_scenario(database_id, scenario)
+                .await
+                .log_err();
+        })
+        .detach();
+    }
+
+    pub(crate) fn save_last_scenario(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let Some(task_inventory) = self
+            .project
+            .read(cx)
+            .task_store()
+            .read(cx)
+            .task_inventory()
+            .cloned()
+        else {
+            return Task::ready(Ok(()));
+        };
+        let Some((scenario, context)) = task_inventory.read(cx).last_scheduled_scenario().cloned()
+        else {
+            return Task::ready(Err(anyhow!("No debug session has been started yet")));
+        };
+
+        let Some(worktree_id) = context.worktree_id.or_else(|| {
+            self.project
+                .read(cx)
+                .visible_worktrees(cx)
+                .next()
+                .map(|worktree| worktree.read(cx).id())
+        }) else {
+            return Task::ready(Err(anyhow!("No worktree to save the debug scenario to")));
+        };
+
+        self.save_scenario(scenario, worktree_id, window, cx)
+    }
+
     pub(crate) fn rerun_last_session(
         &mut self,
         workspace: &mut Workspace,
@@ -999,7 +1113,11 @@ impl DebugPanel {
         if let Some(session) = self.active_session() {
             session.update(cx, |session, cx| {
                 session.running_state().update(cx, |running, cx| {
-                    running.activate_item(item, window, cx);
+                    // Panes like the disassembly are only added on demand.
+                    if item.is_supported(&running.capabilities(cx)) {
+                        running.ensure_pane_item(item, window, cx);
+                        running.activate_item(item, window, cx);
+                    }
                 });
             });
         }
@@ -1633,6 +1751,24 @@ impl Render for DebugPanel {
                     .ok();
                 }
             })
+            .on_action({
+                let this = this.clone();
+                move |_: &FocusDisassembly, window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+                    })
+                    .ok();
+                }
+            })
+            .on_action({
+                let this = this.clone();
+                move |_: &FocusMemory, window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.activate_item(DebuggerPaneItem::Memory, window, cx);
+                    })
+                    .ok();
+                }
+            })
             .on_action({
                 let this = this.clone();
                 move |_: &ToggleThreadPicker, window, cx| {
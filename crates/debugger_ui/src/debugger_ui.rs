@@ -13,7 +13,7 @@ use stack_trace_view::StackTraceView;
 use tasks_ui::{Spawn, TaskOverrides};
 use ui::{FluentBuilder, InteractiveElement};
 use util::maybe;
-use workspace::{ItemHandle, ShutdownDebugAdapters, Workspace};
+use workspace::{ItemHandle, ShutdownDebugAdapters, Workspace, notifications::DetachAndPromptErr};
 use zed_actions::ToggleFocus;
 use zed_actions::debugger::OpenOnboardingModal;
 
@@ -72,6 +72,10 @@ actions!(
         FocusLoadedSources,
         /// Focuses on the terminal panel.
         FocusTerminal,
+        /// Focuses on the disassembly panel.
+        FocusDisassembly,
+        /// Focuses on the memory panel.
+        FocusMemory,
         /// Shows the stack trace for the current thread.
         ShowStackTrace,
         /// Toggles the thread picker dropdown.
@@ -81,6 +85,8 @@ actions!(
         /// Reruns the last debugging session.
         #[action(deprecated_aliases = ["debugger::RerunLastSession"])]
         Rerun,
+        /// Saves the configuration of the last debugging session to the project's `.zed/debug.json`.
+        SaveLastScenario,
         /// Toggles expansion of the selected item in the debugger UI.
         ToggleExpandItem,
     ]
@@ -116,6 +122,29 @@ pub fn init(cx: &mut App) {
                     debug_panel.rerun_last_session(workspace, window, cx);
                 })
             })
+            .register_action(
+                |workspace: &mut Workspace, _: &SaveLastScenario, window, cx| {
+                    let Some(debug_panel) = workspace.panel::<DebugPanel>(cx) else {
+                        return;
+                    };
+
+                    // Saving opens debug.json in the workspace, which can't be updated re-entrantly.
+                    let debug_panel = debug_panel.downgrade();
+                    cx.spawn_in(window, async move |_, cx| {
+                        debug_panel
+                            .update_in(cx, |debug_panel, window, cx| {
+                                debug_panel.save_last_scenario(window, cx)
+                            })?
+                            .await
+                    })
+                    .detach_and_prompt_err(
+                        "Failed to save debug scenario",
+                        window,
+                        cx,
+                        |_, _, _| None,
+                    );
+                },
+            )
             .register_action(
                 |workspace: &mut Workspace, _: &ShutdownDebugAdapters, _window, cx| {
                     workspace.project().update(cx, |project, cx| {
@@ -1344,7 +1344,8 @@ impl PickerDelegate for DebugDelegate {
         } = context;
 
         if secondary {
-            let Some(kind) = kind else { return };
+            // Scenarios without a source, like ones started from the configure tab, are saved to debug.json.
+            let kind = kind.unwrap_or(TaskSourceKind::UserInput);
             let Some(id) = worktree_id else { return };
             let debug_panel = self.debug_panel.clone();
             cx.spawn_in(window, async move |_, cx| {
@@ -1,18 +1,21 @@
-use anyhow::Context as _;
-use collections::HashMap;
+use anyhow::{Context as _, Result};
+use collections::{BTreeMap, HashMap};
 use dap::{Capabilities, adapters::DebugAdapterName};
-use db::kvp::KEY_VALUE_STORE;
+use db::{define_connection, kvp::KEY_VALUE_STORE, query, sqlez_macros::sql};
 use gpui::{Axis, Context, Entity, EntityId, Focusable, Subscription, WeakEntity, Window};
 use project::Project;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use task::{DebugScenario, TaskContext, TaskVariables, VariableName};
 use ui::{App, SharedString};
 use util::ResultExt;
-use workspace::{Member, Pane, PaneAxis, Workspace};
+use workspace::{Member, Pane, PaneAxis, Workspace, WorkspaceDb, WorkspaceId};
 
 use crate::session::running::{
     self, DebugTerminal, RunningState, SubView, breakpoint_list::BreakpointList, console::Console,
-    loaded_source_list::LoadedSourceList, module_list::ModuleList,
-    stack_frame_list::StackFrameList, variable_list::VariableList,
+    disassembly_view::DisassemblyView, loaded_source_list::LoadedSourceList,
+    memory_view::MemoryView, module_list::ModuleList, stack_frame_list::StackFrameList,
+    variable_list::VariableList,
 };
 
 #[derive(Clone, Hash, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -24,6 +27,8 @@ pub(crate) enum DebuggerPaneItem {
     Modules,
     LoadedSources,
     Terminal,
+    Disassembly,
+    Memory,
 }
 
 impl DebuggerPaneItem {
@@ -36,6 +41,8 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::Modules,
             DebuggerPaneItem::LoadedSources,
             DebuggerPaneItem::Terminal,
+            DebuggerPaneItem::Disassembly,
+            DebuggerPaneItem::Memory,
         ];
         VARIANTS
     }
@@ -46,6 +53,12 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::LoadedSources => capabilities
                 .supports_loaded_sources_request
                 .unwrap_or_default(),
+            DebuggerPaneItem::Disassembly => capabilities
+                .supports_disassemble_request
+                .unwrap_or_default(),
+            DebuggerPaneItem::Memory => capabilities
+                .supports_read_memory_request
+                .unwrap_or_default(),
             _ => true,
         }
     }
@@ -59,6 +72,8 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::Modules => SharedString::new_static("Modules"),
             DebuggerPaneItem::LoadedSources => SharedString::new_static("Sources"),
             DebuggerPaneItem::Terminal => SharedString::new_static("Terminal"),
+            DebuggerPaneItem::Disassembly => SharedString::new_static("Disassembly"),
+            DebuggerPaneItem::Memory => SharedString::new_static("Memory"),
         }
     }
     pub(crate) fn tab_tooltip(self) -> SharedString {
@@ -80,6 +95,10 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::Terminal => {
                 "Provides an interactive terminal session within the debugging environment."
             }
+            DebuggerPaneItem::Disassembly => {
+                "Shows the machine instructions around the current instruction pointer."
+            }
+            DebuggerPaneItem::Memory => "Shows the raw memory a variable refers to.",
         };
         SharedString::new_static(tooltip)
     }
@@ -204,6 +223,8 @@ pub(crate) fn deserialize_pane_layout(
     breakpoint_list: &Entity<BreakpointList>,
     loaded_sources: &Entity<LoadedSourceList>,
     terminal: &Entity<DebugTerminal>,
+    disassembly_view: &Entity<DisassemblyView>,
+    memory_view: &Entity<MemoryView>,
     subscriptions: &mut HashMap<EntityId, Subscription>,
     window: &mut Window,
     cx: &mut Context<RunningState>,
@@ -228,6 +249,8 @@ pub(crate) fn deserialize_pane_layout(
                     breakpoint_list,
                     loaded_sources,
                     terminal,
+                    disassembly_view,
+                    memory_view,
                     subscriptions,
                     window,
                     cx,
@@ -298,6 +321,18 @@ pub(crate) fn deserialize_pane_layout(
                         DebuggerPaneItem::Terminal,
                         cx,
                     )),
+                    DebuggerPaneItem::Disassembly => Box::new(SubView::new(
+                        disassembly_view.focus_handle(cx),
+                        disassembly_view.clone().into(),
+                        DebuggerPaneItem::Disassembly,
+                        cx,
+                    )),
+                    DebuggerPaneItem::Memory => Box::new(SubView::new(
+                        memory_view.focus_handle(cx),
+                        memory_view.clone().into(),
+                        DebuggerPaneItem::Memory,
+                        cx,
+                    )),
                 })
                 .collect();
 
@@ -345,3 +380,111 @@ impl SerializedPaneLayout {
         }
     }
 }
+
+/// A debug scenario that was started in a workspace, kept so that it can be rerun after a restart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SerializedDebugScenario {
+    pub(crate) scenario: DebugScenario,
+    /// Worktree ids are not stable across restarts, so the worktree is identified by its path.
+    pub(crate) worktree_path: Option<PathBuf>,
+    pub(crate) cwd: Option<PathBuf>,
+    /// Task variables, keyed by their `ZED_`-prefixed names.
+    pub(crate) task_variables: BTreeMap<String, String>,
+}
+
+impl SerializedDebugScenario {
+    pub(crate) fn new(
+        scenario: DebugScenario,
+        worktree_path: Option<PathBuf>,
+        task_context: &TaskContext,
+    ) -> Self {
+        Self {
+            scenario,
+            worktree_path,
+            cwd: task_context.cwd.clone(),
+            task_variables: task_context
+                .task_variables
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect(),
+        }
+    }
+
+    /// The project environment is not persisted, as it may contain secrets.
+    pub(crate) fn task_context(&self) -> TaskContext {
+        TaskContext {
+            cwd: self.cwd.clone(),
+            task_variables: self
+                .task_variables
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.parse::<VariableName>().ok()?, value.clone()))
+                })
+                .collect::<TaskVariables>(),
+            project_env: HashMap::default(),
+        }
+    }
+}
+
+define_connection! {
+    pub static ref DEBUGGER_DB: DebuggerDb<WorkspaceDb> =
+        &[sql!(
+            CREATE TABLE debug_scenarios (
+                workspace_id INTEGER NOT NULL,
+                label TEXT NOT NULL,
+                scenario TEXT NOT NULL,
+                PRIMARY KEY(workspace_id, label),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+            ) STRICT;
+        )];
+}
+
+impl DebuggerDb {
+    pub(crate) async fn save_debug_scenario(
+        &self,
+        workspace_id: WorkspaceId,
+        scenario: SerializedDebugScenario,
+    ) -> Result<()> {
+        let label = scenario.scenario.label.to_string();
+        let scenario = serde_json::to_string(&scenario)?;
+        self.save_debug_scenario_internal(workspace_id, label, scenario)
+            .await
+    }
+
+    /// Returns the debug scenarios started in the workspace, least recently started first.
+    pub(crate) fn debug_scenarios(
+        &self,
+        workspace_id: WorkspaceId,
+    ) -> Result<Vec<SerializedDebugScenario>> {
+        Ok(self
+            .debug_scenarios_internal(workspace_id)?
+            .into_iter()
+            .filter_map(|scenario| serde_json::from_str(&scenario).log_err())
+            .collect())
+    }
+
+    // Replacing a row gives it a new rowid, so rowids order scenarios by when they were last started.
+    query! {
+        async fn save_debug_scenario_internal(workspace_id: WorkspaceId, label: String, scenario: String) -> Result<()> {
+            INSERT OR REPLACE INTO debug_scenarios(workspace_id, label, scenario)
+            VALUES (?1, ?2, ?3);
+            DELETE FROM debug_scenarios
+            WHERE workspace_id = ?1 AND rowid NOT IN (
+                SELECT rowid FROM debug_scenarios
+                WHERE workspace_id = ?1
+                ORDER BY rowid DESC
+                LIMIT 20
+            );
+        }
+    }
+
+    query! {
+        fn debug_scenarios_internal(workspace_id: WorkspaceId) -> Result<Vec<String>> {
+            SELECT scenario
+            FROM debug_scenarios
+            WHERE workspace_id = ?
+            ORDER BY rowid
+        }
+    }
+}
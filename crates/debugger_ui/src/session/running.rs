@@ -1,6 +1,8 @@
 pub(crate) mod breakpoint_list;
 pub(crate) mod console;
+pub(crate) mod disassembly_view;
 pub(crate) mod loaded_source_list;
+pub(crate) mod memory_view;
 pub(crate) mod module_list;
 pub mod stack_frame_list;
 pub mod variable_list;
@@ -24,6 +26,7 @@ use dap::{
     client::SessionId,
     debugger_settings::DebuggerSettings,
 };
+use disassembly_view::DisassemblyView;
 use futures::{SinkExt, channel::mpsc};
 use gpui::{
     Action as _, AnyView, AppContext, Axis, Entity, EntityId, EventEmitter, FocusHandle, Focusable,
@@ -31,6 +34,7 @@ use gpui::{
 };
 use language::Buffer;
 use loaded_source_list::LoadedSourceList;
+use memory_view::MemoryView;
 use module_list::ModuleList;
 use project::{
     DebugScenarioContext, Project, WorktreeId,
@@ -53,7 +57,7 @@ use ui::{
     VisibleOnHover, VisualContext, Window, div, h_flex, v_flex,
 };
 use util::ResultExt;
-use variable_list::VariableList;
+use variable_list::{VariableList, VariableListEvent};
 use workspace::{
     ActivePaneDecorator, DraggedTab, Item, ItemHandle, Member, Pane, PaneGroup, SplitDirection,
     Workspace, item::TabContentParams, move_item, pane::Event,
@@ -74,6 +78,8 @@ pub struct RunningState {
     module_list: Entity<module_list::ModuleList>,
     console: Entity<Console>,
     breakpoint_list: Entity<BreakpointList>,
+    disassembly_view: Entity<DisassemblyView>,
+    memory_view: Entity<MemoryView>,
     panes: PaneGroup,
     active_pane: Entity<Pane>,
     pane_close_subscriptions: HashMap<EntityId, Subscription>,
@@ -676,7 +682,13 @@ impl RunningState {
         let session_id = session.read(cx).session_id();
         let weak_state = cx.weak_entity();
         let stack_frame_list = cx.new(|cx| {
-            StackFrameList::new(workspace.clone(), session.clone(), weak_state, window, cx)
+            StackFrameList::new(
+                workspace.clone(),
+                session.clone(),
+                weak_state.clone(),
+                window,
+                cx,
+            )
         });
 
         let debug_terminal =
@@ -689,6 +701,12 @@ impl RunningState {
 
         let loaded_source_list = cx.new(|cx| LoadedSourceList::new(session.clone(), cx));
 
+        let disassembly_view = cx.new(|cx| {
+            DisassemblyView::new(session.clone(), stack_frame_list.clone(), weak_state, cx)
+        });
+
+        let memory_view = cx.new(|cx| MemoryView::new(session.clone(), window, cx));
+
         let console = cx.new(|cx| {
             Console::new(
                 session.clone(),
@@ -758,6 +776,12 @@ impl RunningState {
                         {
                             this.remove_pane_item(DebuggerPaneItem::LoadedSources, window, cx);
                         }
+                        if !capabilities.supports_disassemble_request.unwrap_or(false) {
+                            this.remove_pane_item(DebuggerPaneItem::Disassembly, window, cx);
+                        }
+                        if !capabilities.supports_read_memory_request.unwrap_or(false) {
+                            this.remove_pane_item(DebuggerPaneItem::Memory, window, cx);
+                        }
                     }
                     SessionEvent::RunInTerminal { request, sender } => this
                         .handle_run_in_terminal(request, sender.clone(), window, cx)
@@ -767,6 +791,22 @@ impl RunningState {
                 }
                 cx.notify()
             }),
+            cx.subscribe_in(
+                &variable_list,
+                window,
+                |this, _, event: &VariableListEvent, window, cx| match event {
+                    VariableListEvent::ViewMemory {
+                        memory_reference,
+                        name,
+                    } => {
+                        this.ensure_pane_item(DebuggerPaneItem::Memory, window, cx);
+                        this.memory_view.update(cx, |memory_view, cx| {
+                            memory_view.open(memory_reference.clone(), name.clone(), cx);
+                        });
+                        this.activate_item(DebuggerPaneItem::Memory, window, cx);
+                    }
+                },
+            ),
             cx.on_focus_out(&focus_handle, window, |this, _, window, cx| {
                 this.serialize_layout(window, cx);
             }),
@@ -795,6 +835,8 @@ impl RunningState {
                 &breakpoint_list,
                 &loaded_source_list,
                 &debug_terminal,
+                &disassembly_view,
+                &memory_view,
                 &mut pane_close_subscriptions,
                 window,
                 cx,
@@ -837,6 +879,8 @@ impl RunningState {
             module_list,
             console,
             breakpoint_list,
+            disassembly_view,
+            memory_view,
             loaded_sources_list: loaded_source_list,
             pane_close_subscriptions,
             debug_terminal,
@@ -1234,6 +1278,18 @@ impl RunningState {
                 item_kind,
                 cx,
             )),
+            DebuggerPaneItem::Disassembly => Box::new(SubView::new(
+                self.disassembly_view.focus_handle(cx),
+                self.disassembly_view.clone().into(),
+                item_kind,
+                cx,
+            )),
+            DebuggerPaneItem::Memory => Box::new(SubView::new(
+                self.memory_view.focus_handle(cx),
+                self.memory_view.clone().into(),
+                item_kind,
+                cx,
+            )),
         }
     }
 
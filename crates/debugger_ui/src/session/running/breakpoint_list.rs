@@ -16,7 +16,9 @@ use language::Point;
 use project::{
     Project,
     debugger::{
-        breakpoint_store::{BreakpointEditAction, BreakpointStore, SourceBreakpoint},
+        breakpoint_store::{
+            self, BreakpointEditAction, BreakpointState, BreakpointStore, SourceBreakpoint,
+        },
         dap_store::{DapStore, PersistedAdapterOptions},
         session::Session,
     },
@@ -39,12 +41,16 @@ actions!(
         /// Navigates to the previous breakpoint property in the list.
         PreviousBreakpointProperty,
         /// Navigates to the next breakpoint property in the list.
-        NextBreakpointProperty
+        NextBreakpointProperty,
+        /// Adds a breakpoint on a function, entered by name.
+        AddFunctionBreakpoint
     ]
 );
 #[derive(Clone, Copy, PartialEq)]
 pub(crate) enum SelectedBreakpointKind {
     Source,
+    Function,
+    Data,
     Exception,
 }
 pub(crate) struct BreakpointList {
@@ -62,6 +68,7 @@ pub(crate) struct BreakpointList {
     selected_ix: Option<usize>,
     input: Entity<Editor>,
     strip_mode: Option<ActiveBreakpointStripMode>,
+    function_breakpoint_input: Option<Entity<Editor>>,
     serialize_exception_breakpoints_task: Option<Task<anyhow::Result<()>>>,
 }
 
@@ -96,6 +103,8 @@ impl BreakpointList {
 
         let adapter_name = session.as_ref().map(|session| session.read(cx).adapter());
         cx.new(|cx| {
+            cx.observe(&breakpoint_store, |_, _, cx| cx.notify())
+                .detach();
             let this = Self {
                 breakpoint_store,
                 dap_store,
@@ -111,6 +120,7 @@ impl BreakpointList {
                 selected_ix: None,
                 input: cx.new(|cx| Editor::single_line(window, cx)),
                 strip_mode: None,
+                function_breakpoint_input: None,
                 serialize_exception_breakpoints_task: None,
             };
             if let Some(name) = adapter_name {
@@ -145,6 +155,18 @@ impl BreakpointList {
         })
     }
 
+    fn edit_function_breakpoint(&self, name: Arc<str>, action: BreakpointEditAction, cx: &mut App) {
+        self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+            breakpoint_store.edit_function_breakpoint(&name, action, cx);
+        })
+    }
+
+    fn edit_data_breakpoint(&self, data_id: Arc<str>, action: BreakpointEditAction, cx: &mut App) {
+        self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+            breakpoint_store.edit_data_breakpoint(&data_id, action, cx);
+        })
+    }
+
     fn go_to_line_breakpoint(
         &mut self,
         path: Arc<Path>,
@@ -182,8 +204,15 @@ impl BreakpointList {
             self.breakpoints.get(ix).map(|bp| match &bp.kind {
                 BreakpointEntryKind::LineBreakpoint(bp) => (
                     SelectedBreakpointKind::Source,
-                    bp.breakpoint.state
-                        == project::debugger::breakpoint_store::BreakpointState::Enabled,
+                    bp.breakpoint.state == BreakpointState::Enabled,
+                ),
+                BreakpointEntryKind::FunctionBreakpoint(bp) => (
+                    SelectedBreakpointKind::Function,
+                    bp.breakpoint.state == BreakpointState::Enabled,
+                ),
+                BreakpointEntryKind::DataBreakpoint(bp) => (
+                    SelectedBreakpointKind::Data,
+                    bp.breakpoint.state == BreakpointState::Enabled,
                 ),
                 BreakpointEntryKind::ExceptionBreakpoint(bp) => {
                     (SelectedBreakpointKind::Exception, bp.is_enabled)
@@ -204,27 +233,46 @@ impl BreakpointList {
             ActiveBreakpointStripMode::Condition => "Set Condition",
             ActiveBreakpointStripMode::HitCondition => "Set Hit Condition",
         };
-        let mut is_exception_breakpoint = true;
+        let mut is_read_only = true;
         let active_value = self.selected_ix.and_then(|ix| {
             self.breakpoints.get(ix).and_then(|bp| {
-                if let BreakpointEntryKind::LineBreakpoint(bp) = &bp.kind {
-                    is_exception_breakpoint = false;
-                    match prop {
-                        ActiveBreakpointStripMode::Log => bp.breakpoint.message.clone(),
-                        ActiveBreakpointStripMode::Condition => bp.breakpoint.condition.clone(),
-                        ActiveBreakpointStripMode::HitCondition => {
-                            bp.breakpoint.hit_condition.clone()
-                        }
+                let (message, condition, hit_condition) = match &bp.kind {
+                    BreakpointEntryKind::LineBreakpoint(bp) => (
+                        bp.breakpoint.message.clone(),
+                        &bp.breakpoint.condition,
+                        &bp.breakpoint.hit_condition,
+                    ),
+                    // Function and data breakpoints can't log messages.
+                    BreakpointEntryKind::FunctionBreakpoint(bp) => {
+                        (None, &bp.breakpoint.condition, &bp.breakpoint.hit_condition)
                     }
-                } else {
-                    None
+                    BreakpointEntryKind::DataBreakpoint(bp) => {
+                        (None, &bp.breakpoint.condition, &bp.breakpoint.hit_condition)
+                    }
+                    // Exception breakpoints only support conditions.
+                    BreakpointEntryKind::ExceptionBreakpoint(bp) => (None, &bp.condition, &None),
+                };
+                is_read_only = match bp.kind {
+                    BreakpointEntryKind::LineBreakpoint(_) => false,
+                    BreakpointEntryKind::FunctionBreakpoint(_)
+                    | BreakpointEntryKind::DataBreakpoint(_) => {
+                        prop == ActiveBreakpointStripMode::Log
+                    }
+                    BreakpointEntryKind::ExceptionBreakpoint(_) => {
+                        prop != ActiveBreakpointStripMode::Condition
+                    }
+                };
+                match prop {
+                    ActiveBreakpointStripMode::Log => message,
+                    ActiveBreakpointStripMode::Condition => condition.clone(),
+                    ActiveBreakpointStripMode::HitCondition => hit_condition.clone(),
                 }
             })
         });
 
         self.input.update(cx, |this, cx| {
             this.set_placeholder_text(placeholder, cx);
-            this.set_read_only(is_exception_breakpoint);
+            this.set_read_only(is_read_only);
             this.set_text(active_value.as_deref().unwrap_or(""), window, cx);
         });
     }
@@ -319,8 +367,38 @@ impl BreakpointList {
         self.select_ix(ix, window, cx);
     }
 
+    fn add_function_breakpoint(
+        &mut self,
+        _: &AddFunctionBreakpoint,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let input = self
+            .function_breakpoint_input
+            .get_or_insert_with(|| {
+                cx.new(|cx| {
+                    let mut editor = Editor::single_line(window, cx);
+                    editor.set_placeholder_text("Function Name", cx);
+                    editor
+                })
+            })
+            .clone();
+        input.focus_handle(cx).focus(window);
+        cx.notify();
+    }
+
+    fn is_function_breakpoint_input_focused(&self, window: &Window, cx: &App) -> bool {
+        self.function_breakpoint_input
+            .as_ref()
+            .is_some_and(|input| input.focus_handle(cx).contains_focused(window, cx))
+    }
+
     fn dismiss(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
-        if self.input.focus_handle(cx).contains_focused(window, cx) {
+        if self.is_function_breakpoint_input_focused(window, cx) {
+            self.function_breakpoint_input.take();
+            self.focus_handle.focus(window);
+            cx.notify();
+        } else if self.input.focus_handle(cx).contains_focused(window, cx) {
             self.focus_handle.focus(window);
         } else if self.strip_mode.is_some() {
             self.strip_mode.take();
@@ -330,6 +408,21 @@ impl BreakpointList {
         }
     }
     fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self.is_function_breakpoint_input_focused(window, cx) {
+            if let Some(input) = self.function_breakpoint_input.take() {
+                let name = input.read(cx).text(cx);
+                let name = name.trim();
+                if !name.is_empty() {
+                    self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+                        breakpoint_store.add_function_breakpoint(Arc::from(name), cx);
+                    });
+                }
+            }
+            self.focus_handle.focus(window);
+            cx.notify();
+            return;
+        }
+
         let Some(entry) = self.selected_ix.and_then(|ix| self.breakpoints.get_mut(ix)) else {
             return;
         };
@@ -339,6 +432,7 @@ impl BreakpointList {
             if handle.is_focused(window) {
                 // Go back to the main strip. Save the result as well.
                 let text = self.input.read(cx).text(cx);
+                let mut edited_exception_breakpoint = false;
 
                 match mode {
                     ActiveBreakpointStripMode::Log => match &entry.kind {
@@ -363,7 +457,36 @@ impl BreakpointList {
                                 cx,
                             );
                         }
-                        _ => {}
+                        BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                            self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+                                breakpoint_store.edit_function_breakpoint(
+                                    &function_breakpoint.breakpoint.name,
+                                    BreakpointEditAction::EditCondition(Arc::from(text)),
+                                    cx,
+                                );
+                            });
+                        }
+                        BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                            self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+                                breakpoint_store.edit_data_breakpoint(
+                                    &data_breakpoint.breakpoint.data_id,
+                                    BreakpointEditAction::EditCondition(Arc::from(text)),
+                                    cx,
+                                );
+                            });
+                        }
+                        BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => {
+                            if let Some(session) = &self.session {
+                                session.update(cx, |session, cx| {
+                                    session.set_exception_breakpoint_condition(
+                                        &exception_breakpoint.id,
+                                        Arc::from(text),
+                                        cx,
+                                    );
+                                });
+                                edited_exception_breakpoint = true;
+                            }
+                        }
                     },
                     ActiveBreakpointStripMode::HitCondition => match &entry.kind {
                         BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
@@ -375,9 +498,31 @@ impl BreakpointList {
                                 cx,
                             );
                         }
-                        _ => {}
+                        BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                            self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+                                breakpoint_store.edit_function_breakpoint(
+                                    &function_breakpoint.breakpoint.name,
+                                    BreakpointEditAction::EditHitCondition(Arc::from(text)),
+                                    cx,
+                                );
+                            });
+                        }
+                        BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                            self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+                                breakpoint_store.edit_data_breakpoint(
+                                    &data_breakpoint.breakpoint.data_id,
+                                    BreakpointEditAction::EditHitCondition(Arc::from(text)),
+                                    cx,
+                                );
+                            });
+                        }
+                        BreakpointEntryKind::ExceptionBreakpoint(_) => {}
                     },
                 }
+                if edited_exception_breakpoint {
+                    self.schedule_exception_breakpoints_serialization(cx);
+                    cx.notify();
+                }
                 self.focus_handle.focus(window);
             } else {
                 handle.focus(window);
@@ -391,7 +536,9 @@ impl BreakpointList {
                 let row = line_breakpoint.breakpoint.row;
                 self.go_to_line_breakpoint(path, row, window, cx);
             }
-            BreakpointEntryKind::ExceptionBreakpoint(_) => {}
+            BreakpointEntryKind::FunctionBreakpoint(_)
+            | BreakpointEntryKind::DataBreakpoint(_)
+            | BreakpointEntryKind::ExceptionBreakpoint(_) => {}
         }
     }
 
@@ -417,6 +564,14 @@ impl BreakpointList {
                 let row = line_breakpoint.breakpoint.row;
                 self.edit_line_breakpoint(path, row, BreakpointEditAction::InvertState, cx);
             }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                let name = function_breakpoint.breakpoint.name.clone();
+                self.edit_function_breakpoint(name, BreakpointEditAction::InvertState, cx);
+            }
+            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                let data_id = data_breakpoint.breakpoint.data_id.clone();
+                self.edit_data_breakpoint(data_id, BreakpointEditAction::InvertState, cx);
+            }
             BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => {
                 let id = exception_breakpoint.id.clone();
                 self.toggle_exception_breakpoint(&id, cx);
@@ -441,6 +596,14 @@ impl BreakpointList {
                 let row = line_breakpoint.breakpoint.row;
                 self.edit_line_breakpoint(path, row, BreakpointEditAction::Toggle, cx);
             }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                let name = function_breakpoint.breakpoint.name.clone();
+                self.edit_function_breakpoint(name, BreakpointEditAction::Toggle, cx);
+            }
+            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                let data_id = data_breakpoint.breakpoint.data_id.clone();
+                self.edit_data_breakpoint(data_id, BreakpointEditAction::Toggle, cx);
+            }
             BreakpointEntryKind::ExceptionBreakpoint(_) => {}
         }
         cx.notify();
@@ -496,18 +659,22 @@ impl BreakpointList {
                 this.toggle_exception_breakpoint(&id, cx);
             });
             cx.notify();
-            const EXCEPTION_SERIALIZATION_INTERVAL: Duration = Duration::from_secs(1);
-            self.serialize_exception_breakpoints_task = Some(cx.spawn(async move |this, cx| {
-                cx.background_executor()
-                    .timer(EXCEPTION_SERIALIZATION_INTERVAL)
-                    .await;
-                this.update(cx, |this, cx| this.serialize_exception_breakpoints(cx))?
-                    .await?;
-                Ok(())
-            }));
+            self.schedule_exception_breakpoints_serialization(cx);
         }
     }
 
+    fn schedule_exception_breakpoints_serialization(&mut self, cx: &mut Context<Self>) {
+        const EXCEPTION_SERIALIZATION_INTERVAL: Duration = Duration::from_secs(1);
+        self.serialize_exception_breakpoints_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(EXCEPTION_SERIALIZATION_INTERVAL)
+                .await;
+            this.update(cx, |this, cx| this.serialize_exception_breakpoints(cx))?
+                .await?;
+            Ok(())
+        }));
+    }
+
     fn kvp_key(adapter_name: &str) -> String {
         format!("debug_adapter_`{adapter_name}`_persistence")
     }
@@ -638,7 +805,9 @@ impl BreakpointList {
         let selection_kind = self.selection_kind();
         let focus_handle = self.focus_handle.clone();
         let remove_breakpoint_tooltip = selection_kind.map(|(kind, _)| match kind {
-            SelectedBreakpointKind::Source => "Remove breakpoint from a breakpoint list",
+            SelectedBreakpointKind::Source
+            | SelectedBreakpointKind::Function
+            | SelectedBreakpointKind::Data => "Remove breakpoint from a breakpoint list",
             SelectedBreakpointKind::Exception => {
                 "Exception Breakpoints cannot be removed from the breakpoint list"
             }
@@ -656,6 +825,30 @@ impl BreakpointList {
 
         h_flex()
             .gap_2()
+            .child(
+                IconButton::new("add-function-breakpoint-breakpoint-list", IconName::Plus)
+                    .icon_size(IconSize::XSmall)
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |window, cx| {
+                            Tooltip::with_meta_in(
+                                "Add Function Breakpoint",
+                                Some(&AddFunctionBreakpoint),
+                                "Stop whenever a function with the given name is called",
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                        }
+                    })
+                    .on_click({
+                        let focus_handle = focus_handle.clone();
+                        move |_, window, cx| {
+                            focus_handle.focus(window);
+                            window.dispatch_action(AddFunctionBreakpoint.boxed_clone(), cx)
+                        }
+                    }),
+            )
             .child(
                 IconButton::new(
                     "disable-breakpoint-breakpoint-list",
@@ -706,7 +899,8 @@ impl BreakpointList {
                         })
                     })
                     .disabled(
-                        selection_kind.map(|kind| kind.0) != Some(SelectedBreakpointKind::Source),
+                        selection_kind
+                            .map_or(true, |(kind, _)| kind == SelectedBreakpointKind::Exception),
                     )
                     .on_click({
                         let focus_handle = focus_handle.clone();
@@ -770,21 +964,48 @@ impl Render for BreakpointList {
                 })
             })
         });
+        let breakpoint_store = self.breakpoint_store.read(cx);
+        let function_breakpoints =
+            breakpoint_store
+                .function_breakpoints()
+                .iter()
+                .map(|breakpoint| BreakpointEntry {
+                    kind: BreakpointEntryKind::FunctionBreakpoint(FunctionBreakpoint {
+                        breakpoint: breakpoint.clone(),
+                    }),
+                    weak: weak.clone(),
+                });
+        let data_breakpoints = breakpoint_store
+            .data_breakpoints()
+            .iter()
+            .map(|breakpoint| BreakpointEntry {
+                kind: BreakpointEntryKind::DataBreakpoint(DataBreakpoint {
+                    breakpoint: breakpoint.clone(),
+                }),
+                weak: weak.clone(),
+            });
         let exception_breakpoints = self.session.as_ref().into_iter().flat_map(|session| {
+            let session = session.read(cx);
             session
-                .read(cx)
                 .exception_breakpoints()
                 .map(|(data, is_enabled)| BreakpointEntry {
                     kind: BreakpointEntryKind::ExceptionBreakpoint(ExceptionBreakpoint {
                         id: data.filter.clone(),
                         data: data.clone(),
                         is_enabled: *is_enabled,
+                        condition: session
+                            .exception_breakpoint_condition(&data.filter)
+                            .cloned(),
                     }),
                     weak: weak.clone(),
                 })
         });
-        self.breakpoints
-            .extend(breakpoints.chain(exception_breakpoints));
+        self.breakpoints.extend(
+            breakpoints
+                .chain(function_breakpoints)
+                .chain(data_breakpoints)
+                .chain(exception_breakpoints),
+        );
         v_flex()
             .id("breakpoint-list")
             .key_context("BreakpointList")
@@ -808,6 +1029,7 @@ impl Render for BreakpointList {
             .on_action(cx.listener(Self::unset_breakpoint))
             .on_action(cx.listener(Self::next_breakpoint_property))
             .on_action(cx.listener(Self::previous_breakpoint_property))
+            .on_action(cx.listener(Self::add_function_breakpoint))
             .size_full()
             .m_0p5()
             .child(
@@ -839,6 +1061,20 @@ impl Render for BreakpointList {
                         .child(self.input.clone()),
                 )
             })
+            .when_some(self.function_breakpoint_input.clone(), |this, input| {
+                this.child(Divider::horizontal()).child(
+                    h_flex()
+                        .m_0p5()
+                        .p_0p5()
+                        .border_1()
+                        .rounded_sm()
+                        .when(
+                            input.focus_handle(cx).contains_focused(window, cx),
+                            |this| this.border_color(cx.theme().colors().border_focused),
+                        )
+                        .child(input),
+                )
+            })
     }
 }
 
@@ -995,6 +1231,7 @@ struct ExceptionBreakpoint {
     id: String,
     data: ExceptionBreakpointsFilter,
     is_enabled: bool,
+    condition: Option<Arc<str>>,
 }
 
 impl ExceptionBreakpoint {
@@ -1101,9 +1338,21 @@ impl ExceptionBreakpoint {
         .toggle_state(is_selected)
     }
 }
+#[derive(Clone, Debug)]
+struct FunctionBreakpoint {
+    breakpoint: breakpoint_store::FunctionBreakpoint,
+}
+
+#[derive(Clone, Debug)]
+struct DataBreakpoint {
+    breakpoint: breakpoint_store::DataBreakpoint,
+}
+
 #[derive(Clone, Debug)]
 enum BreakpointEntryKind {
     LineBreakpoint(LineBreakpoint),
+    FunctionBreakpoint(FunctionBreakpoint),
+    DataBreakpoint(DataBreakpoint),
     ExceptionBreakpoint(ExceptionBreakpoint),
 }
 
@@ -1131,9 +1380,18 @@ impl BreakpointEntry {
                 focus_handle,
                 self.weak.clone(),
             ),
+            BreakpointEntryKind::FunctionBreakpoint(_) | BreakpointEntryKind::DataBreakpoint(_) => {
+                self.render_named_breakpoint(
+                    props.for_function_and_data_breakpoints(),
+                    strip_mode,
+                    ix,
+                    is_selected,
+                    focus_handle,
+                )
+            }
             BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => exception_breakpoint
                 .render(
-                    props.for_exception_breakpoints(),
+                    props.for_exception_breakpoint(&exception_breakpoint.data),
                     strip_mode,
                     ix,
                     is_selected,
@@ -1143,6 +1401,133 @@ impl BreakpointEntry {
         }
     }
 
+    /// Renders a function or data breakpoint, which are identified by a name rather than a location.
+    fn render_named_breakpoint(
+        &self,
+        props: SupportedBreakpointProperties,
+        strip_mode: Option<ActiveBreakpointStripMode>,
+        ix: usize,
+        is_selected: bool,
+        focus_handle: FocusHandle,
+    ) -> ListItem {
+        let (label, description, is_enabled) = match &self.kind {
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => (
+                SharedString::from(format!("{}()", function_breakpoint.breakpoint.name)),
+                "Function breakpoint",
+                function_breakpoint.breakpoint.state.is_enabled(),
+            ),
+            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => (
+                SharedString::from(data_breakpoint.breakpoint.description.to_string()),
+                match data_breakpoint.breakpoint.access_type {
+                    Some(dap::DataBreakpointAccessType::Read) => "Data breakpoint (read)",
+                    Some(dap::DataBreakpointAccessType::ReadWrite) => {
+                        "Data breakpoint (read/write)"
+                    }
+                    Some(dap::DataBreakpointAccessType::Write) | None => "Data breakpoint",
+                },
+                data_breakpoint.breakpoint.state.is_enabled(),
+            ),
+            BreakpointEntryKind::LineBreakpoint(_)
+            | BreakpointEntryKind::ExceptionBreakpoint(_) => {
+                unreachable!("only function and data breakpoints have names")
+            }
+        };
+        let icon_name = if is_enabled {
+            IconName::DebugBreakpoint
+        } else {
+            IconName::DebugDisabledBreakpoint
+        };
+        let id = self.id();
+        let list = self.weak.clone();
+
+        ListItem::new(SharedString::from(format!("{id}-ui-item")))
+            .on_click({
+                let list = list.clone();
+                move |_, window, cx| {
+                    list.update(cx, |list, cx| list.select_ix(Some(ix), window, cx))
+                        .ok();
+                }
+            })
+            .rounded()
+            .on_secondary_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .start_slot(
+                div()
+                    .id(SharedString::from(format!("{id}-ui-toggle")))
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |window, cx| {
+                            Tooltip::for_action_in(
+                                if is_enabled {
+                                    "Disable Breakpoint"
+                                } else {
+                                    "Enable Breakpoint"
+                                },
+                                &ToggleEnableBreakpoint,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                        }
+                    })
+                    .on_click({
+                        let list = list.clone();
+                        let kind = self.kind.clone();
+                        move |_, _, cx| {
+                            list.update(cx, |list, cx| match &kind {
+                                BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                                    list.edit_function_breakpoint(
+                                        function_breakpoint.breakpoint.name.clone(),
+                                        BreakpointEditAction::InvertState,
+                                        cx,
+                                    )
+                                }
+                                BreakpointEntryKind::DataBreakpoint(data_breakpoint) => list
+                                    .edit_data_breakpoint(
+                                        data_breakpoint.breakpoint.data_id.clone(),
+                                        BreakpointEditAction::InvertState,
+                                        cx,
+                                    ),
+                                BreakpointEntryKind::LineBreakpoint(_)
+                                | BreakpointEntryKind::ExceptionBreakpoint(_) => {}
+                            })
+                            .ok();
+                        }
+                    })
+                    .cursor_pointer()
+                    .child(Indicator::icon(Icon::new(icon_name)).color(Color::Debugger)),
+            )
+            .child(
+                h_flex()
+                    .w_full()
+                    .mr_4()
+                    .py_0p5()
+                    .justify_between()
+                    .child(
+                        h_flex()
+                            .id(SharedString::from(format!("{id}-label")))
+                            .gap_1()
+                            .min_h(px(26.))
+                            .child(
+                                Label::new(label)
+                                    .size(LabelSize::Small)
+                                    .line_height_style(ui::LineHeightStyle::UiLabel),
+                            )
+                            .tooltip(Tooltip::text(description)),
+                    )
+                    .child(BreakpointOptionsStrip {
+                        props,
+                        breakpoint: self.clone(),
+                        is_selected,
+                        focus_handle,
+                        strip_mode,
+                        index: ix,
+                    }),
+            )
+            .toggle_state(is_selected)
+    }
+
     fn id(&self) -> SharedString {
         match &self.kind {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => format!(
@@ -1150,6 +1535,16 @@ impl BreakpointEntry {
                 line_breakpoint.breakpoint.path, line_breakpoint.breakpoint.row
             )
             .into(),
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => format!(
+                "function-breakpoint-control-strip-{}",
+                function_breakpoint.breakpoint.name
+            )
+            .into(),
+            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => format!(
+                "data-breakpoint-control-strip-{}",
+                data_breakpoint.breakpoint.data_id
+            )
+            .into(),
             BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => format!(
                 "exception-breakpoint-control-strip--{}",
                 exception_breakpoint.id
@@ -1172,8 +1567,15 @@ impl BreakpointEntry {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                 line_breakpoint.breakpoint.condition.is_some()
             }
-            // We don't support conditions on exception breakpoints
-            BreakpointEntryKind::ExceptionBreakpoint(_) => false,
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                function_breakpoint.breakpoint.condition.is_some()
+            }
+            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                data_breakpoint.breakpoint.condition.is_some()
+            }
+            BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => {
+                exception_breakpoint.condition.is_some()
+            }
         }
     }
 
@@ -1182,6 +1584,12 @@ impl BreakpointEntry {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                 line_breakpoint.breakpoint.hit_condition.is_some()
             }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                function_breakpoint.breakpoint.hit_condition.is_some()
+            }
+            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                data_breakpoint.breakpoint.hit_condition.is_some()
+            }
             _ => false,
         }
     }
@@ -1208,7 +1616,7 @@ impl From<&Capabilities> for SupportedBreakpointProperties {
                 Self::HIT_CONDITION,
             ),
             (
-                caps.supports_exception_options,
+                caps.supports_exception_filter_options,
                 Self::EXCEPTION_FILTER_OPTIONS,
             ),
         ] {
@@ -1221,9 +1629,18 @@ impl From<&Capabilities> for SupportedBreakpointProperties {
 }
 
 impl SupportedBreakpointProperties {
-    fn for_exception_breakpoints(self) -> Self {
-        // TODO: we don't yet support conditions for exception breakpoints at the data layer, hence all props are disabled here.
-        Self::empty()
+    fn for_function_and_data_breakpoints(self) -> Self {
+        self & (Self::CONDITION | Self::HIT_CONDITION)
+    }
+
+    fn for_exception_breakpoint(self, filter: &ExceptionBreakpointsFilter) -> Self {
+        if self.contains(Self::EXCEPTION_FILTER_OPTIONS)
+            && filter.supports_condition.unwrap_or_default()
+        {
+            Self::CONDITION
+        } else {
+            Self::empty()
+        }
     }
 }
 #[derive(IntoElement)]
@@ -0,0 +1,333 @@
+use dap::SteppingGranularity;
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, MouseButton, ScrollStrategy, Stateful,
+    Subscription, Task, UniformListScrollHandle, WeakEntity, uniform_list,
+};
+use project::debugger::{
+    dap_command::parse_memory_address,
+    session::{Session, ThreadStatus},
+};
+use std::ops::Range;
+use ui::{Scrollbar, ScrollbarState, Tooltip, prelude::*};
+
+use super::{
+    RunningState,
+    stack_frame_list::{StackFrameList, StackFrameListEvent},
+};
+
+/// How many instructions are shown before the instruction pointer.
+const INSTRUCTIONS_BEFORE: u64 = 64;
+/// How many instructions are fetched in total around the instruction pointer.
+const INSTRUCTION_COUNT: u64 = 128;
+
+pub(crate) struct DisassemblyView {
+    session: Entity<Session>,
+    stack_frame_list: Entity<StackFrameList>,
+    state: WeakEntity<RunningState>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+    instructions: Vec<dap::DisassembledInstruction>,
+    instruction_pointer: Option<u64>,
+    error: Option<SharedString>,
+    _fetch_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DisassemblyView {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        stack_frame_list: Entity<StackFrameList>,
+        state: WeakEntity<RunningState>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let focus_handle = cx.focus_handle();
+
+        let _subscriptions =
+            vec![
+                cx.subscribe(&stack_frame_list, |this, _, event, cx| match event {
+                    StackFrameListEvent::SelectedStackFrameChanged(_)
+                    | StackFrameListEvent::BuiltEntries => this.schedule_fetch(cx),
+                }),
+            ];
+
+        let scroll_handle = UniformListScrollHandle::new();
+
+        Self {
+            scrollbar_state: ScrollbarState::new(scroll_handle.clone()),
+            scroll_handle,
+            session,
+            stack_frame_list,
+            state,
+            focus_handle,
+            instructions: Vec::new(),
+            instruction_pointer: None,
+            error: None,
+            _fetch_task: Task::ready(()),
+            _subscriptions,
+        }
+    }
+
+    fn schedule_fetch(&mut self, cx: &mut Context<Self>) {
+        let Some(memory_reference) = self
+            .stack_frame_list
+            .update(cx, |list, cx| list.opened_stack_frame(cx))
+            .and_then(|stack_frame| stack_frame.instruction_pointer_reference)
+        else {
+            self.instructions.clear();
+            self.instruction_pointer = None;
+            self.error = None;
+            self._fetch_task = Task::ready(());
+            cx.notify();
+            return;
+        };
+
+        let instruction_pointer = parse_memory_address(&memory_reference).ok();
+        self.instruction_pointer = instruction_pointer;
+
+        // Instruction stepping usually stays within the instructions we already have.
+        if instruction_pointer.is_some() && self.instruction_pointer_ix().is_some() {
+            self.scroll_to_instruction_pointer();
+            cx.notify();
+            return;
+        }
+
+        let task = self.session.update(cx, |session, cx| {
+            session.disassemble(
+                memory_reference,
+                -(INSTRUCTIONS_BEFORE as i64),
+                INSTRUCTION_COUNT,
+                cx,
+            )
+        });
+        self._fetch_task = cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(instructions) => {
+                        this.instructions = instructions;
+                        this.error = None;
+                    }
+                    Err(error) => {
+                        this.instructions.clear();
+                        this.error = Some(format!("{error:#}").into());
+                    }
+                }
+                this.scroll_to_instruction_pointer();
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn instruction_pointer_ix(&self) -> Option<usize> {
+        let instruction_pointer = self.instruction_pointer?;
+        self.instructions.iter().position(|instruction| {
+            parse_memory_address(&instruction.address).ok() == Some(instruction_pointer)
+        })
+    }
+
+    fn scroll_to_instruction_pointer(&mut self) {
+        if let Some(ix) = self.instruction_pointer_ix() {
+            self.scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Center);
+        }
+    }
+
+    fn is_stopped(&self, cx: &App) -> bool {
+        self.state
+            .read_with(cx, |state, cx| state.thread_status(cx))
+            .ok()
+            .flatten()
+            == Some(ThreadStatus::Stopped)
+    }
+
+    fn step_over(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self
+            .state
+            .read_with(cx, |state, _| state.thread_id())
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+
+        self.session.update(cx, |session, cx| {
+            session.step_over(thread_id, SteppingGranularity::Instruction, cx);
+        });
+    }
+
+    fn step_in(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self
+            .state
+            .read_with(cx, |state, _| state.thread_id())
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+
+        self.session.update(cx, |session, cx| {
+            session.step_in(thread_id, SteppingGranularity::Instruction, cx);
+        });
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let instruction = &self.instructions[ix];
+        let is_current = self.instruction_pointer.is_some()
+            && parse_memory_address(&instruction.address).ok() == self.instruction_pointer;
+
+        h_flex()
+            .id(("disassembly-instruction", ix))
+            .w_full()
+            .px_1()
+            .gap_3()
+            .font_buffer(cx)
+            .text_ui_sm(cx)
+            .when(is_current, |this| {
+                this.bg(cx.theme().colors().editor_debugger_active_line_background)
+            })
+            .child(
+                div()
+                    .flex_none()
+                    .text_color(cx.theme().colors().text_muted)
+                    .child(instruction.address.clone()),
+            )
+            .when_some(instruction.instruction_bytes.clone(), |this, bytes| {
+                this.child(
+                    div()
+                        .flex_none()
+                        .min_w(rems(10.))
+                        .text_color(cx.theme().colors().text_muted)
+                        .child(bytes),
+                )
+            })
+            .child(div().flex_1().child(instruction.instruction.clone()))
+            .when_some(instruction.symbol.clone(), |this, symbol| {
+                this.child(
+                    div()
+                        .flex_none()
+                        .text_color(cx.theme().colors().text_muted)
+                        .child(symbol),
+                )
+            })
+            .into_any()
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_stopped = self.is_stopped(cx);
+
+        h_flex()
+            .w_full()
+            .px_1()
+            .gap_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                Label::new(
+                    self.instruction_pointer
+                        .map(|address| format!("{address:#x}"))
+                        .unwrap_or_default(),
+                )
+                .size(LabelSize::Small)
+                .color(Color::Muted),
+            )
+            .child(
+                h_flex()
+                    .gap_0p5()
+                    .child(
+                        IconButton::new("disassembly-step-over", IconName::DebugStepOver)
+                            .icon_size(IconSize::XSmall)
+                            .disabled(!is_stopped)
+                            .on_click(cx.listener(|this, _, _, cx| this.step_over(cx)))
+                            .tooltip(Tooltip::text("Step Over Instruction")),
+                    )
+                    .child(
+                        IconButton::new("disassembly-step-into", IconName::DebugStepInto)
+                            .icon_size(IconSize::XSmall)
+                            .disabled(!is_stopped)
+                            .on_click(cx.listener(|this, _, _, cx| this.step_in(cx)))
+                            .tooltip(Tooltip::text("Step Into Instruction")),
+                    ),
+            )
+    }
+
+    fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Stateful<Div> {
+        div()
+            .occlude()
+            .id("disassembly-view-vertical-scrollbar")
+            .on_mouse_move(cx.listener(|_, _, _, cx| {
+                cx.notify();
+                cx.stop_propagation()
+            }))
+            .on_hover(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|_, _, _, cx| {
+                    cx.stop_propagation();
+                }),
+            )
+            .on_scroll_wheel(cx.listener(|_, _, _, cx| {
+                cx.notify();
+            }))
+            .h_full()
+            .absolute()
+            .right_1()
+            .top_1()
+            .bottom_0()
+            .w(px(12.))
+            .cursor_default()
+            .children(Scrollbar::vertical(self.scrollbar_state.clone()))
+    }
+
+    fn render_list(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        uniform_list(
+            "disassembly-view",
+            self.instructions.len(),
+            cx.processor(|this, range: Range<usize>, _window, cx| {
+                range.map(|ix| this.render_entry(ix, cx)).collect()
+            }),
+        )
+        .track_scroll(self.scroll_handle.clone())
+        .size_full()
+    }
+}
+
+impl Focusable for DisassemblyView {
+    fn focus_handle(&self, _: &gpui::App) -> gpui::FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DisassemblyView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_1()
+            .child(self.render_header(cx))
+            .map(|this| {
+                if let Some(error) = self.error.clone() {
+                    this.child(
+                        div()
+                            .p_1()
+                            .child(Label::new(error).size(LabelSize::Small).color(Color::Error)),
+                    )
+                } else {
+                    this.child(
+                        div()
+                            .relative()
+                            .size_full()
+                            .child(self.render_list(window, cx))
+                            .child(self.render_vertical_scrollbar(cx)),
+                    )
+                }
+            })
+    }
+}
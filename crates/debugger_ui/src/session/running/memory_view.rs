@@ -0,0 +1,430 @@
+use anyhow::{Context as _, Result};
+use editor::Editor;
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, MouseButton, Stateful, Subscription, Task,
+    UniformListScrollHandle, uniform_list,
+};
+use project::debugger::session::{MemoryContents, Session, SessionEvent};
+use std::ops::Range;
+use ui::{Scrollbar, ScrollbarState, Tooltip, prelude::*};
+
+/// How many bytes are read at a time.
+const PAGE_SIZE: u64 = 1024;
+const BYTES_PER_ROW: usize = 16;
+
+pub(crate) struct MemoryView {
+    session: Entity<Session>,
+    focus_handle: FocusHandle,
+    memory_reference: Option<String>,
+    label: Option<SharedString>,
+    /// Offset of the current page from `memory_reference`, in bytes.
+    offset: i64,
+    contents: Option<MemoryContents>,
+    error: Option<SharedString>,
+    selected_byte: Option<usize>,
+    write_editor: Entity<Editor>,
+    write_error: Option<SharedString>,
+    scroll_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+    _fetch_task: Task<()>,
+    _subscription: Subscription,
+}
+
+impl MemoryView {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let focus_handle = cx.focus_handle();
+
+        let _subscription = cx.subscribe(&session, |this, _, event, cx| match event {
+            SessionEvent::Stopped(_) | SessionEvent::Memory => this.schedule_fetch(cx),
+            _ => {}
+        });
+
+        let write_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Bytes to write at the selection, e.g. de ad be ef", cx);
+            editor
+        });
+
+        let scroll_handle = UniformListScrollHandle::new();
+
+        Self {
+            scrollbar_state: ScrollbarState::new(scroll_handle.clone()),
+            scroll_handle,
+            session,
+            focus_handle,
+            memory_reference: None,
+            label: None,
+            offset: 0,
+            contents: None,
+            error: None,
+            selected_byte: None,
+            write_editor,
+            write_error: None,
+            _fetch_task: Task::ready(()),
+            _subscription,
+        }
+    }
+
+    /// Shows the memory starting at `memory_reference`, as reported by the debug adapter for
+    /// variables, evaluation results and stack frames.
+    pub(crate) fn open(
+        &mut self,
+        memory_reference: String,
+        label: SharedString,
+        cx: &mut Context<Self>,
+    ) {
+        self.memory_reference = Some(memory_reference);
+        self.label = Some(label);
+        self.offset = 0;
+        self.contents = None;
+        self.selected_byte = None;
+        self.write_error = None;
+        self.schedule_fetch(cx);
+    }
+
+    fn schedule_fetch(&mut self, cx: &mut Context<Self>) {
+        let Some(memory_reference) = self.memory_reference.clone() else {
+            return;
+        };
+
+        let offset = self.offset;
+        let task = self.session.update(cx, |session, cx| {
+            session.read_memory(memory_reference, offset, PAGE_SIZE, cx)
+        });
+        self._fetch_task = cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(contents) => {
+                        this.contents = Some(contents);
+                        this.error = None;
+                    }
+                    Err(error) => {
+                        this.contents = None;
+                        this.error = Some(format!("{error:#}").into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn go_to_page(&mut self, delta: i64, cx: &mut Context<Self>) {
+        self.offset = self.offset.saturating_add(delta * PAGE_SIZE as i64);
+        self.selected_byte = None;
+        self.write_error = None;
+        self.schedule_fetch(cx);
+    }
+
+    fn byte_count(&self) -> usize {
+        self.contents.as_ref().map_or(0, |contents| {
+            (contents.data.len() as u64 + contents.unreadable_bytes).min(PAGE_SIZE) as usize
+        })
+    }
+
+    fn supports_write_memory(&self, cx: &App) -> bool {
+        self.session
+            .read(cx)
+            .capabilities()
+            .supports_write_memory_request
+            .unwrap_or_default()
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(memory_reference), Some(selected_byte)) =
+            (self.memory_reference.clone(), self.selected_byte)
+        else {
+            return;
+        };
+
+        let data = match parse_hex_bytes(&self.write_editor.read(cx).text(cx)) {
+            Ok(data) => data,
+            Err(error) => {
+                self.write_error = Some(format!("{error:#}").into());
+                cx.notify();
+                return;
+            }
+        };
+
+        let offset = self.offset + selected_byte as i64;
+        let task = self.session.update(cx, |session, cx| {
+            session.write_memory(memory_reference, offset, data, cx)
+        });
+        self.write_editor
+            .update(cx, |editor, cx| editor.clear(window, cx));
+        self.write_error = None;
+
+        cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                this.write_error = result.err().map(|error| format!("{error:#}").into());
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn render_row(&self, row: usize, cx: &mut Context<Self>) -> AnyElement {
+        let Some(contents) = self.contents.as_ref() else {
+            return div().into_any();
+        };
+
+        let start = row * BYTES_PER_ROW;
+        let end = (start + BYTES_PER_ROW).min(self.byte_count());
+        let address = contents.address.wrapping_add(start as u64);
+
+        let mut ascii = String::with_capacity(BYTES_PER_ROW);
+        let bytes = (start..end)
+            .map(|ix| {
+                let byte = contents.data.get(ix).copied();
+                ascii.push(match byte {
+                    Some(byte) if byte.is_ascii_graphic() || byte == b' ' => byte as char,
+                    _ => '.',
+                });
+
+                div()
+                    .id(("memory-byte", ix))
+                    .px_0p5()
+                    .rounded_xs()
+                    .cursor_pointer()
+                    .hover(|this| this.bg(cx.theme().colors().element_hover))
+                    .when(self.selected_byte == Some(ix), |this| {
+                        this.bg(cx.theme().colors().element_selected)
+                    })
+                    .when(byte.is_none(), |this| {
+                        this.text_color(cx.theme().colors().text_disabled)
+                    })
+                    .child(byte.map_or_else(|| "??".to_string(), |byte| format!("{byte:02x}")))
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.selected_byte = Some(ix);
+                        this.write_error = None;
+                        cx.notify();
+                    }))
+            })
+            .collect::<Vec<_>>();
+
+        h_flex()
+            .w_full()
+            .px_1()
+            .gap_3()
+            .font_buffer(cx)
+            .text_ui_sm(cx)
+            .child(
+                div()
+                    .flex_none()
+                    .text_color(cx.theme().colors().text_muted)
+                    .child(format!("{address:016x}")),
+            )
+            .child(h_flex().flex_none().children(bytes))
+            .child(
+                div()
+                    .text_color(cx.theme().colors().text_muted)
+                    .child(ascii),
+            )
+            .into_any()
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let has_memory = self.memory_reference.is_some();
+        let range = self.contents.as_ref().map(|contents| {
+            format!(
+                "{:#x}..{:#x}",
+                contents.address,
+                contents.address.wrapping_add(self.byte_count() as u64)
+            )
+        });
+
+        h_flex()
+            .w_full()
+            .px_1()
+            .gap_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .when_some(self.label.clone(), |this, label| {
+                        this.child(Label::new(label).size(LabelSize::Small))
+                    })
+                    .when_some(range, |this, range| {
+                        this.child(Label::new(range).size(LabelSize::Small).color(Color::Muted))
+                    }),
+            )
+            .child(
+                h_flex()
+                    .gap_0p5()
+                    .child(
+                        IconButton::new("memory-previous-page", IconName::ChevronLeft)
+                            .icon_size(IconSize::XSmall)
+                            .disabled(!has_memory)
+                            .on_click(cx.listener(|this, _, _, cx| this.go_to_page(-1, cx)))
+                            .tooltip(Tooltip::text("Previous Page")),
+                    )
+                    .child(
+                        IconButton::new("memory-next-page", IconName::ChevronRight)
+                            .icon_size(IconSize::XSmall)
+                            .disabled(!has_memory)
+                            .on_click(cx.listener(|this, _, _, cx| this.go_to_page(1, cx)))
+                            .tooltip(Tooltip::text("Next Page")),
+                    ),
+            )
+    }
+
+    fn render_write_editor(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w_full()
+            .px_1()
+            .pt_1()
+            .gap_0p5()
+            .border_t_1()
+            .border_color(cx.theme().colors().border_variant)
+            .on_action(cx.listener(Self::confirm))
+            .child(self.write_editor.clone())
+            .when_some(self.write_error.clone(), |this, error| {
+                this.child(Label::new(error).size(LabelSize::Small).color(Color::Error))
+            })
+    }
+
+    fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Stateful<Div> {
+        div()
+            .occlude()
+            .id("memory-view-vertical-scrollbar")
+            .on_mouse_move(cx.listener(|_, _, _, cx| {
+                cx.notify();
+                cx.stop_propagation()
+            }))
+            .on_hover(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|_, _, _, cx| {
+                    cx.stop_propagation();
+                }),
+            )
+            .on_scroll_wheel(cx.listener(|_, _, _, cx| {
+                cx.notify();
+            }))
+            .h_full()
+            .absolute()
+            .right_1()
+            .top_1()
+            .bottom_0()
+            .w(px(12.))
+            .cursor_default()
+            .children(Scrollbar::vertical(self.scrollbar_state.clone()))
+    }
+
+    fn render_list(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        uniform_list(
+            "memory-view",
+            self.byte_count().div_ceil(BYTES_PER_ROW),
+            cx.processor(|this, range: Range<usize>, _window, cx| {
+                range.map(|row| this.render_row(row, cx)).collect()
+            }),
+        )
+        .track_scroll(self.scroll_handle.clone())
+        .size_full()
+    }
+}
+
+impl Focusable for MemoryView {
+    fn focus_handle(&self, _: &gpui::App) -> gpui::FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for MemoryView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let show_write_editor = self.selected_byte.is_some() && self.supports_write_memory(cx);
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_1()
+            .child(self.render_header(cx))
+            .map(|this| {
+                if let Some(error) = self.error.clone() {
+                    this.child(
+                        div()
+                            .p_1()
+                            .child(Label::new(error).size(LabelSize::Small).color(Color::Error)),
+                    )
+                } else if self.memory_reference.is_none() {
+                    this.child(
+                        div().p_1().child(
+                            Label::new("Open a variable's memory from the variable list")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        div()
+                            .relative()
+                            .size_full()
+                            .child(self.render_list(window, cx))
+                            .child(self.render_vertical_scrollbar(cx)),
+                    )
+                }
+            })
+            .when(show_write_editor, |this| {
+                this.child(self.render_write_editor(cx))
+            })
+    }
+}
+
+/// Parses bytes written as hexadecimal, e.g. `de ad be ef`, `deadbeef` or `0xde 0xad`.
+fn parse_hex_bytes(text: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    for token in text.split_whitespace() {
+        let digits = token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"))
+            .unwrap_or(token);
+        anyhow::ensure!(
+            !digits.is_empty() && digits.len() % 2 == 0,
+            "Invalid hex bytes: {token}"
+        );
+        for ix in (0..digits.len()).step_by(2) {
+            let byte = digits
+                .get(ix..ix + 2)
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                .with_context(|| format!("Invalid hex bytes: {token}"))?;
+            bytes.push(byte);
+        }
+    }
+    anyhow::ensure!(!bytes.is_empty(), "No bytes to write");
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_bytes() {
+        assert_eq!(
+            parse_hex_bytes("de ad be ef").unwrap(),
+            vec![0xde, 0xad, 0xbe, 0xef]
+        );
+        assert_eq!(
+            parse_hex_bytes("DEADbeef").unwrap(),
+            vec![0xde, 0xad, 0xbe, 0xef]
+        );
+        assert_eq!(parse_hex_bytes("0x01 0X02").unwrap(), vec![0x01, 0x02]);
+        assert!(parse_hex_bytes("abc").is_err());
+        assert!(parse_hex_bytes("zz").is_err());
+        assert!(parse_hex_bytes("  ").is_err());
+    }
+}
@@ -13,7 +13,7 @@ use util::debug_panic;
 use crate::StackTraceView;
 use language::PointUtf16;
 use project::debugger::breakpoint_store::ActiveStackFrame;
-use project::debugger::session::{Session, SessionEvent, StackFrame};
+use project::debugger::session::{ExceptionInfo, Session, SessionEvent, StackFrame, ThreadId};
 use project::{ProjectItem, ProjectPath};
 use ui::{Scrollbar, ScrollbarState, Tooltip, prelude::*};
 use workspace::{ItemHandle, Workspace};
@@ -67,6 +67,9 @@ impl StackFrameList {
                 SessionEvent::Stopped(..) | SessionEvent::StackTrace => {
                     this.schedule_refresh(true, window, cx);
                 }
+                SessionEvent::ExceptionInfo(_) => {
+                    this.update_active_exception(cx);
+                }
                 _ => {}
             });
 
@@ -140,6 +143,20 @@ impl StackFrameList {
         self.opened_stack_frame_id
     }
 
+    /// Returns the opened stack frame, falling back to the top frame when none was opened
+    /// (e.g. because it has no source).
+    pub(crate) fn opened_stack_frame(&self, cx: &mut App) -> Option<dap::StackFrame> {
+        let stack_frames = self.stack_frames(cx).ok()?;
+        self.opened_stack_frame_id
+            .and_then(|id| {
+                stack_frames
+                    .iter()
+                    .find(|stack_frame| stack_frame.dap.id == id)
+            })
+            .or_else(|| stack_frames.first())
+            .map(|stack_frame| stack_frame.dap.clone())
+    }
+
     pub(super) fn schedule_refresh(
         &mut self,
         select_first: bool,
@@ -353,6 +370,7 @@ impl StackFrameList {
                     state.thread_id.context("No selected thread ID found")
                 })??;
 
+                let exception = this.exception_for_stack_frame(thread_id, stack_frame_id, cx);
                 this.workspace.update(cx, |workspace, cx| {
                     let breakpoint_store = workspace.project().read(cx).breakpoint_store();
 
@@ -364,6 +382,7 @@ impl StackFrameList {
                                 stack_frame_id,
                                 path: abs_path,
                                 position,
+                                exception,
                             },
                             cx,
                         );
@@ -373,6 +392,60 @@ impl StackFrameList {
         })
     }
 
+    /// Returns the exception `thread_id` stopped on if it was raised in the given stack frame.
+    fn exception_for_stack_frame(
+        &self,
+        thread_id: ThreadId,
+        stack_frame_id: StackFrameId,
+        cx: &App,
+    ) -> Option<ExceptionInfo> {
+        let exception = self.session.read(cx).exception_info(thread_id)?;
+        // Like when opening the first stack frame, frames without source (e.g. in the runtime) are skipped.
+        let throwing_stack_frame_id = self.entries.iter().find_map(|entry| match entry {
+            StackFrameEntry::Normal(stack_frame)
+                if stack_frame
+                    .source
+                    .as_ref()
+                    .is_some_and(|source| source.path.is_some()) =>
+            {
+                Some(stack_frame.id)
+            }
+            _ => None,
+        })?;
+        (throwing_stack_frame_id == stack_frame_id).then(|| exception.clone())
+    }
+
+    /// Exception details usually arrive after the stack frame was opened, so update the active one.
+    fn update_active_exception(&mut self, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let breakpoint_store = workspace.read(cx).project().read(cx).breakpoint_store();
+        let Some(active_stack_frame) = breakpoint_store.read(cx).active_position().cloned() else {
+            return;
+        };
+        if active_stack_frame.session_id != self.session.read(cx).session_id() {
+            return;
+        }
+
+        let exception = self.exception_for_stack_frame(
+            active_stack_frame.thread_id,
+            active_stack_frame.stack_frame_id,
+            cx,
+        );
+        if exception != active_stack_frame.exception {
+            breakpoint_store.update(cx, |store, cx| {
+                store.set_active_position(
+                    ActiveStackFrame {
+                        exception,
+                        ..active_stack_frame
+                    },
+                    cx,
+                );
+            });
+        }
+    }
+
     pub(crate) fn abs_path_from_stack_frame(stack_frame: &dap::StackFrame) -> Option<Arc<Path>> {
         stack_frame.source.as_ref().and_then(|s| {
             s.path
@@ -6,14 +6,16 @@ use dap::{
 use editor::Editor;
 use gpui::{
     Action, AnyElement, ClickEvent, ClipboardItem, Context, DismissEvent, Empty, Entity,
-    FocusHandle, Focusable, Hsla, MouseButton, MouseDownEvent, Point, Stateful, Subscription,
-    TextStyleRefinement, UniformListScrollHandle, actions, anchored, deferred, uniform_list,
+    EventEmitter, FocusHandle, Focusable, Hsla, MouseButton, MouseDownEvent, Point, Stateful,
+    Subscription, TextStyleRefinement, UniformListScrollHandle, actions, anchored, deferred,
+    uniform_list,
 };
 use menu::{SelectFirst, SelectLast, SelectNext, SelectPrevious};
 use project::debugger::session::{Session, SessionEvent, Watcher};
 use std::{collections::HashMap, ops::Range, sync::Arc};
 use ui::{ContextMenu, ListItem, ScrollableHandle, Scrollbar, ScrollbarState, Tooltip, prelude::*};
 use util::debug_panic;
+use workspace::notifications::DetachAndPromptErr as _;
 
 actions!(
     variable_list,
@@ -32,9 +34,20 @@ actions!(
         AddWatch,
         /// Removes the selected variable from the watch list.
         RemoveWatch,
+        /// Opens the memory the selected variable refers to in the memory view.
+        ViewMemory,
+        /// Adds a data breakpoint that stops when the selected variable changes.
+        BreakOnValueChange,
     ]
 );
 
+pub(crate) enum VariableListEvent {
+    ViewMemory {
+        memory_reference: String,
+        name: SharedString,
+    },
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub(crate) struct EntryState {
     depth: usize,
@@ -575,6 +588,21 @@ impl VariableList {
             .capabilities()
             .supports_set_variable
             .unwrap_or_default();
+        let supports_read_memory = self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_read_memory_request
+            .unwrap_or_default();
+        let supports_data_breakpoints = self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_data_breakpoints
+            .unwrap_or_default();
+        let has_memory_reference = entry
+            .as_variable()
+            .is_some_and(|variable| variable.memory_reference.is_some());
 
         let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
             menu.when(entry.as_variable().is_some(), |menu| {
@@ -584,6 +612,12 @@ impl VariableList {
                         menu.action("Edit Value", EditVariable.boxed_clone())
                     })
                     .action("Watch Variable", AddWatch.boxed_clone())
+                    .when(supports_read_memory && has_memory_reference, |menu| {
+                        menu.action("View Memory", ViewMemory.boxed_clone())
+                    })
+                    .when(supports_data_breakpoints, |menu| {
+                        menu.action("Break When Value Changes", BreakOnValueChange.boxed_clone())
+                    })
             })
             .when(entry.as_watcher().is_some(), |menu| {
                 menu.action("Copy Name", CopyVariableName.boxed_clone())
@@ -736,6 +770,65 @@ impl VariableList {
         self.build_entries(cx);
     }
 
+    fn view_memory(&mut self, _: &ViewMemory, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(selection) = self.selection.as_ref() else {
+            return;
+        };
+
+        let Some(variable) = self
+            .entries
+            .iter()
+            .find(|entry| &entry.path == selection)
+            .and_then(|entry| entry.as_variable())
+        else {
+            return;
+        };
+
+        let Some(memory_reference) = variable.memory_reference.clone() else {
+            return;
+        };
+
+        cx.emit(VariableListEvent::ViewMemory {
+            memory_reference,
+            name: variable.name.clone().into(),
+        });
+    }
+
+    fn break_on_value_change(
+        &mut self,
+        _: &BreakOnValueChange,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(selection) = self.selection.as_ref() else {
+            return;
+        };
+
+        let Some(variable) = self
+            .entries
+            .iter()
+            .find(|entry| &entry.path == selection)
+            .and_then(|entry| entry.as_variable())
+        else {
+            return;
+        };
+
+        let Some(state) = self.entry_states.get(selection) else {
+            return;
+        };
+
+        let variables_reference = state.parent_reference;
+        let name = variable.name.clone();
+
+        self.session
+            .update(cx, |session, cx| {
+                session.add_data_breakpoint(variables_reference, name, cx)
+            })
+            .detach_and_prompt_err("Failed to add a data breakpoint", window, cx, |e, _, _| {
+                Some(e.to_string())
+            });
+    }
+
     #[track_caller]
     #[cfg(test)]
     pub(crate) fn assert_visual_entries(&self, expected: Vec<&str>) {
@@ -1336,6 +1429,8 @@ impl Focusable for VariableList {
     }
 }
 
+impl EventEmitter<VariableListEvent> for VariableList {}
+
 impl Render for VariableList {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         v_flex()
@@ -1358,6 +1453,8 @@ impl Render for VariableList {
             .on_action(cx.listener(Self::edit_variable))
             .on_action(cx.listener(Self::add_watcher))
             .on_action(cx.listener(Self::remove_watcher))
+            .on_action(cx.listener(Self::view_memory))
+            .on_action(cx.listener(Self::break_on_value_change))
             .child(
                 uniform_list(
                     "variable-list",
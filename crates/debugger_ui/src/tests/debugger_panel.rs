@@ -9,8 +9,9 @@ use dap::{
     adapters::DebugTaskDefinition,
     client::SessionId,
     requests::{
-        Continue, Disconnect, Launch, Next, RunInTerminal, SetBreakpoints, StackTrace,
-        StartDebugging, StepBack, StepIn, StepOut, Threads,
+        Continue, DataBreakpointInfo, Disconnect, ExceptionInfo, Launch, Next, RunInTerminal,
+        SetBreakpoints, SetDataBreakpoints, SetFunctionBreakpoints, StackTrace, StartDebugging,
+        StepBack, StepIn, StepOut, Threads,
     },
 };
 use editor::{
@@ -26,7 +27,7 @@ use serde_json::json;
 use std::{
     path::Path,
     sync::{
-        Arc,
+        Arc, Mutex,
         atomic::{AtomicBool, Ordering},
     },
 };
@@ -1361,6 +1362,357 @@ async fn test_unsetting_breakpoints_on_clear_breakpoint_action(
     cx.run_until_parked();
 }
 
+#[gpui::test]
+async fn test_function_breakpoints_are_sent_to_the_adapter(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let breakpoint_store = project.update(cx, |project, _| project.breakpoint_store());
+
+    breakpoint_store.update(cx, |breakpoint_store, cx| {
+        breakpoint_store.add_function_breakpoint("main".into(), cx);
+    });
+
+    let sent_function_breakpoints = Arc::new(Mutex::new(Vec::new()));
+
+    let _session = start_debug_session(&workspace, cx, {
+        let sent_function_breakpoints = sent_function_breakpoints.clone();
+        move |client| {
+            client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+                Ok(dap::Capabilities {
+                    supports_function_breakpoints: Some(true),
+                    ..Default::default()
+                })
+            });
+            let sent_function_breakpoints = sent_function_breakpoints.clone();
+            client.on_request::<SetFunctionBreakpoints, _>(move |_, args| {
+                sent_function_breakpoints.lock().unwrap().push(
+                    args.breakpoints
+                        .into_iter()
+                        .map(|breakpoint| breakpoint.name)
+                        .collect::<Vec<_>>(),
+                );
+                Ok(dap::SetFunctionBreakpointsResponse {
+                    breakpoints: Vec::default(),
+                })
+            });
+        }
+    })
+    .unwrap();
+
+    cx.run_until_parked();
+
+    assert_eq!(
+        sent_function_breakpoints
+            .lock()
+            .unwrap()
+            .drain(..)
+            .collect::<Vec<_>>(),
+        vec![vec!["main".to_string()]],
+        "Function breakpoints should be sent during the configuration sequence"
+    );
+
+    breakpoint_store.update(cx, |breakpoint_store, cx| {
+        breakpoint_store.add_function_breakpoint("helper".into(), cx);
+    });
+    cx.run_until_parked();
+
+    breakpoint_store.update(cx, |breakpoint_store, cx| {
+        breakpoint_store.edit_function_breakpoint(
+            "main",
+            project::debugger::breakpoint_store::BreakpointEditAction::InvertState,
+            cx,
+        );
+    });
+    cx.run_until_parked();
+
+    assert_eq!(
+        sent_function_breakpoints
+            .lock()
+            .unwrap()
+            .drain(..)
+            .collect::<Vec<_>>(),
+        vec![
+            vec!["main".to_string(), "helper".to_string()],
+            vec!["helper".to_string()],
+        ],
+        "Disabled function breakpoints should not be sent"
+    );
+
+    cx.dispatch_action(crate::ClearAllBreakpoints);
+    cx.run_until_parked();
+
+    assert_eq!(
+        sent_function_breakpoints
+            .lock()
+            .unwrap()
+            .drain(..)
+            .collect::<Vec<_>>(),
+        vec![Vec::<String>::new()],
+    );
+}
+
+#[gpui::test]
+async fn test_data_breakpoints_are_sent_to_the_adapter(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let breakpoint_store = project.update(cx, |project, _| project.breakpoint_store());
+
+    let sent_data_breakpoints = Arc::new(Mutex::new(Vec::new()));
+
+    let session = start_debug_session(&workspace, cx, {
+        let sent_data_breakpoints = sent_data_breakpoints.clone();
+        move |client| {
+            client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+                Ok(dap::Capabilities {
+                    supports_data_breakpoints: Some(true),
+                    ..Default::default()
+                })
+            });
+            client.on_request::<DataBreakpointInfo, _>(move |_, args| {
+                assert_eq!(args.variables_reference, Some(7));
+                let access_types = if args.name == "counter" {
+                    vec![
+                        dap::DataBreakpointAccessType::Read,
+                        dap::DataBreakpointAccessType::Write,
+                    ]
+                } else {
+                    vec![dap::DataBreakpointAccessType::Read]
+                };
+                Ok(dap::DataBreakpointInfoResponse {
+                    data_id: Some(format!("0x7ffc0000:{}", args.name)),
+                    description: args.name,
+                    access_types: Some(access_types),
+                    can_persist: Some(false),
+                })
+            });
+            let sent_data_breakpoints = sent_data_breakpoints.clone();
+            client.on_request::<SetDataBreakpoints, _>(move |_, args| {
+                sent_data_breakpoints.lock().unwrap().push(
+                    args.breakpoints
+                        .into_iter()
+                        .map(|breakpoint| (breakpoint.data_id, breakpoint.access_type))
+                        .collect::<Vec<_>>(),
+                );
+                Ok(dap::SetDataBreakpointsResponse {
+                    breakpoints: Vec::default(),
+                })
+            });
+            client.on_request::<Disconnect, _>(move |_, _| Ok(()));
+        }
+    })
+    .unwrap();
+
+    cx.run_until_parked();
+    sent_data_breakpoints.lock().unwrap().clear();
+
+    session
+        .update(cx, |session, cx| {
+            session.add_data_breakpoint(7, "counter".into(), cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    assert_eq!(
+        sent_data_breakpoints
+            .lock()
+            .unwrap()
+            .drain(..)
+            .collect::<Vec<_>>(),
+        vec![vec![(
+            "0x7ffc0000:counter".to_string(),
+            Some(dap::DataBreakpointAccessType::Write)
+        )]],
+        "Data breakpoints should watch for writes when the adapter supports it"
+    );
+
+    let result = session
+        .update(cx, |session, cx| {
+            session.add_data_breakpoint(7, "constant".into(), cx)
+        })
+        .await;
+    cx.run_until_parked();
+    assert!(
+        result.is_err(),
+        "Values that can only be watched for reads shouldn't get a data breakpoint"
+    );
+    assert!(sent_data_breakpoints.lock().unwrap().is_empty());
+
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+    shutdown_session.await.unwrap();
+    cx.run_until_parked();
+
+    assert!(
+        breakpoint_store.read_with(cx, |breakpoint_store, _| breakpoint_store
+            .data_breakpoints()
+            .is_empty()),
+        "Data IDs that can't persist should be dropped with their session"
+    );
+    assert!(
+        sent_data_breakpoints.lock().unwrap().is_empty(),
+        "Nothing should be sent to an adapter that is shutting down"
+    );
+}
+
+#[gpui::test]
+async fn test_exception_info_is_fetched_when_stopped_on_exception(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_exception_info_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<ExceptionInfo, _>(move |_, args| {
+        assert_eq!(args.thread_id, 1);
+        Ok(dap::ExceptionInfoResponse {
+            exception_id: "ValueError".into(),
+            description: Some("invalid literal".into()),
+            break_mode: dap::ExceptionBreakMode::Always,
+            details: Some(dap::ExceptionDetails {
+                message: Some("invalid literal for int()".into()),
+                type_name: Some("ValueError".into()),
+                full_type_name: None,
+                evaluate_name: None,
+                stack_trace: Some("File \"main.py\", line 2".into()),
+                inner_exception: Some(vec![dap::ExceptionDetails {
+                    message: Some("not a digit".into()),
+                    type_name: Some("KeyError".into()),
+                    full_type_name: None,
+                    evaluate_name: None,
+                    stack_trace: None,
+                    inner_exception: None,
+                }]),
+            }),
+        })
+    });
+
+    cx.run_until_parked();
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Exception,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    session.update(cx, |session, _| {
+        assert_eq!(
+            session.exception_info(ThreadId(1)),
+            Some(&project::debugger::session::ExceptionInfo {
+                type_name: "ValueError".into(),
+                message: Some("invalid literal for int()".into()),
+                stack_trace: Some("File \"main.py\", line 2".into()),
+                inner_exceptions: vec![project::debugger::session::ExceptionInfo {
+                    type_name: "KeyError".into(),
+                    message: Some("not a digit".into()),
+                    stack_trace: None,
+                    inner_exceptions: Vec::new(),
+                }],
+            })
+        );
+    });
+
+    client
+        .fake_event(dap::messages::Events::Continued(dap::ContinuedEvent {
+            thread_id: 1,
+            all_threads_continued: Some(true),
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    session.update(cx, |session, _| {
+        assert_eq!(
+            session.exception_info(ThreadId(1)),
+            None,
+            "Exception info should be cleared once the thread continues"
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_debug_session_is_shutdown_when_attach_and_launch_request_fails(
     executor: BackgroundExecutor,
@@ -2,13 +2,14 @@ use std::iter::zip;
 
 use crate::{
     debugger_panel::DebugPanel,
-    persistence::SerializedPaneLayout,
+    persistence::{DEBUGGER_DB, SerializedDebugScenario, SerializedPaneLayout},
     tests::{init_test, init_test_workspace, start_debug_session},
 };
 use dap::{StoppedEvent, StoppedEventReason, messages::Events};
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
 use project::{FakeFs, Project};
 use serde_json::json;
+use task::{DebugScenario, TaskContext, TaskVariables, VariableName};
 use util::path;
 use workspace::{Panel, dock::DockPosition};
 
@@ -129,3 +130,62 @@ async fn test_invert_axis_on_panel_position_change(
         }
     }
 }
+
+#[gpui::test]
+async fn test_recent_debug_scenarios_are_persisted() {
+    let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+
+    let scenario = |label: &str| DebugScenario {
+        adapter: "fake-adapter".into(),
+        label: label.to_owned().into(),
+        build: None,
+        config: json!({ "program": "$ZED_WORKTREE_ROOT/main" }),
+        tcp_connection: None,
+    };
+    let task_context = TaskContext {
+        cwd: Some("/project".into()),
+        task_variables: TaskVariables::from_iter([
+            (VariableName::WorktreeRoot, "/project".to_owned()),
+            (
+                VariableName::Custom("RUST_PACKAGE".into()),
+                "main".to_owned(),
+            ),
+        ]),
+        project_env: [("SECRET".to_owned(), "value".to_owned())]
+            .into_iter()
+            .collect(),
+    };
+
+    for label in ["First", "Second", "First"] {
+        DEBUGGER_DB
+            .save_debug_scenario(
+                workspace_id,
+                SerializedDebugScenario::new(
+                    scenario(label),
+                    Some("/project".into()),
+                    &task_context,
+                ),
+            )
+            .await
+            .unwrap();
+    }
+
+    let scenarios = DEBUGGER_DB.debug_scenarios(workspace_id).unwrap();
+    assert_eq!(
+        scenarios
+            .iter()
+            .map(|scenario| scenario.scenario.label.as_ref())
+            .collect::<Vec<_>>(),
+        vec!["Second", "First"],
+        "Rerunning a scenario should move it to the end of the list"
+    );
+    assert_eq!(scenarios[1].scenario, scenario("First"));
+    assert_eq!(
+        scenarios[1].task_context(),
+        TaskContext {
+            project_env: Default::default(),
+            ..task_context
+        },
+        "Everything but the project environment should be restored"
+    );
+}
@@ -400,6 +400,10 @@ actions!(
         Fold,
         /// Folds all foldable regions in the editor.
         FoldAll,
+        /// Folds all comments reported as foldable by language servers.
+        FoldAllComments,
+        /// Folds all import blocks reported as foldable by language servers.
+        FoldAllImports,
         /// Folds all function bodies in the editor.
         FoldFunctionBodies,
         /// Folds the current code block and all its children.
@@ -53,7 +53,7 @@ use multi_buffer::{
     Anchor, AnchorRangeExt, ExcerptId, MultiBuffer, MultiBufferPoint, MultiBufferRow,
     MultiBufferSnapshot, RowInfo, ToOffset, ToPoint,
 };
-use project::project_settings::DiagnosticSeverity;
+use project::{FoldingRange, project_settings::DiagnosticSeverity};
 use serde::Deserialize;
 
 use std::{
@@ -90,6 +90,7 @@ pub trait ToDisplayPoint {
 
 type TextHighlights = TreeMap<HighlightKey, Arc<(HighlightStyle, Vec<Range<Anchor>>)>>;
 type InlayHighlights = TreeMap<TypeId, TreeMap<InlayId, (HighlightStyle, InlayHighlight)>>;
+type LspFoldingRanges = TreeMap<BufferId, Arc<[FoldingRange]>>;
 
 /// Decides how text in a [`MultiBuffer`] should be displayed in a buffer, handling inlay hints,
 /// folding, hard tabs, soft wrapping, custom blocks (like diagnostics), and highlighting.
@@ -115,6 +116,9 @@ pub struct DisplayMap {
     inlay_highlights: InlayHighlights,
     /// A container for explicitly foldable ranges, which supersede indentation based fold range suggestions.
     crease_map: CreaseMap,
+    /// Foldable ranges reported by language servers, which supersede indentation based fold range suggestions
+    /// in the buffers they were reported for.
+    lsp_folding_ranges: LspFoldingRanges,
     pub(crate) fold_placeholder: FoldPlaceholder,
     pub clip_at_line_ends: bool,
     pub(crate) masked: bool,
@@ -157,6 +161,7 @@ impl DisplayMap {
             crease_map,
             fold_placeholder,
             diagnostics_max_severity,
+            lsp_folding_ranges: Default::default(),
             text_highlights: Default::default(),
             inlay_highlights: Default::default(),
             clip_at_line_ends: false,
@@ -185,6 +190,7 @@ impl DisplayMap {
             block_snapshot,
             diagnostics_max_severity: self.diagnostics_max_severity,
             crease_snapshot: self.crease_map.snapshot(),
+            lsp_folding_ranges: self.lsp_folding_ranges.clone(),
             text_highlights: self.text_highlights.clone(),
             inlay_highlights: self.inlay_highlights.clone(),
             clip_at_line_ends: self.clip_at_line_ends,
@@ -193,6 +199,15 @@ impl DisplayMap {
         }
     }
 
+    /// Replaces the language server folding ranges of the given buffer, sorted by their start.
+    pub fn set_lsp_folding_ranges(&mut self, buffer_id: BufferId, ranges: Arc<[FoldingRange]>) {
+        if ranges.is_empty() {
+            self.lsp_folding_ranges.remove(&buffer_id);
+        } else {
+            self.lsp_folding_ranges.insert(buffer_id, ranges);
+        }
+    }
+
     pub fn set_state(&mut self, other: &DisplaySnapshot, cx: &mut Context<Self>) {
         self.fold(
             other
@@ -519,6 +534,18 @@ impl DisplayMap {
         self.text_highlights.values()
     }
 
+    /// Removes all text highlights of the given type that were added with a key.
+    pub fn clear_keyed_highlights(&mut self, type_id: TypeId) -> bool {
+        let mut cleared = false;
+        self.text_highlights.retain(|key, _| {
+            let retain =
+                !matches!(key, HighlightKey::TypePlus(key_type_id, _) if *key_type_id == type_id);
+            cleared |= !retain;
+            retain
+        });
+        cleared
+    }
+
     pub fn clear_highlights(&mut self, type_id: TypeId) -> bool {
         let mut cleared = self
             .text_highlights
@@ -766,6 +793,7 @@ pub struct DisplaySnapshot {
     pub buffer_snapshot: MultiBufferSnapshot,
     pub fold_snapshot: FoldSnapshot,
     pub crease_snapshot: CreaseSnapshot,
+    lsp_folding_ranges: LspFoldingRanges,
     inlay_snapshot: InlaySnapshot,
     tab_snapshot: TabSnapshot,
     wrap_snapshot: WrapSnapshot,
@@ -1274,6 +1302,67 @@ impl DisplaySnapshot {
             .unwrap_or(false)
     }
 
+    pub fn lsp_folding_ranges(&self, buffer_id: BufferId) -> Option<&Arc<[FoldingRange]>> {
+        self.lsp_folding_ranges.get(&buffer_id)
+    }
+
+    /// Returns the outermost language server folding range starting at the given row,
+    /// or `None` if no language server reported folding ranges for the row's buffer.
+    pub(crate) fn lsp_crease_for_buffer_row(
+        &self,
+        buffer_row: MultiBufferRow,
+    ) -> Option<Option<Crease<Point>>> {
+        if self.lsp_folding_ranges.is_empty() {
+            return None;
+        }
+        let (buffer, buffer_point, excerpt_id) = self
+            .buffer_snapshot
+            .point_to_buffer_point(Point::new(buffer_row.0, 0))?;
+        let folding_ranges = self.lsp_folding_ranges.get(&buffer.remote_id())?;
+        if self.is_line_folded(buffer_row) {
+            return Some(None);
+        }
+
+        let first_ix = folding_ranges.partition_point(|folding_range| {
+            buffer
+                .summary_for_anchor::<Point>(&folding_range.range.start)
+                .row
+                < buffer_point.row
+        });
+        let Some(end) = folding_ranges[first_ix..]
+            .iter()
+            .take_while(|folding_range| {
+                buffer
+                    .summary_for_anchor::<Point>(&folding_range.range.start)
+                    .row
+                    == buffer_point.row
+            })
+            .max_by_key(|folding_range| {
+                buffer.summary_for_anchor::<Point>(&folding_range.range.end)
+            })
+            .and_then(|folding_range| {
+                self.buffer_snapshot
+                    .anchor_in_excerpt(excerpt_id, folding_range.range.end)
+            })
+            .map(|end| end.to_point(&self.buffer_snapshot))
+            .filter(|end| end.row > buffer_row.0)
+        else {
+            return Some(None);
+        };
+
+        Some(Some(Crease::Inline {
+            range: Point::new(buffer_row.0, self.buffer_snapshot.line_len(buffer_row))
+                ..Point::new(
+                    end.row,
+                    self.buffer_snapshot.line_len(MultiBufferRow(end.row)),
+                ),
+            placeholder: self.fold_placeholder.clone(),
+            render_toggle: None,
+            render_trailer: None,
+            metadata: None,
+        }))
+    }
+
     pub fn crease_for_buffer_row(&self, buffer_row: MultiBufferRow) -> Option<Crease<Point>> {
         let start = MultiBufferPoint::new(buffer_row.0, self.buffer_snapshot.line_len(buffer_row));
         if let Some(crease) = self
@@ -1310,6 +1399,8 @@ impl DisplaySnapshot {
                     render_toggle: render_toggle.clone(),
                 }),
             }
+        } else if let Some(lsp_crease) = self.lsp_crease_for_buffer_row(buffer_row) {
+            lsp_crease
         } else if self.starts_indent(MultiBufferRow(start.row))
             && !self.is_line_folded(MultiBufferRow(start.row))
         {
@@ -30,7 +30,10 @@ pub mod items;
 mod jsx_tag_auto_close;
 mod linked_editing_ranges;
 mod lsp_colors;
+mod lsp_document_links;
 mod lsp_ext;
+mod lsp_folding_ranges;
+mod lsp_selection_ranges;
 mod mouse_context_menu;
 pub mod movement;
 mod persistence;
@@ -38,6 +41,7 @@ mod proposed_changes_editor;
 mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
+mod semantic_tokens;
 pub mod tasks;
 
 #[cfg(test)]
@@ -80,6 +84,7 @@ use futures::{
 };
 use fuzzy::{StringMatch, StringMatchCandidate};
 use lsp_colors::LspColorData;
+use semantic_tokens::SemanticTokensData;
 
 use ::git::blame::BlameEntry;
 use ::git::{Restore, blame::ParsedCommitMessage};
@@ -131,7 +136,7 @@ use project::{
             BreakpointEditAction, BreakpointSessionState, BreakpointState, BreakpointStore,
             BreakpointStoreEvent,
         },
-        session::{Session, SessionEvent},
+        session::{ExceptionInfo, Session, SessionEvent},
     },
     git_store::{GitStoreEvent, RepositoryEvent},
     project_settings::DiagnosticSeverity,
@@ -163,9 +168,9 @@ use multi_buffer::{
 };
 use parking_lot::Mutex;
 use project::{
-    CodeAction, Completion, CompletionIntent, CompletionSource, DocumentHighlight, InlayHint,
-    Location, LocationLink, PrepareRenameResponse, Project, ProjectItem, ProjectTransaction,
-    TaskSourceKind,
+    CodeAction, Completion, CompletionIntent, CompletionSource, DocumentHighlight, DocumentLink,
+    InlayHint, Location, LocationLink, PrepareRenameResponse, Project, ProjectItem,
+    ProjectTransaction, TaskSourceKind,
     debugger::breakpoint_store::Breakpoint,
     lsp_store::{CompletionDocumentation, FormatTrigger, LspFormatTarget, OpenLspBufferHandle},
     project_settings::{GitGutterSetting, ProjectSettings},
@@ -1169,9 +1174,17 @@ pub struct Editor {
     hide_mouse_mode: HideMouseMode,
     pub change_list: ChangeList,
     inline_value_cache: InlineValueCache,
+    active_exception_blocks: Vec<CustomBlockId>,
     selection_drag_state: SelectionDragState,
     next_color_inlay_id: usize,
     colors: Option<LspColorData>,
+    semantic_tokens: Option<SemanticTokensData>,
+    folding_ranges_tasks: HashMap<BufferId, Task<()>>,
+    /// The buffer versions folding ranges were last fetched for.
+    folding_ranges_versions: HashMap<BufferId, clock::Global>,
+    document_links: HashMap<BufferId, Vec<DocumentLink>>,
+    document_links_tasks: HashMap<BufferId, Task<()>>,
+    document_links_versions: HashMap<BufferId, clock::Global>,
     folding_newlines: Task<()>,
 }
 
@@ -1874,6 +1887,9 @@ impl Editor {
                             editor
                                 .refresh_inlay_hints(InlayHintRefreshReason::RefreshRequested, cx);
                         }
+                        project::Event::RefreshSemanticTokens => {
+                            editor.refresh_semantic_tokens(true, None, window, cx);
+                        }
                         project::Event::LanguageServerAdded(..)
                         | project::Event::LanguageServerRemoved(..) => {
                             if editor.tasks_update_task.is_none() {
@@ -1927,6 +1943,7 @@ impl Editor {
                     |editor, _, event, window, cx| match event {
                         BreakpointStoreEvent::ClearDebugLines => {
                             editor.clear_row_highlights::<ActiveDebugLine>();
+                            editor.remove_active_exception_blocks(cx);
                             editor.refresh_inline_values(cx);
                         }
                         BreakpointStoreEvent::SetDebugLine => {
@@ -2115,6 +2132,7 @@ impl Editor {
             inline_diagnostics_enabled: mode.is_full(),
             diagnostics_enabled: mode.is_full(),
             inline_value_cache: InlineValueCache::new(inlay_hint_settings.show_value_hints),
+            active_exception_blocks: Vec::new(),
             inlay_hint_cache: InlayHintCache::new(inlay_hint_settings),
 
             gutter_hovered: false,
@@ -2176,6 +2194,12 @@ impl Editor {
             tasks_update_task: None,
             pull_diagnostics_task: Task::ready(()),
             colors: None,
+            semantic_tokens: None,
+            folding_ranges_tasks: HashMap::default(),
+            folding_ranges_versions: HashMap::default(),
+            document_links: HashMap::default(),
+            document_links_tasks: HashMap::default(),
+            document_links_versions: HashMap::default(),
             next_color_inlay_id: 0,
             linked_edit_ranges: Default::default(),
             in_project_search: false,
@@ -2319,6 +2343,7 @@ impl Editor {
             editor.minimap =
                 editor.create_minimap(EditorSettings::get_global(cx).minimap, window, cx);
             editor.colors = Some(LspColorData::new(cx));
+            editor.semantic_tokens = Some(SemanticTokensData::default());
             editor.update_lsp_data(false, None, window, cx);
         }
 
@@ -14436,6 +14461,29 @@ impl Editor {
         _: &SelectLargerSyntaxNode,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) {
+        if let Some(selection_ranges_task) = self.fetch_lsp_selection_ranges(cx) {
+            cx.spawn_in(window, async move |editor, cx| {
+                let selection_ranges = selection_ranges_task.await;
+                editor
+                    .update_in(cx, |editor, window, cx| {
+                        editor.select_larger_node(&selection_ranges, window, cx)
+                    })
+                    .ok();
+            })
+            .detach();
+        } else {
+            self.select_larger_node(&HashMap::default(), window, cx);
+        }
+    }
+
+    /// Expands every selection to the smallest of its language server's selection ranges
+    /// that contains it, or to the enclosing syntax node if there is none.
+    fn select_larger_node(
+        &mut self,
+        selection_ranges: &HashMap<usize, Vec<Range<Anchor>>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         let Some(visible_row_count) = self.visible_row_count() else {
             return;
@@ -14456,6 +14504,32 @@ impl Editor {
             .map(|selection| {
                 let old_range = selection.start..selection.end;
 
+                let larger_selection_range =
+                    selection_ranges.get(&selection.id).and_then(|ranges| {
+                        ranges
+                            .iter()
+                            .map(|range| {
+                                range.start.to_offset(&buffer)..range.end.to_offset(&buffer)
+                            })
+                            .find(|range| {
+                                range.start <= old_range.start
+                                    && range.end >= old_range.end
+                                    && *range != old_range
+                                    && !display_map.intersects_fold(range.start)
+                                    && !display_map.intersects_fold(range.end)
+                            })
+                    });
+                if let Some(new_range) = larger_selection_range {
+                    selected_larger_node = true;
+                    return Selection {
+                        id: selection.id,
+                        start: new_range.start,
+                        end: new_range.end,
+                        goal: SelectionGoal::None,
+                        reversed: selection.reversed,
+                    };
+                }
+
                 if let Some((node, _)) = buffer.syntax_ancestor(old_range.clone()) {
                     // manually select word at selection
                     if ["string_content", "inline"].contains(&node.kind()) {
@@ -18424,6 +18498,7 @@ impl Editor {
             let Some(active_stack_frame) = breakpoint_store.read(cx).active_position().cloned()
             else {
                 self.clear_row_highlights::<ActiveDebugLine>();
+                self.remove_active_exception_blocks(cx);
                 return None;
             };
 
@@ -18437,6 +18512,7 @@ impl Editor {
                 .read(cx)
                 .snapshot();
 
+            self.remove_active_exception_blocks(cx);
             let mut handled = false;
             for (id, ExcerptRange { context, .. }) in
                 self.buffer.read(cx).excerpts_for_buffer(buffer_id, cx)
@@ -18459,6 +18535,10 @@ impl Editor {
                     cx,
                 );
 
+                if let Some(exception) = active_stack_frame.exception.as_ref() {
+                    self.insert_active_exception_block(multibuffer_anchor, exception, cx);
+                }
+
                 cx.notify();
             }
 
@@ -18467,6 +18547,53 @@ impl Editor {
         .is_some()
     }
 
+    fn insert_active_exception_block(
+        &mut self,
+        anchor: Anchor,
+        exception: &ExceptionInfo,
+        cx: &mut Context<Self>,
+    ) {
+        let mut lines = Vec::new();
+        exception_block_lines(exception, 0, &mut lines);
+        let height = lines.len() as u32;
+
+        let block_ids = self.insert_blocks(
+            [BlockProperties {
+                style: BlockStyle::Flex,
+                placement: BlockPlacement::Below(anchor),
+                height: Some(height),
+                render: Arc::new(move |cx: &mut BlockContext| {
+                    v_flex()
+                        .block_mouse_except_scroll()
+                        .pl(cx.anchor_x)
+                        .w_full()
+                        .bg(cx.theme().colors().editor_debugger_active_line_background)
+                        .font(cx.editor_style.text.font())
+                        .children(lines.iter().map(|(depth, text, color)| {
+                            div()
+                                .h(cx.line_height)
+                                .pl(cx.em_width * (*depth as f32 * 2.))
+                                .text_color(color.color(cx.app))
+                                .child(text.clone())
+                        }))
+                        .into_any_element()
+                }),
+                priority: 0,
+                render_in_minimap: false,
+            }],
+            None,
+            cx,
+        );
+        self.active_exception_blocks.extend(block_ids);
+    }
+
+    fn remove_active_exception_blocks(&mut self, cx: &mut Context<Self>) {
+        if !self.active_exception_blocks.is_empty() {
+            let block_ids = self.active_exception_blocks.drain(..).collect();
+            self.remove_blocks(block_ids, None, cx);
+        }
+    }
+
     pub fn copy_file_name_without_extension(
         &mut self,
         _: &CopyFileNameWithoutExtension,
@@ -19557,6 +19684,15 @@ impl Editor {
         }
     }
 
+    pub fn clear_keyed_highlights<T: 'static>(&mut self, cx: &mut Context<Self>) {
+        let cleared = self
+            .display_map
+            .update(cx, |map, _| map.clear_keyed_highlights(TypeId::of::<T>()));
+        if cleared {
+            cx.notify();
+        }
+    }
+
     pub fn show_local_cursors(&self, window: &mut Window, cx: &mut App) -> bool {
         (self.read_only(cx) || self.blink_manager.read(cx).visible())
             && self.focus_handle.is_focused(window)
@@ -19966,6 +20102,11 @@ impl Editor {
             self.refresh_colors(false, None, window, cx);
         }
 
+        if let Some(semantic_tokens) = self.semantic_tokens.as_mut() {
+            semantic_tokens.invalidate();
+            self.refresh_semantic_tokens(false, None, window, cx);
+        }
+
         cx.notify();
     }
 
@@ -20871,6 +21012,9 @@ impl Editor {
     ) {
         self.pull_diagnostics(for_buffer, window, cx);
         self.refresh_colors(ignore_cache, for_buffer, window, cx);
+        self.refresh_semantic_tokens(ignore_cache, for_buffer, window, cx);
+        self.refresh_folding_ranges(ignore_cache, for_buffer, window, cx);
+        self.refresh_document_links(ignore_cache, for_buffer, window, cx);
     }
 }
 
@@ -22425,7 +22569,10 @@ impl EditorSnapshot {
             }
         }
 
-        is_foldable |= self.starts_indent(buffer_row);
+        is_foldable |= match self.lsp_crease_for_buffer_row(buffer_row) {
+            Some(lsp_crease) => lsp_crease.is_some(),
+            None => self.starts_indent(buffer_row),
+        };
 
         if folded || (is_foldable && (row_contains_cursor || self.gutter_hovered)) {
             Some(
@@ -23630,3 +23777,27 @@ fn render_diff_hunk_controls(
         )
         .into_any_element()
 }
+
+/// Flattens an exception and its inner exceptions into the lines of the exception block.
+fn exception_block_lines(
+    exception: &ExceptionInfo,
+    depth: usize,
+    lines: &mut Vec<(usize, SharedString, Color)>,
+) {
+    let header = match exception.message.as_ref() {
+        Some(message) => format!("{}: {}", exception.type_name, message),
+        None => exception.type_name.clone(),
+    };
+    lines.push((depth, header.into(), Color::Error));
+    if let Some(stack_trace) = exception.stack_trace.as_ref() {
+        lines.extend(
+            stack_trace
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| (depth + 1, line.trim_end().to_string().into(), Color::Muted)),
+        );
+    }
+    for inner_exception in &exception.inner_exceptions {
+        exception_block_lines(inner_exception, depth + 1, lines);
+    }
+}
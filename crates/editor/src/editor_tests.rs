@@ -7742,6 +7742,79 @@ async fn test_select_larger_syntax_node_for_cursor_at_end(cx: &mut TestAppContex
     });
 }
 
+#[gpui::test]
+async fn test_select_larger_syntax_node_with_lsp_selection_ranges(cx: &mut TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.lsp_selection_ranges = Some(true);
+    });
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+
+    let name_range = cx.lsp_range(indoc! {r#"
+        let a = format!("hello {«name»}");
+        let b = 1;
+    "#});
+    let placeholder_range = cx.lsp_range(indoc! {r#"
+        let a = format!("hello «{name}»");
+        let b = 1;
+    "#});
+    let literal_range = cx.lsp_range(indoc! {r#"
+        let a = format!("hello {name}");
+        let b = «1»;
+    "#});
+    let mut requests = cx.set_request_handler::<lsp::request::SelectionRangeRequest, _, _>(
+        move |_, params, _| async move {
+            assert_eq!(params.positions.len(), 2);
+            Ok(Some(vec![
+                lsp::SelectionRange {
+                    range: name_range,
+                    parent: Some(Box::new(lsp::SelectionRange {
+                        range: placeholder_range,
+                        parent: None,
+                    })),
+                },
+                lsp::SelectionRange {
+                    range: literal_range,
+                    parent: None,
+                },
+            ]))
+        },
+    );
+
+    cx.set_state(indoc! {r#"
+        let a = format!("hello {ˇname}");
+        let b = ˇ1;
+    "#});
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    requests.next().await;
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {r#"
+        let a = format!("hello {«nameˇ»}");
+        let b = «1ˇ»;
+    "#});
+
+    // The second selection has no larger selection range and falls back to the syntax tree.
+    cx.update_editor(|editor, window, cx| {
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    requests.next().await;
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {r#"
+        let a = format!("hello «{name}ˇ»");
+        «ˇlet b = 1;»
+    "#});
+}
+
 #[gpui::test]
 async fn test_select_larger_smaller_syntax_node_for_string(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -23239,6 +23312,146 @@ async fn test_newline_replacement_in_single_line(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_lsp_folding_ranges(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "main.rs": indoc! {"
+                use std::fmt;
+                use std::io;
+
+                // first
+                // second
+                fn main() {
+                    let a = 5;
+                    let b = 6;
+                }"
+            },
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                folding_range_provider: Some(lsp::FoldingRangeProviderCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let editor = workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.open_abs_path(
+                PathBuf::from(path!("/a/main.rs")),
+                OpenOptions::default(),
+                window,
+                cx,
+            )
+        })
+        .unwrap()
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+    let mut folding_range_requests = fake_server
+        .set_request_handler::<lsp::request::FoldingRangeRequest, _, _>(|_, _| async move {
+            let folding_range = |start_line, end_line, kind| lsp::FoldingRange {
+                start_line,
+                start_character: None,
+                end_line,
+                end_character: None,
+                kind,
+                collapsed_text: None,
+            };
+            Ok(Some(vec![
+                folding_range(0, 1, Some(lsp::FoldingRangeKind::Imports)),
+                folding_range(3, 4, Some(lsp::FoldingRangeKind::Comment)),
+                folding_range(5, 7, None),
+            ]))
+        });
+    cx.executor().advance_clock(Duration::from_millis(300));
+    folding_range_requests.next().await.unwrap();
+    cx.run_until_parked();
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.fold_all_imports(&FoldAllImports, window, cx);
+        assert_eq!(
+            editor.display_text(cx),
+            indoc! {"
+                use std::fmt;⋯
+
+                // first
+                // second
+                fn main() {
+                    let a = 5;
+                    let b = 6;
+                }"
+            }
+        );
+
+        editor.unfold_all(&UnfoldAll, window, cx);
+        editor.fold_all_comments(&FoldAllComments, window, cx);
+        assert_eq!(
+            editor.display_text(cx),
+            indoc! {"
+                use std::fmt;
+                use std::io;
+
+                // first⋯
+                fn main() {
+                    let a = 5;
+                    let b = 6;
+                }"
+            }
+        );
+
+        editor.unfold_all(&UnfoldAll, window, cx);
+        editor.fold_all(&FoldAll, window, cx);
+        assert_eq!(
+            editor.display_text(cx),
+            indoc! {"
+                use std::fmt;⋯
+
+                // first⋯
+                fn main() {⋯
+                }"
+            },
+            "Server ranges should be preferred over indentation based ones"
+        );
+    });
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.refresh_folding_ranges(false, None, window, cx);
+    });
+    cx.executor().advance_clock(Duration::from_millis(300));
+    cx.run_until_parked();
+    assert!(
+        folding_range_requests.try_next().is_err(),
+        "Ranges fetched for the current buffer version should be reused"
+    );
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.unfold_all(&UnfoldAll, window, cx);
+        editor.handle_input("// edited\n", window, cx);
+    });
+    cx.executor().advance_clock(Duration::from_millis(300));
+    folding_range_requests.next().await.unwrap();
+}
+
 #[track_caller]
 fn extract_color_inlays(editor: &Editor, cx: &App) -> Vec<Rgba> {
     editor
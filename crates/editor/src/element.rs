@@ -412,6 +412,8 @@ impl EditorElement {
         register_action(editor, window, Editor::fold);
         register_action(editor, window, Editor::fold_at_level);
         register_action(editor, window, Editor::fold_all);
+        register_action(editor, window, Editor::fold_all_comments);
+        register_action(editor, window, Editor::fold_all_imports);
         register_action(editor, window, Editor::fold_function_bodies);
         register_action(editor, window, Editor::fold_recursive);
         register_action(editor, window, Editor::toggle_fold);
@@ -5,7 +5,7 @@ use crate::{
     hover_popover::{self, InlayHover},
     scroll::ScrollAmount,
 };
-use gpui::{App, AsyncWindowContext, Context, Entity, Modifiers, Task, Window, px};
+use gpui::{App, AsyncWindowContext, Context, Entity, Modifiers, Task, WeakEntity, Window, px};
 use language::{Bias, ToOffset};
 use linkify::{LinkFinder, LinkKind};
 use lsp::LanguageServerId;
@@ -534,7 +534,20 @@ pub fn show_link_definition(
         async move {
             let result = match &trigger_point {
                 TriggerPoint::Text(_) => {
-                    if let Some((url_range, url)) = find_url(&buffer, buffer_position, cx.clone()) {
+                    if let Some((link_range, link)) =
+                        find_document_link(&this, project.clone(), &buffer, buffer_position, cx)
+                            .await
+                    {
+                        let range = maybe!({
+                            let start = snapshot.anchor_in_excerpt(excerpt_id, link_range.start)?;
+                            let end = snapshot.anchor_in_excerpt(excerpt_id, link_range.end)?;
+                            Some(RangeInEditor::Text(start..end))
+                        });
+
+                        Some((range, vec![link]))
+                    } else if let Some((url_range, url)) =
+                        find_url(&buffer, buffer_position, cx.clone())
+                    {
                         this.read_with(cx, |_, _| {
                             let range = maybe!({
                                 let start =
@@ -654,6 +667,57 @@ pub fn show_link_definition(
     editor.hovered_link_state = Some(hovered_link_state);
 }
 
+/// Returns the target of a language server's document link at the given position.
+async fn find_document_link(
+    editor: &WeakEntity<Editor>,
+    project: Option<Entity<Project>>,
+    buffer: &Entity<language::Buffer>,
+    position: text::Anchor,
+    cx: &mut AsyncWindowContext,
+) -> Option<(Range<text::Anchor>, HoverLink)> {
+    let project = project?;
+    let link = editor
+        .read_with(cx, |editor, cx| {
+            editor.document_link_at(buffer, position, cx)
+        })
+        .ok()??;
+    let range = link.range.clone();
+    let target = match link.target.clone() {
+        Some(target) => target,
+        None => {
+            let target = project
+                .update(cx, |project, cx| {
+                    project.lsp_store().update(cx, |lsp_store, cx| {
+                        lsp_store.resolve_document_link(buffer.clone(), link.clone(), cx)
+                    })
+                })
+                .ok()?
+                .await
+                .log_err()??;
+            let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id()).ok()?;
+            editor
+                .update(cx, |editor, _| {
+                    editor.cache_document_link_target(buffer_id, &link, target.clone())
+                })
+                .ok()?;
+            target
+        }
+    };
+
+    if target.scheme() == "file" {
+        let path = target.to_file_path().ok()?;
+        let resolved_path = project
+            .update(cx, |project, cx| {
+                project.resolve_abs_path(&path.to_string_lossy(), cx)
+            })
+            .ok()?
+            .await?;
+        Some((range, HoverLink::File(resolved_path)))
+    } else {
+        Some((range, HoverLink::Url(target.to_string())))
+    }
+}
+
 pub(crate) fn find_url(
     buffer: &Entity<language::Buffer>,
     position: text::Anchor,
@@ -926,7 +990,9 @@ mod tests {
     use gpui::Modifiers;
     use indoc::indoc;
     use language::language_settings::InlayHintSettings;
-    use lsp::request::{GotoDefinition, GotoTypeDefinition};
+    use lsp::request::{
+        DocumentLinkRequest, DocumentLinkResolve, GotoDefinition, GotoTypeDefinition,
+    };
     use util::{assert_set_eq, path};
     use workspace::item::Item;
 
@@ -1503,6 +1569,85 @@ mod tests {
         assert_eq!(cx.opened_url(), Some("https://zed.dev/releases".into()));
     }
 
+    #[gpui::test]
+    async fn test_document_links(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                }),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+
+        let link_range = cx.lsp_range(indoc! {"
+            use «serde»::Deserialize;
+        "});
+        let mut link_requests =
+            cx.lsp
+                .set_request_handler::<DocumentLinkRequest, _, _>(move |_, _| async move {
+                    Ok(Some(vec![lsp::DocumentLink {
+                        range: link_range,
+                        target: None,
+                        tooltip: None,
+                        data: Some(serde_json::json!({ "crate": "serde" })),
+                    }]))
+                });
+        let mut resolve_requests =
+            cx.lsp
+                .set_request_handler::<DocumentLinkResolve, _, _>(|link, _| async move {
+                    assert_eq!(link.data, Some(serde_json::json!({ "crate": "serde" })));
+                    Ok(lsp::DocumentLink {
+                        target: Some(lsp::Url::parse("https://docs.rs/serde").unwrap()),
+                        ..link
+                    })
+                });
+
+        cx.set_state(indoc! {"
+            use serde::Deserializeˇ;
+        "});
+        cx.executor()
+            .advance_clock(crate::lsp_document_links::FETCH_DOCUMENT_LINKS_DEBOUNCE_TIMEOUT);
+        link_requests.next().await;
+        cx.run_until_parked();
+
+        let screen_coord = cx.pixel_position(indoc! {"
+            use seˇrde::Deserialize;
+        "});
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        resolve_requests.next().await;
+        cx.run_until_parked();
+        cx.assert_editor_text_highlights::<HoveredLinkState>(indoc! {"
+            use «serdeˇ»::Deserialize;
+        "});
+
+        cx.simulate_click(screen_coord, Modifiers::secondary_key());
+        assert_eq!(cx.opened_url(), Some("https://docs.rs/serde".into()));
+
+        // The resolved target is reused when the link is hovered again, and the links aren't
+        // fetched again while the buffer is unchanged.
+        let other_coord = cx.pixel_position(indoc! {"
+            use serde::Deseˇrialize;
+        "});
+        cx.simulate_mouse_move(other_coord, None, Modifiers::secondary_key());
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        cx.update_editor(|editor, window, cx| {
+            editor.refresh_document_links(false, None, window, cx)
+        });
+        cx.executor()
+            .advance_clock(crate::lsp_document_links::FETCH_DOCUMENT_LINKS_DEBOUNCE_TIMEOUT);
+        cx.run_until_parked();
+        cx.assert_editor_text_highlights::<HoveredLinkState>(indoc! {"
+            use «serdeˇ»::Deserialize;
+        "});
+        assert!(resolve_requests.try_next().is_err());
+        assert!(link_requests.try_next().is_err());
+    }
+
     #[gpui::test]
     async fn test_surrounding_filename(cx: &mut gpui::TestAppContext) {
         init_test(cx, |_| {});
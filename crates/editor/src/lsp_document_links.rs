@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use gpui::{App, Entity};
+use itertools::Itertools as _;
+use language::Buffer;
+use project::DocumentLink;
+use text::BufferId;
+use ui::{Context, Window};
+
+use crate::Editor;
+
+pub(crate) const FETCH_DOCUMENT_LINKS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(250);
+
+impl Editor {
+    pub(super) fn refresh_document_links(
+        &mut self,
+        ignore_cache: bool,
+        for_buffer: Option<BufferId>,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.mode().is_full() {
+            return;
+        }
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+
+        let multi_buffer = self.buffer.read(cx);
+        self.document_links
+            .retain(|buffer_id, _| multi_buffer.buffer(*buffer_id).is_some());
+        self.document_links_tasks
+            .retain(|buffer_id, _| multi_buffer.buffer(*buffer_id).is_some());
+        self.document_links_versions
+            .retain(|buffer_id, _| multi_buffer.buffer(*buffer_id).is_some());
+        let buffers = self
+            .visible_excerpts(None, cx)
+            .into_values()
+            .filter(|(buffer, ..)| {
+                for_buffer.is_none_or(|buffer_id| buffer.read(cx).remote_id() == buffer_id)
+            })
+            .unique_by(|(buffer, ..)| buffer.read(cx).remote_id())
+            .collect::<Vec<_>>();
+
+        for (buffer, version, _) in buffers {
+            let buffer_id = buffer.read(cx).remote_id();
+            if !ignore_cache && self.document_links_versions.get(&buffer_id) == Some(&version) {
+                continue;
+            }
+            self.document_links_versions.insert(buffer_id, version);
+
+            let project = project.downgrade();
+            let task = cx.spawn_in(window, async move |editor, cx| {
+                cx.background_executor()
+                    .timer(FETCH_DOCUMENT_LINKS_DEBOUNCE_TIMEOUT)
+                    .await;
+                let Ok(document_links_task) = project.update(cx, |project, cx| {
+                    project
+                        .lsp_store()
+                        .update(cx, |lsp_store, cx| lsp_store.document_links(&buffer, cx))
+                }) else {
+                    return;
+                };
+                let document_links = match document_links_task.await {
+                    Ok(document_links) => document_links,
+                    Err(e) => {
+                        log::error!("Failed to fetch document links: {e:#}");
+                        editor
+                            .update(cx, |editor, _| {
+                                editor.document_links_versions.remove(&buffer_id);
+                            })
+                            .ok();
+                        return;
+                    }
+                };
+                editor
+                    .update(cx, |editor, _| {
+                        if document_links.is_empty() {
+                            editor.document_links.remove(&buffer_id);
+                        } else {
+                            editor.document_links.insert(buffer_id, document_links);
+                        }
+                    })
+                    .ok();
+            });
+            self.document_links_tasks.insert(buffer_id, task);
+        }
+    }
+
+    /// Returns the document link that contains the given position, if any.
+    pub(crate) fn document_link_at(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        cx: &App,
+    ) -> Option<DocumentLink> {
+        let buffer = buffer.read(cx);
+        self.document_links
+            .get(&buffer.remote_id())?
+            .iter()
+            .find(|link| {
+                link.range.start.cmp(&position, buffer).is_le()
+                    && link.range.end.cmp(&position, buffer).is_ge()
+            })
+            .cloned()
+    }
+
+    /// Remembers the resolved target of `link`, so that hovering it again doesn't ask the language
+    /// server to resolve it again.
+    pub(crate) fn cache_document_link_target(
+        &mut self,
+        buffer_id: BufferId,
+        link: &DocumentLink,
+        target: lsp::Url,
+    ) {
+        let Some(cached_link) = self
+            .document_links
+            .get_mut(&buffer_id)
+            .and_then(|links| links.iter_mut().find(|cached| cached.range == link.range))
+        else {
+            return;
+        };
+        cached_link.target = Some(target);
+    }
+}
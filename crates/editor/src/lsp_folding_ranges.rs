@@ -0,0 +1,152 @@
+use std::{cmp::Ordering, time::Duration};
+
+use itertools::Itertools as _;
+use project::FoldingRangeKind;
+use text::BufferId;
+use ui::{Context, Window};
+
+use crate::{Crease, Editor, FoldAllComments, FoldAllImports};
+
+const FETCH_FOLDING_RANGES_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(250);
+
+impl Editor {
+    pub(super) fn refresh_folding_ranges(
+        &mut self,
+        ignore_cache: bool,
+        for_buffer: Option<BufferId>,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.mode().is_full() {
+            return;
+        }
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+
+        let multi_buffer = self.buffer.read(cx);
+        self.folding_ranges_tasks
+            .retain(|buffer_id, _| multi_buffer.buffer(*buffer_id).is_some());
+        self.folding_ranges_versions
+            .retain(|buffer_id, _| multi_buffer.buffer(*buffer_id).is_some());
+        let buffers = self
+            .visible_excerpts(None, cx)
+            .into_values()
+            .filter(|(buffer, ..)| {
+                for_buffer.is_none_or(|buffer_id| buffer.read(cx).remote_id() == buffer_id)
+            })
+            .unique_by(|(buffer, ..)| buffer.read(cx).remote_id())
+            .collect::<Vec<_>>();
+
+        for (buffer, version, _) in buffers {
+            let buffer_id = buffer.read(cx).remote_id();
+            if !ignore_cache && self.folding_ranges_versions.get(&buffer_id) == Some(&version) {
+                continue;
+            }
+            self.folding_ranges_versions.insert(buffer_id, version);
+
+            let project = project.downgrade();
+            let task = cx.spawn_in(window, async move |editor, cx| {
+                cx.background_executor()
+                    .timer(FETCH_FOLDING_RANGES_DEBOUNCE_TIMEOUT)
+                    .await;
+                let Ok(folding_ranges_task) = project.update(cx, |project, cx| {
+                    project
+                        .lsp_store()
+                        .update(cx, |lsp_store, cx| lsp_store.folding_ranges(&buffer, cx))
+                }) else {
+                    return;
+                };
+                let folding_ranges = match folding_ranges_task.await {
+                    Ok(folding_ranges) => folding_ranges,
+                    Err(e) => {
+                        log::error!("Failed to fetch folding ranges: {e:#}");
+                        editor
+                            .update(cx, |editor, _| {
+                                editor.folding_ranges_versions.remove(&buffer_id);
+                            })
+                            .ok();
+                        return;
+                    }
+                };
+                editor
+                    .update(cx, |editor, cx| {
+                        editor.display_map.update(cx, |display_map, _| {
+                            display_map.set_lsp_folding_ranges(buffer_id, folding_ranges.into())
+                        });
+                        cx.notify();
+                    })
+                    .ok();
+            });
+            self.folding_ranges_tasks.insert(buffer_id, task);
+        }
+    }
+
+    pub fn fold_all_comments(
+        &mut self,
+        _: &FoldAllComments,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.fold_lsp_folding_ranges_of_kind(FoldingRangeKind::Comment, window, cx);
+    }
+
+    pub fn fold_all_imports(
+        &mut self,
+        _: &FoldAllImports,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.fold_lsp_folding_ranges_of_kind(FoldingRangeKind::Imports, window, cx);
+    }
+
+    fn fold_lsp_folding_ranges_of_kind(
+        &mut self,
+        kind: FoldingRangeKind,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let multi_buffer_snapshot = &display_map.buffer_snapshot;
+        let mut creases = Vec::new();
+        for (excerpt_id, buffer_snapshot, excerpt_range) in multi_buffer_snapshot.excerpts() {
+            let Some(folding_ranges) = display_map.lsp_folding_ranges(buffer_snapshot.remote_id())
+            else {
+                continue;
+            };
+            let excerpt_range = excerpt_range.context;
+            for folding_range in folding_ranges.iter() {
+                if folding_range.kind != Some(kind)
+                    || folding_range
+                        .range
+                        .start
+                        .cmp(&excerpt_range.start, buffer_snapshot)
+                        == Ordering::Less
+                    || folding_range
+                        .range
+                        .end
+                        .cmp(&excerpt_range.end, buffer_snapshot)
+                        == Ordering::Greater
+                {
+                    continue;
+                }
+                let Some(start) =
+                    multi_buffer_snapshot.anchor_in_excerpt(excerpt_id, folding_range.range.start)
+                else {
+                    continue;
+                };
+                let Some(end) =
+                    multi_buffer_snapshot.anchor_in_excerpt(excerpt_id, folding_range.range.end)
+                else {
+                    continue;
+                };
+                creases.push(Crease::simple(
+                    start..end,
+                    display_map.fold_placeholder.clone(),
+                ));
+            }
+        }
+
+        self.fold_creases(creases, true, window, cx);
+    }
+}
@@ -0,0 +1,113 @@
+use std::ops::Range;
+
+use collections::HashMap;
+use futures::future::join_all;
+use gpui::Task;
+use language::language_settings::language_settings;
+use multi_buffer::{Anchor, ExcerptId};
+use text::BufferId;
+use ui::Context;
+
+use crate::Editor;
+
+impl Editor {
+    /// Fetches the selection ranges of the language servers for the selections in buffers that
+    /// have `lsp_selection_ranges` enabled, keyed by selection id.
+    ///
+    /// Returns `None` if no selection uses language server selection ranges.
+    pub(super) fn fetch_lsp_selection_ranges(
+        &self,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<HashMap<usize, Vec<Range<Anchor>>>>> {
+        let project = self.project.clone()?;
+        let multi_buffer = self.buffer.read(cx);
+        let mut positions_by_buffer = HashMap::<BufferId, (_, Vec<_>)>::default();
+        for selection in self.selections.disjoint_anchors().iter() {
+            let Some(buffer_id) = selection.start.buffer_id else {
+                continue;
+            };
+            let Some(buffer) = multi_buffer.buffer(buffer_id) else {
+                continue;
+            };
+            let enabled = {
+                let buffer = buffer.read(cx);
+                language_settings(
+                    buffer.language().map(|language| language.name()),
+                    buffer.file(),
+                    cx,
+                )
+                .lsp_selection_ranges
+            };
+            if enabled {
+                positions_by_buffer
+                    .entry(buffer_id)
+                    .or_insert_with(|| (buffer, Vec::new()))
+                    .1
+                    .push((
+                        selection.id,
+                        selection.start.excerpt_id,
+                        selection.start.text_anchor,
+                    ));
+            }
+        }
+        if positions_by_buffer.is_empty() {
+            return None;
+        }
+
+        let requests = positions_by_buffer
+            .into_values()
+            .map(|(buffer, positions)| {
+                let selection_ranges_task = project.update(cx, |project, cx| {
+                    project.selection_ranges(
+                        &buffer,
+                        positions.iter().map(|(_, _, position)| *position),
+                        cx,
+                    )
+                });
+                async move { (positions, selection_ranges_task.await) }
+            })
+            .collect::<Vec<_>>();
+        Some(cx.spawn(async move |editor, cx| {
+            let responses = join_all(requests).await;
+            editor
+                .read_with(cx, |editor, cx| {
+                    let snapshot = editor.buffer.read(cx).snapshot(cx);
+                    let mut selection_ranges = HashMap::default();
+                    for (positions, ranges) in responses {
+                        let ranges = match ranges {
+                            Ok(ranges) => ranges,
+                            Err(e) => {
+                                log::error!("Failed to fetch selection ranges: {e:#}");
+                                continue;
+                            }
+                        };
+                        for ((selection_id, excerpt_id, _), ranges) in
+                            positions.into_iter().zip(ranges)
+                        {
+                            selection_ranges.insert(
+                                selection_id,
+                                ranges_in_excerpt(&snapshot, excerpt_id, ranges),
+                            );
+                        }
+                    }
+                    selection_ranges
+                })
+                .unwrap_or_default()
+        }))
+    }
+}
+
+fn ranges_in_excerpt(
+    snapshot: &multi_buffer::MultiBufferSnapshot,
+    excerpt_id: ExcerptId,
+    ranges: Vec<Range<text::Anchor>>,
+) -> Vec<Range<Anchor>> {
+    ranges
+        .into_iter()
+        .filter_map(|range| {
+            let start = snapshot.anchor_in_excerpt(excerpt_id, range.start)?;
+            let end = snapshot.anchor_in_excerpt(excerpt_id, range.end)?;
+            Some(start..end)
+        })
+        .collect()
+}
@@ -214,6 +214,14 @@ pub fn deploy_context_menu(
                 .action("Go to Type Definition", Box::new(GoToTypeDefinition))
                 .action("Go to Implementation", Box::new(GoToImplementation))
                 .action("Find All References", Box::new(FindAllReferences))
+                .action(
+                    "Show Call Hierarchy",
+                    Box::new(zed_actions::hierarchy_view::ShowCallHierarchy),
+                )
+                .action(
+                    "Show Type Hierarchy",
+                    Box::new(zed_actions::hierarchy_view::ShowTypeHierarchy),
+                )
                 .separator()
                 .action("Rename Symbol", Box::new(Rename))
                 .action("Format Buffer", Box::new(Format))
@@ -498,6 +498,9 @@ impl Editor {
                     .update_in(cx, |editor, window, cx| {
                         editor.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
                         editor.refresh_colors(false, None, window, cx);
+                        editor.refresh_semantic_tokens(false, None, window, cx);
+                        editor.refresh_folding_ranges(false, None, window, cx);
+                        editor.refresh_document_links(false, None, window, cx);
                     })
                     .ok()
             })
@@ -613,6 +616,9 @@ impl Editor {
 
         self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
         self.refresh_colors(false, None, window, cx);
+        self.refresh_semantic_tokens(false, None, window, cx);
+        self.refresh_folding_ranges(false, None, window, cx);
+        self.refresh_document_links(false, None, window, cx);
         editor_was_scrolled
     }
 
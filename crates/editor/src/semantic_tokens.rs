@@ -0,0 +1,279 @@
+use std::ops::Range;
+
+use collections::HashMap;
+use futures::future::join_all;
+use gpui::SharedString;
+use itertools::Itertools;
+use language::language_settings::language_settings;
+use lsp::LanguageServerId;
+use multi_buffer::Anchor;
+use project::lsp_store::LspFetchStrategy;
+use text::{Bias, BufferId, OffsetRangeExt as _};
+use theme::ActiveTheme as _;
+use ui::{App, Context, Window};
+
+use crate::Editor;
+
+/// Text highlights of semantic tokens, keyed by the index of their style in [`SemanticTokensData::styles`].
+struct SemanticTokenHighlight;
+
+#[derive(Debug, Default)]
+pub(super) struct SemanticTokensData {
+    buffer_tokens: HashMap<BufferId, BufferSemanticTokens>,
+    /// Syntax theme styles referenced by the highlights.
+    styles: Vec<SharedString>,
+}
+
+#[derive(Debug, Default)]
+struct BufferSemanticTokens {
+    highlights: HashMap<usize, Vec<Range<Anchor>>>,
+    cache_version_used: Option<usize>,
+}
+
+impl SemanticTokensData {
+    fn style_index(&mut self, style: &str) -> usize {
+        match self.styles.iter().position(|existing| existing == style) {
+            Some(ix) => ix,
+            None => {
+                self.styles.push(SharedString::from(style.to_owned()));
+                self.styles.len() - 1
+            }
+        }
+    }
+
+    /// Forgets which cached tokens were used, so that the next refresh re-applies them.
+    pub fn invalidate(&mut self) {
+        for buffer_tokens in self.buffer_tokens.values_mut() {
+            buffer_tokens.cache_version_used = None;
+        }
+    }
+}
+
+impl Editor {
+    pub(super) fn refresh_semantic_tokens(
+        &mut self,
+        ignore_cache: bool,
+        buffer_id: Option<BufferId>,
+        _: &Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.mode().is_full() {
+            return;
+        }
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let Some(semantic_tokens) = self.semantic_tokens.as_mut() else {
+            return;
+        };
+
+        let multi_buffer = self.buffer.read(cx);
+        let mut removed_buffers = false;
+        semantic_tokens.buffer_tokens.retain(|buffer_id, _| {
+            let retain = multi_buffer
+                .buffer(*buffer_id)
+                .is_some_and(|buffer| semantic_tokens_enabled(buffer.read(cx), cx));
+            removed_buffers |= !retain;
+            retain
+        });
+        if removed_buffers {
+            self.apply_semantic_token_highlights(cx);
+        }
+
+        let visible_buffers = self
+            .visible_excerpts(None, cx)
+            .into_values()
+            .map(|(buffer, ..)| buffer)
+            .filter(|editor_buffer| {
+                buffer_id.is_none_or(|buffer_id| buffer_id == editor_buffer.read(cx).remote_id())
+                    && semantic_tokens_enabled(editor_buffer.read(cx), cx)
+            })
+            .unique_by(|buffer| buffer.read(cx).remote_id())
+            .collect::<Vec<_>>();
+        if visible_buffers.is_empty() {
+            return;
+        }
+
+        let all_tokens_task = project.read(cx).lsp_store().update(cx, |lsp_store, cx| {
+            visible_buffers
+                .into_iter()
+                .filter_map(|buffer| {
+                    let buffer_id = buffer.read(cx).remote_id();
+                    let fetch_strategy = if ignore_cache {
+                        LspFetchStrategy::IgnoreCache
+                    } else {
+                        LspFetchStrategy::UseCache {
+                            known_cache_version: self.semantic_tokens.as_ref().and_then(
+                                |semantic_tokens| {
+                                    semantic_tokens
+                                        .buffer_tokens
+                                        .get(&buffer_id)?
+                                        .cache_version_used
+                                },
+                            ),
+                        }
+                    };
+                    let tokens_task = lsp_store.semantic_tokens(fetch_strategy, buffer, cx)?;
+                    Some(async move { (buffer_id, tokens_task.await) })
+                })
+                .collect::<Vec<_>>()
+        });
+        cx.spawn(async move |editor, cx| {
+            let all_tokens = join_all(all_tokens_task).await;
+            if all_tokens.is_empty() {
+                return;
+            }
+            editor
+                .update(cx, |editor, cx| {
+                    let multi_buffer_snapshot = editor.buffer().read(cx).snapshot(cx);
+                    let mut editor_excerpts = HashMap::default();
+                    for (excerpt_id, buffer_snapshot, excerpt_range) in
+                        multi_buffer_snapshot.excerpts()
+                    {
+                        editor_excerpts
+                            .entry(buffer_snapshot.remote_id())
+                            .or_insert_with(Vec::new)
+                            .push((
+                                excerpt_id,
+                                buffer_snapshot.clone(),
+                                excerpt_range.context.to_point_utf16(&buffer_snapshot),
+                            ));
+                    }
+
+                    let Some(semantic_tokens) = editor.semantic_tokens.as_mut() else {
+                        return;
+                    };
+                    let mut updated = false;
+                    for (buffer_id, tokens) in all_tokens {
+                        let tokens = match tokens {
+                            Ok(tokens) => tokens,
+                            Err(e) => {
+                                log::error!("Failed to retrieve semantic tokens: {e}");
+                                continue;
+                            }
+                        };
+                        let Some(excerpts) = editor_excerpts.get(&buffer_id) else {
+                            continue;
+                        };
+                        let Some((_, buffer_snapshot, _)) = excerpts.first() else {
+                            continue;
+                        };
+                        let settings = language_settings(
+                            buffer_snapshot.language().map(|language| language.name()),
+                            buffer_snapshot.file(),
+                            cx,
+                        );
+
+                        let mut styles_for_tokens =
+                            HashMap::<(LanguageServerId, u32, u32), Option<usize>>::default();
+                        let mut highlights = HashMap::<usize, Vec<Range<Anchor>>>::default();
+                        for (server_id, server_tokens) in &tokens.tokens {
+                            for token in server_tokens.tokens.iter() {
+                                let Some(style_ix) = *styles_for_tokens
+                                    .entry((*server_id, token.token_type, token.token_modifiers))
+                                    .or_insert_with(|| {
+                                        let style = settings.semantic_tokens.style_for_token(
+                                            server_tokens.token_type(token)?,
+                                            server_tokens.token_modifiers(token),
+                                        )?;
+                                        Some(semantic_tokens.style_index(style))
+                                    })
+                                else {
+                                    continue;
+                                };
+
+                                let token_range = token.range();
+                                for (excerpt_id, buffer_snapshot, excerpt_range) in excerpts {
+                                    if !excerpt_range.contains(&token_range.start)
+                                        || !excerpt_range.contains(&token_range.end)
+                                    {
+                                        continue;
+                                    }
+                                    let start = buffer_snapshot.anchor_after(
+                                        buffer_snapshot.clip_point_utf16(
+                                            language::Unclipped(token_range.start),
+                                            Bias::Left,
+                                        ),
+                                    );
+                                    let end = buffer_snapshot.anchor_before(
+                                        buffer_snapshot.clip_point_utf16(
+                                            language::Unclipped(token_range.end),
+                                            Bias::Right,
+                                        ),
+                                    );
+                                    let Some(start) =
+                                        multi_buffer_snapshot.anchor_in_excerpt(*excerpt_id, start)
+                                    else {
+                                        continue;
+                                    };
+                                    let Some(end) =
+                                        multi_buffer_snapshot.anchor_in_excerpt(*excerpt_id, end)
+                                    else {
+                                        continue;
+                                    };
+                                    highlights.entry(style_ix).or_default().push(start..end);
+                                    break;
+                                }
+                            }
+                        }
+
+                        semantic_tokens.buffer_tokens.insert(
+                            buffer_id,
+                            BufferSemanticTokens {
+                                highlights,
+                                cache_version_used: tokens.cache_version,
+                            },
+                        );
+                        updated = true;
+                    }
+
+                    if updated {
+                        editor.apply_semantic_token_highlights(cx);
+                    }
+                })
+                .ok();
+        })
+        .detach();
+    }
+
+    fn apply_semantic_token_highlights(&mut self, cx: &mut Context<Self>) {
+        let Some(semantic_tokens) = self.semantic_tokens.as_ref() else {
+            return;
+        };
+        let multi_buffer_snapshot = self.buffer.read(cx).snapshot(cx);
+        let syntax_theme = cx.theme().syntax().clone();
+
+        let mut highlights_by_style = HashMap::<usize, Vec<Range<Anchor>>>::default();
+        for buffer_tokens in semantic_tokens.buffer_tokens.values() {
+            for (style_ix, ranges) in &buffer_tokens.highlights {
+                highlights_by_style
+                    .entry(*style_ix)
+                    .or_default()
+                    .extend(ranges.iter().cloned());
+            }
+        }
+        let highlights = highlights_by_style
+            .into_iter()
+            .map(|(style_ix, mut ranges)| {
+                ranges.sort_by(|a, b| a.start.cmp(&b.start, &multi_buffer_snapshot));
+                let style = syntax_theme.get(&semantic_tokens.styles[style_ix]);
+                (style_ix, ranges, style)
+            })
+            .collect::<Vec<_>>();
+
+        self.clear_keyed_highlights::<SemanticTokenHighlight>(cx);
+        for (style_ix, ranges, style) in highlights {
+            self.highlight_text_key::<SemanticTokenHighlight>(style_ix, ranges, style, cx);
+        }
+    }
+}
+
+fn semantic_tokens_enabled(buffer: &language::Buffer, cx: &App) -> bool {
+    language_settings(
+        buffer.language().map(|language| language.name()),
+        buffer.file(),
+        cx,
+    )
+    .semantic_tokens
+    .enabled
+}
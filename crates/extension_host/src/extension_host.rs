@@ -1778,16 +1778,16 @@ impl ExtensionStore {
     }
 
     pub fn register_ssh_client(&mut self, client: Entity<SshRemoteClient>, cx: &mut Context<Self>) {
-        let connection_options = client.read(cx).connection_options();
-        let ssh_url = connection_options.ssh_url();
+        let connection_string = client.read(cx).connection_options().connection_string();
 
-        if let Some(existing_client) = self.ssh_clients.get(&ssh_url) {
+        if let Some(existing_client) = self.ssh_clients.get(&connection_string) {
             if existing_client.upgrade().is_some() {
                 return;
             }
         }
 
-        self.ssh_clients.insert(ssh_url, client.downgrade());
+        self.ssh_clients
+            .insert(connection_string, client.downgrade());
         self.ssh_registered_tx.unbounded_send(()).ok();
     }
 }
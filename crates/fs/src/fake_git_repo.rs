@@ -6,7 +6,8 @@ use git::{
     blame::Blame,
     repository::{
         AskPassDelegate, Branch, CommitDetails, CommitOptions, FetchOptions, GitRepository,
-        GitRepositoryCheckpoint, PushOptions, Remote, RepoPath, ResetMode,
+        GitRepositoryCheckpoint, GitStash, GitWorktree, InProgressOperation, LogOptions,
+        PushOptions, Remote, RepoPath, ResetMode, StashOptions, Tag,
     },
     status::{FileStatus, GitStatus, StatusCode, TrackedStatus, UnmergedStatus},
 };
@@ -36,6 +37,13 @@ pub struct FakeGitRepositoryState {
     pub branches: HashSet<String>,
     pub simulated_index_write_error_message: Option<String>,
     pub refs: HashMap<String, String>,
+    pub stash: GitStash,
+    /// Commits reachable from `HEAD`, most recent first.
+    pub commits: Vec<CommitDetails>,
+    pub in_progress_operation: Option<InProgressOperation>,
+    pub tags: Vec<Tag>,
+    /// Worktrees linked to the repository, not including the main worktree.
+    pub linked_worktrees: Vec<GitWorktree>,
 }
 
 impl FakeGitRepositoryState {
@@ -50,6 +58,11 @@ impl FakeGitRepositoryState {
             branches: Default::default(),
             simulated_index_write_error_message: Default::default(),
             refs: HashMap::from_iter([("HEAD".into(), "abc".into())]),
+            stash: Default::default(),
+            commits: Default::default(),
+            in_progress_operation: None,
+            tags: Default::default(),
+            linked_worktrees: Default::default(),
         }
     }
 }
@@ -112,6 +125,35 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn log(&self, options: LogOptions) -> BoxFuture<'_, Result<Vec<CommitDetails>>> {
+        self.with_state_async(false, move |state| {
+            let limit = if options.limit == 0 {
+                usize::MAX
+            } else {
+                options.limit
+            };
+            Ok(state
+                .commits
+                .iter()
+                .filter(|commit| {
+                    options.author.as_ref().map_or(true, |author| {
+                        commit.author_name.contains(author.as_ref())
+                            || commit.author_email.contains(author.as_ref())
+                    })
+                })
+                .filter(|commit| {
+                    options
+                        .message
+                        .as_ref()
+                        .map_or(true, |message| commit.message.contains(message.as_ref()))
+                })
+                .skip(options.skip)
+                .take(limit)
+                .cloned()
+                .collect())
+        })
+    }
+
     fn set_index_text(
         &self,
         path: RepoPath,
@@ -171,6 +213,67 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn cherry_pick(
+        &self,
+        _commits: Vec<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn revert(
+        &self,
+        _commits: Vec<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn rebase(
+        &self,
+        _onto: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn continue_operation(
+        &self,
+        operation: InProgressOperation,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            anyhow::ensure!(
+                state.in_progress_operation == Some(operation),
+                "no {} in progress",
+                operation.command()
+            );
+            state.in_progress_operation = None;
+            Ok(())
+        })
+    }
+
+    fn abort_operation(
+        &self,
+        operation: InProgressOperation,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            anyhow::ensure!(
+                state.in_progress_operation == Some(operation),
+                "no {} in progress",
+                operation.command()
+            );
+            state.in_progress_operation = None;
+            Ok(())
+        })
+    }
+
+    fn in_progress_operation(&self) -> BoxFuture<'_, Option<InProgressOperation>> {
+        let state = self.with_state_async(false, |state| Ok(state.in_progress_operation));
+        async move { state.await.ok().flatten() }.boxed()
+    }
+
     fn path(&self) -> PathBuf {
         self.repository_dir_path.clone()
     }
@@ -344,6 +447,131 @@ impl GitRepository for FakeGitRepository {
         })
     }
 
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>> {
+        self.with_state_async(false, |state| Ok(state.tags.clone()))
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        message: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            anyhow::ensure!(
+                state.tags.iter().all(|tag| tag.name != name),
+                "tag '{name}' already exists"
+            );
+            let sha = state.refs.get("HEAD").cloned().unwrap_or_default();
+            state.tags.insert(
+                0,
+                Tag {
+                    name: name.into(),
+                    sha: sha.into(),
+                    message: message.map(Into::into),
+                    timestamp: 0,
+                },
+            );
+            Ok(())
+        })
+    }
+
+    fn delete_tag(
+        &self,
+        name: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let ix = state
+                .tags
+                .iter()
+                .position(|tag| tag.name == name)
+                .with_context(|| format!("tag '{name}' not found"))?;
+            state.tags.remove(ix);
+            Ok(())
+        })
+    }
+
+    fn checkout_tag(
+        &self,
+        name: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let tag = state
+                .tags
+                .iter()
+                .find(|tag| tag.name == name)
+                .with_context(|| format!("tag '{name}' not found"))?;
+            state.refs.insert("HEAD".into(), tag.sha.to_string());
+            state.current_branch_name = None;
+            Ok(())
+        })
+    }
+
+    fn worktree_list(&self) -> BoxFuture<'_, Result<Vec<GitWorktree>>> {
+        let main_worktree_path = self.repository_dir_path.clone();
+        self.with_state_async(false, move |state| {
+            let main_worktree = GitWorktree {
+                path: main_worktree_path,
+                ref_name: state
+                    .current_branch_name
+                    .as_ref()
+                    .map(|branch_name| format!("refs/heads/{branch_name}").into()),
+                sha: state.refs.get("HEAD").cloned().unwrap_or_default().into(),
+                is_main: true,
+                is_locked: false,
+            };
+            Ok(std::iter::once(main_worktree)
+                .chain(state.linked_worktrees.iter().cloned())
+                .collect())
+        })
+    }
+
+    fn worktree_add(
+        &self,
+        path: PathBuf,
+        branch_name: String,
+        create_branch: bool,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if create_branch {
+                state.branches.insert(branch_name.clone());
+            } else {
+                anyhow::ensure!(
+                    state.branches.contains(&branch_name),
+                    "invalid reference: {branch_name}"
+                );
+            }
+            state.linked_worktrees.push(GitWorktree {
+                path,
+                ref_name: Some(format!("refs/heads/{branch_name}").into()),
+                sha: state.refs.get("HEAD").cloned().unwrap_or_default().into(),
+                is_main: false,
+                is_locked: false,
+            });
+            Ok(())
+        })
+    }
+
+    fn worktree_remove(
+        &self,
+        path: PathBuf,
+        _force: bool,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let ix = state
+                .linked_worktrees
+                .iter()
+                .position(|worktree| worktree.path == path)
+                .with_context(|| format!("'{}' is not a working tree", path.display()))?;
+            state.linked_worktrees.remove(ix);
+            Ok(())
+        })
+    }
+
     fn blame(&self, path: RepoPath, _content: Rope) -> BoxFuture<'_, Result<git::blame::Blame>> {
         self.with_state_async(false, move |state| {
             state
@@ -400,9 +628,67 @@ impl GitRepository for FakeGitRepository {
 
     fn commit(
         &self,
-        _message: gpui::SharedString,
-        _name_and_email: Option<(gpui::SharedString, gpui::SharedString)>,
-        _options: CommitOptions,
+        message: gpui::SharedString,
+        name_and_email: Option<(gpui::SharedString, gpui::SharedString)>,
+        options: CommitOptions,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if options.amend {
+                anyhow::ensure!(!state.commits.is_empty(), "nothing to amend");
+                state.commits.remove(0);
+            }
+            let (author_name, author_email) = name_and_email.unwrap_or_default();
+            let sha = format!("{:040x}", state.commits.len() + 1);
+            state.head_contents = state.index_contents.clone();
+            state.refs.insert("HEAD".into(), sha.clone());
+            state.commits.insert(
+                0,
+                CommitDetails {
+                    sha: sha.into(),
+                    message,
+                    author_name,
+                    author_email,
+                    commit_timestamp: 0,
+                    parent_count: state.commits.len().min(1),
+                },
+            );
+            Ok(())
+        })
+    }
+
+    fn stash_push(
+        &self,
+        _paths: Vec<RepoPath>,
+        _options: StashOptions,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn stash_list(&self) -> BoxFuture<'_, Result<GitStash>> {
+        self.with_state_async(false, |state| Ok(state.stash.clone()))
+    }
+
+    fn stash_apply(
+        &self,
+        _index: usize,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn stash_pop(
+        &self,
+        _index: usize,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn stash_drop(
+        &self,
+        _index: usize,
         _env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>> {
         unimplemented!()
@@ -420,6 +706,17 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn push_tag(
+        &self,
+        _tag: String,
+        _remote: String,
+        _askpass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        unimplemented!()
+    }
+
     fn pull(
         &self,
         _branch: String,
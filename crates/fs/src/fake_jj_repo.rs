@@ -0,0 +1,54 @@
+use anyhow::Result;
+use git::jujutsu::{Bookmark, Change, JujutsuRepository};
+use git::repository::RepoPath;
+use git::status::GitStatus;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+pub struct FakeJujutsuRepository {
+    pub workspace_root: PathBuf,
+}
+
+impl JujutsuRepository for FakeJujutsuRepository {
+    fn list_bookmarks(&self) -> Vec<Bookmark> {
+        Vec::new()
+    }
+
+    fn log(&self, _revset: &str, _limit: usize) -> Result<Vec<Change>> {
+        Ok(Vec::new())
+    }
+
+    fn describe(&self, _revision: &str, _description: String) -> Result<()> {
+        Ok(())
+    }
+
+    fn new_change(&self, _revision: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn squash(&self, _revision: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn abandon(&self, _revision: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn edit(&self, _revision: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn workspace_root(&self) -> &Path {
+        &self.workspace_root
+    }
+
+    fn load_base_text(&self, _path: &RepoPath) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn status(&self, _path_prefixes: &[RepoPath]) -> Result<GitStatus> {
+        Ok(GitStatus {
+            entries: Arc::new([]),
+        })
+    }
+}
@@ -22,6 +22,7 @@ use std::os::unix::fs::{FileTypeExt, MetadataExt};
 
 use async_tar::Archive;
 use futures::{AsyncRead, Stream, StreamExt, future::BoxFuture};
+use git::jujutsu::{JujutsuRepository, JujutsuRepositoryOpener};
 use git::repository::{GitRepository, RealGitRepository};
 use rope::Rope;
 use serde::{Deserialize, Serialize};
@@ -39,6 +40,8 @@ use text::LineEnding;
 #[cfg(any(test, feature = "test-support"))]
 mod fake_git_repo;
 #[cfg(any(test, feature = "test-support"))]
+mod fake_jj_repo;
+#[cfg(any(test, feature = "test-support"))]
 use collections::{BTreeMap, btree_map};
 #[cfg(any(test, feature = "test-support"))]
 use fake_git_repo::FakeGitRepositoryState;
@@ -133,6 +136,8 @@ pub trait Fs: Send + Sync {
 
     fn home_dir(&self) -> Option<PathBuf>;
     fn open_repo(&self, abs_dot_git: &Path) -> Option<Arc<dyn GitRepository>>;
+    /// Opens the jj workspace rooted at `abs_work_directory`, if it contains a `.jj` directory.
+    fn open_jj_repo(&self, abs_work_directory: &Path) -> Option<Arc<dyn JujutsuRepository>>;
     fn git_init(&self, abs_work_directory: &Path, fallback_branch_name: String) -> Result<()>;
     fn is_fake(&self) -> bool;
     async fn is_case_sensitive(&self) -> Result<bool>;
@@ -244,6 +249,7 @@ impl From<MTime> for proto::Timestamp {
 
 pub struct RealFs {
     git_binary_path: Option<PathBuf>,
+    jj_opener: Option<JujutsuRepositoryOpener>,
     executor: BackgroundExecutor,
 }
 
@@ -320,9 +326,17 @@ impl RealFs {
     pub fn new(git_binary_path: Option<PathBuf>, executor: BackgroundExecutor) -> Self {
         Self {
             git_binary_path,
+            jj_opener: None,
             executor,
         }
     }
+
+    /// Enables opening jj workspaces with `jj_opener`. Without one, jj workspaces are treated as
+    /// plain git repositories.
+    pub fn with_jj_opener(mut self, jj_opener: JujutsuRepositoryOpener) -> Self {
+        self.jj_opener = Some(jj_opener);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -816,6 +830,18 @@ impl Fs for RealFs {
         )?))
     }
 
+    fn open_jj_repo(&self, abs_work_directory: &Path) -> Option<Arc<dyn JujutsuRepository>> {
+        use util::ResultExt as _;
+
+        let jj_opener = self.jj_opener?;
+        if !abs_work_directory.join(".jj").is_dir() {
+            return None;
+        }
+        jj_opener(abs_work_directory)
+            .with_context(|| format!("opening jj workspace at {abs_work_directory:?}"))
+            .log_err()
+    }
+
     fn git_init(&self, abs_work_directory_path: &Path, fallback_branch_name: String) -> Result<()> {
         let config = new_std_command("git")
             .current_dir(abs_work_directory_path)
@@ -2344,6 +2370,17 @@ impl Fs for FakeFs {
         .log_err()
     }
 
+    fn open_jj_repo(&self, abs_work_directory: &Path) -> Option<Arc<dyn JujutsuRepository>> {
+        let state = self.state.lock();
+        let entry = state.read_path(&abs_work_directory.join(".jj")).ok()?;
+        if !matches!(*entry.lock(), FakeFsEntry::Dir { .. }) {
+            return None;
+        }
+        Some(Arc::new(fake_jj_repo::FakeJujutsuRepository {
+            workspace_root: abs_work_directory.to_path_buf(),
+        }))
+    }
+
     fn git_init(
         &self,
         abs_work_directory_path: &Path,
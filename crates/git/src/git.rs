@@ -1,6 +1,7 @@
 pub mod blame;
 pub mod commit;
 mod hosting_provider;
+pub mod jujutsu;
 mod remote;
 pub mod repository;
 pub mod status;
@@ -50,11 +51,27 @@ actions!(
         StageFile,
         /// Unstages the current file.
         UnstageFile,
+        /// Stashes the changes to the current file.
+        StashFile,
+        /// Opens the commit history of the current file.
+        ViewFileHistory,
         // repo-wide
         /// Stages all changes in the repository.
         StageAll,
         /// Unstages all changes in the repository.
         UnstageAll,
+        /// Stashes all changes in the repository, including untracked files.
+        StashAll,
+        /// Applies the most recent stash entry and removes it from the stash.
+        StashPop,
+        /// Opens the list of stash entries.
+        ViewStash,
+        /// Opens the commit history of the repository.
+        ViewHistory,
+        /// Opens the list of tags, to check out, create, delete or push them.
+        ViewTags,
+        /// Opens the list of worktrees, to open one in a new window or create one for a branch.
+        ViewWorktrees,
         /// Restores all tracked files to their last committed state.
         RestoreTrackedFiles,
         /// Moves all untracked files to trash.
@@ -73,6 +90,12 @@ actions!(
         Fetch,
         /// Fetches changes from a specific remote.
         FetchFrom,
+        /// Rebases the current branch onto another branch.
+        Rebase,
+        /// Continues the merge, cherry-pick, revert or rebase that stopped because of conflicts.
+        ContinueOperation,
+        /// Aborts the merge, cherry-pick, revert or rebase that stopped because of conflicts.
+        AbortOperation,
         /// Creates a new commit with staged changes.
         Commit,
         /// Amends the last commit with staged changes.
@@ -115,7 +138,7 @@ impl Oid {
         self.0.as_bytes()
     }
 
-    pub(crate) fn is_zero(&self) -> bool {
+    pub fn is_zero(&self) -> bool {
         self.0.is_zero()
     }
 
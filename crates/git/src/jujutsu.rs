@@ -0,0 +1,85 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Result;
+use gpui::SharedString;
+
+use crate::repository::RepoPath;
+use crate::status::GitStatus;
+
+/// Opens the jj workspace rooted at the given directory. The implementation lives in the `jj`
+/// crate and is handed to the real filesystem by the application, so that crates working with
+/// repositories don't need to depend on jj-lib.
+pub type JujutsuRepositoryOpener = fn(&Path) -> Result<Arc<dyn JujutsuRepository>>;
+
+#[derive(Debug, Clone)]
+pub struct Bookmark {
+    pub ref_name: SharedString,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// The change ID, in jj's reverse-hex encoding.
+    pub change_id: SharedString,
+    pub commit_id: SharedString,
+    pub parent_commit_ids: Vec<SharedString>,
+    pub description: SharedString,
+    pub author_name: SharedString,
+    pub author_email: SharedString,
+    /// The author timestamp, in seconds since the Unix epoch.
+    pub timestamp: i64,
+    pub bookmarks: Vec<SharedString>,
+    pub is_working_copy: bool,
+    pub is_empty: bool,
+    pub is_root: bool,
+}
+
+impl Change {
+    pub fn short_change_id(&self) -> &str {
+        self.change_id.get(..8).unwrap_or(&self.change_id)
+    }
+
+    pub fn short_commit_id(&self) -> &str {
+        self.commit_id.get(..8).unwrap_or(&self.commit_id)
+    }
+
+    /// The first line of the description.
+    pub fn summary(&self) -> &str {
+        self.description.lines().next().unwrap_or_default()
+    }
+}
+
+pub trait JujutsuRepository: Send + Sync {
+    fn list_bookmarks(&self) -> Vec<Bookmark>;
+
+    /// Returns up to `limit` changes in `revset`, newest first.
+    fn log(&self, revset: &str, limit: usize) -> Result<Vec<Change>>;
+
+    /// Replaces the description of the change `revision` resolves to.
+    fn describe(&self, revision: &str, description: String) -> Result<()>;
+
+    /// Creates a new, empty change on top of `revision` and makes it the working-copy change.
+    fn new_change(&self, revision: &str) -> Result<()>;
+
+    /// Moves the contents of `revision` into its parent and abandons it.
+    fn squash(&self, revision: &str) -> Result<()>;
+
+    /// Abandons `revision`, rebasing its descendants onto its parents.
+    fn abandon(&self, revision: &str) -> Result<()>;
+
+    /// Makes `revision` the working-copy change.
+    fn edit(&self, revision: &str) -> Result<()>;
+
+    /// The directory containing the workspace's `.jj` directory.
+    fn workspace_root(&self) -> &Path;
+
+    /// Loads the contents of `path` in the parent of the working-copy change (`@-`), which is
+    /// what changes in the working copy are compared against. The parent is the one seen by the
+    /// last call to [`Self::status`], so that loading many paths doesn't reload the repository.
+    fn load_base_text(&self, path: &RepoPath) -> Result<Option<String>>;
+
+    /// Snapshots the working copy and returns the paths changed in the working-copy change
+    /// relative to its parent, limited to paths under `path_prefixes`. There is no index in jj, so
+    /// every change is reported as an unstaged change in the working tree.
+    fn status(&self, path_prefixes: &[RepoPath]) -> Result<GitStatus>;
+}
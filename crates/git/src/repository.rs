@@ -71,6 +71,45 @@ impl Branch {
     }
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Tag {
+    pub name: SharedString,
+    /// The commit the tag points to.
+    pub sha: SharedString,
+    /// The subject of an annotated tag's message, or `None` for a lightweight tag.
+    pub message: Option<SharedString>,
+    /// When the tag was created, or when its commit was made for a lightweight tag.
+    pub timestamp: i64,
+}
+
+impl Tag {
+    pub fn is_annotated(&self) -> bool {
+        self.message.is_some()
+    }
+}
+
+/// A working tree linked to the repository, as listed by `git worktree list`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct GitWorktree {
+    pub path: PathBuf,
+    /// The branch checked out in the worktree, or `None` if its `HEAD` is detached.
+    pub ref_name: Option<SharedString>,
+    pub sha: SharedString,
+    /// Whether this is the repository's main worktree, rather than a linked one.
+    pub is_main: bool,
+    pub is_locked: bool,
+}
+
+impl GitWorktree {
+    pub fn branch_name(&self) -> Option<&str> {
+        self.ref_name.as_ref().map(|ref_name| {
+            ref_name
+                .strip_prefix("refs/heads/")
+                .unwrap_or(ref_name.as_ref())
+        })
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct Upstream {
     pub ref_name: SharedString,
@@ -98,6 +137,89 @@ pub struct CommitOptions {
     pub amend: bool,
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct StashOptions {
+    pub message: Option<SharedString>,
+    pub include_untracked: bool,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct StashEntry {
+    /// The position of this entry in the stash, where `0` is the most recent.
+    pub index: usize,
+    pub sha: SharedString,
+    pub message: SharedString,
+    /// The branch that was checked out when the entry was created, if any.
+    pub branch: Option<SharedString>,
+    /// This is a unix timestamp
+    pub timestamp: i64,
+}
+
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct GitStash {
+    pub entries: Arc<[StashEntry]>,
+}
+
+/// A git operation that stopped partway through, usually because of conflicts, and must be
+/// continued or aborted before the repository is back to normal.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum InProgressOperation {
+    Merge,
+    CherryPick,
+    Revert,
+    Rebase,
+}
+
+impl InProgressOperation {
+    /// The git subcommand that drives this operation.
+    pub fn command(&self) -> &'static str {
+        match self {
+            InProgressOperation::Merge => "merge",
+            InProgressOperation::CherryPick => "cherry-pick",
+            InProgressOperation::Revert => "revert",
+            InProgressOperation::Rebase => "rebase",
+        }
+    }
+
+    /// A human-readable name for this operation.
+    pub fn label(&self) -> &'static str {
+        match self {
+            InProgressOperation::Merge => "Merge",
+            InProgressOperation::CherryPick => "Cherry-pick",
+            InProgressOperation::Revert => "Revert",
+            InProgressOperation::Rebase => "Rebase",
+        }
+    }
+
+    /// Labels for the two sides of a conflict produced by this operation.
+    pub fn conflict_side_labels(&self) -> (&'static str, &'static str) {
+        match self {
+            InProgressOperation::Merge => ("HEAD", "Origin"),
+            InProgressOperation::CherryPick => ("HEAD", "Picked"),
+            InProgressOperation::Revert => ("HEAD", "Reverted"),
+            // During a rebase, "ours" is the branch being rebased onto and "theirs" is the
+            // commit being replayed.
+            InProgressOperation::Rebase => ("Upstream", "Commit"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogOptions {
+    /// The number of commits to skip, used for pagination.
+    pub skip: usize,
+    /// The maximum number of commits to return.
+    pub limit: usize,
+    /// Only list commits that touch this path.
+    pub path: Option<RepoPath>,
+    /// Only list commits whose author matches this pattern.
+    pub author: Option<SharedString>,
+    /// Only list commits whose message matches this pattern.
+    pub message: Option<SharedString>,
+    /// Only follow the first parent of merge commits.
+    pub first_parent: bool,
+}
+
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub enum UpstreamTracking {
     /// Remote ref not present in local repository.
@@ -159,6 +281,8 @@ pub struct CommitDetails {
     pub commit_timestamp: i64,
     pub author_email: SharedString,
     pub author_name: SharedString,
+    /// The number of parents, zero for root commits.
+    pub parent_count: usize,
 }
 
 #[derive(Debug)]
@@ -344,6 +468,52 @@ pub trait GitRepository: Send + Sync {
     fn change_branch(&self, name: String) -> BoxFuture<'_, Result<()>>;
     fn create_branch(&self, name: String) -> BoxFuture<'_, Result<()>>;
 
+    /// Lists the lightweight and annotated tags in the repository, most recent first.
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>>;
+
+    /// Tags `HEAD`. A tag with a message is created as an annotated tag.
+    fn create_tag(
+        &self,
+        name: String,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn delete_tag(
+        &self,
+        name: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Checks out the commit a tag points to, leaving `HEAD` detached.
+    fn checkout_tag(
+        &self,
+        name: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Lists the repository's worktrees, starting with the main worktree.
+    fn worktree_list(&self) -> BoxFuture<'_, Result<Vec<GitWorktree>>>;
+
+    /// Creates a worktree at `path` with `branch_name` checked out, creating the branch from
+    /// `HEAD` first if `create_branch` is set.
+    fn worktree_add(
+        &self,
+        path: PathBuf,
+        branch_name: String,
+        create_branch: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Removes a linked worktree. Unless `force` is set, this fails if the worktree has
+    /// uncommitted changes.
+    fn worktree_remove(
+        &self,
+        path: PathBuf,
+        force: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
     fn reset(
         &self,
         commit: String,
@@ -358,9 +528,54 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Applies the changes introduced by the given commits on top of `HEAD`.
+    ///
+    /// Stopping because of conflicts is not an error; the operation is then reported by
+    /// [`GitRepository::in_progress_operation`] until it is continued or aborted.
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Creates new commits undoing the changes introduced by the given commits.
+    ///
+    /// Stopping because of conflicts is not an error, as with [`GitRepository::cherry_pick`].
+    fn revert(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Replays the commits of the current branch on top of `onto`.
+    ///
+    /// Stopping because of conflicts is not an error, as with [`GitRepository::cherry_pick`].
+    fn rebase(&self, onto: String, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    /// Resumes an operation that stopped, after its conflicts have been resolved and staged.
+    fn continue_operation(
+        &self,
+        operation: InProgressOperation,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Abandons an operation that stopped, restoring the state from before it began.
+    fn abort_operation(
+        &self,
+        operation: InProgressOperation,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the operation that is waiting to be continued or aborted, if any.
+    fn in_progress_operation(&self) -> BoxFuture<'_, Option<InProgressOperation>>;
+
     fn show(&self, commit: String) -> BoxFuture<'_, Result<CommitDetails>>;
 
     fn load_commit(&self, commit: String, cx: AsyncApp) -> BoxFuture<'_, Result<CommitDiff>>;
+
+    /// Lists the commits reachable from `HEAD`, most recent first.
+    fn log(&self, options: LogOptions) -> BoxFuture<'_, Result<Vec<CommitDetails>>>;
+
     fn blame(&self, path: RepoPath, content: Rope) -> BoxFuture<'_, Result<crate::blame::Blame>>;
 
     /// Returns the absolute path to the repository. For worktrees, this will be the path to the
@@ -394,6 +609,40 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Saves the changes at the given paths to a new stash entry, reverting them in the worktree.
+    ///
+    /// If `paths` is empty, all changes in the repository are stashed.
+    fn stash_push(
+        &self,
+        paths: Vec<RepoPath>,
+        options: StashOptions,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the entries of the stash, most recent first.
+    fn stash_list(&self) -> BoxFuture<'_, Result<GitStash>>;
+
+    /// Applies the stash entry at the given index, keeping it in the stash.
+    fn stash_apply(
+        &self,
+        index: usize,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Applies the stash entry at the given index and removes it from the stash.
+    fn stash_pop(
+        &self,
+        index: usize,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Removes the stash entry at the given index without applying it.
+    fn stash_drop(
+        &self,
+        index: usize,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
     fn push(
         &self,
         branch_name: String,
@@ -406,6 +655,17 @@ pub trait GitRepository: Send + Sync {
         cx: AsyncApp,
     ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
 
+    fn push_tag(
+        &self,
+        tag_name: String,
+        remote_name: String,
+        askpass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        // This method takes an AsyncApp to ensure it's invoked on the main thread,
+        // otherwise git-credentials-manager won't work.
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
     fn pull(
         &self,
         branch_name: String,
@@ -501,6 +761,99 @@ impl RealGitRepository {
             .context("failed to read git work directory")
             .map(Path::to_path_buf)
     }
+
+    /// Runs a command that may stop with conflicts, in which case it leaves an
+    /// [`InProgressOperation`] behind instead of failing.
+    ///
+    /// A failure only counts as stopping for conflicts if the command itself started the
+    /// operation; an operation that was already in progress does not hide other errors.
+    fn run_sequencer_command(
+        &self,
+        args: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        async move {
+            let operation_before = self.in_progress_operation().await;
+            let output = new_smol_command(&git_binary_path)
+                .current_dir(&working_directory?)
+                .envs(env.iter())
+                // Never block on an editor for commit messages.
+                .env("GIT_EDITOR", "true")
+                .args(&args)
+                .output()
+                .await?;
+            if output.status.success() {
+                return Ok(());
+            }
+            let operation_after = self.in_progress_operation().await;
+            let stopped_for_conflicts = operation_before.is_none() && operation_after.is_some();
+            if !stopped_for_conflicts {
+                anyhow::bail!(
+                    "Failed to {}:\n{}",
+                    args[0],
+                    String::from_utf8_lossy(&output.stderr)
+                );
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn run_command(
+        &self,
+        args: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args(&args)
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to {}:\n{}",
+                    args.join(" "),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn run_stash_command(
+        &self,
+        subcommand: &'static str,
+        index: usize,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args(["stash", subcommand, "--quiet"])
+                    .arg(format!("stash@{{{index}}}"))
+                    .output()
+                    .await?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to {subcommand} stash entry:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -569,6 +922,104 @@ impl GitRepository for RealGitRepository {
         repo.commondir().into()
     }
 
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let mut args = vec!["cherry-pick".to_string()];
+        args.extend(commits);
+        self.run_sequencer_command(args, env)
+    }
+
+    fn revert(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let mut args = vec!["revert".to_string(), "--no-edit".to_string()];
+        args.extend(commits);
+        self.run_sequencer_command(args, env)
+    }
+
+    fn rebase(&self, onto: String, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.run_sequencer_command(vec!["rebase".to_string(), onto], env)
+    }
+
+    fn continue_operation(
+        &self,
+        operation: InProgressOperation,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .env("GIT_EDITOR", "true")
+                    .args([operation.command(), "--continue"])
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to continue {}:\n{}",
+                    operation.command(),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn abort_operation(
+        &self,
+        operation: InProgressOperation,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args([operation.command(), "--abort"])
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to abort {}:\n{}",
+                    operation.command(),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn in_progress_operation(&self) -> BoxFuture<'_, Option<InProgressOperation>> {
+        let path = self.path();
+        self.executor
+            .spawn(async move {
+                // A rebase also writes REBASE_HEAD, but only once it stops on a commit, so
+                // check for its state directories instead.
+                if path.join("rebase-merge").is_dir() || path.join("rebase-apply").is_dir() {
+                    Some(InProgressOperation::Rebase)
+                } else if path.join("CHERRY_PICK_HEAD").is_file() {
+                    Some(InProgressOperation::CherryPick)
+                } else if path.join("REVERT_HEAD").is_file() {
+                    Some(InProgressOperation::Revert)
+                } else if path.join("MERGE_HEAD").is_file() {
+                    Some(InProgressOperation::Merge)
+                } else {
+                    None
+                }
+            })
+            .boxed()
+    }
+
     fn show(&self, commit: String) -> BoxFuture<'_, Result<CommitDetails>> {
         let working_directory = self.working_directory();
         self.executor
@@ -580,13 +1031,13 @@ impl GitRepository for RealGitRepository {
                         "--no-optional-locks",
                         "show",
                         "--no-patch",
-                        "--format=%H%x00%B%x00%at%x00%ae%x00%an%x00",
+                        "--format=%H%x00%B%x00%at%x00%ae%x00%an%x00%P%x00",
                         &commit,
                     ])
                     .output()?;
                 let output = std::str::from_utf8(&output.stdout)?;
                 let fields = output.split('\0').collect::<Vec<_>>();
-                if fields.len() != 6 {
+                if fields.len() != 7 {
                     bail!("unexpected git-show output for {commit:?}: {output:?}")
                 }
                 let sha = fields[0].to_string().into();
@@ -594,12 +1045,14 @@ impl GitRepository for RealGitRepository {
                 let commit_timestamp = fields[2].parse()?;
                 let author_email = fields[3].to_string().into();
                 let author_name = fields[4].to_string().into();
+                let parent_count = fields[5].split_whitespace().count();
                 Ok(CommitDetails {
                     sha,
                     message,
                     commit_timestamp,
                     author_email,
                     author_name,
+                    parent_count,
                 })
             })
             .boxed()
@@ -1081,6 +1534,184 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let fields = [
+                    "%(refname:strip=2)",
+                    "%(objecttype)",
+                    "%(objectname)",
+                    "%(*objectname)",
+                    "%(creatordate:unix)",
+                    "%(contents:subject)",
+                ]
+                .join("%00");
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args([
+                        "for-each-ref",
+                        "refs/tags",
+                        "--sort=-creatordate",
+                        "--format",
+                    ])
+                    .arg(&fields)
+                    .output()
+                    .await?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to get git tags:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+
+                parse_tag_input(&String::from_utf8_lossy(&output.stdout))
+            })
+            .boxed()
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let mut args = vec!["tag".to_string()];
+        if let Some(message) = message {
+            args.extend(["--annotate".to_string(), "--message".to_string(), message]);
+        }
+        args.push(name);
+        self.run_command(args, env)
+    }
+
+    fn delete_tag(
+        &self,
+        name: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.run_command(vec!["tag".to_string(), "--delete".to_string(), name], env)
+    }
+
+    fn checkout_tag(
+        &self,
+        name: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.run_command(
+            vec![
+                "checkout".to_string(),
+                "--detach".to_string(),
+                format!("refs/tags/{name}"),
+            ],
+            env,
+        )
+    }
+
+    fn worktree_list(&self) -> BoxFuture<'_, Result<Vec<GitWorktree>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args(["worktree", "list", "--porcelain"])
+                    .output()
+                    .await?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to list git worktrees:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+
+                parse_worktree_input(&String::from_utf8_lossy(&output.stdout))
+            })
+            .boxed()
+    }
+
+    fn worktree_add(
+        &self,
+        path: PathBuf,
+        branch_name: String,
+        create_branch: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let mut args = vec!["worktree".to_string(), "add".to_string()];
+        if create_branch {
+            args.extend(["-b".to_string(), branch_name.clone()]);
+        }
+        args.push(path.to_string_lossy().into_owned());
+        if !create_branch {
+            args.push(branch_name);
+        }
+        self.run_command(args, env)
+    }
+
+    fn worktree_remove(
+        &self,
+        path: PathBuf,
+        force: bool,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let mut args = vec!["worktree".to_string(), "remove".to_string()];
+        if force {
+            args.push("--force".to_string());
+        }
+        args.push(path.to_string_lossy().into_owned());
+        self.run_command(args, env)
+    }
+
+    fn log(&self, options: LogOptions) -> BoxFuture<'_, Result<Vec<CommitDetails>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let mut command = new_smol_command(&git_binary_path);
+                command.current_dir(&working_directory?).args([
+                    "--no-optional-locks",
+                    "log",
+                    "--no-color",
+                    "--format=%x1e%H%x00%B%x00%at%x00%ae%x00%an%x00%P",
+                ]);
+                command.arg(format!("--skip={}", options.skip));
+                if options.limit > 0 {
+                    command.arg(format!("--max-count={}", options.limit));
+                }
+                if options.first_parent {
+                    command.arg("--first-parent");
+                }
+                if options.author.is_some() || options.message.is_some() {
+                    command.args(["--regexp-ignore-case", "--fixed-strings"]);
+                }
+                if let Some(author) = &options.author {
+                    command.arg(format!("--author={author}"));
+                }
+                if let Some(message) = &options.message {
+                    command.arg(format!("--grep={message}"));
+                }
+                command.arg("HEAD");
+                if let Some(path) = &options.path {
+                    command.arg("--").arg(path.to_unix_style());
+                }
+
+                let output = command.output().await?;
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    // A repository without any commits has no history to show.
+                    if stderr.contains("does not have any commits")
+                        || stderr.contains("unknown revision")
+                    {
+                        return Ok(Vec::new());
+                    }
+                    anyhow::bail!("Failed to load commit history:\n{stderr}");
+                }
+
+                parse_log_output(&String::from_utf8_lossy(&output.stdout))
+            })
+            .boxed()
+    }
+
     fn blame(&self, path: RepoPath, content: Rope) -> BoxFuture<'_, Result<crate::blame::Blame>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.git_binary_path.clone();
@@ -1225,11 +1856,135 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
-    fn push(
+    fn stash_push(
+        &self,
+        paths: Vec<RepoPath>,
+        options: StashOptions,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let mut cmd = new_smol_command(&git_binary_path);
+                cmd.current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args(["stash", "push", "--quiet"]);
+
+                if options.include_untracked {
+                    cmd.arg("--include-untracked");
+                }
+
+                if let Some(message) = options.message {
+                    cmd.arg("--message").arg(message.to_string());
+                }
+
+                if !paths.is_empty() {
+                    cmd.arg("--").args(paths.iter().map(|p| p.to_unix_style()));
+                }
+
+                let output = cmd.output().await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to stash:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn stash_list(&self) -> BoxFuture<'_, Result<GitStash>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory?)
+                    .args([
+                        "--no-optional-locks",
+                        "stash",
+                        "list",
+                        "--format=%gd%x00%H%x00%ct%x00%gs",
+                    ])
+                    .output()
+                    .await?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to list stash entries:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+
+                let input = String::from_utf8_lossy(&output.stdout);
+                Ok(GitStash {
+                    entries: parse_stash_input(&input)?.into(),
+                })
+            })
+            .boxed()
+    }
+
+    fn stash_apply(
+        &self,
+        index: usize,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.run_stash_command("apply", index, env)
+    }
+
+    fn stash_pop(
+        &self,
+        index: usize,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.run_stash_command("pop", index, env)
+    }
+
+    fn stash_drop(
+        &self,
+        index: usize,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.run_stash_command("drop", index, env)
+    }
+
+    fn push(
+        &self,
+        branch_name: String,
+        remote_name: String,
+        options: Option<PushOptions>,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let executor = cx.background_executor().clone();
+        async move {
+            let working_directory = working_directory?;
+            let mut command = new_smol_command("git");
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory)
+                .args(["push"])
+                .args(options.map(|option| match option {
+                    PushOptions::SetUpstream => "--set-upstream",
+                    PushOptions::Force => "--force-with-lease",
+                }))
+                .arg(remote_name)
+                .arg(format!("{}:{}", branch_name, branch_name))
+                .stdin(smol::process::Stdio::null())
+                .stdout(smol::process::Stdio::piped())
+                .stderr(smol::process::Stdio::piped());
+
+            run_git_command(env, ask_pass, command, &executor).await
+        }
+        .boxed()
+    }
+
+    fn push_tag(
         &self,
-        branch_name: String,
+        tag_name: String,
         remote_name: String,
-        options: Option<PushOptions>,
         ask_pass: AskPassDelegate,
         env: Arc<HashMap<String, String>>,
         cx: AsyncApp,
@@ -1237,18 +1992,13 @@ impl GitRepository for RealGitRepository {
         let working_directory = self.working_directory();
         let executor = cx.background_executor().clone();
         async move {
-            let working_directory = working_directory?;
             let mut command = new_smol_command("git");
             command
                 .envs(env.iter())
-                .current_dir(&working_directory)
+                .current_dir(&working_directory?)
                 .args(["push"])
-                .args(options.map(|option| match option {
-                    PushOptions::SetUpstream => "--set-upstream",
-                    PushOptions::Force => "--force-with-lease",
-                }))
                 .arg(remote_name)
-                .arg(format!("{}:{}", branch_name, branch_name))
+                .arg(format!("refs/tags/{tag_name}:refs/tags/{tag_name}"))
                 .stdin(smol::process::Stdio::null())
                 .stdout(smol::process::Stdio::piped())
                 .stderr(smol::process::Stdio::piped());
@@ -1956,6 +2706,137 @@ fn parse_branch_input(input: &str) -> Result<Vec<Branch>> {
     Ok(branches)
 }
 
+fn parse_worktree_input(input: &str) -> Result<Vec<GitWorktree>> {
+    let mut worktrees = Vec::new();
+    for record in input.split("\n\n") {
+        if record.trim().is_empty() {
+            continue;
+        }
+        let mut path = None;
+        let mut ref_name = None;
+        let mut sha = SharedString::default();
+        let mut is_locked = false;
+        for line in record.lines() {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "worktree" => path = Some(PathBuf::from(value)),
+                "HEAD" => sha = value.to_string().into(),
+                "branch" => ref_name = Some(value.to_string().into()),
+                "locked" => is_locked = true,
+                _ => {}
+            }
+        }
+        worktrees.push(GitWorktree {
+            path: path.context("no worktree path")?,
+            ref_name,
+            sha,
+            is_main: worktrees.is_empty(),
+            is_locked,
+        });
+    }
+
+    Ok(worktrees)
+}
+
+fn parse_tag_input(input: &str) -> Result<Vec<Tag>> {
+    let mut tags = Vec::new();
+    for line in input.split('\n') {
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split('\x00');
+        let name = fields.next().context("no refname")?;
+        let object_type = fields.next().context("no objecttype")?;
+        let object_name = fields.next().context("no objectname")?;
+        let peeled_object_name = fields.next().context("no *objectname")?;
+        let timestamp = fields.next().context("no creatordate")?.parse::<i64>()?;
+        let subject = fields.next().context("no contents:subject")?;
+
+        let is_annotated = object_type == "tag";
+        tags.push(Tag {
+            name: name.to_string().into(),
+            sha: if is_annotated {
+                peeled_object_name
+            } else {
+                object_name
+            }
+            .to_string()
+            .into(),
+            message: is_annotated.then(|| subject.to_string().into()),
+            timestamp,
+        });
+    }
+
+    Ok(tags)
+}
+
+fn parse_log_output(input: &str) -> Result<Vec<CommitDetails>> {
+    let mut commits = Vec::new();
+    for record in input.split('\x1e') {
+        let record = record.trim_end_matches('\n');
+        if record.is_empty() {
+            continue;
+        }
+        let mut fields = record.split('\x00');
+        let sha = fields.next().context("no commit sha")?;
+        let message = fields.next().context("no commit message")?;
+        let commit_timestamp = fields
+            .next()
+            .context("no commit timestamp")?
+            .parse::<i64>()?;
+        let author_email = fields.next().context("no author email")?;
+        let author_name = fields.next().context("no author name")?;
+        let parents = fields.next().context("no parents")?;
+        commits.push(CommitDetails {
+            sha: sha.to_string().into(),
+            message: message.trim_end().to_string().into(),
+            commit_timestamp,
+            author_email: author_email.to_string().into(),
+            author_name: author_name.to_string().into(),
+            parent_count: parents.split_whitespace().count(),
+        });
+    }
+    Ok(commits)
+}
+
+fn parse_stash_input(input: &str) -> Result<Vec<StashEntry>> {
+    let mut entries = Vec::new();
+    for line in input.split('\n') {
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split('\x00');
+        let selector = fields.next().context("no reflog selector")?;
+        let index = selector
+            .strip_prefix("stash@{")
+            .and_then(|rest| rest.strip_suffix('}'))
+            .with_context(|| format!("unexpected stash selector {selector:?}"))?
+            .parse::<usize>()?;
+        let sha: SharedString = fields.next().context("no objectname")?.to_string().into();
+        let timestamp = fields.next().context("no committerdate")?.parse::<i64>()?;
+        let subject = fields.next().context("no reflog subject")?;
+
+        // Stash subjects look like "WIP on <branch>: <sha> <subject>" when no message was
+        // given, and "On <branch>: <message>" otherwise.
+        let (branch, message) = subject
+            .strip_prefix("WIP on ")
+            .or_else(|| subject.strip_prefix("On "))
+            .and_then(|rest| rest.split_once(": "))
+            .map(|(branch, message)| (Some(branch.to_string().into()), message))
+            .unwrap_or((None, subject));
+
+        entries.push(StashEntry {
+            index,
+            sha,
+            message: message.to_string().into(),
+            branch,
+            timestamp,
+        });
+    }
+
+    Ok(entries)
+}
+
 fn parse_upstream_track(upstream_track: &str) -> Result<UpstreamTracking> {
     if upstream_track == "" {
         return Ok(UpstreamTracking::Tracked(UpstreamTrackingStatus {
@@ -2246,6 +3127,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_worktrees_parsing() {
+        let input = "worktree /code/zed\n\
+                     HEAD 368fb3179af22d5b28d10aa7cf312248fe667e07\n\
+                     branch refs/heads/main\n\
+                     \n\
+                     worktree /code/zed-review\n\
+                     HEAD c9d4205bac648f81b5af16e03d1d4b35180847c1\n\
+                     detached\n\
+                     locked reviewing\n\
+                     \n";
+        assert_eq!(
+            parse_worktree_input(input).unwrap(),
+            vec![
+                GitWorktree {
+                    path: PathBuf::from("/code/zed"),
+                    ref_name: Some("refs/heads/main".into()),
+                    sha: "368fb3179af22d5b28d10aa7cf312248fe667e07".into(),
+                    is_main: true,
+                    is_locked: false,
+                },
+                GitWorktree {
+                    path: PathBuf::from("/code/zed-review"),
+                    ref_name: None,
+                    sha: "c9d4205bac648f81b5af16e03d1d4b35180847c1".into(),
+                    is_main: false,
+                    is_locked: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tags_parsing() {
+        // suppress "help: octal escapes are not supported, `\0` is always null"
+        #[allow(clippy::octal_escapes)]
+        let input = "v1.0\0tag\00f553fbc83f7642e70cb8d9dc4c9aee5cfb32103\0368fb3179af22d5b28d10aa7cf312248fe667e07\01792273205\0Release 1.0\n\
+                     light\0commit\0c9d4205bac648f81b5af16e03d1d4b35180847c1\0\01792273159\0Fix a bug\n";
+        assert_eq!(
+            parse_tag_input(input).unwrap(),
+            vec![
+                Tag {
+                    name: "v1.0".into(),
+                    sha: "368fb3179af22d5b28d10aa7cf312248fe667e07".into(),
+                    message: Some("Release 1.0".into()),
+                    timestamp: 1792273205,
+                },
+                Tag {
+                    name: "light".into(),
+                    sha: "c9d4205bac648f81b5af16e03d1d4b35180847c1".into(),
+                    message: None,
+                    timestamp: 1792273159,
+                },
+            ]
+        )
+    }
+
     #[test]
     fn test_branches_parsing() {
         // suppress "help: octal escapes are not supported, `\0` is always null"
@@ -2273,6 +3211,371 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_stash_parsing() {
+        let input = "stash@{0}\0a1b2c3d4e5f60718293a4b5c6d7e8f901234567\01733187470\0On main: fix the thing\nstash@{1}\0060964da10574cd9bf06463a53bf6e0769c5c45e\01733180000\0WIP on feature/x: 060964d generated protobuf\n";
+        assert_eq!(
+            parse_stash_input(input).unwrap(),
+            vec![
+                StashEntry {
+                    index: 0,
+                    sha: "a1b2c3d4e5f60718293a4b5c6d7e8f901234567".into(),
+                    message: "fix the thing".into(),
+                    branch: Some("main".into()),
+                    timestamp: 1733187470,
+                },
+                StashEntry {
+                    index: 1,
+                    sha: "060964da10574cd9bf06463a53bf6e0769c5c45e".into(),
+                    message: "060964d generated protobuf".into(),
+                    branch: Some("feature/x".into()),
+                    timestamp: 1733180000,
+                },
+            ]
+        )
+    }
+
+    #[test]
+    fn test_log_parsing() {
+        let input = "\x1ea1b2c3d4e5f60718293a4b5c6d7e8f901234567\0Fix the thing\n\nLonger description\n\01733187470\0jane@example.com\0Jane Doe\0060964da10574cd9bf06463a53bf6e0769c5c45e\n\x1e060964da10574cd9bf06463a53bf6e0769c5c45e\0Initial commit\n\01733180000\0john@example.com\0John Doe\0\n";
+        assert_eq!(
+            parse_log_output(input).unwrap(),
+            vec![
+                CommitDetails {
+                    sha: "a1b2c3d4e5f60718293a4b5c6d7e8f901234567".into(),
+                    message: "Fix the thing\n\nLonger description".into(),
+                    commit_timestamp: 1733187470,
+                    author_email: "jane@example.com".into(),
+                    author_name: "Jane Doe".into(),
+                    parent_count: 1,
+                },
+                CommitDetails {
+                    sha: "060964da10574cd9bf06463a53bf6e0769c5c45e".into(),
+                    message: "Initial commit".into(),
+                    commit_timestamp: 1733180000,
+                    author_email: "john@example.com".into(),
+                    author_name: "John Doe".into(),
+                    parent_count: 0,
+                },
+            ]
+        )
+    }
+
+    #[gpui::test]
+    async fn test_log(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+        assert!(repo.log(LogOptions::default()).await.unwrap().is_empty());
+
+        for (path, message) in [("a", "Add a"), ("b", "Add b"), ("a", "Update a")] {
+            smol::fs::write(repo_dir.path().join(path), message)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![RepoPath::from_str(path)], env.clone())
+                .await
+                .unwrap();
+            repo.commit(message.into(), None, CommitOptions::default(), env.clone())
+                .await
+                .unwrap();
+        }
+
+        let messages = |commits: Vec<CommitDetails>| {
+            commits
+                .into_iter()
+                .map(|commit| commit.message.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            messages(repo.log(LogOptions::default()).await.unwrap()),
+            ["Update a", "Add b", "Add a"]
+        );
+        assert_eq!(
+            repo.log(LogOptions::default())
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|commit| commit.parent_count)
+                .collect::<Vec<_>>(),
+            [1, 1, 0]
+        );
+        assert_eq!(
+            messages(
+                repo.log(LogOptions {
+                    skip: 1,
+                    limit: 1,
+                    ..Default::default()
+                })
+                .await
+                .unwrap()
+            ),
+            ["Add b"]
+        );
+        assert_eq!(
+            messages(
+                repo.log(LogOptions {
+                    path: Some(RepoPath::from_str("a")),
+                    ..Default::default()
+                })
+                .await
+                .unwrap()
+            ),
+            ["Update a", "Add a"]
+        );
+        assert_eq!(
+            messages(
+                repo.log(LogOptions {
+                    message: Some("add".into()),
+                    ..Default::default()
+                })
+                .await
+                .unwrap()
+            ),
+            ["Add b", "Add a"]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_worktrees(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        smol::fs::write(repo_dir.path().join("file"), "contents")
+            .await
+            .unwrap();
+        repo.stage_paths(vec![RepoPath::from_str("file")], env.clone())
+            .await
+            .unwrap();
+        repo.commit(
+            "Initial".into(),
+            None,
+            CommitOptions::default(),
+            env.clone(),
+        )
+        .await
+        .unwrap();
+
+        let worktrees = repo.worktree_list().await.unwrap();
+        assert_eq!(worktrees.len(), 1);
+        assert!(worktrees[0].is_main);
+
+        let worktrees_dir = tempfile::tempdir().unwrap();
+        let review_path = worktrees_dir.path().join("review");
+        repo.worktree_add(review_path.clone(), "review".into(), true, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            smol::fs::read_to_string(review_path.join("file"))
+                .await
+                .unwrap(),
+            "contents"
+        );
+
+        let worktrees = repo.worktree_list().await.unwrap();
+        assert_eq!(
+            worktrees
+                .iter()
+                .map(|worktree| (worktree.is_main, worktree.branch_name()))
+                .collect::<Vec<_>>(),
+            [(true, worktrees[0].branch_name()), (false, Some("review"))]
+        );
+
+        repo.worktree_remove(worktrees[1].path.clone(), false, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(repo.worktree_list().await.unwrap().len(), 1);
+        assert!(!review_path.exists());
+    }
+
+    #[gpui::test]
+    async fn test_tags(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        for contents in ["one", "two"] {
+            smol::fs::write(repo_dir.path().join("file"), contents)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![RepoPath::from_str("file")], env.clone())
+                .await
+                .unwrap();
+            repo.commit(contents.into(), None, CommitOptions::default(), env.clone())
+                .await
+                .unwrap();
+            repo.create_tag(format!("light-{contents}"), None, env.clone())
+                .await
+                .unwrap();
+        }
+        repo.create_tag("v1.0".into(), Some("Release 1.0".into()), env.clone())
+            .await
+            .unwrap();
+        let head_sha = repo.head_sha().await.unwrap();
+
+        let mut tags = repo.tags().await.unwrap();
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(
+            tags.iter()
+                .map(|tag| (tag.name.as_ref(), tag.message.as_deref()))
+                .collect::<Vec<_>>(),
+            [
+                ("light-one", None),
+                ("light-two", None),
+                ("v1.0", Some("Release 1.0")),
+            ]
+        );
+        assert_eq!(tags[2].sha.as_ref(), head_sha);
+
+        repo.checkout_tag("light-one".into(), env.clone())
+            .await
+            .unwrap();
+        assert_eq!(repo.head_sha().await.unwrap(), tags[0].sha.as_ref());
+        assert_eq!(
+            smol::fs::read_to_string(repo_dir.path().join("file"))
+                .await
+                .unwrap(),
+            "one"
+        );
+
+        repo.delete_tag("light-two".into(), env.clone())
+            .await
+            .unwrap();
+        let tags = repo.tags().await.unwrap();
+        assert!(tags.iter().all(|tag| tag.name != "light-two"));
+        assert_eq!(tags.len(), 2);
+    }
+
+    #[gpui::test]
+    async fn test_revert_conflict_and_abort(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        for contents in ["one\n", "two\n", "three\n"] {
+            smol::fs::write(repo_dir.path().join("file"), contents)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![RepoPath::from_str("file")], env.clone())
+                .await
+                .unwrap();
+            repo.commit(contents.into(), None, CommitOptions::default(), env.clone())
+                .await
+                .unwrap();
+        }
+        let head_sha = repo.head_sha().await.unwrap();
+        let commits = repo.log(LogOptions::default()).await.unwrap();
+        assert_eq!(repo.in_progress_operation().await, None);
+
+        // Reverting the middle commit conflicts with the most recent one.
+        repo.revert(vec![commits[1].sha.to_string()], env.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.in_progress_operation().await,
+            Some(InProgressOperation::Revert)
+        );
+
+        // Starting another operation fails instead of being mistaken for a conflict.
+        let error = repo
+            .cherry_pick(vec![commits[0].sha.to_string()], env.clone())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Failed to cherry-pick"));
+
+        repo.abort_operation(InProgressOperation::Revert, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(repo.in_progress_operation().await, None);
+        assert_eq!(repo.head_sha().await.unwrap(), head_sha);
+        assert_eq!(
+            smol::fs::read_to_string(repo_dir.path().join("file"))
+                .await
+                .unwrap(),
+            "three\n"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_stash_push_and_pop(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let file_path = repo_dir.path().join("file");
+        smol::fs::write(&file_path, "initial").await.unwrap();
+
+        let repo =
+            RealGitRepository::new(&repo_dir.path().join(".git"), None, cx.executor()).unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+        repo.stage_paths(vec![RepoPath::from_str("file")], env.clone())
+            .await
+            .unwrap();
+        repo.commit(
+            "Initial commit".into(),
+            None,
+            CommitOptions::default(),
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        assert!(repo.stash_list().await.unwrap().entries.is_empty());
+
+        smol::fs::write(&file_path, "modified").await.unwrap();
+        smol::fs::write(repo_dir.path().join("untracked"), "new")
+            .await
+            .unwrap();
+        repo.stash_push(
+            Vec::new(),
+            StashOptions {
+                message: Some("my changes".into()),
+                include_untracked: true,
+            },
+            env.clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            smol::fs::read_to_string(&file_path).await.unwrap(),
+            "initial"
+        );
+        assert!(!repo_dir.path().join("untracked").exists());
+        let stash = repo.stash_list().await.unwrap();
+        assert_eq!(stash.entries.len(), 1);
+        assert_eq!(stash.entries[0].index, 0);
+        assert_eq!(stash.entries[0].message.as_ref(), "my changes");
+
+        repo.stash_pop(0, env.clone()).await.unwrap();
+        assert_eq!(
+            smol::fs::read_to_string(&file_path).await.unwrap(),
+            "modified"
+        );
+        assert_eq!(
+            smol::fs::read_to_string(repo_dir.path().join("untracked"))
+                .await
+                .unwrap(),
+            "new"
+        );
+        assert!(repo.stash_list().await.unwrap().entries.is_empty());
+    }
+
     impl RealGitRepository {
         /// Force a Git garbage collection on the repository.
         fn gc(&self) -> BoxFuture<'_, Result<()>> {
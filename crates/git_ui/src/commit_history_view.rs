@@ -0,0 +1,776 @@
+//! CommitHistoryView lists the commits of a repository, or of a single file within it.
+
+use crate::commit_view::{CommitView, GitBlob, build_buffer};
+use crate::diff_view::DiffView;
+use anyhow::Context as _;
+use editor::{Editor, EditorEvent};
+use git::repository::{CommitDetails, CommitSummary, LogOptions, RepoPath};
+use gpui::{
+    Action, AnyElement, App, Corner, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    ListSizingBehavior, MouseDownEvent, Pixels, Point, ScrollStrategy, Subscription, Task,
+    UniformListScrollHandle, WeakEntity, actions, anchored, deferred, uniform_list,
+};
+use menu::{Confirm, SecondaryConfirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::git_store::{Repository, RepositoryEvent};
+use std::{ops::Range, sync::Arc, time::Duration};
+use time::OffsetDateTime;
+use time_format::format_local_timestamp;
+use ui::{Checkbox, ContextMenu, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::maybe;
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, TabContentParams},
+    notifications::DetachAndPromptErr,
+};
+
+actions!(
+    commit_history,
+    [
+        /// Opens a diff between the selected commit's version of the file and the working tree.
+        CompareWithWorkingTree,
+        /// Toggles whether only the first parent of merge commits is followed.
+        ToggleFirstParent,
+        /// Applies the changes introduced by the selected commit onto the current branch.
+        CherryPick,
+        /// Creates a new commit that undoes the changes introduced by the selected commit.
+        Revert,
+        /// Rebases the current branch onto the selected commit.
+        RebaseOntoCommit,
+    ]
+);
+
+/// The number of commits requested from the repository at a time.
+const PAGE_SIZE: usize = 100;
+/// How close to the end of the loaded commits the list may scroll before the next page is loaded.
+const LOAD_MORE_THRESHOLD: usize = 20;
+const FILTER_DEBOUNCE: Duration = Duration::from_millis(250);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &git::ViewHistory, window, cx| {
+        let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+            return;
+        };
+        CommitHistoryView::open(repository, None, workspace, window, cx);
+    });
+    workspace.register_action(|workspace, _: &git::ViewFileHistory, window, cx| {
+        let Some(project_path) = workspace
+            .active_item(cx)
+            .and_then(|item| item.project_path(cx))
+        else {
+            return;
+        };
+        let Some((repository, repo_path)) = workspace
+            .project()
+            .read(cx)
+            .git_store()
+            .read(cx)
+            .repository_and_path_for_project_path(&project_path, cx)
+        else {
+            return;
+        };
+        CommitHistoryView::open(repository, Some(repo_path), workspace, window, cx);
+    });
+}
+
+pub struct CommitHistoryView {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    /// When set, only commits touching this path are listed.
+    path: Option<RepoPath>,
+    commits: Vec<CommitDetails>,
+    selected_index: Option<usize>,
+    has_more: bool,
+    first_parent: bool,
+    error: Option<SharedString>,
+    head_sha: Option<SharedString>,
+    message_filter: Entity<Editor>,
+    author_filter: Entity<Editor>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    load_task: Option<Task<()>>,
+    filter_task: Option<Task<()>>,
+    context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl CommitHistoryView {
+    pub fn open(
+        repository: Entity<Repository>,
+        path: Option<RepoPath>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let repository_id = repository.read(cx).id;
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .filter_map(|item| item.downcast::<Self>())
+            .find(|view| {
+                let view = view.read(cx);
+                view.repository.read(cx).id == repository_id && view.path == path
+            });
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return;
+        }
+
+        let workspace_handle = cx.entity().downgrade();
+        let view = cx.new(|cx| Self::new(repository, path, workspace_handle, window, cx));
+        workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        path: Option<RepoPath>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let message_filter = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Filter by message…", cx);
+            editor
+        });
+        let author_filter = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Filter by author…", cx);
+            editor
+        });
+
+        let subscriptions = vec![
+            cx.subscribe(&message_filter, Self::on_filter_event),
+            cx.subscribe(&author_filter, Self::on_filter_event),
+            cx.subscribe(
+                &repository,
+                |this, repository, event: &RepositoryEvent, cx| {
+                    if let RepositoryEvent::Updated { .. } = event {
+                        let head_sha = repository
+                            .read(cx)
+                            .head_commit
+                            .as_ref()
+                            .map(|commit| commit.sha.clone());
+                        if head_sha != this.head_sha {
+                            this.head_sha = head_sha;
+                            this.reload(cx);
+                        }
+                    }
+                },
+            ),
+        ];
+        let head_sha = repository
+            .read(cx)
+            .head_commit
+            .as_ref()
+            .map(|commit| commit.sha.clone());
+
+        let mut this = Self {
+            repository,
+            workspace,
+            path,
+            commits: Vec::new(),
+            selected_index: None,
+            has_more: true,
+            first_parent: false,
+            error: None,
+            head_sha,
+            message_filter,
+            author_filter,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            load_task: None,
+            filter_task: None,
+            context_menu: None,
+            _subscriptions: subscriptions,
+        };
+        this.load_more(cx);
+        this
+    }
+
+    fn on_filter_event(&mut self, _: Entity<Editor>, event: &EditorEvent, cx: &mut Context<Self>) {
+        if let EditorEvent::BufferEdited = event {
+            self.filter_task = Some(cx.spawn(async move |this, cx| {
+                cx.background_executor().timer(FILTER_DEBOUNCE).await;
+                this.update(cx, |this, cx| this.reload(cx)).ok();
+            }));
+        }
+    }
+
+    fn filter_text(editor: &Entity<Editor>, cx: &App) -> Option<SharedString> {
+        let text = editor.read(cx).text(cx);
+        let text = text.trim();
+        (!text.is_empty()).then(|| text.to_string().into())
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        self.commits.clear();
+        self.selected_index = None;
+        self.has_more = true;
+        self.error = None;
+        self.load_task = None;
+        self.load_more(cx);
+        cx.notify();
+    }
+
+    fn load_more(&mut self, cx: &mut Context<Self>) {
+        if self.load_task.is_some() || !self.has_more {
+            return;
+        }
+
+        let options = LogOptions {
+            skip: self.commits.len(),
+            limit: PAGE_SIZE,
+            path: self.path.clone(),
+            author: Self::filter_text(&self.author_filter, cx),
+            message: Self::filter_text(&self.message_filter, cx),
+            first_parent: self.first_parent,
+        };
+        let log = self
+            .repository
+            .update(cx, |repository, _| repository.log(options));
+        self.load_task = Some(cx.spawn(async move |this, cx| {
+            let commits = maybe!(async { log.await? }).await;
+            this.update(cx, |this, cx| {
+                this.load_task = None;
+                match commits {
+                    Ok(commits) => {
+                        this.has_more = commits.len() == PAGE_SIZE;
+                        this.commits.extend(commits);
+                        if this.selected_index.is_none() && !this.commits.is_empty() {
+                            this.selected_index = Some(0);
+                        }
+                    }
+                    Err(error) => {
+                        this.has_more = false;
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn select_index(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_index = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        if ix + LOAD_MORE_THRESHOLD >= self.commits.len() {
+            self.load_more(cx);
+        }
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.commits.is_empty() {
+            return;
+        }
+        let ix = self
+            .selected_index
+            .map_or(0, |ix| (ix + 1).min(self.commits.len() - 1));
+        self.select_index(ix, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        if self.commits.is_empty() {
+            return;
+        }
+        let ix = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_index(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.commits.is_empty() {
+            self.select_index(0, cx);
+        }
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.commits.is_empty() {
+            self.select_index(self.commits.len() - 1, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.open_commit(ix, window, cx);
+        }
+    }
+
+    fn secondary_confirm(
+        &mut self,
+        _: &SecondaryConfirm,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.selected_index {
+            self.compare_with_working_tree(ix, window, cx);
+        }
+    }
+
+    fn compare_selected_with_working_tree(
+        &mut self,
+        _: &CompareWithWorkingTree,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.selected_index {
+            self.compare_with_working_tree(ix, window, cx);
+        }
+    }
+
+    fn toggle_first_parent(
+        &mut self,
+        _: &ToggleFirstParent,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.first_parent = !self.first_parent;
+        self.reload(cx);
+    }
+
+    fn selected_sha(&self) -> Option<String> {
+        let commit = self.commits.get(self.selected_index?)?;
+        Some(commit.sha.to_string())
+    }
+
+    fn cherry_pick(&mut self, _: &CherryPick, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(sha) = self.selected_sha() else {
+            return;
+        };
+        let cherry_pick = self
+            .repository
+            .update(cx, |repository, cx| repository.cherry_pick(vec![sha], cx));
+        cx.spawn(async move |_, _| cherry_pick.await?)
+            .detach_and_prompt_err("Failed to cherry-pick commit", window, cx, |_, _, _| None);
+    }
+
+    fn revert(&mut self, _: &Revert, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(commit) = self.selected_index.and_then(|ix| self.commits.get(ix)) else {
+            return;
+        };
+        // A root commit has no parent to revert to.
+        if commit.parent_count == 0 {
+            return;
+        }
+        let sha = commit.sha.to_string();
+        let revert = self
+            .repository
+            .update(cx, |repository, cx| repository.revert(vec![sha], cx));
+        cx.spawn(async move |_, _| revert.await?)
+            .detach_and_prompt_err("Failed to revert commit", window, cx, |_, _, _| None);
+    }
+
+    fn rebase_onto_commit(
+        &mut self,
+        _: &RebaseOntoCommit,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(sha) = self.selected_sha() else {
+            return;
+        };
+        let rebase = self
+            .repository
+            .update(cx, |repository, cx| repository.rebase(sha, cx));
+        cx.spawn(async move |_, _| rebase.await?)
+            .detach_and_prompt_err("Failed to rebase", window, cx, |_, _, _| None);
+    }
+
+    fn deploy_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.selected_index = Some(ix);
+        let has_path = self.path.is_some();
+        let has_parent = self
+            .commits
+            .get(ix)
+            .is_some_and(|commit| commit.parent_count > 0);
+        let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
+            context_menu
+                .context(self.focus_handle.clone())
+                .action("Open Commit", Confirm.boxed_clone())
+                .when(has_path, |menu| {
+                    menu.action(
+                        "Compare with Working Tree",
+                        CompareWithWorkingTree.boxed_clone(),
+                    )
+                })
+                .separator()
+                .action("Cherry-Pick", CherryPick.boxed_clone())
+                .when(has_parent, |menu| {
+                    menu.action("Revert", Revert.boxed_clone())
+                })
+                .action("Rebase Onto This Commit", RebaseOntoCommit.boxed_clone())
+        });
+        let subscription = cx.subscribe_in(
+            &context_menu,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(window, cx)
+                }) {
+                    cx.focus_self(window);
+                }
+                this.context_menu.take();
+                cx.notify();
+            },
+        );
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
+    fn open_commit(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(commit) = self.commits.get(ix) else {
+            return;
+        };
+        let summary = CommitSummary {
+            sha: commit.sha.clone(),
+            subject: commit
+                .message
+                .split('\n')
+                .next()
+                .unwrap_or_default()
+                .to_string()
+                .into(),
+            commit_timestamp: commit.commit_timestamp,
+            has_parent: commit.parent_count > 0,
+        };
+        CommitView::open(
+            summary,
+            self.repository.downgrade(),
+            self.workspace.clone(),
+            window,
+            cx,
+        );
+    }
+
+    /// Opens a diff of the file's contents as of the given commit against the working tree.
+    fn compare_with_working_tree(
+        &mut self,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(path) = self.path.clone() else {
+            return;
+        };
+        let Some(commit) = self.commits.get(ix) else {
+            return;
+        };
+        let Some(project_path) = self
+            .repository
+            .read(cx)
+            .repo_path_to_project_path(&path, cx)
+        else {
+            return;
+        };
+        let sha = commit.sha.clone();
+        let commit_diff = self.repository.update(cx, |repository, _| {
+            repository.load_commit_diff(sha.to_string())
+        });
+        let workspace = self.workspace.clone();
+
+        window
+            .spawn(cx, async move |cx| {
+                let commit_diff = commit_diff.await??;
+                let file = commit_diff
+                    .files
+                    .into_iter()
+                    .find(|file| file.path == path)
+                    .with_context(|| format!("{path} was not changed in commit {sha}"))?;
+
+                let project =
+                    workspace.read_with(cx, |workspace, _| workspace.project().clone())?;
+                let language_registry =
+                    project.read_with(cx, |project, _| project.languages().clone())?;
+                let new_buffer = project
+                    .update(cx, |project, cx| {
+                        project.open_buffer(project_path.clone(), cx)
+                    })?
+                    .await?;
+                let blob = Arc::new(GitBlob {
+                    path,
+                    worktree_id: project_path.worktree_id,
+                    is_deleted: file.new_text.is_none(),
+                }) as Arc<dyn language::File>;
+                let old_buffer = build_buffer(
+                    file.new_text.unwrap_or_default(),
+                    blob,
+                    &language_registry,
+                    cx,
+                )
+                .await?;
+
+                workspace
+                    .update_in(cx, |workspace, window, cx| {
+                        DiffView::open_buffers(old_buffer, new_buffer, workspace, window, cx)
+                    })?
+                    .await?;
+                anyhow::Ok(())
+            })
+            .detach_and_prompt_err(
+                "Failed to compare with working tree",
+                window,
+                cx,
+                |e, _, _| Some(e.to_string()),
+            );
+    }
+
+    fn render_commit(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let commit = &self.commits[ix];
+        let short_sha = commit
+            .sha
+            .get(0..git::SHORT_SHA_LENGTH)
+            .unwrap_or(&commit.sha)
+            .to_string();
+        let subject = commit
+            .message
+            .split('\n')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let commit_time = OffsetDateTime::from_unix_timestamp(commit.commit_timestamp)
+            .unwrap_or_else(|_| OffsetDateTime::now_utc());
+        let formatted_time = format_local_timestamp(
+            commit_time,
+            OffsetDateTime::now_utc(),
+            time_format::TimestampFormat::Relative,
+        );
+
+        ListItem::new(("commit", ix))
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(self.selected_index == Some(ix))
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .overflow_hidden()
+                    .child(
+                        Label::new(short_sha)
+                            .buffer_font(cx)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .overflow_hidden()
+                            .child(Label::new(subject).truncate()),
+                    )
+                    .child(
+                        Label::new(commit.author_name.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        Label::new(formatted_time)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .when(self.path.is_some(), |item| {
+                item.end_slot(
+                    IconButton::new(("compare-with-working-tree", ix), IconName::Diff)
+                        .icon_size(IconSize::Small)
+                        .icon_color(Color::Muted)
+                        .tooltip(Tooltip::for_action_title(
+                            "Compare with Working Tree",
+                            &CompareWithWorkingTree,
+                        ))
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            cx.stop_propagation();
+                            this.compare_with_working_tree(ix, window, cx);
+                        })),
+                )
+            })
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.selected_index = Some(ix);
+                this.open_commit(ix, window, cx);
+                cx.notify();
+            }))
+            .on_secondary_mouse_down(cx.listener(
+                move |this, event: &MouseDownEvent, window, cx| {
+                    this.deploy_context_menu(event.position, ix, window, cx);
+                    cx.stop_propagation();
+                },
+            ))
+            .into_any_element()
+    }
+
+    fn render_empty_state(&self) -> impl IntoElement {
+        let message: SharedString = if let Some(error) = &self.error {
+            error.clone()
+        } else if self.load_task.is_some() {
+            "Loading commits…".into()
+        } else {
+            "No commits".into()
+        };
+        h_flex()
+            .size_full()
+            .justify_center()
+            .child(Label::new(message).color(Color::Muted))
+    }
+}
+
+impl EventEmitter<ItemEvent> for CommitHistoryView {}
+
+impl Focusable for CommitHistoryView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for CommitHistoryView {
+    type Event = ItemEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, cx: &App) -> SharedString {
+        match &self.path {
+            Some(path) => {
+                let file_name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.to_string());
+                format!("History: {file_name}").into()
+            }
+            None => {
+                let repository = self.repository.read(cx);
+                let name = repository
+                    .work_directory_abs_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                format!("History: {name}").into()
+            }
+        }
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        match &self.path {
+            Some(path) => Some(format!("Commit History of {path}").into()),
+            None => Some(
+                format!(
+                    "Commit History of {}",
+                    self.repository.read(cx).work_directory_abs_path.display()
+                )
+                .into(),
+            ),
+        }
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Commit History Opened")
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
+
+impl Render for CommitHistoryView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let commit_count = self.commits.len();
+
+        v_flex()
+            .key_context("CommitHistory")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::secondary_confirm))
+            .on_action(cx.listener(Self::compare_selected_with_working_tree))
+            .on_action(cx.listener(Self::toggle_first_parent))
+            .on_action(cx.listener(Self::cherry_pick))
+            .on_action(cx.listener(Self::revert))
+            .on_action(cx.listener(Self::rebase_onto_commit))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        div()
+                            .flex_1()
+                            .px_2()
+                            .py_1()
+                            .rounded_sm()
+                            .border_1()
+                            .border_color(cx.theme().colors().border)
+                            .child(self.message_filter.clone()),
+                    )
+                    .child(
+                        div()
+                            .w(rems(16.))
+                            .px_2()
+                            .py_1()
+                            .rounded_sm()
+                            .border_1()
+                            .border_color(cx.theme().colors().border)
+                            .child(self.author_filter.clone()),
+                    )
+                    .child(
+                        Checkbox::new("first-parent", self.first_parent.into())
+                            .label("First Parent Only")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.toggle_first_parent(&ToggleFirstParent, window, cx);
+                            })),
+                    ),
+            )
+            .child(if commit_count == 0 {
+                self.render_empty_state().into_any_element()
+            } else {
+                uniform_list(
+                    "commits",
+                    commit_count,
+                    cx.processor(move |this, range: Range<usize>, _window, cx| {
+                        if range.end + LOAD_MORE_THRESHOLD >= this.commits.len() {
+                            this.load_more(cx);
+                        }
+                        range.map(|ix| this.render_commit(ix, cx)).collect()
+                    }),
+                )
+                .size_full()
+                .flex_grow()
+                .with_sizing_behavior(ListSizingBehavior::Auto)
+                .track_scroll(self.scroll_handle.clone())
+                .into_any_element()
+            })
+            .children(self.context_menu.as_ref().map(|(menu, position, _)| {
+                deferred(
+                    anchored()
+                        .position(*position)
+                        .anchor(Corner::TopLeft)
+                        .child(menu.clone()),
+                )
+                .with_priority(1)
+            }))
+    }
+}
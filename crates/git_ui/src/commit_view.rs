@@ -33,10 +33,10 @@ pub struct CommitView {
     multibuffer: Entity<MultiBuffer>,
 }
 
-struct GitBlob {
-    path: RepoPath,
-    worktree_id: WorktreeId,
-    is_deleted: bool,
+pub(crate) struct GitBlob {
+    pub(crate) path: RepoPath,
+    pub(crate) worktree_id: WorktreeId,
+    pub(crate) is_deleted: bool,
 }
 
 struct CommitMetadataFile {
@@ -287,7 +287,7 @@ impl language::File for CommitMetadataFile {
     }
 }
 
-async fn build_buffer(
+pub(crate) async fn build_buffer(
     mut text: String,
     blob: Arc<dyn File>,
     language_registry: &Arc<language::LanguageRegistry>,
@@ -4,6 +4,7 @@ use editor::{
     Editor, EditorEvent, ExcerptId, MultiBuffer, RowHighlightOptions,
     display_map::{BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
 };
+use git::repository::InProgressOperation;
 use gpui::{
     App, Context, Entity, InteractiveElement as _, ParentElement as _, Subscription, Task,
     WeakEntity,
@@ -260,6 +261,16 @@ fn conflicts_updated(
     }
 
     // Add new highlights and blocks
+    let (ours_label, theirs_label) = editor
+        .project
+        .as_ref()
+        .and_then(|project| {
+            let git_store = project.read(cx).git_store().read(cx);
+            let (repository, _) = git_store.repository_and_path_for_buffer_id(buffer_id, cx)?;
+            repository.read(cx).merge.operation
+        })
+        .unwrap_or(InProgressOperation::Merge)
+        .conflict_side_labels();
     let editor_handle = cx.weak_entity();
     let new_conflicts = &conflict_set.conflicts[event.new_range.clone()];
     let mut blocks = Vec::new();
@@ -294,7 +305,15 @@ fn conflicts_updated(
             style: BlockStyle::Fixed,
             render: Arc::new({
                 let conflict = conflict.clone();
-                move |cx| render_conflict_buttons(&conflict, excerpt_id, editor_handle.clone(), cx)
+                move |cx| {
+                    render_conflict_buttons(
+                        &conflict,
+                        excerpt_id,
+                        (ours_label, theirs_label),
+                        editor_handle.clone(),
+                        cx,
+                    )
+                }
             }),
             priority: 0,
             render_in_minimap: true,
@@ -384,6 +403,7 @@ fn update_conflict_highlighting(
 fn render_conflict_buttons(
     conflict: &ConflictRegion,
     excerpt_id: ExcerptId,
+    (ours_label, theirs_label): (&'static str, &'static str),
     editor: WeakEntity<Editor>,
     cx: &mut BlockContext,
 ) -> AnyElement {
@@ -395,7 +415,7 @@ fn render_conflict_buttons(
         .gap_1()
         .bg(cx.theme().colors().editor_background)
         .child(
-            Button::new("head", "Use HEAD")
+            Button::new("head", format!("Use {ours_label}"))
                 .label_size(LabelSize::Small)
                 .on_click({
                     let editor = editor.clone();
@@ -415,7 +435,7 @@ fn render_conflict_buttons(
                 }),
         )
         .child(
-            Button::new("origin", "Use Origin")
+            Button::new("origin", format!("Use {theirs_label}"))
                 .label_size(LabelSize::Small)
                 .on_click({
                     let editor = editor.clone();
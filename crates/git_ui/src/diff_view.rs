@@ -53,6 +53,25 @@ impl DiffView {
                 .update(cx, |project, cx| project.open_local_buffer(&new_path, cx))?
                 .await?;
 
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    Self::open_buffers(old_buffer, new_buffer, workspace, window, cx)
+                })?
+                .await
+        })
+    }
+
+    /// Opens a diff between two already loaded buffers.
+    pub fn open_buffers(
+        old_buffer: Entity<Buffer>,
+        new_buffer: Entity<Buffer>,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let workspace = workspace.weak_handle();
+        window.spawn(cx, async move |cx| {
+            let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
             let buffer_diff = build_buffer_diff(&old_buffer, &new_buffer, cx).await?;
 
             workspace.update_in(cx, |workspace, window, cx| {
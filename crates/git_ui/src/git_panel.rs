@@ -21,12 +21,16 @@ use futures::StreamExt as _;
 use git::blame::ParsedCommitMessage;
 use git::repository::{
     Branch, CommitDetails, CommitOptions, CommitSummary, DiffType, FetchOptions, GitCommitter,
-    PushOptions, Remote, RemoteCommandOutput, ResetMode, Upstream, UpstreamTracking,
+    PushOptions, Remote, RemoteCommandOutput, ResetMode, StashOptions, Upstream, UpstreamTracking,
     UpstreamTrackingStatus, get_git_committer,
 };
 use git::status::StageStatus;
+use git::{
+    AbortOperation, ContinueOperation, ExpandCommitEditor, Rebase, RestoreTrackedFiles, StageAll,
+    StashAll, StashPop, TrashUntrackedFiles, UnstageAll, ViewHistory, ViewStash, ViewTags,
+    ViewWorktrees,
+};
 use git::{Amend, ToggleStaged, repository::RepoPath, status::FileStatus};
-use git::{ExpandCommitEditor, RestoreTrackedFiles, StageAll, TrashUntrackedFiles, UnstageAll};
 use gpui::{
     Action, Animation, AnimationExt as _, AsyncApp, AsyncWindowContext, Axis, ClickEvent, Corner,
     DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, KeyContext,
@@ -138,7 +142,18 @@ fn git_panel_context_menu(
                 UnstageAll.boxed_clone(),
             )
             .separator()
+            .action_disabled_when(
+                !state.has_tracked_changes && !state.has_new_changes,
+                "Stash All",
+                StashAll.boxed_clone(),
+            )
+            .action("Pop Stash", StashPop.boxed_clone())
+            .action("View Stash…", ViewStash.boxed_clone())
+            .separator()
             .action("Open Diff", project_diff::Diff.boxed_clone())
+            .action("View History", ViewHistory.boxed_clone())
+            .action("View Tags…", ViewTags.boxed_clone())
+            .action("View Worktrees…", ViewWorktrees.boxed_clone())
             .separator()
             .action_disabled_when(
                 !state.has_tracked_changes,
@@ -1250,6 +1265,201 @@ impl GitPanel {
         self.change_file_stage(false, entries, cx);
     }
 
+    pub fn stash_all(&mut self, _: &StashAll, _window: &mut Window, cx: &mut Context<Self>) {
+        self.stash_paths(Vec::new(), true, cx);
+    }
+
+    fn stash_selected(&mut self, _: &git::StashFile, _window: &mut Window, cx: &mut Context<Self>) {
+        let Some(status_entry) = self
+            .get_selected_entry()
+            .and_then(|entry| entry.status_entry())
+        else {
+            return;
+        };
+        let include_untracked = status_entry.status.is_created();
+        self.stash_paths(vec![status_entry.repo_path.clone()], include_untracked, cx);
+    }
+
+    fn stash_paths(
+        &mut self,
+        paths: Vec<RepoPath>,
+        include_untracked: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+        telemetry::event!("Git Stashed");
+
+        cx.spawn(async move |this, cx| {
+            let stash = active_repository.update(cx, |repo, cx| {
+                repo.stash_push(
+                    paths,
+                    StashOptions {
+                        message: None,
+                        include_untracked,
+                    },
+                    cx,
+                )
+            })?;
+            let result = maybe!(async { stash.await? }).await;
+
+            this.update(cx, |this, cx| {
+                result
+                    .map_err(|e| this.show_error_toast("stash", e, cx))
+                    .ok();
+            })
+        })
+        .detach();
+    }
+
+    pub fn stash_pop(&mut self, _: &StashPop, _window: &mut Window, cx: &mut Context<Self>) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+        telemetry::event!("Git Stash Popped");
+
+        cx.spawn(async move |this, cx| {
+            let pop = active_repository.update(cx, |repo, cx| repo.stash_pop(0, cx))?;
+            let result = maybe!(async { pop.await? }).await;
+
+            this.update(cx, |this, cx| {
+                result
+                    .map_err(|e| this.show_error_toast("stash pop", e, cx))
+                    .ok();
+            })
+        })
+        .detach();
+    }
+
+    pub fn continue_operation(
+        &mut self,
+        _: &ContinueOperation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+        let Some(operation) = active_repository.read(cx).merge.operation else {
+            return;
+        };
+        if self.has_unstaged_conflicts() {
+            let prompt = window.prompt(
+                PromptLevel::Warning,
+                "There are still conflicts. You must stage these before continuing",
+                None,
+                &["Ok"],
+                cx,
+            );
+            cx.background_spawn(prompt).detach();
+            return;
+        }
+        telemetry::event!("Git Operation Continued", operation = operation.command());
+
+        cx.spawn(async move |this, cx| {
+            let task =
+                active_repository.update(cx, |repo, cx| repo.continue_operation(operation, cx))?;
+            let result = maybe!(async { task.await? }).await;
+
+            this.update(cx, |this, cx| {
+                result
+                    .map_err(|e| {
+                        this.show_error_toast(format!("{} --continue", operation.command()), e, cx)
+                    })
+                    .ok();
+            })
+        })
+        .detach();
+    }
+
+    pub fn abort_operation(
+        &mut self,
+        _: &AbortOperation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+        let Some(operation) = active_repository.read(cx).merge.operation else {
+            return;
+        };
+        let prompt = window.prompt(
+            PromptLevel::Warning,
+            &format!(
+                "Are you sure you want to abort the {}?",
+                operation.label().to_lowercase()
+            ),
+            Some("All changes made since it started will be lost."),
+            &["Abort", "Cancel"],
+            cx,
+        );
+
+        cx.spawn(async move |this, cx| {
+            if prompt.await? != 0 {
+                return Ok(());
+            }
+            telemetry::event!("Git Operation Aborted", operation = operation.command());
+
+            let task =
+                active_repository.update(cx, |repo, cx| repo.abort_operation(operation, cx))?;
+            let result = maybe!(async { task.await? }).await;
+
+            this.update(cx, |this, cx| {
+                result
+                    .map_err(|e| {
+                        this.show_error_toast(format!("{} --abort", operation.command()), e, cx)
+                    })
+                    .ok();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub fn rebase(&mut self, _: &Rebase, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+        let workspace = self.workspace.clone();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let branches = active_repository.update(cx, |repo, _| repo.branches())?;
+            let branch_names = maybe!(async { branches.await? })
+                .await?
+                .into_iter()
+                .filter(|branch| !branch.is_head)
+                .map(|branch| branch.name().to_string())
+                .collect::<Vec<_>>();
+            let selection = cx
+                .update(|window, cx| {
+                    picker_prompt::prompt(
+                        "Pick a branch to rebase onto",
+                        branch_names.iter().map(SharedString::from).collect(),
+                        workspace,
+                        window,
+                        cx,
+                    )
+                })?
+                .await;
+            let Some(selection) = selection else {
+                return Ok(());
+            };
+            let onto = branch_names[selection].clone();
+            telemetry::event!("Git Rebased");
+
+            let task = active_repository.update(cx, |repo, cx| repo.rebase(onto, cx))?;
+            let result = maybe!(async { task.await? }).await;
+
+            this.update(cx, |this, cx| {
+                result
+                    .map_err(|e| this.show_error_toast("rebase", e, cx))
+                    .ok();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn toggle_staged_for_entry(
         &mut self,
         entry: &GitListEntry,
@@ -1582,36 +1792,17 @@ impl GitPanel {
             self.fill_co_authors(&mut message, cx);
         }
 
-        let task = if self.has_staged_changes() {
-            // Repository serializes all git operations, so we can just send a commit immediately
-            let commit_task = active_repository.update(cx, |repo, cx| {
-                repo.commit(message.into(), None, options, cx)
-            });
-            cx.background_spawn(async move { commit_task.await? })
-        } else {
-            let changed_files = self
-                .entries
-                .iter()
-                .filter_map(|entry| entry.status_entry())
-                .filter(|status_entry| !status_entry.status.is_created())
-                .map(|status_entry| status_entry.repo_path.clone())
-                .collect::<Vec<_>>();
-
-            if changed_files.is_empty() {
-                error_spawn("No changes to commit", window, cx);
-                return;
-            }
+        if !self.has_staged_changes() && !self.has_tracked_changes() {
+            error_spawn("No changes to commit", window, cx);
+            return;
+        }
 
-            let stage_task =
-                active_repository.update(cx, |repo, cx| repo.stage_entries(changed_files, cx));
-            cx.spawn(async move |_, cx| {
-                stage_task.await?;
-                let commit_task = active_repository.update(cx, |repo, cx| {
-                    repo.commit(message.into(), None, options, cx)
-                })?;
-                commit_task.await?
-            })
-        };
+        // Pending stage operations count as staged, so they aren't committed along with every
+        // other tracked change before the status catches up.
+        let stage_tracked = !self.has_staged_changes();
+        let task = active_repository.update(cx, |repo, cx| {
+            repo.commit_staged_or_tracked(message.into(), None, stage_tracked, options, cx)
+        });
         let task = cx.spawn_in(window, async move |this, cx| {
             let result = task.await;
             this.update_in(cx, |this, window, cx| {
@@ -2190,6 +2381,59 @@ impl GitPanel {
         .detach_and_log_err(cx);
     }
 
+    pub(crate) fn push_tag(
+        &mut self,
+        tag_name: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        telemetry::event!("Git Tag Pushed");
+        let remote = self.get_remote(true, window, cx);
+
+        cx.spawn_in(window, async move |this, cx| {
+            let remote = match remote.await {
+                Ok(Some(remote)) => remote,
+                Ok(None) => {
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::error!("Failed to get current remote: {}", e);
+                    this.update(cx, |this, cx| this.show_error_toast("push", e, cx))
+                        .ok();
+                    return Ok(());
+                }
+            };
+
+            let askpass_delegate = this.update_in(cx, |this, window, cx| {
+                this.askpass_delegate(format!("git push {}", remote.name), window, cx)
+            })?;
+
+            let push = repo.update(cx, |repo, cx| {
+                repo.push_tag(tag_name.clone(), remote.name.clone(), askpass_delegate, cx)
+            })?;
+
+            let remote_output = push.await?;
+
+            let action = RemoteAction::Push(tag_name, remote);
+            this.update(cx, |this, cx| match remote_output {
+                Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                Err(e) => {
+                    log::error!("Error while pushing tag {:?}", e);
+                    this.show_error_toast(action.name(), e, cx)
+                }
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn askpass_delegate(
         &self,
         operation: impl Into<SharedString>,
@@ -3444,6 +3688,74 @@ impl GitPanel {
             })
     }
 
+    fn render_in_progress_operation(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let active_repository = self.active_repository.as_ref()?;
+        let operation = active_repository.read(cx).merge.operation?;
+        let has_unstaged_conflicts = self.has_unstaged_conflicts();
+
+        Some(
+            h_flex()
+                .py_1p5()
+                .px_2()
+                .gap_1p5()
+                .justify_between()
+                .border_t_1()
+                .border_color(cx.theme().colors().border)
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Icon::new(IconName::Warning)
+                                .size(IconSize::Small)
+                                .color(Color::Warning),
+                        )
+                        .child(
+                            Label::new(format!("{} in progress", operation.label()))
+                                .size(LabelSize::Small),
+                        ),
+                )
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Button::new("abort-operation", "Abort")
+                                .label_size(LabelSize::Small)
+                                .tooltip(Tooltip::for_action_title(
+                                    format!("git {} --abort", operation.command()),
+                                    &AbortOperation,
+                                ))
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(AbortOperation.boxed_clone(), cx)
+                                }),
+                        )
+                        .child(
+                            Button::new("continue-operation", "Continue")
+                                .label_size(LabelSize::Small)
+                                .style(ButtonStyle::Filled)
+                                .disabled(has_unstaged_conflicts)
+                                .tooltip(move |window, cx| {
+                                    if has_unstaged_conflicts {
+                                        Tooltip::simple(
+                                            "Stage all conflicted files before continuing",
+                                            cx,
+                                        )
+                                    } else {
+                                        Tooltip::for_action(
+                                            format!("git {} --continue", operation.command()),
+                                            &ContinueOperation,
+                                            window,
+                                            cx,
+                                        )
+                                    }
+                                })
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(ContinueOperation.boxed_clone(), cx)
+                                }),
+                        ),
+                ),
+        )
+    }
+
     fn render_pending_amend(&self, cx: &mut Context<Self>) -> impl IntoElement {
         div()
             .p_2()
@@ -3954,6 +4266,7 @@ impl GitPanel {
                 .context(self.focus_handle.clone())
                 .action(stage_title, ToggleStaged.boxed_clone())
                 .action(restore_title, git::RestoreFile::default().boxed_clone())
+                .action("Stash File", git::StashFile.boxed_clone())
                 .separator()
                 .action("Open Diff", Confirm.boxed_clone())
                 .action("Open File", SecondaryConfirm.boxed_clone())
@@ -4323,6 +4636,12 @@ impl Render for GitPanel {
                     .on_action(cx.listener(GitPanel::cancel))
                     .on_action(cx.listener(Self::stage_all))
                     .on_action(cx.listener(Self::unstage_all))
+                    .on_action(cx.listener(Self::stash_all))
+                    .on_action(cx.listener(Self::stash_selected))
+                    .on_action(cx.listener(Self::stash_pop))
+                    .on_action(cx.listener(Self::continue_operation))
+                    .on_action(cx.listener(Self::abort_operation))
+                    .on_action(cx.listener(Self::rebase))
                     .on_action(cx.listener(Self::stage_selected))
                     .on_action(cx.listener(Self::unstage_selected))
                     .on_action(cx.listener(Self::restore_tracked_files))
@@ -4366,6 +4685,7 @@ impl Render for GitPanel {
                             this.child(self.render_empty_state(cx).into_any_element())
                         }
                     })
+                    .children(self.render_in_progress_operation(cx))
                     .children(self.render_footer(window, cx))
                     .when(self.amend_pending, |this| {
                         this.child(self.render_pending_amend(cx))
@@ -5322,4 +5642,93 @@ mod tests {
             ],
         );
     }
+
+    #[gpui::test]
+    async fn test_commit_right_after_staging(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/root",
+            json!({
+                "project": {
+                    ".git": {},
+                    "src": {
+                        "main.rs": "fn main() {}",
+                        "lib.rs": "pub fn hello() {}",
+                    },
+                }
+            }),
+        )
+        .await;
+
+        let dot_git = Path::new(path!("/root/project/.git"));
+        fs.set_status_for_repo(
+            dot_git,
+            &[
+                (Path::new("src/main.rs"), StatusCode::Modified.worktree()),
+                (Path::new("src/lib.rs"), StatusCode::Modified.worktree()),
+            ],
+        );
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/root/project"))], cx).await;
+        let workspace =
+            cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+        cx.read(|cx| {
+            project
+                .read(cx)
+                .worktrees(cx)
+                .nth(0)
+                .unwrap()
+                .read(cx)
+                .as_local()
+                .unwrap()
+                .scan_complete()
+        })
+        .await;
+
+        cx.executor().run_until_parked();
+
+        let panel = workspace.update(cx, GitPanel::new).unwrap();
+
+        let handle = cx.update_window_entity(&panel, |panel, _, _| {
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
+
+        let entries = panel.read_with(cx, |panel, _| panel.entries.clone());
+        let lib_entry = entries[1].clone();
+        assert_eq!(
+            lib_entry
+                .status_entry()
+                .map(|entry| entry.repo_path.clone()),
+            Some("src/lib.rs".into())
+        );
+
+        // Commit before the repository status reflects the staged file
+        panel.update_in(cx, |panel, window, cx| {
+            panel.toggle_staged_for_entry(&lib_entry, window, cx);
+            panel.commit_editor.update(cx, |editor, cx| {
+                editor.set_text("Say hello", window, cx);
+            });
+            panel.commit_changes(CommitOptions::default(), window, cx);
+        });
+        cx.executor().run_until_parked();
+
+        fs.with_git_state(dot_git, false, |state| {
+            assert_eq!(state.commits.len(), 1);
+            assert_eq!(state.commits[0].message.as_ref(), "Say hello");
+            assert_eq!(
+                state.head_contents.get(&RepoPath::from("src/lib.rs")),
+                Some(&"pub fn hello() {}".to_string())
+            );
+            assert_eq!(
+                state.head_contents.get(&RepoPath::from("src/main.rs")),
+                Some(&"fn main() {} (modified in working copy)".to_string())
+            );
+        })
+        .unwrap();
+    }
 }
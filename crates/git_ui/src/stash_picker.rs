@@ -0,0 +1,334 @@
+use anyhow::Context as _;
+use fuzzy::StringMatchCandidate;
+
+use git::repository::StashEntry;
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, IntoElement,
+    ParentElement, Render, SharedString, Styled, Subscription, Task, Window, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::sync::Arc;
+use time::OffsetDateTime;
+use time_format::format_local_timestamp;
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::notifications::DetachAndPromptErr;
+use workspace::{ModalView, Workspace};
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    _: &git::ViewStash,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let repository = workspace.project().read(cx).active_repository(cx).clone();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        StashList::new(repository, rems(34.), window, cx)
+    })
+}
+
+pub struct StashList {
+    width: Rems,
+    pub picker: Entity<Picker<StashListDelegate>>,
+    _subscription: Subscription,
+}
+
+impl StashList {
+    fn new(
+        repository: Option<Entity<Repository>>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let stash_request = repository
+            .clone()
+            .map(|repository| repository.update(cx, |repository, _| repository.stash_list()));
+
+        cx.spawn_in(window, async move |this, cx| {
+            let stash = stash_request.context("No active repository")?.await??;
+
+            this.update_in(cx, |this, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.delegate.all_entries = Some(stash.entries.to_vec());
+                    picker.refresh(window, cx);
+                })
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+
+        let delegate = StashListDelegate::new(repository);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        });
+
+        Self {
+            picker,
+            width,
+            _subscription,
+        }
+    }
+}
+
+impl ModalView for StashList {}
+impl EventEmitter<DismissEvent> for StashList {}
+
+impl Focusable for StashList {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for StashList {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(self.width).child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StashListEntry {
+    entry: StashEntry,
+    positions: Vec<usize>,
+}
+
+pub struct StashListDelegate {
+    matches: Vec<StashListEntry>,
+    all_entries: Option<Vec<StashEntry>>,
+    repo: Option<Entity<Repository>>,
+    selected_index: usize,
+}
+
+impl StashListDelegate {
+    fn new(repo: Option<Entity<Repository>>) -> Self {
+        Self {
+            matches: vec![],
+            all_entries: None,
+            repo,
+            selected_index: 0,
+        }
+    }
+
+    fn drop_entry(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.matches.get(ix).map(|entry| entry.entry.clone()) else {
+            return;
+        };
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+
+        cx.spawn_in(window, async move |picker, cx| {
+            repo.update(cx, |repo, cx| repo.stash_drop(entry.index, cx))?
+                .await??;
+            let stash = repo.update(cx, |repo, _| repo.stash_list())?.await??;
+
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.all_entries = Some(stash.entries.to_vec());
+                picker.refresh(window, cx);
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to drop stash entry", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+}
+
+impl PickerDelegate for StashListDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select stash entry…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let Some(all_entries) = self.all_entries.clone() else {
+            return Task::ready(());
+        };
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches: Vec<StashListEntry> = if query.is_empty() {
+                all_entries
+                    .into_iter()
+                    .map(|entry| StashListEntry {
+                        entry,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = all_entries
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, entry)| StringMatchCandidate::new(ix, &entry.message))
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| StashListEntry {
+                    entry: all_entries[candidate.candidate_id].clone(),
+                    positions: candidate.positions,
+                })
+                .collect()
+            };
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                })
+                .log_err();
+        })
+    }
+
+    /// Pops the selected entry, or applies it while keeping it in the stash if `secondary` is set.
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.matches.get(self.selected_index()) else {
+            return;
+        };
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let index = entry.entry.index;
+
+        cx.spawn_in(window, async move |picker, cx| {
+            repo.update(cx, |repo, cx| {
+                if secondary {
+                    repo.stash_apply(index, cx)
+                } else {
+                    repo.stash_pop(index, cx)
+                }
+            })?
+            .await??;
+
+            picker.update(cx, |_, cx| {
+                cx.emit(DismissEvent);
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to apply stash entry", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let entry = &self.matches[ix];
+
+        let stash_time = OffsetDateTime::from_unix_timestamp(entry.entry.timestamp)
+            .unwrap_or_else(|_| OffsetDateTime::now_utc());
+        let formatted_time = format_local_timestamp(
+            stash_time,
+            OffsetDateTime::now_utc(),
+            time_format::TimestampFormat::Relative,
+        );
+        let detail = match &entry.entry.branch {
+            Some(branch) => format!("stash@{{{}}} on {branch}", entry.entry.index),
+            None => format!("stash@{{{}}}", entry.entry.index),
+        };
+
+        Some(
+            ListItem::new(SharedString::from(format!("stash-entry-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    v_flex()
+                        .w_full()
+                        .overflow_hidden()
+                        .child(
+                            h_flex()
+                                .gap_6()
+                                .justify_between()
+                                .overflow_x_hidden()
+                                .child(
+                                    HighlightedLabel::new(
+                                        entry.entry.message.clone(),
+                                        entry.positions.clone(),
+                                    )
+                                    .truncate(),
+                                )
+                                .child(
+                                    Label::new(formatted_time)
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                ),
+                        )
+                        .child(
+                            Label::new(detail)
+                                .size(LabelSize::Small)
+                                .truncate()
+                                .color(Color::Muted),
+                        ),
+                )
+                .end_slot(
+                    IconButton::new(("drop-stash-entry", ix), IconName::Trash)
+                        .icon_size(IconSize::Small)
+                        .icon_color(Color::Muted)
+                        .tooltip(Tooltip::text("Drop Stash Entry"))
+                        .on_click(cx.listener(move |picker, _, window, cx| {
+                            cx.stop_propagation();
+                            window.prevent_default();
+
+                            picker.delegate.drop_entry(ix, window, cx);
+                        })),
+                ),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No stash entries".into())
+    }
+}
@@ -0,0 +1,482 @@
+use anyhow::Context as _;
+use fuzzy::StringMatchCandidate;
+
+use git::repository::Tag;
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, IntoElement,
+    ParentElement, Render, SharedString, Styled, Subscription, Task, WeakEntity, Window, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::sync::Arc;
+use time::OffsetDateTime;
+use time_format::format_local_timestamp;
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::notifications::DetachAndPromptErr;
+use workspace::{ModalView, Workspace};
+
+use crate::git_panel::GitPanel;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    _: &git::ViewTags,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let repository = workspace.project().read(cx).active_repository(cx).clone();
+    let workspace_handle = cx.weak_entity();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        TagList::new(repository, workspace_handle, rems(34.), window, cx)
+    })
+}
+
+pub struct TagList {
+    width: Rems,
+    pub picker: Entity<Picker<TagListDelegate>>,
+    _subscription: Subscription,
+}
+
+impl TagList {
+    fn new(
+        repository: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let tags_request = repository
+            .clone()
+            .map(|repository| repository.update(cx, |repository, _| repository.tags()));
+
+        cx.spawn_in(window, async move |this, cx| {
+            let tags = tags_request.context("No active repository")?.await??;
+
+            this.update_in(cx, |this, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.delegate.all_tags = Some(tags);
+                    picker.refresh(window, cx);
+                })
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+
+        let delegate = TagListDelegate::new(repository, workspace);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        });
+
+        Self {
+            picker,
+            width,
+            _subscription,
+        }
+    }
+}
+
+impl ModalView for TagList {}
+impl EventEmitter<DismissEvent> for TagList {}
+
+impl Focusable for TagList {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for TagList {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(self.width).child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TagEntry {
+    tag: Tag,
+    positions: Vec<usize>,
+    is_new: bool,
+}
+
+pub struct TagListDelegate {
+    matches: Vec<TagEntry>,
+    all_tags: Option<Vec<Tag>>,
+    repo: Option<Entity<Repository>>,
+    workspace: WeakEntity<Workspace>,
+    /// The name of the tag being created, once it has been entered and the picker is asking for
+    /// the tag's message.
+    new_tag_name: Option<String>,
+    selected_index: usize,
+}
+
+impl TagListDelegate {
+    fn new(repo: Option<Entity<Repository>>, workspace: WeakEntity<Workspace>) -> Self {
+        Self {
+            matches: vec![],
+            all_tags: None,
+            repo,
+            workspace,
+            new_tag_name: None,
+            selected_index: 0,
+        }
+    }
+
+    fn new_tag_entry(name: &str, message: &str) -> TagEntry {
+        TagEntry {
+            tag: Tag {
+                name: name.replace(' ', "-").into(),
+                sha: SharedString::default(),
+                message: (!message.is_empty()).then(|| message.to_string().into()),
+                timestamp: 0,
+            },
+            positions: Vec::new(),
+            is_new: true,
+        }
+    }
+
+    fn create_tag(&self, tag: Tag, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        cx.spawn(async move |_, cx| {
+            repo.update(cx, |repo, _| {
+                repo.create_tag(
+                    tag.name.to_string(),
+                    tag.message.map(|message| message.to_string()),
+                )
+            })?
+            .await??;
+
+            Ok(())
+        })
+        .detach_and_prompt_err("Failed to create tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn delete_tag(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(tag) = self.matches.get(ix).map(|entry| entry.tag.clone()) else {
+            return;
+        };
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+
+        cx.spawn_in(window, async move |picker, cx| {
+            repo.update(cx, |repo, _| repo.delete_tag(tag.name.to_string()))?
+                .await??;
+            let tags = repo.update(cx, |repo, _| repo.tags())?.await??;
+
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.all_tags = Some(tags);
+                picker.refresh(window, cx);
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to delete tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    fn push_tag(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(tag) = self.matches.get(ix).map(|entry| entry.tag.clone()) else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let Some(panel) = workspace.panel::<GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| panel.push_tag(tag.name, window, cx));
+            })
+            .ok();
+        cx.emit(DismissEvent);
+    }
+}
+
+impl PickerDelegate for TagListDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        if self.new_tag_name.is_some() {
+            "Tag message, or leave empty for a lightweight tag…".into()
+        } else {
+            "Select tag…".into()
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        if let Some(new_tag_name) = &self.new_tag_name {
+            self.matches = vec![Self::new_tag_entry(new_tag_name, query.trim())];
+            self.selected_index = 0;
+            return Task::ready(());
+        }
+        let Some(all_tags) = self.all_tags.clone() else {
+            return Task::ready(());
+        };
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut matches: Vec<TagEntry> = if query.is_empty() {
+                all_tags
+                    .into_iter()
+                    .map(|tag| TagEntry {
+                        tag,
+                        positions: Vec::new(),
+                        is_new: false,
+                    })
+                    .collect()
+            } else {
+                let candidates = all_tags
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, tag)| StringMatchCandidate::new(ix, &tag.name))
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| TagEntry {
+                    tag: all_tags[candidate.candidate_id].clone(),
+                    positions: candidate.positions,
+                    is_new: false,
+                })
+                .collect()
+            };
+            picker
+                .update(cx, |picker, _| {
+                    if !query.is_empty()
+                        && !matches.first().is_some_and(|entry| entry.tag.name == query)
+                    {
+                        matches.push(Self::new_tag_entry(&query, ""));
+                    }
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                })
+                .log_err();
+        })
+    }
+
+    /// Choosing to create a tag first asks for its message, by reusing the query editor.
+    fn confirm_update_query(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<String> {
+        if self.new_tag_name.is_some() {
+            return None;
+        }
+        let entry = self.matches.get(self.selected_index)?;
+        if !entry.is_new {
+            return None;
+        }
+        self.new_tag_name = Some(entry.tag.name.to_string());
+        cx.defer_in(window, |picker, window, cx| {
+            picker.refresh_placeholder(window, cx)
+        });
+        Some(String::new())
+    }
+
+    /// Checks out the selected tag, or pushes it to a remote if `secondary` is set.
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.matches.get(self.selected_index()) else {
+            return;
+        };
+        if entry.is_new {
+            self.create_tag(entry.tag.clone(), window, cx);
+            return;
+        }
+        if secondary {
+            self.push_tag(self.selected_index(), window, cx);
+            return;
+        }
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let tag_name = entry.tag.name.to_string();
+
+        cx.spawn_in(window, async move |picker, cx| {
+            repo.update(cx, |repo, _| repo.checkout_tag(tag_name))?
+                .await??;
+
+            picker.update(cx, |_, cx| {
+                cx.emit(DismissEvent);
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to check out tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let entry = &self.matches[ix];
+
+        if entry.is_new {
+            let label = if let Some(message) = &entry.tag.message {
+                format!(
+                    "Create tag \"{}\" with message \"{message}\"",
+                    entry.tag.name
+                )
+            } else if self.new_tag_name.is_some() {
+                format!("Create lightweight tag \"{}\"", entry.tag.name)
+            } else {
+                format!("Create tag \"{}\"…", entry.tag.name)
+            };
+            return Some(
+                ListItem::new(SharedString::from(format!("tag-{ix}")))
+                    .inset(true)
+                    .spacing(ListItemSpacing::Sparse)
+                    .toggle_state(selected)
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                Icon::new(IconName::Plus)
+                                    .size(IconSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .child(Label::new(label).single_line().truncate()),
+                    ),
+            );
+        }
+
+        let tag_time = OffsetDateTime::from_unix_timestamp(entry.tag.timestamp)
+            .unwrap_or_else(|_| OffsetDateTime::now_utc());
+        let formatted_time = format_local_timestamp(
+            tag_time,
+            OffsetDateTime::now_utc(),
+            time_format::TimestampFormat::Relative,
+        );
+        let short_sha = entry
+            .tag
+            .sha
+            .get(0..git::SHORT_SHA_LENGTH)
+            .unwrap_or(&entry.tag.sha);
+        let detail = match &entry.tag.message {
+            Some(message) => format!("{short_sha} · {message}"),
+            None => short_sha.to_string(),
+        };
+
+        Some(
+            ListItem::new(SharedString::from(format!("tag-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    v_flex()
+                        .w_full()
+                        .overflow_hidden()
+                        .child(
+                            h_flex()
+                                .gap_6()
+                                .justify_between()
+                                .overflow_x_hidden()
+                                .child(
+                                    HighlightedLabel::new(
+                                        entry.tag.name.clone(),
+                                        entry.positions.clone(),
+                                    )
+                                    .truncate(),
+                                )
+                                .child(
+                                    Label::new(formatted_time)
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                ),
+                        )
+                        .child(
+                            Label::new(detail)
+                                .size(LabelSize::Small)
+                                .truncate()
+                                .color(Color::Muted),
+                        ),
+                )
+                .end_slot(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            IconButton::new(("push-tag", ix), IconName::ArrowUpFromLine)
+                                .icon_size(IconSize::Small)
+                                .icon_color(Color::Muted)
+                                .tooltip(Tooltip::text("Push Tag"))
+                                .on_click(cx.listener(move |picker, _, window, cx| {
+                                    cx.stop_propagation();
+                                    window.prevent_default();
+
+                                    picker.delegate.push_tag(ix, window, cx);
+                                })),
+                        )
+                        .child(
+                            IconButton::new(("delete-tag", ix), IconName::Trash)
+                                .icon_size(IconSize::Small)
+                                .icon_color(Color::Muted)
+                                .tooltip(Tooltip::text("Delete Tag"))
+                                .on_click(cx.listener(move |picker, _, window, cx| {
+                                    cx.stop_propagation();
+                                    window.prevent_default();
+
+                                    picker.delegate.delete_tag(ix, window, cx);
+                                })),
+                        ),
+                ),
+        )
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No tags".into())
+    }
+}
@@ -0,0 +1,540 @@
+use anyhow::Context as _;
+use fuzzy::StringMatchCandidate;
+
+use git::repository::{Branch, GitWorktree};
+use gpui::{
+    App, AsyncWindowContext, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    IntoElement, ParentElement, Render, SharedString, Styled, Subscription, Task, WeakEntity,
+    Window, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::notifications::DetachAndPromptErr;
+use workspace::{ModalView, OpenOptions, Workspace};
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    _: &git::ViewWorktrees,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let repository = workspace.project().read(cx).active_repository(cx).clone();
+    let workspace_handle = cx.weak_entity();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        WorktreeList::new(repository, workspace_handle, rems(34.), window, cx)
+    })
+}
+
+/// Opens the worktree at `path` in a new window, connecting to the same SSH host as the current
+/// project if it is remote.
+fn open_worktree(
+    workspace: WeakEntity<Workspace>,
+    path: PathBuf,
+    cx: &mut AsyncWindowContext,
+) -> Task<anyhow::Result<()>> {
+    let result = workspace.update_in(cx, |workspace, window, cx| {
+        let project = workspace.project().read(cx);
+        if project.is_local() {
+            return Ok(workspace.open_workspace_for_paths(false, vec![path], window, cx));
+        }
+        let connection_options = project
+            .remote_connection_options(cx)
+            .context("Worktrees can only be opened from local and remote projects")?;
+        let app_state = workspace.app_state().clone();
+        Ok(cx.spawn_in(window, async move |_, cx| {
+            recent_projects::open_remote_project(
+                connection_options,
+                vec![path],
+                app_state,
+                OpenOptions::default(),
+                cx,
+            )
+            .await
+        }))
+    });
+    match result {
+        Ok(Ok(task)) => task,
+        Ok(Err(error)) | Err(error) => Task::ready(Err(error)),
+    }
+}
+
+/// Where a new worktree for `branch_name` is created: next to the main worktree, named after the
+/// repository and the branch.
+fn default_worktree_path(main_worktree_path: &Path, branch_name: &str) -> PathBuf {
+    let repository_name = main_worktree_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let worktree_name = format!("{repository_name}-{}", branch_name.replace('/', "-"));
+    match main_worktree_path.parent() {
+        Some(parent) => parent.join(worktree_name),
+        None => PathBuf::from(worktree_name),
+    }
+}
+
+pub struct WorktreeList {
+    width: Rems,
+    pub picker: Entity<Picker<WorktreeListDelegate>>,
+    _subscription: Subscription,
+}
+
+impl WorktreeList {
+    fn new(
+        repository: Option<Entity<Repository>>,
+        workspace: WeakEntity<Workspace>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let requests = repository.clone().map(|repository| {
+            repository.update(cx, |repository, _| {
+                (repository.worktree_list(), repository.branches())
+            })
+        });
+
+        cx.spawn_in(window, async move |this, cx| {
+            let (worktrees_request, branches_request) = requests.context("No active repository")?;
+            let worktrees = worktrees_request.await??;
+            let mut branches = branches_request.await??;
+            // Branches that are checked out somewhere can't get another worktree.
+            branches.retain(|branch| {
+                !worktrees
+                    .iter()
+                    .any(|worktree| worktree.ref_name.as_ref() == Some(&branch.ref_name))
+            });
+            branches.sort_by_key(|branch| {
+                branch
+                    .most_recent_commit
+                    .as_ref()
+                    .map(|commit| 0 - commit.commit_timestamp)
+            });
+
+            this.update_in(cx, |this, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.delegate.all_worktrees = Some(worktrees);
+                    picker.delegate.all_branches = branches;
+                    picker.refresh(window, cx);
+                })
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+
+        let delegate = WorktreeListDelegate::new(repository, workspace);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        });
+
+        Self {
+            picker,
+            width,
+            _subscription,
+        }
+    }
+}
+
+impl ModalView for WorktreeList {}
+impl EventEmitter<DismissEvent> for WorktreeList {}
+
+impl Focusable for WorktreeList {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for WorktreeList {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(self.width).child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum WorktreeEntry {
+    Worktree {
+        worktree: GitWorktree,
+        positions: Vec<usize>,
+    },
+    /// Creates a worktree for an existing branch.
+    Branch {
+        branch: Branch,
+        positions: Vec<usize>,
+    },
+    /// Creates a worktree with a new branch, based on `HEAD`.
+    NewBranch { name: String },
+}
+
+pub struct WorktreeListDelegate {
+    matches: Vec<WorktreeEntry>,
+    all_worktrees: Option<Vec<GitWorktree>>,
+    all_branches: Vec<Branch>,
+    repo: Option<Entity<Repository>>,
+    workspace: WeakEntity<Workspace>,
+    selected_index: usize,
+}
+
+impl WorktreeListDelegate {
+    fn new(repo: Option<Entity<Repository>>, workspace: WeakEntity<Workspace>) -> Self {
+        Self {
+            matches: vec![],
+            all_worktrees: None,
+            all_branches: Vec::new(),
+            repo,
+            workspace,
+            selected_index: 0,
+        }
+    }
+
+    fn main_worktree_path(&self) -> Option<PathBuf> {
+        self.all_worktrees
+            .as_ref()?
+            .iter()
+            .find(|worktree| worktree.is_main)
+            .map(|worktree| worktree.path.clone())
+    }
+
+    fn create_worktree(
+        &self,
+        branch_name: String,
+        create_branch: bool,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let Some(main_worktree_path) = self.main_worktree_path() else {
+            return;
+        };
+        let path = default_worktree_path(&main_worktree_path, &branch_name);
+        let workspace = self.workspace.clone();
+
+        cx.spawn_in(window, async move |_, cx| {
+            repo.update(cx, |repo, _| {
+                repo.worktree_add(path.clone(), branch_name, create_branch)
+            })?
+            .await??;
+            open_worktree(workspace, path, cx).await
+        })
+        .detach_and_prompt_err("Failed to create worktree", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn remove_worktree(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(WorktreeEntry::Worktree { worktree, .. }) = self.matches.get(ix).cloned() else {
+            return;
+        };
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+
+        cx.spawn_in(window, async move |picker, cx| {
+            repo.update(cx, |repo, _| repo.worktree_remove(worktree.path, false))?
+                .await??;
+            let worktrees = repo.update(cx, |repo, _| repo.worktree_list())?.await??;
+
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.all_worktrees = Some(worktrees);
+                picker.refresh(window, cx);
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to remove worktree", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+    }
+}
+
+impl PickerDelegate for WorktreeListDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select worktree, or type a branch to create one…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let Some(all_worktrees) = self.all_worktrees.clone() else {
+            return Task::ready(());
+        };
+        let all_branches = self.all_branches.clone();
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut matches: Vec<WorktreeEntry> = if query.is_empty() {
+                all_worktrees
+                    .into_iter()
+                    .map(|worktree| WorktreeEntry::Worktree {
+                        worktree,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let worktree_candidates = all_worktrees
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, worktree)| {
+                        StringMatchCandidate::new(ix, &worktree.path.to_string_lossy())
+                    })
+                    .collect::<Vec<StringMatchCandidate>>();
+                let branch_candidates = all_branches
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, branch)| StringMatchCandidate::new(ix, branch.name()))
+                    .collect::<Vec<StringMatchCandidate>>();
+                let executor = cx.background_executor().clone();
+                let worktree_matches = fuzzy::match_strings(
+                    &worktree_candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    executor.clone(),
+                )
+                .await;
+                let branch_matches = fuzzy::match_strings(
+                    &branch_candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    executor,
+                )
+                .await;
+
+                worktree_matches
+                    .into_iter()
+                    .map(|candidate| WorktreeEntry::Worktree {
+                        worktree: all_worktrees[candidate.candidate_id].clone(),
+                        positions: candidate.positions,
+                    })
+                    .chain(
+                        branch_matches
+                            .into_iter()
+                            .map(|candidate| WorktreeEntry::Branch {
+                                branch: all_branches[candidate.candidate_id].clone(),
+                                positions: candidate.positions,
+                            }),
+                    )
+                    .collect()
+            };
+            picker
+                .update(cx, |picker, _| {
+                    let query = query.trim().replace(' ', "-");
+                    if !query.is_empty()
+                        && !matches.iter().any(|entry| match entry {
+                            WorktreeEntry::Worktree { worktree, .. } => {
+                                worktree.branch_name() == Some(query.as_str())
+                            }
+                            WorktreeEntry::Branch { branch, .. } => branch.name() == query,
+                            WorktreeEntry::NewBranch { .. } => false,
+                        })
+                    {
+                        matches.push(WorktreeEntry::NewBranch { name: query });
+                    }
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.matches.get(self.selected_index()) else {
+            return;
+        };
+        match entry.clone() {
+            WorktreeEntry::Worktree { worktree, .. } => {
+                let workspace = self.workspace.clone();
+                cx.spawn_in(window, async move |_, cx| {
+                    open_worktree(workspace, worktree.path, cx).await
+                })
+                .detach_and_prompt_err(
+                    "Failed to open worktree",
+                    window,
+                    cx,
+                    |e, _, _| Some(e.to_string()),
+                );
+                cx.emit(DismissEvent);
+            }
+            WorktreeEntry::Branch { branch, .. } => {
+                // For a remote branch, git creates a local branch that tracks it.
+                let branch_name = if branch.is_remote() {
+                    branch
+                        .name()
+                        .split_once('/')
+                        .map_or(branch.name(), |(_, name)| name)
+                } else {
+                    branch.name()
+                };
+                self.create_worktree(branch_name.to_string(), false, window, cx);
+            }
+            WorktreeEntry::NewBranch { name } => {
+                self.create_worktree(name, true, window, cx);
+            }
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let entry = &self.matches[ix];
+        let item = ListItem::new(SharedString::from(format!("worktree-{ix}")))
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected);
+
+        let item = match entry {
+            WorktreeEntry::Worktree {
+                worktree,
+                positions,
+            } => {
+                let detail = match worktree.branch_name() {
+                    Some(branch_name) => branch_name.to_string(),
+                    None => {
+                        let short_sha = worktree
+                            .sha
+                            .get(0..git::SHORT_SHA_LENGTH)
+                            .unwrap_or(&worktree.sha);
+                        format!("{short_sha} (detached)")
+                    }
+                };
+                let is_current = self
+                    .repo
+                    .as_ref()
+                    .is_some_and(|repo| *repo.read(cx).work_directory_abs_path == *worktree.path);
+                let can_remove = !worktree.is_main && !worktree.is_locked && !is_current;
+
+                item.child(
+                    v_flex()
+                        .w_full()
+                        .overflow_hidden()
+                        .child(
+                            HighlightedLabel::new(
+                                worktree.path.to_string_lossy().into_owned(),
+                                positions.clone(),
+                            )
+                            .truncate(),
+                        )
+                        .child(
+                            Label::new(detail)
+                                .size(LabelSize::Small)
+                                .truncate()
+                                .color(Color::Muted),
+                        ),
+                )
+                .when(can_remove, |item| {
+                    item.end_slot(
+                        IconButton::new(("remove-worktree", ix), IconName::Trash)
+                            .icon_size(IconSize::Small)
+                            .icon_color(Color::Muted)
+                            .tooltip(Tooltip::text("Remove Worktree"))
+                            .on_click(cx.listener(move |picker, _, window, cx| {
+                                cx.stop_propagation();
+                                window.prevent_default();
+
+                                picker.delegate.remove_worktree(ix, window, cx);
+                            })),
+                    )
+                })
+            }
+            WorktreeEntry::Branch { branch, positions } => item.child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Icon::new(IconName::GitBranch)
+                            .size(IconSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(Label::new("Create worktree for").single_line())
+                    .child(
+                        HighlightedLabel::new(branch.name().to_owned(), positions.clone())
+                            .truncate(),
+                    ),
+            ),
+            WorktreeEntry::NewBranch { name } => item.child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Icon::new(IconName::Plus)
+                            .size(IconSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        Label::new(format!("Create worktree with new branch \"{name}\"…"))
+                            .single_line()
+                            .truncate(),
+                    ),
+            ),
+        };
+        Some(item)
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No worktrees".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_worktree_path() {
+        assert_eq!(
+            default_worktree_path(Path::new("/code/zed"), "feature/review"),
+            PathBuf::from("/code/zed-feature-review")
+        );
+    }
+}
@@ -0,0 +1,561 @@
+use std::ops::Range;
+
+use editor::{Editor, SelectionEffects, scroll::Autoscroll};
+use gpui::{
+    Entity, EventEmitter, FocusHandle, Focusable, Task, UniformListScrollHandle, WeakEntity,
+    uniform_list,
+};
+use language::{Buffer, ToPoint as _};
+use project::{HierarchyItem, Project};
+use ui::{ListItem, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    SplitDirection, Workspace,
+    item::{Item, ItemEvent},
+};
+use zed_actions::hierarchy_view::{ShowCallHierarchy, ShowTypeHierarchy};
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(HierarchyView::register).detach();
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HierarchyDirection {
+    IncomingCalls,
+    OutgoingCalls,
+    Supertypes,
+    Subtypes,
+}
+
+impl HierarchyDirection {
+    fn label(self) -> &'static str {
+        match self {
+            Self::IncomingCalls => "Incoming Calls",
+            Self::OutgoingCalls => "Outgoing Calls",
+            Self::Supertypes => "Supertypes",
+            Self::Subtypes => "Subtypes",
+        }
+    }
+
+    /// The directions that can be explored from the same root items.
+    fn siblings(self) -> [Self; 2] {
+        match self {
+            Self::IncomingCalls | Self::OutgoingCalls => [Self::IncomingCalls, Self::OutgoingCalls],
+            Self::Supertypes | Self::Subtypes => [Self::Supertypes, Self::Subtypes],
+        }
+    }
+
+    fn children(
+        self,
+        project: &Entity<Project>,
+        item: HierarchyItem,
+        cx: &mut App,
+    ) -> Task<anyhow::Result<Vec<HierarchyItem>>> {
+        project.update(cx, |project, cx| match self {
+            Self::IncomingCalls => project.incoming_calls(item, cx),
+            Self::OutgoingCalls => project.outgoing_calls(item, cx),
+            Self::Supertypes => project.supertypes(item, cx),
+            Self::Subtypes => project.subtypes(item, cx),
+        })
+    }
+}
+
+struct HierarchyNode {
+    item: HierarchyItem,
+    depth: usize,
+    expanded: bool,
+    /// Indices of the child nodes, `None` until they are fetched from the language server.
+    children: Option<Vec<usize>>,
+    children_task: Option<Task<()>>,
+}
+
+/// A tree of the callers and callees, or supertypes and subtypes, of a symbol.
+pub struct HierarchyView {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    direction: HierarchyDirection,
+    root_items: Vec<HierarchyItem>,
+    nodes: Vec<HierarchyNode>,
+    roots: Vec<usize>,
+    visible_nodes: Vec<usize>,
+    selected_node: Option<usize>,
+    loading_roots: bool,
+    _load_roots: Option<Task<()>>,
+    list_scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+}
+
+impl HierarchyView {
+    fn register(editor: &mut Editor, _: Option<&mut Window>, cx: &mut Context<Editor>) {
+        if !editor.mode().is_full() {
+            return;
+        }
+        let handle = cx.entity().downgrade();
+        editor
+            .register_action({
+                let handle = handle.clone();
+                move |_: &ShowCallHierarchy, window, cx| {
+                    if let Some(editor) = handle.upgrade() {
+                        Self::deploy(editor, HierarchyDirection::IncomingCalls, window, cx);
+                    }
+                }
+            })
+            .detach();
+        editor
+            .register_action(move |_: &ShowTypeHierarchy, window, cx| {
+                if let Some(editor) = handle.upgrade() {
+                    Self::deploy(editor, HierarchyDirection::Supertypes, window, cx);
+                }
+            })
+            .detach();
+    }
+
+    fn deploy(
+        editor: Entity<Editor>,
+        direction: HierarchyDirection,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let (Some(workspace), Some(project)) =
+            (editor.read(cx).workspace(), editor.read(cx).project.clone())
+        else {
+            return;
+        };
+        let head = editor.read(cx).selections.newest_anchor().head();
+        let Some((buffer, position)) = editor
+            .read(cx)
+            .buffer()
+            .read(cx)
+            .text_anchor_for_position(head, cx)
+        else {
+            return;
+        };
+
+        workspace.update(cx, |workspace, cx| {
+            let existing = workspace.items_of_type::<Self>(cx).next();
+            if let Some(existing) = existing {
+                existing.update(cx, |view, cx| {
+                    view.set_root(buffer, position, direction, window, cx)
+                });
+                workspace.activate_item(&existing, true, true, window, cx);
+            } else {
+                let workspace_handle = workspace.weak_handle();
+                let view = cx.new(|cx| {
+                    let mut view = Self::new(workspace_handle, project, direction, cx);
+                    view.set_root(buffer, position, direction, window, cx);
+                    view
+                });
+                workspace.split_item(SplitDirection::Right, Box::new(view), window, cx);
+            }
+        });
+    }
+
+    fn new(
+        workspace: WeakEntity<Workspace>,
+        project: Entity<Project>,
+        direction: HierarchyDirection,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self {
+            workspace,
+            project,
+            direction,
+            root_items: Vec::new(),
+            nodes: Vec::new(),
+            roots: Vec::new(),
+            visible_nodes: Vec::new(),
+            selected_node: None,
+            loading_roots: false,
+            _load_roots: None,
+            list_scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn set_root(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: language::Anchor,
+        direction: HierarchyDirection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let prepare = self.project.update(cx, |project, cx| match direction {
+            HierarchyDirection::IncomingCalls | HierarchyDirection::OutgoingCalls => {
+                project.prepare_call_hierarchy(&buffer, position, cx)
+            }
+            HierarchyDirection::Supertypes | HierarchyDirection::Subtypes => {
+                project.prepare_type_hierarchy(&buffer, position, cx)
+            }
+        });
+        self.direction = direction;
+        self.root_items.clear();
+        self.reset_nodes(cx);
+        self.loading_roots = true;
+        self._load_roots = Some(cx.spawn_in(window, async move |view, cx| {
+            let root_items = prepare.await.log_err().unwrap_or_default();
+            view.update(cx, |view, cx| {
+                view.loading_roots = false;
+                view.root_items = root_items;
+                view.reset_nodes(cx);
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn set_direction(&mut self, direction: HierarchyDirection, cx: &mut Context<Self>) {
+        if self.direction != direction {
+            self.direction = direction;
+            self.reset_nodes(cx);
+        }
+    }
+
+    /// Rebuilds the tree from the root items, expanding them in the current direction.
+    fn reset_nodes(&mut self, cx: &mut Context<Self>) {
+        self.nodes.clear();
+        self.roots.clear();
+        self.selected_node = None;
+        for item in self.root_items.clone() {
+            let ix = self.push_node(item, 0);
+            self.roots.push(ix);
+            self.toggle_node(ix, cx);
+        }
+        self.update_visible_nodes(cx);
+    }
+
+    fn push_node(&mut self, item: HierarchyItem, depth: usize) -> usize {
+        self.nodes.push(HierarchyNode {
+            item,
+            depth,
+            expanded: false,
+            children: None,
+            children_task: None,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn toggle_node(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some(node) = self.nodes.get_mut(ix) else {
+            return;
+        };
+        node.expanded = !node.expanded;
+        if node.expanded && node.children.is_none() && node.children_task.is_none() {
+            let children = self
+                .direction
+                .children(&self.project, node.item.clone(), cx);
+            node.children_task = Some(cx.spawn(async move |view, cx| {
+                let children = children.await.log_err().unwrap_or_default();
+                view.update(cx, |view, cx| {
+                    let Some(depth) = view.nodes.get(ix).map(|node| node.depth + 1) else {
+                        return;
+                    };
+                    let children = children
+                        .into_iter()
+                        .map(|item| view.push_node(item, depth))
+                        .collect();
+                    view.nodes[ix].children = Some(children);
+                    view.update_visible_nodes(cx);
+                })
+                .ok();
+            }));
+        }
+        self.update_visible_nodes(cx);
+    }
+
+    fn update_visible_nodes(&mut self, cx: &mut Context<Self>) {
+        let mut visible_nodes = Vec::new();
+        let mut stack = self.roots.iter().rev().copied().collect::<Vec<_>>();
+        while let Some(ix) = stack.pop() {
+            visible_nodes.push(ix);
+            let node = &self.nodes[ix];
+            if node.expanded {
+                if let Some(children) = &node.children {
+                    stack.extend(children.iter().rev().copied());
+                }
+            }
+        }
+        self.visible_nodes = visible_nodes;
+        cx.notify();
+    }
+
+    fn open_node(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(node) = self.nodes.get(ix) else {
+            return;
+        };
+        self.selected_node = Some(ix);
+        let buffer = node.item.location.buffer.clone();
+        let position = node.item.location.range.start.to_point(buffer.read(cx));
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let pane = workspace.adjacent_pane(window, cx);
+                let editor =
+                    workspace.open_project_item::<Editor>(pane, buffer, true, true, window, cx);
+                editor.update(cx, |editor, cx| {
+                    editor.change_selections(
+                        SelectionEffects::scroll(Autoscroll::center()),
+                        window,
+                        cx,
+                        |selections| selections.select_ranges([position..position]),
+                    );
+                });
+            })
+            .log_err();
+        cx.notify();
+    }
+
+    fn render_node(&self, ix: usize, cx: &mut Context<Self>) -> ListItem {
+        let node = &self.nodes[ix];
+        let loading = node.expanded && node.children.is_none();
+        let has_children = node
+            .children
+            .as_ref()
+            .is_none_or(|children| !children.is_empty());
+        let path = node
+            .item
+            .location
+            .buffer
+            .read(cx)
+            .file()
+            .map(|file| file.path().to_string_lossy().into_owned());
+        let detail = node.item.detail.clone().or(path);
+
+        ListItem::new(ix)
+            .indent_level(node.depth)
+            .indent_step_size(px(12.))
+            .toggle(has_children.then_some(node.expanded))
+            .toggle_state(self.selected_node == Some(ix))
+            .on_toggle(cx.listener(move |view, _, _, cx| view.toggle_node(ix, cx)))
+            .on_click(cx.listener(move |view, _, window, cx| view.open_node(ix, window, cx)))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new(node.item.name.clone()))
+                    .children(detail.map(|detail| {
+                        Label::new(detail)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate()
+                    })),
+            )
+            .end_slot::<Label>(loading.then(|| {
+                Label::new("Loading…")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+            }))
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .p_1()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .children(self.direction.siblings().map(|direction| {
+                Button::new(direction.label(), direction.label())
+                    .label_size(LabelSize::Small)
+                    .toggle_state(self.direction == direction)
+                    .on_click(cx.listener(move |view, _, _, cx| view.set_direction(direction, cx)))
+            }))
+    }
+}
+
+impl Render for HierarchyView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = if self.visible_nodes.is_empty() {
+            let message = if self.loading_roots {
+                "Loading…"
+            } else {
+                "No hierarchy found for the symbol at the cursor"
+            };
+            div()
+                .flex_1()
+                .p_2()
+                .child(Label::new(message).color(Color::Muted))
+                .into_any_element()
+        } else {
+            uniform_list(
+                "HierarchyView",
+                self.visible_nodes.len(),
+                cx.processor(|view, range: Range<usize>, _, cx| {
+                    view.visible_nodes[range]
+                        .to_vec()
+                        .into_iter()
+                        .map(|ix| view.render_node(ix, cx))
+                        .collect()
+                }),
+            )
+            .flex_1()
+            .track_scroll(self.list_scroll_handle.clone())
+            .into_any_element()
+        };
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_header(cx))
+            .child(content)
+    }
+}
+
+impl EventEmitter<()> for HierarchyView {}
+
+impl Focusable for HierarchyView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for HierarchyView {
+    type Event = ();
+
+    fn to_item_events(_: &Self::Event, _: impl FnMut(ItemEvent)) {}
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        let title = match self.direction {
+            HierarchyDirection::IncomingCalls | HierarchyDirection::OutgoingCalls => {
+                "Call Hierarchy"
+            }
+            HierarchyDirection::Supertypes | HierarchyDirection::Subtypes => "Type Hierarchy",
+        };
+        match self.root_items.first() {
+            Some(root) => format!("{title}: {}", root.name).into(),
+            None => title.into(),
+        }
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use editor::test::editor_lsp_test_context::EditorLspTestContext;
+    use gpui::{TestAppContext, VisualTestContext};
+    use indoc::indoc;
+
+    #[gpui::test]
+    async fn test_expanding_incoming_and_outgoing_calls(cx: &mut TestAppContext) {
+        cx.update(init);
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+        cx.set_state(indoc! {"
+            fn main() {
+                helper();
+            }
+
+            fn hˇelper() {
+                leaf();
+            }
+
+            fn leaf() {}
+        "});
+
+        let uri = cx.buffer_lsp_url.clone();
+        let main = call_item("main", &uri, range(0, 3, 7));
+        let helper = call_item("helper", &uri, range(4, 3, 9));
+        let leaf = call_item("leaf", &uri, range(8, 3, 7));
+        cx.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>({
+            let helper = helper.clone();
+            move |_, _, _| {
+                let helper = helper.clone();
+                async move { Ok(Some(vec![helper])) }
+            }
+        });
+        cx.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>({
+            let main = main.clone();
+            move |_, params, _| {
+                let calls = (params.item.name == "helper").then(|| {
+                    vec![lsp::CallHierarchyIncomingCall {
+                        from: main.clone(),
+                        from_ranges: vec![range(1, 4, 10)],
+                    }]
+                });
+                async move { Ok(Some(calls.unwrap_or_default())) }
+            }
+        });
+        cx.set_request_handler::<lsp::request::CallHierarchyOutgoingCalls, _, _>({
+            let leaf = leaf.clone();
+            move |_, params, _| {
+                let calls = (params.item.name == "helper").then(|| {
+                    vec![lsp::CallHierarchyOutgoingCall {
+                        to: leaf.clone(),
+                        from_ranges: vec![range(5, 4, 8)],
+                    }]
+                });
+                async move { Ok(Some(calls.unwrap_or_default())) }
+            }
+        });
+
+        cx.dispatch_action(ShowCallHierarchy);
+        cx.run_until_parked();
+        let view = cx.update_workspace(|workspace, _, cx| {
+            workspace
+                .items_of_type::<HierarchyView>(cx)
+                .next()
+                .expect("call hierarchy should be open")
+        });
+        assert_eq!(visible_entries(&view, &mut cx), ["helper", "  main"]);
+
+        // Expanding a caller fetches its own callers, of which there are none.
+        view.update(&mut cx.cx.cx, |view, cx| view.toggle_node(1, cx));
+        cx.run_until_parked();
+        view.read_with(&cx.cx.cx, |view, _| {
+            assert_eq!(view.nodes[1].children.as_deref(), Some(&[][..]));
+        });
+        assert_eq!(visible_entries(&view, &mut cx), ["helper", "  main"]);
+
+        view.update(&mut cx.cx.cx, |view, cx| {
+            view.set_direction(HierarchyDirection::OutgoingCalls, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(visible_entries(&view, &mut cx), ["helper", "  leaf"]);
+
+        // Collapsing and expanding again reuses the fetched callees.
+        view.update(&mut cx.cx.cx, |view, cx| view.toggle_node(0, cx));
+        assert_eq!(visible_entries(&view, &mut cx), ["helper"]);
+        view.update(&mut cx.cx.cx, |view, cx| view.toggle_node(0, cx));
+        assert_eq!(visible_entries(&view, &mut cx), ["helper", "  leaf"]);
+    }
+
+    fn range(line: u32, start: u32, end: u32) -> lsp::Range {
+        lsp::Range::new(
+            lsp::Position::new(line, start),
+            lsp::Position::new(line, end),
+        )
+    }
+
+    fn call_item(name: &str, uri: &lsp::Url, range: lsp::Range) -> lsp::CallHierarchyItem {
+        lsp::CallHierarchyItem {
+            name: name.to_string(),
+            kind: lsp::SymbolKind::FUNCTION,
+            tags: None,
+            detail: None,
+            uri: uri.clone(),
+            range,
+            selection_range: range,
+            data: None,
+        }
+    }
+
+    fn visible_entries(view: &Entity<HierarchyView>, cx: &mut VisualTestContext) -> Vec<String> {
+        view.read_with(cx, |view, _| {
+            view.visible_nodes
+                .iter()
+                .map(|&ix| {
+                    let node = &view.nodes[ix];
+                    format!("{}{}", "  ".repeat(node.depth), node.item.name)
+                })
+                .collect()
+        })
+    }
+}
@@ -1,5 +1,17 @@
 mod jj_repository;
 mod jj_store;
 
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+pub use git::jujutsu::{Bookmark, Change, JujutsuRepository};
 pub use jj_repository::*;
 pub use jj_store::*;
+
+/// Opens the jj workspace rooted at `workspace_root`. This is the
+/// [`git::jujutsu::JujutsuRepositoryOpener`] the application hands to the real filesystem.
+pub fn open_repository(workspace_root: &Path) -> Result<Arc<dyn JujutsuRepository>> {
+    Ok(Arc::new(RealJujutsuRepository::new(workspace_root)?))
+}
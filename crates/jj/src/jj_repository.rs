@@ -1,24 +1,47 @@
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use anyhow::Result;
-use gpui::SharedString;
+use anyhow::{Context as _, Result, bail};
+use git::jujutsu::{Bookmark, Change, JujutsuRepository};
+use git::repository::RepoPath;
+use git::status::{
+    FileStatus, GitStatus, StatusCode, TrackedStatus, UnmergedStatus, UnmergedStatusCode,
+};
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
 use jj_lib::config::StackedConfig;
-use jj_lib::repo::StoreFactories;
+use jj_lib::conflicts::{ConflictMarkerStyle, MaterializedTreeValue, materialize_tree_value};
+use jj_lib::fsmonitor::FsmonitorSettings;
+use jj_lib::gitignore::GitIgnoreFile;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::repo::{ReadonlyRepo, Repo as _, StoreFactories};
+use jj_lib::repo_path::{RepoPathBuf, RepoPathUiConverter};
+use jj_lib::revset::{
+    self, RevsetAliasesMap, RevsetDiagnostics, RevsetExtensions, RevsetIteratorExt as _,
+    RevsetParseContext, RevsetWorkspaceContext, SymbolResolver,
+};
 use jj_lib::settings::UserSettings;
-use jj_lib::workspace::{self, DefaultWorkspaceLoaderFactory, WorkspaceLoaderFactory};
+use jj_lib::transaction::Transaction;
+use jj_lib::working_copy::{CheckoutOptions, SnapshotOptions, WorkingCopyFreshness};
+use jj_lib::workspace::{self, DefaultWorkspaceLoaderFactory, Workspace, WorkspaceLoaderFactory};
+use parking_lot::Mutex;
 
-#[derive(Debug, Clone)]
-pub struct Bookmark {
-    pub ref_name: SharedString,
-}
+/// The revset shown by default: the working-copy change and its ancestors.
+pub const DEFAULT_LOG_REVSET: &str = "::@";
 
-pub trait JujutsuRepository: Send + Sync {
-    fn list_bookmarks(&self) -> Vec<Bookmark>;
-}
+/// New files larger than this aren't tracked by snapshots, matching the jj CLI's default.
+const MAX_NEW_FILE_SIZE: u64 = 1024 * 1024;
 
 pub struct RealJujutsuRepository {
-    repository: Arc<jj_lib::repo::ReadonlyRepo>,
+    workspace_root: PathBuf,
+    workspace: Mutex<Workspace>,
+    settings: UserSettings,
+    repository: Mutex<Arc<ReadonlyRepo>>,
+    /// The tree of the working-copy change's parents as of the last snapshot, along with the
+    /// repository it was read from.
+    base_tree: Mutex<Option<(Arc<ReadonlyRepo>, MergedTree)>>,
 }
 
 impl RealJujutsuRepository {
@@ -38,7 +61,13 @@ impl RealJujutsuRepository {
         let repo_loader = workspace.repo_loader();
         let repository = repo_loader.load_at_head()?;
 
-        Ok(Self { repository })
+        Ok(Self {
+            workspace_root: workspace.workspace_root().to_path_buf(),
+            workspace: Mutex::new(workspace),
+            settings,
+            repository: Mutex::new(repository),
+            base_tree: Mutex::new(None),
+        })
     }
 
     fn find_workspace_dir(cwd: &Path) -> &Path {
@@ -46,12 +75,243 @@ impl RealJujutsuRepository {
             .find(|path| path.join(".jj").is_dir())
             .unwrap_or(cwd)
     }
+
+    /// Reloads the repository at its latest operation, so that changes made with the jj CLI are
+    /// picked up.
+    fn reload(&self) -> Result<Arc<ReadonlyRepo>> {
+        let repository = self.workspace.lock().repo_loader().load_at_head()?;
+        *self.repository.lock() = repository.clone();
+        Ok(repository)
+    }
+
+    /// Snapshots the files on disk into the working-copy change, as the jj CLI does before every
+    /// command, and returns the repository at the resulting operation.
+    fn snapshot(&self) -> Result<Arc<ReadonlyRepo>> {
+        let repository = self.reload()?;
+        let mut workspace = self.workspace.lock();
+        let workspace_name = workspace.workspace_name().to_owned();
+        let Some(working_copy_commit_id) = repository.view().get_wc_commit_id(&workspace_name)
+        else {
+            return Ok(repository);
+        };
+        let working_copy_commit = repository.store().get_commit(working_copy_commit_id)?;
+
+        let mut locked_workspace = workspace.start_working_copy_mutation()?;
+        match WorkingCopyFreshness::check_stale(
+            locked_workspace.locked_wc(),
+            &working_copy_commit,
+            &repository,
+        )? {
+            WorkingCopyFreshness::Fresh => {}
+            WorkingCopyFreshness::Updated(_) | WorkingCopyFreshness::SiblingOperation => {
+                bail!("the working copy was changed by a concurrent operation")
+            }
+            WorkingCopyFreshness::WorkingCopyStale => {
+                bail!("the working copy is stale, run `jj workspace update-stale`")
+            }
+        }
+
+        let (new_tree_id, _) = locked_workspace.locked_wc().snapshot(&SnapshotOptions {
+            base_ignores: GitIgnoreFile::empty(),
+            fsmonitor_settings: FsmonitorSettings::None,
+            progress: None,
+            start_tracking_matcher: &EverythingMatcher,
+            max_new_file_size: MAX_NEW_FILE_SIZE,
+            conflict_marker_style: ConflictMarkerStyle::default(),
+        })?;
+        let repository = if new_tree_id == *working_copy_commit.tree_id() {
+            repository
+        } else {
+            let mut transaction = repository.start_transaction();
+            transaction.set_is_snapshot(true);
+            let commit = transaction
+                .repo_mut()
+                .rewrite_commit(&working_copy_commit)
+                .set_tree_id(new_tree_id)
+                .write()?;
+            transaction
+                .repo_mut()
+                .set_wc_commit(workspace_name, commit.id().clone())?;
+            transaction.repo_mut().rebase_descendants()?;
+            transaction.commit("snapshot working copy")?
+        };
+        locked_workspace.finish(repository.op_id().clone())?;
+
+        *self.repository.lock() = repository.clone();
+        Ok(repository)
+    }
+
+    /// Evaluates `revset`, stopping after `limit` changes.
+    fn resolve_revset(
+        &self,
+        repository: &Arc<ReadonlyRepo>,
+        revset: &str,
+        limit: usize,
+    ) -> Result<Vec<Commit>> {
+        let workspace = self.workspace.lock();
+        let workspace_root = workspace.workspace_root().to_path_buf();
+        let path_converter = RepoPathUiConverter::Fs {
+            cwd: workspace_root.clone(),
+            base: workspace_root,
+        };
+        let aliases_map = RevsetAliasesMap::new();
+        let extensions = RevsetExtensions::default();
+        let context = RevsetParseContext {
+            aliases_map: &aliases_map,
+            local_variables: HashMap::new(),
+            user_email: self.settings.user_email(),
+            date_pattern_context: chrono::Local::now().into(),
+            extensions: &extensions,
+            workspace: Some(RevsetWorkspaceContext {
+                path_converter: &path_converter,
+                workspace_name: workspace.workspace_name(),
+            }),
+        };
+
+        let mut diagnostics = RevsetDiagnostics::new();
+        let expression = revset::parse(&mut diagnostics, revset, &context)
+            .with_context(|| format!("invalid revset {revset:?}"))?;
+        let symbol_resolver =
+            SymbolResolver::new(repository.as_ref(), extensions.symbol_resolvers());
+        let commits = expression
+            .resolve_user_expression(repository.as_ref(), &symbol_resolver)?
+            .evaluate(repository.as_ref())?
+            .iter()
+            .commits(repository.store())
+            .take(limit)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(commits)
+    }
+
+    fn resolve_single(&self, repository: &Arc<ReadonlyRepo>, revision: &str) -> Result<Commit> {
+        let mut commits = self.resolve_revset(repository, revision, 2)?;
+        match commits.len() {
+            0 => bail!("revision {revision:?} doesn't exist"),
+            1 => Ok(commits.remove(0)),
+            _ => bail!("revision {revision:?} resolved to more than one change"),
+        }
+    }
+
+    /// Snapshots the working copy, runs `f` in a transaction on the resulting operation, rebases
+    /// descendants of any rewritten changes and updates the working copy if the working-copy
+    /// change was replaced.
+    fn transact(
+        &self,
+        description: String,
+        f: impl FnOnce(&mut Transaction, &Arc<ReadonlyRepo>) -> Result<()>,
+    ) -> Result<()> {
+        let repository = self.snapshot()?;
+        let mut transaction = repository.start_transaction();
+        f(&mut transaction, &repository)?;
+        transaction.repo_mut().rebase_descendants()?;
+        let new_repository = transaction.commit(description)?;
+
+        self.update_working_copy(&repository, &new_repository)?;
+        *self.repository.lock() = new_repository;
+        *self.base_tree.lock() = None;
+        Ok(())
+    }
+
+    fn update_working_copy(
+        &self,
+        old_repository: &Arc<ReadonlyRepo>,
+        new_repository: &Arc<ReadonlyRepo>,
+    ) -> Result<()> {
+        let mut workspace = self.workspace.lock();
+        let workspace_name = workspace.workspace_name().to_owned();
+        let old_commit_id = old_repository.view().get_wc_commit_id(&workspace_name);
+        let Some(new_commit_id) = new_repository.view().get_wc_commit_id(&workspace_name) else {
+            return Ok(());
+        };
+        if old_commit_id == Some(new_commit_id) {
+            return Ok(());
+        }
+
+        let old_tree_id = old_commit_id
+            .map(|commit_id| old_repository.store().get_commit(commit_id))
+            .transpose()?
+            .map(|commit| commit.tree_id().clone());
+        let new_commit = new_repository.store().get_commit(new_commit_id)?;
+        workspace.check_out(
+            new_repository.op_id().clone(),
+            old_tree_id.as_ref(),
+            &new_commit,
+            &CheckoutOptions {
+                conflict_marker_style: ConflictMarkerStyle::default(),
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Snapshots the working copy and returns the trees of the working-copy change's parents and
+    /// of the working-copy change.
+    fn working_copy_trees(&self) -> Result<(Arc<ReadonlyRepo>, MergedTree, MergedTree)> {
+        let repository = self.snapshot()?;
+        let working_copy_commit_id = repository
+            .view()
+            .get_wc_commit_id(self.workspace.lock().workspace_name())
+            .cloned()
+            .context("workspace has no working-copy change")?;
+        let working_copy_commit = repository.store().get_commit(&working_copy_commit_id)?;
+        let base_tree = working_copy_commit.parent_tree(repository.as_ref())?;
+        let working_copy_tree = working_copy_commit.tree()?;
+        *self.base_tree.lock() = Some((repository.clone(), base_tree.clone()));
+        Ok((repository, base_tree, working_copy_tree))
+    }
+
+    fn change_for_commit(
+        &self,
+        repository: &Arc<ReadonlyRepo>,
+        commit: &Commit,
+        working_copy_commit_id: Option<&CommitId>,
+    ) -> Result<Change> {
+        let author = commit.author();
+        let bookmarks = repository
+            .view()
+            .local_bookmarks_for_commit(commit.id())
+            .map(|(ref_name, _)| ref_name.as_str().to_string().into())
+            .collect();
+
+        Ok(Change {
+            change_id: commit.change_id().reverse_hex().into(),
+            commit_id: commit.id().hex().into(),
+            parent_commit_ids: commit
+                .parent_ids()
+                .iter()
+                .map(|parent_id| parent_id.hex().into())
+                .collect(),
+            description: commit.description().to_string().into(),
+            author_name: author.name.clone().into(),
+            author_email: author.email.clone().into(),
+            timestamp: author.timestamp.timestamp.0 / 1000,
+            bookmarks,
+            is_working_copy: working_copy_commit_id == Some(commit.id()),
+            is_empty: commit.is_empty(repository.as_ref())?,
+            is_root: commit.id() == repository.store().root_commit_id(),
+        })
+    }
+}
+
+fn to_jj_path(path: &RepoPath) -> Result<RepoPathBuf> {
+    Ok(RepoPathBuf::from_relative_path(&*path.0)?)
+}
+
+/// jj stores non-empty descriptions with a trailing newline.
+fn complete_description(description: &str) -> String {
+    let description = description.trim();
+    if description.is_empty() {
+        String::new()
+    } else {
+        format!("{description}\n")
+    }
 }
 
 impl JujutsuRepository for RealJujutsuRepository {
     fn list_bookmarks(&self) -> Vec<Bookmark> {
         let bookmarks = self
             .repository
+            .lock()
             .view()
             .bookmarks()
             .map(|(ref_name, _target)| Bookmark {
@@ -61,12 +321,347 @@ impl JujutsuRepository for RealJujutsuRepository {
 
         bookmarks
     }
+
+    fn log(&self, revset: &str, limit: usize) -> Result<Vec<Change>> {
+        let repository = self.reload()?;
+        let working_copy_commit_id = {
+            let workspace = self.workspace.lock();
+            repository
+                .view()
+                .get_wc_commit_id(workspace.workspace_name())
+                .cloned()
+        };
+
+        self.resolve_revset(&repository, revset, limit)?
+            .iter()
+            .map(|commit| {
+                self.change_for_commit(&repository, commit, working_copy_commit_id.as_ref())
+            })
+            .collect()
+    }
+
+    fn describe(&self, revision: &str, description: String) -> Result<()> {
+        self.transact(format!("describe {revision}"), |transaction, repository| {
+            let commit = self.resolve_single(repository, revision)?;
+            if commit.id() == repository.store().root_commit_id() {
+                bail!("cannot describe the root change");
+            }
+            transaction
+                .repo_mut()
+                .rewrite_commit(&commit)
+                .set_description(complete_description(&description))
+                .write()?;
+            Ok(())
+        })
+    }
+
+    fn new_change(&self, revision: &str) -> Result<()> {
+        let workspace_name = self.workspace.lock().workspace_name().to_owned();
+        self.transact(format!("new {revision}"), |transaction, repository| {
+            let parent = self.resolve_single(repository, revision)?;
+            let new_commit = transaction
+                .repo_mut()
+                .new_commit(vec![parent.id().clone()], parent.tree_id().clone())
+                .write()?;
+            transaction.repo_mut().edit(workspace_name, &new_commit)?;
+            Ok(())
+        })
+    }
+
+    fn squash(&self, revision: &str) -> Result<()> {
+        self.transact(format!("squash {revision}"), |transaction, repository| {
+            let commit = self.resolve_single(repository, revision)?;
+            let [parent_id] = commit.parent_ids() else {
+                bail!("cannot squash a merge change");
+            };
+            if parent_id == repository.store().root_commit_id() {
+                bail!("cannot squash into the root change");
+            }
+            let parent = repository.store().get_commit(parent_id)?;
+
+            let description = match (
+                parent.description().trim().is_empty(),
+                commit.description().trim().is_empty(),
+            ) {
+                (_, true) => parent.description().to_string(),
+                (true, false) => commit.description().to_string(),
+                (false, false) => complete_description(&format!(
+                    "{}\n\n{}",
+                    parent.description().trim(),
+                    commit.description().trim()
+                )),
+            };
+
+            // With a single parent, the squashed tree is the child's tree.
+            transaction
+                .repo_mut()
+                .rewrite_commit(&parent)
+                .set_tree_id(commit.tree_id().clone())
+                .set_description(description)
+                .write()?;
+            transaction.repo_mut().record_abandoned_commit(&commit);
+            Ok(())
+        })
+    }
+
+    fn abandon(&self, revision: &str) -> Result<()> {
+        self.transact(format!("abandon {revision}"), |transaction, repository| {
+            let commit = self.resolve_single(repository, revision)?;
+            if commit.id() == repository.store().root_commit_id() {
+                bail!("cannot abandon the root change");
+            }
+            transaction.repo_mut().record_abandoned_commit(&commit);
+            Ok(())
+        })
+    }
+
+    fn workspace_root(&self) -> &Path {
+        &self.workspace_root
+    }
+
+    fn load_base_text(&self, path: &RepoPath) -> Result<Option<String>> {
+        let cached_base_tree = self.base_tree.lock().clone();
+        let (repository, base_tree) = match cached_base_tree {
+            Some(cached_base_tree) => cached_base_tree,
+            None => {
+                let (repository, base_tree, _) = self.working_copy_trees()?;
+                (repository, base_tree)
+            }
+        };
+        let path = to_jj_path(path)?;
+        let value = base_tree.path_value(&path)?;
+        let value =
+            futures::executor::block_on(materialize_tree_value(repository.store(), &path, value))?;
+        let MaterializedTreeValue::File(mut file) = value else {
+            return Ok(None);
+        };
+        let content = futures::executor::block_on(file.read_all(&path))?;
+        Ok(String::from_utf8(content).ok())
+    }
+
+    fn status(&self, path_prefixes: &[RepoPath]) -> Result<GitStatus> {
+        let (_, base_tree, working_copy_tree) = self.working_copy_trees()?;
+        let mut entries = Vec::new();
+        for entry in base_tree.diff(&working_copy_tree, &EverythingMatcher) {
+            let path = RepoPath::from_str(entry.path.as_internal_file_string());
+            if !path_prefixes
+                .iter()
+                .any(|prefix| path.starts_with(&prefix.0))
+            {
+                continue;
+            }
+            let (before, after) = entry.values?;
+            let status: FileStatus = if !after.is_resolved() {
+                UnmergedStatus {
+                    first_head: UnmergedStatusCode::Updated,
+                    second_head: UnmergedStatusCode::Updated,
+                }
+                .into()
+            } else {
+                let worktree_status = if before.is_absent() {
+                    StatusCode::Added
+                } else if after.is_absent() {
+                    StatusCode::Deleted
+                } else {
+                    StatusCode::Modified
+                };
+                TrackedStatus {
+                    index_status: StatusCode::Unmodified,
+                    worktree_status,
+                }
+                .into()
+            };
+            entries.push((path, status));
+        }
+
+        Ok(GitStatus {
+            entries: entries.into(),
+        })
+    }
+
+    fn edit(&self, revision: &str) -> Result<()> {
+        let workspace_name = self.workspace.lock().workspace_name().to_owned();
+        self.transact(format!("edit {revision}"), |transaction, repository| {
+            let commit = self.resolve_single(repository, revision)?;
+            if commit.id() == repository.store().root_commit_id() {
+                bail!("cannot edit the root change");
+            }
+            transaction.repo_mut().edit(workspace_name, &commit)?;
+            Ok(())
+        })
+    }
 }
 
-pub struct FakeJujutsuRepository {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::repository::WORK_DIRECTORY_REPO_PATH;
 
-impl JujutsuRepository for FakeJujutsuRepository {
-    fn list_bookmarks(&self) -> Vec<Bookmark> {
-        Vec::new()
+    fn init_repository() -> (tempfile::TempDir, RealJujutsuRepository) {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = UserSettings::from_config(StackedConfig::with_defaults()).unwrap();
+        Workspace::init_simple(&settings, dir.path()).unwrap();
+        let repository = RealJujutsuRepository::new(dir.path()).unwrap();
+        (dir, repository)
+    }
+
+    #[test]
+    fn test_log_stops_at_limit() {
+        let (_dir, repository) = init_repository();
+        repository.new_change("@").unwrap();
+        repository.new_change("@").unwrap();
+
+        assert_eq!(repository.log(DEFAULT_LOG_REVSET, 10).unwrap().len(), 4);
+        let changes = repository.log(DEFAULT_LOG_REVSET, 2).unwrap();
+        assert_eq!(changes.len(), 2);
+        assert!(changes[0].is_working_copy);
+    }
+
+    #[test]
+    fn test_describe_and_new_change_snapshot_the_working_copy() {
+        let (dir, repository) = init_repository();
+        std::fs::write(dir.path().join("file"), "one\n").unwrap();
+        repository.describe("@", "First change".into()).unwrap();
+        repository.new_change("@").unwrap();
+
+        let changes = repository.log(DEFAULT_LOG_REVSET, 10).unwrap();
+        assert_eq!(changes.len(), 3);
+        assert!(changes[0].is_working_copy);
+        assert!(changes[0].is_empty);
+        assert_eq!(changes[1].description.as_ref(), "First change\n");
+        assert!(!changes[1].is_empty);
+        assert!(changes[2].is_root);
+    }
+
+    #[test]
+    fn test_squash() {
+        let (dir, repository) = init_repository();
+        let file_path = dir.path().join("file");
+        std::fs::write(&file_path, "one\n").unwrap();
+        repository.describe("@", "Parent".into()).unwrap();
+        repository.new_change("@").unwrap();
+        std::fs::write(&file_path, "two\n").unwrap();
+        repository.describe("@", "Child".into()).unwrap();
+
+        repository.squash("@").unwrap();
+
+        let changes = repository.log(DEFAULT_LOG_REVSET, 10).unwrap();
+        assert_eq!(changes.len(), 3);
+        assert!(changes[0].is_working_copy);
+        assert!(changes[0].is_empty);
+        assert_eq!(changes[1].description.as_ref(), "Parent\n\nChild\n");
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "two\n");
+        assert!(repository.squash("@-").is_err());
+    }
+
+    #[test]
+    fn test_abandon_updates_the_working_copy() {
+        let (dir, repository) = init_repository();
+        let file_path = dir.path().join("file");
+        std::fs::write(&file_path, "one\n").unwrap();
+        repository.new_change("@").unwrap();
+
+        repository.abandon("@-").unwrap();
+
+        let changes = repository.log(DEFAULT_LOG_REVSET, 10).unwrap();
+        assert_eq!(changes.len(), 2);
+        assert!(changes[0].is_working_copy);
+        assert_eq!(
+            changes[0].parent_commit_ids,
+            vec![changes[1].commit_id.clone()]
+        );
+        assert!(changes[1].is_root);
+        assert!(!file_path.exists());
+        assert!(repository.abandon("root()").is_err());
+    }
+
+    #[test]
+    fn test_status_and_base_text() {
+        let (dir, repository) = init_repository();
+        let root = dir.path();
+        std::fs::write(root.join(".gitignore"), "ignored\n").unwrap();
+        std::fs::write(root.join("modified"), "one\n").unwrap();
+        std::fs::write(root.join("deleted"), "deleted\n").unwrap();
+        repository.new_change("@").unwrap();
+
+        std::fs::write(root.join("modified"), "two\n").unwrap();
+        std::fs::remove_file(root.join("deleted")).unwrap();
+        std::fs::write(root.join("added"), "added\n").unwrap();
+        std::fs::write(root.join("ignored"), "ignored\n").unwrap();
+
+        // Files that aren't tracked yet are picked up by the snapshot, except ignored ones.
+        let status = repository
+            .status(&[WORK_DIRECTORY_REPO_PATH.clone()])
+            .unwrap();
+        assert_eq!(
+            status.entries.as_ref(),
+            &[
+                (
+                    RepoPath::from_str("added"),
+                    FileStatus::worktree(StatusCode::Added)
+                ),
+                (
+                    RepoPath::from_str("deleted"),
+                    FileStatus::worktree(StatusCode::Deleted)
+                ),
+                (
+                    RepoPath::from_str("modified"),
+                    FileStatus::worktree(StatusCode::Modified)
+                ),
+            ]
+        );
+        let status = repository
+            .status(&[RepoPath::from_str("modified")])
+            .unwrap();
+        assert_eq!(status.entries.len(), 1);
+
+        assert_eq!(
+            repository
+                .load_base_text(&RepoPath::from_str("modified"))
+                .unwrap()
+                .as_deref(),
+            Some("one\n")
+        );
+        assert_eq!(
+            repository
+                .load_base_text(&RepoPath::from_str("deleted"))
+                .unwrap()
+                .as_deref(),
+            Some("deleted\n")
+        );
+        assert_eq!(
+            repository
+                .load_base_text(&RepoPath::from_str("added"))
+                .unwrap(),
+            None
+        );
+
+        // Base texts come from the parent seen by the last status until the repository changes.
+        repository.new_change("@").unwrap();
+        assert_eq!(
+            repository
+                .load_base_text(&RepoPath::from_str("modified"))
+                .unwrap()
+                .as_deref(),
+            Some("two\n")
+        );
+    }
+
+    #[test]
+    fn test_edit() {
+        let (dir, repository) = init_repository();
+        let file_path = dir.path().join("file");
+        std::fs::write(&file_path, "one\n").unwrap();
+        repository.describe("@", "Edited".into()).unwrap();
+        repository.new_change("root()").unwrap();
+        assert!(!file_path.exists());
+
+        repository.edit("description(Edited)").unwrap();
+
+        let changes = repository.log(DEFAULT_LOG_REVSET, 10).unwrap();
+        assert!(changes[0].is_working_copy);
+        assert_eq!(changes[0].description.as_ref(), "Edited\n");
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "one\n");
+        assert!(repository.edit("root()").is_err());
     }
 }
@@ -1,19 +1,27 @@
 use std::path::Path;
 use std::sync::Arc;
 
-use gpui::{App, Entity, Global, prelude::*};
+use anyhow::Result;
+use gpui::{App, Entity, EventEmitter, Global, Task, prelude::*};
 
-use crate::{JujutsuRepository, RealJujutsuRepository};
+use crate::{Change, JujutsuRepository, RealJujutsuRepository};
 
 /// Note: We won't ultimately be storing the jj store in a global, we're just doing this for exploration purposes.
 struct GlobalJujutsuStore(Entity<JujutsuStore>);
 
 impl Global for GlobalJujutsuStore {}
 
+pub enum JujutsuStoreEvent {
+    /// The repository was changed through the store.
+    RepositoryUpdated,
+}
+
 pub struct JujutsuStore {
     repository: Arc<dyn JujutsuRepository>,
 }
 
+impl EventEmitter<JujutsuStoreEvent> for JujutsuStore {}
+
 impl JujutsuStore {
     pub fn init_global(cx: &mut App) {
         let Some(repository) = RealJujutsuRepository::new(&Path::new(".")).ok() else {
@@ -38,4 +46,56 @@ impl JujutsuStore {
     pub fn repository(&self) -> &Arc<dyn JujutsuRepository> {
         &self.repository
     }
+
+    pub fn log(
+        &self,
+        revset: String,
+        limit: usize,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Change>>> {
+        let repository = self.repository.clone();
+        cx.background_spawn(async move { repository.log(&revset, limit) })
+    }
+
+    pub fn describe(
+        &self,
+        revision: String,
+        description: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.update_repository(cx, move |repository| {
+            repository.describe(&revision, description)
+        })
+    }
+
+    pub fn new_change(&self, revision: String, cx: &mut Context<Self>) -> Task<Result<()>> {
+        self.update_repository(cx, move |repository| repository.new_change(&revision))
+    }
+
+    pub fn squash(&self, revision: String, cx: &mut Context<Self>) -> Task<Result<()>> {
+        self.update_repository(cx, move |repository| repository.squash(&revision))
+    }
+
+    pub fn abandon(&self, revision: String, cx: &mut Context<Self>) -> Task<Result<()>> {
+        self.update_repository(cx, move |repository| repository.abandon(&revision))
+    }
+
+    pub fn edit(&self, revision: String, cx: &mut Context<Self>) -> Task<Result<()>> {
+        self.update_repository(cx, move |repository| repository.edit(&revision))
+    }
+
+    fn update_repository(
+        &self,
+        cx: &mut Context<Self>,
+        f: impl FnOnce(&dyn JujutsuRepository) -> Result<()> + Send + 'static,
+    ) -> Task<Result<()>> {
+        let repository = self.repository.clone();
+        cx.spawn(async move |this, cx| {
+            let result = cx
+                .background_spawn(async move { f(repository.as_ref()) })
+                .await;
+            this.update(cx, |_, cx| cx.emit(JujutsuStoreEvent::RepositoryUpdated))?;
+            result
+        })
+    }
 }
@@ -0,0 +1,570 @@
+use std::ops::Range;
+
+use anyhow::Result;
+use editor::Editor;
+use feature_flags::{FeatureFlagAppExt as _, JjUiFeatureFlag};
+use gpui::{
+    Action, App, Entity, EventEmitter, FocusHandle, Focusable, ListSizingBehavior, Pixels,
+    ScrollStrategy, Subscription, Task, UniformListScrollHandle, Window, actions, px, uniform_list,
+};
+use jj::{Change, DEFAULT_LOG_REVSET, JujutsuStore, JujutsuStoreEvent};
+use menu::{Cancel, SecondaryConfirm, SelectNext, SelectPrevious};
+use panel::PanelHeader;
+use ui::{ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+    notifications::DetachAndPromptErr,
+};
+
+actions!(
+    jj,
+    [
+        /// Toggles focus on the Jujutsu panel.
+        ToggleFocus,
+        /// Creates a new change on top of the selected change.
+        NewChange,
+        /// Edits the description of the selected change.
+        Describe,
+        /// Squashes the selected change into its parent.
+        Squash,
+        /// Abandons the selected change.
+        Abandon,
+        /// Makes the selected change the working-copy change.
+        Edit,
+        /// Reloads the change log.
+        Refresh,
+    ]
+);
+
+/// How many changes the panel loads.
+const LOG_LIMIT: usize = 200;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+        workspace.toggle_panel_focus::<JjPanel>(window, cx);
+    });
+}
+
+struct DescriptionEditor {
+    change_id: SharedString,
+    editor: Entity<Editor>,
+}
+
+pub struct JjPanel {
+    jj_store: Option<Entity<JujutsuStore>>,
+    changes: Vec<Change>,
+    selected_index: Option<usize>,
+    description_editor: Option<DescriptionEditor>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    position: DockPosition,
+    width: Option<Pixels>,
+    pending_reload: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl JjPanel {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let jj_store = JujutsuStore::try_global(cx);
+        let mut subscriptions = Vec::new();
+        if let Some(jj_store) = &jj_store {
+            subscriptions.push(
+                cx.subscribe_in(jj_store, window, |this, _, event, window, cx| match event {
+                    JujutsuStoreEvent::RepositoryUpdated => this.reload(window, cx),
+                }),
+            );
+        }
+
+        let mut this = Self {
+            jj_store,
+            changes: Vec::new(),
+            selected_index: None,
+            description_editor: None,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            position: DockPosition::Left,
+            width: None,
+            pending_reload: Task::ready(()),
+            _subscriptions: subscriptions,
+        };
+        this.reload(window, cx);
+        this
+    }
+
+    fn reload(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(jj_store) = self.jj_store.clone() else {
+            return;
+        };
+        let log = jj_store.update(cx, |jj_store, cx| {
+            jj_store.log(DEFAULT_LOG_REVSET.to_string(), LOG_LIMIT, cx)
+        });
+
+        self.pending_reload = cx.spawn_in(window, async move |this, cx| {
+            let Some(changes) = log.await.log_err() else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                let selected_change_id = this
+                    .selected_change()
+                    .map(|change| change.change_id.clone());
+                this.selected_index = selected_change_id
+                    .and_then(|change_id| {
+                        changes
+                            .iter()
+                            .position(|change| change.change_id == change_id)
+                    })
+                    .or_else(|| changes.iter().position(|change| change.is_working_copy))
+                    .or(if changes.is_empty() { None } else { Some(0) });
+                this.changes = changes;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn selected_change(&self) -> Option<&Change> {
+        self.changes.get(self.selected_index?)
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.changes.is_empty() {
+            return;
+        }
+        let ix = self
+            .selected_index
+            .map_or(0, |ix| (ix + 1).min(self.changes.len() - 1));
+        self.select(ix, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        if self.changes.is_empty() {
+            return;
+        }
+        let ix = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+        self.select(ix, cx);
+    }
+
+    fn select(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_index = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    /// Runs `operation` on the selected change, reporting failures to the user.
+    fn update_selected_change(
+        &mut self,
+        error_message: &'static str,
+        operation: impl FnOnce(&JujutsuStore, String, &mut Context<JujutsuStore>) -> Task<Result<()>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(jj_store) = self.jj_store.clone() else {
+            return;
+        };
+        let Some(change) = self.selected_change() else {
+            return;
+        };
+        let revision = change.change_id.to_string();
+        jj_store
+            .update(cx, |jj_store, cx| operation(jj_store, revision, cx))
+            .detach_and_prompt_err(error_message, window, cx, |e, _, _| Some(e.to_string()));
+    }
+
+    fn new_change(&mut self, _: &NewChange, window: &mut Window, cx: &mut Context<Self>) {
+        self.update_selected_change(
+            "Failed to create a new change",
+            JujutsuStore::new_change,
+            window,
+            cx,
+        );
+    }
+
+    fn squash(&mut self, _: &Squash, window: &mut Window, cx: &mut Context<Self>) {
+        self.update_selected_change("Failed to squash change", JujutsuStore::squash, window, cx);
+    }
+
+    fn abandon(&mut self, _: &Abandon, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(change) = self.selected_change() else {
+            return;
+        };
+        let prompt = window.prompt(
+            gpui::PromptLevel::Warning,
+            &format!("Abandon change {}?", change.short_change_id()),
+            Some("Its descendants will be rebased onto its parents."),
+            &["Abandon", "Cancel"],
+            cx,
+        );
+        cx.spawn_in(window, async move |this, cx| {
+            if prompt.await.ok() != Some(0) {
+                return;
+            }
+            this.update_in(cx, |this, window, cx| {
+                this.update_selected_change(
+                    "Failed to abandon change",
+                    JujutsuStore::abandon,
+                    window,
+                    cx,
+                );
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn edit(&mut self, _: &Edit, window: &mut Window, cx: &mut Context<Self>) {
+        self.update_selected_change("Failed to edit change", JujutsuStore::edit, window, cx);
+    }
+
+    fn refresh(&mut self, _: &Refresh, window: &mut Window, cx: &mut Context<Self>) {
+        self.reload(window, cx);
+    }
+
+    fn describe(&mut self, _: &Describe, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(change) = self.selected_change() else {
+            return;
+        };
+        if change.is_root {
+            return;
+        }
+        let change_id = change.change_id.clone();
+        let description = change.description.trim_end().to_string();
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::auto_height(1, 8, window, cx);
+            editor.set_placeholder_text("Describe the change…", cx);
+            editor.set_text(description, window, cx);
+            editor
+        });
+        window.focus(&editor.focus_handle(cx));
+        self.description_editor = Some(DescriptionEditor { change_id, editor });
+        cx.notify();
+    }
+
+    fn confirm_description(
+        &mut self,
+        _: &SecondaryConfirm,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(jj_store) = self.jj_store.clone() else {
+            return;
+        };
+        let Some(DescriptionEditor { change_id, editor }) = self.description_editor.take() else {
+            return;
+        };
+        let description = editor.read(cx).text(cx);
+        jj_store
+            .update(cx, |jj_store, cx| {
+                jj_store.describe(change_id.to_string(), description, cx)
+            })
+            .detach_and_prompt_err("Failed to describe change", window, cx, |e, _, _| {
+                Some(e.to_string())
+            });
+        window.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    fn cancel_description(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.description_editor.take().is_some() {
+            window.focus(&self.focus_handle);
+            cx.notify();
+        }
+    }
+
+    fn render_header(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+        let has_selection = self.selected_change().is_some();
+        let button = move |id: &'static str,
+                           icon: IconName,
+                           label: &'static str,
+                           action: &dyn Action| {
+            let focus_handle = focus_handle.clone();
+            let action = action.boxed_clone();
+            IconButton::new(id, icon)
+                .icon_size(IconSize::Small)
+                .disabled(!has_selection)
+                .tooltip({
+                    let action = action.boxed_clone();
+                    move |window, cx| {
+                        Tooltip::for_action_in(label, action.as_ref(), &focus_handle, window, cx)
+                    }
+                })
+                .on_click(move |_, window, cx| window.dispatch_action(action.boxed_clone(), cx))
+        };
+
+        self.panel_header_container(window, cx)
+            .justify_between()
+            .child(
+                Label::new("Changes")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                h_flex()
+                    .gap_0p5()
+                    .child(button("jj-new", IconName::Plus, "New Change", &NewChange))
+                    .child(button(
+                        "jj-describe",
+                        IconName::Pencil,
+                        "Describe",
+                        &Describe,
+                    ))
+                    .child(button("jj-edit", IconName::Check, "Edit", &Edit))
+                    .child(button("jj-squash", IconName::ArrowUp, "Squash", &Squash))
+                    .child(button("jj-abandon", IconName::Trash, "Abandon", &Abandon))
+                    .child(
+                        IconButton::new("jj-refresh", IconName::RotateCw)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Refresh"))
+                            .on_click(cx.listener(|this, _, window, cx| this.reload(window, cx))),
+                    ),
+            )
+    }
+
+    fn render_change(&self, ix: usize, change: &Change, cx: &mut Context<Self>) -> AnyElement {
+        let marker = if change.is_working_copy {
+            "@"
+        } else if change.is_root {
+            "◆"
+        } else {
+            "○"
+        };
+        let summary: SharedString = if change.is_root {
+            "root()".into()
+        } else if change.summary().is_empty() {
+            "(no description set)".into()
+        } else {
+            change.summary().to_string().into()
+        };
+        let has_description = !change.summary().is_empty();
+
+        ListItem::new(ix)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(self.selected_index == Some(ix))
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_1p5()
+                    .overflow_hidden()
+                    .child(Label::new(marker).size(LabelSize::Small).color(
+                        if change.is_working_copy {
+                            Color::Accent
+                        } else {
+                            Color::Muted
+                        },
+                    ))
+                    .child(
+                        Label::new(change.short_change_id().to_string())
+                            .size(LabelSize::Small)
+                            .buffer_font(cx)
+                            .color(Color::Accent),
+                    )
+                    .children(change.bookmarks.iter().map(|bookmark| {
+                        Label::new(bookmark.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Created)
+                    }))
+                    .when(change.is_empty && !change.is_root, |this| {
+                        this.child(
+                            Label::new("(empty)")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .child(
+                        Label::new(summary)
+                            .size(LabelSize::Small)
+                            .truncate()
+                            .when(!has_description, |label| label.color(Color::Muted).italic()),
+                    ),
+            )
+            .tooltip({
+                let tooltip: SharedString = format!(
+                    "{} {}\n{} <{}>",
+                    change.short_change_id(),
+                    change.short_commit_id(),
+                    change.author_name,
+                    change.author_email
+                )
+                .into();
+                Tooltip::text(tooltip)
+            })
+            .on_click(
+                cx.listener(move |this, event: &gpui::ClickEvent, window, cx| {
+                    this.select(ix, cx);
+                    if event.down.click_count > 1 {
+                        this.edit(&Edit, window, cx);
+                    }
+                }),
+            )
+            .into_any_element()
+    }
+
+    fn render_description_editor(
+        &self,
+        description_editor: &DescriptionEditor,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        v_flex()
+            .id("jj-description-editor")
+            .key_context("JjDescriptionEditor")
+            .on_action(cx.listener(Self::confirm_description))
+            .on_action(cx.listener(Self::cancel_description))
+            .p_2()
+            .gap_1()
+            .border_t_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                Label::new(format!(
+                    "Describe {}",
+                    &description_editor.change_id[..8.min(description_editor.change_id.len())]
+                ))
+                .size(LabelSize::Small)
+                .color(Color::Muted),
+            )
+            .child(description_editor.editor.clone())
+            .child(
+                h_flex()
+                    .justify_end()
+                    .gap_1()
+                    .child(
+                        Button::new("jj-cancel-description", "Cancel")
+                            .label_size(LabelSize::Small)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.cancel_description(&Cancel, window, cx)
+                            })),
+                    )
+                    .child(
+                        Button::new("jj-save-description", "Describe")
+                            .label_size(LabelSize::Small)
+                            .style(ButtonStyle::Filled)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.confirm_description(&SecondaryConfirm, window, cx)
+                            })),
+                    ),
+            )
+    }
+}
+
+impl Render for JjPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = if self.jj_store.is_none() {
+            v_flex()
+                .size_full()
+                .justify_center()
+                .items_center()
+                .child(
+                    Label::new("No Jujutsu repository found")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any_element()
+        } else {
+            uniform_list(
+                "jj-changes",
+                self.changes.len(),
+                cx.processor(move |this, range: Range<usize>, _window, cx| {
+                    range
+                        .filter_map(|ix| {
+                            let change = this.changes.get(ix)?.clone();
+                            Some(this.render_change(ix, &change, cx))
+                        })
+                        .collect()
+                }),
+            )
+            .size_full()
+            .with_sizing_behavior(ListSizingBehavior::Auto)
+            .track_scroll(self.scroll_handle.clone())
+            .into_any_element()
+        };
+
+        v_flex()
+            .id("jj-panel")
+            .key_context("JjPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::new_change))
+            .on_action(cx.listener(Self::describe))
+            .on_action(cx.listener(Self::squash))
+            .on_action(cx.listener(Self::abandon))
+            .on_action(cx.listener(Self::edit))
+            .on_action(cx.listener(Self::refresh))
+            .size_full()
+            .bg(cx.theme().colors().panel_background)
+            .child(self.render_header(window, cx))
+            .child(v_flex().flex_1().overflow_hidden().child(content))
+            .when_some(
+                self.description_editor.as_ref(),
+                |this, description_editor| {
+                    this.child(self.render_description_editor(description_editor, cx))
+                },
+            )
+    }
+}
+
+impl Focusable for JjPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for JjPanel {}
+
+impl Panel for JjPanel {
+    fn persistent_name() -> &'static str {
+        "JjPanel"
+    }
+
+    fn position(&self, _: &Window, _: &App) -> DockPosition {
+        self.position
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        self.position = position;
+        cx.notify();
+    }
+
+    fn size(&self, _: &Window, _: &App) -> Pixels {
+        self.width.unwrap_or(px(360.))
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        cx.notify();
+    }
+
+    fn set_active(&mut self, active: bool, window: &mut Window, cx: &mut Context<Self>) {
+        if active {
+            self.reload(window, cx);
+        }
+    }
+
+    fn icon(&self, _: &Window, _: &App) -> Option<IconName> {
+        Some(IconName::GitBranchSmall)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Jujutsu Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        4
+    }
+
+    fn enabled(&self, cx: &App) -> bool {
+        self.jj_store.is_some() && cx.has_flag::<JjUiFeatureFlag>()
+    }
+}
+
+impl PanelHeader for JjPanel {}
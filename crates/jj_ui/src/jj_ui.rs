@@ -1,4 +1,5 @@
 mod bookmark_picker;
+pub mod jj_panel;
 
 use command_palette_hooks::CommandPaletteFilter;
 use feature_flags::FeatureFlagAppExt as _;
@@ -11,6 +12,7 @@ pub fn init(cx: &mut App) {
 
     cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
         bookmark_picker::register(workspace);
+        jj_panel::register(workspace);
     })
     .detach();
 
@@ -126,6 +126,8 @@ pub struct LanguageSettings {
     pub extend_comment_on_newline: bool,
     /// Inlay hint related settings.
     pub inlay_hints: InlayHintSettings,
+    /// LSP semantic tokens related settings.
+    pub semantic_tokens: SemanticTokensSettings,
     /// Whether to automatically close brackets.
     pub use_autoclose: bool,
     /// Whether to automatically surround text with brackets.
@@ -141,6 +143,8 @@ pub struct LanguageSettings {
     pub code_actions_on_format: HashMap<String, bool>,
     /// Whether to perform linked edits
     pub linked_edits: bool,
+    /// Whether to expand and shrink selections with the language server's selection ranges.
+    pub lsp_selection_ranges: bool,
     /// Task configuration for this language.
     pub tasks: LanguageTaskConfig,
     /// Whether to pop the completions menu while typing in an editor without
@@ -528,6 +532,9 @@ pub struct LanguageSettingsContent {
     /// Inlay hint related settings.
     #[serde(default)]
     pub inlay_hints: Option<InlayHintSettings>,
+    /// LSP semantic tokens related settings.
+    #[serde(default)]
+    pub semantic_tokens: Option<SemanticTokensSettingsContent>,
     /// Whether to automatically type closing characters for you. For example,
     /// when you type (, Zed will automatically add a closing ) at the correct position.
     ///
@@ -561,6 +568,11 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: true
     pub linked_edits: Option<bool>,
+    /// Whether to expand and shrink selections with the selection ranges of the language server,
+    /// falling back to the syntax tree if the server does not provide them.
+    ///
+    /// Default: false
+    pub lsp_selection_ranges: Option<bool>,
     /// Whether indentation of pasted content should be adjusted based on the context.
     ///
     /// Default: true
@@ -979,6 +991,49 @@ pub enum IndentGuideBackgroundColoring {
     IndentAware,
 }
 
+/// The settings for LSP semantic tokens.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+pub struct SemanticTokensSettings {
+    /// Whether to highlight the buffer with semantic tokens from language servers.
+    pub enabled: bool,
+    /// Syntax theme styles to apply to semantic tokens, keyed by token type,
+    /// or by token type and modifier separated with a dot.
+    pub styles: HashMap<String, String>,
+}
+
+impl SemanticTokensSettings {
+    /// Returns the syntax theme style for a token with the given type and modifiers.
+    ///
+    /// Styles for a modifier of the token type take precedence over the style for the type itself.
+    pub fn style_for_token<'a>(
+        &self,
+        token_type: &str,
+        token_modifiers: impl IntoIterator<Item = &'a str>,
+    ) -> Option<&str> {
+        token_modifiers
+            .into_iter()
+            .find_map(|modifier| self.styles.get(&format!("{token_type}.{modifier}")))
+            .or_else(|| self.styles.get(token_type))
+            .map(String::as_str)
+            .filter(|style| !style.is_empty())
+    }
+}
+
+/// The settings for LSP semantic tokens.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct SemanticTokensSettingsContent {
+    /// Whether to highlight the buffer with semantic tokens from language servers.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// Syntax theme styles to apply to semantic tokens, keyed by token type,
+    /// or by token type and modifier separated with a dot, e.g. `"variable.readonly"`.
+    /// These are merged into the default styles, an empty style disables highlighting
+    /// of the token.
+    #[serde(default)]
+    pub styles: HashMap<String, String>,
+}
+
 /// The settings for inlay hints.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct InlayHintSettings {
@@ -1530,6 +1585,7 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         src.code_actions_on_format.clone(),
     );
     merge(&mut settings.linked_edits, src.linked_edits);
+    merge(&mut settings.lsp_selection_ranges, src.lsp_selection_ranges);
     merge(&mut settings.tasks, src.tasks.clone());
 
     merge(
@@ -1571,6 +1627,18 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         src.extend_comment_on_newline,
     );
     merge(&mut settings.inlay_hints, src.inlay_hints);
+    if let Some(semantic_tokens) = &src.semantic_tokens {
+        merge(
+            &mut settings.semantic_tokens.enabled,
+            semantic_tokens.enabled,
+        );
+        settings.semantic_tokens.styles.extend(
+            semantic_tokens
+                .styles
+                .iter()
+                .map(|(token, style)| (token.clone(), style.clone())),
+        );
+    }
     merge(
         &mut settings.show_completions_on_input,
         src.show_completions_on_input,
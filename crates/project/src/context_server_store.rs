@@ -5,16 +5,25 @@ use std::{path::Path, sync::Arc};
 
 use anyhow::{Context as _, Result};
 use collections::{HashMap, HashSet};
-use context_server::{ContextServer, ContextServerCommand, ContextServerId};
+use context_server::types::{
+    CreateMessageRequest, CreateMessageResult, ListRootsResponse, ResourcesSubscribeParams,
+    ResourcesUnsubscribeParams, Root,
+    notifications::{ResourcesUpdated, RootsListChanged},
+    requests,
+};
+use context_server::{ContextServer, ContextServerCommand, ContextServerEndpoint, ContextServerId};
+use credentials_provider::CredentialsProvider;
 use futures::{FutureExt as _, future::join_all};
 use gpui::{App, AsyncApp, Context, Entity, EventEmitter, Subscription, Task, WeakEntity, actions};
 use registry::ContextServerDescriptorRegistry;
 use settings::{Settings as _, SettingsStore};
+use smol::channel::Sender;
+use url::Url;
 use util::ResultExt as _;
 
 use crate::{
     project_settings::{ContextServerSettings, ProjectSettings},
-    worktree_store::WorktreeStore,
+    worktree_store::{WorktreeStore, WorktreeStoreEvent},
 };
 
 pub fn init(cx: &mut App) {
@@ -98,13 +107,17 @@ pub enum ContextServerConfiguration {
         command: ContextServerCommand,
         settings: serde_json::Value,
     },
+    Remote {
+        endpoint: ContextServerEndpoint,
+    },
 }
 
 impl ContextServerConfiguration {
-    pub fn command(&self) -> &ContextServerCommand {
+    pub fn command(&self) -> Option<&ContextServerCommand> {
         match self {
-            ContextServerConfiguration::Custom { command } => command,
-            ContextServerConfiguration::Extension { command, .. } => command,
+            ContextServerConfiguration::Custom { command } => Some(command),
+            ContextServerConfiguration::Extension { command, .. } => Some(command),
+            ContextServerConfiguration::Remote { .. } => None,
         }
     }
 
@@ -133,6 +146,25 @@ impl ContextServerConfiguration {
 
                 Some(ContextServerConfiguration::Extension { command, settings })
             }
+            ContextServerSettings::Remote {
+                enabled: _,
+                mut endpoint,
+            } => {
+                let credentials_provider =
+                    cx.update(|cx| <dyn CredentialsProvider>::global(cx)).ok()?;
+                if let Some((header, value)) = credentials_provider
+                    .read_credentials(&endpoint.url, cx)
+                    .await
+                    .log_err()
+                    .flatten()
+                {
+                    if let Some(value) = String::from_utf8(value).log_err() {
+                        endpoint.headers.entry(header).or_insert(value);
+                    }
+                }
+
+                Some(ContextServerConfiguration::Remote { endpoint })
+            }
         }
     }
 }
@@ -148,6 +180,7 @@ pub struct ContextServerStore {
     update_servers_task: Option<Task<Result<()>>>,
     context_server_factory: Option<ContextServerFactory>,
     needs_server_update: bool,
+    resource_subscriptions: HashMap<(ContextServerId, String), usize>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -156,10 +189,30 @@ pub enum Event {
         server_id: ContextServerId,
         status: ContextServerStatus,
     },
+    ResourceUpdated {
+        server_id: ContextServerId,
+        uri: String,
+    },
+    SamplingRequested(SamplingRequest),
 }
 
 impl EventEmitter<Event> for ContextServerStore {}
 
+/// A request of a context server to generate a message with one of the user's language models,
+/// which has to be approved by the user.
+#[derive(Clone, Debug)]
+pub struct SamplingRequest {
+    pub server_id: ContextServerId,
+    pub request: Arc<CreateMessageRequest>,
+    response_channel: Sender<Result<CreateMessageResult>>,
+}
+
+impl SamplingRequest {
+    pub async fn respond(self, result: Result<CreateMessageResult>) -> Option<()> {
+        self.response_channel.send(result).await.ok()
+    }
+}
+
 impl ContextServerStore {
     pub fn new(worktree_store: Entity<WorktreeStore>, cx: &mut Context<Self>) -> Self {
         Self::new_internal(
@@ -212,7 +265,7 @@ impl ContextServerStore {
         worktree_store: Entity<WorktreeStore>,
         cx: &mut Context<Self>,
     ) -> Self {
-        let subscriptions = if maintain_server_loop {
+        let mut subscriptions = if maintain_server_loop {
             vec![
                 cx.observe(&registry, |this, _registry, cx| {
                     this.available_context_servers_changed(cx);
@@ -229,6 +282,14 @@ impl ContextServerStore {
         } else {
             Vec::new()
         };
+        subscriptions.push(
+            cx.subscribe(&worktree_store, |this, _, event, _| match event {
+                WorktreeStoreEvent::WorktreeAdded(_) | WorktreeStoreEvent::WorktreeRemoved(..) => {
+                    this.roots_changed()
+                }
+                _ => {}
+            }),
+        );
 
         let mut this = Self {
             _subscriptions: subscriptions,
@@ -240,6 +301,7 @@ impl ContextServerStore {
             servers: HashMap::default(),
             update_servers_task: None,
             context_server_factory,
+            resource_subscriptions: HashMap::default(),
         };
         if maintain_server_loop {
             this.available_context_servers_changed(cx);
@@ -360,7 +422,7 @@ impl ContextServerStore {
             let configuration = state.configuration();
 
             self.stop_server(&state.server().id(), cx)?;
-            let new_server = self.create_context_server(id.clone(), configuration.clone())?;
+            let new_server = self.create_context_server(id.clone(), configuration.clone(), cx)?;
             self.run_server(new_server, configuration, cx);
         }
         Ok(())
@@ -379,6 +441,7 @@ impl ContextServerStore {
         ) {
             self.stop_server(&id, cx).log_err();
         }
+        self.register_request_handlers(&server, cx);
 
         let task = cx.spawn({
             let id = server.id();
@@ -394,11 +457,12 @@ impl ContextServerStore {
                             this.update_server_state(
                                 id.clone(),
                                 ContextServerState::Running {
-                                    server,
+                                    server: server.clone(),
                                     configuration,
                                 },
                                 cx,
-                            )
+                            );
+                            this.server_started(&server, cx);
                         })
                         .log_err()
                     }
@@ -432,6 +496,182 @@ impl ContextServerStore {
         );
     }
 
+    /// Answers the requests context servers send to us, which also determines the client
+    /// capabilities that are advertised to them.
+    fn register_request_handlers(&self, server: &Arc<ContextServer>, cx: &mut Context<Self>) {
+        let worktree_store = self.worktree_store.downgrade();
+        server.handle_request::<requests::ListRoots>(move |(), cx| {
+            Task::ready(
+                worktree_store.read_with(&cx, |worktree_store, cx| Self::roots(worktree_store, cx)),
+            )
+        });
+
+        let server_id = server.id();
+        let this = cx.weak_entity();
+        server.handle_request::<requests::CreateMessage>(move |request, mut cx| {
+            let (tx, rx) = smol::channel::bounded(1);
+            let request = SamplingRequest {
+                server_id: server_id.clone(),
+                request: Arc::new(request),
+                response_channel: tx,
+            };
+            if let Err(error) = this.update(&mut cx, |_, cx| {
+                cx.emit(Event::SamplingRequested(request));
+            }) {
+                return Task::ready(Err(error));
+            }
+            // When nobody listens for the request, it is dropped and thereby rejected.
+            cx.background_spawn(async move {
+                rx.recv()
+                    .await
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("sampling is not available")))
+            })
+        });
+    }
+
+    fn roots(worktree_store: &WorktreeStore, cx: &App) -> ListRootsResponse {
+        let roots = worktree_store
+            .visible_worktrees(cx)
+            .filter_map(|worktree| {
+                let worktree = worktree.read(cx);
+                let uri = Url::from_file_path(worktree.abs_path()).ok()?;
+                Some(Root {
+                    uri,
+                    name: Some(worktree.root_name().to_string()),
+                })
+            })
+            .collect();
+        ListRootsResponse { roots, meta: None }
+    }
+
+    fn roots_changed(&self) {
+        for server in self.running_servers() {
+            if let Some(protocol) = server.client() {
+                protocol.notify::<RootsListChanged>(()).log_err();
+            }
+        }
+    }
+
+    fn server_started(&mut self, server: &Arc<ContextServer>, cx: &mut Context<Self>) {
+        let Some(protocol) = server.client() else {
+            return;
+        };
+
+        let server_id = server.id();
+        let this = cx.weak_entity();
+        protocol.on_notification::<ResourcesUpdated>(move |params, mut cx| {
+            this.update(&mut cx, |_, cx| {
+                cx.emit(Event::ResourceUpdated {
+                    server_id: server_id.clone(),
+                    uri: params.uri,
+                })
+            })
+            .ok();
+        });
+
+        // Subscriptions don't outlive the server process, so they are renewed after a restart.
+        let server_id = server.id();
+        let uris = self
+            .resource_subscriptions
+            .keys()
+            .filter(|(id, _)| *id == server_id)
+            .map(|(_, uri)| uri.clone())
+            .collect::<Vec<_>>();
+        for uri in uris {
+            self.send_resource_subscription(&server_id, &uri, true, cx);
+        }
+    }
+
+    /// Subscribes to updates of a resource of the given server, which are reported with
+    /// [`Event::ResourceUpdated`]. Every call has to be balanced with a call to
+    /// [`Self::unsubscribe_from_resource`].
+    pub fn subscribe_to_resource(
+        &mut self,
+        server_id: &ContextServerId,
+        uri: &str,
+        cx: &mut Context<Self>,
+    ) {
+        let subscriber_count = self
+            .resource_subscriptions
+            .entry((server_id.clone(), uri.to_string()))
+            .or_default();
+        *subscriber_count += 1;
+        if *subscriber_count == 1 {
+            self.send_resource_subscription(server_id, uri, true, cx);
+        }
+    }
+
+    pub fn unsubscribe_from_resource(
+        &mut self,
+        server_id: &ContextServerId,
+        uri: &str,
+        cx: &mut Context<Self>,
+    ) {
+        let key = (server_id.clone(), uri.to_string());
+        let Some(subscriber_count) = self.resource_subscriptions.get_mut(&key) else {
+            return;
+        };
+        *subscriber_count -= 1;
+        if *subscriber_count == 0 {
+            self.resource_subscriptions.remove(&key);
+            self.send_resource_subscription(server_id, uri, false, cx);
+        }
+    }
+
+    /// The number of subscribers to updates of the given resource.
+    pub fn resource_subscriber_count(&self, server_id: &ContextServerId, uri: &str) -> usize {
+        self.resource_subscriptions
+            .get(&(server_id.clone(), uri.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn send_resource_subscription(
+        &self,
+        server_id: &ContextServerId,
+        uri: &str,
+        subscribe: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(protocol) = self
+            .get_running_server(server_id)
+            .and_then(|server| server.client())
+        else {
+            return;
+        };
+        let supports_subscriptions = protocol
+            .initialize
+            .capabilities
+            .resources
+            .as_ref()
+            .and_then(|resources| resources.subscribe)
+            .unwrap_or(false);
+        if !supports_subscriptions {
+            return;
+        }
+
+        let uri = uri.to_string();
+        cx.spawn(async move |_, _| {
+            let uri = Url::parse(&uri)?;
+            if subscribe {
+                protocol
+                    .request::<requests::ResourcesSubscribe>(ResourcesSubscribeParams {
+                        uri,
+                        meta: None,
+                    })
+                    .await
+            } else {
+                protocol
+                    .request::<requests::ResourcesUnsubscribe>(ResourcesUnsubscribeParams {
+                        uri,
+                        meta: None,
+                    })
+                    .await
+            }
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn remove_server(&mut self, id: &ContextServerId, cx: &mut Context<Self>) -> Result<()> {
         let state = self
             .servers
@@ -449,14 +689,22 @@ impl ContextServerStore {
         &self,
         id: ContextServerId,
         configuration: Arc<ContextServerConfiguration>,
+        cx: &App,
     ) -> Result<Arc<ContextServer>> {
         if let Some(factory) = self.context_server_factory.as_ref() {
-            Ok(factory(id, configuration))
-        } else {
-            Ok(Arc::new(ContextServer::stdio(
+            return Ok(factory(id, configuration));
+        }
+
+        match configuration.as_ref() {
+            ContextServerConfiguration::Custom { command }
+            | ContextServerConfiguration::Extension { command, .. } => {
+                Ok(Arc::new(ContextServer::stdio(id, command.clone())))
+            }
+            ContextServerConfiguration::Remote { endpoint } => Ok(Arc::new(ContextServer::http(
                 id,
-                configuration.command().clone(),
-            )))
+                endpoint.clone(),
+                cx.http_client(),
+            ))),
         }
     }
 
@@ -553,7 +801,7 @@ impl ContextServerStore {
         let mut servers_to_remove = HashSet::default();
         let mut servers_to_stop = HashSet::default();
 
-        this.update(cx, |this, _cx| {
+        this.update(cx, |this, cx| {
             for server_id in this.servers.keys() {
                 // All servers that are not in desired_servers should be removed from the store.
                 // This can happen if the user removed a server from the context server settings.
@@ -573,7 +821,7 @@ impl ContextServerStore {
                 if existing_config.as_deref() != Some(&config) || is_stopped {
                     let config = Arc::new(config);
                     if let Some(server) = this
-                        .create_context_server(id.clone(), config.clone())
+                        .create_context_server(id.clone(), config.clone(), cx)
                         .log_err()
                     {
                         servers_to_start.push((server, config));
@@ -607,10 +855,16 @@ mod tests {
         FakeFs, Project, context_server_store::registry::ContextServerDescriptor,
         project_settings::ProjectSettings,
     };
-    use context_server::test::create_fake_transport;
+    use context_server::{
+        HttpTransportKind,
+        test::{
+            FakeTransport, create_fake_http_server, create_fake_transport,
+            create_initialize_response,
+        },
+    };
     use gpui::{AppContext, TestAppContext, UpdateGlobal as _};
     use serde_json::json;
-    use std::{cell::RefCell, rc::Rc};
+    use std::{cell::RefCell, path::PathBuf, rc::Rc};
     use util::path;
 
     #[gpui::test]
@@ -1145,6 +1399,160 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    async fn test_remote_context_servers(cx: &mut TestAppContext) {
+        const SERVER_1_ID: &'static str = "mcp-1";
+        const SERVER_2_ID: &'static str = "mcp-2";
+
+        let server_1_id = ContextServerId(SERVER_1_ID.into());
+        let server_2_id = ContextServerId(SERVER_2_ID.into());
+
+        let (_fs, project) = setup_context_server_test(
+            cx,
+            json!({"code.rs": ""}),
+            vec![
+                (
+                    SERVER_1_ID.into(),
+                    ContextServerSettings::Remote {
+                        enabled: true,
+                        endpoint: ContextServerEndpoint {
+                            url: "https://mcp.example.com/mcp".to_string(),
+                            headers: HashMap::from_iter([(
+                                "Authorization".to_string(),
+                                "Bearer token".to_string(),
+                            )]),
+                            transport: HttpTransportKind::StreamableHttp,
+                        },
+                    },
+                ),
+                (
+                    SERVER_2_ID.into(),
+                    ContextServerSettings::Remote {
+                        enabled: true,
+                        endpoint: ContextServerEndpoint {
+                            url: "https://mcp.example.com/sse".to_string(),
+                            headers: HashMap::default(),
+                            transport: HttpTransportKind::Sse,
+                        },
+                    },
+                ),
+            ],
+        )
+        .await;
+
+        let registry = cx.new(|_| ContextServerDescriptorRegistry::new());
+        let store = cx.new(|cx| {
+            ContextServerStore::test_maintain_server_loop(
+                Box::new(move |id, configuration| {
+                    let ContextServerConfiguration::Remote { endpoint } = configuration.as_ref()
+                    else {
+                        panic!("expected a remote context server");
+                    };
+                    Arc::new(ContextServer::http(
+                        id.clone(),
+                        endpoint.clone(),
+                        create_fake_http_server(id.0.to_string(), endpoint.transport),
+                    ))
+                }),
+                registry.clone(),
+                project.read(cx).worktree_store(),
+                cx,
+            )
+        });
+
+        cx.run_until_parked();
+
+        store.read_with(cx, |store, _| {
+            for id in [&server_1_id, &server_2_id] {
+                assert_eq!(
+                    store.status_for_server(id),
+                    Some(ContextServerStatus::Running)
+                );
+                let client = store.get_running_server(id).unwrap().client().unwrap();
+                assert_eq!(client.initialize.server_info.name, id.0.as_ref());
+            }
+        });
+    }
+
+    #[gpui::test]
+    async fn test_context_server_roots(cx: &mut TestAppContext) {
+        const SERVER_1_ID: &'static str = "mcp-1";
+
+        let (_fs, project) = setup_context_server_test(
+            cx,
+            json!({"code.rs": ""}),
+            vec![(SERVER_1_ID.into(), dummy_server_settings())],
+        )
+        .await;
+
+        let registry = cx.new(|_| ContextServerDescriptorRegistry::new());
+        let store = cx.new(|cx| {
+            ContextServerStore::test(registry.clone(), project.read(cx).worktree_store(), cx)
+        });
+
+        let client_capabilities = Arc::new(parking_lot::Mutex::new(None));
+        let transport = Arc::new(
+            FakeTransport::new(cx.executor()).on_request::<requests::Initialize>({
+                let client_capabilities = client_capabilities.clone();
+                move |params| {
+                    *client_capabilities.lock() = Some(params.capabilities);
+                    create_initialize_response(SERVER_1_ID.to_string())
+                }
+            }),
+        );
+        let server_1_id = ContextServerId(SERVER_1_ID.into());
+        let server_1 = Arc::new(ContextServer::new(server_1_id.clone(), transport.clone()));
+
+        store.update(cx, |store, cx| store.start_server(server_1, cx));
+        cx.run_until_parked();
+
+        let client_capabilities = client_capabilities.lock().take().unwrap();
+        assert!(client_capabilities.sampling.is_some());
+        assert_eq!(
+            client_capabilities
+                .roots
+                .and_then(|roots| roots.list_changed),
+            Some(true)
+        );
+
+        let roots = cx
+            .executor()
+            .spawn({
+                let transport = transport.clone();
+                async move { transport.request_client::<requests::ListRoots>(()).await }
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            roots
+                .roots
+                .iter()
+                .map(|root| (root.uri.to_file_path().unwrap(), root.name.clone()))
+                .collect::<Vec<_>>(),
+            vec![(PathBuf::from(path!("/test")), Some("test".to_string()))]
+        );
+
+        // Without anyone to approve it, a sampling request is rejected.
+        let result = cx
+            .executor()
+            .spawn(async move {
+                transport
+                    .request_client::<requests::CreateMessage>(CreateMessageRequest {
+                        messages: Vec::new(),
+                        model_preferences: None,
+                        system_prompt: None,
+                        include_context: None,
+                        temperature: None,
+                        max_tokens: 100,
+                        stop_sequences: None,
+                        metadata: None,
+                    })
+                    .await
+            })
+            .await;
+        assert!(result.is_err());
+    }
+
     fn set_context_server_configuration(
         context_servers: Vec<(Arc<str>, ContextServerSettings)>,
         cx: &mut TestAppContext,
@@ -1222,6 +1630,7 @@ mod tests {
                         ix += 1;
                         *received_event_count.borrow_mut() += 1;
                     }
+                    Event::ResourceUpdated { .. } | Event::SamplingRequested(_) => {}
                 }
             });
             ServerEvents {
@@ -19,7 +19,7 @@ use util::maybe;
 
 use crate::{Project, ProjectPath, buffer_store::BufferStore, worktree_store::WorktreeStore};
 
-use super::session::ThreadId;
+use super::session::{ExceptionInfo, ThreadId};
 
 mod breakpoints_in_file {
     use collections::HashMap;
@@ -152,10 +152,14 @@ pub struct ActiveStackFrame {
     pub stack_frame_id: StackFrameId,
     pub path: Arc<Path>,
     pub position: text::Anchor,
+    /// The exception raised in this stack frame, if the thread stopped on one.
+    pub exception: Option<ExceptionInfo>,
 }
 
 pub struct BreakpointStore {
     breakpoints: BTreeMap<Arc<Path>, BreakpointsInFile>,
+    function_breakpoints: Vec<FunctionBreakpoint>,
+    data_breakpoints: Vec<DataBreakpoint>,
     downstream_client: Option<(AnyProtoClient, u64)>,
     active_stack_frame: Option<ActiveStackFrame>,
     // E.g ssh
@@ -170,6 +174,8 @@ impl BreakpointStore {
     pub fn local(worktree_store: Entity<WorktreeStore>, buffer_store: Entity<BufferStore>) -> Self {
         BreakpointStore {
             breakpoints: BTreeMap::new(),
+            function_breakpoints: Vec::new(),
+            data_breakpoints: Vec::new(),
             mode: BreakpointStoreMode::Local(LocalBreakpointStore {
                 worktree_store,
                 buffer_store,
@@ -182,6 +188,8 @@ impl BreakpointStore {
     pub(crate) fn remote(upstream_project_id: u64, upstream_client: AnyProtoClient) -> Self {
         BreakpointStore {
             breakpoints: BTreeMap::new(),
+            function_breakpoints: Vec::new(),
+            data_breakpoints: Vec::new(),
             mode: BreakpointStoreMode::Remote(RemoteBreakpointStore {
                 upstream_client,
                 _upstream_project_id: upstream_project_id,
@@ -601,6 +609,161 @@ impl BreakpointStore {
         let breakpoint_paths = self.breakpoints.keys().cloned().collect();
         self.breakpoints.clear();
         cx.emit(BreakpointStoreEvent::BreakpointsCleared(breakpoint_paths));
+
+        if !self.function_breakpoints.is_empty() {
+            self.function_breakpoints.clear();
+            cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+        }
+        if !self.data_breakpoints.is_empty() {
+            self.data_breakpoints.clear();
+            cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+        }
+    }
+
+    pub fn function_breakpoints(&self) -> &[FunctionBreakpoint] {
+        &self.function_breakpoints
+    }
+
+    pub fn add_function_breakpoint(&mut self, name: Arc<str>, cx: &mut Context<Self>) {
+        if name.is_empty()
+            || self
+                .function_breakpoints
+                .iter()
+                .any(|breakpoint| breakpoint.name == name)
+        {
+            return;
+        }
+
+        self.function_breakpoints
+            .push(FunctionBreakpoint::new(name));
+        cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+        cx.notify();
+    }
+
+    /// Edits the function breakpoint with the given name. `BreakpointEditAction::Toggle` removes it,
+    /// and log messages are ignored as function breakpoints can't have them.
+    pub fn edit_function_breakpoint(
+        &mut self,
+        name: &str,
+        edit_action: BreakpointEditAction,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self
+            .function_breakpoints
+            .iter()
+            .position(|breakpoint| breakpoint.name.as_ref() == name)
+        else {
+            return;
+        };
+
+        let breakpoint = &mut self.function_breakpoints[ix];
+        match edit_action {
+            BreakpointEditAction::Toggle => {
+                self.function_breakpoints.remove(ix);
+            }
+            BreakpointEditAction::InvertState => {
+                breakpoint.state = breakpoint.state.inverted();
+            }
+            BreakpointEditAction::EditLogMessage(_) => return,
+            BreakpointEditAction::EditCondition(condition) => {
+                breakpoint.condition = (!condition.is_empty()).then_some(condition);
+            }
+            BreakpointEditAction::EditHitCondition(hit_condition) => {
+                breakpoint.hit_condition = (!hit_condition.is_empty()).then_some(hit_condition);
+            }
+        }
+
+        cx.emit(BreakpointStoreEvent::FunctionBreakpointsUpdated);
+        cx.notify();
+    }
+
+    pub fn data_breakpoints(&self) -> &[DataBreakpoint] {
+        &self.data_breakpoints
+    }
+
+    pub fn add_data_breakpoint(&mut self, breakpoint: DataBreakpoint, cx: &mut Context<Self>) {
+        if let Some(existing) = self
+            .data_breakpoints
+            .iter_mut()
+            .find(|existing| existing.data_id == breakpoint.data_id)
+        {
+            *existing = breakpoint;
+        } else {
+            self.data_breakpoints.push(breakpoint);
+        }
+
+        cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+        cx.notify();
+    }
+
+    /// Edits the data breakpoint with the given data ID, the same way as [`Self::edit_function_breakpoint`].
+    pub fn edit_data_breakpoint(
+        &mut self,
+        data_id: &str,
+        edit_action: BreakpointEditAction,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self
+            .data_breakpoints
+            .iter()
+            .position(|breakpoint| breakpoint.data_id.as_ref() == data_id)
+        else {
+            return;
+        };
+
+        let breakpoint = &mut self.data_breakpoints[ix];
+        match edit_action {
+            BreakpointEditAction::Toggle => {
+                self.data_breakpoints.remove(ix);
+            }
+            BreakpointEditAction::InvertState => {
+                breakpoint.state = breakpoint.state.inverted();
+            }
+            BreakpointEditAction::EditLogMessage(_) => return,
+            BreakpointEditAction::EditCondition(condition) => {
+                breakpoint.condition = (!condition.is_empty()).then_some(condition);
+            }
+            BreakpointEditAction::EditHitCondition(hit_condition) => {
+                breakpoint.hit_condition = (!hit_condition.is_empty()).then_some(hit_condition);
+            }
+        }
+
+        cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+        cx.notify();
+    }
+
+    /// Data breakpoints are only worth keeping around after the debug sessions
+    /// they were created in when the debug adapter said their data IDs persist.
+    pub fn persistent_data_breakpoints(&self) -> impl Iterator<Item = &DataBreakpoint> {
+        self.data_breakpoints
+            .iter()
+            .filter(|breakpoint| breakpoint.can_persist)
+    }
+
+    /// The data breakpoints whose data IDs are valid in the given session.
+    pub fn data_breakpoints_for_session(
+        &self,
+        session_id: SessionId,
+    ) -> impl Iterator<Item = &DataBreakpoint> {
+        self.data_breakpoints.iter().filter(move |breakpoint| {
+            breakpoint.can_persist || breakpoint.session_id == Some(session_id)
+        })
+    }
+
+    /// Removes the data breakpoints whose data IDs stopped being valid when the given session ended.
+    pub fn remove_session_data_breakpoints(
+        &mut self,
+        session_id: SessionId,
+        cx: &mut Context<Self>,
+    ) {
+        let len = self.data_breakpoints.len();
+        self.data_breakpoints.retain(|breakpoint| {
+            breakpoint.can_persist || breakpoint.session_id != Some(session_id)
+        });
+        if self.data_breakpoints.len() != len {
+            cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+            cx.notify();
+        }
     }
 
     pub fn breakpoints<'a>(
@@ -856,6 +1019,19 @@ impl BreakpointStore {
         }
     }
 
+    pub fn with_serialized_function_and_data_breakpoints(
+        &mut self,
+        function_breakpoints: Vec<FunctionBreakpoint>,
+        data_breakpoints: Vec<DataBreakpoint>,
+        cx: &mut Context<BreakpointStore>,
+    ) {
+        if let BreakpointStoreMode::Local(_) = &self.mode {
+            self.function_breakpoints = function_breakpoints;
+            self.data_breakpoints = data_breakpoints;
+            cx.notify();
+        }
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) fn breakpoint_paths(&self) -> Vec<Arc<Path>> {
         self.breakpoints.keys().cloned().collect()
@@ -873,6 +1049,8 @@ pub enum BreakpointStoreEvent {
     ClearDebugLines,
     BreakpointsUpdated(Arc<Path>, BreakpointUpdatedReason),
     BreakpointsCleared(Vec<Arc<Path>>),
+    FunctionBreakpointsUpdated,
+    DataBreakpointsUpdated,
 }
 
 impl EventEmitter<BreakpointStoreEvent> for BreakpointStore {}
@@ -912,6 +1090,14 @@ impl BreakpointState {
             BreakpointState::Disabled => 1,
         }
     }
+
+    #[inline]
+    pub fn inverted(&self) -> Self {
+        match self {
+            BreakpointState::Enabled => BreakpointState::Disabled,
+            BreakpointState::Disabled => BreakpointState::Enabled,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -1034,3 +1220,69 @@ impl From<SourceBreakpoint> for dap::SourceBreakpoint {
         }
     }
 }
+
+/// Breakpoint that stops whenever a function with the given name is entered.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FunctionBreakpoint {
+    pub name: Arc<str>,
+    pub condition: Option<Arc<str>>,
+    pub hit_condition: Option<Arc<str>>,
+    pub state: BreakpointState,
+}
+
+impl FunctionBreakpoint {
+    pub fn new(name: Arc<str>) -> Self {
+        Self {
+            name,
+            condition: None,
+            hit_condition: None,
+            state: BreakpointState::Enabled,
+        }
+    }
+}
+
+impl From<FunctionBreakpoint> for dap::FunctionBreakpoint {
+    fn from(bp: FunctionBreakpoint) -> Self {
+        Self {
+            name: String::from(bp.name.as_ref()),
+            condition: bp
+                .condition
+                .map(|condition| String::from(condition.as_ref())),
+            hit_condition: bp
+                .hit_condition
+                .map(|hit_condition| String::from(hit_condition.as_ref())),
+        }
+    }
+}
+
+/// Breakpoint (also known as a watchpoint) that stops whenever a piece of data is accessed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataBreakpoint {
+    /// Identifier of the data, as returned by the debug adapter's `dataBreakpointInfo` response.
+    pub data_id: Arc<str>,
+    /// User-facing description of the data, e.g. the name of the variable.
+    pub description: Arc<str>,
+    pub access_type: Option<dap::DataBreakpointAccessType>,
+    /// Whether `data_id` stays valid across debug sessions.
+    pub can_persist: bool,
+    /// The debug session that `data_id` was created in, if it's still running.
+    pub session_id: Option<SessionId>,
+    pub condition: Option<Arc<str>>,
+    pub hit_condition: Option<Arc<str>>,
+    pub state: BreakpointState,
+}
+
+impl From<DataBreakpoint> for dap::DataBreakpoint {
+    fn from(bp: DataBreakpoint) -> Self {
+        Self {
+            data_id: String::from(bp.data_id.as_ref()),
+            access_type: bp.access_type,
+            condition: bp
+                .condition
+                .map(|condition| String::from(condition.as_ref())),
+            hit_condition: bp
+                .hit_condition
+                .map(|hit_condition| String::from(hit_condition.as_ref())),
+        }
+    }
+}
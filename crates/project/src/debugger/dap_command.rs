@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use anyhow::{Context as _, Ok, Result};
+use base64::prelude::*;
 use dap::{
     Capabilities, ContinueArguments, ExceptionFilterOptions, InitializeRequestArguments,
     InitializeRequestArgumentsPathFormat, NextArguments, SetVariableResponse, SourceBreakpoint,
@@ -14,6 +15,8 @@ use rpc::proto;
 use serde_json::Value;
 use util::ResultExt;
 
+use super::session::MemoryContents;
+
 pub trait LocalDapCommand: 'static + Send + Sync + std::fmt::Debug {
     type Response: 'static + Send + std::fmt::Debug;
     type DapRequest: 'static + Send + dap::requests::Request;
@@ -1705,6 +1708,125 @@ impl LocalDapCommand for SetExceptionBreakpoints {
     }
 }
 
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub(super) struct SetFunctionBreakpoints {
+    pub(super) breakpoints: Vec<dap::FunctionBreakpoint>,
+}
+
+impl LocalDapCommand for SetFunctionBreakpoints {
+    type Response = Vec<dap::Breakpoint>;
+    type DapRequest = dap::requests::SetFunctionBreakpoints;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_function_breakpoints
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::SetFunctionBreakpointsArguments {
+            breakpoints: self.breakpoints.clone(),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.breakpoints)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub(super) struct SetDataBreakpoints {
+    pub(super) breakpoints: Vec<dap::DataBreakpoint>,
+}
+
+impl LocalDapCommand for SetDataBreakpoints {
+    type Response = Vec<dap::Breakpoint>;
+    type DapRequest = dap::requests::SetDataBreakpoints;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities.supports_data_breakpoints.unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::SetDataBreakpointsArguments {
+            breakpoints: self.breakpoints.clone(),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.breakpoints)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(super) struct DataBreakpointInfoCommand {
+    /// Reference to the container of the variable `name`.
+    pub(super) variables_reference: u64,
+    pub(super) name: String,
+}
+
+impl LocalDapCommand for DataBreakpointInfoCommand {
+    type Response = dap::DataBreakpointInfoResponse;
+    type DapRequest = dap::requests::DataBreakpointInfo;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities.supports_data_breakpoints.unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::DataBreakpointInfoArguments {
+            variables_reference: Some(self.variables_reference),
+            name: self.name.clone(),
+            frame_id: None,
+            bytes: None,
+            as_address: None,
+            mode: None,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(super) struct ExceptionInfoCommand {
+    pub(super) thread_id: u64,
+}
+
+impl LocalDapCommand for ExceptionInfoCommand {
+    type Response = dap::ExceptionInfoResponse;
+    type DapRequest = dap::requests::ExceptionInfo;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_exception_info_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::ExceptionInfoArguments {
+            thread_id: self.thread_id,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message)
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub(super) struct LocationsCommand {
     pub(super) reference: u64,
@@ -1774,3 +1896,144 @@ impl DapCommand for LocationsCommand {
         })
     }
 }
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(super) struct DisassembleCommand {
+    pub(super) memory_reference: String,
+    pub(super) instruction_offset: i64,
+    pub(super) instruction_count: u64,
+}
+
+impl LocalDapCommand for DisassembleCommand {
+    type Response = Vec<dap::DisassembledInstruction>;
+    type DapRequest = dap::requests::Disassemble;
+    const CACHEABLE: bool = true;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_disassemble_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::DisassembleArguments {
+            memory_reference: self.memory_reference.clone(),
+            offset: None,
+            instruction_offset: Some(self.instruction_offset),
+            instruction_count: self.instruction_count,
+            resolve_symbols: Some(true),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.instructions)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(super) struct ReadMemoryCommand {
+    pub(super) memory_reference: String,
+    pub(super) offset: i64,
+    pub(super) count: u64,
+}
+
+impl LocalDapCommand for ReadMemoryCommand {
+    type Response = MemoryContents;
+    type DapRequest = dap::requests::ReadMemory;
+    const CACHEABLE: bool = true;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_read_memory_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::ReadMemoryArguments {
+            memory_reference: self.memory_reference.clone(),
+            offset: Some(self.offset),
+            count: self.count,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        let data = message
+            .data
+            .map(|data| BASE64_STANDARD.decode(data))
+            .transpose()
+            .context("Invalid base64 data in ReadMemory response")?
+            .unwrap_or_default();
+        Ok(MemoryContents {
+            address: parse_memory_address(&message.address)?,
+            unreadable_bytes: message.unreadable_bytes.unwrap_or_default(),
+            data,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub(super) struct WriteMemoryCommand {
+    pub(super) memory_reference: String,
+    pub(super) offset: i64,
+    pub(super) data: Vec<u8>,
+}
+
+impl LocalDapCommand for WriteMemoryCommand {
+    type Response = ();
+    type DapRequest = dap::requests::WriteMemory;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_write_memory_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::WriteMemoryArguments {
+            memory_reference: self.memory_reference.clone(),
+            offset: Some(self.offset),
+            allow_partial: Some(false),
+            data: BASE64_STANDARD.encode(&self.data),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        _message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(())
+    }
+}
+
+/// Parses a memory address as sent by debug adapters, which is either hexadecimal with a `0x`
+/// prefix or decimal.
+pub fn parse_memory_address(address: &str) -> Result<u64> {
+    let address = address.trim();
+    match address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => address.parse(),
+    }
+    .with_context(|| format!("Invalid memory address: {address}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_memory_address() {
+        assert_eq!(parse_memory_address("0x7ffe0010").unwrap(), 0x7ffe0010);
+        assert_eq!(parse_memory_address("0X1F").unwrap(), 0x1f);
+        assert_eq!(parse_memory_address("4096").unwrap(), 4096);
+        assert!(parse_memory_address("0xnope").is_err());
+    }
+}
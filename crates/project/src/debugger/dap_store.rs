@@ -101,12 +101,15 @@ impl EventEmitter<DapStoreEvent> for DapStore {}
 #[derive(Clone, Serialize, Deserialize)]
 pub struct PersistedExceptionBreakpoint {
     pub enabled: bool,
+    /// Condition under which the exception filter stops, for adapters that support it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
 }
 
 /// Represents best-effort serialization of adapter state during last session (e.g. watches)
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct PersistedAdapterOptions {
-    /// Which exception breakpoints were enabled during the last session with this adapter?
+    /// Which exception breakpoints were enabled during the last session with this adapter, and with which conditions?
     pub exception_breakpoints: BTreeMap<String, PersistedExceptionBreakpoint>,
 }
 
@@ -834,7 +837,12 @@ impl DapStore {
             .map(|(exception, enabled)| {
                 (
                     exception.filter.clone(),
-                    PersistedExceptionBreakpoint { enabled: *enabled },
+                    PersistedExceptionBreakpoint {
+                        enabled: *enabled,
+                        condition: session
+                            .exception_breakpoint_condition(&exception.filter)
+                            .map(|condition| condition.to_string()),
+                    },
                 )
             })
             .collect();
@@ -1,15 +1,18 @@
 use crate::debugger::breakpoint_store::BreakpointSessionState;
 
 use super::breakpoint_store::{
-    BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason, SourceBreakpoint,
+    BreakpointState, BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason,
+    DataBreakpoint, FunctionBreakpoint, SourceBreakpoint,
 };
 use super::dap_command::{
-    self, Attach, ConfigurationDone, ContinueCommand, DisconnectCommand, EvaluateCommand,
-    Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand, ModulesCommand,
-    NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand, ScopesCommand,
-    SetExceptionBreakpoints, SetVariableValueCommand, StackTraceCommand, StepBackCommand,
-    StepCommand, StepInCommand, StepOutCommand, TerminateCommand, TerminateThreadsCommand,
-    ThreadsCommand, VariablesCommand,
+    self, Attach, ConfigurationDone, ContinueCommand, DataBreakpointInfoCommand,
+    DisassembleCommand, DisconnectCommand, EvaluateCommand, ExceptionInfoCommand, Initialize,
+    Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand, ModulesCommand, NextCommand,
+    PauseCommand, ReadMemoryCommand, RestartCommand, RestartStackFrameCommand, ScopesCommand,
+    SetDataBreakpoints, SetExceptionBreakpoints, SetFunctionBreakpoints, SetVariableValueCommand,
+    StackTraceCommand, StepBackCommand, StepCommand, StepInCommand, StepOutCommand,
+    TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    WriteMemoryCommand,
 };
 use super::dap_store::DapStore;
 use anyhow::{Context as _, Result, anyhow};
@@ -85,6 +88,59 @@ impl From<dap::StackFrame> for StackFrame {
     }
 }
 
+/// A range of the debuggee's memory, as returned by a `readMemory` request.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryContents {
+    /// The address of the first byte of `data`.
+    pub address: u64,
+    pub data: Vec<u8>,
+    /// The number of bytes after `data` that couldn't be read.
+    pub unreadable_bytes: u64,
+}
+
+/// The exception a thread stopped on, as returned by an `exceptionInfo` request.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExceptionInfo {
+    pub type_name: String,
+    pub message: Option<String>,
+    /// The stack trace of the exception, as formatted by the debuggee.
+    pub stack_trace: Option<String>,
+    /// Exceptions that caused this one.
+    pub inner_exceptions: Vec<ExceptionInfo>,
+}
+
+impl From<dap::ExceptionDetails> for ExceptionInfo {
+    fn from(details: dap::ExceptionDetails) -> Self {
+        Self {
+            type_name: details
+                .type_name
+                .or(details.full_type_name)
+                .unwrap_or_default(),
+            message: details.message,
+            stack_trace: details.stack_trace,
+            inner_exceptions: details
+                .inner_exception
+                .into_iter()
+                .flatten()
+                .map(Self::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<dap::ExceptionInfoResponse> for ExceptionInfo {
+    fn from(response: dap::ExceptionInfoResponse) -> Self {
+        let mut info = response.details.map(Self::from).unwrap_or_default();
+        if info.type_name.is_empty() {
+            info.type_name = response.exception_id;
+        }
+        if info.message.is_none() {
+            info.message = response.description;
+        }
+        info
+    }
+}
+
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum ThreadStatus {
     #[default]
@@ -300,28 +356,59 @@ impl RunningMode {
 
     fn send_exception_breakpoints(
         &self,
-        filters: Vec<ExceptionBreakpointsFilter>,
+        filters: Vec<(ExceptionBreakpointsFilter, Option<Arc<str>>)>,
         supports_filter_options: bool,
     ) -> Task<Result<Vec<dap::Breakpoint>>> {
         let arg = if supports_filter_options {
             SetExceptionBreakpoints::WithOptions {
                 filters: filters
                     .into_iter()
-                    .map(|filter| ExceptionFilterOptions {
+                    .map(|(filter, condition)| ExceptionFilterOptions {
+                        condition: condition
+                            .filter(|_| filter.supports_condition.unwrap_or_default())
+                            .map(|condition| String::from(condition.as_ref())),
                         filter_id: filter.filter,
-                        condition: None,
                         mode: None,
                     })
                     .collect(),
             }
         } else {
             SetExceptionBreakpoints::Plain {
-                filters: filters.into_iter().map(|filter| filter.filter).collect(),
+                filters: filters
+                    .into_iter()
+                    .map(|(filter, _)| filter.filter)
+                    .collect(),
             }
         };
         self.request(arg)
     }
 
+    fn send_function_breakpoints<'a>(
+        &self,
+        breakpoints: impl IntoIterator<Item = &'a FunctionBreakpoint>,
+    ) -> Task<Result<Vec<dap::Breakpoint>>> {
+        let breakpoints = breakpoints
+            .into_iter()
+            .filter(|bp| bp.state.is_enabled())
+            .cloned()
+            .map(Into::into)
+            .collect();
+        self.request(SetFunctionBreakpoints { breakpoints })
+    }
+
+    fn send_data_breakpoints<'a>(
+        &self,
+        breakpoints: impl IntoIterator<Item = &'a DataBreakpoint>,
+    ) -> Task<Result<Vec<dap::Breakpoint>>> {
+        let breakpoints = breakpoints
+            .into_iter()
+            .filter(|bp| bp.state.is_enabled())
+            .cloned()
+            .map(Into::into)
+            .collect();
+        self.request(SetDataBreakpoints { breakpoints })
+    }
+
     fn send_source_breakpoints(
         &self,
         ignore_breakpoints: bool,
@@ -427,9 +514,11 @@ impl RunningMode {
         let supports_exception_filters = capabilities
             .supports_exception_filter_options
             .unwrap_or_default();
+        let supports_function_breakpoints = SetFunctionBreakpoints::is_supported(capabilities);
+        let supports_data_breakpoints = SetDataBreakpoints::is_supported(capabilities);
         let this = self.clone();
         let worktree = self.worktree().clone();
-        let mut filters = capabilities
+        let filters = capabilities
             .exception_breakpoint_filters
             .clone()
             .unwrap_or_default();
@@ -475,24 +564,55 @@ impl RunningMode {
                     }
                 })?;
 
+                if supports_function_breakpoints {
+                    cx.update(|cx| {
+                        this.send_function_breakpoints(
+                            breakpoint_store.read(cx).function_breakpoints(),
+                        )
+                    })?
+                    .await
+                    .log_err();
+                }
+
+                // Data IDs that can't persist were only valid in the session they were created in.
+                if supports_data_breakpoints {
+                    cx.update(|cx| {
+                        this.send_data_breakpoints(
+                            breakpoint_store.read(cx).persistent_data_breakpoints(),
+                        )
+                    })?
+                    .await
+                    .log_err();
+                }
+
                 if should_send_exception_breakpoints {
-                    _ = session.update(cx, |this, _| {
-                        filters.retain(|filter| {
-                            let is_enabled = if let Some(defaults) = adapter_defaults.as_ref() {
-                                defaults
-                                    .exception_breakpoints
-                                    .get(&filter.filter)
+                    let filters = session.update(cx, |this, _| {
+                        filters
+                            .into_iter()
+                            .filter_map(|filter| {
+                                let persisted = adapter_defaults.as_ref().and_then(|defaults| {
+                                    defaults.exception_breakpoints.get(&filter.filter)
+                                });
+                                let is_enabled = persisted
                                     .map(|options| options.enabled)
-                                    .unwrap_or_else(|| filter.default.unwrap_or_default())
-                            } else {
-                                filter.default.unwrap_or_default()
-                            };
-                            this.exception_breakpoints
-                                .entry(filter.filter.clone())
-                                .or_insert_with(|| (filter.clone(), is_enabled));
-                            is_enabled
-                        });
-                    });
+                                    .unwrap_or_else(|| filter.default.unwrap_or_default());
+                                if let Some(condition) = persisted
+                                    .and_then(|options| options.condition.as_deref())
+                                    .filter(|condition| !condition.is_empty())
+                                {
+                                    this.exception_breakpoint_conditions
+                                        .entry(filter.filter.clone())
+                                        .or_insert_with(|| condition.into());
+                                }
+                                this.exception_breakpoints
+                                    .entry(filter.filter.clone())
+                                    .or_insert_with(|| (filter.clone(), is_enabled));
+                                let condition =
+                                    this.exception_breakpoint_conditions.get(&filter.filter);
+                                is_enabled.then(|| (filter, condition.cloned()))
+                            })
+                            .collect()
+                    })?;
 
                     this.send_exception_breakpoints(filters, supports_exception_filters)
                         .await
@@ -682,6 +802,8 @@ pub struct Session {
     pub(crate) breakpoint_store: Entity<BreakpointStore>,
     ignore_breakpoints: bool,
     exception_breakpoints: BTreeMap<String, (ExceptionBreakpointsFilter, IsEnabled)>,
+    exception_breakpoint_conditions: BTreeMap<String, Arc<str>>,
+    exception_info: HashMap<ThreadId, ExceptionInfo>,
     background_tasks: Vec<Task<()>>,
     restart_task: Option<Task<()>>,
     task_context: TaskContext,
@@ -771,11 +893,14 @@ pub enum SessionEvent {
     Threads,
     InvalidateInlineValue,
     CapabilitiesLoaded,
+    Memory,
     RunInTerminal {
         request: RunInTerminalRequestArguments,
         sender: mpsc::Sender<Result<u32>>,
     },
     ConsoleOutput,
+    /// Details of the exception a thread stopped on were fetched.
+    ExceptionInfo(ThreadId),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -825,6 +950,12 @@ impl Session {
                         local.unset_breakpoints_from_paths(paths, cx).detach();
                     }
                 }
+                BreakpointStoreEvent::FunctionBreakpointsUpdated => {
+                    this.send_function_breakpoints(cx);
+                }
+                BreakpointStoreEvent::DataBreakpointsUpdated => {
+                    this.send_data_breakpoints(cx);
+                }
                 BreakpointStoreEvent::SetDebugLine | BreakpointStoreEvent::ClearDebugLines => {}
             })
             .detach();
@@ -852,6 +983,8 @@ impl Session {
                 ignore_breakpoints: false,
                 breakpoint_store,
                 exception_breakpoints: Default::default(),
+                exception_breakpoint_conditions: Default::default(),
+                exception_info: Default::default(),
                 label,
                 adapter,
                 task_context,
@@ -1423,6 +1556,12 @@ impl Session {
         self.invalidate_generic();
         self.threads.clear();
         self.variables.clear();
+        self.exception_info.clear();
+        if let Some(thread_id) = event.thread_id
+            && matches!(event.reason, dap::StoppedEventReason::Exception)
+        {
+            self.fetch_exception_info(ThreadId(thread_id), cx);
+        }
         cx.emit(SessionEvent::Stopped(
             event
                 .thread_id
@@ -1443,6 +1582,7 @@ impl Session {
             }
             Events::Stopped(event) => self.handle_stopped_event(event, cx),
             Events::Continued(event) => {
+                self.exception_info.clear();
                 if event.all_threads_continued.unwrap_or_default() {
                     self.thread_states.continue_all_threads();
                     self.breakpoint_store.update(cx, |store, cx| {
@@ -1544,7 +1684,10 @@ impl Session {
                 // Remove the ones that no longer exist.
                 cx.notify();
             }
-            Events::Memory(_) => {}
+            Events::Memory(_) => {
+                cx.emit(SessionEvent::Memory);
+                cx.notify();
+            }
             Events::Process(_) => {}
             Events::ProgressEnd(_) => {}
             Events::ProgressStart(_) => {}
@@ -1757,6 +1900,8 @@ impl Session {
         }
 
         self.ignore_breakpoints = ignore;
+        self.send_function_breakpoints(cx);
+        self.send_data_breakpoints(cx);
 
         if let Some(local) = self.as_running() {
             local.send_source_breakpoints(ignore, &self.breakpoint_store, cx)
@@ -1779,12 +1924,42 @@ impl Session {
         }
     }
 
+    pub fn exception_breakpoint_condition(&self, id: &str) -> Option<&Arc<str>> {
+        self.exception_breakpoint_conditions.get(id)
+    }
+
+    /// Sets the condition under which the exception filter `id` stops, clearing it when `condition` is empty.
+    pub fn set_exception_breakpoint_condition(&mut self, id: &str, condition: Arc<str>, cx: &App) {
+        let Some((_, is_enabled)) = self.exception_breakpoints.get(id) else {
+            return;
+        };
+        let is_enabled = *is_enabled;
+
+        if condition.is_empty() {
+            self.exception_breakpoint_conditions.remove(id);
+        } else {
+            self.exception_breakpoint_conditions
+                .insert(id.to_owned(), condition);
+        }
+
+        if is_enabled {
+            self.send_exception_breakpoints(cx);
+        }
+    }
+
     fn send_exception_breakpoints(&mut self, cx: &App) {
         if let Some(local) = self.as_running() {
             let exception_filters = self
                 .exception_breakpoints
-                .values()
-                .filter_map(|(filter, is_enabled)| is_enabled.then(|| filter.clone()))
+                .iter()
+                .filter_map(|(id, (filter, is_enabled))| {
+                    is_enabled.then(|| {
+                        (
+                            filter.clone(),
+                            self.exception_breakpoint_conditions.get(id).cloned(),
+                        )
+                    })
+                })
                 .collect();
 
             let supports_exception_filters = self
@@ -1799,6 +1974,132 @@ impl Session {
         }
     }
 
+    /// Details of the exception `thread_id` is stopped on, if any.
+    pub fn exception_info(&self, thread_id: ThreadId) -> Option<&ExceptionInfo> {
+        self.exception_info.get(&thread_id)
+    }
+
+    fn fetch_exception_info(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        if !ExceptionInfoCommand::is_supported(&self.capabilities) {
+            return;
+        }
+        let task = self.request(
+            ExceptionInfoCommand {
+                thread_id: thread_id.0,
+            },
+            |_, result, _| result.log_err(),
+            cx,
+        );
+
+        cx.spawn(async move |this, cx| {
+            let info = task.await?;
+            this.update(cx, |this, cx| {
+                if this.thread_status(thread_id) != ThreadStatus::Stopped {
+                    return;
+                }
+                this.exception_info.insert(thread_id, info.into());
+                cx.emit(SessionEvent::ExceptionInfo(thread_id));
+                cx.notify();
+            })
+            .ok()
+        })
+        .detach();
+    }
+
+    fn send_function_breakpoints(&self, cx: &App) {
+        if !SetFunctionBreakpoints::is_supported(&self.capabilities) {
+            return;
+        }
+        if let Some(local) = self.as_running() {
+            let breakpoints = if self.ignore_breakpoints {
+                &[]
+            } else {
+                self.breakpoint_store.read(cx).function_breakpoints()
+            };
+            local
+                .send_function_breakpoints(breakpoints)
+                .detach_and_log_err(cx);
+        }
+    }
+
+    fn send_data_breakpoints(&self, cx: &App) {
+        if !SetDataBreakpoints::is_supported(&self.capabilities) || self.is_session_terminated {
+            return;
+        }
+        if let Some(local) = self.as_running() {
+            // Data IDs created by other sessions mean nothing to this session's adapter.
+            let breakpoints = if self.ignore_breakpoints {
+                Vec::new()
+            } else {
+                self.breakpoint_store
+                    .read(cx)
+                    .data_breakpoints_for_session(self.id)
+                    .collect()
+            };
+            local
+                .send_data_breakpoints(breakpoints)
+                .detach_and_log_err(cx);
+        }
+    }
+
+    /// Asks the debug adapter whether the variable `name` within the `variables_reference` container
+    /// can be watched, and adds a data breakpoint for it if so.
+    pub fn add_data_breakpoint(
+        &mut self,
+        variables_reference: VariableReference,
+        name: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let task = self.request(
+            DataBreakpointInfoCommand {
+                variables_reference,
+                name,
+            },
+            |_, result, _| result.log_err(),
+            cx,
+        );
+
+        cx.spawn(async move |this, cx| {
+            let info = task.await.context("failed to fetch data breakpoint info")?;
+            let data_id = info
+                .data_id
+                .with_context(|| format!("Can't set a data breakpoint: {}", info.description))?;
+            // The breakpoint is meant to stop when the value changes, so it has to watch writes.
+            // Adapters that don't list access types only support their default one.
+            let access_type = match info.access_types.as_deref() {
+                None => None,
+                Some(access_types) => {
+                    let access_type = [
+                        dap::DataBreakpointAccessType::Write,
+                        dap::DataBreakpointAccessType::ReadWrite,
+                    ]
+                    .into_iter()
+                    .find(|access_type| access_types.contains(access_type))
+                    .with_context(|| format!("Can't break when {} changes", info.description))?;
+                    Some(access_type)
+                }
+            };
+
+            this.update(cx, |this, cx| {
+                this.breakpoint_store.update(cx, |store, cx| {
+                    store.add_data_breakpoint(
+                        DataBreakpoint {
+                            data_id: data_id.into(),
+                            description: info.description.into(),
+                            access_type,
+                            can_persist: info.can_persist.unwrap_or_default(),
+                            session_id: Some(this.id),
+                            condition: None,
+                            hit_condition: None,
+                            state: BreakpointState::Enabled,
+                        },
+                        cx,
+                    )
+                })
+            })
+        })
+    }
+
     pub fn breakpoints_enabled(&self) -> bool {
         self.ignore_breakpoints
     }
@@ -1926,6 +2227,10 @@ impl Session {
 
         self.is_session_terminated = true;
         self.thread_states.exit_all_threads();
+        let session_id = self.id;
+        self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+            breakpoint_store.remove_session_data_breakpoints(session_id, cx)
+        });
         cx.notify();
 
         let task = match &mut self.mode {
@@ -1988,6 +2293,81 @@ impl Session {
         })
     }
 
+    /// Disassembles `instruction_count` instructions, starting `instruction_offset` instructions
+    /// away from the given memory reference.
+    pub fn disassemble(
+        &mut self,
+        memory_reference: String,
+        instruction_offset: i64,
+        instruction_count: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<dap::DisassembledInstruction>>> {
+        let task = self.request(
+            DisassembleCommand {
+                memory_reference,
+                instruction_offset,
+                instruction_count,
+            },
+            |_, result, _| result.log_err(),
+            cx,
+        );
+
+        cx.background_executor()
+            .spawn(async move { anyhow::Ok(task.await.context("failed to disassemble")?) })
+    }
+
+    pub fn read_memory(
+        &mut self,
+        memory_reference: String,
+        offset: i64,
+        count: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<MemoryContents>> {
+        let task = self.request(
+            ReadMemoryCommand {
+                memory_reference,
+                offset,
+                count,
+            },
+            |_, result, _| result.log_err(),
+            cx,
+        );
+
+        cx.background_executor()
+            .spawn(async move { anyhow::Ok(task.await.context("failed to read memory")?) })
+    }
+
+    pub fn write_memory(
+        &mut self,
+        memory_reference: String,
+        offset: i64,
+        data: Vec<u8>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let task = self.request(
+            WriteMemoryCommand {
+                memory_reference,
+                offset,
+                data,
+            },
+            |this, result, cx| {
+                let result = result.log_err();
+                if result.is_some() {
+                    // Writing memory can change the values of variables too.
+                    this.invalidate_command_type::<VariablesCommand>();
+                    cx.emit(SessionEvent::Memory);
+                    cx.emit(SessionEvent::Variables);
+                    cx.notify();
+                }
+                result
+            },
+            cx,
+        );
+
+        cx.background_executor()
+            .spawn(async move { anyhow::Ok(task.await.context("failed to write memory")?) })
+    }
+
     pub fn continue_thread(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
         let supports_single_thread_execution_requests =
             self.capabilities.supports_single_thread_execution_requests;
@@ -21,19 +21,22 @@ use futures::{
 use git::{
     BuildPermalinkParams, GitHostingProviderRegistry, WORK_DIRECTORY_REPO_PATH,
     blame::Blame,
+    jujutsu::JujutsuRepository,
     parse_git_remote_url,
     repository::{
         Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, DiffType, FetchOptions,
-        GitRepository, GitRepositoryCheckpoint, PushOptions, Remote, RemoteCommandOutput, RepoPath,
-        ResetMode, UpstreamTrackingStatus,
+        GitRepository, GitRepositoryCheckpoint, GitStash, GitWorktree, InProgressOperation,
+        LogOptions, PushOptions, Remote, RemoteCommandOutput, RepoPath, ResetMode, StashEntry,
+        StashOptions, Tag, UpstreamTrackingStatus,
     },
     status::{
-        FileStatus, GitSummary, StatusCode, TrackedStatus, UnmergedStatus, UnmergedStatusCode,
+        FileStatus, GitStatus, GitSummary, StatusCode, TrackedStatus, UnmergedStatus,
+        UnmergedStatusCode,
     },
 };
 use gpui::{
-    App, AppContext, AsyncApp, Context, Entity, EventEmitter, SharedString, Subscription, Task,
-    WeakEntity,
+    App, AppContext, AsyncApp, BackgroundExecutor, Context, Entity, EventEmitter, SharedString,
+    Subscription, Task, WeakEntity,
 };
 use language::{
     Buffer, BufferEvent, Language, LanguageRegistry,
@@ -234,6 +237,8 @@ pub struct MergeDetails {
     pub conflicted_paths: TreeSet<RepoPath>,
     pub message: Option<SharedString>,
     pub heads: Vec<Option<SharedString>>,
+    /// The merge, cherry-pick, revert or rebase that is waiting to be continued or aborted.
+    pub operation: Option<InProgressOperation>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -283,6 +288,9 @@ pub enum RepositoryState {
     Local {
         backend: Arc<dyn GitRepository>,
         environment: Arc<HashMap<String, String>>,
+        /// Set when the work directory is also a Jujutsu workspace, in which case diff bases and
+        /// statuses come from jj rather than from the git index.
+        jj_repository: Option<Arc<dyn JujutsuRepository>>,
     },
     Remote {
         project_id: ProjectId,
@@ -420,8 +428,27 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_stage);
         client.add_entity_request_handler(Self::handle_unstage);
         client.add_entity_request_handler(Self::handle_commit);
+        client.add_entity_request_handler(Self::handle_stash_list);
+        client.add_entity_request_handler(Self::handle_stash_push);
+        client.add_entity_request_handler(Self::handle_stash_apply);
+        client.add_entity_request_handler(Self::handle_stash_pop);
+        client.add_entity_request_handler(Self::handle_stash_drop);
         client.add_entity_request_handler(Self::handle_reset);
         client.add_entity_request_handler(Self::handle_show);
+        client.add_entity_request_handler(Self::handle_log);
+        client.add_entity_request_handler(Self::handle_cherry_pick);
+        client.add_entity_request_handler(Self::handle_revert);
+        client.add_entity_request_handler(Self::handle_rebase);
+        client.add_entity_request_handler(Self::handle_continue_operation);
+        client.add_entity_request_handler(Self::handle_abort_operation);
+        client.add_entity_request_handler(Self::handle_get_tags);
+        client.add_entity_request_handler(Self::handle_create_tag);
+        client.add_entity_request_handler(Self::handle_delete_tag);
+        client.add_entity_request_handler(Self::handle_checkout_tag);
+        client.add_entity_request_handler(Self::handle_push_tag);
+        client.add_entity_request_handler(Self::handle_worktree_list);
+        client.add_entity_request_handler(Self::handle_worktree_add);
+        client.add_entity_request_handler(Self::handle_worktree_remove);
         client.add_entity_request_handler(Self::handle_load_commit_diff);
         client.add_entity_request_handler(Self::handle_checkout_files);
         client.add_entity_request_handler(Self::handle_open_commit_message_buffer);
@@ -1746,6 +1773,104 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_stash_list(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashList>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitStashListResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let stash = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.stash_list()
+            })?
+            .await??;
+
+        Ok(proto::GitStashListResponse {
+            entries: stash.entries.iter().map(stash_entry_to_proto).collect(),
+        })
+    }
+
+    async fn handle_stash_push(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashPush>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let paths = envelope
+            .payload
+            .paths
+            .into_iter()
+            .map(PathBuf::from)
+            .map(RepoPath::new)
+            .collect();
+        let options = StashOptions {
+            message: envelope.payload.message.map(SharedString::from),
+            include_untracked: envelope.payload.include_untracked,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.stash_push(paths, options, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_stash_apply(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashApply>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let index = envelope.payload.index as usize;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.stash_apply(index, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_stash_pop(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashPop>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let index = envelope.payload.index as usize;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.stash_pop(index, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_stash_drop(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitStashDrop>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let index = envelope.payload.index as usize;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.stash_drop(index, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
     async fn handle_get_remotes(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::GetRemotes>,
@@ -1827,6 +1952,167 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_get_tags(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetTags>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitTagsResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let tags = repository_handle
+            .update(&mut cx, |repository_handle, _| repository_handle.tags())?
+            .await??;
+
+        Ok(proto::GitTagsResponse {
+            tags: tags.iter().map(tag_to_proto).collect(),
+        })
+    }
+
+    async fn handle_create_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCreateTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let tag_name = envelope.payload.tag_name;
+        let message = envelope.payload.message;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.create_tag(tag_name, message)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_delete_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDeleteTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let tag_name = envelope.payload.tag_name;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.delete_tag(tag_name)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_checkout_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCheckoutTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let tag_name = envelope.payload.tag_name;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.checkout_tag(tag_name)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_push_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitPushTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let askpass_id = envelope.payload.askpass_id;
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            askpass_id,
+            &mut cx,
+        );
+
+        let tag_name = envelope.payload.tag_name.into();
+        let remote_name = envelope.payload.remote_name.into();
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.push_tag(tag_name, remote_name, askpass, cx)
+            })?
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
+    }
+
+    async fn handle_worktree_list(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitWorktreeList>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitWorktreesResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let worktrees = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.worktree_list()
+            })?
+            .await??;
+
+        Ok(proto::GitWorktreesResponse {
+            worktrees: worktrees.iter().map(worktree_to_proto).collect(),
+        })
+    }
+
+    async fn handle_worktree_add(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitWorktreeAdd>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = PathBuf::from_proto(envelope.payload.path);
+        let branch_name = envelope.payload.branch_name;
+        let create_branch = envelope.payload.create_branch;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.worktree_add(path, branch_name, create_branch)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_worktree_remove(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitWorktreeRemove>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = PathBuf::from_proto(envelope.payload.path);
+        let force = envelope.payload.force;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.worktree_remove(path, force)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
     async fn handle_show(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::GitShow>,
@@ -1846,6 +2132,34 @@ impl GitStore {
             commit_timestamp: commit.commit_timestamp,
             author_email: commit.author_email.into(),
             author_name: commit.author_name.into(),
+            parent_count: commit.parent_count as u32,
+        })
+    }
+
+    async fn handle_log(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitLog>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitLogResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let options = LogOptions {
+            skip: envelope.payload.skip as usize,
+            limit: envelope.payload.limit as usize,
+            path: envelope.payload.path.map(PathBuf::from).map(RepoPath::new),
+            author: envelope.payload.author.map(SharedString::from),
+            message: envelope.payload.message.map(SharedString::from),
+            first_parent: envelope.payload.first_parent,
+        };
+
+        let commits = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.log(options)
+            })?
+            .await??;
+        Ok(proto::GitLogResponse {
+            commits: commits.iter().map(commit_details_to_proto).collect(),
         })
     }
 
@@ -1896,70 +2210,152 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
-    async fn handle_checkout_files(
+    async fn handle_cherry_pick(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
-        let paths = envelope
-            .payload
-            .paths
-            .iter()
-            .map(|s| RepoPath::from_str(s))
-            .collect();
 
         repository_handle
             .update(&mut cx, |repository_handle, cx| {
-                repository_handle.checkout_files(&envelope.payload.commit, paths, cx)
+                repository_handle.cherry_pick(envelope.payload.commits, cx)
             })?
             .await??;
         Ok(proto::Ack {})
     }
 
-    async fn handle_open_commit_message_buffer(
+    async fn handle_revert(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::OpenCommitMessageBuffer>,
+        envelope: TypedEnvelope<proto::GitRevert>,
         mut cx: AsyncApp,
-    ) -> Result<proto::OpenBufferResponse> {
+    ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
-        let buffer = repository
-            .update(&mut cx, |repository, cx| {
-                repository.open_commit_buffer(None, this.read(cx).buffer_store.clone(), cx)
-            })?
-            .await?;
-
-        let buffer_id = buffer.read_with(&cx, |buffer, _| buffer.remote_id())?;
-        this.update(&mut cx, |this, cx| {
-            this.buffer_store.update(cx, |buffer_store, cx| {
-                buffer_store
-                    .create_buffer_for_peer(
-                        &buffer,
-                        envelope.original_sender_id.unwrap_or(envelope.sender_id),
-                        cx,
-                    )
-                    .detach_and_log_err(cx);
-            })
-        })?;
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-        Ok(proto::OpenBufferResponse {
-            buffer_id: buffer_id.to_proto(),
-        })
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.revert(envelope.payload.commits, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
     }
 
-    async fn handle_askpass(
+    async fn handle_rebase(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::AskPassRequest>,
+        envelope: TypedEnvelope<proto::GitRebase>,
         mut cx: AsyncApp,
-    ) -> Result<proto::AskPassResponse> {
+    ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
-        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
 
-        let delegates = cx.update(|cx| repository.read(cx).askpass_delegates.clone())?;
-        let Some(mut askpass) = delegates.lock().remove(&envelope.payload.askpass_id) else {
-            debug_panic!("no askpass found");
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase(envelope.payload.onto, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_continue_operation(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitContinueOperation>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let operation = proto_to_operation(envelope.payload.operation());
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.continue_operation(operation, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_abort_operation(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitAbortOperation>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let operation = proto_to_operation(envelope.payload.operation());
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.abort_operation(operation, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_checkout_files(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|s| RepoPath::from_str(s))
+            .collect();
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.checkout_files(&envelope.payload.commit, paths, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_open_commit_message_buffer(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::OpenCommitMessageBuffer>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::OpenBufferResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let buffer = repository
+            .update(&mut cx, |repository, cx| {
+                repository.open_commit_buffer(None, this.read(cx).buffer_store.clone(), cx)
+            })?
+            .await?;
+
+        let buffer_id = buffer.read_with(&cx, |buffer, _| buffer.remote_id())?;
+        this.update(&mut cx, |this, cx| {
+            this.buffer_store.update(cx, |buffer_store, cx| {
+                buffer_store
+                    .create_buffer_for_peer(
+                        &buffer,
+                        envelope.original_sender_id.unwrap_or(envelope.sender_id),
+                        cx,
+                    )
+                    .detach_and_log_err(cx);
+            })
+        })?;
+
+        Ok(proto::OpenBufferResponse {
+            buffer_id: buffer_id.to_proto(),
+        })
+    }
+
+    async fn handle_askpass(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::AskPassRequest>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::AskPassResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let delegates = cx.update(|cx| repository.read(cx).askpass_delegates.clone())?;
+        let Some(mut askpass) = delegates.lock().remove(&envelope.payload.askpass_id) else {
+            debug_panic!("no askpass found");
             anyhow::bail!("no askpass found");
         };
 
@@ -2561,6 +2957,10 @@ impl RepositorySnapshot {
         proto::UpdateRepository {
             branch_summary: self.branch.as_ref().map(branch_to_proto),
             head_commit_details: self.head_commit.as_ref().map(commit_details_to_proto),
+            in_progress_operation: self
+                .merge
+                .operation
+                .map(|operation| operation_to_proto(operation).into()),
             updated_statuses: self
                 .statuses_by_path
                 .iter()
@@ -2627,6 +3027,10 @@ impl RepositorySnapshot {
         proto::UpdateRepository {
             branch_summary: self.branch.as_ref().map(branch_to_proto),
             head_commit_details: self.head_commit.as_ref().map(commit_details_to_proto),
+            in_progress_operation: self
+                .merge
+                .operation
+                .map(|operation| operation_to_proto(operation).into()),
             updated_statuses,
             removed_statuses,
             current_merge_conflicts: self
@@ -2697,6 +3101,7 @@ impl MergeDetails {
     ) -> Result<(MergeDetails, bool)> {
         log::debug!("load merge details");
         let message = backend.merge_message().await;
+        let operation = backend.in_progress_operation().await;
         let heads = backend
             .revparse_batch(vec![
                 "MERGE_HEAD".into(),
@@ -2733,6 +3138,7 @@ impl MergeDetails {
                 return Ok((
                     MergeDetails {
                         message: message.map(SharedString::from),
+                        operation,
                         ..prev_snapshot.merge.clone()
                     },
                     false,
@@ -2747,6 +3153,7 @@ impl MergeDetails {
             conflicted_paths,
             message: message.map(SharedString::from),
             heads,
+            operation,
         };
         Ok((details, merge_heads_changed))
     }
@@ -2825,10 +3232,16 @@ impl Repository {
             Some(GitJobKey::ReloadBufferDiffBases),
             None,
             |state, mut cx| async move {
-                let RepositoryState::Local { backend, .. } = state else {
+                let RepositoryState::Local {
+                    backend,
+                    jj_repository,
+                    ..
+                } = state
+                else {
                     log::error!("tried to recompute diffs for a non-local repository");
                     return Ok(());
                 };
+                let executor = cx.background_executor().clone();
 
                 let Some(this) = this.upgrade() else {
                     return Ok(());
@@ -2879,12 +3292,24 @@ impl Repository {
                             &repo_diff_state_updates
                         {
                             let index_text = if current_index_text.is_some() {
-                                backend.load_index_text(repo_path.clone()).await
+                                load_index_text(
+                                    &backend,
+                                    jj_repository.as_ref(),
+                                    repo_path.clone(),
+                                    &executor,
+                                )
+                                .await
                             } else {
                                 None
                             };
                             let head_text = if current_head_text.is_some() {
-                                backend.load_committed_text(repo_path.clone()).await
+                                load_committed_text(
+                                    &backend,
+                                    jj_repository.as_ref(),
+                                    repo_path.clone(),
+                                    &executor,
+                                )
+                                .await
                             } else {
                                 None
                             };
@@ -3244,6 +3669,155 @@ impl Repository {
         })
     }
 
+    pub fn cherry_pick(
+        &mut self,
+        commits: Vec<String>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some("git cherry-pick".into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.cherry_pick(commits, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitCherryPick {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commits,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn revert(&mut self, commits: Vec<String>, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(Some("git revert".into()), move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local {
+                    backend,
+                    environment,
+                    ..
+                } => backend.revert(commits, environment).await,
+                RepositoryState::Remote { project_id, client } => {
+                    client
+                        .request(proto::GitRevert {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            commits,
+                        })
+                        .await?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn rebase(&mut self, onto: String, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git rebase {onto}").into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.rebase(onto, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitRebase {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                onto,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn continue_operation(
+        &mut self,
+        operation: InProgressOperation,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git {} --continue", operation.command()).into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.continue_operation(operation, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitContinueOperation {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                operation: operation_to_proto(operation).into(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn abort_operation(
+        &mut self,
+        operation: InProgressOperation,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git {} --abort", operation.command()).into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.abort_operation(operation, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitAbortOperation {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                operation: operation_to_proto(operation).into(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
     pub fn show(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDetails>> {
         let id = self.id;
         self.send_job(None, move |git_repo, _cx| async move {
@@ -3264,12 +3838,43 @@ impl Repository {
                         commit_timestamp: resp.commit_timestamp,
                         author_email: resp.author_email.into(),
                         author_name: resp.author_name.into(),
+                        parent_count: resp.parent_count as usize,
                     })
                 }
             }
         })
     }
 
+    /// Lists the commits reachable from `HEAD`, most recent first.
+    pub fn log(&mut self, options: LogOptions) -> oneshot::Receiver<Result<Vec<CommitDetails>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local { backend, .. } => backend.log(options).await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitLog {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            skip: options.skip as u64,
+                            limit: options.limit as u64,
+                            path: options.path.map(|path| path.to_string_lossy().to_string()),
+                            author: options.author.map(String::from),
+                            message: options.message.map(String::from),
+                            first_parent: options.first_parent,
+                        })
+                        .await?;
+
+                    Ok(response
+                        .commits
+                        .iter()
+                        .map(proto_to_commit_details)
+                        .collect())
+                }
+            }
+        })
+    }
+
     pub fn load_commit_diff(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDiff>> {
         let id = self.id;
         self.send_job(None, move |git_repo, cx| async move {
@@ -3499,22 +4104,203 @@ impl Repository {
         })
     }
 
-    pub fn fetch(
+    /// Commits the staged changes, first staging every change to a tracked file when
+    /// `stage_tracked` is set, like `git commit --all`.
+    ///
+    /// Callers decide whether to stage, since they may know about staging operations
+    /// that aren't reflected in the cached status yet.
+    pub fn commit_staged_or_tracked(
         &mut self,
-        fetch_options: FetchOptions,
-        askpass: AskPassDelegate,
-        _cx: &mut App,
-    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
-        let askpass_delegates = self.askpass_delegates.clone();
-        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
-        let id = self.id;
-
-        self.send_job(Some("git fetch".into()), move |git_repo, cx| async move {
-            match git_repo {
-                RepositoryState::Local {
-                    backend,
-                    environment,
-                    ..
+        message: SharedString,
+        name_and_email: Option<(SharedString, SharedString)>,
+        stage_tracked: bool,
+        options: CommitOptions,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let stage = if stage_tracked {
+            let changed_files = self
+                .cached_status()
+                .filter(|entry| entry.status.has_changes() && !entry.status.is_created())
+                .map(|entry| entry.repo_path)
+                .collect::<Vec<_>>();
+            if changed_files.is_empty() {
+                return Task::ready(Err(anyhow!("No changes to commit")));
+            }
+            self.stage_entries(changed_files, cx)
+        } else {
+            Task::ready(Ok(()))
+        };
+
+        cx.spawn(async move |this, cx| {
+            stage.await?;
+            // Repository serializes all git operations, so the commit runs after the files are staged.
+            this.update(cx, |this, cx| {
+                this.commit(message, name_and_email, options, cx)
+            })?
+            .await?
+        })
+    }
+
+    pub fn stash_list(&mut self) -> oneshot::Receiver<Result<GitStash>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => backend.stash_list().await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitStashList {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(GitStash {
+                        entries: response.entries.iter().map(proto_to_stash_entry).collect(),
+                    })
+                }
+            }
+        })
+    }
+
+    pub fn stash_push(
+        &mut self,
+        paths: Vec<RepoPath>,
+        options: StashOptions,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(Some("git stash".into()), move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local {
+                    backend,
+                    environment,
+                    ..
+                } => backend.stash_push(paths, options, environment).await,
+                RepositoryState::Remote { project_id, client } => {
+                    client
+                        .request(proto::GitStashPush {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            paths: paths
+                                .into_iter()
+                                .map(|p| p.to_string_lossy().to_string())
+                                .collect(),
+                            message: options.message.map(String::from),
+                            include_untracked: options.include_untracked,
+                        })
+                        .await
+                        .context("sending stash request")?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    pub fn stash_apply(&mut self, index: usize, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git stash apply stash@{{{index}}}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.stash_apply(index, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitStashApply {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                index: index as u64,
+                            })
+                            .await
+                            .context("sending stash apply request")?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn stash_pop(&mut self, index: usize, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git stash pop stash@{{{index}}}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.stash_pop(index, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitStashPop {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                index: index as u64,
+                            })
+                            .await
+                            .context("sending stash pop request")?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn stash_drop(&mut self, index: usize, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git stash drop stash@{{{index}}}").into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.stash_drop(index, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitStashDrop {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                index: index as u64,
+                            })
+                            .await
+                            .context("sending stash drop request")?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn fetch(
+        &mut self,
+        fetch_options: FetchOptions,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
+
+        self.send_job(Some("git fetch".into()), move |git_repo, cx| async move {
+            match git_repo {
+                RepositoryState::Local {
+                    backend,
+                    environment,
+                    ..
                 } => backend.fetch(fetch_options, askpass, environment, cx).await,
                 RepositoryState::Remote { project_id, client } => {
                     askpass_delegates.lock().insert(askpass_id, askpass);
@@ -3612,42 +4398,400 @@ impl Repository {
                         result
                     }
                     RepositoryState::Remote { project_id, client } => {
-                        askpass_delegates.lock().insert(askpass_id, askpass);
-                        let _defer = util::defer(|| {
-                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
-                            debug_assert!(askpass_delegate.is_some());
-                        });
-                        let response = client
-                            .request(proto::Push {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+                        let response = client
+                            .request(proto::Push {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                askpass_id,
+                                branch_name: branch.to_string(),
+                                remote_name: remote.to_string(),
+                                options: options.map(|options| match options {
+                                    PushOptions::Force => proto::push::PushOptions::Force,
+                                    PushOptions::SetUpstream => {
+                                        proto::push::PushOptions::SetUpstream
+                                    }
+                                }
+                                    as i32),
+                            })
+                            .await
+                            .context("sending push request")?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn pull(
+        &mut self,
+        branch: SharedString,
+        remote: SharedString,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
+
+        self.send_job(
+            Some(format!("git pull {} {}", remote, branch).into()),
+            move |git_repo, cx| async move {
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => {
+                        backend
+                            .pull(
+                                branch.to_string(),
+                                remote.to_string(),
+                                askpass,
+                                environment.clone(),
+                                cx,
+                            )
+                            .await
+                    }
+                    RepositoryState::Remote { project_id, client } => {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+                        let response = client
+                            .request(proto::Pull {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                askpass_id,
+                                branch_name: branch.to_string(),
+                                remote_name: remote.to_string(),
+                            })
+                            .await
+                            .context("sending pull request")?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
+    fn spawn_set_index_text_job(
+        &mut self,
+        path: RepoPath,
+        content: Option<String>,
+        hunk_staging_operation_count: Option<usize>,
+        cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<anyhow::Result<()>> {
+        let id = self.id;
+        let this = cx.weak_entity();
+        let git_store = self.git_store.clone();
+        self.send_keyed_job(
+            Some(GitJobKey::WriteIndex(path.clone())),
+            None,
+            move |git_repo, mut cx| async move {
+                log::debug!("start updating index text for buffer {}", path.display());
+                match git_repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => {
+                        backend
+                            .set_index_text(path.clone(), content, environment.clone())
+                            .await?;
+                    }
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::SetIndexText {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                path: path.as_ref().to_proto(),
+                                text: content,
+                            })
+                            .await?;
+                    }
+                }
+                log::debug!("finish updating index text for buffer {}", path.display());
+
+                if let Some(hunk_staging_operation_count) = hunk_staging_operation_count {
+                    let project_path = this
+                        .read_with(&cx, |this, cx| this.repo_path_to_project_path(&path, cx))
+                        .ok()
+                        .flatten();
+                    git_store.update(&mut cx, |git_store, cx| {
+                        let buffer_id = git_store
+                            .buffer_store
+                            .read(cx)
+                            .get_by_path(&project_path?)?
+                            .read(cx)
+                            .remote_id();
+                        let diff_state = git_store.diffs.get(&buffer_id)?;
+                        diff_state.update(cx, |diff_state, _| {
+                            diff_state.hunk_staging_operation_count_as_of_write =
+                                hunk_staging_operation_count;
+                        });
+                        Some(())
+                    })?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    pub fn get_remotes(
+        &mut self,
+        branch_name: Option<String>,
+    ) -> oneshot::Receiver<Result<Vec<Remote>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => backend.get_remotes(branch_name).await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GetRemotes {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            branch_name,
+                        })
+                        .await?;
+
+                    let remotes = response
+                        .remotes
+                        .into_iter()
+                        .map(|remotes| git::repository::Remote {
+                            name: remotes.name.into(),
+                        })
+                        .collect();
+
+                    Ok(remotes)
+                }
+            }
+        })
+    }
+
+    pub fn branches(&mut self) -> oneshot::Receiver<Result<Vec<Branch>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => backend.branches().await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitGetBranches {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    let branches = response
+                        .branches
+                        .into_iter()
+                        .map(|branch| proto_to_branch(&branch))
+                        .collect();
+
+                    Ok(branches)
+                }
+            }
+        })
+    }
+
+    pub fn diff(&mut self, diff_type: DiffType, _cx: &App) -> oneshot::Receiver<Result<String>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => backend.diff(diff_type).await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitDiff {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            diff_type: match diff_type {
+                                DiffType::HeadToIndex => {
+                                    proto::git_diff::DiffType::HeadToIndex.into()
+                                }
+                                DiffType::HeadToWorktree => {
+                                    proto::git_diff::DiffType::HeadToWorktree.into()
+                                }
+                            },
+                        })
+                        .await?;
+
+                    Ok(response.diff)
+                }
+            }
+        })
+    }
+
+    pub fn create_branch(&mut self, branch_name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git switch -c {branch_name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local { backend, .. } => {
+                        backend.create_branch(branch_name).await
+                    }
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitCreateBranch {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                branch_name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn change_branch(&mut self, branch_name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git switch {branch_name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local { backend, .. } => {
+                        backend.change_branch(branch_name).await
+                    }
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitChangeBranch {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                branch_name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn tags(&mut self) -> oneshot::Receiver<Result<Vec<Tag>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _| async move {
+            match repo {
+                RepositoryState::Local { backend, .. } => backend.tags().await,
+                RepositoryState::Remote { project_id, client } => {
+                    let response = client
+                        .request(proto::GitGetTags {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(response.tags.iter().map(proto_to_tag).collect())
+                }
+            }
+        })
+    }
+
+    pub fn create_tag(
+        &mut self,
+        tag_name: String,
+        message: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag {tag_name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.create_tag(tag_name, message, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitCreateTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                tag_name,
+                                message,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn delete_tag(&mut self, tag_name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag --delete {tag_name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.delete_tag(tag_name, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitDeleteTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                tag_name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn checkout_tag(&mut self, tag_name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git checkout --detach {tag_name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.checkout_tag(tag_name, environment).await,
+                    RepositoryState::Remote { project_id, client } => {
+                        client
+                            .request(proto::GitCheckoutTag {
                                 project_id: project_id.0,
                                 repository_id: id.to_proto(),
-                                askpass_id,
-                                branch_name: branch.to_string(),
-                                remote_name: remote.to_string(),
-                                options: options.map(|options| match options {
-                                    PushOptions::Force => proto::push::PushOptions::Force,
-                                    PushOptions::SetUpstream => {
-                                        proto::push::PushOptions::SetUpstream
-                                    }
-                                }
-                                    as i32),
+                                tag_name,
                             })
-                            .await
-                            .context("sending push request")?;
+                            .await?;
 
-                        Ok(RemoteCommandOutput {
-                            stdout: response.stdout,
-                            stderr: response.stderr,
-                        })
+                        Ok(())
                     }
                 }
             },
         )
     }
 
-    pub fn pull(
+    pub fn push_tag(
         &mut self,
-        branch: SharedString,
+        tag: SharedString,
         remote: SharedString,
         askpass: AskPassDelegate,
         _cx: &mut App,
@@ -3657,7 +4801,7 @@ impl Repository {
         let id = self.id;
 
         self.send_job(
-            Some(format!("git pull {} {}", remote, branch).into()),
+            Some(format!("git push {remote} {tag}").into()),
             move |git_repo, cx| async move {
                 match git_repo {
                     RepositoryState::Local {
@@ -3666,8 +4810,8 @@ impl Repository {
                         ..
                     } => {
                         backend
-                            .pull(
-                                branch.to_string(),
+                            .push_tag(
+                                tag.to_string(),
                                 remote.to_string(),
                                 askpass,
                                 environment.clone(),
@@ -3682,15 +4826,15 @@ impl Repository {
                             debug_assert!(askpass_delegate.is_some());
                         });
                         let response = client
-                            .request(proto::Pull {
+                            .request(proto::GitPushTag {
                                 project_id: project_id.0,
                                 repository_id: id.to_proto(),
                                 askpass_id,
-                                branch_name: branch.to_string(),
+                                tag_name: tag.to_string(),
                                 remote_name: remote.to_string(),
                             })
                             .await
-                            .context("sending pull request")?;
+                            .context("sending push tag request")?;
 
                         Ok(RemoteCommandOutput {
                             stdout: response.stdout,
@@ -3702,167 +4846,57 @@ impl Repository {
         )
     }
 
-    fn spawn_set_index_text_job(
-        &mut self,
-        path: RepoPath,
-        content: Option<String>,
-        hunk_staging_operation_count: Option<usize>,
-        cx: &mut Context<Self>,
-    ) -> oneshot::Receiver<anyhow::Result<()>> {
-        let id = self.id;
-        let this = cx.weak_entity();
-        let git_store = self.git_store.clone();
-        self.send_keyed_job(
-            Some(GitJobKey::WriteIndex(path.clone())),
-            None,
-            move |git_repo, mut cx| async move {
-                log::debug!("start updating index text for buffer {}", path.display());
-                match git_repo {
-                    RepositoryState::Local {
-                        backend,
-                        environment,
-                        ..
-                    } => {
-                        backend
-                            .set_index_text(path.clone(), content, environment.clone())
-                            .await?;
-                    }
-                    RepositoryState::Remote { project_id, client } => {
-                        client
-                            .request(proto::SetIndexText {
-                                project_id: project_id.0,
-                                repository_id: id.to_proto(),
-                                path: path.as_ref().to_proto(),
-                                text: content,
-                            })
-                            .await?;
-                    }
-                }
-                log::debug!("finish updating index text for buffer {}", path.display());
-
-                if let Some(hunk_staging_operation_count) = hunk_staging_operation_count {
-                    let project_path = this
-                        .read_with(&cx, |this, cx| this.repo_path_to_project_path(&path, cx))
-                        .ok()
-                        .flatten();
-                    git_store.update(&mut cx, |git_store, cx| {
-                        let buffer_id = git_store
-                            .buffer_store
-                            .read(cx)
-                            .get_by_path(&project_path?)?
-                            .read(cx)
-                            .remote_id();
-                        let diff_state = git_store.diffs.get(&buffer_id)?;
-                        diff_state.update(cx, |diff_state, _| {
-                            diff_state.hunk_staging_operation_count_as_of_write =
-                                hunk_staging_operation_count;
-                        });
-                        Some(())
-                    })?;
-                }
-                Ok(())
-            },
-        )
-    }
-
-    pub fn get_remotes(
-        &mut self,
-        branch_name: Option<String>,
-    ) -> oneshot::Receiver<Result<Vec<Remote>>> {
-        let id = self.id;
-        self.send_job(None, move |repo, _cx| async move {
-            match repo {
-                RepositoryState::Local { backend, .. } => backend.get_remotes(branch_name).await,
-                RepositoryState::Remote { project_id, client } => {
-                    let response = client
-                        .request(proto::GetRemotes {
-                            project_id: project_id.0,
-                            repository_id: id.to_proto(),
-                            branch_name,
-                        })
-                        .await?;
-
-                    let remotes = response
-                        .remotes
-                        .into_iter()
-                        .map(|remotes| git::repository::Remote {
-                            name: remotes.name.into(),
-                        })
-                        .collect();
-
-                    Ok(remotes)
-                }
-            }
-        })
-    }
-
-    pub fn branches(&mut self) -> oneshot::Receiver<Result<Vec<Branch>>> {
+    pub fn worktree_list(&mut self) -> oneshot::Receiver<Result<Vec<GitWorktree>>> {
         let id = self.id;
         self.send_job(None, move |repo, _| async move {
             match repo {
-                RepositoryState::Local { backend, .. } => backend.branches().await,
+                RepositoryState::Local { backend, .. } => backend.worktree_list().await,
                 RepositoryState::Remote { project_id, client } => {
                     let response = client
-                        .request(proto::GitGetBranches {
+                        .request(proto::GitWorktreeList {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
                         })
                         .await?;
 
-                    let branches = response
-                        .branches
+                    Ok(response
+                        .worktrees
                         .into_iter()
-                        .map(|branch| proto_to_branch(&branch))
-                        .collect();
-
-                    Ok(branches)
-                }
-            }
-        })
-    }
-
-    pub fn diff(&mut self, diff_type: DiffType, _cx: &App) -> oneshot::Receiver<Result<String>> {
-        let id = self.id;
-        self.send_job(None, move |repo, _cx| async move {
-            match repo {
-                RepositoryState::Local { backend, .. } => backend.diff(diff_type).await,
-                RepositoryState::Remote { project_id, client } => {
-                    let response = client
-                        .request(proto::GitDiff {
-                            project_id: project_id.0,
-                            repository_id: id.to_proto(),
-                            diff_type: match diff_type {
-                                DiffType::HeadToIndex => {
-                                    proto::git_diff::DiffType::HeadToIndex.into()
-                                }
-                                DiffType::HeadToWorktree => {
-                                    proto::git_diff::DiffType::HeadToWorktree.into()
-                                }
-                            },
-                        })
-                        .await?;
-
-                    Ok(response.diff)
+                        .map(proto_to_worktree)
+                        .collect())
                 }
             }
         })
     }
 
-    pub fn create_branch(&mut self, branch_name: String) -> oneshot::Receiver<Result<()>> {
+    pub fn worktree_add(
+        &mut self,
+        path: PathBuf,
+        branch_name: String,
+        create_branch: bool,
+    ) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
         self.send_job(
-            Some(format!("git switch -c {branch_name}").into()),
+            Some(format!("git worktree add {}", path.display()).into()),
             move |repo, _cx| async move {
                 match repo {
-                    RepositoryState::Local { backend, .. } => {
-                        backend.create_branch(branch_name).await
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => {
+                        backend
+                            .worktree_add(path, branch_name, create_branch, environment)
+                            .await
                     }
                     RepositoryState::Remote { project_id, client } => {
                         client
-                            .request(proto::GitCreateBranch {
+                            .request(proto::GitWorktreeAdd {
                                 project_id: project_id.0,
                                 repository_id: id.to_proto(),
+                                path: path.to_proto(),
                                 branch_name,
+                                create_branch,
                             })
                             .await?;
 
@@ -3873,21 +4907,24 @@ impl Repository {
         )
     }
 
-    pub fn change_branch(&mut self, branch_name: String) -> oneshot::Receiver<Result<()>> {
+    pub fn worktree_remove(&mut self, path: PathBuf, force: bool) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
         self.send_job(
-            Some(format!("git switch {branch_name}").into()),
+            Some(format!("git worktree remove {}", path.display()).into()),
             move |repo, _cx| async move {
                 match repo {
-                    RepositoryState::Local { backend, .. } => {
-                        backend.change_branch(branch_name).await
-                    }
+                    RepositoryState::Local {
+                        backend,
+                        environment,
+                        ..
+                    } => backend.worktree_remove(path, force, environment).await,
                     RepositoryState::Remote { project_id, client } => {
                         client
-                            .request(proto::GitChangeBranch {
+                            .request(proto::GitWorktreeRemove {
                                 project_id: project_id.0,
                                 repository_id: id.to_proto(),
-                                branch_name,
+                                path: path.to_proto(),
+                                force,
                             })
                             .await?;
 
@@ -3961,6 +4998,10 @@ impl Repository {
             .map(proto_to_commit_details);
 
         self.snapshot.merge.conflicted_paths = conflicted_paths;
+        self.snapshot.merge.operation = update
+            .in_progress_operation
+            .and_then(proto::GitOperation::from_i32)
+            .map(proto_to_operation);
 
         let edits = update
             .removed_statuses
@@ -4033,9 +5074,15 @@ impl Repository {
                 let Some(this) = this.upgrade() else {
                     return Ok(());
                 };
-                let RepositoryState::Local { backend, .. } = state else {
+                let RepositoryState::Local {
+                    backend,
+                    jj_repository,
+                    ..
+                } = state
+                else {
                     bail!("not a local repository")
                 };
+                let executor = cx.background_executor().clone();
                 let (snapshot, events) = this
                     .read_with(&mut cx, |this, _| {
                         compute_snapshot(
@@ -4043,6 +5090,8 @@ impl Repository {
                             this.work_directory_abs_path.clone(),
                             this.snapshot.clone(),
                             backend.clone(),
+                            jj_repository.clone(),
+                            executor,
                         )
                     })?
                     .await?;
@@ -4085,10 +5134,19 @@ impl Repository {
                     log::error!("failed to get working directory environment for repository {work_directory_abs_path:?}");
                     HashMap::default()
                 });
-            let backend = cx
-                .background_spawn(async move {
-                    fs.open_repo(&dot_git_abs_path)
-                        .with_context(|| format!("opening repository at {dot_git_abs_path:?}"))
+            // jj workspaces are only picked up when they're colocated with a git repository,
+            // since repositories are discovered by their `.git` and every other operation still
+            // goes through the git backend.
+            let (backend, jj_repository) = cx
+                .background_spawn({
+                    let work_directory_abs_path = work_directory_abs_path.clone();
+                    async move {
+                        let backend = fs.open_repo(&dot_git_abs_path).with_context(|| {
+                            format!("opening repository at {dot_git_abs_path:?}")
+                        })?;
+                        let jj_repository = fs.open_jj_repo(&work_directory_abs_path);
+                        anyhow::Ok((backend, jj_repository))
+                    }
                 })
                 .await?;
 
@@ -4104,6 +5162,7 @@ impl Repository {
             let state = RepositoryState::Local {
                 backend,
                 environment: Arc::new(environment),
+                jj_repository,
             };
             let mut jobs = VecDeque::new();
             loop {
@@ -4178,11 +5237,21 @@ impl Repository {
         repo_path: RepoPath,
         cx: &App,
     ) -> Task<Result<Option<String>>> {
-        let rx = self.send_job(None, move |state, _| async move {
+        let rx = self.send_job(None, move |state, cx| async move {
             match state {
-                RepositoryState::Local { backend, .. } => {
-                    anyhow::Ok(backend.load_index_text(repo_path).await)
-                }
+                RepositoryState::Local {
+                    backend,
+                    jj_repository,
+                    ..
+                } => anyhow::Ok(
+                    load_index_text(
+                        &backend,
+                        jj_repository.as_ref(),
+                        repo_path,
+                        cx.background_executor(),
+                    )
+                    .await,
+                ),
                 RepositoryState::Remote { project_id, client } => {
                     let response = client
                         .request(proto::OpenUnstagedDiff {
@@ -4203,11 +5272,24 @@ impl Repository {
         repo_path: RepoPath,
         cx: &App,
     ) -> Task<Result<DiffBasesChange>> {
-        let rx = self.send_job(None, move |state, _| async move {
+        let rx = self.send_job(None, move |state, cx| async move {
             match state {
-                RepositoryState::Local { backend, .. } => {
-                    let committed_text = backend.load_committed_text(repo_path.clone()).await;
-                    let staged_text = backend.load_index_text(repo_path).await;
+                RepositoryState::Local {
+                    backend,
+                    jj_repository,
+                    ..
+                } => {
+                    let executor = cx.background_executor();
+                    let committed_text = load_committed_text(
+                        &backend,
+                        jj_repository.as_ref(),
+                        repo_path.clone(),
+                        executor,
+                    )
+                    .await;
+                    let staged_text =
+                        load_index_text(&backend, jj_repository.as_ref(), repo_path, executor)
+                            .await;
                     let diff_bases_change = if committed_text == staged_text {
                         DiffBasesChange::SetBoth(committed_text)
                     } else {
@@ -4262,12 +5344,23 @@ impl Repository {
                         mem::take(&mut this.paths_needing_status_update),
                     )
                 })?;
-                let RepositoryState::Local { backend, .. } = state else {
+                let RepositoryState::Local {
+                    backend,
+                    jj_repository,
+                    ..
+                } = state
+                else {
                     bail!("not a local repository")
                 };
 
                 let paths = changed_paths.iter().cloned().collect::<Vec<_>>();
-                let statuses = backend.status(&paths).await?;
+                let statuses = load_status(
+                    &backend,
+                    jj_repository.as_ref(),
+                    paths,
+                    cx.background_executor(),
+                )
+                .await?;
 
                 let changed_path_statuses = cx
                     .background_spawn(async move {
@@ -4467,6 +5560,47 @@ fn deserialize_blame_buffer_response(
     })
 }
 
+fn worktree_to_proto(worktree: &GitWorktree) -> proto::GitWorktree {
+    proto::GitWorktree {
+        path: worktree.path.as_path().to_proto(),
+        ref_name: worktree
+            .ref_name
+            .as_ref()
+            .map(|ref_name| ref_name.to_string()),
+        sha: worktree.sha.to_string(),
+        is_main: worktree.is_main,
+        is_locked: worktree.is_locked,
+    }
+}
+
+fn proto_to_worktree(proto: proto::GitWorktree) -> GitWorktree {
+    GitWorktree {
+        path: PathBuf::from_proto(proto.path),
+        ref_name: proto.ref_name.map(Into::into),
+        sha: proto.sha.into(),
+        is_main: proto.is_main,
+        is_locked: proto.is_locked,
+    }
+}
+
+fn tag_to_proto(tag: &Tag) -> proto::GitTag {
+    proto::GitTag {
+        name: tag.name.to_string(),
+        sha: tag.sha.to_string(),
+        message: tag.message.as_ref().map(|message| message.to_string()),
+        timestamp: tag.timestamp,
+    }
+}
+
+fn proto_to_tag(proto: &proto::GitTag) -> Tag {
+    Tag {
+        name: proto.name.clone().into(),
+        sha: proto.sha.clone().into(),
+        message: proto.message.clone().map(Into::into),
+        timestamp: proto.timestamp,
+    }
+}
+
 fn branch_to_proto(branch: &git::repository::Branch) -> proto::Branch {
     proto::Branch {
         is_head: branch.is_head,
@@ -4534,6 +5668,7 @@ fn commit_details_to_proto(commit: &CommitDetails) -> proto::GitCommitDetails {
         commit_timestamp: commit.commit_timestamp,
         author_email: commit.author_email.to_string(),
         author_name: commit.author_name.to_string(),
+        parent_count: commit.parent_count as u32,
     }
 }
 
@@ -4544,6 +5679,101 @@ fn proto_to_commit_details(proto: &proto::GitCommitDetails) -> CommitDetails {
         commit_timestamp: proto.commit_timestamp,
         author_email: proto.author_email.clone().into(),
         author_name: proto.author_name.clone().into(),
+        parent_count: proto.parent_count as usize,
+    }
+}
+
+fn operation_to_proto(operation: InProgressOperation) -> proto::GitOperation {
+    match operation {
+        InProgressOperation::Merge => proto::GitOperation::Merge,
+        InProgressOperation::CherryPick => proto::GitOperation::CherryPick,
+        InProgressOperation::Revert => proto::GitOperation::Revert,
+        InProgressOperation::Rebase => proto::GitOperation::Rebase,
+    }
+}
+
+fn proto_to_operation(operation: proto::GitOperation) -> InProgressOperation {
+    match operation {
+        proto::GitOperation::Merge => InProgressOperation::Merge,
+        proto::GitOperation::CherryPick => InProgressOperation::CherryPick,
+        proto::GitOperation::Revert => InProgressOperation::Revert,
+        proto::GitOperation::Rebase => InProgressOperation::Rebase,
+    }
+}
+
+fn stash_entry_to_proto(entry: &StashEntry) -> proto::StashEntry {
+    proto::StashEntry {
+        index: entry.index as u64,
+        sha: entry.sha.to_string(),
+        message: entry.message.to_string(),
+        branch: entry.branch.as_ref().map(|branch| branch.to_string()),
+        timestamp: entry.timestamp,
+    }
+}
+
+fn proto_to_stash_entry(proto: &proto::StashEntry) -> StashEntry {
+    StashEntry {
+        index: proto.index as usize,
+        sha: proto.sha.clone().into(),
+        message: proto.message.clone().into(),
+        branch: proto.branch.clone().map(SharedString::from),
+        timestamp: proto.timestamp,
+    }
+}
+
+/// Loads the text `repo_path` is compared against for unstaged changes. jj has no index, so in a
+/// jj workspace this is the parent of the working-copy change, like the committed text.
+async fn load_index_text(
+    backend: &Arc<dyn GitRepository>,
+    jj_repository: Option<&Arc<dyn JujutsuRepository>>,
+    repo_path: RepoPath,
+    executor: &BackgroundExecutor,
+) -> Option<String> {
+    match jj_repository {
+        Some(jj_repository) => load_jj_base_text(jj_repository, repo_path, executor).await,
+        None => backend.load_index_text(repo_path).await,
+    }
+}
+
+async fn load_committed_text(
+    backend: &Arc<dyn GitRepository>,
+    jj_repository: Option<&Arc<dyn JujutsuRepository>>,
+    repo_path: RepoPath,
+    executor: &BackgroundExecutor,
+) -> Option<String> {
+    match jj_repository {
+        Some(jj_repository) => load_jj_base_text(jj_repository, repo_path, executor).await,
+        None => backend.load_committed_text(repo_path).await,
+    }
+}
+
+async fn load_jj_base_text(
+    jj_repository: &Arc<dyn JujutsuRepository>,
+    repo_path: RepoPath,
+    executor: &BackgroundExecutor,
+) -> Option<String> {
+    let jj_repository = jj_repository.clone();
+    executor
+        .spawn(async move { jj_repository.load_base_text(&repo_path) })
+        .await
+        .log_err()
+        .flatten()
+}
+
+async fn load_status(
+    backend: &Arc<dyn GitRepository>,
+    jj_repository: Option<&Arc<dyn JujutsuRepository>>,
+    path_prefixes: Vec<RepoPath>,
+    executor: &BackgroundExecutor,
+) -> Result<GitStatus> {
+    match jj_repository {
+        Some(jj_repository) => {
+            let jj_repository = jj_repository.clone();
+            executor
+                .spawn(async move { jj_repository.status(&path_prefixes) })
+                .await
+        }
+        None => backend.status(&path_prefixes).await,
     }
 }
 
@@ -4552,13 +5782,19 @@ async fn compute_snapshot(
     work_directory_abs_path: Arc<Path>,
     prev_snapshot: RepositorySnapshot,
     backend: Arc<dyn GitRepository>,
+    jj_repository: Option<Arc<dyn JujutsuRepository>>,
+    executor: BackgroundExecutor,
 ) -> Result<(RepositorySnapshot, Vec<RepositoryEvent>)> {
     let mut events = Vec::new();
     let branches = backend.branches().await?;
     let branch = branches.into_iter().find(|branch| branch.is_head);
-    let statuses = backend
-        .status(std::slice::from_ref(&WORK_DIRECTORY_REPO_PATH))
-        .await?;
+    let statuses = load_status(
+        &backend,
+        jj_repository.as_ref(),
+        vec![WORK_DIRECTORY_REPO_PATH.clone()],
+        &executor,
+    )
+    .await?;
     let statuses_by_path = SumTree::from_iter(
         statuses
             .entries
@@ -4574,6 +5810,7 @@ async fn compute_snapshot(
     log::debug!("new merge details (changed={merge_heads_changed:?}): {merge_details:?}");
 
     if merge_heads_changed
+        || merge_details.operation != prev_snapshot.merge.operation
         || branch != prev_snapshot.branch
         || statuses_by_path != prev_snapshot.statuses_by_path
     {
@@ -2,10 +2,11 @@ mod signature_help;
 
 use crate::{
     CodeAction, CompletionSource, CoreCompletion, CoreCompletionResponse, DocumentColor,
-    DocumentHighlight, DocumentSymbol, Hover, HoverBlock, HoverBlockKind, InlayHint,
-    InlayHintLabel, InlayHintLabelPart, InlayHintLabelPartTooltip, InlayHintTooltip, Location,
-    LocationLink, LspAction, LspPullDiagnostics, MarkupContent, PrepareRenameResponse,
-    ProjectTransaction, PulledDiagnostics, ResolveState,
+    DocumentHighlight, DocumentLink, DocumentSymbol, FoldingRange, FoldingRangeKind, HierarchyItem,
+    Hover, HoverBlock, HoverBlockKind, InlayHint, InlayHintLabel, InlayHintLabelPart,
+    InlayHintLabelPartTooltip, InlayHintTooltip, Location, LocationLink, LspAction,
+    LspPullDiagnostics, MarkupContent, PrepareRenameResponse, ProjectTransaction,
+    PulledDiagnostics, ResolveState, SemanticTokens,
     lsp_store::{LocalLspStore, LspStore},
 };
 use anyhow::{Context as _, Result};
@@ -16,8 +17,8 @@ use collections::{HashMap, HashSet};
 use futures::future;
 use gpui::{App, AsyncApp, Entity, Task};
 use language::{
-    Anchor, Bias, Buffer, BufferSnapshot, CachedLspAdapter, CharKind, OffsetRangeExt, PointUtf16,
-    ToOffset, ToPointUtf16, Transaction, Unclipped,
+    Anchor, Bias, Buffer, BufferSnapshot, CachedLspAdapter, CharKind, OffsetRangeExt, Point,
+    PointUtf16, ToOffset, ToPointUtf16, Transaction, Unclipped,
     language_settings::{InlayHintKind, LanguageSettings, language_settings},
     point_from_lsp, point_to_lsp,
     proto::{deserialize_anchor, deserialize_version, serialize_anchor, serialize_version},
@@ -85,6 +86,11 @@ pub trait LspCommand: 'static + Sized + Send + std::fmt::Debug {
         None
     }
 
+    /// The language server that must handle the request, e.g. the one that produced an item the request refers to.
+    fn language_server_id(&self) -> Option<LanguageServerId> {
+        None
+    }
+
     fn to_lsp_params_or_response(
         &self,
         path: &Path,
@@ -245,6 +251,70 @@ pub(crate) struct GetCodeLens;
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct GetDocumentColor;
 
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct GetFoldingRanges;
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct GetDocumentLinks;
+
+#[derive(Debug)]
+pub(crate) struct ResolveDocumentLink {
+    pub link: DocumentLink,
+}
+
+#[derive(Debug)]
+pub(crate) struct GetSelectionRanges {
+    pub positions: Vec<PointUtf16>,
+}
+
+#[derive(Debug)]
+pub(crate) struct PrepareCallHierarchy {
+    pub position: PointUtf16,
+}
+
+#[derive(Debug)]
+pub(crate) struct GetIncomingCalls {
+    pub item: HierarchyItem,
+}
+
+#[derive(Debug)]
+pub(crate) struct GetOutgoingCalls {
+    pub item: HierarchyItem,
+}
+
+#[derive(Debug)]
+pub(crate) struct PrepareTypeHierarchy {
+    pub position: PointUtf16,
+}
+
+#[derive(Debug)]
+pub(crate) struct GetSupertypes {
+    pub item: HierarchyItem,
+}
+
+#[derive(Debug)]
+pub(crate) struct GetSubtypes {
+    pub item: HierarchyItem,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GetSemanticTokensFull {
+    pub legend: Arc<lsp::SemanticTokensLegend>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GetSemanticTokensDelta {
+    pub legend: Arc<lsp::SemanticTokensLegend>,
+    pub previous_result_id: String,
+    pub previous_data: Arc<[u32]>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GetSemanticTokensRange {
+    pub legend: Arc<lsp::SemanticTokensLegend>,
+    pub range: Range<Anchor>,
+}
+
 impl GetCodeLens {
     pub(crate) fn can_resolve_lens(capabilities: &ServerCapabilities) -> bool {
         capabilities
@@ -4344,133 +4414,1925 @@ impl LspCommand for GetDocumentColor {
     }
 }
 
-fn process_related_documents(
-    diagnostics: &mut HashMap<lsp::Url, LspPullDiagnostics>,
-    server_id: LanguageServerId,
-    documents: impl IntoIterator<Item = (lsp::Url, lsp::DocumentDiagnosticReportKind)>,
-) {
-    for (url, report_kind) in documents {
-        match report_kind {
-            lsp::DocumentDiagnosticReportKind::Full(report) => {
-                process_full_diagnostics_report(diagnostics, server_id, url, report)
-            }
-            lsp::DocumentDiagnosticReportKind::Unchanged(report) => {
-                process_unchanged_diagnostics_report(diagnostics, server_id, url, report)
-            }
+#[async_trait(?Send)]
+impl LspCommand for GetFoldingRanges {
+    type Response = Vec<FoldingRange>;
+    type LspRequest = lsp::request::FoldingRangeRequest;
+    type ProtoRequest = proto::GetFoldingRanges;
+
+    fn display_name(&self) -> &str {
+        "Folding ranges"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        match &capabilities.server_capabilities.folding_range_provider {
+            Some(lsp::FoldingRangeProviderCapability::Simple(supported)) => *supported,
+            Some(_) => true,
+            None => false,
         }
     }
-}
 
-fn process_unchanged_diagnostics_report(
-    diagnostics: &mut HashMap<lsp::Url, LspPullDiagnostics>,
-    server_id: LanguageServerId,
-    uri: lsp::Url,
-    report: lsp::UnchangedDocumentDiagnosticReport,
-) {
-    let result_id = report.result_id;
-    match diagnostics.entry(uri.clone()) {
-        hash_map::Entry::Occupied(mut o) => match o.get_mut() {
-            LspPullDiagnostics::Default => {
-                o.insert(LspPullDiagnostics::Response {
-                    server_id,
-                    uri,
-                    diagnostics: PulledDiagnostics::Unchanged { result_id },
-                });
-            }
-            LspPullDiagnostics::Response {
-                server_id: existing_server_id,
-                uri: existing_uri,
-                diagnostics: existing_diagnostics,
-            } => {
-                if server_id != *existing_server_id || &uri != existing_uri {
-                    debug_panic!(
-                        "Unexpected state: file {uri} has two different sets of diagnostics reported"
-                    );
-                }
-                match existing_diagnostics {
-                    PulledDiagnostics::Unchanged { .. } => {
-                        *existing_diagnostics = PulledDiagnostics::Unchanged { result_id };
-                    }
-                    PulledDiagnostics::Changed { .. } => {}
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::FoldingRangeParams> {
+        Ok(lsp::FoldingRangeParams {
+            text_document: make_text_document_identifier(path)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::FoldingRange>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<FoldingRange>> {
+        let snapshot = buffer.read_with(&mut cx, |buffer, _| buffer.snapshot())?;
+        let max_row = snapshot.max_point().row;
+        let mut folding_ranges = message
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|range| range.start_line < range.end_line && range.end_line <= max_row)
+            .map(|range| {
+                let start = Point::new(range.start_line, snapshot.line_len(range.start_line));
+                let end = Point::new(range.end_line, snapshot.line_len(range.end_line));
+                FoldingRange {
+                    range: snapshot.anchor_after(start)..snapshot.anchor_before(end),
+                    kind: range.kind.map(|kind| match kind {
+                        lsp::FoldingRangeKind::Comment => FoldingRangeKind::Comment,
+                        lsp::FoldingRangeKind::Imports => FoldingRangeKind::Imports,
+                        lsp::FoldingRangeKind::Region => FoldingRangeKind::Region,
+                    }),
                 }
-            }
-        },
-        hash_map::Entry::Vacant(v) => {
-            v.insert(LspPullDiagnostics::Response {
-                server_id,
-                uri,
-                diagnostics: PulledDiagnostics::Unchanged { result_id },
-            });
+            })
+            .collect::<Vec<_>>();
+        folding_ranges.sort_by(|a, b| {
+            a.range
+                .start
+                .cmp(&b.range.start, &snapshot)
+                .then_with(|| b.range.end.cmp(&a.range.end, &snapshot))
+        });
+        Ok(folding_ranges)
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetFoldingRanges {
+        proto::GetFoldingRanges {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            version: serialize_version(&buffer.version()),
         }
     }
-}
 
-fn process_full_diagnostics_report(
-    diagnostics: &mut HashMap<lsp::Url, LspPullDiagnostics>,
-    server_id: LanguageServerId,
-    uri: lsp::Url,
-    report: lsp::FullDocumentDiagnosticReport,
-) {
-    let result_id = report.result_id;
-    match diagnostics.entry(uri.clone()) {
-        hash_map::Entry::Occupied(mut o) => match o.get_mut() {
-            LspPullDiagnostics::Default => {
-                o.insert(LspPullDiagnostics::Response {
-                    server_id,
-                    uri,
-                    diagnostics: PulledDiagnostics::Changed {
-                        result_id,
-                        diagnostics: report.items,
-                    },
-                });
-            }
-            LspPullDiagnostics::Response {
-                server_id: existing_server_id,
-                uri: existing_uri,
-                diagnostics: existing_diagnostics,
-            } => {
-                if server_id != *existing_server_id || &uri != existing_uri {
-                    debug_panic!(
-                        "Unexpected state: file {uri} has two different sets of diagnostics reported"
-                    );
-                }
-                match existing_diagnostics {
-                    PulledDiagnostics::Unchanged { .. } => {
-                        *existing_diagnostics = PulledDiagnostics::Changed {
-                            result_id,
-                            diagnostics: report.items,
-                        };
-                    }
-                    PulledDiagnostics::Changed {
-                        result_id: existing_result_id,
-                        diagnostics: existing_diagnostics,
-                    } => {
-                        if result_id.is_some() {
-                            *existing_result_id = result_id;
+    async fn from_proto(
+        message: proto::GetFoldingRanges,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self)
+    }
+
+    fn response_to_proto(
+        response: Vec<FoldingRange>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetFoldingRangesResponse {
+        proto::GetFoldingRangesResponse {
+            ranges: response
+                .into_iter()
+                .map(|range| proto::FoldingRange {
+                    start: Some(serialize_anchor(&range.range.start)),
+                    end: Some(serialize_anchor(&range.range.end)),
+                    kind: range.kind.map(|kind| {
+                        match kind {
+                            FoldingRangeKind::Comment => proto::folding_range::Kind::Comment,
+                            FoldingRangeKind::Imports => proto::folding_range::Kind::Imports,
+                            FoldingRangeKind::Region => proto::folding_range::Kind::Region,
                         }
-                        existing_diagnostics.extend(report.items);
-                    }
-                }
-            }
-        },
-        hash_map::Entry::Vacant(v) => {
-            v.insert(LspPullDiagnostics::Response {
-                server_id,
-                uri,
-                diagnostics: PulledDiagnostics::Changed {
-                    result_id,
-                    diagnostics: report.items,
-                },
-            });
+                        .into()
+                    }),
+                })
+                .collect(),
+            version: serialize_version(buffer_version),
         }
     }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetFoldingRangesResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<FoldingRange>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        message
+            .ranges
+            .into_iter()
+            .map(|range| {
+                let start = range
+                    .start
+                    .and_then(deserialize_anchor)
+                    .context("invalid folding range start")?;
+                let end = range
+                    .end
+                    .and_then(deserialize_anchor)
+                    .context("invalid folding range end")?;
+                let kind = match range.kind.map(proto::folding_range::Kind::from_i32) {
+                    Some(Some(proto::folding_range::Kind::Comment)) => {
+                        Some(FoldingRangeKind::Comment)
+                    }
+                    Some(Some(proto::folding_range::Kind::Imports)) => {
+                        Some(FoldingRangeKind::Imports)
+                    }
+                    Some(Some(proto::folding_range::Kind::Region)) => {
+                        Some(FoldingRangeKind::Region)
+                    }
+                    _ => None,
+                };
+                Ok(FoldingRange {
+                    range: start..end,
+                    kind,
+                })
+            })
+            .collect()
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetFoldingRanges) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use lsp::{DiagnosticSeverity, DiagnosticTag};
-    use serde_json::json;
+#[async_trait(?Send)]
+impl LspCommand for GetDocumentLinks {
+    type Response = Vec<DocumentLink>;
+    type LspRequest = lsp::request::DocumentLinkRequest;
+    type ProtoRequest = proto::GetDocumentLinks;
+
+    fn display_name(&self) -> &str {
+        "Document links"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        capabilities
+            .server_capabilities
+            .document_link_provider
+            .is_some()
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::DocumentLinkParams> {
+        Ok(lsp::DocumentLinkParams {
+            text_document: make_text_document_identifier(path)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::DocumentLink>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<DocumentLink>> {
+        let snapshot = buffer.read_with(&mut cx, |buffer, _| buffer.snapshot())?;
+        message
+            .unwrap_or_default()
+            .into_iter()
+            .map(|link| {
+                let start = snapshot.clip_point_utf16(point_from_lsp(link.range.start), Bias::Left);
+                let end = snapshot.clip_point_utf16(point_from_lsp(link.range.end), Bias::Left);
+                Ok(DocumentLink {
+                    range: snapshot.anchor_after(start)..snapshot.anchor_before(end),
+                    target: link.target.clone(),
+                    language_server_id: server_id,
+                    lsp_link: serde_json::to_value(link)?,
+                })
+            })
+            .collect()
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetDocumentLinks {
+        proto::GetDocumentLinks {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetDocumentLinks,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self)
+    }
+
+    fn response_to_proto(
+        response: Vec<DocumentLink>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetDocumentLinksResponse {
+        proto::GetDocumentLinksResponse {
+            links: response.iter().map(serialize_document_link).collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetDocumentLinksResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<DocumentLink>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        message
+            .links
+            .into_iter()
+            .map(deserialize_document_link)
+            .collect()
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetDocumentLinks) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for ResolveDocumentLink {
+    type Response = Option<lsp::Url>;
+    type LspRequest = lsp::request::DocumentLinkResolve;
+    type ProtoRequest = proto::ResolveDocumentLink;
+
+    fn display_name(&self) -> &str {
+        "Resolve document link"
+    }
+
+    fn language_server_id(&self) -> Option<LanguageServerId> {
+        Some(self.link.language_server_id)
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        capabilities
+            .server_capabilities
+            .document_link_provider
+            .as_ref()
+            .is_some_and(|options| options.resolve_provider == Some(true))
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::DocumentLink> {
+        Ok(serde_json::from_value(self.link.lsp_link.clone())?)
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: lsp::DocumentLink,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: LanguageServerId,
+        _: AsyncApp,
+    ) -> Result<Option<lsp::Url>> {
+        Ok(message.target)
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::ResolveDocumentLink {
+        proto::ResolveDocumentLink {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            link: Some(serialize_document_link(&self.link)),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::ResolveDocumentLink,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        let link = message.link.context("missing document link")?;
+        Ok(Self {
+            link: deserialize_document_link(link)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Option<lsp::Url>,
+        _: &mut LspStore,
+        _: PeerId,
+        _: &clock::Global,
+        _: &mut App,
+    ) -> proto::ResolveDocumentLinkResponse {
+        proto::ResolveDocumentLinkResponse {
+            target: response.map(|target| target.to_string()),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::ResolveDocumentLinkResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Option<lsp::Url>> {
+        message
+            .target
+            .map(|target| lsp::Url::parse(&target).context("invalid document link target"))
+            .transpose()
+    }
+
+    fn buffer_id_from_proto(message: &proto::ResolveDocumentLink) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+fn serialize_document_link(link: &DocumentLink) -> proto::DocumentLink {
+    proto::DocumentLink {
+        start: Some(serialize_anchor(&link.range.start)),
+        end: Some(serialize_anchor(&link.range.end)),
+        target: link.target.as_ref().map(|target| target.to_string()),
+        language_server_id: link.language_server_id.to_proto(),
+        lsp_link: link.lsp_link.to_string(),
+    }
+}
+
+fn deserialize_document_link(link: proto::DocumentLink) -> Result<DocumentLink> {
+    let start = link
+        .start
+        .and_then(deserialize_anchor)
+        .context("invalid document link start")?;
+    let end = link
+        .end
+        .and_then(deserialize_anchor)
+        .context("invalid document link end")?;
+    let target = link
+        .target
+        .map(|target| lsp::Url::parse(&target).context("invalid document link target"))
+        .transpose()?;
+    Ok(DocumentLink {
+        range: start..end,
+        target,
+        language_server_id: LanguageServerId::from_proto(link.language_server_id),
+        lsp_link: serde_json::from_str(&link.lsp_link)?,
+    })
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSelectionRanges {
+    type Response = Vec<Vec<Range<Anchor>>>;
+    type LspRequest = lsp::request::SelectionRangeRequest;
+    type ProtoRequest = proto::GetSelectionRanges;
+
+    fn display_name(&self) -> &str {
+        "Selection ranges"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        match &capabilities.server_capabilities.selection_range_provider {
+            Some(lsp::SelectionRangeProviderCapability::Simple(supported)) => *supported,
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::SelectionRangeParams> {
+        Ok(lsp::SelectionRangeParams {
+            text_document: make_text_document_identifier(path)?,
+            positions: self.positions.iter().copied().map(point_to_lsp).collect(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::SelectionRange>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<Vec<Range<Anchor>>>> {
+        let snapshot = buffer.read_with(&mut cx, |buffer, _| buffer.snapshot())?;
+        Ok(message
+            .unwrap_or_default()
+            .into_iter()
+            .map(|selection_range| {
+                // Flatten the chain of parents, from the innermost range to the outermost one.
+                let mut ranges = Vec::new();
+                let mut selection_range = Some(Box::new(selection_range));
+                while let Some(range) = selection_range {
+                    let start =
+                        snapshot.clip_point_utf16(point_from_lsp(range.range.start), Bias::Left);
+                    let end =
+                        snapshot.clip_point_utf16(point_from_lsp(range.range.end), Bias::Left);
+                    ranges.push(snapshot.anchor_before(start)..snapshot.anchor_after(end));
+                    selection_range = range.parent;
+                }
+                ranges
+            })
+            .collect())
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSelectionRanges {
+        proto::GetSelectionRanges {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            positions: self
+                .positions
+                .iter()
+                .map(|position| serialize_anchor(&buffer.anchor_before(*position)))
+                .collect(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSelectionRanges,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let positions = message
+            .positions
+            .into_iter()
+            .map(|position| deserialize_anchor(position).context("invalid position"))
+            .collect::<Result<Vec<_>>>()?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self {
+            positions: buffer.read_with(&mut cx, |buffer, _| {
+                positions
+                    .iter()
+                    .map(|position| position.to_point_utf16(buffer))
+                    .collect()
+            })?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<Vec<Range<Anchor>>>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetSelectionRangesResponse {
+        proto::GetSelectionRangesResponse {
+            selection_ranges: response
+                .into_iter()
+                .map(|ranges| proto::SelectionRanges {
+                    ranges: ranges
+                        .into_iter()
+                        .map(|range| proto::AnchorRange {
+                            start: Some(serialize_anchor(&range.start)),
+                            end: Some(serialize_anchor(&range.end)),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSelectionRangesResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<Vec<Range<Anchor>>>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        message
+            .selection_ranges
+            .into_iter()
+            .map(|selection_ranges| {
+                selection_ranges
+                    .ranges
+                    .into_iter()
+                    .map(|range| {
+                        let start = range
+                            .start
+                            .and_then(deserialize_anchor)
+                            .context("invalid selection range start")?;
+                        let end = range
+                            .end
+                            .and_then(deserialize_anchor)
+                            .context("invalid selection range end")?;
+                        Ok(start..end)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSelectionRanges) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+/// The parts of a call or type hierarchy item that are needed to present it.
+struct LspHierarchyItem {
+    name: String,
+    kind: lsp::SymbolKind,
+    detail: Option<String>,
+    uri: lsp::Url,
+    selection_range: lsp::Range,
+    lsp_item: Value,
+}
+
+impl LspHierarchyItem {
+    fn from_call_item(item: lsp::CallHierarchyItem) -> Result<Self> {
+        Ok(Self {
+            name: item.name.clone(),
+            kind: item.kind,
+            detail: item.detail.clone(),
+            uri: item.uri.clone(),
+            selection_range: item.selection_range,
+            lsp_item: serde_json::to_value(item)?,
+        })
+    }
+
+    fn from_type_item(item: lsp::TypeHierarchyItem) -> Result<Self> {
+        Ok(Self {
+            name: item.name.clone(),
+            kind: item.kind,
+            detail: item.detail.clone(),
+            uri: item.uri.clone(),
+            selection_range: item.selection_range,
+            lsp_item: serde_json::to_value(item)?,
+        })
+    }
+}
+
+async fn hierarchy_items_from_lsp(
+    items: Vec<LspHierarchyItem>,
+    lsp_store: &Entity<LspStore>,
+    buffer: &Entity<Buffer>,
+    server_id: LanguageServerId,
+    cx: &mut AsyncApp,
+) -> Result<Vec<HierarchyItem>> {
+    if items.is_empty() {
+        return Ok(Vec::new());
+    }
+    let (lsp_adapter, language_server) =
+        language_server_for_buffer(lsp_store, buffer, server_id, cx)?;
+
+    let mut hierarchy_items = Vec::with_capacity(items.len());
+    for item in items {
+        let target_buffer = lsp_store
+            .update(cx, |lsp_store, cx| {
+                lsp_store.open_local_buffer_via_lsp(
+                    item.uri,
+                    language_server.server_id(),
+                    lsp_adapter.name.clone(),
+                    cx,
+                )
+            })?
+            .await?;
+        let range = target_buffer.read_with(cx, |target_buffer, _| {
+            let start = target_buffer
+                .clip_point_utf16(point_from_lsp(item.selection_range.start), Bias::Left);
+            let end = target_buffer
+                .clip_point_utf16(point_from_lsp(item.selection_range.end), Bias::Left);
+            target_buffer.anchor_after(start)..target_buffer.anchor_before(end)
+        })?;
+        hierarchy_items.push(HierarchyItem {
+            name: item.name,
+            kind: item.kind,
+            detail: item.detail,
+            location: Location {
+                buffer: target_buffer,
+                range,
+            },
+            language_server_id: server_id,
+            lsp_item: item.lsp_item,
+        });
+    }
+    Ok(hierarchy_items)
+}
+
+fn serialize_hierarchy_item(item: &HierarchyItem, buffer_id: BufferId) -> proto::HierarchyItem {
+    proto::HierarchyItem {
+        location: Some(proto::Location {
+            buffer_id: buffer_id.into(),
+            start: Some(serialize_anchor(&item.location.range.start)),
+            end: Some(serialize_anchor(&item.location.range.end)),
+        }),
+        language_server_id: item.language_server_id.to_proto(),
+        lsp_item: item.lsp_item.to_string(),
+    }
+}
+
+/// Deserializes an item that is located in the given buffer.
+fn deserialize_hierarchy_item(
+    item: proto::HierarchyItem,
+    buffer: Entity<Buffer>,
+) -> Result<HierarchyItem> {
+    let location = item.location.context("missing hierarchy item location")?;
+    let start = location
+        .start
+        .and_then(deserialize_anchor)
+        .context("missing hierarchy item start")?;
+    let end = location
+        .end
+        .and_then(deserialize_anchor)
+        .context("missing hierarchy item end")?;
+    let lsp_item = serde_json::from_str::<Value>(&item.lsp_item)?;
+    let name = lsp_item
+        .get("name")
+        .and_then(Value::as_str)
+        .context("missing hierarchy item name")?
+        .to_owned();
+    let kind = serde_json::from_value(
+        lsp_item
+            .get("kind")
+            .cloned()
+            .context("missing hierarchy item kind")?,
+    )?;
+    let detail = lsp_item
+        .get("detail")
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned);
+    Ok(HierarchyItem {
+        name,
+        kind,
+        detail,
+        location: Location {
+            buffer,
+            range: start..end,
+        },
+        language_server_id: LanguageServerId::from_proto(item.language_server_id),
+        lsp_item,
+    })
+}
+
+fn hierarchy_items_to_proto(
+    items: Vec<HierarchyItem>,
+    lsp_store: &mut LspStore,
+    peer_id: PeerId,
+    cx: &mut App,
+) -> proto::HierarchyItemsResponse {
+    let items = items
+        .into_iter()
+        .map(|item| {
+            lsp_store
+                .buffer_store()
+                .update(cx, |buffer_store, cx| {
+                    buffer_store.create_buffer_for_peer(&item.location.buffer, peer_id, cx)
+                })
+                .detach_and_log_err(cx);
+            serialize_hierarchy_item(&item, item.location.buffer.read(cx).remote_id())
+        })
+        .collect();
+    proto::HierarchyItemsResponse { items }
+}
+
+async fn hierarchy_items_from_proto(
+    message: proto::HierarchyItemsResponse,
+    lsp_store: Entity<LspStore>,
+    mut cx: AsyncApp,
+) -> Result<Vec<HierarchyItem>> {
+    let mut items = Vec::with_capacity(message.items.len());
+    for item in message.items {
+        let location = item
+            .location
+            .as_ref()
+            .context("missing hierarchy item location")?;
+        let buffer_id = BufferId::new(location.buffer_id)?;
+        let anchors = location
+            .start
+            .clone()
+            .and_then(deserialize_anchor)
+            .into_iter()
+            .chain(location.end.clone().and_then(deserialize_anchor))
+            .collect::<Vec<_>>();
+        let target_buffer = lsp_store
+            .update(&mut cx, |lsp_store, cx| {
+                lsp_store.wait_for_remote_buffer(buffer_id, cx)
+            })?
+            .await?;
+        target_buffer
+            .update(&mut cx, |buffer, _| buffer.wait_for_anchors(anchors))?
+            .await?;
+        items.push(deserialize_hierarchy_item(item, target_buffer)?);
+    }
+    Ok(items)
+}
+
+fn call_hierarchy_supported(capabilities: &ServerCapabilities) -> bool {
+    capabilities
+        .call_hierarchy_provider
+        .as_ref()
+        .is_some_and(|capability| match capability {
+            lsp::CallHierarchyServerCapability::Simple(supported) => *supported,
+            lsp::CallHierarchyServerCapability::Options(_) => true,
+        })
+}
+
+#[async_trait(?Send)]
+impl LspCommand for PrepareCallHierarchy {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::CallHierarchyPrepare;
+    type ProtoRequest = proto::PrepareCallHierarchy;
+
+    fn display_name(&self) -> &str {
+        "Prepare call hierarchy"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        call_hierarchy_supported(&capabilities.server_capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyPrepareParams> {
+        Ok(lsp::CallHierarchyPrepareParams {
+            text_document_position_params: make_lsp_text_document_position(path, self.position)?,
+            work_done_progress_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        items: Option<Vec<lsp::CallHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        let items = items
+            .unwrap_or_default()
+            .into_iter()
+            .map(LspHierarchyItem::from_call_item)
+            .collect::<Result<Vec<_>>>()?;
+        hierarchy_items_from_lsp(items, &lsp_store, &buffer, server_id, &mut cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::PrepareCallHierarchy {
+        proto::PrepareCallHierarchy {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(serialize_anchor(&buffer.anchor_before(self.position))),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::PrepareCallHierarchy,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let position = message
+            .position
+            .and_then(deserialize_anchor)
+            .context("invalid position")?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self {
+            position: buffer.read_with(&mut cx, |buffer, _| position.to_point_utf16(buffer))?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::HierarchyItemsResponse {
+        hierarchy_items_to_proto(response, lsp_store, peer_id, cx)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::HierarchyItemsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        hierarchy_items_from_proto(message, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::PrepareCallHierarchy) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetIncomingCalls {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::CallHierarchyIncomingCalls;
+    type ProtoRequest = proto::GetIncomingCalls;
+
+    fn display_name(&self) -> &str {
+        "Incoming calls"
+    }
+
+    fn language_server_id(&self) -> Option<LanguageServerId> {
+        Some(self.item.language_server_id)
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        call_hierarchy_supported(&capabilities.server_capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyIncomingCallsParams> {
+        Ok(lsp::CallHierarchyIncomingCallsParams {
+            item: serde_json::from_value(self.item.lsp_item.clone())?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        calls: Option<Vec<lsp::CallHierarchyIncomingCall>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        let items = calls
+            .unwrap_or_default()
+            .into_iter()
+            .map(|call| LspHierarchyItem::from_call_item(call.from))
+            .collect::<Result<Vec<_>>>()?;
+        hierarchy_items_from_lsp(items, &lsp_store, &buffer, server_id, &mut cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetIncomingCalls {
+        proto::GetIncomingCalls {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            item: Some(serialize_hierarchy_item(&self.item, buffer.remote_id())),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetIncomingCalls,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        let item = message.item.context("missing hierarchy item")?;
+        Ok(Self {
+            item: deserialize_hierarchy_item(item, buffer)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::HierarchyItemsResponse {
+        hierarchy_items_to_proto(response, lsp_store, peer_id, cx)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::HierarchyItemsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        hierarchy_items_from_proto(message, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetIncomingCalls) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetOutgoingCalls {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::CallHierarchyOutgoingCalls;
+    type ProtoRequest = proto::GetOutgoingCalls;
+
+    fn display_name(&self) -> &str {
+        "Outgoing calls"
+    }
+
+    fn language_server_id(&self) -> Option<LanguageServerId> {
+        Some(self.item.language_server_id)
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        call_hierarchy_supported(&capabilities.server_capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyOutgoingCallsParams> {
+        Ok(lsp::CallHierarchyOutgoingCallsParams {
+            item: serde_json::from_value(self.item.lsp_item.clone())?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        calls: Option<Vec<lsp::CallHierarchyOutgoingCall>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        let items = calls
+            .unwrap_or_default()
+            .into_iter()
+            .map(|call| LspHierarchyItem::from_call_item(call.to))
+            .collect::<Result<Vec<_>>>()?;
+        hierarchy_items_from_lsp(items, &lsp_store, &buffer, server_id, &mut cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetOutgoingCalls {
+        proto::GetOutgoingCalls {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            item: Some(serialize_hierarchy_item(&self.item, buffer.remote_id())),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetOutgoingCalls,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        let item = message.item.context("missing hierarchy item")?;
+        Ok(Self {
+            item: deserialize_hierarchy_item(item, buffer)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::HierarchyItemsResponse {
+        hierarchy_items_to_proto(response, lsp_store, peer_id, cx)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::HierarchyItemsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        hierarchy_items_from_proto(message, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetOutgoingCalls) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for PrepareTypeHierarchy {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::TypeHierarchyPrepare;
+    type ProtoRequest = proto::PrepareTypeHierarchy;
+
+    fn display_name(&self) -> &str {
+        "Prepare type hierarchy"
+    }
+
+    fn check_capabilities(&self, _: AdapterServerCapabilities) -> bool {
+        // `lsp::ServerCapabilities` does not expose the type hierarchy provider,
+        // servers without support respond with an error instead.
+        true
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchyPrepareParams> {
+        Ok(lsp::TypeHierarchyPrepareParams {
+            text_document_position_params: make_lsp_text_document_position(path, self.position)?,
+            work_done_progress_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        items: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        let items = items
+            .unwrap_or_default()
+            .into_iter()
+            .map(LspHierarchyItem::from_type_item)
+            .collect::<Result<Vec<_>>>()?;
+        hierarchy_items_from_lsp(items, &lsp_store, &buffer, server_id, &mut cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::PrepareTypeHierarchy {
+        proto::PrepareTypeHierarchy {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(serialize_anchor(&buffer.anchor_before(self.position))),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::PrepareTypeHierarchy,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let position = message
+            .position
+            .and_then(deserialize_anchor)
+            .context("invalid position")?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self {
+            position: buffer.read_with(&mut cx, |buffer, _| position.to_point_utf16(buffer))?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::HierarchyItemsResponse {
+        hierarchy_items_to_proto(response, lsp_store, peer_id, cx)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::HierarchyItemsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        hierarchy_items_from_proto(message, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::PrepareTypeHierarchy) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSupertypes {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::TypeHierarchySupertypes;
+    type ProtoRequest = proto::GetSupertypes;
+
+    fn display_name(&self) -> &str {
+        "Supertypes"
+    }
+
+    fn language_server_id(&self) -> Option<LanguageServerId> {
+        Some(self.item.language_server_id)
+    }
+
+    fn check_capabilities(&self, _: AdapterServerCapabilities) -> bool {
+        true
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchySupertypesParams> {
+        Ok(lsp::TypeHierarchySupertypesParams {
+            item: serde_json::from_value(self.item.lsp_item.clone())?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        items: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        let items = items
+            .unwrap_or_default()
+            .into_iter()
+            .map(LspHierarchyItem::from_type_item)
+            .collect::<Result<Vec<_>>>()?;
+        hierarchy_items_from_lsp(items, &lsp_store, &buffer, server_id, &mut cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSupertypes {
+        proto::GetSupertypes {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            item: Some(serialize_hierarchy_item(&self.item, buffer.remote_id())),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSupertypes,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        let item = message.item.context("missing hierarchy item")?;
+        Ok(Self {
+            item: deserialize_hierarchy_item(item, buffer)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::HierarchyItemsResponse {
+        hierarchy_items_to_proto(response, lsp_store, peer_id, cx)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::HierarchyItemsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        hierarchy_items_from_proto(message, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSupertypes) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSubtypes {
+    type Response = Vec<HierarchyItem>;
+    type LspRequest = lsp::request::TypeHierarchySubtypes;
+    type ProtoRequest = proto::GetSubtypes;
+
+    fn display_name(&self) -> &str {
+        "Subtypes"
+    }
+
+    fn language_server_id(&self) -> Option<LanguageServerId> {
+        Some(self.item.language_server_id)
+    }
+
+    fn check_capabilities(&self, _: AdapterServerCapabilities) -> bool {
+        true
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchySubtypesParams> {
+        Ok(lsp::TypeHierarchySubtypesParams {
+            item: serde_json::from_value(self.item.lsp_item.clone())?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        items: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        let items = items
+            .unwrap_or_default()
+            .into_iter()
+            .map(LspHierarchyItem::from_type_item)
+            .collect::<Result<Vec<_>>>()?;
+        hierarchy_items_from_lsp(items, &lsp_store, &buffer, server_id, &mut cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSubtypes {
+        proto::GetSubtypes {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            item: Some(serialize_hierarchy_item(&self.item, buffer.remote_id())),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSubtypes,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        let item = message.item.context("missing hierarchy item")?;
+        Ok(Self {
+            item: deserialize_hierarchy_item(item, buffer)?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<HierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::HierarchyItemsResponse {
+        hierarchy_items_to_proto(response, lsp_store, peer_id, cx)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::HierarchyItemsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<HierarchyItem>> {
+        hierarchy_items_from_proto(message, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSubtypes) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+pub(crate) fn semantic_tokens_options(
+    capabilities: &ServerCapabilities,
+) -> Option<&lsp::SemanticTokensOptions> {
+    match capabilities.semantic_tokens_provider.as_ref()? {
+        lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(options) => Some(options),
+        lsp::SemanticTokensServerCapabilities::SemanticTokensRegistrationOptions(options) => {
+            Some(&options.semantic_tokens_options)
+        }
+    }
+}
+
+impl GetSemanticTokensFull {
+    pub(crate) fn is_supported(options: &lsp::SemanticTokensOptions) -> bool {
+        match options.full {
+            Some(lsp::SemanticTokensFullOptions::Bool(supported)) => supported,
+            Some(lsp::SemanticTokensFullOptions::Delta { .. }) => true,
+            None => false,
+        }
+    }
+}
+
+impl GetSemanticTokensDelta {
+    pub(crate) fn is_supported(options: &lsp::SemanticTokensOptions) -> bool {
+        matches!(
+            options.full,
+            Some(lsp::SemanticTokensFullOptions::Delta { delta: Some(true) })
+        )
+    }
+}
+
+impl GetSemanticTokensRange {
+    pub(crate) fn is_supported(options: &lsp::SemanticTokensOptions) -> bool {
+        options.range.unwrap_or(false)
+    }
+}
+
+fn flatten_semantic_tokens(tokens: Vec<lsp::SemanticToken>) -> Vec<u32> {
+    tokens
+        .into_iter()
+        .flat_map(|token| {
+            [
+                token.delta_line,
+                token.delta_start,
+                token.length,
+                token.token_type,
+                token.token_modifiers_bitset,
+            ]
+        })
+        .collect()
+}
+
+/// Applies the edits of a semantic tokens delta, which are all relative to the previous data.
+fn apply_semantic_tokens_edits(
+    previous: &[u32],
+    mut edits: Vec<lsp::SemanticTokensEdit>,
+) -> Vec<u32> {
+    edits.sort_by_key(|edit| edit.start);
+    let mut data = previous.to_vec();
+    for edit in edits.into_iter().rev() {
+        let start = (edit.start as usize).min(data.len());
+        let end = (start + edit.delete_count as usize).min(data.len());
+        data.splice(
+            start..end,
+            flatten_semantic_tokens(edit.data.unwrap_or_default()),
+        );
+    }
+    data
+}
+
+fn semantic_tokens_to_proto(
+    tokens: SemanticTokens,
+    buffer_version: &clock::Global,
+) -> proto::GetSemanticTokensResponse {
+    proto::GetSemanticTokensResponse {
+        token_types: tokens
+            .legend
+            .token_types
+            .iter()
+            .map(|token_type| token_type.as_str().to_owned())
+            .collect(),
+        token_modifiers: tokens
+            .legend
+            .token_modifiers
+            .iter()
+            .map(|modifier| modifier.as_str().to_owned())
+            .collect(),
+        data: tokens.data.to_vec(),
+        version: serialize_version(buffer_version),
+    }
+}
+
+pub(crate) fn semantic_tokens_from_proto(
+    message: proto::GetSemanticTokensResponse,
+) -> SemanticTokens {
+    let legend = lsp::SemanticTokensLegend {
+        token_types: message.token_types.into_iter().map(Into::into).collect(),
+        token_modifiers: message
+            .token_modifiers
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+    };
+    SemanticTokens::new(Arc::new(legend), None, message.data.into())
+}
+
+fn semantic_tokens_to_proto_request(project_id: u64, buffer: &Buffer) -> proto::GetSemanticTokens {
+    proto::GetSemanticTokens {
+        project_id,
+        buffer_id: buffer.remote_id().to_proto(),
+        version: serialize_version(&buffer.version()),
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSemanticTokensFull {
+    type Response = SemanticTokens;
+    type LspRequest = lsp::request::SemanticTokensFullRequest;
+    type ProtoRequest = proto::GetSemanticTokens;
+
+    fn display_name(&self) -> &str {
+        "Semantic tokens"
+    }
+
+    fn check_capabilities(&self, server_capabilities: AdapterServerCapabilities) -> bool {
+        semantic_tokens_options(&server_capabilities.server_capabilities)
+            .is_some_and(Self::is_supported)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::SemanticTokensParams> {
+        Ok(lsp::SemanticTokensParams {
+            text_document: make_text_document_identifier(path)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<lsp::SemanticTokensResult>,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: LanguageServerId,
+        _: AsyncApp,
+    ) -> Result<Self::Response> {
+        let (result_id, data) = match message {
+            Some(lsp::SemanticTokensResult::Tokens(tokens)) => (tokens.result_id, tokens.data),
+            Some(lsp::SemanticTokensResult::Partial(partial)) => (None, partial.data),
+            None => (None, Vec::new()),
+        };
+        Ok(SemanticTokens::new(
+            self.legend,
+            result_id,
+            flatten_semantic_tokens(data).into(),
+        ))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> Self::ProtoRequest {
+        semantic_tokens_to_proto_request(project_id, buffer)
+    }
+
+    async fn from_proto(
+        _: Self::ProtoRequest,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        anyhow::bail!(
+            "proto::GetSemanticTokens is not expected to be converted from proto directly, as it needs the server's token legend"
+        )
+    }
+
+    fn response_to_proto(
+        response: Self::Response,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetSemanticTokensResponse {
+        semantic_tokens_to_proto(response, buffer_version)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSemanticTokensResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self::Response> {
+        Ok(semantic_tokens_from_proto(message))
+    }
+
+    fn buffer_id_from_proto(message: &Self::ProtoRequest) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSemanticTokensDelta {
+    type Response = SemanticTokens;
+    type LspRequest = lsp::request::SemanticTokensFullDeltaRequest;
+    type ProtoRequest = proto::GetSemanticTokens;
+
+    fn display_name(&self) -> &str {
+        "Semantic tokens delta"
+    }
+
+    fn check_capabilities(&self, server_capabilities: AdapterServerCapabilities) -> bool {
+        semantic_tokens_options(&server_capabilities.server_capabilities)
+            .is_some_and(Self::is_supported)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::SemanticTokensDeltaParams> {
+        Ok(lsp::SemanticTokensDeltaParams {
+            text_document: make_text_document_identifier(path)?,
+            previous_result_id: self.previous_result_id.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<lsp::SemanticTokensFullDeltaResult>,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: LanguageServerId,
+        _: AsyncApp,
+    ) -> Result<Self::Response> {
+        let (result_id, data) = match message {
+            Some(lsp::SemanticTokensFullDeltaResult::Tokens(tokens)) => {
+                (tokens.result_id, flatten_semantic_tokens(tokens.data))
+            }
+            Some(lsp::SemanticTokensFullDeltaResult::TokensDelta(delta)) => (
+                delta.result_id,
+                apply_semantic_tokens_edits(&self.previous_data, delta.edits),
+            ),
+            Some(lsp::SemanticTokensFullDeltaResult::PartialTokensDelta { edits }) => (
+                None,
+                apply_semantic_tokens_edits(&self.previous_data, edits),
+            ),
+            None => (None, Vec::new()),
+        };
+        Ok(SemanticTokens::new(self.legend, result_id, data.into()))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> Self::ProtoRequest {
+        semantic_tokens_to_proto_request(project_id, buffer)
+    }
+
+    async fn from_proto(
+        _: Self::ProtoRequest,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        anyhow::bail!(
+            "proto::GetSemanticTokens is not expected to be converted from proto directly, as it needs the previous result fetched first"
+        )
+    }
+
+    fn response_to_proto(
+        response: Self::Response,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetSemanticTokensResponse {
+        semantic_tokens_to_proto(response, buffer_version)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSemanticTokensResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self::Response> {
+        Ok(semantic_tokens_from_proto(message))
+    }
+
+    fn buffer_id_from_proto(message: &Self::ProtoRequest) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSemanticTokensRange {
+    type Response = SemanticTokens;
+    type LspRequest = lsp::request::SemanticTokensRangeRequest;
+    type ProtoRequest = proto::GetSemanticTokens;
+
+    fn display_name(&self) -> &str {
+        "Semantic tokens range"
+    }
+
+    fn check_capabilities(&self, server_capabilities: AdapterServerCapabilities) -> bool {
+        semantic_tokens_options(&server_capabilities.server_capabilities)
+            .is_some_and(Self::is_supported)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        buffer: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::SemanticTokensRangeParams> {
+        Ok(lsp::SemanticTokensRangeParams {
+            text_document: make_text_document_identifier(path)?,
+            range: range_to_lsp(self.range.to_point_utf16(buffer))?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<lsp::SemanticTokensRangeResult>,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: LanguageServerId,
+        _: AsyncApp,
+    ) -> Result<Self::Response> {
+        let data = match message {
+            Some(lsp::SemanticTokensRangeResult::Tokens(tokens)) => tokens.data,
+            Some(lsp::SemanticTokensRangeResult::Partial(partial)) => partial.data,
+            None => Vec::new(),
+        };
+        Ok(SemanticTokens::new(
+            self.legend,
+            None,
+            flatten_semantic_tokens(data).into(),
+        ))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> Self::ProtoRequest {
+        semantic_tokens_to_proto_request(project_id, buffer)
+    }
+
+    async fn from_proto(
+        _: Self::ProtoRequest,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        anyhow::bail!(
+            "proto::GetSemanticTokens is not expected to be converted from proto directly, as it needs the server's token legend"
+        )
+    }
+
+    fn response_to_proto(
+        response: Self::Response,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetSemanticTokensResponse {
+        semantic_tokens_to_proto(response, buffer_version)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSemanticTokensResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self::Response> {
+        Ok(semantic_tokens_from_proto(message))
+    }
+
+    fn buffer_id_from_proto(message: &Self::ProtoRequest) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+fn process_related_documents(
+    diagnostics: &mut HashMap<lsp::Url, LspPullDiagnostics>,
+    server_id: LanguageServerId,
+    documents: impl IntoIterator<Item = (lsp::Url, lsp::DocumentDiagnosticReportKind)>,
+) {
+    for (url, report_kind) in documents {
+        match report_kind {
+            lsp::DocumentDiagnosticReportKind::Full(report) => {
+                process_full_diagnostics_report(diagnostics, server_id, url, report)
+            }
+            lsp::DocumentDiagnosticReportKind::Unchanged(report) => {
+                process_unchanged_diagnostics_report(diagnostics, server_id, url, report)
+            }
+        }
+    }
+}
+
+fn process_unchanged_diagnostics_report(
+    diagnostics: &mut HashMap<lsp::Url, LspPullDiagnostics>,
+    server_id: LanguageServerId,
+    uri: lsp::Url,
+    report: lsp::UnchangedDocumentDiagnosticReport,
+) {
+    let result_id = report.result_id;
+    match diagnostics.entry(uri.clone()) {
+        hash_map::Entry::Occupied(mut o) => match o.get_mut() {
+            LspPullDiagnostics::Default => {
+                o.insert(LspPullDiagnostics::Response {
+                    server_id,
+                    uri,
+                    diagnostics: PulledDiagnostics::Unchanged { result_id },
+                });
+            }
+            LspPullDiagnostics::Response {
+                server_id: existing_server_id,
+                uri: existing_uri,
+                diagnostics: existing_diagnostics,
+            } => {
+                if server_id != *existing_server_id || &uri != existing_uri {
+                    debug_panic!(
+                        "Unexpected state: file {uri} has two different sets of diagnostics reported"
+                    );
+                }
+                match existing_diagnostics {
+                    PulledDiagnostics::Unchanged { .. } => {
+                        *existing_diagnostics = PulledDiagnostics::Unchanged { result_id };
+                    }
+                    PulledDiagnostics::Changed { .. } => {}
+                }
+            }
+        },
+        hash_map::Entry::Vacant(v) => {
+            v.insert(LspPullDiagnostics::Response {
+                server_id,
+                uri,
+                diagnostics: PulledDiagnostics::Unchanged { result_id },
+            });
+        }
+    }
+}
+
+fn process_full_diagnostics_report(
+    diagnostics: &mut HashMap<lsp::Url, LspPullDiagnostics>,
+    server_id: LanguageServerId,
+    uri: lsp::Url,
+    report: lsp::FullDocumentDiagnosticReport,
+) {
+    let result_id = report.result_id;
+    match diagnostics.entry(uri.clone()) {
+        hash_map::Entry::Occupied(mut o) => match o.get_mut() {
+            LspPullDiagnostics::Default => {
+                o.insert(LspPullDiagnostics::Response {
+                    server_id,
+                    uri,
+                    diagnostics: PulledDiagnostics::Changed {
+                        result_id,
+                        diagnostics: report.items,
+                    },
+                });
+            }
+            LspPullDiagnostics::Response {
+                server_id: existing_server_id,
+                uri: existing_uri,
+                diagnostics: existing_diagnostics,
+            } => {
+                if server_id != *existing_server_id || &uri != existing_uri {
+                    debug_panic!(
+                        "Unexpected state: file {uri} has two different sets of diagnostics reported"
+                    );
+                }
+                match existing_diagnostics {
+                    PulledDiagnostics::Unchanged { .. } => {
+                        *existing_diagnostics = PulledDiagnostics::Changed {
+                            result_id,
+                            diagnostics: report.items,
+                        };
+                    }
+                    PulledDiagnostics::Changed {
+                        result_id: existing_result_id,
+                        diagnostics: existing_diagnostics,
+                    } => {
+                        if result_id.is_some() {
+                            *existing_result_id = result_id;
+                        }
+                        existing_diagnostics.extend(report.items);
+                    }
+                }
+            }
+        },
+        hash_map::Entry::Vacant(v) => {
+            v.insert(LspPullDiagnostics::Response {
+                server_id,
+                uri,
+                diagnostics: PulledDiagnostics::Changed {
+                    result_id,
+                    diagnostics: report.items,
+                },
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp::{DiagnosticSeverity, DiagnosticTag};
+    use serde_json::json;
+
+    #[test]
+    fn test_semantic_tokens_delta() {
+        let legend = Arc::new(lsp::SemanticTokensLegend {
+            token_types: vec![
+                lsp::SemanticTokenType::FUNCTION,
+                lsp::SemanticTokenType::VARIABLE,
+            ],
+            token_modifiers: vec![
+                lsp::SemanticTokenModifier::DECLARATION,
+                lsp::SemanticTokenModifier::READONLY,
+            ],
+        });
+        let previous_data = [0, 3, 4, 0, 1, 1, 4, 1, 1, 0, 0, 6, 2, 1, 3];
+
+        let data = apply_semantic_tokens_edits(
+            &previous_data,
+            vec![
+                lsp::SemanticTokensEdit {
+                    start: 10,
+                    delete_count: 5,
+                    data: None,
+                },
+                lsp::SemanticTokensEdit {
+                    start: 0,
+                    delete_count: 0,
+                    data: Some(vec![lsp::SemanticToken {
+                        delta_line: 0,
+                        delta_start: 0,
+                        length: 2,
+                        token_type: 1,
+                        token_modifiers_bitset: 0,
+                    }]),
+                },
+            ],
+        );
+        assert_eq!(data, [0, 0, 2, 1, 0, 0, 3, 4, 0, 1, 1, 4, 1, 1, 0]);
+
+        let tokens = SemanticTokens::new(legend, None, data.into());
+        assert_eq!(
+            tokens
+                .tokens
+                .iter()
+                .map(|token| (
+                    token.range(),
+                    tokens.token_type(token),
+                    tokens.token_modifiers(token).collect::<Vec<_>>()
+                ))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    PointUtf16::new(0, 0)..PointUtf16::new(0, 2),
+                    Some("variable"),
+                    vec![]
+                ),
+                (
+                    PointUtf16::new(0, 3)..PointUtf16::new(0, 7),
+                    Some("function"),
+                    vec!["declaration"]
+                ),
+                (
+                    PointUtf16::new(1, 4)..PointUtf16::new(1, 5),
+                    Some("variable"),
+                    vec![]
+                ),
+            ]
+        );
+    }
 
     #[test]
     fn test_serialize_lsp_diagnostic() {
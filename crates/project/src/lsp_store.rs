@@ -4,9 +4,9 @@ pub mod rust_analyzer_ext;
 
 use crate::{
     CodeAction, ColorPresentation, Completion, CompletionResponse, CompletionSource,
-    CoreCompletion, DocumentColor, Hover, InlayHint, LocationLink, LspAction, LspPullDiagnostics,
-    ProjectItem, ProjectPath, ProjectTransaction, PulledDiagnostics, ResolveState, Symbol,
-    ToolchainStore,
+    CoreCompletion, DocumentColor, DocumentLink, FoldingRange, Hover, InlayHint, LocationLink,
+    LspAction, LspPullDiagnostics, ProjectItem, ProjectPath, ProjectTransaction, PulledDiagnostics,
+    ResolveState, SemanticTokens, Symbol, ToolchainStore,
     buffer_store::{BufferStore, BufferStoreEvent},
     environment::ProjectEnvironment,
     lsp_command::{self, *},
@@ -880,6 +880,28 @@ impl LocalLspStore {
             })
             .detach();
 
+        language_server
+            .on_request::<lsp::request::SemanticTokensRefresh, _, _>({
+                let this = this.clone();
+                move |(), cx| {
+                    let this = this.clone();
+                    let mut cx = cx.clone();
+                    async move {
+                        this.update(&mut cx, |this, cx| {
+                            cx.emit(LspStoreEvent::RefreshSemanticTokens);
+                            this.downstream_client.as_ref().map(|(client, project_id)| {
+                                client.send(proto::RefreshSemanticTokens {
+                                    project_id: *project_id,
+                                })
+                            })
+                        })?
+                        .transpose()?;
+                        Ok(())
+                    }
+                }
+            })
+            .detach();
+
         language_server
             .on_request::<lsp::request::WorkspaceDiagnosticRefresh, _, _>({
                 let this = this.clone();
@@ -3551,7 +3573,7 @@ pub struct LspStore {
     _maintain_buffer_languages: Task<()>,
     diagnostic_summaries:
         HashMap<WorktreeId, HashMap<Arc<Path>, HashMap<LanguageServerId, DiagnosticSummary>>>,
-    lsp_data: HashMap<BufferId, DocumentColorData>,
+    lsp_data: HashMap<BufferId, BufferLspData>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -3562,16 +3584,29 @@ pub struct DocumentColors {
 
 type DocumentColorTask = Shared<Task<std::result::Result<DocumentColors, Arc<anyhow::Error>>>>;
 
+#[derive(Debug, Default, Clone)]
+pub struct DocumentSemanticTokens {
+    pub tokens: HashMap<LanguageServerId, SemanticTokens>,
+    pub cache_version: Option<usize>,
+}
+
+type DocumentSemanticTokensTask =
+    Shared<Task<std::result::Result<DocumentSemanticTokens, Arc<anyhow::Error>>>>;
+
 #[derive(Debug, Default)]
-struct DocumentColorData {
+struct BufferLspData {
     colors_for_version: Global,
     colors: HashMap<LanguageServerId, HashSet<DocumentColor>>,
     cache_version: usize,
     colors_update: Option<(Global, DocumentColorTask)>,
+    semantic_tokens_for_version: Global,
+    semantic_tokens: HashMap<LanguageServerId, SemanticTokens>,
+    semantic_tokens_cache_version: usize,
+    semantic_tokens_update: Option<(Global, DocumentSemanticTokensTask)>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub enum ColorFetchStrategy {
+pub enum LspFetchStrategy {
     IgnoreCache,
     UseCache { known_cache_version: Option<usize> },
 }
@@ -3594,6 +3629,7 @@ pub enum LspStoreEvent {
     Notification(String),
     RefreshInlayHints,
     RefreshCodeLens,
+    RefreshSemanticTokens,
     DiagnosticsUpdated {
         language_server_id: LanguageServerId,
         path: ProjectPath,
@@ -3652,6 +3688,7 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_open_buffer_for_symbol);
         client.add_entity_request_handler(Self::handle_refresh_inlay_hints);
         client.add_entity_request_handler(Self::handle_refresh_code_lens);
+        client.add_entity_request_handler(Self::handle_refresh_semantic_tokens);
         client.add_entity_request_handler(Self::handle_on_type_formatting);
         client.add_entity_request_handler(Self::handle_apply_additional_edits_for_completion);
         client.add_entity_request_handler(Self::handle_register_buffer_with_language_servers);
@@ -3666,6 +3703,14 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_lsp_command::<PrepareRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PerformRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<LinkedEditingRange>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareCallHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetIncomingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetOutgoingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareTypeHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSupertypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSubtypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<ResolveDocumentLink>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSelectionRanges>);
 
         client.add_entity_request_handler(Self::handle_lsp_ext_cancel_flycheck);
         client.add_entity_request_handler(Self::handle_lsp_ext_run_flycheck);
@@ -5768,6 +5813,166 @@ impl LspStore {
         }
     }
 
+    pub fn folding_ranges(
+        &mut self,
+        buffer_handle: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<FoldingRange>>> {
+        if let Some((upstream_client, project_id)) = self.upstream_client() {
+            let request_task = upstream_client.request(proto::MultiLspQuery {
+                buffer_id: buffer_handle.read(cx).remote_id().into(),
+                version: serialize_version(&buffer_handle.read(cx).version()),
+                project_id,
+                strategy: Some(proto::multi_lsp_query::Strategy::All(
+                    proto::AllLanguageServers {},
+                )),
+                request: Some(proto::multi_lsp_query::Request::GetFoldingRanges(
+                    GetFoldingRanges.to_proto(project_id, buffer_handle.read(cx)),
+                )),
+            });
+            let buffer = buffer_handle.clone();
+            cx.spawn(async move |weak_project, cx| {
+                let Some(project) = weak_project.upgrade() else {
+                    return Ok(Vec::new());
+                };
+                let responses = request_task.await?.responses;
+                let folding_ranges = join_all(
+                    responses
+                        .into_iter()
+                        .filter_map(|lsp_response| match lsp_response.response? {
+                            proto::lsp_response::Response::GetFoldingRangesResponse(response) => {
+                                Some(response)
+                            }
+                            unexpected => {
+                                debug_panic!("Unexpected response: {unexpected:?}");
+                                None
+                            }
+                        })
+                        .map(|folding_ranges_response| {
+                            GetFoldingRanges.response_from_proto(
+                                folding_ranges_response,
+                                project.clone(),
+                                buffer.clone(),
+                                cx.clone(),
+                            )
+                        }),
+                )
+                .await;
+
+                Ok(folding_ranges
+                    .into_iter()
+                    .collect::<Result<Vec<Vec<_>>>>()?
+                    .into_iter()
+                    .max_by_key(|ranges| ranges.len())
+                    .unwrap_or_default())
+            })
+        } else {
+            let folding_ranges_task = self.request_multiple_lsp_locally(
+                buffer_handle,
+                None::<usize>,
+                GetFoldingRanges,
+                cx,
+            );
+            cx.spawn(async move |_, _| {
+                // Ranges of different servers overlap, so only the most detailed response is used.
+                Ok(folding_ranges_task
+                    .await
+                    .into_iter()
+                    .map(|(_, folding_ranges)| folding_ranges)
+                    .max_by_key(|folding_ranges| folding_ranges.len())
+                    .unwrap_or_default())
+            })
+        }
+    }
+
+    pub fn document_links(
+        &mut self,
+        buffer_handle: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<DocumentLink>>> {
+        if let Some((upstream_client, project_id)) = self.upstream_client() {
+            let request_task = upstream_client.request(proto::MultiLspQuery {
+                buffer_id: buffer_handle.read(cx).remote_id().into(),
+                version: serialize_version(&buffer_handle.read(cx).version()),
+                project_id,
+                strategy: Some(proto::multi_lsp_query::Strategy::All(
+                    proto::AllLanguageServers {},
+                )),
+                request: Some(proto::multi_lsp_query::Request::GetDocumentLinks(
+                    GetDocumentLinks.to_proto(project_id, buffer_handle.read(cx)),
+                )),
+            });
+            let buffer = buffer_handle.clone();
+            cx.spawn(async move |weak_project, cx| {
+                let Some(project) = weak_project.upgrade() else {
+                    return Ok(Vec::new());
+                };
+                let responses = request_task.await?.responses;
+                let document_links = join_all(
+                    responses
+                        .into_iter()
+                        .filter_map(|lsp_response| match lsp_response.response? {
+                            proto::lsp_response::Response::GetDocumentLinksResponse(response) => {
+                                Some(response)
+                            }
+                            unexpected => {
+                                debug_panic!("Unexpected response: {unexpected:?}");
+                                None
+                            }
+                        })
+                        .map(|document_links_response| {
+                            GetDocumentLinks.response_from_proto(
+                                document_links_response,
+                                project.clone(),
+                                buffer.clone(),
+                                cx.clone(),
+                            )
+                        }),
+                )
+                .await;
+
+                Ok(document_links
+                    .into_iter()
+                    .collect::<Result<Vec<Vec<_>>>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect())
+            })
+        } else {
+            let document_links_task = self.request_multiple_lsp_locally(
+                buffer_handle,
+                None::<usize>,
+                GetDocumentLinks,
+                cx,
+            );
+            cx.spawn(async move |_, _| {
+                Ok(document_links_task
+                    .await
+                    .into_iter()
+                    .flat_map(|(_, document_links)| document_links)
+                    .collect())
+            })
+        }
+    }
+
+    /// Returns the target of the given link, asking the server that reported it if it is not known yet.
+    pub fn resolve_document_link(
+        &mut self,
+        buffer_handle: Entity<Buffer>,
+        link: DocumentLink,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<lsp::Url>>> {
+        if link.target.is_some() {
+            return Task::ready(Ok(link.target));
+        }
+        self.request_lsp(
+            buffer_handle,
+            LanguageServerToQuery::Other(link.language_server_id),
+            ResolveDocumentLink { link },
+            cx,
+        )
+    }
+
     #[inline(never)]
     pub fn completions(
         &self,
@@ -6597,7 +6802,7 @@ impl LspStore {
 
     pub fn document_colors(
         &mut self,
-        fetch_strategy: ColorFetchStrategy,
+        fetch_strategy: LspFetchStrategy,
         buffer: Entity<Buffer>,
         cx: &mut Context<Self>,
     ) -> Option<DocumentColorTask> {
@@ -6605,8 +6810,8 @@ impl LspStore {
         let buffer_id = buffer.read(cx).remote_id();
 
         match fetch_strategy {
-            ColorFetchStrategy::IgnoreCache => {}
-            ColorFetchStrategy::UseCache {
+            LspFetchStrategy::IgnoreCache => {}
+            LspFetchStrategy::UseCache {
                 known_cache_version,
             } => {
                 if let Some(cached_data) = self.lsp_data.get(&buffer_id) {
@@ -6663,7 +6868,7 @@ impl LspStore {
                     .map_err(Arc::new);
                 let fetched_colors = match fetched_colors {
                     Ok(fetched_colors) => {
-                        if fetch_strategy != ColorFetchStrategy::IgnoreCache
+                        if fetch_strategy != LspFetchStrategy::IgnoreCache
                             && Some(true)
                                 == buffer
                                     .update(cx, |buffer, _| {
@@ -6802,6 +7007,224 @@ impl LspStore {
         }
     }
 
+    pub fn semantic_tokens(
+        &mut self,
+        fetch_strategy: LspFetchStrategy,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Option<DocumentSemanticTokensTask> {
+        let version_queried_for = buffer.read(cx).version();
+        let buffer_id = buffer.read(cx).remote_id();
+
+        match fetch_strategy {
+            LspFetchStrategy::IgnoreCache => {}
+            LspFetchStrategy::UseCache {
+                known_cache_version,
+            } => {
+                if let Some(cached_data) = self.lsp_data.get(&buffer_id) {
+                    if !version_queried_for.changed_since(&cached_data.semantic_tokens_for_version)
+                    {
+                        if Some(cached_data.semantic_tokens_cache_version) == known_cache_version {
+                            return None;
+                        } else {
+                            return Some(
+                                Task::ready(Ok(DocumentSemanticTokens {
+                                    tokens: cached_data.semantic_tokens.clone(),
+                                    cache_version: Some(cached_data.semantic_tokens_cache_version),
+                                }))
+                                .shared(),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        let lsp_data = self.lsp_data.entry(buffer_id).or_default();
+        if let Some((updating_for, running_update)) = &lsp_data.semantic_tokens_update {
+            if !version_queried_for.changed_since(&updating_for) {
+                return Some(running_update.clone());
+            }
+        }
+        let query_version_queried_for = version_queried_for.clone();
+        let new_task = cx
+            .spawn(async move |lsp_store, cx| {
+                cx.background_executor()
+                    .timer(Duration::from_millis(30))
+                    .await;
+                let fetched_tokens = lsp_store
+                    .update(cx, |lsp_store, cx| {
+                        lsp_store.fetch_semantic_tokens_for_buffer(buffer.clone(), cx)
+                    })?
+                    .await
+                    .context("fetching semantic tokens")
+                    .map_err(Arc::new);
+                let fetched_tokens = match fetched_tokens {
+                    Ok(fetched_tokens) => fetched_tokens,
+                    Err(e) => {
+                        lsp_store
+                            .update(cx, |lsp_store, _| {
+                                lsp_store
+                                    .lsp_data
+                                    .entry(buffer_id)
+                                    .or_default()
+                                    .semantic_tokens_update = None;
+                            })
+                            .ok();
+                        return Err(e);
+                    }
+                };
+
+                lsp_store
+                    .update(cx, |lsp_store, _| {
+                        let lsp_data = lsp_store.lsp_data.entry(buffer_id).or_default();
+                        if lsp_data.semantic_tokens_for_version == query_version_queried_for {
+                            lsp_data.semantic_tokens.extend(fetched_tokens);
+                            lsp_data.semantic_tokens_cache_version += 1;
+                        } else if !lsp_data
+                            .semantic_tokens_for_version
+                            .changed_since(&query_version_queried_for)
+                        {
+                            lsp_data.semantic_tokens_for_version = query_version_queried_for;
+                            lsp_data.semantic_tokens = fetched_tokens;
+                            lsp_data.semantic_tokens_cache_version += 1;
+                        }
+                        lsp_data.semantic_tokens_update = None;
+                        DocumentSemanticTokens {
+                            tokens: lsp_data.semantic_tokens.clone(),
+                            cache_version: Some(lsp_data.semantic_tokens_cache_version),
+                        }
+                    })
+                    .map_err(Arc::new)
+            })
+            .shared();
+        lsp_data.semantic_tokens_update = Some((version_queried_for, new_task.clone()));
+        Some(new_task)
+    }
+
+    fn fetch_semantic_tokens_for_buffer(
+        &mut self,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<HashMap<LanguageServerId, SemanticTokens>>> {
+        let buffer_id = buffer.read(cx).remote_id();
+        if let Some((client, project_id)) = self.upstream_client() {
+            let request_task = client.request(proto::MultiLspQuery {
+                project_id,
+                buffer_id: buffer_id.to_proto(),
+                version: serialize_version(&buffer.read(cx).version()),
+                strategy: Some(proto::multi_lsp_query::Strategy::All(
+                    proto::AllLanguageServers {},
+                )),
+                request: Some(proto::multi_lsp_query::Request::GetSemanticTokens(
+                    proto::GetSemanticTokens {
+                        project_id,
+                        buffer_id: buffer_id.to_proto(),
+                        version: serialize_version(&buffer.read(cx).version()),
+                    },
+                )),
+            });
+            cx.background_spawn(async move {
+                Ok(request_task
+                    .await?
+                    .responses
+                    .into_iter()
+                    .filter_map(|lsp_response| match lsp_response.response? {
+                        proto::lsp_response::Response::GetSemanticTokensResponse(response) => {
+                            Some((
+                                LanguageServerId::from_proto(lsp_response.server_id),
+                                semantic_tokens_from_proto(response),
+                            ))
+                        }
+                        unexpected => {
+                            debug_panic!("Unexpected response: {unexpected:?}");
+                            None
+                        }
+                    })
+                    .collect())
+            })
+        } else {
+            let previous_tokens = self
+                .lsp_data
+                .get(&buffer_id)
+                .map(|lsp_data| lsp_data.semantic_tokens.clone())
+                .unwrap_or_default();
+            let servers = buffer.update(cx, |buffer, cx| {
+                self.language_servers_for_local_buffer(buffer, cx)
+                    .map(|(_, server)| server.clone())
+                    .filter(|server| {
+                        self.as_local().is_some_and(|local| {
+                            local
+                                .buffers_opened_in_servers
+                                .get(&buffer_id)
+                                .is_some_and(|servers| servers.contains(&server.server_id()))
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            });
+            let whole_buffer = {
+                let buffer = buffer.read(cx);
+                buffer.anchor_before(0)..buffer.anchor_after(buffer.len())
+            };
+
+            let requests = servers
+                .into_iter()
+                .filter_map(|server| {
+                    let server_id = server.server_id();
+                    let capabilities = server.capabilities();
+                    let options = semantic_tokens_options(&capabilities)?;
+                    let legend = Arc::new(options.legend.clone());
+                    let previous_result = previous_tokens.get(&server_id).and_then(|previous| {
+                        Some((previous.result_id.clone()?, previous.data.clone()))
+                    });
+                    let request = if GetSemanticTokensFull::is_supported(options) {
+                        match previous_result
+                            .filter(|_| GetSemanticTokensDelta::is_supported(options))
+                        {
+                            Some((previous_result_id, previous_data)) => self.request_lsp(
+                                buffer.clone(),
+                                LanguageServerToQuery::Other(server_id),
+                                GetSemanticTokensDelta {
+                                    legend,
+                                    previous_result_id,
+                                    previous_data,
+                                },
+                                cx,
+                            ),
+                            None => self.request_lsp(
+                                buffer.clone(),
+                                LanguageServerToQuery::Other(server_id),
+                                GetSemanticTokensFull { legend },
+                                cx,
+                            ),
+                        }
+                    } else if GetSemanticTokensRange::is_supported(options) {
+                        self.request_lsp(
+                            buffer.clone(),
+                            LanguageServerToQuery::Other(server_id),
+                            GetSemanticTokensRange {
+                                legend,
+                                range: whole_buffer.clone(),
+                            },
+                            cx,
+                        )
+                    } else {
+                        return None;
+                    };
+                    Some(async move { (server_id, request.await) })
+                })
+                .collect::<Vec<_>>();
+
+            cx.background_spawn(async move {
+                Ok(join_all(requests)
+                    .await
+                    .into_iter()
+                    .filter_map(|(server_id, tokens)| Some((server_id, tokens.log_err()?)))
+                    .collect())
+            })
+        }
+    }
+
     pub fn signature_help<T: ToPointUtf16>(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -7963,14 +8386,13 @@ impl LspStore {
             cx.clone(),
         )
         .await?;
+        let server = request.language_server_id().map_or(
+            LanguageServerToQuery::FirstCapable,
+            LanguageServerToQuery::Other,
+        );
         let response = this
             .update(&mut cx, |this, cx| {
-                this.request_lsp(
-                    buffer_handle.clone(),
-                    LanguageServerToQuery::FirstCapable,
-                    request,
-                    cx,
-                )
+                this.request_lsp(buffer_handle.clone(), server, request, cx)
             })?
             .await?;
         this.update(&mut cx, |this, cx| {
@@ -8190,6 +8612,96 @@ impl LspStore {
                         .collect(),
                 })
             }
+            Some(proto::multi_lsp_query::Request::GetFoldingRanges(message)) => {
+                buffer
+                    .update(&mut cx, |buffer, _| {
+                        buffer.wait_for_version(deserialize_version(&message.version))
+                    })?
+                    .await?;
+                let get_folding_ranges = GetFoldingRanges::from_proto(
+                    message,
+                    lsp_store.clone(),
+                    buffer.clone(),
+                    cx.clone(),
+                )
+                .await?;
+
+                let folding_ranges = lsp_store
+                    .update(&mut cx, |project, cx| {
+                        project.request_multiple_lsp_locally(
+                            &buffer,
+                            None::<usize>,
+                            get_folding_ranges,
+                            cx,
+                        )
+                    })?
+                    .await
+                    .into_iter();
+
+                lsp_store.update(&mut cx, |project, cx| proto::MultiLspQueryResponse {
+                    responses: folding_ranges
+                        .map(|(server_id, folding_ranges)| proto::LspResponse {
+                            server_id: server_id.to_proto(),
+                            response: Some(
+                                proto::lsp_response::Response::GetFoldingRangesResponse(
+                                    GetFoldingRanges::response_to_proto(
+                                        folding_ranges,
+                                        project,
+                                        sender_id,
+                                        &buffer_version,
+                                        cx,
+                                    ),
+                                ),
+                            ),
+                        })
+                        .collect(),
+                })
+            }
+            Some(proto::multi_lsp_query::Request::GetDocumentLinks(message)) => {
+                buffer
+                    .update(&mut cx, |buffer, _| {
+                        buffer.wait_for_version(deserialize_version(&message.version))
+                    })?
+                    .await?;
+                let get_document_links = GetDocumentLinks::from_proto(
+                    message,
+                    lsp_store.clone(),
+                    buffer.clone(),
+                    cx.clone(),
+                )
+                .await?;
+
+                let document_links = lsp_store
+                    .update(&mut cx, |project, cx| {
+                        project.request_multiple_lsp_locally(
+                            &buffer,
+                            None::<usize>,
+                            get_document_links,
+                            cx,
+                        )
+                    })?
+                    .await
+                    .into_iter();
+
+                lsp_store.update(&mut cx, |project, cx| proto::MultiLspQueryResponse {
+                    responses: document_links
+                        .map(|(server_id, document_links)| proto::LspResponse {
+                            server_id: server_id.to_proto(),
+                            response: Some(
+                                proto::lsp_response::Response::GetDocumentLinksResponse(
+                                    GetDocumentLinks::response_to_proto(
+                                        document_links,
+                                        project,
+                                        sender_id,
+                                        &buffer_version,
+                                        cx,
+                                    ),
+                                ),
+                            ),
+                        })
+                        .collect(),
+                })
+            }
             Some(proto::multi_lsp_query::Request::GetDocumentDiagnostics(message)) => {
                 buffer
                     .update(&mut cx, |buffer, _| {
@@ -8252,6 +8764,46 @@ impl LspStore {
                         .collect(),
                 })
             }
+            Some(proto::multi_lsp_query::Request::GetSemanticTokens(message)) => {
+                buffer
+                    .update(&mut cx, |buffer, _| {
+                        buffer.wait_for_version(deserialize_version(&message.version))
+                    })?
+                    .await?;
+                let semantic_tokens_task = lsp_store.update(&mut cx, |lsp_store, cx| {
+                    lsp_store.semantic_tokens(
+                        LspFetchStrategy::UseCache {
+                            known_cache_version: None,
+                        },
+                        buffer.clone(),
+                        cx,
+                    )
+                })?;
+                let semantic_tokens = match semantic_tokens_task {
+                    Some(task) => task.await.map_err(|e| anyhow!("{e:#}"))?.tokens,
+                    None => HashMap::default(),
+                };
+
+                lsp_store.update(&mut cx, |project, cx| proto::MultiLspQueryResponse {
+                    responses: semantic_tokens
+                        .into_iter()
+                        .map(|(server_id, tokens)| proto::LspResponse {
+                            server_id: server_id.to_proto(),
+                            response: Some(
+                                proto::lsp_response::Response::GetSemanticTokensResponse(
+                                    GetSemanticTokensFull::response_to_proto(
+                                        tokens,
+                                        project,
+                                        sender_id,
+                                        &buffer_version,
+                                        cx,
+                                    ),
+                                ),
+                            ),
+                        })
+                        .collect(),
+                })
+            }
             Some(proto::multi_lsp_query::Request::GetDefinition(message)) => {
                 let get_definitions = GetDefinitions::from_proto(
                     message,
@@ -9584,6 +10136,17 @@ impl LspStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_refresh_semantic_tokens(
+        this: Entity<Self>,
+        _: TypedEnvelope<proto::RefreshSemanticTokens>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        this.update(&mut cx, |_, cx| {
+            cx.emit(LspStoreEvent::RefreshSemanticTokens);
+        })?;
+        Ok(proto::Ack {})
+    }
+
     async fn handle_open_buffer_for_symbol(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::OpenBufferForSymbol>,
@@ -11248,6 +11811,8 @@ impl LspStore {
         for buffer_lsp_data in self.lsp_data.values_mut() {
             buffer_lsp_data.colors.remove(&for_server);
             buffer_lsp_data.cache_version += 1;
+            buffer_lsp_data.semantic_tokens.remove(&for_server);
+            buffer_lsp_data.semantic_tokens_cache_version += 1;
         }
         if let Some(local) = self.as_local_mut() {
             local.buffer_pull_diagnostics_result_ids.remove(&for_server);
@@ -90,7 +90,7 @@ use node_runtime::NodeRuntime;
 use parking_lot::Mutex;
 pub use prettier_store::PrettierStore;
 use project_settings::{ProjectSettings, SettingsObserver, SettingsObserverEvent};
-use remote::{SshConnectionOptions, SshRemoteClient};
+use remote::{RemoteConnectionOptions, SshConnectionOptions, SshRemoteClient};
 use rpc::{
     AnyProtoClient, ErrorCode,
     proto::{FromProto, LanguageServerPromptResponse, SSH_PROJECT_ID, ToProto},
@@ -318,6 +318,7 @@ pub enum Event {
     Rejoined,
     RefreshInlayHints,
     RefreshCodeLens,
+    RefreshSemanticTokens,
     RevealInProjectPanel(ProjectEntryId),
     SnippetEdit(BufferId, Vec<(lsp::Range, Snippet)>),
     ExpandedAllForEntry(WorktreeId, ProjectEntryId),
@@ -798,6 +799,132 @@ impl std::hash::Hash for DocumentColor {
     }
 }
 
+/// A range of text that a language server links to a file or a URL.
+#[derive(Clone, Debug)]
+pub struct DocumentLink {
+    pub range: Range<text::Anchor>,
+    /// The file or URL the link points to, or `None` if the link has to be resolved first.
+    pub target: Option<lsp::Url>,
+    pub language_server_id: LanguageServerId,
+    /// The link as reported by the server, sent back when resolving its target.
+    pub(crate) lsp_link: serde_json::Value,
+}
+
+/// A range of lines that a language server reports as foldable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FoldingRange {
+    /// Spans from the end of the first line to the end of the last line to fold.
+    pub range: Range<text::Anchor>,
+    pub kind: Option<FoldingRangeKind>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FoldingRangeKind {
+    Comment,
+    Imports,
+    Region,
+}
+
+/// An item of a call or type hierarchy, as reported by a language server.
+#[derive(Clone, Debug)]
+pub struct HierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The range to reveal when navigating to the item, e.g. the name of a function.
+    pub location: Location,
+    pub language_server_id: LanguageServerId,
+    /// The item as reported by the server, sent back when querying its callers, supertypes, etc.
+    pub(crate) lsp_item: serde_json::Value,
+}
+
+/// A single semantic token, positioned absolutely in the buffer it was reported for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SemanticToken {
+    pub start: PointUtf16,
+    /// Length of the token in UTF-16 code units, tokens never span multiple lines.
+    pub length: u32,
+    /// Index into the token types of the server's legend.
+    pub token_type: u32,
+    /// Bitset of indices into the token modifiers of the server's legend.
+    pub token_modifiers: u32,
+}
+
+impl SemanticToken {
+    pub fn range(&self) -> Range<PointUtf16> {
+        self.start..PointUtf16::new(self.start.row, self.start.column + self.length)
+    }
+}
+
+/// Semantic tokens of a buffer, as reported by a single language server.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SemanticTokens {
+    pub legend: Arc<lsp::SemanticTokensLegend>,
+    pub tokens: Arc<[SemanticToken]>,
+    /// Identifier of the server's response, used to request deltas against it.
+    pub(crate) result_id: Option<String>,
+    /// The tokens in the relative encoding the server uses, deltas are applied to these.
+    pub(crate) data: Arc<[u32]>,
+}
+
+impl SemanticTokens {
+    pub(crate) fn new(
+        legend: Arc<lsp::SemanticTokensLegend>,
+        result_id: Option<String>,
+        data: Arc<[u32]>,
+    ) -> Self {
+        let mut row = 0;
+        let mut column = 0;
+        let tokens = data
+            .chunks_exact(5)
+            .map(|token| {
+                let [delta_row, delta_column, length, token_type, token_modifiers] = token else {
+                    unreachable!("chunks are exactly 5 elements long");
+                };
+                if *delta_row > 0 {
+                    row += delta_row;
+                    column = *delta_column;
+                } else {
+                    column += delta_column;
+                }
+                SemanticToken {
+                    start: PointUtf16::new(row, column),
+                    length: *length,
+                    token_type: *token_type,
+                    token_modifiers: *token_modifiers,
+                }
+            })
+            .collect();
+        Self {
+            legend,
+            tokens,
+            result_id,
+            data,
+        }
+    }
+
+    pub fn token_type(&self, token: &SemanticToken) -> Option<&str> {
+        self.legend
+            .token_types
+            .get(token.token_type as usize)
+            .map(|token_type| token_type.as_str())
+    }
+
+    pub fn token_modifiers<'a>(
+        &'a self,
+        token: &SemanticToken,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        let modifiers = token.token_modifiers;
+        self.legend
+            .token_modifiers
+            .iter()
+            .enumerate()
+            .take(u32::BITS as usize)
+            .filter(move |(ix, _)| modifiers & (1 << ix) != 0)
+            .map(|(_, modifier)| modifier.as_str())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ColorPresentation {
     pub label: SharedString,
@@ -1876,6 +2003,12 @@ impl Project {
     }
 
     pub fn ssh_connection_options(&self, cx: &App) -> Option<SshConnectionOptions> {
+        self.ssh_client
+            .as_ref()
+            .and_then(|ssh| ssh.read(cx).ssh_connection_options())
+    }
+
+    pub fn remote_connection_options(&self, cx: &App) -> Option<RemoteConnectionOptions> {
         self.ssh_client
             .as_ref()
             .map(|ssh| ssh.read(cx).connection_options())
@@ -2887,6 +3020,7 @@ impl Project {
             }
             LspStoreEvent::RefreshInlayHints => cx.emit(Event::RefreshInlayHints),
             LspStoreEvent::RefreshCodeLens => cx.emit(Event::RefreshCodeLens),
+            LspStoreEvent::RefreshSemanticTokens => cx.emit(Event::RefreshSemanticTokens),
             LspStoreEvent::LanguageServerPrompt(prompt) => {
                 cx.emit(Event::LanguageServerPrompt(prompt.clone()))
             }
@@ -3484,6 +3618,111 @@ impl Project {
         )
     }
 
+    /// Returns, for each of the given positions, the ranges that contain it,
+    /// ordered from the innermost to the outermost one.
+    pub fn selection_ranges<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        positions: impl IntoIterator<Item = T>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Vec<Range<text::Anchor>>>>> {
+        let snapshot = buffer.read(cx).snapshot();
+        let positions = positions
+            .into_iter()
+            .map(|position| position.to_point_utf16(&snapshot))
+            .collect();
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetSelectionRanges { positions },
+            cx,
+        )
+    }
+
+    pub fn prepare_call_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<HierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareCallHierarchy { position },
+            cx,
+        )
+    }
+
+    /// Returns the items calling the given call hierarchy item.
+    pub fn incoming_calls(
+        &mut self,
+        item: HierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<HierarchyItem>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.language_server_id),
+            GetIncomingCalls { item },
+            cx,
+        )
+    }
+
+    /// Returns the items called by the given call hierarchy item.
+    pub fn outgoing_calls(
+        &mut self,
+        item: HierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<HierarchyItem>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.language_server_id),
+            GetOutgoingCalls { item },
+            cx,
+        )
+    }
+
+    pub fn prepare_type_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<HierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareTypeHierarchy { position },
+            cx,
+        )
+    }
+
+    pub fn supertypes(
+        &mut self,
+        item: HierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<HierarchyItem>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.language_server_id),
+            GetSupertypes { item },
+            cx,
+        )
+    }
+
+    pub fn subtypes(
+        &mut self,
+        item: HierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<HierarchyItem>>> {
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::Other(item.language_server_id),
+            GetSubtypes { item },
+            cx,
+        )
+    }
+
     pub fn symbols(&self, query: &str, cx: &mut Context<Self>) -> Task<Result<Vec<Symbol>>> {
         self.lsp_store
             .update(cx, |lsp_store, cx| lsp_store.symbols(query, cx))
@@ -1,6 +1,6 @@
 use anyhow::Context as _;
 use collections::HashMap;
-use context_server::ContextServerCommand;
+use context_server::{ContextServerCommand, ContextServerEndpoint};
 use dap::adapters::DebugAdapterName;
 use fs::Fs;
 use futures::StreamExt as _;
@@ -110,6 +110,18 @@ pub enum ContextServerSettings {
         /// are supported.
         settings: serde_json::Value,
     },
+    Remote {
+        /// Whether the context server is enabled.
+        #[serde(default = "default_true")]
+        enabled: bool,
+        /// The HTTP endpoint of the context server.
+        ///
+        /// A header stored in the system keychain for the endpoint's URL is sent
+        /// along with the configured headers, so that secrets can be kept out of
+        /// the settings file.
+        #[serde(flatten)]
+        endpoint: ContextServerEndpoint,
+    },
 }
 
 /// Common language server settings.
@@ -134,6 +146,7 @@ impl ContextServerSettings {
         match self {
             ContextServerSettings::Custom { enabled, .. } => *enabled,
             ContextServerSettings::Extension { enabled, .. } => *enabled,
+            ContextServerSettings::Remote { enabled, .. } => *enabled,
         }
     }
 
@@ -141,6 +154,7 @@ impl ContextServerSettings {
         match self {
             ContextServerSettings::Custom { enabled: e, .. } => *e = enabled,
             ContextServerSettings::Extension { enabled: e, .. } => *e = enabled,
+            ContextServerSettings::Remote { enabled: e, .. } => *e = enabled,
         }
     }
 }
@@ -7771,6 +7771,58 @@ async fn test_home_dir_as_git_repository(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_jj_workspaces_must_be_colocated(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        path!("/root"),
+        json!({
+            "colocated": {
+                ".git": {},
+                ".jj": {},
+                "a.txt": "A",
+            },
+            "jj-only": {
+                ".jj": {},
+                "b.txt": "B",
+            },
+        }),
+    )
+    .await;
+    fs.set_status_for_repo(
+        path!("/root/colocated/.git").as_ref(),
+        &[("a.txt".as_ref(), StatusCode::Modified.worktree())],
+    );
+
+    let project = Project::test(
+        fs.clone(),
+        [
+            path!("/root/colocated").as_ref(),
+            path!("/root/jj-only").as_ref(),
+        ],
+        cx,
+    )
+    .await;
+    project
+        .update(cx, |project, cx| project.git_scans_complete(cx))
+        .await;
+    cx.run_until_parked();
+
+    // Only the colocated workspace has a repository, and its statuses come from jj rather than
+    // from the git index, which jj doesn't use.
+    project.read_with(cx, |project, cx| {
+        let repositories = project.repositories(cx).values().collect::<Vec<_>>();
+        assert_eq!(repositories.len(), 1);
+        let repository = repositories[0].read(cx);
+        assert_eq!(
+            repository.work_directory_abs_path.as_ref(),
+            Path::new(path!("/root/colocated"))
+        );
+        assert_eq!(repository.cached_status().count(), 0);
+    });
+}
+
 #[gpui::test]
 async fn test_git_repository_status(cx: &mut gpui::TestAppContext) {
     init_test(cx);
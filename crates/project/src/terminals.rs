@@ -4,7 +4,7 @@ use collections::HashMap;
 use gpui::{AnyWindowHandle, App, AppContext as _, Context, Entity, Task, WeakEntity};
 use itertools::Itertools;
 use language::LanguageName;
-use remote::ssh_session::SshArgs;
+use remote::{ContainerExecArgs, ssh_session::SshArgs};
 use settings::{Settings, SettingsLocation};
 use smol::channel::bounded;
 use std::{
@@ -51,9 +51,17 @@ impl SshCommand {
     }
 }
 
-pub struct SshDetails {
+/// RemoteCommand describes how to run programs on the host of a remote project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteCommand {
+    Ssh(SshCommand),
+    /// Runs programs in a dev container with its container runtime's `exec`.
+    Container(ContainerExecArgs),
+}
+
+pub struct RemoteDetails {
     pub host: String,
-    pub ssh_command: SshCommand,
+    pub command: RemoteCommand,
     pub envs: Option<HashMap<String, String>>,
     pub path_style: PathStyle,
 }
@@ -79,20 +87,29 @@ impl Project {
         }
     }
 
-    pub fn ssh_details(&self, cx: &App) -> Option<SshDetails> {
-        if let Some(ssh_client) = &self.ssh_client {
-            let ssh_client = ssh_client.read(cx);
-            if let Some((SshArgs { arguments, envs }, path_style)) = ssh_client.ssh_info() {
-                return Some(SshDetails {
-                    host: ssh_client.connection_options().host.clone(),
-                    ssh_command: SshCommand { arguments },
-                    envs,
-                    path_style,
-                });
-            }
+    pub fn remote_details(&self, cx: &App) -> Option<RemoteDetails> {
+        let ssh_client = self.ssh_client.as_ref()?.read(cx);
+        if let Some(container_exec_args) = ssh_client.container_exec_args() {
+            let connection_options = ssh_client.connection_options();
+            return Some(RemoteDetails {
+                host: connection_options
+                    .nickname()
+                    .unwrap_or_else(|| connection_options.connection_string()),
+                command: RemoteCommand::Container(container_exec_args),
+                envs: None,
+                path_style: ssh_client.path_style(),
+            });
         }
 
-        return None;
+        let ((SshArgs { arguments, envs }, path_style), connection_options) = ssh_client
+            .ssh_info()
+            .zip(ssh_client.ssh_connection_options())?;
+        Some(RemoteDetails {
+            host: connection_options.host,
+            command: RemoteCommand::Ssh(SshCommand { arguments }),
+            envs,
+            path_style,
+        })
     }
 
     pub fn create_terminal(
@@ -158,10 +175,11 @@ impl Project {
 
     pub fn exec_in_shell(&self, command: String, cx: &App) -> std::process::Command {
         let path = self.first_project_directory(cx);
-        let ssh_details = self.ssh_details(cx);
+        let remote_details = self.remote_details(cx);
         let settings = self.terminal_settings(&path, cx).clone();
 
-        let builder = ShellBuilder::new(ssh_details.is_none(), &settings.shell).non_interactive();
+        let builder =
+            ShellBuilder::new(remote_details.is_none(), &settings.shell).non_interactive();
         let (command, args) = builder.build(Some(command), &Vec::new());
 
         let mut env = self
@@ -171,15 +189,16 @@ impl Project {
             .unwrap_or_default();
         env.extend(settings.env.clone());
 
-        match self.ssh_details(cx) {
-            Some(SshDetails {
-                ssh_command,
+        match remote_details {
+            Some(RemoteDetails {
+                command: remote_command,
                 envs,
                 path_style,
                 ..
             }) => {
-                let (command, args) = wrap_for_ssh(
-                    &ssh_command,
+                let (command, args) = wrap_for_remote(
+                    &remote_command,
+                    false,
                     Some((&command, &args)),
                     path.as_deref(),
                     env,
@@ -223,8 +242,8 @@ impl Project {
                 }
             }
         };
-        let ssh_details = this.ssh_details(cx);
-        let is_ssh_terminal = ssh_details.is_some();
+        let remote_details = this.remote_details(cx);
+        let is_ssh_terminal = remote_details.is_some();
 
         let mut settings_location = None;
         if let Some(path) = path.as_ref() {
@@ -260,14 +279,14 @@ impl Project {
                         this.python_activate_command(python_venv_directory, &settings.detect_venv);
                 }
 
-                match ssh_details {
-                    Some(SshDetails {
+                match remote_details {
+                    Some(RemoteDetails {
                         host,
-                        ssh_command,
+                        command,
                         envs,
                         path_style,
                     }) => {
-                        log::debug!("Connecting to a remote server: {ssh_command:?}");
+                        log::debug!("Connecting to a remote server: {command:?}");
 
                         // Alacritty sets its terminfo to `alacritty`, this requiring hosts to have it installed
                         // to properly display colors.
@@ -276,8 +295,9 @@ impl Project {
                         env.entry("TERM".to_string())
                             .or_insert_with(|| "xterm-256color".to_string());
 
-                        let (program, args) = wrap_for_ssh(
-                            &ssh_command,
+                        let (program, args) = wrap_for_remote(
+                            &command,
+                            true,
                             None,
                             path.as_deref(),
                             env,
@@ -323,18 +343,19 @@ impl Project {
                     );
                 }
 
-                match ssh_details {
-                    Some(SshDetails {
+                match remote_details {
+                    Some(RemoteDetails {
                         host,
-                        ssh_command,
+                        command,
                         envs,
                         path_style,
                     }) => {
-                        log::debug!("Connecting to a remote server: {ssh_command:?}");
+                        log::debug!("Connecting to a remote server: {command:?}");
                         env.entry("TERM".to_string())
                             .or_insert_with(|| "xterm-256color".to_string());
-                        let (program, args) = wrap_for_ssh(
-                            &ssh_command,
+                        let (program, args) = wrap_for_remote(
+                            &command,
+                            true,
                             spawn_task
                                 .command
                                 .as_ref()
@@ -569,6 +590,28 @@ impl Project {
     }
 }
 
+/// Wraps `command` to run on the remote host. Terminals are always attached over ssh, but only
+/// attached to a container if `tty` is set.
+pub fn wrap_for_remote(
+    remote_command: &RemoteCommand,
+    tty: bool,
+    command: Option<(&String, &Vec<String>)>,
+    path: Option<&Path>,
+    env: HashMap<String, String>,
+    venv_directory: Option<&Path>,
+    path_style: PathStyle,
+) -> (String, Vec<String>) {
+    match remote_command {
+        RemoteCommand::Ssh(ssh_command) => {
+            wrap_for_ssh(ssh_command, command, path, env, venv_directory, path_style)
+        }
+        RemoteCommand::Container(container_exec_args) => {
+            let commands = remote_shell_commands(command, path, env, venv_directory, path_style);
+            container_exec_args.wrap(tty, vec!["sh".to_string(), "-c".to_string(), commands])
+        }
+    }
+}
+
 pub fn wrap_for_ssh(
     ssh_command: &SshCommand,
     command: Option<(&String, &Vec<String>)>,
@@ -577,6 +620,26 @@ pub fn wrap_for_ssh(
     venv_directory: Option<&Path>,
     path_style: PathStyle,
 ) -> (String, Vec<String>) {
+    let commands = remote_shell_commands(command, path, env, venv_directory, path_style);
+    let shell_invocation = format!("sh -c {}", shlex::try_quote(&commands).unwrap());
+
+    let program = "ssh".to_string();
+    let mut args = ssh_command.arguments.clone();
+
+    args.push("-t".to_string());
+    args.push(shell_invocation);
+    (program, args)
+}
+
+/// Builds the shell commands that run `command` in `path` on the remote host, or a login shell if
+/// there is no command.
+fn remote_shell_commands(
+    command: Option<(&String, &Vec<String>)>,
+    path: Option<&Path>,
+    env: HashMap<String, String>,
+    venv_directory: Option<&Path>,
+    path_style: PathStyle,
+) -> String {
     let to_run = if let Some((command, args)) = command {
         // DEFAULT_REMOTE_SHELL is '"${SHELL:-sh}"' so must not be escaped
         let command: Option<Cow<str>> = if command == DEFAULT_REMOTE_SHELL {
@@ -603,7 +666,7 @@ pub fn wrap_for_ssh(
         }
     }
 
-    let commands = if let Some(path) = path {
+    if let Some(path) = path {
         let path = RemotePathBuf::new(path.to_path_buf(), path_style).to_string();
         // shlex will wrap the command in single quotes (''), disabling ~ expansion,
         // replace ith with something that works
@@ -620,15 +683,7 @@ pub fn wrap_for_ssh(
         }
     } else {
         format!("cd; {env_changes} {to_run}")
-    };
-    let shell_invocation = format!("sh -c {}", shlex::try_quote(&commands).unwrap());
-
-    let program = "ssh".to_string();
-    let mut args = ssh_command.arguments.clone();
-
-    args.push("-t".to_string());
-    args.push(shell_invocation);
-    (program, args)
+    }
 }
 
 fn add_environment_path(env: &mut HashMap<String, String>, new_path: &Path) -> Result<()> {
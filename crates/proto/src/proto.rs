@@ -223,6 +223,24 @@ messages!(
     (ResolveInlayHintResponse, Background),
     (GetDocumentColor, Background),
     (GetDocumentColorResponse, Background),
+    (GetSemanticTokens, Background),
+    (GetSemanticTokensResponse, Background),
+    (RefreshSemanticTokens, Background),
+    (PrepareCallHierarchy, Background),
+    (GetIncomingCalls, Background),
+    (GetOutgoingCalls, Background),
+    (PrepareTypeHierarchy, Background),
+    (GetSupertypes, Background),
+    (GetSubtypes, Background),
+    (HierarchyItemsResponse, Background),
+    (GetFoldingRanges, Background),
+    (GetFoldingRangesResponse, Background),
+    (GetDocumentLinks, Background),
+    (GetDocumentLinksResponse, Background),
+    (ResolveDocumentLink, Background),
+    (ResolveDocumentLinkResponse, Background),
+    (GetSelectionRanges, Background),
+    (GetSelectionRangesResponse, Background),
     (GetColorPresentation, Background),
     (GetColorPresentationResponse, Background),
     (RefreshCodeLens, Background),
@@ -306,6 +324,29 @@ messages!(
     (GitDiff, Background),
     (GitDiffResponse, Background),
     (GitInit, Background),
+    (GitStashList, Background),
+    (GitStashListResponse, Background),
+    (GitStashPush, Background),
+    (GitStashApply, Background),
+    (GitStashPop, Background),
+    (GitStashDrop, Background),
+    (GitLog, Background),
+    (GitLogResponse, Background),
+    (GitCherryPick, Background),
+    (GitRevert, Background),
+    (GitRebase, Background),
+    (GitContinueOperation, Background),
+    (GitAbortOperation, Background),
+    (GitGetTags, Background),
+    (GitTagsResponse, Background),
+    (GitCreateTag, Background),
+    (GitDeleteTag, Background),
+    (GitCheckoutTag, Background),
+    (GitPushTag, Background),
+    (GitWorktreeList, Background),
+    (GitWorktreesResponse, Background),
+    (GitWorktreeAdd, Background),
+    (GitWorktreeRemove, Background),
     (GetDebugAdapterBinary, Background),
     (DebugAdapterBinary, Background),
     (RunDebugLocators, Background),
@@ -388,6 +429,7 @@ request_messages!(
     (PrepareRename, PrepareRenameResponse),
     (RefreshInlayHints, Ack),
     (RefreshCodeLens, Ack),
+    (RefreshSemanticTokens, Ack),
     (RejoinChannelBuffers, RejoinChannelBuffersResponse),
     (RejoinRoom, RejoinRoomResponse),
     (ReloadBuffers, ReloadBuffersResponse),
@@ -405,6 +447,17 @@ request_messages!(
     ),
     (ResolveInlayHint, ResolveInlayHintResponse),
     (GetDocumentColor, GetDocumentColorResponse),
+    (GetSemanticTokens, GetSemanticTokensResponse),
+    (PrepareCallHierarchy, HierarchyItemsResponse),
+    (GetIncomingCalls, HierarchyItemsResponse),
+    (GetOutgoingCalls, HierarchyItemsResponse),
+    (PrepareTypeHierarchy, HierarchyItemsResponse),
+    (GetSupertypes, HierarchyItemsResponse),
+    (GetSubtypes, HierarchyItemsResponse),
+    (GetFoldingRanges, GetFoldingRangesResponse),
+    (GetDocumentLinks, GetDocumentLinksResponse),
+    (ResolveDocumentLink, ResolveDocumentLinkResponse),
+    (GetSelectionRanges, GetSelectionRangesResponse),
     (GetColorPresentation, GetColorPresentationResponse),
     (RespondToChannelInvite, Ack),
     (RespondToContactRequest, Ack),
@@ -475,6 +528,25 @@ request_messages!(
     (CheckForPushedCommits, CheckForPushedCommitsResponse),
     (GitDiff, GitDiffResponse),
     (GitInit, Ack),
+    (GitStashList, GitStashListResponse),
+    (GitStashPush, Ack),
+    (GitStashApply, Ack),
+    (GitStashPop, Ack),
+    (GitStashDrop, Ack),
+    (GitLog, GitLogResponse),
+    (GitCherryPick, Ack),
+    (GitRevert, Ack),
+    (GitRebase, Ack),
+    (GitContinueOperation, Ack),
+    (GitAbortOperation, Ack),
+    (GitGetTags, GitTagsResponse),
+    (GitCreateTag, Ack),
+    (GitDeleteTag, Ack),
+    (GitCheckoutTag, Ack),
+    (GitPushTag, RemoteMessageResponse),
+    (GitWorktreeList, GitWorktreesResponse),
+    (GitWorktreeAdd, Ack),
+    (GitWorktreeRemove, Ack),
     (ToggleBreakpoint, Ack),
     (GetDebugAdapterBinary, DebugAdapterBinary),
     (RunDebugLocators, DebugRequest),
@@ -498,6 +570,17 @@ entity_messages!(
     CreateBufferForPeer,
     CreateProjectEntry,
     GetDocumentColor,
+    GetSemanticTokens,
+    PrepareCallHierarchy,
+    GetIncomingCalls,
+    GetOutgoingCalls,
+    PrepareTypeHierarchy,
+    GetSupertypes,
+    GetSubtypes,
+    GetFoldingRanges,
+    GetDocumentLinks,
+    ResolveDocumentLink,
+    GetSelectionRanges,
     DeleteProjectEntry,
     ExpandProjectEntry,
     ExpandAllForProjectEntry,
@@ -537,6 +620,7 @@ entity_messages!(
     PrepareRename,
     RefreshInlayHints,
     RefreshCodeLens,
+    RefreshSemanticTokens,
     ReloadBuffers,
     RemoveProjectCollaborator,
     RenameProjectEntry,
@@ -603,6 +687,25 @@ entity_messages!(
     CheckForPushedCommits,
     GitDiff,
     GitInit,
+    GitStashList,
+    GitStashPush,
+    GitStashApply,
+    GitStashPop,
+    GitStashDrop,
+    GitLog,
+    GitCherryPick,
+    GitRevert,
+    GitRebase,
+    GitContinueOperation,
+    GitAbortOperation,
+    GitGetTags,
+    GitCreateTag,
+    GitDeleteTag,
+    GitCheckoutTag,
+    GitPushTag,
+    GitWorktreeList,
+    GitWorktreeAdd,
+    GitWorktreeRemove,
     BreakpointsForFile,
     ToggleBreakpoint,
     RunDebugLocators,
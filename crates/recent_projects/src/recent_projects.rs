@@ -3,7 +3,7 @@ mod remote_servers;
 mod ssh_config;
 mod ssh_connections;
 
-pub use ssh_connections::{is_connecting_over_ssh, open_ssh_project};
+pub use ssh_connections::{is_connecting_over_ssh, open_remote_project, open_ssh_project};
 
 use disconnected_overlay::DisconnectedOverlay;
 use fuzzy::{StringMatch, StringMatchCandidate};
@@ -354,6 +354,43 @@ impl PickerDelegate for RecentProjectsDelegate {
                                     .await
                                 })
                             }
+                            SerializedWorkspaceLocation::DevContainer(dev_container_project) => {
+                                let app_state = workspace.app_state().clone();
+
+                                let replace_window = if replace_current_window {
+                                    window.window_handle().downcast::<Workspace>()
+                                } else {
+                                    None
+                                };
+
+                                let open_options = OpenOptions {
+                                    replace_window,
+                                    ..Default::default()
+                                };
+
+                                let connection_options = SshSettings::get_global(cx)
+                                    .dev_container_options_for(
+                                        PathBuf::from(&dev_container_project.project_path),
+                                        dev_container_project.runtime,
+                                    );
+
+                                let paths = dev_container_project
+                                    .paths
+                                    .iter()
+                                    .map(PathBuf::from)
+                                    .collect();
+
+                                cx.spawn_in(window, async move |_, cx| {
+                                    open_remote_project(
+                                        connection_options.into(),
+                                        paths,
+                                        app_state,
+                                        open_options,
+                                        cx,
+                                    )
+                                    .await
+                                })
+                            }
                         }
                     }
                 })
@@ -420,9 +457,12 @@ impl PickerDelegate for RecentProjectsDelegate {
                                         .color(Color::Muted)
                                         .into_any_element()
                                 }
-                                SerializedWorkspaceLocation::Ssh(_) => Icon::new(IconName::Server)
-                                    .color(Color::Muted)
-                                    .into_any_element(),
+                                SerializedWorkspaceLocation::Ssh(_)
+                                | SerializedWorkspaceLocation::DevContainer(_) => {
+                                    Icon::new(IconName::Server)
+                                        .color(Color::Muted)
+                                        .into_any_element()
+                                }
                             })
                         })
                         .child({
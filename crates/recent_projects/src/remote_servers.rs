@@ -21,13 +21,14 @@ use gpui::WeakEntity;
 use gpui::canvas;
 use gpui::{
     AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
-    PromptLevel, ScrollHandle, Window,
+    PathPromptOptions, PromptLevel, ScrollHandle, Window,
 };
 use paths::global_ssh_config_file;
 use paths::user_ssh_config_file;
 use picker::Picker;
 use project::Fs;
 use project::Project;
+use remote::dev_container::DevContainerConfig;
 use remote::ssh_session::ConnectionIdentifier;
 use remote::{SshConnectionOptions, SshRemoteClient};
 use settings::Settings;
@@ -54,6 +55,7 @@ use workspace::{
 };
 
 use crate::ssh_config::parse_ssh_config_hosts;
+use crate::ssh_connections::DevContainerConnection;
 use crate::ssh_connections::RemoteSettingsContent;
 use crate::ssh_connections::SshConnection;
 use crate::ssh_connections::SshConnectionHeader;
@@ -62,6 +64,7 @@ use crate::ssh_connections::SshProject;
 use crate::ssh_connections::SshPrompt;
 use crate::ssh_connections::SshSettings;
 use crate::ssh_connections::connect_over_ssh;
+use crate::ssh_connections::open_remote_project;
 use crate::ssh_connections::open_ssh_project;
 
 mod navigation_base {}
@@ -300,6 +303,8 @@ struct DefaultState {
     scrollbar: ScrollbarState,
     add_new_server: NavigableEntry,
     servers: Vec<RemoteEntry>,
+    add_dev_container: NavigableEntry,
+    dev_containers: Vec<(NavigableEntry, DevContainerConnection)>,
 }
 
 impl DefaultState {
@@ -345,10 +350,18 @@ impl DefaultState {
             }));
         }
 
+        let add_dev_container = NavigableEntry::new(&handle, cx);
+        let dev_containers = ssh_settings
+            .dev_containers()
+            .map(|dev_container| (NavigableEntry::new(&handle, cx), dev_container))
+            .collect();
+
         Self {
             scrollbar,
             add_new_server,
             servers,
+            add_dev_container,
+            dev_containers,
         }
     }
 }
@@ -472,7 +485,8 @@ impl RemoteServerProjects {
                 return;
             }
         };
-        let ssh_prompt = cx.new(|cx| SshPrompt::new(&connection_options, window, cx));
+        let ssh_prompt =
+            cx.new(|cx| SshPrompt::new(&connection_options.clone().into(), window, cx));
 
         let connection = connect_over_ssh(
             ConnectionIdentifier::setup(),
@@ -552,12 +566,17 @@ impl RemoteServerProjects {
         };
 
         let create_new_window = self.create_new_window;
-        let connection_options = ssh_connection.into();
+        let connection_options: SshConnectionOptions = ssh_connection.into();
         workspace.update(cx, |_, cx| {
             cx.defer_in(window, move |workspace, window, cx| {
                 let app_state = workspace.app_state().clone();
                 workspace.toggle_modal(window, cx, |window, cx| {
-                    SshConnectionModal::new(&connection_options, Vec::new(), window, cx)
+                    SshConnectionModal::new(
+                        &connection_options.clone().into(),
+                        Vec::new(),
+                        window,
+                        cx,
+                    )
                 });
                 let prompt = workspace
                     .active_modal::<SshConnectionModal>(cx)
@@ -1036,6 +1055,162 @@ impl RemoteServerProjects {
         });
     }
 
+    fn render_dev_container(
+        &mut self,
+        ix: usize,
+        (navigation, dev_container): &(NavigableEntry, DevContainerConnection),
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let label = dev_container
+            .nickname
+            .clone()
+            .unwrap_or_else(|| dev_container.path.to_string_lossy().to_string());
+
+        div()
+            .id(("dev-container-container", ix))
+            .track_focus(&navigation.focus_handle)
+            .anchor_scroll(navigation.scroll_anchor.clone())
+            .on_action(cx.listener({
+                let dev_container = dev_container.clone();
+                move |this, _: &menu::Confirm, window, cx| {
+                    this.open_dev_container(dev_container.clone(), false, window, cx);
+                }
+            }))
+            .on_action(cx.listener({
+                let dev_container = dev_container.clone();
+                move |this, _: &menu::SecondaryConfirm, window, cx| {
+                    this.open_dev_container(dev_container.clone(), true, window, cx);
+                }
+            }))
+            .child(
+                ListItem::new(("dev-container", ix))
+                    .toggle_state(navigation.focus_handle.contains_focused(window, cx))
+                    .inset(true)
+                    .spacing(ui::ListItemSpacing::Sparse)
+                    .start_slot(
+                        Icon::new(IconName::Server)
+                            .color(Color::Muted)
+                            .size(IconSize::Small),
+                    )
+                    .child(Label::new(label))
+                    .on_click(cx.listener({
+                        let dev_container = dev_container.clone();
+                        move |this, e: &ClickEvent, window, cx| {
+                            let secondary_confirm = e.down.modifiers.platform;
+                            this.open_dev_container(
+                                dev_container.clone(),
+                                secondary_confirm,
+                                window,
+                                cx,
+                            )
+                        }
+                    }))
+                    .end_hover_slot::<AnyElement>(Some(
+                        div()
+                            .mr_2()
+                            .child(
+                                // Right-margin to offset it from the Scrollbar
+                                IconButton::new("remove-dev-container", IconName::TrashAlt)
+                                    .icon_size(IconSize::Small)
+                                    .shape(IconButtonShape::Square)
+                                    .size(ButtonSize::Large)
+                                    .tooltip(Tooltip::text("Remove Dev Container"))
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.delete_dev_container(ix, cx)
+                                    })),
+                            )
+                            .into_any_element(),
+                    )),
+            )
+    }
+
+    fn open_dev_container(
+        &mut self,
+        dev_container: DevContainerConnection,
+        secondary_confirm: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(app_state) = self
+            .workspace
+            .read_with(cx, |workspace, _| workspace.app_state().clone())
+            .log_err()
+        else {
+            return;
+        };
+        cx.emit(DismissEvent);
+
+        let replace_window = match (self.create_new_window, secondary_confirm) {
+            (true, false) | (false, true) => None,
+            (true, true) | (false, false) => window.window_handle().downcast::<Workspace>(),
+        };
+
+        let fs = app_state.fs.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            let config = DevContainerConfig::load(fs.as_ref(), &dev_container.path).await?;
+            let workspace_folder = config.workspace_folder(&dev_container.path);
+            open_remote_project(
+                remote::DevContainerConnectionOptions::from(dev_container).into(),
+                vec![PathBuf::from(workspace_folder)],
+                app_state,
+                OpenOptions {
+                    replace_window,
+                    ..OpenOptions::default()
+                },
+                cx,
+            )
+            .await
+        })
+        .detach_and_prompt_err("Failed to open dev container", window, cx, |_, _, _| None);
+    }
+
+    fn add_dev_container(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(fs) = self
+            .workspace
+            .read_with(cx, |workspace, _| workspace.app_state().fs.clone())
+            .log_err()
+        else {
+            return;
+        };
+        let paths = cx.prompt_for_paths(PathPromptOptions {
+            files: false,
+            directories: true,
+            multiple: false,
+        });
+        cx.spawn_in(window, async move |this, cx| {
+            let Some(path) = paths.await??.and_then(|paths| paths.into_iter().next()) else {
+                return Ok(());
+            };
+            anyhow::ensure!(
+                DevContainerConfig::find(fs.as_ref(), &path).await.is_some(),
+                "{} has no .devcontainer/devcontainer.json",
+                path.display()
+            );
+            this.update(cx, |this, cx| {
+                this.update_settings_file(cx, move |setting, _| {
+                    setting
+                        .dev_containers
+                        .get_or_insert(Default::default())
+                        .push(DevContainerConnection {
+                            path,
+                            runtime: Default::default(),
+                            nickname: None,
+                        })
+                });
+            })
+        })
+        .detach_and_prompt_err("Failed to add dev container", window, cx, |_, _, _| None);
+    }
+
+    fn delete_dev_container(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.update_settings_file(cx, move |setting, _| {
+            if let Some(dev_containers) = setting.dev_containers.as_mut() {
+                dev_containers.remove(ix);
+            }
+        });
+    }
+
     fn render_create_remote_server(
         &self,
         state: &CreateRemoteServer,
@@ -1425,6 +1600,15 @@ impl RemoteServerProjects {
             should_rebuild = true;
         };
 
+        if state
+            .dev_containers
+            .iter()
+            .map(|(_, dev_container)| dev_container)
+            .ne(ssh_settings.dev_containers.iter().flatten())
+        {
+            should_rebuild = true;
+        }
+
         if !should_rebuild && ssh_settings.read_ssh_config {
             let current_ssh_hosts: BTreeSet<SharedString> = state
                 .servers
@@ -1481,6 +1665,52 @@ impl RemoteServerProjects {
                 cx.notify();
             }));
 
+        let add_dev_container_button = div()
+            .id("add-dev-container-container")
+            .track_focus(&state.add_dev_container.focus_handle)
+            .anchor_scroll(state.add_dev_container.scroll_anchor.clone())
+            .child(
+                ListItem::new("add-dev-container")
+                    .toggle_state(
+                        state
+                            .add_dev_container
+                            .focus_handle
+                            .contains_focused(window, cx),
+                    )
+                    .inset(true)
+                    .spacing(ui::ListItemSpacing::Sparse)
+                    .start_slot(Icon::new(IconName::Plus).color(Color::Muted))
+                    .child(Label::new("Add Dev Container"))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.add_dev_container(window, cx);
+                    })),
+            )
+            .on_action(cx.listener(|this, _: &menu::Confirm, window, cx| {
+                this.add_dev_container(window, cx);
+            }));
+
+        let dev_containers =
+            v_flex()
+                .w_full()
+                .child(ListSeparator)
+                .child(
+                    div().px_3().pt_0p5().child(
+                        Label::new("Dev Containers")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+                )
+                .child(
+                    List::new()
+                        .children(state.dev_containers.iter().enumerate().map(
+                            |(ix, dev_container)| {
+                                self.render_dev_container(ix, dev_container, window, cx)
+                                    .into_any_element()
+                            },
+                        ))
+                        .child(add_dev_container_button),
+                );
+
         let handle = &**scroll_state.scroll_handle() as &dyn Any;
         let Some(scroll_handle) = handle.downcast_ref::<ScrollHandle>() else {
             unreachable!()
@@ -1511,6 +1741,7 @@ impl RemoteServerProjects {
                                 .into_any_element()
                         })),
                 )
+                .child(dev_containers)
                 .into_any_element(),
         )
         .entry(state.add_new_server.clone());
@@ -1535,6 +1766,10 @@ impl RemoteServerProjects {
                 }
             }
         }
+        for (navigation, _) in &state.dev_containers {
+            modal_section = modal_section.entry(navigation.clone());
+        }
+        modal_section = modal_section.entry(state.add_dev_container.clone());
         let mut modal_section = modal_section.render(window, cx).into_any_element();
 
         let (create_window, reuse_window) = if self.create_new_window {
@@ -16,7 +16,9 @@ use language::CursorShape;
 use markdown::{Markdown, MarkdownElement, MarkdownStyle};
 use release_channel::ReleaseChannel;
 use remote::ssh_session::{ConnectionIdentifier, SshPortForwardOption};
-use remote::{SshConnectionOptions, SshPlatform, SshRemoteClient};
+use remote::{
+    ContainerRuntime, RemoteConnectionOptions, SshConnectionOptions, SshPlatform, SshRemoteClient,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsSources};
@@ -31,6 +33,7 @@ use workspace::{AppState, ModalView, Workspace};
 #[derive(Deserialize)]
 pub struct SshSettings {
     pub ssh_connections: Option<Vec<SshConnection>>,
+    pub dev_containers: Option<Vec<DevContainerConnection>>,
     /// Whether to read ~/.ssh/config for ssh connection sources.
     #[serde(default = "default_true")]
     pub read_ssh_config: bool,
@@ -41,6 +44,10 @@ impl SshSettings {
         self.ssh_connections.clone().into_iter().flatten()
     }
 
+    pub fn dev_containers(&self) -> impl Iterator<Item = DevContainerConnection> + use<> {
+        self.dev_containers.clone().into_iter().flatten()
+    }
+
     pub fn connection_options_for(
         &self,
         host: String,
@@ -68,6 +75,24 @@ impl SshSettings {
             ..Default::default()
         }
     }
+
+    pub fn dev_container_options_for(
+        &self,
+        project_path: PathBuf,
+        runtime: ContainerRuntime,
+    ) -> remote::DevContainerConnectionOptions {
+        let nickname = self
+            .dev_containers()
+            .find(|dev_container| {
+                dev_container.path == project_path && dev_container.runtime == runtime
+            })
+            .and_then(|dev_container| dev_container.nickname);
+        remote::DevContainerConnectionOptions {
+            project_path,
+            runtime,
+            nickname,
+        }
+    }
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
@@ -116,9 +141,34 @@ pub struct SshProject {
     pub paths: Vec<String>,
 }
 
+/// A local project that is opened inside of the container described by its
+/// `.devcontainer/devcontainer.json`.
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct DevContainerConnection {
+    /// The local folder of the project.
+    pub path: PathBuf,
+    /// The container runtime used to build and run the container.
+    #[serde(default)]
+    pub runtime: ContainerRuntime,
+    /// Name to use for this container in UI.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+}
+
+impl From<DevContainerConnection> for remote::DevContainerConnectionOptions {
+    fn from(val: DevContainerConnection) -> Self {
+        remote::DevContainerConnectionOptions {
+            project_path: val.path,
+            runtime: val.runtime,
+            nickname: val.nickname,
+        }
+    }
+}
+
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct RemoteSettingsContent {
     pub ssh_connections: Option<Vec<SshConnection>>,
+    pub dev_containers: Option<Vec<DevContainerConnection>>,
     pub read_ssh_config: Option<bool>,
 }
 
@@ -159,12 +209,12 @@ pub struct SshConnectionModal {
 
 impl SshPrompt {
     pub(crate) fn new(
-        connection_options: &SshConnectionOptions,
+        connection_options: &RemoteConnectionOptions,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
         let connection_string = connection_options.connection_string().into();
-        let nickname = connection_options.nickname.clone().map(|s| s.into());
+        let nickname = connection_options.nickname().map(|s| s.into());
 
         Self {
             connection_string,
@@ -298,7 +348,7 @@ impl Render for SshPrompt {
 
 impl SshConnectionModal {
     pub(crate) fn new(
-        connection_options: &SshConnectionOptions,
+        connection_options: &RemoteConnectionOptions,
         paths: Vec<PathBuf>,
         window: &mut Window,
         cx: &mut Context<Self>,
@@ -551,7 +601,7 @@ pub fn connect_over_ssh(
 
     remote::SshRemoteClient::new(
         unique_identifier,
-        connection_options,
+        connection_options.into(),
         rx,
         Arc::new(SshClientDelegate {
             window,
@@ -568,22 +618,51 @@ pub async fn open_ssh_project(
     app_state: Arc<AppState>,
     open_options: workspace::OpenOptions,
     cx: &mut AsyncApp,
+) -> Result<()> {
+    open_remote_project(
+        connection_options.into(),
+        paths,
+        app_state,
+        open_options,
+        cx,
+    )
+    .await
+}
+
+pub async fn open_remote_project(
+    connection_options: RemoteConnectionOptions,
+    paths: Vec<PathBuf>,
+    app_state: Arc<AppState>,
+    open_options: workspace::OpenOptions,
+    cx: &mut AsyncApp,
 ) -> Result<()> {
     let window = if let Some(window) = open_options.replace_window {
         window
     } else {
-        let workspace_position = cx
-            .update(|cx| {
-                workspace::ssh_workspace_position_from_db(
-                    connection_options.host.clone(),
-                    connection_options.port,
-                    connection_options.username.clone(),
-                    &paths,
-                    cx,
-                )
-            })?
-            .await
-            .context("fetching ssh workspace position from db")?;
+        let workspace_position = match &connection_options {
+            RemoteConnectionOptions::Ssh(connection_options) => cx
+                .update(|cx| {
+                    workspace::ssh_workspace_position_from_db(
+                        connection_options.host.clone(),
+                        connection_options.port,
+                        connection_options.username.clone(),
+                        &paths,
+                        cx,
+                    )
+                })?
+                .await
+                .context("fetching ssh workspace position from db")?,
+            RemoteConnectionOptions::DevContainer(connection_options) => cx
+                .update(|cx| {
+                    workspace::dev_container_workspace_position_from_db(
+                        connection_options.clone(),
+                        &paths,
+                        cx,
+                    )
+                })?
+                .await
+                .context("fetching dev container workspace position from db")?,
+        };
 
         let mut options =
             cx.update(|cx| (app_state.build_window_options)(workspace_position.display, cx))?;
@@ -628,10 +707,16 @@ pub async fn open_ssh_project(
                     ui.set_cancellation_tx(cancel_tx);
                 });
 
+                let known_password = match &connection_options {
+                    RemoteConnectionOptions::Ssh(connection_options) => {
+                        connection_options.password.clone()
+                    }
+                    RemoteConnectionOptions::DevContainer(_) => None,
+                };
                 Some(Arc::new(SshClientDelegate {
                     window: window.window_handle(),
                     ui: ui.downgrade(),
-                    known_password: connection_options.password.clone(),
+                    known_password,
                 }))
             }
         })?;
@@ -664,9 +749,15 @@ pub async fn open_ssh_project(
             log::error!("Failed to open project: {e:?}");
             let response = window
                 .update(cx, |_, window, cx| {
+                    let message = match &connection_options {
+                        RemoteConnectionOptions::Ssh(_) => "Failed to connect over SSH",
+                        RemoteConnectionOptions::DevContainer(_) => {
+                            "Failed to connect to the dev container"
+                        }
+                    };
                     window.prompt(
                         PromptLevel::Critical,
-                        "Failed to connect over SSH",
+                        message,
                         Some(&e.to_string()),
                         &["Retry", "Ok"],
                         cx,
@@ -0,0 +1,877 @@
+use crate::ssh_session::{
+    RemoteConnection, RemoteConnectionOptions, SshArgs, SshClientDelegate, SshRemoteConnection,
+    parse_platform, remote_server_binary_name,
+};
+use anyhow::{Context as _, Result, anyhow};
+use async_trait::async_trait;
+use fs::Fs;
+use futures::channel::mpsc::{Sender, UnboundedReceiver, UnboundedSender};
+use gpui::{App, AsyncApp, Task};
+use release_channel::{AppCommitSha, AppVersion, ReleaseChannel};
+use rpc::proto::Envelope;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smol::process::{self, Stdio};
+use std::{
+    collections::BTreeMap,
+    hash::{DefaultHasher, Hash as _, Hasher as _},
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+use util::{
+    ResultExt as _,
+    paths::{PathStyle, RemotePathBuf},
+};
+
+/// The label put on the containers Zed creates, holding the path of the project they were
+/// created for, so that they can be found again when reconnecting.
+const PROJECT_LABEL: &str = "dev.zed.project";
+
+/// Keeps the container running while Zed is not attached to it.
+const KEEP_ALIVE_SCRIPT: &str = "trap 'exit 0' TERM; while sleep 1000 & wait $!; do :; done";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerRuntime {
+    #[default]
+    Docker,
+    Podman,
+}
+
+impl ContainerRuntime {
+    pub fn command(&self) -> &'static str {
+        match self {
+            Self::Docker => "docker",
+            Self::Podman => "podman",
+        }
+    }
+
+    pub fn from_command(command: &str) -> Option<Self> {
+        match command {
+            "docker" => Some(Self::Docker),
+            "podman" => Some(Self::Podman),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Serialize, Deserialize)]
+pub struct DevContainerProjectId(pub u64);
+
+/// Options for running the remote server inside of the dev container of a local project.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DevContainerConnectionOptions {
+    /// The local project folder containing the `.devcontainer` configuration.
+    pub project_path: PathBuf,
+    pub runtime: ContainerRuntime,
+    pub nickname: Option<String>,
+}
+
+impl DevContainerConnectionOptions {
+    pub fn connection_string(&self) -> String {
+        format!(
+            "{} ({})",
+            self.project_path.display(),
+            self.runtime.command()
+        )
+    }
+}
+
+/// The subset of the [dev container specification](https://containers.dev/implementors/json_reference/)
+/// that Zed supports.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevContainerConfig {
+    pub name: Option<String>,
+    pub image: Option<String>,
+    pub build: Option<DevContainerBuild>,
+    pub workspace_folder: Option<String>,
+    pub workspace_mount: Option<String>,
+    #[serde(default)]
+    pub mounts: Vec<DevContainerMount>,
+    #[serde(default)]
+    pub container_env: BTreeMap<String, String>,
+    pub container_user: Option<String>,
+    pub remote_user: Option<String>,
+    #[serde(default)]
+    pub run_args: Vec<String>,
+    pub post_create_command: Option<LifecycleCommand>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevContainerBuild {
+    pub dockerfile: Option<String>,
+    pub context: Option<String>,
+    #[serde(default)]
+    pub args: BTreeMap<String, String>,
+    pub target: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum DevContainerMount {
+    /// A mount in the format of `--mount`, e.g. `type=bind,source=/tmp,target=/tmp`.
+    Spec(String),
+    Object {
+        #[serde(rename = "type")]
+        kind: Option<String>,
+        source: Option<String>,
+        target: String,
+    },
+}
+
+impl DevContainerMount {
+    fn to_spec(&self) -> String {
+        match self {
+            Self::Spec(spec) => spec.clone(),
+            Self::Object {
+                kind,
+                source,
+                target,
+            } => {
+                let mut spec = format!("type={}", kind.as_deref().unwrap_or("bind"));
+                if let Some(source) = source {
+                    spec.push_str(&format!(",source={source}"));
+                }
+                spec.push_str(&format!(",target={target}"));
+                spec
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum LifecycleCommand {
+    /// A command run by a shell.
+    Shell(String),
+    /// A program and its arguments, run without a shell.
+    Exec(Vec<String>),
+    /// Named commands, which Zed runs one after another.
+    Named(BTreeMap<String, LifecycleCommand>),
+}
+
+impl LifecycleCommand {
+    fn commands(&self) -> Vec<Vec<String>> {
+        match self {
+            Self::Shell(script) => vec![vec!["/bin/sh".into(), "-c".into(), script.clone()]],
+            Self::Exec(args) => vec![args.clone()],
+            Self::Named(commands) => commands.values().flat_map(Self::commands).collect(),
+        }
+    }
+}
+
+impl DevContainerConfig {
+    pub fn parse(contents: &str) -> Result<Self> {
+        serde_json_lenient::from_str(contents).context("parsing devcontainer.json")
+    }
+
+    /// Loads the configuration of the given project.
+    pub async fn load(fs: &dyn Fs, project_path: &Path) -> Result<Self> {
+        let path = Self::find(fs, project_path).await.with_context(|| {
+            format!(
+                "no .devcontainer/devcontainer.json found in {}",
+                project_path.display()
+            )
+        })?;
+        Self::parse(&fs.load(&path).await?)
+    }
+
+    /// Returns the path of the configuration of the given project, if it has one.
+    pub async fn find(fs: &dyn Fs, project_path: &Path) -> Option<PathBuf> {
+        for path in [
+            project_path.join(".devcontainer").join("devcontainer.json"),
+            project_path.join(".devcontainer.json"),
+        ] {
+            if fs.is_file(&path).await {
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    /// The folder in the container where the project is mounted.
+    pub fn workspace_folder(&self, project_path: &Path) -> String {
+        self.workspace_folder.clone().unwrap_or_else(|| {
+            format!(
+                "/workspaces/{}",
+                project_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy())
+                    .unwrap_or_default()
+            )
+        })
+    }
+}
+
+/// Replaces the variables of the specification that refer to the local machine, looking up
+/// environment variables with `env_var`.
+fn substitute_variables(
+    value: &str,
+    project_path: &Path,
+    workspace_folder: &str,
+    env_var: impl Fn(&str) -> Option<String>,
+) -> String {
+    let basename = project_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut value = value
+        .replace("${localWorkspaceFolder}", &project_path.to_string_lossy())
+        .replace("${localWorkspaceFolderBasename}", &basename)
+        .replace("${containerWorkspaceFolder}", workspace_folder)
+        .replace("${containerWorkspaceFolderBasename}", &basename);
+    // Scan on from the end of each replacement, so that values which themselves contain
+    // variables are left as they are.
+    let mut search_start = 0;
+    while let Some(offset) = value[search_start..].find("${localEnv:") {
+        let start = search_start + offset;
+        let Some(len) = value[start..].find('}') else {
+            break;
+        };
+        let variable = &value[start + "${localEnv:".len()..start + len];
+        let (name, default) = variable.split_once(':').unwrap_or((variable, ""));
+        let replacement = env_var(name).unwrap_or_else(|| default.to_string());
+        value.replace_range(start..start + len + 1, &replacement);
+        search_start = start + replacement.len();
+    }
+    value
+}
+
+/// How to run programs in a dev container from the local machine, e.g. for terminals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerExecArgs {
+    pub runtime: ContainerRuntime,
+    pub container_id: String,
+    pub user: Option<String>,
+}
+
+impl ContainerExecArgs {
+    /// Returns the program and arguments that run `command` in the container, allocating a
+    /// terminal for it if `tty` is set.
+    pub fn wrap(&self, tty: bool, command: Vec<String>) -> (String, Vec<String>) {
+        let mut args = vec![
+            "exec".to_string(),
+            if tty { "-it" } else { "-i" }.to_string(),
+        ];
+        if let Some(user) = &self.user {
+            args.push("-u".to_string());
+            args.push(user.clone());
+        }
+        args.push(self.container_id.clone());
+        args.extend(command);
+        (self.runtime.command().to_string(), args)
+    }
+}
+
+/// A container that runs the remote server.
+struct Container {
+    runtime: ContainerRuntime,
+    id: String,
+    user: Option<String>,
+}
+
+impl Container {
+    fn exec_command(&self, program: &str, args: &[&str]) -> process::Command {
+        let mut command = util::command::new_smol_command(self.runtime.command());
+        command.arg("exec");
+        if let Some(user) = &self.user {
+            command.args(["-u", user]);
+        }
+        command
+            .arg(&self.id)
+            .arg(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        command
+    }
+
+    async fn run_command(&self, program: &str, args: &[&str]) -> Result<String> {
+        let output = self.exec_command(program, args).output().await?;
+        anyhow::ensure!(
+            output.status.success(),
+            "failed to run command: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+async fn run_runtime_command(runtime: ContainerRuntime, args: &[&str]) -> Result<String> {
+    log::debug!("{} {:?}", runtime.command(), args);
+    let output = util::command::new_smol_command(runtime.command())
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await
+        .with_context(|| format!("failed to run {}", runtime.command()))?;
+    anyhow::ensure!(
+        output.status.success(),
+        "failed to run {} {}: {}",
+        runtime.command(),
+        args.first().unwrap_or(&""),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+pub(crate) struct DevContainerConnection {
+    options: DevContainerConnectionOptions,
+    container: Container,
+    remote_binary_path: RemotePathBuf,
+    killed: AtomicBool,
+}
+
+impl DevContainerConnection {
+    pub(crate) async fn new(
+        options: DevContainerConnectionOptions,
+        delegate: Arc<dyn SshClientDelegate>,
+        cx: &mut AsyncApp,
+    ) -> Result<Self> {
+        let fs = cx.update(|cx| <dyn Fs>::global(cx))?;
+        let config = DevContainerConfig::load(fs.as_ref(), &options.project_path).await?;
+        let config_path = DevContainerConfig::find(fs.as_ref(), &options.project_path).await;
+        let config_dir = config_path
+            .as_deref()
+            .and_then(Path::parent)
+            .unwrap_or(&options.project_path);
+
+        let label = format!("{PROJECT_LABEL}={}", options.project_path.display());
+        delegate.set_status(Some("Looking for an existing container"), cx);
+        let existing_container = run_runtime_command(
+            options.runtime,
+            &["ps", "-a", "-q", "--filter", &format!("label={label}")],
+        )
+        .await?
+        .lines()
+        .next()
+        .map(ToString::to_string);
+
+        let (container_id, created) = if let Some(container_id) = existing_container {
+            delegate.set_status(Some("Starting container"), cx);
+            run_runtime_command(options.runtime, &["start", &container_id]).await?;
+            (container_id, false)
+        } else {
+            let container_id =
+                create_container(&options, &config, config_dir, &label, &delegate, cx).await?;
+            (container_id, true)
+        };
+
+        let container = Container {
+            runtime: options.runtime,
+            id: container_id,
+            user: config
+                .remote_user
+                .clone()
+                .or_else(|| config.container_user.clone()),
+        };
+
+        if created && let Some(post_create_command) = &config.post_create_command {
+            delegate.set_status(Some("Running postCreateCommand"), cx);
+            if let Err(error) = run_lifecycle_command(&container, post_create_command).await {
+                // Start over with a new container on the next attempt.
+                run_runtime_command(options.runtime, &["rm", "-f", &container.id])
+                    .await
+                    .log_err();
+                return Err(error.context("postCreateCommand failed"));
+            }
+        }
+
+        let platform = parse_platform(&container.run_command("uname", &["-sm"]).await?)?;
+        let (release_channel, version, commit) = cx.update(|cx| {
+            (
+                ReleaseChannel::global(cx),
+                AppVersion::global(cx),
+                AppCommitSha::try_global(cx),
+            )
+        })?;
+
+        let home_dir = container
+            .run_command("sh", &["-c", "echo $HOME"])
+            .await?
+            .trim()
+            .to_string();
+        let remote_binary_path = RemotePathBuf::new(
+            Path::new(&home_dir)
+                .join(paths::remote_server_dir_relative())
+                .join(remote_server_binary_name(release_channel, version, commit)),
+            PathStyle::Posix,
+        );
+
+        if container
+            .run_command(&remote_binary_path.to_string(), &["version"])
+            .await
+            .is_err()
+        {
+            let wanted_version = match release_channel {
+                ReleaseChannel::Nightly => None,
+                ReleaseChannel::Dev => anyhow::bail!(
+                    "no remote server exists in the container at ({:?})",
+                    remote_binary_path
+                ),
+                _ => Some(version),
+            };
+            let src_path = delegate
+                .download_server_binary_locally(platform, release_channel, wanted_version, cx)
+                .await?;
+
+            delegate.set_status(Some("Uploading remote development server"), cx);
+            let tmp_path = format!("/tmp/zed-remote-server-{}.gz", std::process::id());
+            run_runtime_command(
+                options.runtime,
+                &[
+                    "cp",
+                    &src_path.to_string_lossy(),
+                    &format!("{}:{}", container.id, tmp_path),
+                ],
+            )
+            .await
+            .context("failed to upload server binary")?;
+
+            delegate.set_status(Some("Extracting remote development server"), cx);
+            let dst_path = remote_binary_path.to_string();
+            let tmp_binary_path = format!("{dst_path}-{}", std::process::id());
+            let dst_dir = remote_binary_path
+                .parent()
+                .map(|parent| parent.to_string())
+                .unwrap_or_default();
+            let script = format!(
+                "mkdir -p {dst_dir} && gunzip -c {tmp_path} > {tmp_binary_path} && chmod 755 {tmp_binary_path} && mv {tmp_binary_path} {dst_path}; rm -f {tmp_path}",
+                dst_dir = quote(&dst_dir),
+                tmp_path = quote(&tmp_path),
+                tmp_binary_path = quote(&tmp_binary_path),
+                dst_path = quote(&dst_path),
+            );
+            container.run_command("sh", &["-c", &script]).await?;
+        }
+
+        Ok(Self {
+            options,
+            container,
+            remote_binary_path,
+            killed: AtomicBool::new(false),
+        })
+    }
+}
+
+async fn run_lifecycle_command(container: &Container, command: &LifecycleCommand) -> Result<()> {
+    for command in command.commands() {
+        let Some((program, args)) = command.split_first() else {
+            continue;
+        };
+        let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+        container.run_command(program, &args).await?;
+    }
+    Ok(())
+}
+
+async fn create_container(
+    options: &DevContainerConnectionOptions,
+    config: &DevContainerConfig,
+    config_dir: &Path,
+    label: &str,
+    delegate: &Arc<dyn SshClientDelegate>,
+    cx: &mut AsyncApp,
+) -> Result<String> {
+    let image = if let Some(build) = &config.build {
+        let mut hasher = DefaultHasher::new();
+        options.project_path.hash(&mut hasher);
+        let tag = format!("zed-dev-container-{:x}", hasher.finish());
+
+        delegate.set_status(Some("Building container image"), cx);
+        let dockerfile = config_dir.join(build.dockerfile.as_deref().unwrap_or("Dockerfile"));
+        let context = config_dir.join(build.context.as_deref().unwrap_or("."));
+        let mut args = vec![
+            "build".to_string(),
+            "-f".to_string(),
+            dockerfile.to_string_lossy().to_string(),
+            "-t".to_string(),
+            tag.clone(),
+        ];
+        for (name, value) in &build.args {
+            args.push("--build-arg".to_string());
+            args.push(format!("{name}={value}"));
+        }
+        if let Some(target) = &build.target {
+            args.push("--target".to_string());
+            args.push(target.clone());
+        }
+        args.push(context.to_string_lossy().to_string());
+        run_runtime_command(
+            options.runtime,
+            &args.iter().map(String::as_str).collect::<Vec<_>>(),
+        )
+        .await
+        .context("failed to build the container image")?;
+        tag
+    } else if let Some(image) = &config.image {
+        image.clone()
+    } else {
+        anyhow::bail!(
+            "devcontainer.json must specify either `image` or `build`, Docker Compose configurations are not supported"
+        );
+    };
+
+    delegate.set_status(Some("Creating container"), cx);
+    let workspace_folder = config.workspace_folder(&options.project_path);
+    let substitute = |value: &str| {
+        substitute_variables(value, &options.project_path, &workspace_folder, |name| {
+            std::env::var(name).ok()
+        })
+    };
+    let workspace_mount = config.workspace_mount.as_deref().map_or_else(
+        || {
+            format!(
+                "type=bind,source={},target={}",
+                options.project_path.display(),
+                workspace_folder
+            )
+        },
+        substitute,
+    );
+
+    let mut args = vec![
+        "run".to_string(),
+        "-d".to_string(),
+        "--label".to_string(),
+        label.to_string(),
+        "--mount".to_string(),
+        workspace_mount,
+        "-w".to_string(),
+        workspace_folder.clone(),
+    ];
+    for mount in &config.mounts {
+        args.push("--mount".to_string());
+        args.push(substitute(&mount.to_spec()));
+    }
+    for (name, value) in &config.container_env {
+        args.push("-e".to_string());
+        args.push(format!("{name}={}", substitute(value)));
+    }
+    if let Some(user) = &config.container_user {
+        args.push("-u".to_string());
+        args.push(user.clone());
+    }
+    args.extend(config.run_args.iter().map(|arg| substitute(arg)));
+    args.extend([
+        "--entrypoint".to_string(),
+        "/bin/sh".to_string(),
+        image,
+        "-c".to_string(),
+        KEEP_ALIVE_SCRIPT.to_string(),
+    ]);
+
+    run_runtime_command(
+        options.runtime,
+        &args.iter().map(String::as_str).collect::<Vec<_>>(),
+    )
+    .await
+    .context("failed to create the container")
+}
+
+fn quote(value: &str) -> std::borrow::Cow<'_, str> {
+    shlex::try_quote(value).unwrap_or_default()
+}
+
+#[async_trait(?Send)]
+impl RemoteConnection for DevContainerConnection {
+    fn start_proxy(
+        &self,
+        unique_identifier: String,
+        reconnect: bool,
+        incoming_tx: UnboundedSender<Envelope>,
+        outgoing_rx: UnboundedReceiver<Envelope>,
+        connection_activity_tx: Sender<()>,
+        delegate: Arc<dyn SshClientDelegate>,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<i32>> {
+        delegate.set_status(Some("Starting proxy"), cx);
+
+        let mut start_proxy_command = format!(
+            "exec {} proxy --identifier {}",
+            quote(&self.remote_binary_path.to_string()),
+            quote(&unique_identifier),
+        );
+        if let Some(rust_log) = std::env::var("RUST_LOG").ok() {
+            start_proxy_command = format!("RUST_LOG={} {}", quote(&rust_log), start_proxy_command)
+        }
+        if let Some(rust_backtrace) = std::env::var("RUST_BACKTRACE").ok() {
+            start_proxy_command = format!(
+                "RUST_BACKTRACE={} {}",
+                quote(&rust_backtrace),
+                start_proxy_command
+            )
+        }
+        if reconnect {
+            start_proxy_command.push_str(" --reconnect");
+        }
+
+        let mut command = util::command::new_smol_command(self.container.runtime.command());
+        command.args(["exec", "-i"]);
+        if let Some(user) = &self.container.user {
+            command.args(["-u", user]);
+        }
+        let proxy_process = match command
+            .args([self.container.id.as_str(), "sh", "-c", &start_proxy_command])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            // IMPORTANT: we kill this process when we drop the task that uses it.
+            .kill_on_drop(true)
+            .spawn()
+        {
+            Ok(process) => process,
+            Err(error) => {
+                return Task::ready(Err(anyhow!("failed to spawn remote server: {}", error)));
+            }
+        };
+
+        SshRemoteConnection::multiplex(
+            proxy_process,
+            incoming_tx,
+            outgoing_rx,
+            connection_activity_tx,
+            cx,
+        )
+    }
+
+    fn upload_directory(
+        &self,
+        src_path: PathBuf,
+        dest_path: RemotePathBuf,
+        cx: &App,
+    ) -> Task<Result<()>> {
+        let runtime = self.container.runtime;
+        let container_id = self.container.id.clone();
+        let user = self.container.user.clone();
+        cx.background_spawn(async move {
+            let dest_path = dest_path.to_string();
+            run_runtime_command(
+                runtime,
+                &[
+                    "cp",
+                    &src_path.to_string_lossy(),
+                    &format!("{container_id}:{dest_path}"),
+                ],
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to upload directory {} -> {}",
+                    src_path.display(),
+                    dest_path
+                )
+            })?;
+            // Copied files belong to root, hand them over to the user the server runs as.
+            if let Some(user) = user {
+                run_runtime_command(
+                    runtime,
+                    &[
+                        "exec",
+                        "-u",
+                        "root",
+                        &container_id,
+                        "chown",
+                        "-R",
+                        &user,
+                        &dest_path,
+                    ],
+                )
+                .await?;
+            }
+            Ok(())
+        })
+    }
+
+    async fn kill(&self) -> Result<()> {
+        // The container is left running, so that reconnecting does not have to recreate it.
+        self.killed.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn has_been_killed(&self) -> bool {
+        self.killed.load(Ordering::SeqCst)
+    }
+
+    fn ssh_args(&self) -> Option<SshArgs> {
+        None
+    }
+
+    fn container_exec_args(&self) -> Option<ContainerExecArgs> {
+        Some(ContainerExecArgs {
+            runtime: self.container.runtime,
+            container_id: self.container.id.clone(),
+            user: self.container.user.clone(),
+        })
+    }
+
+    fn connection_options(&self) -> RemoteConnectionOptions {
+        self.options.clone().into()
+    }
+
+    fn path_style(&self) -> PathStyle {
+        PathStyle::Posix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collections::HashMap;
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use serde_json::json;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_find_config(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/projects"),
+            json!({
+                "nested": {
+                    ".devcontainer": { "devcontainer.json": r#"{ "image": "rust" }"# },
+                    ".devcontainer.json": r#"{ "image": "node" }"#,
+                },
+                "flat": { ".devcontainer.json": r#"{ "image": "node" }"# },
+                "none": { ".devcontainer": {} },
+            }),
+        )
+        .await;
+
+        assert_eq!(
+            DevContainerConfig::find(fs.as_ref(), Path::new(path!("/projects/nested"))).await,
+            Some(PathBuf::from(path!(
+                "/projects/nested/.devcontainer/devcontainer.json"
+            )))
+        );
+        assert_eq!(
+            DevContainerConfig::load(fs.as_ref(), Path::new(path!("/projects/flat")))
+                .await
+                .unwrap()
+                .image
+                .as_deref(),
+            Some("node")
+        );
+        assert_eq!(
+            DevContainerConfig::find(fs.as_ref(), Path::new(path!("/projects/none"))).await,
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config = DevContainerConfig::parse(
+            r#"{
+                // Comments and trailing commas are allowed.
+                "name": "toolchain",
+                "build": { "dockerfile": "Dockerfile", "args": { "VARIANT": "bookworm" } },
+                "mounts": [
+                    "type=volume,source=cargo,target=/usr/local/cargo",
+                    { "source": "${localEnv:HOME}/.ssh", "target": "/root/.ssh", "type": "bind" },
+                ],
+                "containerEnv": { "RUST_LOG": "info" },
+                "postCreateCommand": { "deps": ["cargo", "fetch"], "hooks": "git config core.hooksPath .githooks" },
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.name.as_deref(), Some("toolchain"));
+        assert_eq!(config.image, None);
+        let build = config.build.as_ref().unwrap();
+        assert_eq!(build.dockerfile.as_deref(), Some("Dockerfile"));
+        assert_eq!(build.args["VARIANT"], "bookworm");
+        assert_eq!(
+            config
+                .mounts
+                .iter()
+                .map(DevContainerMount::to_spec)
+                .collect::<Vec<_>>(),
+            [
+                "type=volume,source=cargo,target=/usr/local/cargo",
+                "type=bind,source=${localEnv:HOME}/.ssh,target=/root/.ssh",
+            ]
+        );
+        assert_eq!(config.container_env["RUST_LOG"], "info");
+        assert_eq!(
+            config.post_create_command.unwrap().commands(),
+            [
+                vec!["cargo".to_string(), "fetch".to_string()],
+                vec![
+                    "/bin/sh".to_string(),
+                    "-c".to_string(),
+                    "git config core.hooksPath .githooks".to_string()
+                ],
+            ]
+        );
+        assert_eq!(
+            config.workspace_folder(Path::new("/home/me/projects/zed")),
+            "/workspaces/zed"
+        );
+    }
+
+    #[test]
+    fn test_container_exec_args() {
+        let exec_args = ContainerExecArgs {
+            runtime: ContainerRuntime::Podman,
+            container_id: "1234".to_string(),
+            user: Some("vscode".to_string()),
+        };
+        assert_eq!(
+            exec_args.wrap(true, vec!["sh".to_string()]),
+            (
+                "podman".to_string(),
+                ["exec", "-it", "-u", "vscode", "1234", "sh"]
+                    .map(String::from)
+                    .to_vec()
+            )
+        );
+    }
+
+    #[test]
+    fn test_substitute_variables() {
+        let project_path = Path::new("/home/me/projects/zed");
+        let env = HashMap::from_iter([
+            ("CARGO_HOME".to_string(), "/home/me/.cargo".to_string()),
+            (
+                "RECURSIVE_VARIABLE".to_string(),
+                "${localEnv:RECURSIVE_VARIABLE}".to_string(),
+            ),
+        ]);
+        let substitute = |value: &str| {
+            substitute_variables(value, project_path, "/workspaces/zed", |name| {
+                env.get(name).cloned()
+            })
+        };
+
+        assert_eq!(
+            substitute(
+                "source=${localWorkspaceFolder}/target,target=${containerWorkspaceFolder}/target"
+            ),
+            "source=/home/me/projects/zed/target,target=/workspaces/zed/target"
+        );
+        assert_eq!(
+            substitute("${localEnv:CARGO_HOME}/registry"),
+            "/home/me/.cargo/registry"
+        );
+        assert_eq!(substitute("${localEnv:UNSET_VARIABLE}/cache"), "/cache");
+        assert_eq!(
+            substitute("${localEnv:UNSET_VARIABLE:/tmp}/cache"),
+            "/tmp/cache"
+        );
+
+        // Values containing variables are not substituted again.
+        assert_eq!(
+            substitute("${localEnv:RECURSIVE_VARIABLE}/cache"),
+            "${localEnv:RECURSIVE_VARIABLE}/cache"
+        );
+    }
+}
@@ -1,9 +1,13 @@
+pub mod dev_container;
 pub mod json_log;
 pub mod protocol;
 pub mod proxy;
 pub mod ssh_session;
 
+pub use dev_container::{
+    ContainerExecArgs, ContainerRuntime, DevContainerConnectionOptions, DevContainerProjectId,
+};
 pub use ssh_session::{
-    ConnectionState, SshClientDelegate, SshConnectionOptions, SshPlatform, SshRemoteClient,
-    SshRemoteEvent,
+    ConnectionState, RemoteConnectionOptions, SshClientDelegate, SshConnectionOptions, SshPlatform,
+    SshRemoteClient, SshRemoteEvent,
 };
@@ -1,4 +1,5 @@
 use crate::{
+    dev_container::{ContainerExecArgs, DevContainerConnection, DevContainerConnectionOptions},
     json_log::LogRecord,
     protocol::{
         MESSAGE_LEN_SIZE, MessageId, message_len_from_buffer, read_message_with_len, write_message,
@@ -308,6 +309,41 @@ impl SshConnectionOptions {
     }
 }
 
+/// The options of a connection to any kind of remote host that runs the remote server.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RemoteConnectionOptions {
+    Ssh(SshConnectionOptions),
+    DevContainer(DevContainerConnectionOptions),
+}
+
+impl RemoteConnectionOptions {
+    pub fn connection_string(&self) -> String {
+        match self {
+            Self::Ssh(options) => options.connection_string(),
+            Self::DevContainer(options) => options.connection_string(),
+        }
+    }
+
+    pub fn nickname(&self) -> Option<String> {
+        match self {
+            Self::Ssh(options) => options.nickname.clone(),
+            Self::DevContainer(options) => options.nickname.clone(),
+        }
+    }
+}
+
+impl From<SshConnectionOptions> for RemoteConnectionOptions {
+    fn from(options: SshConnectionOptions) -> Self {
+        Self::Ssh(options)
+    }
+}
+
+impl From<DevContainerConnectionOptions> for RemoteConnectionOptions {
+    fn from(options: DevContainerConnectionOptions) -> Self {
+        Self::DevContainer(options)
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct SshPlatform {
     pub os: &'static str,
@@ -439,34 +475,38 @@ impl SshSocket {
 
     async fn platform(&self) -> Result<SshPlatform> {
         let uname = self.run_command("sh", &["-c", "uname -sm"]).await?;
-        let Some((os, arch)) = uname.split_once(" ") else {
-            anyhow::bail!("unknown uname: {uname:?}")
-        };
+        parse_platform(&uname)
+    }
+}
 
-        let os = match os.trim() {
-            "Darwin" => "macos",
-            "Linux" => "linux",
-            _ => anyhow::bail!(
-                "Prebuilt remote servers are not yet available for {os:?}. See https://zed.dev/docs/remote-development"
-            ),
-        };
-        // exclude armv5,6,7 as they are 32-bit.
-        let arch = if arch.starts_with("armv8")
-            || arch.starts_with("armv9")
-            || arch.starts_with("arm64")
-            || arch.starts_with("aarch64")
-        {
-            "aarch64"
-        } else if arch.starts_with("x86") {
-            "x86_64"
-        } else {
-            anyhow::bail!(
-                "Prebuilt remote servers are not yet available for {arch:?}. See https://zed.dev/docs/remote-development"
-            )
-        };
+pub(crate) fn parse_platform(uname: &str) -> Result<SshPlatform> {
+    let Some((os, arch)) = uname.split_once(" ") else {
+        anyhow::bail!("unknown uname: {uname:?}")
+    };
 
-        Ok(SshPlatform { os, arch })
-    }
+    let os = match os.trim() {
+        "Darwin" => "macos",
+        "Linux" => "linux",
+        _ => anyhow::bail!(
+            "Prebuilt remote servers are not yet available for {os:?}. See https://zed.dev/docs/remote-development"
+        ),
+    };
+    // exclude armv5,6,7 as they are 32-bit.
+    let arch = if arch.starts_with("armv8")
+        || arch.starts_with("armv9")
+        || arch.starts_with("arm64")
+        || arch.starts_with("aarch64")
+    {
+        "aarch64"
+    } else if arch.starts_with("x86") {
+        "x86_64"
+    } else {
+        anyhow::bail!(
+            "Prebuilt remote servers are not yet available for {arch:?}. See https://zed.dev/docs/remote-development"
+        )
+    };
+
+    Ok(SshPlatform { os, arch })
 }
 
 const MAX_MISSED_HEARTBEATS: usize = 5;
@@ -633,7 +673,7 @@ impl From<&State> for ConnectionState {
 pub struct SshRemoteClient {
     client: Arc<ChannelClient>,
     unique_identifier: String,
-    connection_options: SshConnectionOptions,
+    connection_options: RemoteConnectionOptions,
     path_style: PathStyle,
     state: Arc<Mutex<Option<State>>>,
 }
@@ -681,7 +721,7 @@ impl ConnectionIdentifier {
 impl SshRemoteClient {
     pub fn new(
         unique_identifier: ConnectionIdentifier,
-        connection_options: SshConnectionOptions,
+        connection_options: RemoteConnectionOptions,
         cancellation: oneshot::Receiver<()>,
         delegate: Arc<dyn SshClientDelegate>,
         cx: &mut App,
@@ -1148,7 +1188,17 @@ impl SshRemoteClient {
             .lock()
             .as_ref()
             .and_then(|state| state.ssh_connection())
-            .map(|ssh_connection| (ssh_connection.ssh_args(), ssh_connection.path_style()))
+            .and_then(|ssh_connection| {
+                Some((ssh_connection.ssh_args()?, ssh_connection.path_style()))
+            })
+    }
+
+    pub fn container_exec_args(&self) -> Option<ContainerExecArgs> {
+        self.state
+            .lock()
+            .as_ref()
+            .and_then(|state| state.ssh_connection())
+            .and_then(|connection| connection.container_exec_args())
     }
 
     pub fn upload_directory(
@@ -1172,10 +1222,17 @@ impl SshRemoteClient {
         self.connection_options.connection_string()
     }
 
-    pub fn connection_options(&self) -> SshConnectionOptions {
+    pub fn connection_options(&self) -> RemoteConnectionOptions {
         self.connection_options.clone()
     }
 
+    pub fn ssh_connection_options(&self) -> Option<SshConnectionOptions> {
+        match &self.connection_options {
+            RemoteConnectionOptions::Ssh(options) => Some(options.clone()),
+            RemoteConnectionOptions::DevContainer(_) => None,
+        }
+    }
+
     pub fn connection_state(&self) -> ConnectionState {
         self.state
             .lock()
@@ -1229,7 +1286,7 @@ impl SshRemoteClient {
         let server_client =
             server_cx.update(|cx| ChannelClient::new(incoming_rx, outgoing_tx, cx, "fake-server"));
         let connection: Arc<dyn RemoteConnection> = Arc::new(fake::FakeRemoteConnection {
-            connection_options: opts.clone(),
+            connection_options: opts.clone().into(),
             server_cx: fake::SendableCx::new(server_cx),
             server_channel: server_client.clone(),
         });
@@ -1237,7 +1294,7 @@ impl SshRemoteClient {
         client_cx.update(|cx| {
             cx.update_default_global(|c: &mut ConnectionPool, cx| {
                 c.connections.insert(
-                    opts.clone(),
+                    opts.clone().into(),
                     ConnectionPoolEntry::Connecting(
                         cx.background_spawn({
                             let connection = connection.clone();
@@ -1262,7 +1319,7 @@ impl SshRemoteClient {
             .update(|cx| {
                 Self::new(
                     ConnectionIdentifier::setup(),
-                    opts,
+                    opts.into(),
                     rx,
                     Arc::new(fake::Delegate),
                     cx,
@@ -1281,7 +1338,7 @@ enum ConnectionPoolEntry {
 
 #[derive(Default)]
 struct ConnectionPool {
-    connections: HashMap<SshConnectionOptions, ConnectionPoolEntry>,
+    connections: HashMap<RemoteConnectionOptions, ConnectionPoolEntry>,
 }
 
 impl Global for ConnectionPool {}
@@ -1289,7 +1346,7 @@ impl Global for ConnectionPool {}
 impl ConnectionPool {
     pub fn connect(
         &mut self,
-        opts: SshConnectionOptions,
+        opts: RemoteConnectionOptions,
         delegate: &Arc<dyn SshClientDelegate>,
         cx: &mut App,
     ) -> Shared<Task<Result<Arc<dyn RemoteConnection>, Arc<anyhow::Error>>>> {
@@ -1319,9 +1376,18 @@ impl ConnectionPool {
                 let opts = opts.clone();
                 let delegate = delegate.clone();
                 async move |cx| {
-                    let connection = SshRemoteConnection::new(opts.clone(), delegate, cx)
-                        .await
-                        .map(|connection| Arc::new(connection) as Arc<dyn RemoteConnection>);
+                    let connection = match opts.clone() {
+                        RemoteConnectionOptions::Ssh(options) => {
+                            SshRemoteConnection::new(options, delegate, cx)
+                                .await
+                                .map(|connection| Arc::new(connection) as Arc<dyn RemoteConnection>)
+                        }
+                        RemoteConnectionOptions::DevContainer(options) => {
+                            DevContainerConnection::new(options, delegate, cx)
+                                .await
+                                .map(|connection| Arc::new(connection) as Arc<dyn RemoteConnection>)
+                        }
+                    };
 
                     cx.update_global(|pool: &mut Self, _| {
                         debug_assert!(matches!(
@@ -1359,7 +1425,7 @@ impl From<SshRemoteClient> for AnyProtoClient {
 }
 
 #[async_trait(?Send)]
-trait RemoteConnection: Send + Sync {
+pub(crate) trait RemoteConnection: Send + Sync {
     fn start_proxy(
         &self,
         unique_identifier: String,
@@ -1380,15 +1446,21 @@ trait RemoteConnection: Send + Sync {
     fn has_been_killed(&self) -> bool;
     /// On Windows, we need to use `SSH_ASKPASS` to provide the password to ssh.
     /// On Linux, we use the `ControlPath` option to create a socket file that ssh can use to
-    fn ssh_args(&self) -> SshArgs;
-    fn connection_options(&self) -> SshConnectionOptions;
+    ///
+    /// Returns `None` for connections that are not made over ssh.
+    fn ssh_args(&self) -> Option<SshArgs>;
+    /// Returns how to run programs in the container, for connections to dev containers.
+    fn container_exec_args(&self) -> Option<ContainerExecArgs> {
+        None
+    }
+    fn connection_options(&self) -> RemoteConnectionOptions;
     fn path_style(&self) -> PathStyle;
 
     #[cfg(any(test, feature = "test-support"))]
     fn simulate_disconnect(&self, _: &AsyncApp) {}
 }
 
-struct SshRemoteConnection {
+pub(crate) struct SshRemoteConnection {
     socket: SshSocket,
     master_process: Mutex<Option<Child>>,
     remote_binary_path: Option<RemotePathBuf>,
@@ -1412,12 +1484,12 @@ impl RemoteConnection for SshRemoteConnection {
         self.master_process.lock().is_none()
     }
 
-    fn ssh_args(&self) -> SshArgs {
-        self.socket.ssh_args()
+    fn ssh_args(&self) -> Option<SshArgs> {
+        Some(self.socket.ssh_args())
     }
 
-    fn connection_options(&self) -> SshConnectionOptions {
-        self.socket.connection_options.clone()
+    fn connection_options(&self) -> RemoteConnectionOptions {
+        self.socket.connection_options.clone().into()
     }
 
     fn upload_directory(
@@ -1661,7 +1733,8 @@ impl SshRemoteConnection {
         Ok(this)
     }
 
-    fn multiplex(
+    /// Proxies the messages of the client to the stdio of a remote server's proxy process.
+    pub(crate) fn multiplex(
         mut ssh_proxy_process: Child,
         incoming_tx: UnboundedSender<Envelope>,
         mut outgoing_rx: UnboundedReceiver<Envelope>,
@@ -1762,7 +1835,29 @@ impl SshRemoteConnection {
             }
         })
     }
+}
 
+pub(crate) fn remote_server_binary_name(
+    release_channel: ReleaseChannel,
+    version: SemanticVersion,
+    commit: Option<AppCommitSha>,
+) -> String {
+    let version_str = match release_channel {
+        ReleaseChannel::Nightly => {
+            let commit = commit.map(|s| s.full()).unwrap_or_default();
+            format!("{}-{}", version, commit)
+        }
+        ReleaseChannel::Dev => "build".to_string(),
+        _ => version.to_string(),
+    };
+    format!(
+        "zed-remote-server-{}-{}",
+        release_channel.dev_name(),
+        version_str
+    )
+}
+
+impl SshRemoteConnection {
     #[allow(unused)]
     async fn ensure_server_binary(
         &self,
@@ -1772,19 +1867,7 @@ impl SshRemoteConnection {
         commit: Option<AppCommitSha>,
         cx: &mut AsyncApp,
     ) -> Result<RemotePathBuf> {
-        let version_str = match release_channel {
-            ReleaseChannel::Nightly => {
-                let commit = commit.map(|s| s.full()).unwrap_or_default();
-                format!("{}-{}", version, commit)
-            }
-            ReleaseChannel::Dev => "build".to_string(),
-            _ => version.to_string(),
-        };
-        let binary_name = format!(
-            "zed-remote-server-{}-{}",
-            release_channel.dev_name(),
-            version_str
-        );
+        let binary_name = remote_server_binary_name(release_channel, version, commit);
         let dst_path = RemotePathBuf::new(
             paths::remote_server_dir_relative().join(binary_name),
             self.ssh_path_style,
@@ -2585,12 +2668,12 @@ mod fake {
     use util::paths::{PathStyle, RemotePathBuf};
 
     use super::{
-        ChannelClient, RemoteConnection, SshArgs, SshClientDelegate, SshConnectionOptions,
+        ChannelClient, RemoteConnection, RemoteConnectionOptions, SshArgs, SshClientDelegate,
         SshPlatform,
     };
 
     pub(super) struct FakeRemoteConnection {
-        pub(super) connection_options: SshConnectionOptions,
+        pub(super) connection_options: RemoteConnectionOptions,
         pub(super) server_channel: Arc<ChannelClient>,
         pub(super) server_cx: SendableCx,
     }
@@ -2622,11 +2705,11 @@ mod fake {
             false
         }
 
-        fn ssh_args(&self) -> SshArgs {
-            SshArgs {
+        fn ssh_args(&self) -> Option<SshArgs> {
+            Some(SshArgs {
                 arguments: Vec::new(),
                 envs: None,
-            }
+            })
         }
 
         fn upload_directory(
@@ -2638,7 +2721,7 @@ mod fake {
             unreachable!()
         }
 
-        fn connection_options(&self) -> SshConnectionOptions {
+        fn connection_options(&self) -> RemoteConnectionOptions {
             self.connection_options.clone()
         }
 
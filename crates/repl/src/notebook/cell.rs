@@ -1,14 +1,16 @@
 #![allow(unused, dead_code)]
 use std::sync::Arc;
 
-use editor::{Editor, EditorMode, MultiBuffer};
-use futures::future::Shared;
+use editor::{Editor, EditorEvent, EditorMode, MultiBuffer};
+use futures::{FutureExt as _, future::Shared};
 use gpui::{
-    App, Entity, Hsla, RetainAllImageCache, Task, TextStyleRefinement, image_cache, prelude::*,
+    App, Entity, EventEmitter, Hsla, RetainAllImageCache, Subscription, Task, TextStyleRefinement,
+    image_cache, prelude::*,
 };
 use language::{Buffer, Language, LanguageRegistry};
 use markdown_preview::{markdown_parser::parse_markdown, markdown_renderer::render_markdown_block};
 use nbformat::v4::{CellId, CellMetadata, CellType};
+use runtimelib::{JupyterMessageContent, Stdio};
 use settings::Settings as _;
 use theme::ThemeSettings;
 use ui::{IconButtonShape, prelude::*};
@@ -85,31 +87,84 @@ pub enum Cell {
     Raw(Entity<RawCell>),
 }
 
-fn convert_outputs(
-    outputs: &Vec<nbformat::v4::Output>,
+pub enum CellEvent {
+    /// The cell's source was edited.
+    Edited,
+    /// The cell asked to be run by the notebook.
+    Run,
+}
+
+fn convert_output(output: &nbformat::v4::Output, window: &mut Window, cx: &mut App) -> Output {
+    match output {
+        nbformat::v4::Output::Stream { text, .. } => Output::Stream {
+            content: cx.new(|cx| TerminalOutput::from(&text.0, window, cx)),
+        },
+        nbformat::v4::Output::DisplayData(display_data) => {
+            Output::new(&display_data.data, None, window, cx)
+        }
+        nbformat::v4::Output::ExecuteResult(execute_result) => {
+            Output::new(&execute_result.data, None, window, cx)
+        }
+        nbformat::v4::Output::Error(error) => Output::ErrorOutput(ErrorView {
+            ename: error.ename.clone(),
+            evalue: error.evalue.clone(),
+            traceback: cx.new(|cx| TerminalOutput::from(&error.traceback.join("\n"), window, cx)),
+        }),
+    }
+}
+
+/// Splits cell source into lines the way nbformat stores it, keeping the line endings.
+fn source_lines(source: &str) -> Vec<String> {
+    source.split_inclusive('\n').map(str::to_string).collect()
+}
+
+fn cell_editor(
+    text: &str,
+    language: Shared<Task<Option<Arc<Language>>>>,
     window: &mut Window,
     cx: &mut App,
-) -> Vec<Output> {
-    outputs
-        .into_iter()
-        .map(|output| match output {
-            nbformat::v4::Output::Stream { text, .. } => Output::Stream {
-                content: cx.new(|cx| TerminalOutput::from(&text.0, window, cx)),
+) -> (Entity<Editor>, Task<()>) {
+    let buffer = cx.new(|cx| Buffer::local(text, cx));
+    let multi_buffer = cx.new(|cx| MultiBuffer::singleton(buffer.clone(), cx));
+
+    let editor = cx.new(|cx| {
+        let mut editor = Editor::new(
+            EditorMode::AutoHeight {
+                min_lines: 1,
+                max_lines: Some(1024),
             },
-            nbformat::v4::Output::DisplayData(display_data) => {
-                Output::new(&display_data.data, None, window, cx)
-            }
-            nbformat::v4::Output::ExecuteResult(execute_result) => {
-                Output::new(&execute_result.data, None, window, cx)
-            }
-            nbformat::v4::Output::Error(error) => Output::ErrorOutput(ErrorView {
-                ename: error.ename.clone(),
-                evalue: error.evalue.clone(),
-                traceback: cx
-                    .new(|cx| TerminalOutput::from(&error.traceback.join("\n"), window, cx)),
-            }),
-        })
-        .collect()
+            multi_buffer,
+            None,
+            window,
+            cx,
+        );
+
+        let theme = ThemeSettings::get_global(cx);
+
+        let refinement = TextStyleRefinement {
+            font_family: Some(theme.buffer_font.family.clone()),
+            font_size: Some(theme.buffer_font_size(cx).into()),
+            color: Some(cx.theme().colors().editor_foreground),
+            background_color: Some(gpui::transparent_black()),
+            ..Default::default()
+        };
+
+        editor.set_show_gutter(false, cx);
+        editor.set_text_style_refinement(refinement);
+        editor
+    });
+
+    let language_task = window.spawn(cx, async move |cx| {
+        let language = language.await;
+
+        buffer
+            .update(cx, |buffer, cx| {
+                buffer.set_language(language, cx);
+            })
+            .ok();
+    });
+
+    (editor, language_task)
 }
 
 impl Cell {
@@ -125,43 +180,48 @@ impl Cell {
                 id,
                 metadata,
                 source,
-                ..
+                attachments,
             } => {
                 let source = source.join("");
-
-                let entity = cx.new(|cx| {
-                    let markdown_parsing_task = {
+                let markdown_language = cx
+                    .spawn({
                         let languages = languages.clone();
-                        let source = source.clone();
-
-                        cx.spawn_in(window, async move |this, cx| {
-                            let parsed_markdown = cx
-                                .background_spawn(async move {
-                                    parse_markdown(&source, None, Some(languages)).await
-                                })
-                                .await;
-
-                            this.update(cx, |cell: &mut MarkdownCell, _| {
-                                cell.parsed_markdown = Some(parsed_markdown);
-                            })
-                            .log_err();
-                        })
-                    };
+                        async move |_| languages.language_for_name("Markdown").await.ok()
+                    })
+                    .shared();
+
+                Cell::Markdown(cx.new(|cx| {
+                    let (editor, language_task) =
+                        cell_editor(&source, markdown_language, window, cx);
+                    let editor_subscription = cx.subscribe(
+                        &editor,
+                        |this: &mut MarkdownCell, editor, event: &EditorEvent, cx| {
+                            if let EditorEvent::BufferEdited = event {
+                                this.source = editor.read(cx).text(cx);
+                                this.update_parsed_markdown(cx);
+                                cx.emit(CellEvent::Edited);
+                            }
+                        },
+                    );
 
-                    MarkdownCell {
-                        markdown_parsing_task,
+                    let mut cell = MarkdownCell {
+                        markdown_parsing_task: Task::ready(()),
                         image_cache: RetainAllImageCache::new(cx),
                         languages: languages.clone(),
                         id: id.clone(),
                         metadata: metadata.clone(),
-                        source: source.clone(),
+                        attachments: attachments.clone(),
+                        source,
                         parsed_markdown: None,
+                        editor,
                         selected: false,
                         cell_position: None,
-                    }
-                });
-
-                Cell::Markdown(entity)
+                        language_task,
+                        _editor_subscription: editor_subscription,
+                    };
+                    cell.update_parsed_markdown(cx);
+                    cell
+                }))
             }
             nbformat::v4::Cell::Code {
                 id,
@@ -170,75 +230,116 @@ impl Cell {
                 source,
                 outputs,
             } => Cell::Code(cx.new(|cx| {
-                let text = source.join("");
-
-                let buffer = cx.new(|cx| Buffer::local(text.clone(), cx));
-                let multi_buffer = cx.new(|cx| MultiBuffer::singleton(buffer.clone(), cx));
-
-                let editor_view = cx.new(|cx| {
-                    let mut editor = Editor::new(
-                        EditorMode::AutoHeight {
-                            min_lines: 1,
-                            max_lines: Some(1024),
-                        },
-                        multi_buffer,
-                        None,
-                        window,
-                        cx,
-                    );
-
-                    let theme = ThemeSettings::get_global(cx);
-
-                    let refinement = TextStyleRefinement {
-                        font_family: Some(theme.buffer_font.family.clone()),
-                        font_size: Some(theme.buffer_font_size(cx).into()),
-                        color: Some(cx.theme().colors().editor_foreground),
-                        background_color: Some(gpui::transparent_black()),
-                        ..Default::default()
-                    };
-
-                    editor.set_text(text, window, cx);
-                    editor.set_show_gutter(false, cx);
-                    editor.set_text_style_refinement(refinement);
-
-                    // editor.set_read_only(true);
-                    editor
-                });
-
-                let buffer = buffer.clone();
-                let language_task = cx.spawn_in(window, async move |this, cx| {
-                    let language = notebook_language.await;
-
-                    buffer.update(cx, |buffer, cx| {
-                        buffer.set_language(language.clone(), cx);
-                    });
-                });
+                let source = source.join("");
+                let (editor, language_task) = cell_editor(&source, notebook_language, window, cx);
+                let editor_subscription = cx.subscribe(
+                    &editor,
+                    |this: &mut CodeCell, editor, event: &EditorEvent, cx| {
+                        if let EditorEvent::BufferEdited = event {
+                            this.source = editor.read(cx).text(cx);
+                            cx.emit(CellEvent::Edited);
+                        }
+                    },
+                );
 
                 CodeCell {
                     id: id.clone(),
                     metadata: metadata.clone(),
                     execution_count: *execution_count,
-                    source: source.join(""),
-                    editor: editor_view,
-                    outputs: convert_outputs(outputs, window, cx),
+                    source,
+                    editor,
+                    outputs: outputs
+                        .iter()
+                        .map(|output| convert_output(output, window, cx))
+                        .collect(),
+                    nbformat_outputs: outputs.clone(),
+                    clear_on_next_output: false,
                     selected: false,
                     language_task,
                     cell_position: None,
+                    _editor_subscription: editor_subscription,
                 }
             })),
             nbformat::v4::Cell::Raw {
                 id,
                 metadata,
                 source,
-            } => Cell::Raw(cx.new(|_| RawCell {
-                id: id.clone(),
-                metadata: metadata.clone(),
-                source: source.join(""),
-                selected: false,
-                cell_position: None,
+            } => Cell::Raw(cx.new(|cx| {
+                let source = source.join("");
+                let (editor, language_task) =
+                    cell_editor(&source, Task::ready(None).shared(), window, cx);
+                let editor_subscription = cx.subscribe(
+                    &editor,
+                    |this: &mut RawCell, editor, event: &EditorEvent, cx| {
+                        if let EditorEvent::BufferEdited = event {
+                            this.source = editor.read(cx).text(cx);
+                            cx.emit(CellEvent::Edited);
+                        }
+                    },
+                );
+
+                RawCell {
+                    id: id.clone(),
+                    metadata: metadata.clone(),
+                    source,
+                    editor,
+                    selected: false,
+                    cell_position: None,
+                    language_task,
+                    _editor_subscription: editor_subscription,
+                }
             })),
         }
     }
+
+    pub fn id(&self, cx: &App) -> CellId {
+        match self {
+            Cell::Code(cell) => cell.read(cx).id().clone(),
+            Cell::Markdown(cell) => cell.read(cx).id().clone(),
+            Cell::Raw(cell) => cell.read(cx).id().clone(),
+        }
+    }
+
+    pub fn editor(&self, cx: &App) -> Entity<Editor> {
+        match self {
+            Cell::Code(cell) => cell.read(cx).editor.clone(),
+            Cell::Markdown(cell) => cell.read(cx).editor.clone(),
+            Cell::Raw(cell) => cell.read(cx).editor.clone(),
+        }
+    }
+
+    /// Converts the cell back into its nbformat representation, keeping its ID and metadata.
+    pub fn to_nbformat(&self, cx: &App) -> nbformat::v4::Cell {
+        match self {
+            Cell::Code(cell) => {
+                let cell = cell.read(cx);
+                nbformat::v4::Cell::Code {
+                    id: cell.id.clone(),
+                    metadata: cell.metadata.clone(),
+                    execution_count: cell.execution_count,
+                    source: source_lines(&cell.source),
+                    outputs: cell.nbformat_outputs.clone(),
+                }
+            }
+            Cell::Markdown(cell) => {
+                let cell = cell.read(cx);
+                nbformat::v4::Cell::Markdown {
+                    id: cell.id.clone(),
+                    metadata: cell.metadata.clone(),
+                    source: source_lines(&cell.source),
+                    attachments: cell.attachments.clone(),
+                }
+            }
+            Cell::Raw(cell) => {
+                let cell = cell.read(cx);
+                nbformat::v4::Cell::Raw {
+                    id: cell.id.clone(),
+                    metadata: cell.metadata.clone(),
+                    source: source_lines(&cell.source),
+                }
+            }
+        }
+    }
 }
 
 pub trait RenderableCell: Render {
@@ -335,15 +436,42 @@ pub trait RunnableCell: RenderableCell {
 pub struct MarkdownCell {
     id: CellId,
     metadata: CellMetadata,
+    attachments: Option<serde_json::Value>,
     image_cache: Entity<RetainAllImageCache>,
     source: String,
+    editor: Entity<Editor>,
     parsed_markdown: Option<markdown_preview::markdown_elements::ParsedMarkdown>,
     markdown_parsing_task: Task<()>,
     selected: bool,
     cell_position: Option<CellPosition>,
     languages: Arc<LanguageRegistry>,
+    language_task: Task<()>,
+    _editor_subscription: Subscription,
 }
 
+impl MarkdownCell {
+    fn update_parsed_markdown(&mut self, cx: &mut Context<Self>) {
+        let languages = self.languages.clone();
+        let source = self.source.clone();
+
+        self.markdown_parsing_task = cx.spawn(async move |this, cx| {
+            let parsed_markdown = cx
+                .background_spawn(
+                    async move { parse_markdown(&source, None, Some(languages)).await },
+                )
+                .await;
+
+            this.update(cx, |cell, cx| {
+                cell.parsed_markdown = Some(parsed_markdown);
+                cx.notify();
+            })
+            .log_err();
+        });
+    }
+}
+
+impl EventEmitter<CellEvent> for MarkdownCell {}
+
 impl RenderableCell for MarkdownCell {
     const CELL_TYPE: CellType = CellType::Markdown;
 
@@ -388,6 +516,39 @@ impl RenderableCell for MarkdownCell {
 
 impl Render for MarkdownCell {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Selected cells are edited as source, everything else shows the rendered markdown
+        if self.selected {
+            return v_flex()
+                .size_full()
+                .children(self.cell_position_spacer(true, window, cx))
+                .child(
+                    h_flex()
+                        .w_full()
+                        .pr_6()
+                        .rounded_xs()
+                        .items_start()
+                        .gap(DynamicSpacing::Base08.rems(cx))
+                        .bg(self.selected_bg_color(window, cx))
+                        .child(self.gutter(window, cx))
+                        .child(
+                            div().py_1p5().w_full().child(
+                                div()
+                                    .flex()
+                                    .size_full()
+                                    .flex_1()
+                                    .py_3()
+                                    .px_5()
+                                    .rounded_lg()
+                                    .border_1()
+                                    .border_color(cx.theme().colors().border)
+                                    .bg(cx.theme().colors().editor_background)
+                                    .child(div().w_full().child(self.editor.clone())),
+                            ),
+                        ),
+                )
+                .children(self.cell_position_spacer(false, window, cx));
+        }
+
         let Some(parsed) = self.parsed_markdown.as_ref() else {
             return div();
         };
@@ -435,21 +596,116 @@ pub struct CodeCell {
     source: String,
     editor: Entity<editor::Editor>,
     outputs: Vec<Output>,
+    // The outputs as they are written back to the notebook file
+    nbformat_outputs: Vec<nbformat::v4::Output>,
+    clear_on_next_output: bool,
     selected: bool,
     cell_position: Option<CellPosition>,
     language_task: Task<()>,
+    _editor_subscription: Subscription,
 }
 
 impl CodeCell {
-    pub fn is_dirty(&self, cx: &App) -> bool {
-        self.editor.read(cx).buffer().read(cx).is_dirty(cx)
-    }
     pub fn has_outputs(&self) -> bool {
         !self.outputs.is_empty()
     }
 
     pub fn clear_outputs(&mut self) {
         self.outputs.clear();
+        self.nbformat_outputs.clear();
+        self.clear_on_next_output = false;
+    }
+
+    /// Accept a Jupyter message from the execution of this cell, returning whether it changed
+    /// the cell
+    pub fn push_message(
+        &mut self,
+        message: &JupyterMessageContent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let output = match message {
+            JupyterMessageContent::ExecuteInput(input) => {
+                self.execution_count = Some(input.execution_count.0 as i32);
+                cx.notify();
+                return true;
+            }
+            JupyterMessageContent::StreamContent(stream) => {
+                let name = match stream.name {
+                    Stdio::Stdout => "stdout",
+                    Stdio::Stderr => "stderr",
+                };
+                nbformat::v4::Output::Stream {
+                    name: name.to_string(),
+                    text: nbformat::v4::MultilineString(stream.text.clone()),
+                }
+            }
+            JupyterMessageContent::DisplayData(display_data) => {
+                let mut display_data = display_data.clone();
+                display_data.transient = None;
+                nbformat::v4::Output::DisplayData(display_data)
+            }
+            JupyterMessageContent::ExecuteResult(execute_result) => {
+                let mut execute_result = execute_result.clone();
+                execute_result.transient = None;
+                nbformat::v4::Output::ExecuteResult(execute_result)
+            }
+            JupyterMessageContent::ErrorOutput(error) => nbformat::v4::Output::Error(error.clone()),
+            JupyterMessageContent::ClearOutput(options) => {
+                if options.wait {
+                    self.clear_on_next_output = true;
+                } else {
+                    self.clear_outputs();
+                }
+                cx.notify();
+                return true;
+            }
+            _ => return false,
+        };
+
+        if self.clear_on_next_output {
+            self.clear_outputs();
+        }
+
+        self.push_output(output, window, cx);
+        cx.notify();
+        true
+    }
+
+    /// Shows a message below the cell without storing it in the notebook
+    pub fn push_error_message(&mut self, message: String, cx: &mut Context<Self>) {
+        self.outputs.push(Output::Message(message));
+        cx.notify();
+    }
+
+    fn push_output(
+        &mut self,
+        output: nbformat::v4::Output,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Consecutive writes to the same stream are stored as a single output, like Jupyter does
+        if let nbformat::v4::Output::Stream { name, text } = &output {
+            if let (
+                Some(Output::Stream { content }),
+                Some(nbformat::v4::Output::Stream {
+                    name: last_name,
+                    text: last_text,
+                }),
+            ) = (self.outputs.last(), self.nbformat_outputs.last_mut())
+            {
+                if last_name == name {
+                    content.update(cx, |content, cx| {
+                        content.append_text(&text.0, cx);
+                    });
+                    last_text.0.push_str(&text.0);
+                    return;
+                }
+            }
+        }
+
+        self.outputs.push(convert_output(&output, window, cx));
+        self.nbformat_outputs.push(output);
     }
 
     fn output_control(&self) -> Option<CellControlType> {
@@ -526,8 +782,8 @@ impl RenderableCell for CodeCell {
             CellControl::new("rerun-cell", CellControlType::RerunCell)
         } else {
             CellControl::new("run-cell", CellControlType::RunCell)
-                .on_click(cx.listener(move |this, _, window, cx| this.run(window, cx)))
-        };
+        }
+        .on_click(cx.listener(move |this, _, window, cx| this.run(window, cx)));
 
         Some(cell_control)
     }
@@ -552,8 +808,8 @@ impl RenderableCell for CodeCell {
 }
 
 impl RunnableCell for CodeCell {
-    fn run(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Running code cell: {}", self.id);
+    fn run(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(CellEvent::Run);
     }
 
     fn execution_count(&self) -> Option<i32> {
@@ -567,6 +823,8 @@ impl RunnableCell for CodeCell {
     }
 }
 
+impl EventEmitter<CellEvent> for CodeCell {}
+
 impl Render for CodeCell {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         v_flex()
@@ -672,10 +930,15 @@ pub struct RawCell {
     id: CellId,
     metadata: CellMetadata,
     source: String,
+    editor: Entity<Editor>,
     selected: bool,
     cell_position: Option<CellPosition>,
+    language_task: Task<()>,
+    _editor_subscription: Subscription,
 }
 
+impl EventEmitter<CellEvent> for RawCell {}
+
 impl RenderableCell for RawCell {
     const CELL_TYPE: CellType = CellType::Raw;
 
@@ -735,9 +998,7 @@ impl Render for RawCell {
                             .size_full()
                             .flex_1()
                             .p_3()
-                            .font_ui(cx)
-                            .text_size(TextSize::Default.rems(cx))
-                            .child(self.source.clone()),
+                            .child(div().w_full().child(self.editor.clone())),
                     ),
             )
             // TODO: Move base cell render into trait impl so we don't have to repeat this
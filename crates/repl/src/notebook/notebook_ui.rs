@@ -1,35 +1,43 @@
 #![allow(unused, dead_code)]
+use std::env::temp_dir;
 use std::future::Future;
+use std::time::Duration;
 use std::{path::PathBuf, sync::Arc};
 
 use anyhow::{Context as _, Result};
 use client::proto::ViewId;
 use collections::HashMap;
 use feature_flags::{FeatureFlagAppExt as _, NotebookFeatureFlag};
-use futures::FutureExt;
 use futures::future::Shared;
+use futures::{FutureExt, StreamExt as _};
 use gpui::{
     AnyElement, App, Entity, EventEmitter, FocusHandle, Focusable, ListScrollEvent, ListState,
-    Point, Task, actions, list, prelude::*,
+    MouseButton, Point, Subscription, Task, WeakEntity, actions, list, prelude::*,
 };
 use language::{Language, LanguageRegistry};
-use project::{Project, ProjectEntryId, ProjectPath};
+use project::{Fs, Project, ProjectEntryId, ProjectPath};
+use runtimelib::{ExecuteRequest, ExecutionState, JupyterMessage, JupyterMessageContent};
+use serde::Serialize as _;
 use ui::{Tooltip, prelude::*};
+use util::ResultExt as _;
+use uuid::Uuid;
 use workspace::item::{ItemEvent, SaveOptions, TabContentParams};
 use workspace::searchable::SearchableItemHandle;
 use workspace::{Item, ItemHandle, Pane, ProjectItem, ToolbarItemLocation};
 use workspace::{ToolbarItemEvent, ToolbarItemView};
 
-use super::{Cell, CellPosition, RenderableCell};
+use super::{Cell, CellEvent, CellPosition, RenderableCell};
+use crate::kernels::{
+    Kernel, KernelSession, KernelSpecification, NativeRunningKernel, RemoteRunningKernel,
+};
+use crate::repl_store::ReplStore;
 
-use nbformat::v4::CellId;
 use nbformat::v4::Metadata as NotebookMetadata;
+use nbformat::v4::{CellId, CellMetadata, CellType};
 
 actions!(
     notebook,
     [
-        /// Opens a Jupyter notebook file.
-        OpenNotebook,
         /// Runs all cells in the notebook.
         RunAll,
         /// Clears all cell outputs.
@@ -42,6 +50,16 @@ actions!(
         AddMarkdownBlock,
         /// Adds a new code cell.
         AddCodeBlock,
+        /// Runs the current cell.
+        RunCell,
+        /// Deletes the current cell.
+        DeleteCell,
+        /// Changes the current cell into a code cell.
+        ChangeCellToCode,
+        /// Changes the current cell into a markdown cell.
+        ChangeCellToMarkdown,
+        /// Changes the current cell into a raw cell.
+        ChangeCellToRaw,
     ]
 );
 
@@ -53,6 +71,8 @@ pub(crate) const GUTTER_WIDTH: f32 = 19.0;
 pub(crate) const CODE_BLOCK_INSET: f32 = MEDIUM_SPACING_SIZE;
 pub(crate) const CONTROL_SIZE: f32 = 20.0;
 
+const FILE_WATCH_LATENCY: Duration = Duration::from_millis(100);
+
 pub fn init(cx: &mut App) {
     if cx.has_flag::<NotebookFeatureFlag>() || std::env::var("LOCAL_NOTEBOOK_DEV").is_ok() {
         workspace::register_project_item::<NotebookEditor>(cx);
@@ -77,6 +97,7 @@ pub struct NotebookEditor {
 
     focus_handle: FocusHandle,
     notebook_item: Entity<NotebookItem>,
+    notebook_language: Shared<Task<Option<Arc<Language>>>>,
 
     remote_id: Option<ViewId>,
     cell_list: ListState,
@@ -84,6 +105,17 @@ pub struct NotebookEditor {
     selected_cell_index: usize,
     cell_order: Vec<CellId>,
     cell_map: HashMap<CellId, Cell>,
+    cell_subscriptions: HashMap<CellId, Subscription>,
+
+    kernel: Kernel,
+    // Code cells waiting on the kernel, keyed by the message ID of their execute request
+    executions: HashMap<String, CellId>,
+
+    is_dirty: bool,
+    // Bumped on every edit, so a save only clears the dirty flag if nothing changed meanwhile
+    edit_version: usize,
+    has_conflict: bool,
+    _notebook_item_subscription: Subscription,
 }
 
 impl NotebookEditor {
@@ -96,38 +128,16 @@ impl NotebookEditor {
         let focus_handle = cx.focus_handle();
 
         let languages = project.read(cx).languages().clone();
-        let language_name = notebook_item.read(cx).language_name();
 
         let notebook_language = notebook_item.read(cx).notebook_language();
         let notebook_language = cx
             .spawn_in(window, async move |_, _| notebook_language.await)
             .shared();
 
-        let mut cell_order = vec![]; // Vec<CellId>
-        let mut cell_map = HashMap::default(); // HashMap<CellId, Cell>
-
-        for (index, cell) in notebook_item
-            .read(cx)
-            .notebook
-            .clone()
-            .cells
-            .iter()
-            .enumerate()
-        {
-            let cell_id = cell.id();
-            cell_order.push(cell_id.clone());
-            cell_map.insert(
-                cell_id.clone(),
-                Cell::load(cell, &languages, notebook_language.clone(), window, cx),
-            );
-        }
-
         let notebook_handle = cx.entity().downgrade();
-        let cell_count = cell_order.len();
 
-        let this = cx.entity();
         let cell_list = ListState::new(
-            cell_count,
+            0,
             gpui::ListAlignment::Top,
             px(1000.),
             move |ix, window, cx| {
@@ -150,17 +160,136 @@ impl NotebookEditor {
             },
         );
 
-        Self {
+        let notebook_item_subscription =
+            cx.subscribe_in(&notebook_item, window, Self::on_notebook_item_event);
+
+        let mut this = Self {
             project,
-            languages: languages.clone(),
+            languages,
             focus_handle,
             notebook_item,
+            notebook_language,
             remote_id: None,
             cell_list,
             selected_cell_index: 0,
-            cell_order: cell_order.clone(),
-            cell_map: cell_map.clone(),
+            cell_order: Vec::new(),
+            cell_map: HashMap::default(),
+            cell_subscriptions: HashMap::default(),
+            kernel: Kernel::Shutdown,
+            executions: HashMap::default(),
+            is_dirty: false,
+            edit_version: 0,
+            has_conflict: false,
+            _notebook_item_subscription: notebook_item_subscription,
+        };
+        this.load_cells(window, cx);
+        this
+    }
+
+    /// Replaces all cells with the ones from the notebook as it was last read from disk.
+    fn load_cells(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let notebook = self.notebook_item.read(cx).notebook.clone();
+
+        self.cell_order.clear();
+        self.cell_map.clear();
+        self.cell_subscriptions.clear();
+        self.executions.clear();
+
+        for cell in notebook.cells.iter() {
+            let cell = Cell::load(
+                cell,
+                &self.languages,
+                self.notebook_language.clone(),
+                window,
+                cx,
+            );
+            self.insert_cell(self.cell_order.len(), cell, window, cx);
         }
+
+        self.cell_list.reset(self.cell_order.len());
+        self.selected_cell_index = self
+            .selected_cell_index
+            .min(self.cell_count().saturating_sub(1));
+        self.is_dirty = false;
+        self.has_conflict = false;
+        cx.emit(NotebookEditorEvent::DirtyChanged);
+        cx.notify();
+    }
+
+    fn insert_cell(
+        &mut self,
+        index: usize,
+        cell: Cell,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let cell_id = cell.id(cx);
+        let subscription = match &cell {
+            Cell::Code(cell) => Self::subscribe_to_cell(cell, cell_id.clone(), window, cx),
+            Cell::Markdown(cell) => Self::subscribe_to_cell(cell, cell_id.clone(), window, cx),
+            Cell::Raw(cell) => Self::subscribe_to_cell(cell, cell_id.clone(), window, cx),
+        };
+
+        self.cell_order.insert(index, cell_id.clone());
+        self.cell_subscriptions
+            .insert(cell_id.clone(), subscription);
+        self.cell_map.insert(cell_id, cell);
+    }
+
+    fn subscribe_to_cell<C: EventEmitter<CellEvent>>(
+        cell: &Entity<C>,
+        cell_id: CellId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Subscription {
+        cx.subscribe_in(
+            cell,
+            window,
+            move |this, _, event: &CellEvent, window, cx| match event {
+                CellEvent::Edited => this.mark_dirty(cx),
+                CellEvent::Run => this.run_cell(&cell_id, window, cx),
+            },
+        )
+    }
+
+    fn on_notebook_item_event(
+        &mut self,
+        _: &Entity<NotebookItem>,
+        event: &NotebookItemEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            NotebookItemEvent::ChangedOnDisk => {
+                // Keep unsaved edits around, the user decides which version wins
+                if self.is_dirty {
+                    self.has_conflict = true;
+                    cx.emit(NotebookEditorEvent::DirtyChanged);
+                    cx.notify();
+                } else {
+                    self.load_cells(window, cx);
+                }
+            }
+        }
+    }
+
+    fn mark_dirty(&mut self, cx: &mut Context<Self>) {
+        self.is_dirty = true;
+        self.edit_version = self.edit_version.wrapping_add(1);
+        cx.emit(NotebookEditorEvent::Edited);
+        cx.notify();
+    }
+
+    /// Builds the notebook as it should be written to disk, keeping the metadata it was loaded with.
+    fn to_notebook(&self, cx: &App) -> nbformat::v4::Notebook {
+        let mut notebook = self.notebook_item.read(cx).notebook.clone();
+        notebook.cells = self
+            .cell_order
+            .iter()
+            .filter_map(|cell_id| self.cell_map.get(cell_id))
+            .map(|cell| cell.to_nbformat(cx))
+            .collect();
+        notebook
     }
 
     fn has_outputs(&self, window: &mut Window, cx: &mut Context<Self>) -> bool {
@@ -174,37 +303,319 @@ impl NotebookEditor {
     }
 
     fn clear_outputs(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.has_outputs(window, cx) {
+            return;
+        }
+
         for cell in self.cell_map.values() {
             if let Cell::Code(code_cell) = cell {
-                code_cell.update(cx, |cell, _cx| {
+                code_cell.update(cx, |cell, cx| {
                     cell.clear_outputs();
+                    cx.notify();
                 });
             }
         }
+        self.mark_dirty(cx);
     }
 
     fn run_cells(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Cells would all run here, if that was implemented!");
+        for cell_id in self.cell_order.clone() {
+            self.run_cell(&cell_id, window, cx);
+        }
+    }
+
+    fn run_selected_cell(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(cell_id) = self.cell_order.get(self.selected_cell_index).cloned() {
+            self.run_cell(&cell_id, window, cx);
+        }
+    }
+
+    fn run_cell(&mut self, cell_id: &CellId, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(Cell::Code(cell)) = self.cell_map.get(cell_id).cloned() else {
+            return;
+        };
+
+        let code = cell.read(cx).source().clone();
+        if code.trim().is_empty() {
+            return;
+        }
+
+        cell.update(cx, |cell, cx| {
+            cell.clear_outputs();
+            cx.notify();
+        });
+
+        let execute_request = ExecuteRequest {
+            code,
+            ..ExecuteRequest::default()
+        };
+        let message: JupyterMessage = execute_request.into();
+
+        self.executions
+            .insert(message.header.msg_id.clone(), cell_id.clone());
+        self.mark_dirty(cx);
+        self.send(message, window, cx);
     }
 
-    fn open_notebook(&mut self, _: &OpenNotebook, _window: &mut Window, _cx: &mut Context<Self>) {
-        println!("Open notebook triggered");
+    fn send(&mut self, message: JupyterMessage, window: &mut Window, cx: &mut Context<Self>) {
+        if let Kernel::Shutdown | Kernel::ErroredLaunch(_) = self.kernel {
+            self.start_kernel(window, cx);
+        }
+
+        match &mut self.kernel {
+            Kernel::RunningKernel(kernel) => {
+                kernel.request_tx().try_send(message).ok();
+            }
+            Kernel::StartingKernel(task) => {
+                // Queue up the execution as a task to run after the kernel starts
+                let task = task.clone();
+
+                cx.spawn(async move |this, cx| {
+                    task.await;
+                    this.update(cx, |this, _| {
+                        if let Kernel::RunningKernel(kernel) = &mut this.kernel {
+                            kernel.request_tx().try_send(message).ok();
+                        }
+                    })
+                    .ok();
+                })
+                .detach();
+            }
+            _ => {}
+        }
+    }
+
+    /// Picks the kernel named in the notebook's metadata, falling back to one for its language.
+    fn kernel_specification(&self, cx: &App) -> Option<KernelSpecification> {
+        let store = ReplStore::global(cx);
+        let store = store.read(cx);
+        let notebook_item = self.notebook_item.read(cx);
+        let worktree_id = notebook_item.project_path.worktree_id;
+
+        let kernel_name = notebook_item
+            .notebook
+            .metadata
+            .kernelspec
+            .as_ref()
+            .map(|kernelspec| kernelspec.name.clone());
+
+        kernel_name
+            .and_then(|kernel_name| {
+                store
+                    .kernel_specifications_for_worktree(worktree_id)
+                    .find(|specification| specification.name().as_ref() == kernel_name)
+                    .cloned()
+            })
+            .or_else(|| {
+                let language = self.notebook_language.clone().now_or_never().flatten();
+                store.active_kernelspec(worktree_id, language, cx)
+            })
+    }
+
+    fn start_kernel(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(kernel_specification) = self.kernel_specification(cx) else {
+            self.kernel_errored("No kernel found for this notebook".to_string(), cx);
+            return;
+        };
+
+        let fs = self.project.read(cx).fs().clone();
+        let working_directory = self
+            .notebook_item
+            .read(cx)
+            .path
+            .parent()
+            .map(|path| path.to_path_buf())
+            .unwrap_or_else(temp_dir);
+        let entity_id = cx.entity_id();
+        let notebook_editor = cx.entity();
+
+        let kernel = match kernel_specification {
+            KernelSpecification::Jupyter(kernel_specification)
+            | KernelSpecification::PythonEnv(kernel_specification) => NativeRunningKernel::new(
+                kernel_specification,
+                entity_id,
+                working_directory,
+                fs,
+                notebook_editor,
+                window,
+                cx,
+            ),
+            KernelSpecification::Remote(remote_kernel_specification) => RemoteRunningKernel::new(
+                remote_kernel_specification,
+                working_directory,
+                notebook_editor,
+                window,
+                cx,
+            ),
+        };
+
+        let pending_kernel = cx
+            .spawn(async move |this, cx| match kernel.await {
+                Ok(kernel) => {
+                    this.update(cx, |this, cx| {
+                        this.kernel = Kernel::RunningKernel(kernel);
+                        cx.notify();
+                    })
+                    .ok();
+                }
+                Err(err) => {
+                    this.update(cx, |this, cx| {
+                        this.kernel_errored(err.to_string(), cx);
+                    })
+                    .ok();
+                }
+            })
+            .shared();
+
+        self.kernel = Kernel::StartingKernel(pending_kernel);
+        cx.notify();
     }
 
     fn move_cell_up(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Move cell up triggered");
+        let index = self.selected_cell_index;
+        if index == 0 || index >= self.cell_count() {
+            return;
+        }
+
+        self.cell_order.swap(index - 1, index);
+        self.cell_list.splice(index - 1..index + 1, 2);
+        self.set_selected_index(index - 1, true, window, cx);
+        self.mark_dirty(cx);
     }
 
     fn move_cell_down(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Move cell down triggered");
+        let index = self.selected_cell_index;
+        if index + 1 >= self.cell_count() {
+            return;
+        }
+
+        self.cell_order.swap(index, index + 1);
+        self.cell_list.splice(index..index + 2, 2);
+        self.set_selected_index(index + 1, true, window, cx);
+        self.mark_dirty(cx);
     }
 
     fn add_markdown_block(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Add markdown block triggered");
+        self.add_cell(CellType::Markdown, window, cx);
     }
 
     fn add_code_block(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Add code block triggered");
+        self.add_cell(CellType::Code, window, cx);
+    }
+
+    /// Adds an empty cell below the current one and starts editing it.
+    fn add_cell(&mut self, cell_type: CellType, window: &mut Window, cx: &mut Context<Self>) {
+        let cell = new_nbformat_cell(
+            cell_type,
+            CellId::from(Uuid::new_v4()),
+            CellMetadata::default(),
+            Vec::new(),
+        );
+        let cell = Cell::load(
+            &cell,
+            &self.languages,
+            self.notebook_language.clone(),
+            window,
+            cx,
+        );
+        let editor = cell.editor(cx);
+
+        let index = if self.cell_order.is_empty() {
+            0
+        } else {
+            (self.selected_cell_index + 1).min(self.cell_count())
+        };
+        self.insert_cell(index, cell, window, cx);
+        self.cell_list.splice(index..index, 1);
+        self.set_selected_index(index, true, window, cx);
+        editor.focus_handle(cx).focus(window);
+        self.mark_dirty(cx);
+    }
+
+    fn delete_cell(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let index = self.selected_cell_index;
+        if index >= self.cell_count() {
+            return;
+        }
+
+        let cell_id = self.cell_order.remove(index);
+        self.cell_map.remove(&cell_id);
+        self.cell_subscriptions.remove(&cell_id);
+        self.executions
+            .retain(|_, execution_cell_id| *execution_cell_id != cell_id);
+
+        self.cell_list.splice(index..index + 1, 0);
+        let index = index.min(self.cell_count().saturating_sub(1));
+        self.set_selected_index(index, true, window, cx);
+        self.mark_dirty(cx);
+    }
+
+    /// Converts the current cell to another type, keeping its ID, metadata and source.
+    fn change_cell_type(
+        &mut self,
+        cell_type: CellType,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let index = self.selected_cell_index;
+        let Some(cell) = self
+            .cell_order
+            .get(index)
+            .and_then(|cell_id| self.cell_map.get(cell_id))
+        else {
+            return;
+        };
+
+        let is_same_type = matches!(
+            (cell, &cell_type),
+            (Cell::Code(_), CellType::Code)
+                | (Cell::Markdown(_), CellType::Markdown)
+                | (Cell::Raw(_), CellType::Raw)
+        );
+        if is_same_type {
+            return;
+        }
+
+        let (id, metadata, source) = match cell.to_nbformat(cx) {
+            nbformat::v4::Cell::Code {
+                id,
+                metadata,
+                source,
+                ..
+            }
+            | nbformat::v4::Cell::Markdown {
+                id,
+                metadata,
+                source,
+                ..
+            }
+            | nbformat::v4::Cell::Raw {
+                id,
+                metadata,
+                source,
+            } => (id, metadata, source),
+        };
+
+        let cell = new_nbformat_cell(cell_type, id, metadata, source);
+        let cell = Cell::load(
+            &cell,
+            &self.languages,
+            self.notebook_language.clone(),
+            window,
+            cx,
+        );
+        let editor = cell.editor(cx);
+
+        let cell_id = self.cell_order.remove(index);
+        self.cell_map.remove(&cell_id);
+        self.cell_subscriptions.remove(&cell_id);
+        self.executions
+            .retain(|_, execution_cell_id| *execution_cell_id != cell_id);
+
+        self.insert_cell(index, cell, window, cx);
+        self.cell_list.splice(index..index + 1, 1);
+        editor.focus_handle(cx).focus(window);
+        self.mark_dirty(cx);
     }
 
     fn cell_count(&self) -> usize {
@@ -404,6 +815,20 @@ impl NotebookEditor {
                                 .on_click(|_, window, cx| {
                                     window.dispatch_action(Box::new(MoveCellDown), cx);
                                 }),
+                            )
+                            .child(
+                                Self::render_notebook_control(
+                                    "delete-cell",
+                                    IconName::Trash,
+                                    window,
+                                    cx,
+                                )
+                                .tooltip(move |window, cx| {
+                                    Tooltip::for_action("Delete cell", &DeleteCell, window, cx)
+                                })
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(Box::new(DeleteCell), cx);
+                                }),
                             ),
                     )
                     .child(
@@ -479,7 +904,7 @@ impl NotebookEditor {
 
         let is_selected = index == self.selected_cell_index;
 
-        match cell {
+        let cell = match cell {
             Cell::Code(cell) => {
                 cell.update(cx, |cell, _cx| {
                     cell.set_selected(is_selected)
@@ -501,7 +926,29 @@ impl NotebookEditor {
                 });
                 cell.clone().into_any_element()
             }
-        }
+        };
+
+        div()
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, window, cx| {
+                    if this.selected_cell_index == index {
+                        return;
+                    }
+
+                    this.set_selected_index(index, false, window, cx);
+                    // Markdown cells only show their editor once selected, so focus it for them
+                    if let Some(cell) = this
+                        .cell_order
+                        .get(index)
+                        .and_then(|cell_id| this.cell_map.get(cell_id))
+                    {
+                        cell.editor(cx).focus_handle(cx).focus(window);
+                    }
+                    cx.notify();
+                }),
+            )
+            .child(cell)
     }
 }
 
@@ -510,9 +957,6 @@ impl Render for NotebookEditor {
         div()
             .key_context("notebook")
             .track_focus(&self.focus_handle)
-            .on_action(cx.listener(|this, &OpenNotebook, window, cx| {
-                this.open_notebook(&OpenNotebook, window, cx)
-            }))
             .on_action(
                 cx.listener(|this, &ClearOutputs, window, cx| this.clear_outputs(window, cx)),
             )
@@ -527,6 +971,17 @@ impl Render for NotebookEditor {
             .on_action(
                 cx.listener(|this, &AddCodeBlock, window, cx| this.add_code_block(window, cx)),
             )
+            .on_action(cx.listener(|this, &RunCell, window, cx| this.run_selected_cell(window, cx)))
+            .on_action(cx.listener(|this, &DeleteCell, window, cx| this.delete_cell(window, cx)))
+            .on_action(cx.listener(|this, &ChangeCellToCode, window, cx| {
+                this.change_cell_type(CellType::Code, window, cx)
+            }))
+            .on_action(cx.listener(|this, &ChangeCellToMarkdown, window, cx| {
+                this.change_cell_type(CellType::Markdown, window, cx)
+            }))
+            .on_action(cx.listener(|this, &ChangeCellToRaw, window, cx| {
+                this.change_cell_type(CellType::Raw, window, cx)
+            }))
             .on_action(cx.listener(Self::select_next))
             .on_action(cx.listener(Self::select_previous))
             .on_action(cx.listener(Self::select_first))
@@ -550,6 +1005,97 @@ impl Render for NotebookEditor {
     }
 }
 
+impl KernelSession for NotebookEditor {
+    fn route(&mut self, message: &JupyterMessage, window: &mut Window, cx: &mut Context<Self>) {
+        match &message.content {
+            JupyterMessageContent::Status(status) => {
+                self.kernel.set_execution_state(&status.execution_state);
+                cx.notify();
+            }
+            JupyterMessageContent::KernelInfoReply(reply) => {
+                self.kernel.set_kernel_info(reply);
+                cx.notify();
+            }
+            _ => {}
+        }
+
+        let Some(parent_message_id) = message
+            .parent_header
+            .as_ref()
+            .map(|header| header.msg_id.clone())
+        else {
+            return;
+        };
+        let Some(cell) = self
+            .executions
+            .get(&parent_message_id)
+            .and_then(|cell_id| self.cell_map.get(cell_id))
+            .cloned()
+        else {
+            return;
+        };
+
+        if let Cell::Code(cell) = cell {
+            let changed = cell.update(cx, |cell, cx| {
+                cell.push_message(&message.content, window, cx)
+            });
+            // Outputs can arrive after a save, and are stored in the notebook too
+            if changed {
+                self.mark_dirty(cx);
+            }
+        }
+
+        // The kernel goes back to idle once it's done with a request
+        if let JupyterMessageContent::Status(status) = &message.content {
+            if let ExecutionState::Idle = status.execution_state {
+                self.executions.remove(&parent_message_id);
+            }
+        }
+    }
+
+    fn kernel_errored(&mut self, error_message: String, cx: &mut Context<Self>) {
+        for cell_id in self.executions.values() {
+            if let Some(Cell::Code(cell)) = self.cell_map.get(cell_id) {
+                cell.update(cx, |cell, cx| {
+                    cell.push_error_message(format!("Kernel error: {error_message}"), cx);
+                });
+            }
+        }
+
+        self.executions.clear();
+        self.kernel = Kernel::ErroredLaunch(error_message);
+        cx.notify();
+    }
+}
+
+fn new_nbformat_cell(
+    cell_type: CellType,
+    id: CellId,
+    metadata: CellMetadata,
+    source: Vec<String>,
+) -> nbformat::v4::Cell {
+    match cell_type {
+        CellType::Code => nbformat::v4::Cell::Code {
+            id,
+            metadata,
+            execution_count: None,
+            source,
+            outputs: Vec::new(),
+        },
+        CellType::Markdown => nbformat::v4::Cell::Markdown {
+            id,
+            metadata,
+            source,
+            attachments: None,
+        },
+        CellType::Raw => nbformat::v4::Cell::Raw {
+            id,
+            metadata,
+            source,
+        },
+    }
+}
+
 impl Focusable for NotebookEditor {
     fn focus_handle(&self, _: &App) -> FocusHandle {
         self.focus_handle.clone()
@@ -560,13 +1106,22 @@ impl Focusable for NotebookEditor {
 pub struct NotebookItem {
     path: PathBuf,
     project_path: ProjectPath,
+    project: WeakEntity<Project>,
+    fs: Arc<dyn Fs>,
     languages: Arc<LanguageRegistry>,
-    // Raw notebook data
+    // Raw notebook data, as it was last read from or written to disk
     notebook: nbformat::v4::Notebook,
-    // Store our version of the notebook in memory (cell_order, cell_map)
-    id: ProjectEntryId,
+    file_content: String,
+    _file_watcher: Task<()>,
+}
+
+pub enum NotebookItemEvent {
+    /// The notebook file was changed by something other than Zed.
+    ChangedOnDisk,
 }
 
+impl EventEmitter<NotebookItemEvent> for NotebookItem {}
+
 impl project::ProjectItem for NotebookItem {
     fn try_open(
         project: &Entity<Project>,
@@ -584,36 +1139,26 @@ impl project::ProjectItem for NotebookItem {
                     .read_with(cx, |project, cx| project.absolute_path(&path, cx))?
                     .with_context(|| format!("finding the absolute path of {path:?}"))?;
 
-                // todo: watch for changes to the file
                 let file_content = fs.load(&abs_path.as_path()).await?;
-                let notebook = nbformat::parse_notebook(&file_content);
-
-                let notebook = match notebook {
-                    Ok(nbformat::Notebook::V4(notebook)) => notebook,
-                    // 4.1 - 4.4 are converted to 4.5
-                    Ok(nbformat::Notebook::Legacy(legacy_notebook)) => {
-                        // TODO: Decide if we want to mutate the notebook by including Cell IDs
-                        // and any other conversions
-                        let notebook = nbformat::upgrade_legacy_notebook(legacy_notebook)?;
-                        notebook
-                    }
-                    // Bad notebooks and notebooks v4.0 and below are not supported
-                    Err(e) => {
-                        anyhow::bail!("Failed to parse notebook: {:?}", e);
-                    }
-                };
+                let notebook = parse_notebook(&file_content)?;
 
-                let id = project
+                project
                     .update(cx, |project, cx| project.entry_for_path(&path, cx))?
-                    .context("Entry not found")?
-                    .id;
-
-                cx.new(|_| NotebookItem {
-                    path: abs_path,
-                    project_path: path,
-                    languages,
-                    notebook,
-                    id,
+                    .context("Entry not found")?;
+
+                cx.new(|cx| {
+                    let mut notebook_item = NotebookItem {
+                        path: abs_path,
+                        project_path: path,
+                        project: project.downgrade(),
+                        fs,
+                        languages,
+                        notebook,
+                        file_content,
+                        _file_watcher: Task::ready(()),
+                    };
+                    notebook_item.watch_file(cx);
+                    notebook_item
                 })
             }))
         } else {
@@ -621,8 +1166,12 @@ impl project::ProjectItem for NotebookItem {
         }
     }
 
-    fn entry_id(&self, _: &App) -> Option<ProjectEntryId> {
-        Some(self.id)
+    fn entry_id(&self, cx: &App) -> Option<ProjectEntryId> {
+        self.project
+            .upgrade()?
+            .read(cx)
+            .entry_for_path(&self.project_path, cx)
+            .map(|entry| entry.id)
     }
 
     fn project_path(&self, _: &App) -> Option<ProjectPath> {
@@ -634,6 +1183,34 @@ impl project::ProjectItem for NotebookItem {
     }
 }
 
+fn parse_notebook(content: &str) -> Result<nbformat::v4::Notebook> {
+    match nbformat::parse_notebook(content) {
+        Ok(nbformat::Notebook::V4(notebook)) => Ok(notebook),
+        // 4.1 - 4.4 are converted to 4.5
+        Ok(nbformat::Notebook::Legacy(legacy_notebook)) => {
+            // Upgrading assigns cell IDs, which are written back on the next save
+            let notebook = nbformat::upgrade_legacy_notebook(legacy_notebook)?;
+            Ok(notebook)
+        }
+        // Bad notebooks and notebooks v4.0 and below are not supported
+        Err(e) => {
+            anyhow::bail!("Failed to parse notebook: {:?}", e);
+        }
+    }
+}
+
+/// Serializes a notebook the way Jupyter writes it, to keep diffs of saved notebooks small.
+fn serialize_notebook(notebook: &nbformat::v4::Notebook) -> Result<String> {
+    let mut content = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b" ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut content, formatter);
+    notebook.serialize(&mut serializer)?;
+
+    let mut content = String::from_utf8(content)?;
+    content.push('\n');
+    Ok(content)
+}
+
 impl NotebookItem {
     pub fn language_name(&self) -> Option<String> {
         self.notebook
@@ -661,9 +1238,121 @@ impl NotebookItem {
             }
         }
     }
+
+    fn watch_file(&mut self, cx: &mut Context<Self>) {
+        let fs = self.fs.clone();
+        let path = self.path.clone();
+
+        self._file_watcher = cx.spawn(async move |this, cx| {
+            let (mut events, _watcher) = fs.watch(&path, FILE_WATCH_LATENCY).await;
+
+            while events.next().await.is_some() {
+                // The file may be gone or halfway written, we'll hear about it again
+                let Ok(file_content) = fs.load(&path).await else {
+                    continue;
+                };
+
+                let result = this.update(cx, |notebook_item, cx| {
+                    if notebook_item.file_content == file_content {
+                        return;
+                    }
+
+                    match parse_notebook(&file_content) {
+                        Ok(notebook) => {
+                            notebook_item.notebook = notebook;
+                            notebook_item.file_content = file_content;
+                            cx.emit(NotebookItemEvent::ChangedOnDisk);
+                        }
+                        Err(err) => log::warn!("failed to reload notebook {path:?}: {err}"),
+                    }
+                });
+
+                if result.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Writes the notebook to disk.
+    pub fn save(
+        &mut self,
+        notebook: nbformat::v4::Notebook,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let file_content = match serialize_notebook(&notebook) {
+            Ok(file_content) => file_content,
+            Err(err) => return Task::ready(Err(err)),
+        };
+
+        // Remember what we wrote, so the file watcher doesn't mistake it for an external change
+        self.notebook = notebook;
+        self.file_content = file_content.clone();
+
+        let fs = self.fs.clone();
+        let path = self.path.clone();
+        cx.background_spawn(async move { fs.atomic_write(path, file_content).await })
+    }
+
+    /// Writes the notebook to a new path, which this item refers to from then on.
+    pub fn save_as(
+        &mut self,
+        project: Entity<Project>,
+        project_path: ProjectPath,
+        notebook: nbformat::v4::Notebook,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let Some(abs_path) = project.read(cx).absolute_path(&project_path, cx) else {
+            return Task::ready(Err(anyhow::anyhow!(
+                "finding the absolute path of {project_path:?}"
+            )));
+        };
+        let file_content = match serialize_notebook(&notebook) {
+            Ok(file_content) => file_content,
+            Err(err) => return Task::ready(Err(err)),
+        };
+
+        let fs = self.fs.clone();
+        cx.spawn(async move |this, cx| {
+            fs.atomic_write(abs_path.clone(), file_content.clone())
+                .await?;
+
+            this.update(cx, |notebook_item, cx| {
+                notebook_item.path = abs_path;
+                notebook_item.project_path = project_path;
+                notebook_item.project = project.downgrade();
+                notebook_item.notebook = notebook;
+                notebook_item.file_content = file_content;
+                notebook_item.watch_file(cx);
+            })
+        })
+    }
+
+    /// Reads the notebook from disk again, replacing what was loaded before.
+    pub fn reload(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let fs = self.fs.clone();
+        let path = self.path.clone();
+
+        cx.spawn(async move |this, cx| {
+            let file_content = fs.load(&path).await?;
+            let notebook = parse_notebook(&file_content)?;
+
+            this.update(cx, |notebook_item, _| {
+                notebook_item.notebook = notebook;
+                notebook_item.file_content = file_content;
+            })
+        })
+    }
+}
+
+pub enum NotebookEditorEvent {
+    /// The cells or their outputs were edited.
+    Edited,
+    /// The dirty or conflict state changed without an edit, e.g. after saving.
+    DirtyChanged,
 }
 
-impl EventEmitter<()> for NotebookEditor {}
+impl EventEmitter<NotebookEditorEvent> for NotebookEditor {}
 
 // pub struct NotebookControls {
 //     pane_focused: bool,
@@ -711,7 +1400,17 @@ impl EventEmitter<()> for NotebookEditor {}
 // }
 
 impl Item for NotebookEditor {
-    type Event = ();
+    type Event = NotebookEditorEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        match event {
+            NotebookEditorEvent::Edited => {
+                f(ItemEvent::Edit);
+                f(ItemEvent::UpdateTab);
+            }
+            NotebookEditorEvent::DirtyChanged => f(ItemEvent::UpdateTab),
+        }
+    }
 
     fn clone_on_split(
         &self,
@@ -782,49 +1481,90 @@ impl Item for NotebookEditor {
         // TODO
     }
 
-    // TODO
     fn can_save(&self, _cx: &App) -> bool {
-        false
+        true
     }
-    // TODO
+
+    fn can_save_as(&self, _cx: &App) -> bool {
+        true
+    }
+
     fn save(
         &mut self,
         _options: SaveOptions,
         _project: Entity<Project>,
         _window: &mut Window,
-        _cx: &mut Context<Self>,
+        cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
-        unimplemented!("save() must be implemented if can_save() returns true")
+        let notebook = self.to_notebook(cx);
+        let saved_version = self.edit_version;
+        let save = self
+            .notebook_item
+            .update(cx, |notebook_item, cx| notebook_item.save(notebook, cx));
+
+        cx.spawn(async move |this, cx| {
+            save.await?;
+            this.update(cx, |this, cx| {
+                if this.edit_version == saved_version {
+                    this.is_dirty = false;
+                }
+                this.has_conflict = false;
+                cx.emit(NotebookEditorEvent::DirtyChanged);
+                cx.notify();
+            })
+        })
     }
 
-    // TODO
     fn save_as(
         &mut self,
-        _project: Entity<Project>,
-        _path: ProjectPath,
+        project: Entity<Project>,
+        path: ProjectPath,
         _window: &mut Window,
-        _cx: &mut Context<Self>,
+        cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
-        unimplemented!("save_as() must be implemented if can_save() returns true")
+        let notebook = self.to_notebook(cx);
+        let saved_version = self.edit_version;
+        let save = self.notebook_item.update(cx, |notebook_item, cx| {
+            notebook_item.save_as(project, path, notebook, cx)
+        });
+
+        cx.spawn(async move |this, cx| {
+            save.await?;
+            this.update(cx, |this, cx| {
+                if this.edit_version == saved_version {
+                    this.is_dirty = false;
+                }
+                this.has_conflict = false;
+                cx.emit(NotebookEditorEvent::DirtyChanged);
+                cx.notify();
+            })
+        })
     }
-    // TODO
+
     fn reload(
         &mut self,
         _project: Entity<Project>,
-        _window: &mut Window,
-        _cx: &mut Context<Self>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
-        unimplemented!("reload() must be implemented if can_save() returns true")
+        let reload = self
+            .notebook_item
+            .update(cx, |notebook_item, cx| notebook_item.reload(cx));
+
+        cx.spawn_in(window, async move |this, cx| {
+            reload.await?;
+            this.update_in(cx, |this, window, cx| {
+                this.load_cells(window, cx);
+            })
+        })
     }
 
-    fn is_dirty(&self, cx: &App) -> bool {
-        self.cell_map.values().any(|cell| {
-            if let Cell::Code(code_cell) = cell {
-                code_cell.read(cx).is_dirty(cx)
-            } else {
-                false
-            }
-        })
+    fn is_dirty(&self, _cx: &App) -> bool {
+        self.is_dirty
+    }
+
+    fn has_conflict(&self, _cx: &App) -> bool {
+        self.has_conflict
     }
 }
 
@@ -847,3 +1587,380 @@ impl ProjectItem for NotebookEditor {
         Self::new(project, item, window, cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use indoc::indoc;
+    use project::FakeFs;
+    use runtimelib::{Status, Stdio, StreamContent};
+    use serde_json::json;
+    use settings::{Settings as _, SettingsStore};
+    use std::path::Path;
+    use terminal::terminal_settings::TerminalSettings;
+    use util::path;
+
+    const NOTEBOOK: &str = indoc! {r##"
+        {
+         "cells": [
+          {
+           "cell_type": "markdown",
+           "id": "introduction",
+           "metadata": {
+            "tags": ["header"]
+           },
+           "source": ["# Title\n", "Some text"]
+          },
+          {
+           "cell_type": "code",
+           "execution_count": 1,
+           "id": "computation",
+           "metadata": {},
+           "outputs": [
+            {
+             "name": "stdout",
+             "output_type": "stream",
+             "text": ["2\n"]
+            }
+           ],
+           "source": ["print(1 + 1)"]
+          }
+         ],
+         "metadata": {
+          "kernelspec": {
+           "display_name": "Python 3",
+           "language": "python",
+           "name": "python3"
+          }
+         },
+         "nbformat": 4,
+         "nbformat_minor": 5
+        }
+    "##};
+
+    #[test]
+    fn test_saved_notebook_keeps_metadata_and_cell_ids() {
+        let notebook = parse_notebook(NOTEBOOK).unwrap();
+        let saved_content = serialize_notebook(&notebook).unwrap();
+        assert!(saved_content.ends_with("}\n"));
+
+        let saved_notebook = parse_notebook(&saved_content).unwrap();
+        let cell_ids = saved_notebook
+            .cells
+            .iter()
+            .map(|cell| cell.id().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(cell_ids, ["introduction", "computation"]);
+        assert_eq!(
+            saved_notebook
+                .metadata
+                .kernelspec
+                .map(|kernelspec| kernelspec.name),
+            Some("python3".to_string())
+        );
+
+        let saved_json: serde_json::Value = serde_json::from_str(&saved_content).unwrap();
+        assert_eq!(
+            saved_json["cells"][0]["metadata"]["tags"],
+            serde_json::json!(["header"])
+        );
+        assert_eq!(saved_json["cells"][1]["execution_count"], 1);
+        assert_eq!(saved_json["cells"][1]["outputs"][0]["name"], "stdout");
+    }
+
+    #[gpui::test]
+    async fn test_adding_moving_and_deleting_cells(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (notebook_editor, fs, cx) = open_notebook(cx).await;
+        assert_eq!(
+            cell_ids(&notebook_editor, cx),
+            ["introduction", "computation"]
+        );
+
+        notebook_editor.update_in(cx, |notebook_editor, window, cx| {
+            notebook_editor.set_selected_index(0, false, window, cx);
+            notebook_editor.add_cell(CellType::Code, window, cx);
+        });
+        let ids = cell_ids(&notebook_editor, cx);
+        assert_eq!(ids.len(), 3);
+        assert_eq!(
+            [ids[0].as_str(), ids[2].as_str()],
+            ["introduction", "computation"]
+        );
+        let new_id = ids[1].clone();
+        assert!(notebook_editor.read_with(cx, |notebook_editor, _| notebook_editor.is_dirty));
+
+        notebook_editor.update_in(cx, |notebook_editor, window, cx| {
+            notebook_editor.move_cell_up(window, cx);
+        });
+        assert_eq!(
+            cell_ids(&notebook_editor, cx),
+            [new_id.as_str(), "introduction", "computation"]
+        );
+
+        notebook_editor.update_in(cx, |notebook_editor, window, cx| {
+            notebook_editor.move_cell_down(window, cx);
+            notebook_editor.move_cell_down(window, cx);
+        });
+        assert_eq!(
+            cell_ids(&notebook_editor, cx),
+            ["introduction", "computation", new_id.as_str()]
+        );
+
+        save(&notebook_editor, cx).await.unwrap();
+        let saved_notebook = parse_notebook(
+            &fs.load(path!("/root/notebook.ipynb").as_ref())
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        let saved_ids = saved_notebook
+            .cells
+            .iter()
+            .map(|cell| cell.id().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(saved_ids, ["introduction", "computation", new_id.as_str()]);
+        assert!(!notebook_editor.read_with(cx, |notebook_editor, _| notebook_editor.is_dirty));
+
+        notebook_editor.update_in(cx, |notebook_editor, window, cx| {
+            notebook_editor.delete_cell(window, cx);
+        });
+        assert_eq!(
+            cell_ids(&notebook_editor, cx),
+            ["introduction", "computation"]
+        );
+        assert!(notebook_editor.read_with(cx, |notebook_editor, _| notebook_editor.is_dirty));
+    }
+
+    #[gpui::test]
+    async fn test_execution_output_is_saved_with_the_cell(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (notebook_editor, _, cx) = open_notebook(cx).await;
+
+        notebook_editor.update_in(cx, |notebook_editor, window, cx| {
+            notebook_editor.clear_outputs(window, cx);
+
+            let request: JupyterMessage = ExecuteRequest {
+                code: "print(1 + 1)\nprint(3)".to_string(),
+                ..ExecuteRequest::default()
+            }
+            .into();
+            let cell_id = notebook_editor.cell_order[1].clone();
+            notebook_editor
+                .executions
+                .insert(request.header.msg_id.clone(), cell_id);
+
+            for text in ["2\n", "3\n"] {
+                let stream = StreamContent {
+                    name: Stdio::Stdout,
+                    text: text.to_string(),
+                };
+                notebook_editor.route(&JupyterMessage::new(stream, Some(&request)), window, cx);
+            }
+            let idle = Status {
+                execution_state: ExecutionState::Idle,
+            };
+            notebook_editor.route(&JupyterMessage::new(idle, Some(&request)), window, cx);
+        });
+
+        notebook_editor.read_with(cx, |notebook_editor, cx| {
+            assert!(notebook_editor.executions.is_empty());
+
+            let notebook = notebook_editor.to_notebook(cx);
+            let nbformat::v4::Cell::Code { outputs, .. } = &notebook.cells[1] else {
+                panic!("expected a code cell");
+            };
+            // Consecutive stream writes end up in a single output
+            assert_eq!(outputs.len(), 1);
+            let nbformat::v4::Output::Stream { name, text } = &outputs[0] else {
+                panic!("expected a stream output");
+            };
+            assert_eq!(name, "stdout");
+            assert_eq!(text.0, "2\n3\n");
+        });
+    }
+
+    #[gpui::test]
+    async fn test_outputs_arriving_after_save_mark_notebook_dirty(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (notebook_editor, fs, cx) = open_notebook(cx).await;
+
+        let request: JupyterMessage = ExecuteRequest {
+            code: "print(1 + 1)".to_string(),
+            ..ExecuteRequest::default()
+        }
+        .into();
+        notebook_editor.update_in(cx, |notebook_editor, window, cx| {
+            notebook_editor.clear_outputs(window, cx);
+            let cell_id = notebook_editor.cell_order[1].clone();
+            notebook_editor
+                .executions
+                .insert(request.header.msg_id.clone(), cell_id);
+        });
+        save(&notebook_editor, cx).await.unwrap();
+        assert!(!notebook_editor.read_with(cx, |notebook_editor, _| notebook_editor.is_dirty));
+
+        // The kernel answers once the notebook has been saved
+        notebook_editor.update_in(cx, |notebook_editor, window, cx| {
+            let stream = StreamContent {
+                name: Stdio::Stdout,
+                text: "2\n".to_string(),
+            };
+            notebook_editor.route(&JupyterMessage::new(stream, Some(&request)), window, cx);
+        });
+        assert!(notebook_editor.read_with(cx, |notebook_editor, _| notebook_editor.is_dirty));
+
+        save(&notebook_editor, cx).await.unwrap();
+        let saved_content = fs
+            .load(path!("/root/notebook.ipynb").as_ref())
+            .await
+            .unwrap();
+        let saved_notebook = parse_notebook(&saved_content).unwrap();
+        let nbformat::v4::Cell::Code { outputs, .. } = &saved_notebook.cells[1] else {
+            panic!("expected a code cell");
+        };
+        let [nbformat::v4::Output::Stream { text, .. }] = outputs.as_slice() else {
+            panic!("expected a single stream output, got {outputs:?}");
+        };
+        assert_eq!(text.0, "2\n");
+    }
+
+    #[gpui::test]
+    async fn test_reload_and_conflict_on_disk_change(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (notebook_editor, fs, cx) = open_notebook(cx).await;
+        let mut notebook = parse_notebook(NOTEBOOK).unwrap();
+
+        // Without unsaved edits, changes on disk are picked up right away
+        notebook.cells.remove(0);
+        write_notebook(&fs, &notebook).await;
+        cx.run_until_parked();
+        assert_eq!(cell_ids(&notebook_editor, cx), ["computation"]);
+        notebook_editor.read_with(cx, |notebook_editor, _| {
+            assert!(!notebook_editor.is_dirty);
+            assert!(!notebook_editor.has_conflict);
+        });
+
+        // Unsaved edits are kept, and the notebook is marked as conflicted
+        notebook_editor.update_in(cx, |notebook_editor, window, cx| {
+            notebook_editor.add_cell(CellType::Markdown, window, cx);
+        });
+        notebook.cells.clear();
+        write_notebook(&fs, &notebook).await;
+        cx.run_until_parked();
+        assert_eq!(cell_ids(&notebook_editor, cx).len(), 2);
+        notebook_editor.read_with(cx, |notebook_editor, _| {
+            assert!(notebook_editor.is_dirty);
+            assert!(notebook_editor.has_conflict);
+        });
+
+        // Reloading discards the edits in favor of the file on disk
+        notebook_editor
+            .update_in(cx, |notebook_editor, window, cx| {
+                let project = notebook_editor.project.clone();
+                notebook_editor.reload(project, window, cx)
+            })
+            .await
+            .unwrap();
+        assert!(cell_ids(&notebook_editor, cx).is_empty());
+        notebook_editor.read_with(cx, |notebook_editor, _| {
+            assert!(!notebook_editor.is_dirty);
+            assert!(!notebook_editor.has_conflict);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_edits_during_save_keep_notebook_dirty(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (notebook_editor, _, cx) = open_notebook(cx).await;
+
+        notebook_editor.update_in(cx, |notebook_editor, window, cx| {
+            notebook_editor.add_cell(CellType::Code, window, cx);
+        });
+        let save_task = save(&notebook_editor, cx);
+        notebook_editor.update_in(cx, |notebook_editor, window, cx| {
+            notebook_editor.add_cell(CellType::Markdown, window, cx);
+        });
+        save_task.await.unwrap();
+        notebook_editor.read_with(cx, |notebook_editor, _| {
+            assert!(notebook_editor.is_dirty);
+            assert!(!notebook_editor.has_conflict);
+        });
+
+        save(&notebook_editor, cx).await.unwrap();
+        assert!(!notebook_editor.read_with(cx, |notebook_editor, _| notebook_editor.is_dirty));
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            Project::init_settings(cx);
+            workspace::init_settings(cx);
+            editor::init(cx);
+            TerminalSettings::register(cx);
+        });
+    }
+
+    async fn open_notebook(
+        cx: &mut TestAppContext,
+    ) -> (Entity<NotebookEditor>, Arc<FakeFs>, &mut VisualTestContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/root"), json!({ "notebook.ipynb": NOTEBOOK }))
+            .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+
+        let worktree_id = project.update(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        let project_path = ProjectPath {
+            worktree_id,
+            path: Path::new("notebook.ipynb").into(),
+        };
+        let notebook_item = cx
+            .update(|cx| {
+                <NotebookItem as project::ProjectItem>::try_open(&project, &project_path, cx)
+            })
+            .unwrap()
+            .await
+            .unwrap();
+
+        let (notebook_editor, cx) = cx
+            .add_window_view(|window, cx| NotebookEditor::new(project, notebook_item, window, cx));
+        cx.run_until_parked();
+        (notebook_editor, fs, cx)
+    }
+
+    fn cell_ids(
+        notebook_editor: &Entity<NotebookEditor>,
+        cx: &mut VisualTestContext,
+    ) -> Vec<String> {
+        notebook_editor.read_with(cx, |notebook_editor, cx| {
+            notebook_editor
+                .to_notebook(cx)
+                .cells
+                .iter()
+                .map(|cell| cell.id().to_string())
+                .collect()
+        })
+    }
+
+    fn save(
+        notebook_editor: &Entity<NotebookEditor>,
+        cx: &mut VisualTestContext,
+    ) -> Task<Result<()>> {
+        notebook_editor.update_in(cx, |notebook_editor, window, cx| {
+            let project = notebook_editor.project.clone();
+            notebook_editor.save(SaveOptions::default(), project, window, cx)
+        })
+    }
+
+    async fn write_notebook(fs: &FakeFs, notebook: &nbformat::v4::Notebook) {
+        let file_content = serialize_notebook(notebook).unwrap();
+        fs.insert_file(path!("/root/notebook.ipynb"), file_content.into_bytes())
+            .await;
+    }
+}
@@ -1,6 +1,6 @@
 use gpui::{Action, SharedString};
 
-use crate::{ActivateRegexMode, ActivateTextMode};
+use crate::{ActivateRegexMode, ActivateSemanticMode, ActivateTextMode};
 
 // TODO: Update the default search mode to get from config
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
@@ -8,6 +8,7 @@ pub enum SearchMode {
     #[default]
     Text,
     Regex,
+    Semantic,
 }
 
 impl SearchMode {
@@ -15,6 +16,7 @@ impl SearchMode {
         match self {
             SearchMode::Text => "Text",
             SearchMode::Regex => "Regex",
+            SearchMode::Semantic => "Semantic",
         }
     }
     pub(crate) fn tooltip(&self) -> SharedString {
@@ -24,13 +26,15 @@ impl SearchMode {
         match self {
             SearchMode::Text => ActivateTextMode.boxed_clone(),
             SearchMode::Regex => ActivateRegexMode.boxed_clone(),
+            SearchMode::Semantic => ActivateSemanticMode.boxed_clone(),
         }
     }
 }
 
-pub(crate) fn next_mode(mode: &SearchMode) -> SearchMode {
+pub(crate) fn next_mode(mode: &SearchMode, semantic_enabled: bool) -> SearchMode {
     match mode {
         SearchMode::Text => SearchMode::Regex,
-        SearchMode::Regex => SearchMode::Text,
+        SearchMode::Regex if semantic_enabled => SearchMode::Semantic,
+        SearchMode::Regex | SearchMode::Semantic => SearchMode::Text,
     }
 }
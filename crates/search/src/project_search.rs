@@ -1,28 +1,33 @@
 use crate::{
-    BufferSearchBar, FocusSearch, NextHistoryQuery, PreviousHistoryQuery, ReplaceAll, ReplaceNext,
-    SearchOptions, SelectNextMatch, SelectPreviousMatch, ToggleCaseSensitive, ToggleIncludeIgnored,
-    ToggleRegex, ToggleReplace, ToggleWholeWord, buffer_search::Deploy,
+    ActivateRegexMode, ActivateSemanticMode, ActivateTextMode, BufferSearchBar, CycleMode,
+    FocusSearch, NextHistoryQuery, PreviousHistoryQuery, ReplaceAll, ReplaceNext, SearchOptions,
+    SelectNextMatch, SelectPreviousMatch, ToggleCaseSensitive, ToggleIncludeIgnored, ToggleRegex,
+    ToggleReplace, ToggleWholeWord,
+    buffer_search::Deploy,
+    mode::{self, SearchMode},
 };
 use anyhow::Context as _;
 use collections::{HashMap, HashSet};
 use editor::{
-    Anchor, Editor, EditorElement, EditorEvent, EditorSettings, EditorStyle, MAX_TAB_TITLE_LEN,
-    MultiBuffer, SelectionEffects, actions::SelectAll, items::active_match_index,
+    Anchor, Editor, EditorElement, EditorEvent, EditorSettings, EditorStyle, ExcerptRange,
+    MAX_TAB_TITLE_LEN, MultiBuffer, SelectionEffects, actions::SelectAll,
+    items::active_match_index,
 };
-use futures::{StreamExt, stream::FuturesOrdered};
+use futures::{StreamExt, future::join_all, stream::FuturesOrdered};
 use gpui::{
     Action, AnyElement, AnyView, App, Axis, Context, Entity, EntityId, EventEmitter, FocusHandle,
     Focusable, Global, Hsla, InteractiveElement, IntoElement, KeyContext, ParentElement, Point,
     Render, SharedString, Styled, Subscription, Task, TextStyle, UpdateGlobal, WeakEntity, Window,
     actions, div,
 };
-use language::{Buffer, Language};
+use language::{Bias, Buffer, Language};
 use menu::Confirm;
 use project::{
     Project, ProjectPath,
     search::{SearchInputKind, SearchQuery},
     search_history::SearchHistoryCursor,
 };
+use semantic_index::{ProjectIndex, SemanticDb};
 use settings::Settings;
 use std::{
     any::{Any, TypeId},
@@ -59,6 +64,8 @@ actions!(
     ]
 );
 
+const SEMANTIC_SEARCH_RESULT_LIMIT: usize = 64;
+
 #[derive(Default)]
 struct ActiveSettings(HashMap<WeakEntity<Project>, ProjectSearchSettings>);
 
@@ -94,6 +101,27 @@ pub fn init(cx: &mut App) {
         register_workspace_action(workspace, move |search_bar, _: &ToggleRegex, window, cx| {
             search_bar.toggle_search_option(SearchOptions::REGEX, window, cx);
         });
+        register_workspace_action(workspace, move |search_bar, action, window, cx| {
+            search_bar.cycle_mode(action, window, cx);
+        });
+        register_workspace_action(
+            workspace,
+            move |search_bar, _: &ActivateTextMode, window, cx| {
+                search_bar.activate_search_mode(SearchMode::Text, window, cx);
+            },
+        );
+        register_workspace_action(
+            workspace,
+            move |search_bar, _: &ActivateRegexMode, window, cx| {
+                search_bar.activate_search_mode(SearchMode::Regex, window, cx);
+            },
+        );
+        register_workspace_action(
+            workspace,
+            move |search_bar, _: &ActivateSemanticMode, window, cx| {
+                search_bar.activate_search_mode(SearchMode::Semantic, window, cx);
+            },
+        );
         register_workspace_action(
             workspace,
             move |search_bar, action: &ToggleReplace, window, cx| {
@@ -216,10 +244,20 @@ pub struct ProjectSearchView {
     replace_enabled: bool,
     included_opened_only: bool,
     regex_language: Option<Arc<Language>>,
+    semantic_state: Option<SemanticState>,
     _subscriptions: Vec<Subscription>,
     query_error: Option<String>,
 }
 
+struct SemanticState {
+    index: Entity<ProjectIndex>,
+    index_status: semantic_index::Status,
+    /// Whether the last search ran before the index was ready, and has to be repeated once
+    /// indexing finishes.
+    search_when_indexed: bool,
+    _subscription: Subscription,
+}
+
 #[derive(Debug, Clone)]
 pub struct ProjectSearchSettings {
     search_options: SearchOptions,
@@ -376,6 +414,95 @@ impl ProjectSearch {
         }));
         cx.notify();
     }
+
+    fn semantic_search(
+        &mut self,
+        query: String,
+        project_index: Entity<ProjectIndex>,
+        cx: &mut Context<Self>,
+    ) {
+        let search =
+            project_index
+                .read(cx)
+                .search(vec![query.clone()], SEMANTIC_SEARCH_RESULT_LIMIT, cx);
+        self.project.update(cx, |project, _| {
+            project
+                .search_history_mut(SearchInputKind::Query)
+                .add(&mut self.search_history_cursor, query.clone());
+        });
+        self.last_search_query_text = Some(query);
+        self.search_id += 1;
+        self.active_query = None;
+        self.match_ranges.clear();
+        self.pending_search = Some(cx.spawn(async move |project_search, cx| {
+            let mut results = search.await.log_err().unwrap_or_default();
+            results.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+            let buffers = project_search
+                .update(cx, |project_search, cx| {
+                    project_search.match_ranges.clear();
+                    project_search
+                        .excerpts
+                        .update(cx, |excerpts, cx| excerpts.clear(cx));
+                    project_search.no_results = Some(true);
+                    project_search.limit_reached = false;
+                    project_search.project.update(cx, |project, cx| {
+                        results
+                            .iter()
+                            .map(|result| {
+                                let project_path = ProjectPath {
+                                    worktree_id: result.worktree.read(cx).id(),
+                                    path: result.path.clone(),
+                                };
+                                project.open_buffer(project_path, cx)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .ok()?;
+            let buffers = join_all(buffers).await;
+
+            project_search
+                .update(cx, |project_search, cx| {
+                    // Excerpts are pushed in the order of their score, so the best hits come first.
+                    let match_ranges = project_search.excerpts.update(cx, |excerpts, cx| {
+                        let mut match_ranges = Vec::with_capacity(results.len());
+                        for (result, buffer) in results.iter().zip(buffers) {
+                            let Some(buffer) = buffer.log_err() else {
+                                continue;
+                            };
+                            let snapshot = buffer.read(cx).snapshot();
+                            let range = snapshot
+                                .anchor_before(snapshot.clip_offset(result.range.start, Bias::Left))
+                                ..snapshot.anchor_after(
+                                    snapshot.clip_offset(result.range.end, Bias::Right),
+                                );
+                            let excerpt_ids = excerpts.push_excerpts(
+                                buffer,
+                                [ExcerptRange::new(range.clone())],
+                                cx,
+                            );
+                            let multibuffer = excerpts.snapshot(cx);
+                            match_ranges.extend(excerpt_ids.into_iter().filter_map(|excerpt_id| {
+                                Some(
+                                    multibuffer.anchor_in_excerpt(excerpt_id, range.start)?
+                                        ..multibuffer.anchor_in_excerpt(excerpt_id, range.end)?,
+                                )
+                            }));
+                        }
+                        match_ranges
+                    });
+                    project_search.no_results = Some(match_ranges.is_empty());
+                    project_search.match_ranges = match_ranges;
+                    project_search.pending_search.take();
+                    cx.notify();
+                })
+                .ok()?;
+
+            None
+        }));
+        cx.notify();
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -413,8 +540,18 @@ impl Render for ProjectSearchView {
                 .justify_center()
                 .child(Label::new(heading_text).size(LabelSize::Large));
 
+            let waiting_for_index = self
+                .semantic_state
+                .as_ref()
+                .is_some_and(|semantic_state| semantic_state.search_when_indexed);
             let page_content: Option<AnyElement> = if let Some(no_results) = model.no_results {
-                if model.pending_search.is_none() && no_results {
+                if model.pending_search.is_none() && no_results && waiting_for_index {
+                    Some(
+                        Label::new("The project is still being indexed, results will update once it is done")
+                            .size(LabelSize::Small)
+                            .into_any_element(),
+                    )
+                } else if model.pending_search.is_none() && no_results {
                     Some(
                         Label::new("No results found in this project for the provided query")
                             .size(LabelSize::Small)
@@ -664,6 +801,9 @@ impl ProjectSearchView {
     }
 
     fn toggle_search_option(&mut self, option: SearchOptions, cx: &mut Context<Self>) {
+        if option == SearchOptions::REGEX {
+            self.semantic_state = None;
+        }
         self.search_options.toggle(option);
         ActiveSettings::update_global(cx, |settings, cx| {
             settings.0.insert(
@@ -674,6 +814,65 @@ impl ProjectSearchView {
         self.adjust_query_regex_language(cx);
     }
 
+    fn current_mode(&self) -> SearchMode {
+        if self.semantic_state.is_some() {
+            SearchMode::Semantic
+        } else if self.search_options.contains(SearchOptions::REGEX) {
+            SearchMode::Regex
+        } else {
+            SearchMode::Text
+        }
+    }
+
+    fn activate_search_mode(&mut self, mode: SearchMode, cx: &mut Context<Self>) {
+        let semantic_state = if mode == SearchMode::Semantic {
+            match self.semantic_state.take() {
+                Some(semantic_state) => Some(semantic_state),
+                None => {
+                    let Some(semantic_state) = self.index_project(cx) else {
+                        return;
+                    };
+                    Some(semantic_state)
+                }
+            }
+        } else {
+            None
+        };
+        if self.search_options.contains(SearchOptions::REGEX) != (mode == SearchMode::Regex) {
+            self.toggle_search_option(SearchOptions::REGEX, cx);
+        }
+        self.semantic_state = semantic_state;
+        cx.notify();
+    }
+
+    fn index_project(&self, cx: &mut Context<Self>) -> Option<SemanticState> {
+        if !cx.has_global::<SemanticDb>() {
+            return None;
+        }
+        let project = self.entity.read(cx).project.clone();
+        let index = cx.update_global::<SemanticDb, _>(|semantic_db, cx| {
+            semantic_db
+                .project_index(project.clone(), cx)
+                .unwrap_or_else(|| semantic_db.create_project_index(project, cx))
+        });
+        let subscription = cx.subscribe(&index, |this, _, status: &semantic_index::Status, cx| {
+            if let Some(semantic_state) = this.semantic_state.as_mut() {
+                semantic_state.index_status = *status;
+                if *status == semantic_index::Status::Idle && semantic_state.search_when_indexed {
+                    semantic_state.search_when_indexed = false;
+                    this.search(cx);
+                }
+                cx.notify();
+            }
+        });
+        Some(SemanticState {
+            index_status: index.read(cx).status(),
+            index,
+            search_when_indexed: false,
+            _subscription: subscription,
+        })
+    }
+
     fn toggle_opened_only(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {
         self.included_opened_only = !self.included_opened_only;
     }
@@ -885,6 +1084,7 @@ impl ProjectSearchView {
             replace_enabled: false,
             included_opened_only: false,
             regex_language: None,
+            semantic_state: None,
             _subscriptions: subscriptions,
             query_error: None,
         };
@@ -1126,7 +1326,16 @@ impl ProjectSearchView {
     }
 
     fn search(&mut self, cx: &mut Context<Self>) {
-        if let Some(query) = self.build_search_query(cx) {
+        let query = self.search_query_text(cx);
+        if let Some(semantic_state) = self.semantic_state.as_mut() {
+            if !query.is_empty() {
+                semantic_state.search_when_indexed =
+                    semantic_state.index_status != semantic_index::Status::Idle;
+                let index = semantic_state.index.clone();
+                self.entity
+                    .update(cx, |model, cx| model.semantic_search(query, index, cx));
+            }
+        } else if let Some(query) = self.build_search_query(cx) {
             self.entity.update(cx, |model, cx| model.search(query, cx));
         }
     }
@@ -1704,6 +1913,51 @@ impl ProjectSearchBar {
         true
     }
 
+    fn activate_search_mode(
+        &mut self,
+        mode: SearchMode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if self.active_project_search.is_none() {
+            return false;
+        }
+
+        cx.spawn_in(window, async move |this, cx| {
+            let task = this.update_in(cx, |this, window, cx| {
+                let search_view = this.active_project_search.as_ref()?;
+                search_view.update(cx, |search_view, cx| {
+                    search_view.activate_search_mode(mode, cx);
+                    search_view
+                        .entity
+                        .read(cx)
+                        .last_search_query_text
+                        .is_some()
+                        .then(|| search_view.prompt_to_save_if_dirty_then_search(window, cx))
+                })
+            })?;
+            if let Some(task) = task {
+                task.await?;
+            }
+            this.update(cx, |_, cx| {
+                cx.notify();
+            })?;
+            anyhow::Ok(())
+        })
+        .detach();
+        true
+    }
+
+    fn cycle_mode(&mut self, _: &CycleMode, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(search_view) = self.active_project_search.as_ref() {
+            let mode = mode::next_mode(
+                &search_view.read(cx).current_mode(),
+                cx.has_global::<SemanticDb>(),
+            );
+            self.activate_search_mode(mode, window, cx);
+        }
+    }
+
     fn toggle_replace(&mut self, _: &ToggleReplace, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(search) = &self.active_project_search {
             search.update(cx, |this, cx| {
@@ -2069,6 +2323,40 @@ impl Render for ProjectSearchBar {
                     }),
             );
 
+        let semantic_modes = cx.has_global::<SemanticDb>().then(|| {
+            let current_mode = search.current_mode();
+            h_flex()
+                .gap_0p5()
+                .children(
+                    [SearchMode::Text, SearchMode::Regex, SearchMode::Semantic].map(|mode| {
+                        Button::new(mode.label(), mode.label())
+                            .label_size(LabelSize::Small)
+                            .toggle_state(mode == current_mode)
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.activate_search_mode(mode, window, cx);
+                            }))
+                            .tooltip({
+                                let focus_handle = focus_handle.clone();
+                                move |window, cx| {
+                                    Tooltip::for_action_in(
+                                        mode.tooltip(),
+                                        &*mode.action(),
+                                        &focus_handle,
+                                        window,
+                                        cx,
+                                    )
+                                }
+                            })
+                    }),
+                )
+                .children(search.semantic_state.as_ref().map(|semantic_state| {
+                    Label::new(index_status_text(semantic_state.index_status))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .ml_1()
+                }))
+        });
+
         let limit_reached = search.entity.read(cx).limit_reached;
 
         let match_text = search
@@ -2164,6 +2452,7 @@ impl Render for ProjectSearchBar {
             .w_full()
             .gap_2()
             .child(query_column)
+            .children(semantic_modes)
             .child(h_flex().min_w_64().child(mode_column).child(matches_column));
 
         let replace_line = search.replace_enabled.then(|| {
@@ -2339,6 +2628,7 @@ impl Render for ProjectSearchBar {
             .on_action(cx.listener(|this, _: &ToggleCaseSensitive, window, cx| {
                 this.toggle_search_option(SearchOptions::CASE_SENSITIVE, window, cx);
             }))
+            .on_action(cx.listener(Self::cycle_mode))
             .on_action(cx.listener(|this, action, window, cx| {
                 if let Some(search) = this.active_project_search.as_ref() {
                     search.update(cx, |this, cx| {
@@ -2369,6 +2659,16 @@ impl Render for ProjectSearchBar {
     }
 }
 
+fn index_status_text(status: semantic_index::Status) -> SharedString {
+    match status {
+        semantic_index::Status::Idle => "Indexed".into(),
+        semantic_index::Status::Loading => "Loading index…".into(),
+        semantic_index::Status::Scanning { remaining_count } => {
+            format!("Indexing {remaining_count} files…").into()
+        }
+    }
+}
+
 impl EventEmitter<ToolbarItemEvent> for ProjectSearchBar {}
 
 impl ToolbarItemView for ProjectSearchBar {
@@ -4251,6 +4551,101 @@ pub mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_semantic_search(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+        init_test(cx);
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let semantic_db = SemanticDb::new(
+            temp_dir.path().into(),
+            Arc::new(semantic_index::FakeEmbeddingProvider),
+            &mut cx.to_async(),
+        )
+        .await
+        .unwrap();
+        cx.update(|cx| cx.set_global(semantic_db));
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "one.rs": "const ONE: usize = 1;",
+                "two.rs": "const TWO: usize = one::ONE + one::ONE;",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+        let search = cx.new(|cx| ProjectSearch::new(project.clone(), cx));
+        let search_view = cx.add_window(|window, cx| {
+            ProjectSearchView::new(workspace.downgrade(), search.clone(), window, cx, None)
+        });
+
+        // Searching before the index is loaded runs the search again once indexing is done.
+        search_view
+            .update(cx, |search_view, window, cx| {
+                search_view.activate_search_mode(SearchMode::Semantic, cx);
+                assert_eq!(search_view.current_mode(), SearchMode::Semantic);
+                let semantic_state = search_view.semantic_state.as_ref().unwrap();
+                assert_eq!(semantic_state.index_status, semantic_index::Status::Loading);
+
+                search_view.query_editor.update(cx, |query_editor, cx| {
+                    query_editor.set_text("a constant", window, cx)
+                });
+                search_view.search(cx);
+                assert!(
+                    search_view
+                        .semantic_state
+                        .as_ref()
+                        .unwrap()
+                        .search_when_indexed
+                );
+            })
+            .unwrap();
+        let search_id_before_indexing = search.read_with(cx, |search, _| search.search_id);
+        cx.background_executor.run_until_parked();
+
+        search_view
+            .update(cx, |search_view, _, cx| {
+                let semantic_state = search_view.semantic_state.as_ref().unwrap();
+                assert_eq!(semantic_state.index_status, semantic_index::Status::Idle);
+                assert!(!semantic_state.search_when_indexed);
+                assert!(search.read(cx).search_id > search_id_before_indexing);
+                assert_eq!(search.read(cx).no_results, Some(false));
+                assert_eq!(search.read(cx).match_ranges.len(), 2);
+
+                let results_text = search_view
+                    .results_editor
+                    .update(cx, |editor, cx| editor.display_text(cx));
+                assert!(results_text.contains("const ONE: usize = 1;"));
+                assert!(results_text.contains("const TWO: usize = one::ONE + one::ONE;"));
+            })
+            .unwrap();
+
+        // Once the index is ready, searching doesn't wait for it.
+        search_view
+            .update(cx, |search_view, _, cx| {
+                search_view.search(cx);
+                assert!(
+                    !search_view
+                        .semantic_state
+                        .as_ref()
+                        .unwrap()
+                        .search_when_indexed
+                );
+            })
+            .unwrap();
+        cx.background_executor.run_until_parked();
+        search_view
+            .update(cx, |search_view, _, cx| {
+                assert!(search_view.has_matches());
+                assert_eq!(search.read(cx).match_ranges.len(), 2);
+            })
+            .unwrap();
+    }
+
     fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings = SettingsStore::test(cx);
@@ -71,11 +71,10 @@ fn syntactic_ranges(
         .split('\n')
         .map({
             let mut offset = 0;
-            move |line| {
-                let line = line.trim_start();
+            move |line: &str| {
                 let is_comment = line_comment_prefixes
                     .iter()
-                    .any(|prefix| line.starts_with(prefix.as_ref()));
+                    .any(|prefix| line.trim_start().starts_with(prefix.as_ref()));
                 let result = RowInfo { offset, is_comment };
                 offset += line.len() + 1;
                 result
@@ -180,6 +179,24 @@ fn chunk_text_with_syntactic_ranges(
             }
         }
 
+        // Always end the chunk before a top-level outline item once it is big enough,
+        // so that editing one item doesn't shift the boundaries of the chunks after it,
+        // and their embeddings can be reused.
+        if nesting_depth == 0
+            && line_ix - range.start >= size_config.min
+            && starts_outline_item(text, line_ix, syntactic_ranges)
+        {
+            range.end = line_ix;
+            chunks.push(Chunk {
+                range: range.clone(),
+                digest: Sha256::digest(&text[range.clone()]).into(),
+            });
+            range_end_nesting_depth = 0;
+            range.start = range.end;
+            line_ixs.next();
+            continue;
+        }
+
         // Extend the current range to this position, unless an earlier candidate
         // end position was less nested syntactically.
         if range.len() < size_config.min || nesting_depth <= range_end_nesting_depth {
@@ -200,6 +217,18 @@ fn chunk_text_with_syntactic_ranges(
     chunks
 }
 
+fn starts_outline_item(text: &str, line_ix: usize, syntactic_ranges: &[Range<usize>]) -> bool {
+    let line_end = text[line_ix..]
+        .find('\n')
+        .map_or(text.len(), |ix| line_ix + ix);
+    // The ranges are sorted by their start, so the first range starting at or after this line
+    // is the only one to check.
+    let ix = syntactic_ranges.partition_point(|range| range.start < line_ix);
+    syntactic_ranges
+        .get(ix)
+        .is_some_and(|range| range.start <= line_end)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -331,6 +360,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_chunk_boundaries_follow_outline_items() {
+        let language = rust_language();
+
+        let text = "
+            fn first() {
+                let a = 1;
+            }
+
+            fn second() {
+                let b = 2;
+            }
+
+            fn third() {
+                let c = 3;
+            }
+        "
+        .unindent();
+        let size_range = ChunkSizeRange {
+            min: 16,
+            max: text.len(),
+        };
+
+        let chunks =
+            chunk_text_with_size_range(&text, Some(&language), Path::new("lib.rs"), size_range);
+        assert_chunks(&text, &chunks, &["fn first", "fn second", "fn third"]);
+
+        // Growing the first function leaves the chunks after it untouched.
+        let edited_text = text.replace("let a = 1;", "let a = 1;\n    let b = 2;");
+        let edited_chunks = chunk_text_with_size_range(
+            &edited_text,
+            Some(&language),
+            Path::new("lib.rs"),
+            size_range,
+        );
+        assert_chunks(
+            &edited_text,
+            &edited_chunks,
+            &["fn first", "fn second", "fn third"],
+        );
+        assert_ne!(chunks[0].digest, edited_chunks[0].digest);
+        assert_eq!(
+            chunks[1..]
+                .iter()
+                .map(|chunk| chunk.digest)
+                .collect::<Vec<_>>(),
+            edited_chunks[1..]
+                .iter()
+                .map(|chunk| chunk.digest)
+                .collect::<Vec<_>>()
+        );
+    }
+
     #[track_caller]
     fn assert_chunks(text: &str, chunks: &[Chunk], expected_chunk_text_prefixes: &[&str]) {
         check_chunk_invariants(text, chunks);
@@ -4,8 +4,7 @@ use crate::{
     indexing::{IndexingEntryHandle, IndexingEntrySet},
 };
 use anyhow::{Context as _, Result};
-use collections::Bound;
-use feature_flags::FeatureFlagAppExt;
+use collections::{Bound, HashMap};
 use fs::Fs;
 use fs::MTime;
 use futures::{FutureExt as _, stream::StreamExt};
@@ -60,10 +59,6 @@ impl EmbeddingIndex {
         &self,
         cx: &App,
     ) -> impl Future<Output = Result<()>> + use<> {
-        if !cx.is_staff() {
-            return async move { Ok(()) }.boxed();
-        }
-
         let worktree = self.worktree.read(cx).snapshot();
         let worktree_abs_path = worktree.abs_path().clone();
         let scan = self.scan_entries(worktree, cx);
@@ -82,10 +77,6 @@ impl EmbeddingIndex {
         updated_entries: UpdatedEntriesSet,
         cx: &App,
     ) -> impl Future<Output = Result<()>> + use<> {
-        if !cx.is_staff() {
-            return async move { Ok(()) }.boxed();
-        }
-
         let worktree = self.worktree.read(cx).snapshot();
         let worktree_abs_path = worktree.abs_path().clone();
         let scan = self.scan_updated_entries(worktree, updated_entries.clone(), cx);
@@ -231,6 +222,8 @@ impl EmbeddingIndex {
     ) -> ChunkFiles {
         let language_registry = self.language_registry.clone();
         let fs = self.fs.clone();
+        let db_connection = self.db_connection.clone();
+        let db = self.db;
         let (chunked_files_tx, chunked_files_rx) = channel::bounded(2048);
         let task = cx.spawn(async move |cx| {
             cx.background_executor()
@@ -244,12 +237,17 @@ impl EmbeddingIndex {
                                         .language_for_file_path(&entry.path)
                                         .await
                                         .ok();
+                                    let cached_embeddings =
+                                        cached_embeddings(&db_connection, db, &entry.path)
+                                            .log_err()
+                                            .unwrap_or_default();
                                     let chunked_file = ChunkedFile {
                                         chunks: chunking::chunk_text(
                                             &text,
                                             language.as_ref(),
                                             &entry.path,
                                         ),
+                                        cached_embeddings,
                                         handle,
                                         path: entry.path,
                                         mtime: entry.mtime,
@@ -289,16 +287,29 @@ impl EmbeddingIndex {
                 // Flatten out to a vec of chunks that we can subdivide into batch sized pieces
                 // Once those are done, reassemble them back into the files in which they belong
                 // If any embeddings fail for a file, the entire file is discarded
+                // Chunks whose content was already embedded reuse that embedding
 
                 let chunks: Vec<TextToEmbed> = chunked_files
                     .iter()
                     .flat_map(|file| {
-                        file.chunks.iter().map(|chunk| TextToEmbed {
-                            text: &file.text[chunk.range.clone()],
-                            digest: chunk.digest,
-                        })
+                        file.chunks
+                            .iter()
+                            .filter(|chunk| !file.cached_embeddings.contains_key(&chunk.digest))
+                            .map(|chunk| TextToEmbed {
+                                text: &file.text[chunk.range.clone()],
+                                digest: chunk.digest,
+                            })
                     })
                     .collect::<Vec<_>>();
+                log::debug!(
+                    "embedding {} chunks, reusing {}",
+                    chunks.len(),
+                    chunked_files
+                        .iter()
+                        .map(|file| file.chunks.len())
+                        .sum::<usize>()
+                        - chunks.len()
+                );
 
                 let mut embeddings: Vec<Option<Embedding>> = Vec::new();
                 for embedding_batch in chunks.chunks(embedding_provider.batch_size()) {
@@ -327,9 +338,11 @@ impl EmbeddingIndex {
                     };
 
                     let mut embedded_all_chunks = true;
-                    for (chunk, embedding) in
-                        chunked_file.chunks.into_iter().zip(embeddings.by_ref())
-                    {
+                    for chunk in chunked_file.chunks {
+                        let embedding = match chunked_file.cached_embeddings.get(&chunk.digest) {
+                            Some(embedding) => Some(embedding.clone()),
+                            None => embeddings.next().flatten(),
+                        };
                         if let Some(embedding) = embedding {
                             embedded_file
                                 .chunks
@@ -446,6 +459,8 @@ pub struct ChunkedFile {
     pub handle: IndexingEntryHandle,
     pub text: String,
     pub chunks: Vec<Chunk>,
+    /// Embeddings of the chunks this file had when it was last indexed, keyed by their digest.
+    pub cached_embeddings: HashMap<[u8; 32], Embedding>,
 }
 
 pub struct EmbedFiles {
@@ -466,6 +481,24 @@ pub struct EmbeddedChunk {
     pub embedding: Embedding,
 }
 
+fn cached_embeddings(
+    db_connection: &heed::Env,
+    db: heed::Database<Str, SerdeBincode<EmbeddedFile>>,
+    path: &Arc<Path>,
+) -> Result<HashMap<[u8; 32], Embedding>> {
+    let txn = db_connection
+        .read_txn()
+        .context("failed to create read transaction")?;
+    let Some(embedded_file) = db.get(&txn, &db_key_for_path(path))? else {
+        return Ok(HashMap::default());
+    };
+    Ok(embedded_file
+        .chunks
+        .into_iter()
+        .map(|embedded_chunk| (embedded_chunk.chunk.digest, embedded_chunk.embedding))
+        .collect())
+}
+
 fn db_key_for_path(path: &Arc<Path>) -> String {
     path.to_string_lossy().replace('/', "\0")
 }
@@ -4,16 +4,20 @@ mod embedding_index;
 mod indexing;
 mod project_index;
 mod project_index_debug_view;
+mod semantic_index_settings;
 mod summary_backlog;
 mod summary_index;
 mod worktree_index;
 
 use anyhow::{Context as _, Result};
 use collections::HashMap;
+use credentials_provider::CredentialsProvider;
 use fs::Fs;
 use gpui::{App, AppContext as _, AsyncApp, BorrowAppContext, Context, Entity, Global, WeakEntity};
+use http_client::HttpClient;
 use language::LineEnding;
 use project::{Project, Worktree};
+use settings::{Settings as _, SettingsStore};
 use std::{
     cmp::Ordering,
     path::{Path, PathBuf},
@@ -25,8 +29,111 @@ use workspace::Workspace;
 pub use embedding::*;
 pub use project_index::{LoadedSearchResult, ProjectIndex, SearchResult, Status};
 pub use project_index_debug_view::ProjectIndexDebugView;
+pub use semantic_index_settings::{EmbeddingProviderKind, SemanticIndexSettings};
 pub use summary_index::FileSummary;
 
+const OPENAI_API_KEY_VAR: &str = "OPENAI_API_KEY";
+
+/// Indexes the projects of every workspace while the semantic index is enabled in the settings.
+pub fn init(http_client: Arc<dyn HttpClient>, cx: &mut App) {
+    SemanticIndexSettings::register(cx);
+
+    cx.observe_new(
+        |workspace: &mut Workspace, _window, cx: &mut Context<Workspace>| {
+            let project = workspace.project().clone();
+
+            if cx.has_global::<SemanticDb>() {
+                cx.update_global::<SemanticDb, _>(|this, cx| {
+                    this.create_project_index(project, cx);
+                })
+            } else {
+                log::info!("No SemanticDb, skipping project index")
+            }
+        },
+    )
+    .detach();
+
+    let mut active_provider = None;
+    update_semantic_db(&http_client, &mut active_provider, cx);
+    cx.observe_global::<SettingsStore>(move |cx| {
+        update_semantic_db(&http_client, &mut active_provider, cx);
+    })
+    .detach();
+}
+
+fn update_semantic_db(
+    http_client: &Arc<dyn HttpClient>,
+    active_provider: &mut Option<EmbeddingProviderKind>,
+    cx: &mut App,
+) {
+    let settings = SemanticIndexSettings::get_global(cx);
+    let provider = settings.enabled.then_some(settings.embedding_provider);
+    if provider == *active_provider {
+        return;
+    }
+    *active_provider = provider;
+
+    if cx.has_global::<SemanticDb>() {
+        cx.remove_global::<SemanticDb>();
+    }
+    let Some(provider) = provider else {
+        return;
+    };
+
+    let http_client = http_client.clone();
+    let credentials_provider = <dyn CredentialsProvider>::global(cx);
+    cx.spawn(async move |cx| {
+        // Each provider embeds into a different vector space, so they can't share a database.
+        let (embedding_provider, db_name): (Arc<dyn EmbeddingProvider>, _) = match provider {
+            EmbeddingProviderKind::Ollama => (
+                Arc::new(OllamaEmbeddingProvider::new(
+                    http_client,
+                    OllamaEmbeddingModel::NomicEmbedText,
+                )),
+                "ollama",
+            ),
+            EmbeddingProviderKind::LmStudio => (
+                Arc::new(LmStudioEmbeddingProvider::new(
+                    http_client,
+                    LmStudioEmbeddingModel::NomicEmbedText,
+                )),
+                "lm-studio",
+            ),
+            EmbeddingProviderKind::OpenAi => {
+                let api_key = if let Ok(api_key) = std::env::var(OPENAI_API_KEY_VAR) {
+                    api_key
+                } else {
+                    let (_, api_key) = credentials_provider
+                        .read_credentials(open_ai::OPEN_AI_API_URL, cx)
+                        .await?
+                        .context("no OpenAI API key is configured")?;
+                    String::from_utf8(api_key).context("invalid OpenAI API key")?
+                };
+                (
+                    Arc::new(OpenAiEmbeddingProvider::new(
+                        http_client,
+                        OpenAiEmbeddingModel::TextEmbedding3Small,
+                        open_ai::OPEN_AI_API_URL.to_string(),
+                        api_key,
+                    )),
+                    "open-ai",
+                )
+            }
+        };
+
+        let db_path = paths::embeddings_dir().join(format!("semantic-index-{db_name}.0.mdb"));
+        let semantic_db = SemanticDb::new(db_path, embedding_provider, cx).await?;
+        cx.update(|cx| {
+            // The settings may have changed while the database was opening.
+            let settings = SemanticIndexSettings::get_global(cx);
+            if settings.enabled && settings.embedding_provider == provider {
+                cx.set_global(semantic_db);
+            }
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
 pub struct SemanticDb {
     embedding_provider: Arc<dyn EmbeddingProvider>,
     db_connection: Option<heed::Env>,
@@ -54,24 +161,6 @@ impl SemanticDb {
             .await
             .context("opening database connection")?;
 
-        cx.update(|cx| {
-            cx.observe_new(
-                |workspace: &mut Workspace, _window, cx: &mut Context<Workspace>| {
-                    let project = workspace.project().clone();
-
-                    if cx.has_global::<SemanticDb>() {
-                        cx.update_global::<SemanticDb, _>(|this, cx| {
-                            this.create_project_index(project, cx);
-                        })
-                    } else {
-                        log::info!("No SemanticDb, skipping project index")
-                    }
-                },
-            )
-            .detach();
-        })
-        .ok();
-
         Ok(SemanticDb {
             db_connection: Some(db_connection),
             embedding_provider,
@@ -473,6 +562,7 @@ mod tests {
                         digest: Default::default(),
                     })
                     .collect(),
+                cached_embeddings: HashMap::default(),
             })
             .unwrap();
         chunked_files_tx
@@ -488,6 +578,28 @@ mod tests {
                         digest: Default::default(),
                     })
                     .collect(),
+                cached_embeddings: HashMap::default(),
+            })
+            .unwrap();
+        // The embedding of the chunk containing a 'g' is reused, so the provider isn't asked for it.
+        let cached_embedding = Embedding::new(vec![1.0; 26]);
+        chunked_files_tx
+            .send_blocking(ChunkedFile {
+                path: Path::new("test3.md").into(),
+                mtime: None,
+                handle: indexing_entries.insert(ProjectEntryId::from_proto(2)),
+                text: "abcdefgh".to_string(),
+                chunks: vec![
+                    Chunk {
+                        range: 0..4,
+                        digest: [0; 32],
+                    },
+                    Chunk {
+                        range: 4..8,
+                        digest: [1; 32],
+                    },
+                ],
+                cached_embeddings: HashMap::from_iter([([1; 32], cached_embedding.clone())]),
             })
             .unwrap();
         chunked_files_tx.close();
@@ -502,7 +614,7 @@ mod tests {
             embedded_files.push(embedded_file);
         }
 
-        assert_eq!(embedded_files.len(), 1);
+        assert_eq!(embedded_files.len(), 2);
         assert_eq!(embedded_files[0].path.as_ref(), Path::new("test2.md"));
         assert_eq!(
             embedded_files[0]
@@ -516,6 +628,18 @@ mod tests {
                 (provider.compute_embedding)("yz").unwrap(),
             ],
         );
+        assert_eq!(embedded_files[1].path.as_ref(), Path::new("test3.md"));
+        assert_eq!(
+            embedded_files[1]
+                .chunks
+                .iter()
+                .map(|embedded_chunk| embedded_chunk.embedding.clone())
+                .collect::<Vec<Embedding>>(),
+            vec![
+                (provider.compute_embedding)("abcd").unwrap(),
+                cached_embedding,
+            ],
+        );
     }
 
     #[gpui::test]
@@ -0,0 +1,44 @@
+use anyhow::Result;
+use gpui::App;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+/// Settings for the semantic index, which powers the semantic mode of project search.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SemanticIndexSettings {
+    /// Whether to index the files of your projects so they can be searched semantically.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub enabled: bool,
+    /// Which service computes the embeddings of the indexed files.
+    ///
+    /// Default: ollama
+    #[serde(default)]
+    pub embedding_provider: EmbeddingProviderKind,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbeddingProviderKind {
+    /// Embed with the `nomic-embed-text` model of a local Ollama server.
+    #[default]
+    Ollama,
+    /// Embed with the `nomic-embed-text` model of a local LM Studio server.
+    LmStudio,
+    /// Embed with OpenAI's `text-embedding-3-small` model.
+    OpenAi,
+}
+
+impl Settings for SemanticIndexSettings {
+    const KEY: Option<&'static str> = Some("semantic_index");
+
+    type FileContent = Self;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut App) -> Result<Self> {
+        sources.json_merge()
+    }
+
+    fn import_from_vscode(_vscode: &settings::VsCodeSettings, _current: &mut Self::FileContent) {}
+}
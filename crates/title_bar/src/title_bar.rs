@@ -298,12 +298,11 @@ impl TitleBar {
     }
 
     fn render_ssh_project_host(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
-        let options = self.project.read(cx).ssh_connection_options(cx)?;
+        let options = self.project.read(cx).remote_connection_options(cx)?;
         let host: SharedString = options.connection_string().into();
 
         let nickname = options
-            .nickname
-            .clone()
+            .nickname()
             .map(|nick| nick.into())
             .unwrap_or_else(|| host.clone());
 
@@ -13,11 +13,13 @@ use client::DevServerProjectId;
 use db::{define_connection, query, sqlez::connection::Connection, sqlez_macros::sql};
 use gpui::{Axis, Bounds, Task, WindowBounds, WindowId, point, size};
 use itertools::Itertools;
-use project::debugger::breakpoint_store::{BreakpointState, SourceBreakpoint};
+use project::debugger::breakpoint_store::{
+    BreakpointState, DataBreakpoint, FunctionBreakpoint, SourceBreakpoint,
+};
 
 use language::{LanguageName, Toolchain};
 use project::WorktreeId;
-use remote::ssh_session::SshProjectId;
+use remote::{ContainerRuntime, DevContainerProjectId, ssh_session::SshProjectId};
 use sqlez::{
     bindable::{Bind, Column, StaticColumnCount},
     statement::{SqlType, Statement},
@@ -31,8 +33,8 @@ use uuid::Uuid;
 use crate::WorkspaceId;
 
 use model::{
-    GroupId, ItemId, LocalPaths, PaneId, SerializedItem, SerializedPane, SerializedPaneGroup,
-    SerializedSshProject, SerializedWorkspace,
+    GroupId, ItemId, LocalPaths, PaneId, SerializedDevContainerProject, SerializedItem,
+    SerializedPane, SerializedPaneGroup, SerializedSshProject, SerializedWorkspace,
 };
 
 use self::model::{DockStructure, LocalPathsOrder, SerializedWorkspaceLocation};
@@ -186,6 +188,39 @@ impl Column for BreakpointStateWrapper<'_> {
     }
 }
 
+/// Wrapper for DB type of a data breakpoint's access type
+struct DataBreakpointAccessTypeWrapper(dap::DataBreakpointAccessType);
+
+impl StaticColumnCount for DataBreakpointAccessTypeWrapper {}
+
+impl Bind for DataBreakpointAccessTypeWrapper {
+    fn bind(&self, statement: &Statement, start_index: i32) -> anyhow::Result<i32> {
+        let discriminant: i32 = match self.0 {
+            dap::DataBreakpointAccessType::Read => 0,
+            dap::DataBreakpointAccessType::Write => 1,
+            dap::DataBreakpointAccessType::ReadWrite => 2,
+        };
+        statement.bind(&discriminant, start_index)
+    }
+}
+
+impl Column for DataBreakpointAccessTypeWrapper {
+    fn column(statement: &mut Statement, start_index: i32) -> anyhow::Result<(Self, i32)> {
+        let access_type = statement.column_int(start_index)?;
+
+        let access_type = match access_type {
+            0 => dap::DataBreakpointAccessType::Read,
+            1 => dap::DataBreakpointAccessType::Write,
+            2 => dap::DataBreakpointAccessType::ReadWrite,
+            _ => anyhow::bail!("Invalid DataBreakpointAccessType discriminant {access_type}"),
+        };
+        Ok((
+            DataBreakpointAccessTypeWrapper(access_type),
+            start_index + 1,
+        ))
+    }
+}
+
 /// This struct is used to implement traits on Vec<breakpoint>
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -542,6 +577,39 @@ define_connection! {
         ALTER TABLE breakpoints ADD COLUMN condition TEXT;
         ALTER TABLE breakpoints ADD COLUMN hit_condition TEXT;
     ),
+    sql!(
+        CREATE TABLE function_breakpoints (
+            workspace_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            condition TEXT,
+            hit_condition TEXT,
+            state INTEGER DEFAULT(0) NOT NULL,
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        );
+        CREATE TABLE data_breakpoints (
+            workspace_id INTEGER NOT NULL,
+            data_id TEXT NOT NULL,
+            description TEXT NOT NULL,
+            access_type INTEGER,
+            condition TEXT,
+            hit_condition TEXT,
+            state INTEGER DEFAULT(0) NOT NULL,
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        );
+    ),
+    sql!(
+        CREATE TABLE dev_container_projects (
+            id INTEGER PRIMARY KEY,
+            project_path TEXT NOT NULL,
+            runtime TEXT NOT NULL,
+            paths TEXT NOT NULL
+        );
+        ALTER TABLE workspaces ADD COLUMN dev_container_project_id INTEGER REFERENCES dev_container_projects(id) ON DELETE CASCADE;
+    ),
     ];
 }
 
@@ -630,6 +698,8 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            function_breakpoints: self.function_breakpoints(workspace_id),
+            data_breakpoints: self.data_breakpoints(workspace_id),
             window_id,
         })
     }
@@ -684,6 +754,67 @@ impl WorkspaceDb {
             window_bounds,
             centered_layout: centered_layout.unwrap_or(false),
             breakpoints: self.breakpoints(workspace_id),
+            function_breakpoints: self.function_breakpoints(workspace_id),
+            data_breakpoints: self.data_breakpoints(workspace_id),
+            display,
+            docks,
+            session_id: None,
+            window_id,
+        })
+    }
+
+    pub(crate) fn workspace_for_dev_container_project(
+        &self,
+        dev_container_project: &SerializedDevContainerProject,
+    ) -> Option<SerializedWorkspace> {
+        let (workspace_id, window_bounds, display, centered_layout, docks, window_id): (
+            WorkspaceId,
+            Option<SerializedWindowBounds>,
+            Option<Uuid>,
+            Option<bool>,
+            DockStructure,
+            Option<u64>,
+        ) = self
+            .select_row_bound(sql! {
+                SELECT
+                    workspace_id,
+                    window_state,
+                    window_x,
+                    window_y,
+                    window_width,
+                    window_height,
+                    display,
+                    centered_layout,
+                    left_dock_visible,
+                    left_dock_active_panel,
+                    left_dock_zoom,
+                    right_dock_visible,
+                    right_dock_active_panel,
+                    right_dock_zoom,
+                    bottom_dock_visible,
+                    bottom_dock_active_panel,
+                    bottom_dock_zoom,
+                    window_id
+                FROM workspaces
+                WHERE dev_container_project_id = ?
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(dev_container_project.id.0))
+            .context("No workspaces found")
+            .warn_on_err()
+            .flatten()?;
+
+        Some(SerializedWorkspace {
+            id: workspace_id,
+            location: SerializedWorkspaceLocation::DevContainer(dev_container_project.clone()),
+            center_group: self
+                .get_center_pane_group(workspace_id)
+                .context("Getting center group")
+                .log_err()?,
+            window_bounds,
+            centered_layout: centered_layout.unwrap_or(false),
+            breakpoints: self.breakpoints(workspace_id),
+            function_breakpoints: self.function_breakpoints(workspace_id),
+            data_breakpoints: self.data_breakpoints(workspace_id),
             display,
             docks,
             session_id: None,
@@ -737,6 +868,85 @@ impl WorkspaceDb {
         }
     }
 
+    fn function_breakpoints(&self, workspace_id: WorkspaceId) -> Vec<FunctionBreakpoint> {
+        let breakpoints: Result<
+            Vec<(
+                String,
+                Option<String>,
+                Option<String>,
+                BreakpointStateWrapper<'static>,
+            )>,
+        > = self
+            .select_bound(sql! {
+                SELECT name, condition, hit_condition, state
+                FROM function_breakpoints
+                WHERE workspace_id = ?
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match breakpoints {
+            Ok(breakpoints) => breakpoints
+                .into_iter()
+                .map(
+                    |(name, condition, hit_condition, state)| FunctionBreakpoint {
+                        name: name.into(),
+                        condition: condition.map(Arc::from),
+                        hit_condition: hit_condition.map(Arc::from),
+                        state: state.0.into_owned(),
+                    },
+                )
+                .collect(),
+            Err(msg) => {
+                log::error!("Function breakpoints query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
+    fn data_breakpoints(&self, workspace_id: WorkspaceId) -> Vec<DataBreakpoint> {
+        let breakpoints: Result<
+            Vec<(
+                String,
+                String,
+                Option<DataBreakpointAccessTypeWrapper>,
+                Option<String>,
+                Option<String>,
+                BreakpointStateWrapper<'static>,
+            )>,
+        > = self
+            .select_bound(sql! {
+                SELECT data_id, description, access_type, condition, hit_condition, state
+                FROM data_breakpoints
+                WHERE workspace_id = ?
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match breakpoints {
+            Ok(breakpoints) => breakpoints
+                .into_iter()
+                .map(
+                    |(data_id, description, access_type, condition, hit_condition, state)| {
+                        DataBreakpoint {
+                            data_id: data_id.into(),
+                            description: description.into(),
+                            access_type: access_type.map(|access_type| access_type.0),
+                            // Only persistable data breakpoints are ever stored.
+                            can_persist: true,
+                            session_id: None,
+                            condition: condition.map(Arc::from),
+                            hit_condition: hit_condition.map(Arc::from),
+                            state: state.0.into_owned(),
+                        }
+                    },
+                )
+                .collect(),
+            Err(msg) => {
+                log::error!("Data breakpoints query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
     /// Saves a workspace using the worktree roots. Will garbage collect any workspaces
     /// that used this workspace previously
     pub(crate) async fn save_workspace(&self, workspace: SerializedWorkspace) {
@@ -779,6 +989,42 @@ impl WorkspaceDb {
 
                 }
 
+                conn.exec_bound(sql!(
+                    DELETE FROM function_breakpoints WHERE workspace_id = ?1;
+                    DELETE FROM data_breakpoints WHERE workspace_id = ?1;
+                ))?(workspace.id)
+                .context("Clearing old function and data breakpoints")?;
+
+                for bp in workspace.function_breakpoints {
+                    conn.exec_bound(sql!(
+                        INSERT INTO function_breakpoints (workspace_id, name, condition, hit_condition, state)
+                        VALUES (?1, ?2, ?3, ?4, ?5);
+                    ))?((
+                        workspace.id,
+                        bp.name,
+                        bp.condition,
+                        bp.hit_condition,
+                        BreakpointStateWrapper::from(bp.state),
+                    ))
+                    .log_err();
+                }
+
+                for bp in workspace.data_breakpoints {
+                    conn.exec_bound(sql!(
+                        INSERT INTO data_breakpoints (workspace_id, data_id, description, access_type, condition, hit_condition, state)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);
+                    ))?((
+                        workspace.id,
+                        bp.data_id,
+                        bp.description,
+                        bp.access_type.map(DataBreakpointAccessTypeWrapper),
+                        bp.condition,
+                        bp.hit_condition,
+                        BreakpointStateWrapper::from(bp.state),
+                    ))
+                    .log_err();
+                }
+
 
                 match workspace.location {
                     SerializedWorkspaceLocation::Local(local_paths, local_paths_order) => {
@@ -884,6 +1130,56 @@ impl WorkspaceDb {
                         ))
                         .context("Updating workspace")?;
                     }
+                    SerializedWorkspaceLocation::DevContainer(dev_container_project) => {
+                        conn.exec_bound(sql!(
+                            DELETE FROM toolchains WHERE workspace_id = ?1;
+                            DELETE FROM workspaces WHERE dev_container_project_id = ? AND workspace_id != ?
+                        ))?((dev_container_project.id.0, workspace.id))
+                        .context("clearing out old locations")?;
+
+                        // Upsert
+                        conn.exec_bound(sql!(
+                            INSERT INTO workspaces(
+                                workspace_id,
+                                dev_container_project_id,
+                                left_dock_visible,
+                                left_dock_active_panel,
+                                left_dock_zoom,
+                                right_dock_visible,
+                                right_dock_active_panel,
+                                right_dock_zoom,
+                                bottom_dock_visible,
+                                bottom_dock_active_panel,
+                                bottom_dock_zoom,
+                                session_id,
+                                window_id,
+                                timestamp
+                            )
+                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, CURRENT_TIMESTAMP)
+                            ON CONFLICT DO
+                            UPDATE SET
+                                dev_container_project_id = ?2,
+                                left_dock_visible = ?3,
+                                left_dock_active_panel = ?4,
+                                left_dock_zoom = ?5,
+                                right_dock_visible = ?6,
+                                right_dock_active_panel = ?7,
+                                right_dock_zoom = ?8,
+                                bottom_dock_visible = ?9,
+                                bottom_dock_active_panel = ?10,
+                                bottom_dock_zoom = ?11,
+                                session_id = ?12,
+                                window_id = ?13,
+                                timestamp = CURRENT_TIMESTAMP
+                        ))?((
+                            workspace.id,
+                            dev_container_project.id.0,
+                            workspace.docks,
+                            workspace.session_id,
+                            workspace.window_id
+                        ))
+                        .context("Updating workspace")?;
+                    }
                 }
 
                 // Save center pane group
@@ -939,6 +1235,47 @@ impl WorkspaceDb {
         }
     }
 
+    pub(crate) async fn get_or_create_dev_container_project(
+        &self,
+        project_path: String,
+        runtime: ContainerRuntime,
+        paths: Vec<String>,
+    ) -> Result<SerializedDevContainerProject> {
+        let paths = serde_json::to_string(&paths)?;
+        let runtime = runtime.command().to_string();
+        if let Some(project) = self
+            .get_dev_container_project(project_path.clone(), runtime.clone(), paths.clone())
+            .await?
+        {
+            Ok(project)
+        } else {
+            log::debug!("Inserting dev container project for {project_path}");
+            self.insert_dev_container_project(project_path, runtime, paths)
+                .await?
+                .context("failed to insert dev container project")
+        }
+    }
+
+    query! {
+        async fn get_dev_container_project(project_path: String, runtime: String, paths: String) -> Result<Option<SerializedDevContainerProject>> {
+            SELECT id, project_path, runtime, paths
+            FROM dev_container_projects
+            WHERE project_path IS ? AND runtime IS ? AND paths IS ?
+            LIMIT 1
+        }
+    }
+
+    query! {
+        async fn insert_dev_container_project(project_path: String, runtime: String, paths: String) -> Result<Option<SerializedDevContainerProject>> {
+            INSERT INTO dev_container_projects(
+                project_path,
+                runtime,
+                paths
+            ) VALUES (?1, ?2, ?3)
+            RETURNING id, project_path, runtime, paths
+        }
+    }
+
     query! {
         pub async fn next_id() -> Result<WorkspaceId> {
             INSERT INTO workspaces DEFAULT VALUES RETURNING workspace_id
@@ -946,18 +1283,19 @@ impl WorkspaceDb {
     }
 
     query! {
-        fn recent_workspaces() -> Result<Vec<(WorkspaceId, LocalPaths, LocalPathsOrder, Option<u64>)>> {
-            SELECT workspace_id, local_paths, local_paths_order, ssh_project_id
+        fn recent_workspaces() -> Result<Vec<(WorkspaceId, LocalPaths, LocalPathsOrder, Option<u64>, Option<u64>)>> {
+            SELECT workspace_id, local_paths, local_paths_order, ssh_project_id, dev_container_project_id
             FROM workspaces
             WHERE local_paths IS NOT NULL
                 OR ssh_project_id IS NOT NULL
+                OR dev_container_project_id IS NOT NULL
             ORDER BY timestamp DESC
         }
     }
 
     query! {
-        fn session_workspaces(session_id: String) -> Result<Vec<(LocalPaths, LocalPathsOrder, Option<u64>, Option<u64>)>> {
-            SELECT local_paths, local_paths_order, window_id, ssh_project_id
+        fn session_workspaces(session_id: String) -> Result<Vec<(LocalPaths, LocalPathsOrder, Option<u64>, Option<u64>, Option<u64>)>> {
+            SELECT local_paths, local_paths_order, window_id, ssh_project_id, dev_container_project_id
             FROM workspaces
             WHERE session_id = ?1 AND dev_server_project_id IS NULL
             ORDER BY timestamp DESC
@@ -994,6 +1332,21 @@ impl WorkspaceDb {
         }
     }
 
+    query! {
+        fn dev_container_projects() -> Result<Vec<SerializedDevContainerProject>> {
+            SELECT id, project_path, runtime, paths
+            FROM dev_container_projects
+        }
+    }
+
+    query! {
+        fn dev_container_project(id: u64) -> Result<SerializedDevContainerProject> {
+            SELECT id, project_path, runtime, paths
+            FROM dev_container_projects
+            WHERE id = ?
+        }
+    }
+
     pub(crate) fn last_window(
         &self,
     ) -> anyhow::Result<(Option<Uuid>, Option<SerializedWindowBounds>)> {
@@ -1045,8 +1398,11 @@ impl WorkspaceDb {
         let mut result = Vec::new();
         let mut delete_tasks = Vec::new();
         let ssh_projects = self.ssh_projects()?;
+        let dev_container_projects = self.dev_container_projects()?;
 
-        for (id, location, order, ssh_project_id) in self.recent_workspaces()? {
+        for (id, location, order, ssh_project_id, dev_container_project_id) in
+            self.recent_workspaces()?
+        {
             if let Some(ssh_project_id) = ssh_project_id.map(SshProjectId) {
                 if let Some(ssh_project) = ssh_projects.iter().find(|rp| rp.id == ssh_project_id) {
                     result.push((id, SerializedWorkspaceLocation::Ssh(ssh_project.clone())));
@@ -1056,6 +1412,24 @@ impl WorkspaceDb {
                 continue;
             }
 
+            if let Some(dev_container_project_id) =
+                dev_container_project_id.map(DevContainerProjectId)
+            {
+                if let Some(dev_container_project) = dev_container_projects
+                    .iter()
+                    .find(|project| project.id == dev_container_project_id)
+                    .filter(|project| Path::new(&project.project_path).is_dir())
+                {
+                    result.push((
+                        id,
+                        SerializedWorkspaceLocation::DevContainer(dev_container_project.clone()),
+                    ));
+                } else {
+                    delete_tasks.push(self.delete_workspace_by_id(id));
+                }
+                continue;
+            }
+
             if location.paths().iter().all(|path| path.exists())
                 && location.paths().iter().any(|path| path.is_dir())
             {
@@ -1089,12 +1463,17 @@ impl WorkspaceDb {
     ) -> Result<Vec<SerializedWorkspaceLocation>> {
         let mut workspaces = Vec::new();
 
-        for (location, order, window_id, ssh_project_id) in
+        for (location, order, window_id, ssh_project_id, dev_container_project_id) in
             self.session_workspaces(last_session_id.to_owned())?
         {
             if let Some(ssh_project_id) = ssh_project_id {
                 let location = SerializedWorkspaceLocation::Ssh(self.ssh_project(ssh_project_id)?);
                 workspaces.push((location, window_id.map(WindowId::from)));
+            } else if let Some(dev_container_project_id) = dev_container_project_id {
+                let location = SerializedWorkspaceLocation::DevContainer(
+                    self.dev_container_project(dev_container_project_id)?,
+                );
+                workspaces.push((location, window_id.map(WindowId::from)));
             } else if location.paths().iter().all(|path| path.exists())
                 && location.paths().iter().any(|path| path.is_dir())
             {
@@ -1570,6 +1949,8 @@ mod tests {
                 );
                 map
             },
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1649,6 +2030,72 @@ mod tests {
         assert_eq!(loaded_breakpoints[4].path, Arc::from(path));
     }
 
+    #[gpui::test]
+    async fn test_function_and_data_breakpoints() {
+        zlog::init_test();
+
+        let db = WorkspaceDb::open_test_db("test_function_and_data_breakpoints").await;
+        let id = db.next_id().await.unwrap();
+
+        let function_breakpoint = FunctionBreakpoint {
+            condition: Some("argc > 1".into()),
+            ..FunctionBreakpoint::new("main".into())
+        };
+        let disabled_function_breakpoint = FunctionBreakpoint {
+            hit_condition: Some(">= 3".into()),
+            state: BreakpointState::Disabled,
+            ..FunctionBreakpoint::new("helper".into())
+        };
+        let data_breakpoint = DataBreakpoint {
+            data_id: "0x7ffc0000:4".into(),
+            description: "counter".into(),
+            access_type: Some(dap::DataBreakpointAccessType::Write),
+            can_persist: true,
+            session_id: None,
+            condition: None,
+            hit_condition: None,
+            state: BreakpointState::Enabled,
+        };
+
+        let workspace = SerializedWorkspace {
+            id,
+            location: SerializedWorkspaceLocation::from_local_paths(["/tmp"]),
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            breakpoints: Default::default(),
+            function_breakpoints: vec![
+                function_breakpoint.clone(),
+                disabled_function_breakpoint.clone(),
+            ],
+            data_breakpoints: vec![data_breakpoint.clone()],
+            session_id: None,
+            window_id: None,
+        };
+
+        db.save_workspace(workspace.clone()).await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(
+            loaded.function_breakpoints,
+            vec![function_breakpoint, disabled_function_breakpoint]
+        );
+        assert_eq!(loaded.data_breakpoints, vec![data_breakpoint]);
+
+        db.save_workspace(SerializedWorkspace {
+            function_breakpoints: Vec::new(),
+            data_breakpoints: Vec::new(),
+            ..workspace
+        })
+        .await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert!(loaded.function_breakpoints.is_empty());
+        assert!(loaded.data_breakpoints.is_empty());
+    }
+
     #[gpui::test]
     async fn test_remove_last_breakpoint() {
         zlog::init_test();
@@ -1689,6 +2136,8 @@ mod tests {
                 );
                 map
             },
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1721,6 +2170,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: collections::BTreeMap::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1815,6 +2266,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1828,6 +2281,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1933,6 +2388,8 @@ mod tests {
             center_group,
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -1968,6 +2425,8 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -1984,6 +2443,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: Some(2),
         };
@@ -2024,6 +2485,8 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2064,6 +2527,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
         };
@@ -2077,6 +2542,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
         };
@@ -2090,6 +2557,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
         };
@@ -2103,6 +2572,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -2121,6 +2592,8 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
         };
@@ -2134,6 +2607,8 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2192,6 +2667,8 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             centered_layout: false,
             session_id: None,
             window_id: None,
@@ -2240,6 +2717,8 @@ mod tests {
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             window_id: Some(window_id),
         })
         .collect::<Vec<_>>();
@@ -2333,6 +2812,8 @@ mod tests {
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             window_id: Some(window_id),
         })
         .collect::<Vec<_>>();
@@ -2416,6 +2897,81 @@ mod tests {
         assert_eq!(different_project.user, user2);
     }
 
+    #[gpui::test]
+    async fn test_get_or_create_dev_container_project() {
+        let db = WorkspaceDb::open_test_db("test_get_or_create_dev_container_project").await;
+
+        let project_path = std::env::temp_dir().to_string_lossy().to_string();
+        let paths = vec!["/workspaces/project".to_string()];
+
+        let project = db
+            .get_or_create_dev_container_project(
+                project_path.clone(),
+                ContainerRuntime::Docker,
+                paths.clone(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(project.project_path, project_path);
+        assert_eq!(project.runtime, ContainerRuntime::Docker);
+        assert_eq!(project.paths, paths);
+
+        let same_project = db
+            .get_or_create_dev_container_project(
+                project_path.clone(),
+                ContainerRuntime::Docker,
+                paths.clone(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(project.id, same_project.id);
+
+        let podman_project = db
+            .get_or_create_dev_container_project(
+                project_path.clone(),
+                ContainerRuntime::Podman,
+                paths.clone(),
+            )
+            .await
+            .unwrap();
+        assert_ne!(project.id, podman_project.id);
+        assert_eq!(podman_project.runtime, ContainerRuntime::Podman);
+
+        let workspace = SerializedWorkspace {
+            id: WorkspaceId(1),
+            location: SerializedWorkspaceLocation::DevContainer(project.clone()),
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            breakpoints: Default::default(),
+            function_breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
+            session_id: Some("session-id".to_owned()),
+            window_id: Some(10),
+        };
+        db.save_workspace(workspace.clone()).await;
+
+        let restored = db.workspace_for_dev_container_project(&project).unwrap();
+        assert_eq!(restored.id, workspace.id);
+        assert_eq!(restored.location, workspace.location);
+        assert!(
+            db.workspace_for_dev_container_project(&podman_project)
+                .is_none()
+        );
+
+        assert_eq!(
+            db.recent_workspaces_on_disk().await.unwrap(),
+            vec![(workspace.id, workspace.location.clone())]
+        );
+        assert_eq!(
+            db.last_session_workspace_locations("session-id", None)
+                .unwrap(),
+            vec![workspace.location]
+        );
+    }
+
     #[gpui::test]
     async fn test_get_or_create_ssh_project_with_null_user() {
         let db = WorkspaceDb::open_test_db("test_get_or_create_ssh_project_with_null_user").await;
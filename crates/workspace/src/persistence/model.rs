@@ -10,8 +10,11 @@ use db::sqlez::{
 };
 use gpui::{AsyncWindowContext, Entity, WeakEntity};
 use itertools::Itertools as _;
-use project::{Project, debugger::breakpoint_store::SourceBreakpoint};
-use remote::ssh_session::SshProjectId;
+use project::{
+    Project,
+    debugger::breakpoint_store::{DataBreakpoint, FunctionBreakpoint, SourceBreakpoint},
+};
+use remote::{ContainerRuntime, DevContainerProjectId, ssh_session::SshProjectId};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
@@ -92,6 +95,54 @@ impl Column for SerializedSshProject {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SerializedDevContainerProject {
+    pub id: DevContainerProjectId,
+    /// The local project folder containing the `.devcontainer` configuration.
+    pub project_path: String,
+    pub runtime: ContainerRuntime,
+    /// The paths opened inside of the container.
+    pub paths: Vec<String>,
+}
+
+impl StaticColumnCount for SerializedDevContainerProject {
+    fn column_count() -> usize {
+        4
+    }
+}
+
+impl Bind for &SerializedDevContainerProject {
+    fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
+        let next_index = statement.bind(&self.id.0, start_index)?;
+        let next_index = statement.bind(&self.project_path, next_index)?;
+        let next_index = statement.bind(&self.runtime.command(), next_index)?;
+        let raw_paths = serde_json::to_string(&self.paths)?;
+        statement.bind(&raw_paths, next_index)
+    }
+}
+
+impl Column for SerializedDevContainerProject {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let id = statement.column_int64(start_index)?;
+        let project_path = statement.column_text(start_index + 1)?.to_string();
+        let raw_runtime = statement.column_text(start_index + 2)?;
+        let runtime = ContainerRuntime::from_command(raw_runtime)
+            .with_context(|| format!("unknown container runtime {raw_runtime:?}"))?;
+        let raw_paths = statement.column_text(start_index + 3)?.to_string();
+        let paths: Vec<String> = serde_json::from_str(&raw_paths)?;
+
+        Ok((
+            Self {
+                id: DevContainerProjectId(id as u64),
+                project_path,
+                runtime,
+                paths,
+            },
+            start_index + 4,
+        ))
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct LocalPaths(Arc<Vec<PathBuf>>);
 
@@ -173,6 +224,7 @@ impl Column for LocalPathsOrder {
 pub enum SerializedWorkspaceLocation {
     Local(LocalPaths, LocalPathsOrder),
     Ssh(SerializedSshProject),
+    DevContainer(SerializedDevContainerProject),
 }
 
 impl SerializedWorkspaceLocation {
@@ -250,6 +302,13 @@ impl SerializedWorkspaceLocation {
                 }
             }
             SerializedWorkspaceLocation::Ssh(ssh_project) => Arc::new(ssh_project.ssh_urls()),
+            SerializedWorkspaceLocation::DevContainer(dev_container_project) => Arc::new(
+                dev_container_project
+                    .paths
+                    .iter()
+                    .map(PathBuf::from)
+                    .collect(),
+            ),
         }
     }
 }
@@ -265,6 +324,8 @@ pub(crate) struct SerializedWorkspace {
     pub(crate) docks: DockStructure,
     pub(crate) session_id: Option<String>,
     pub(crate) breakpoints: BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>,
+    pub(crate) function_breakpoints: Vec<FunctionBreakpoint>,
+    pub(crate) data_breakpoints: Vec<DataBreakpoint>,
     pub(crate) window_id: Option<u64>,
 }
 
@@ -59,7 +59,7 @@ pub use pane::*;
 pub use pane_group::*;
 use persistence::{
     DB, SerializedWindowBounds,
-    model::{SerializedSshProject, SerializedWorkspace},
+    model::{SerializedDevContainerProject, SerializedSshProject, SerializedWorkspace},
 };
 pub use persistence::{
     DB as WORKSPACE_DB, WorkspaceDb, delete_unloaded_items,
@@ -70,7 +70,10 @@ use project::{
     DirectoryLister, Project, ProjectEntryId, ProjectPath, ResolvedPath, Worktree, WorktreeId,
     debugger::{breakpoint_store::BreakpointStoreEvent, session::ThreadStatus},
 };
-use remote::{SshClientDelegate, SshConnectionOptions, ssh_session::ConnectionIdentifier};
+use remote::{
+    DevContainerConnectionOptions, RemoteConnectionOptions, SshClientDelegate,
+    SshConnectionOptions, ssh_session::ConnectionIdentifier,
+};
 use schemars::JsonSchema;
 use serde::Deserialize;
 use session::AppSession;
@@ -1086,6 +1089,7 @@ pub struct Workspace {
     debugger_provider: Option<Arc<dyn DebuggerProvider>>,
     serializable_items_tx: UnboundedSender<Box<dyn SerializableItemHandle>>,
     serialized_ssh_project: Option<SerializedSshProject>,
+    serialized_dev_container_project: Option<SerializedDevContainerProject>,
     _items_serializer: Task<Result<()>>,
     session_id: Option<String>,
 }
@@ -1210,7 +1214,9 @@ impl Workspace {
             window,
             |workspace, _, event, window, cx| match event {
                 BreakpointStoreEvent::BreakpointsUpdated(_, _)
-                | BreakpointStoreEvent::BreakpointsCleared(_) => {
+                | BreakpointStoreEvent::BreakpointsCleared(_)
+                | BreakpointStoreEvent::FunctionBreakpointsUpdated
+                | BreakpointStoreEvent::DataBreakpointsUpdated => {
                     workspace.serialize_workspace(window, cx);
                 }
                 BreakpointStoreEvent::SetDebugLine | BreakpointStoreEvent::ClearDebugLines => {}
@@ -1420,6 +1426,7 @@ impl Workspace {
             _items_serializer,
             session_id: Some(session_id),
             serialized_ssh_project: None,
+            serialized_dev_container_project: None,
         }
     }
 
@@ -1987,6 +1994,13 @@ impl Workspace {
         self.serialized_ssh_project = Some(serialized_ssh_project);
     }
 
+    pub fn set_serialized_dev_container_project(
+        &mut self,
+        serialized_dev_container_project: SerializedDevContainerProject,
+    ) {
+        self.serialized_dev_container_project = Some(serialized_dev_container_project);
+    }
+
     pub fn prompt_for_open_path(
         &mut self,
         path_prompt_options: PathPromptOptions,
@@ -5195,12 +5209,18 @@ impl Workspace {
         }
 
         if let Some(location) = self.serialize_workspace_location(cx) {
-            let breakpoints = self.project.update(cx, |project, cx| {
-                project
-                    .breakpoint_store()
-                    .read(cx)
-                    .all_source_breakpoints(cx)
-            });
+            let (breakpoints, function_breakpoints, data_breakpoints) =
+                self.project.update(cx, |project, cx| {
+                    let breakpoint_store = project.breakpoint_store().read(cx);
+                    (
+                        breakpoint_store.all_source_breakpoints(cx),
+                        breakpoint_store.function_breakpoints().to_vec(),
+                        breakpoint_store
+                            .persistent_data_breakpoints()
+                            .cloned()
+                            .collect(),
+                    )
+                });
 
             let center_group = build_serialized_pane_group(&self.center.root, window, cx);
             let docks = build_serialized_docks(self, window, cx);
@@ -5215,6 +5235,8 @@ impl Workspace {
                 centered_layout: self.centered_layout,
                 session_id: self.session_id.clone(),
                 breakpoints,
+                function_breakpoints,
+                data_breakpoints,
                 window_id: Some(window.window_handle().window_id().as_u64()),
             };
 
@@ -5228,6 +5250,10 @@ impl Workspace {
     fn serialize_workspace_location(&self, cx: &App) -> Option<SerializedWorkspaceLocation> {
         if let Some(ssh_project) = &self.serialized_ssh_project {
             Some(SerializedWorkspaceLocation::Ssh(ssh_project.clone()))
+        } else if let Some(dev_container_project) = &self.serialized_dev_container_project {
+            Some(SerializedWorkspaceLocation::DevContainer(
+                dev_container_project.clone(),
+            ))
         } else if let Some(local_paths) = self.local_paths(cx) {
             if !local_paths.is_empty() {
                 Some(SerializedWorkspaceLocation::from_local_paths(local_paths))
@@ -5386,6 +5412,11 @@ impl Workspace {
                     project
                         .breakpoint_store()
                         .update(cx, |breakpoint_store, cx| {
+                            breakpoint_store.with_serialized_function_and_data_breakpoints(
+                                serialized_workspace.function_breakpoints,
+                                serialized_workspace.data_breakpoints,
+                                cx,
+                            );
                             breakpoint_store
                                 .with_serialized_breakpoints(serialized_workspace.breakpoints, cx)
                         })
@@ -7217,7 +7248,7 @@ pub fn create_and_open_local_file(
 
 pub fn open_ssh_project_with_new_connection(
     window: WindowHandle<Workspace>,
-    connection_options: SshConnectionOptions,
+    connection_options: RemoteConnectionOptions,
     cancel_rx: oneshot::Receiver<()>,
     delegate: Arc<dyn SshClientDelegate>,
     app_state: Arc<AppState>,
@@ -7225,8 +7256,32 @@ pub fn open_ssh_project_with_new_connection(
     cx: &mut App,
 ) -> Task<Result<()>> {
     cx.spawn(async move |cx| {
-        let (serialized_ssh_project, workspace_id, serialized_workspace) =
-            serialize_ssh_project(connection_options.clone(), paths.clone(), &cx).await?;
+        let (serialized_location, workspace_id, serialized_workspace) = match &connection_options {
+            RemoteConnectionOptions::Ssh(ssh_connection_options) => {
+                let (serialized_ssh_project, workspace_id, serialized_workspace) =
+                    serialize_ssh_project(ssh_connection_options.clone(), paths.clone(), &cx)
+                        .await?;
+                (
+                    SerializedWorkspaceLocation::Ssh(serialized_ssh_project),
+                    workspace_id,
+                    serialized_workspace,
+                )
+            }
+            RemoteConnectionOptions::DevContainer(dev_container_connection_options) => {
+                let (serialized_dev_container_project, workspace_id, serialized_workspace) =
+                    serialize_dev_container_project(
+                        dev_container_connection_options.clone(),
+                        paths.clone(),
+                        &cx,
+                    )
+                    .await?;
+                (
+                    SerializedWorkspaceLocation::DevContainer(serialized_dev_container_project),
+                    workspace_id,
+                    serialized_workspace,
+                )
+            }
+        };
 
         let session = match cx
             .update(|cx| {
@@ -7259,7 +7314,7 @@ pub fn open_ssh_project_with_new_connection(
         open_ssh_project_inner(
             project,
             paths,
-            serialized_ssh_project,
+            serialized_location,
             workspace_id,
             serialized_workspace,
             app_state,
@@ -7285,7 +7340,7 @@ pub fn open_ssh_project_with_existing_connection(
         open_ssh_project_inner(
             project,
             paths,
-            serialized_ssh_project,
+            SerializedWorkspaceLocation::Ssh(serialized_ssh_project),
             workspace_id,
             serialized_workspace,
             app_state,
@@ -7299,7 +7354,7 @@ pub fn open_ssh_project_with_existing_connection(
 async fn open_ssh_project_inner(
     project: Entity<Project>,
     paths: Vec<PathBuf>,
-    serialized_ssh_project: SerializedSshProject,
+    serialized_location: SerializedWorkspaceLocation,
     workspace_id: WorkspaceId,
     serialized_workspace: Option<SerializedWorkspace>,
     app_state: Arc<AppState>,
@@ -7341,7 +7396,16 @@ async fn open_ssh_project_inner(
 
             let mut workspace =
                 Workspace::new(Some(workspace_id), project, app_state.clone(), window, cx);
-            workspace.set_serialized_ssh_project(serialized_ssh_project);
+            match serialized_location {
+                SerializedWorkspaceLocation::Ssh(serialized_ssh_project) => {
+                    workspace.set_serialized_ssh_project(serialized_ssh_project);
+                }
+                SerializedWorkspaceLocation::DevContainer(serialized_dev_container_project) => {
+                    workspace
+                        .set_serialized_dev_container_project(serialized_dev_container_project);
+                }
+                SerializedWorkspaceLocation::Local(..) => {}
+            }
             workspace.update_history(cx);
 
             if let Some(ref serialized) = serialized_workspace {
@@ -7413,6 +7477,51 @@ fn serialize_ssh_project(
     })
 }
 
+fn serialize_dev_container_project(
+    connection_options: DevContainerConnectionOptions,
+    paths: Vec<PathBuf>,
+    cx: &AsyncApp,
+) -> Task<
+    Result<(
+        SerializedDevContainerProject,
+        WorkspaceId,
+        Option<SerializedWorkspace>,
+    )>,
+> {
+    cx.background_spawn(async move {
+        let serialized_dev_container_project = persistence::DB
+            .get_or_create_dev_container_project(
+                connection_options
+                    .project_path
+                    .to_string_lossy()
+                    .to_string(),
+                connection_options.runtime,
+                paths
+                    .iter()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .collect::<Vec<_>>(),
+            )
+            .await?;
+
+        let serialized_workspace =
+            persistence::DB.workspace_for_dev_container_project(&serialized_dev_container_project);
+
+        let workspace_id = if let Some(workspace_id) =
+            serialized_workspace.as_ref().map(|workspace| workspace.id)
+        {
+            workspace_id
+        } else {
+            persistence::DB.next_id().await?
+        };
+
+        Ok((
+            serialized_dev_container_project,
+            workspace_id,
+            serialized_workspace,
+        ))
+    })
+}
+
 pub fn join_in_room_project(
     project_id: u64,
     follow_user_id: u64,
@@ -7972,36 +8081,67 @@ pub fn ssh_workspace_position_from_db(
             .context("fetching serialized ssh project")?;
         let serialized_workspace =
             persistence::DB.workspace_for_ssh_project(&serialized_ssh_project);
+        Ok(workspace_position(serialized_workspace))
+    })
+}
 
-        let (window_bounds, display) = if let Some(bounds) = window_bounds_env_override() {
-            (Some(WindowBounds::Windowed(bounds)), None)
-        } else {
-            let restorable_bounds = serialized_workspace
-                .as_ref()
-                .and_then(|workspace| Some((workspace.display?, workspace.window_bounds?)))
-                .or_else(|| {
-                    let (display, window_bounds) = DB.last_window().log_err()?;
-                    Some((display?, window_bounds?))
-                });
+pub fn dev_container_workspace_position_from_db(
+    connection_options: DevContainerConnectionOptions,
+    paths_to_open: &[PathBuf],
+    cx: &App,
+) -> Task<Result<WorkspacePosition>> {
+    let paths = paths_to_open
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect::<Vec<_>>();
 
-            if let Some((serialized_display, serialized_status)) = restorable_bounds {
-                (Some(serialized_status.0), Some(serialized_display))
-            } else {
-                (None, None)
-            }
-        };
+    cx.background_spawn(async move {
+        let serialized_dev_container_project = persistence::DB
+            .get_or_create_dev_container_project(
+                connection_options
+                    .project_path
+                    .to_string_lossy()
+                    .to_string(),
+                connection_options.runtime,
+                paths,
+            )
+            .await
+            .context("fetching serialized dev container project")?;
+        let serialized_workspace =
+            persistence::DB.workspace_for_dev_container_project(&serialized_dev_container_project);
+        Ok(workspace_position(serialized_workspace))
+    })
+}
 
-        let centered_layout = serialized_workspace
+fn workspace_position(serialized_workspace: Option<SerializedWorkspace>) -> WorkspacePosition {
+    let (window_bounds, display) = if let Some(bounds) = window_bounds_env_override() {
+        (Some(WindowBounds::Windowed(bounds)), None)
+    } else {
+        let restorable_bounds = serialized_workspace
             .as_ref()
-            .map(|w| w.centered_layout)
-            .unwrap_or(false);
+            .and_then(|workspace| Some((workspace.display?, workspace.window_bounds?)))
+            .or_else(|| {
+                let (display, window_bounds) = DB.last_window().log_err()?;
+                Some((display?, window_bounds?))
+            });
 
-        Ok(WorkspacePosition {
-            window_bounds,
-            display,
-            centered_layout,
-        })
-    })
+        if let Some((serialized_display, serialized_status)) = restorable_bounds {
+            (Some(serialized_status.0), Some(serialized_display))
+        } else {
+            (None, None)
+        }
+    };
+
+    let centered_layout = serialized_workspace
+        .as_ref()
+        .map(|w| w.centered_layout)
+        .unwrap_or(false);
+
+    WorkspacePosition {
+        window_bounds,
+        display,
+        centered_layout,
+    }
 }
 
 pub fn with_active_or_new_workspace(
@@ -300,7 +300,9 @@ pub fn main() {
         };
     log::info!("Using git binary path: {:?}", git_binary_path);
 
-    let fs = Arc::new(RealFs::new(git_binary_path, app.background_executor()));
+    let fs = Arc::new(
+        RealFs::new(git_binary_path, app.background_executor()).with_jj_opener(jj::open_repository),
+    );
     let user_settings_file_rx = watch_config_file(
         &app.background_executor(),
         fs.clone(),
@@ -585,12 +587,14 @@ pub fn main() {
         file_finder::init(cx);
         tab_switcher::init(cx);
         outline::init(cx);
+        hierarchy_view::init(cx);
         project_symbols::init(cx);
         project_panel::init(cx);
         outline_panel::init(cx);
         tasks_ui::init(cx);
         snippets_ui::init(cx);
         channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
+        semantic_index::init(app_state.client.http_client(), cx);
         search::init(cx);
         vim::init(cx);
         terminal_view::init(cx);
@@ -958,6 +962,27 @@ async fn restore_or_create_workspace(app_state: Arc<AppState>, cx: &mut AsyncApp
                     });
                     tasks.push(task);
                 }
+                SerializedWorkspaceLocation::DevContainer(dev_container) => {
+                    let app_state = app_state.clone();
+                    let task = cx.spawn(async move |cx| {
+                        let connection_options = cx.update(|cx| {
+                            SshSettings::get_global(cx).dev_container_options_for(
+                                PathBuf::from(dev_container.project_path),
+                                dev_container.runtime,
+                            )
+                        })?;
+
+                        recent_projects::open_remote_project(
+                            connection_options.into(),
+                            dev_container.paths.into_iter().map(PathBuf::from).collect(),
+                            app_state,
+                            workspace::OpenOptions::default(),
+                            cx,
+                        )
+                        .await
+                    });
+                    tasks.push(task);
+                }
             }
         }
 
@@ -43,7 +43,7 @@ use project::{DirectoryLister, ProjectItem};
 use project_panel::ProjectPanel;
 use prompt_store::PromptBuilder;
 use quick_action_bar::QuickActionBar;
-use recent_projects::open_ssh_project;
+use recent_projects::open_remote_project;
 use release_channel::{AppCommitSha, ReleaseChannel};
 use rope::Rope;
 use search::project_search::ProjectSearchBar;
@@ -534,6 +534,9 @@ fn initialize_panels(
             workspace.add_panel(chat_panel, window, cx);
             workspace.add_panel(notification_panel, window, cx);
             workspace.add_panel(debug_panel, window, cx);
+
+            let jj_panel = cx.new(|cx| jj_ui::jj_panel::JjPanel::new(window, cx));
+            workspace.add_panel(jj_panel, window, cx);
         })?;
 
         let is_assistant2_enabled = !cfg!(test);
@@ -1511,7 +1514,7 @@ pub fn open_new_ssh_project_from_project(
     };
     let connection_options = ssh_client.read(cx).connection_options();
     cx.spawn_in(window, async move |_, cx| {
-        open_ssh_project(
+        open_remote_project(
             connection_options,
             paths,
             app_state,
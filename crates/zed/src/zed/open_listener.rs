@@ -15,7 +15,7 @@ use futures::{FutureExt, SinkExt, StreamExt};
 use git_ui::diff_view::DiffView;
 use gpui::{App, AsyncApp, Global, WindowHandle};
 use language::Point;
-use recent_projects::{SshSettings, open_ssh_project};
+use recent_projects::{SshSettings, open_remote_project, open_ssh_project};
 use remote::SshConnectionOptions;
 use settings::Settings;
 use std::path::{Path, PathBuf};
@@ -434,6 +434,29 @@ async fn open_workspaces(
                         errored = false;
                     }
                 }
+                SerializedWorkspaceLocation::DevContainer(dev_container) => {
+                    let app_state = app_state.clone();
+                    let connection_options = cx.update(|cx| {
+                        SshSettings::get_global(cx).dev_container_options_for(
+                            PathBuf::from(dev_container.project_path),
+                            dev_container.runtime,
+                        )
+                    });
+                    if let Ok(connection_options) = connection_options {
+                        cx.spawn(async move |mut cx| {
+                            open_remote_project(
+                                connection_options.into(),
+                                dev_container.paths.into_iter().map(PathBuf::from).collect(),
+                                app_state,
+                                OpenOptions::default(),
+                                &mut cx,
+                            )
+                            .await
+                            .log_err();
+                        })
+                        .detach();
+                    }
+                }
             }
         }
 
@@ -422,6 +422,20 @@ pub struct Rerun {
     pub task_id: Option<String>,
 }
 
+pub mod hierarchy_view {
+    use gpui::actions;
+
+    actions!(
+        hierarchy_view,
+        [
+            /// Shows the callers and callees of the symbol at cursor.
+            ShowCallHierarchy,
+            /// Shows the supertypes and subtypes of the symbol at cursor.
+            ShowTypeHierarchy
+        ]
+    );
+}
+
 pub mod outline {
     use std::sync::OnceLock;
 